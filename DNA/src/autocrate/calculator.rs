@@ -1,7 +1,7 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: calculator.rs | DNA/src/autocrate/calculator.rs
 //! PURPOSE: Shipping crate geometry calculator for dimensions and components
-//! MODIFIED: 2025-12-09
+//! MODIFIED: 2026-10-18
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
 
@@ -13,10 +13,12 @@
 #![allow(clippy::let_and_return)]
 
 use super::geometry::*;
-use super::types::{CrateGeometry, CrateSpec};
+use super::standards::{effective_spec, lift_cleat_size, notch_size, StandardProfile};
+use super::types::{BaseStyle, CrateGeometry, CrateSpec};
 
 /// Calculate complete crate geometry from specification
 pub fn calculate_crate(spec: &CrateSpec) -> CrateGeometry {
+    let spec = &*effective_spec(spec);
     let product = &spec.product;
     let clearances = &spec.clearances;
 
@@ -57,6 +59,13 @@ pub fn calculate_crate(spec: &CrateSpec) -> CrateGeometry {
     cleats.extend(panels.right.cleats.iter().cloned());
     cleats.extend(panels.top.cleats.iter().cloned());
 
+    // Step 7: Hoisting cleats
+    let lift_cleats = if spec.requirements.lift_cleats {
+        calculate_lift_cleats(spec, &panels, overall_length)
+    } else {
+        Vec::new()
+    };
+
     CrateGeometry {
         overall_length,
         overall_width,
@@ -66,6 +75,7 @@ pub fn calculate_crate(spec: &CrateSpec) -> CrateGeometry {
         floorboards,
         panels,
         cleats,
+        lift_cleats,
     }
 }
 
@@ -79,9 +89,17 @@ fn calculate_skids(spec: &CrateSpec, length: f32, width: f32) -> Vec<SkidGeometr
     let skid_length = length;
     let total_skid_width = count as f32 * skid_dims.1;
     let gap = (width - total_skid_width) / (count + 1) as f32;
+    let sub_base = spec.requirements.base == BaseStyle::SubBase;
 
     for i in 0..count {
-        let x_center = gap * (i + 1) as f32 + skid_dims.1 * (i as f32 + 0.5);
+        let x_center = if sub_base && count > 1 {
+            // Sub-base runners: outer runners flush with the crate sides
+            skid_dims.1 / 2.0 + (width - skid_dims.1) * i as f32 / (count - 1) as f32
+        } else if sub_base {
+            width / 2.0
+        } else {
+            gap * (i + 1) as f32 + skid_dims.1 * (i as f32 + 0.5)
+        };
 
         let min = Point3::new(
             x_center - skid_dims.1 / 2.0 - width / 2.0,
@@ -94,10 +112,28 @@ fn calculate_skids(spec: &CrateSpec, length: f32, width: f32) -> Vec<SkidGeometr
             skid_dims.0,
         );
 
+        // Forklift notches at the quarter points of each runner
+        let notches = if sub_base {
+            let (notch_length, depth) = notch_size(spec.product.weight, spec.skid_size);
+            let half = notch_length / 2.0;
+            [-skid_length / 4.0, skid_length / 4.0]
+                .iter()
+                .map(|&y| {
+                    BoundingBox::new(
+                        Point3::new(min.x, y - half, 0.0),
+                        Point3::new(max.x, y + half, depth),
+                    )
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
         skids.push(SkidGeometry {
             bounds: BoundingBox::new(min, max),
             lumber_size: spec.skid_size,
             index: i,
+            notches,
         });
     }
 
@@ -321,6 +357,12 @@ fn calculate_panel_cleats(
 
     let mut cleats: Vec<CleatGeometry> = Vec::new();
 
+    // Nailed boxes: the sheathing carries the sides and top, only ends are cleated
+    let profile = StandardProfile::for_requirements(&spec.requirements);
+    if profile.end_cleats_only && !matches!(panel_type, PanelType::Front | PanelType::Back) {
+        return cleats;
+    }
+
     // Helper: compute evenly spaced centers between two endpoints (inclusive endpoints handled by caller).
     fn intermediate_centers(start_center: f32, end_center: f32, max_spacing: f32) -> Vec<f32> {
         if max_spacing <= 0.0 {
@@ -340,7 +382,7 @@ fn calculate_panel_cleats(
             .collect()
     }
 
-    let max_spacing = super::constants::cleat::MAX_VERTICAL_SPACING;
+    let max_spacing = profile.max_cleat_spacing;
 
     // Cleat placement differs by panel orientation.
     match panel_type {
@@ -579,6 +621,41 @@ fn calculate_panel_cleats(
     cleats
 }
 
+/// Hoisting cleats: one near each end of both side panels, outside face,
+/// full panel height; the sling bears on the bottom outside edge
+fn calculate_lift_cleats(
+    spec: &CrateSpec,
+    panels: &PanelSet,
+    length: f32,
+) -> Vec<LiftCleatGeometry> {
+    let lumber_size = lift_cleat_size(spec.product.weight);
+    let (thickness, width) = lumber_size.actual();
+    let inset = super::constants::lift::END_INSET.min(length / 4.0);
+    let mut cleats = Vec::with_capacity(4);
+
+    for panel in [&panels.left, &panels.right] {
+        let b = &panel.bounds;
+        let (x0, x1, outer) = if panel.panel_type == PanelType::Left {
+            (b.min.x - thickness, b.min.x, b.min.x - thickness)
+        } else {
+            (b.max.x, b.max.x + thickness, b.max.x + thickness)
+        };
+        for y in [-length / 2.0 + inset, length / 2.0 - inset] {
+            cleats.push(LiftCleatGeometry {
+                bounds: BoundingBox::new(
+                    Point3::new(x0, y - width / 2.0, b.min.z),
+                    Point3::new(x1, y + width / 2.0, b.max.z),
+                ),
+                lumber_size,
+                panel: panel.panel_type,
+                lift_point: Point3::new(outer, y, b.min.z),
+            });
+        }
+    }
+
+    cleats
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::types::CrateSpec;
@@ -602,4 +679,40 @@ mod tests {
 
         assert_eq!(geom.skids.len(), 4);
     }
+
+    #[test]
+    fn test_requirements_select_construction() {
+        use super::super::types::{CrateStandard, CrateStyle};
+
+        let mut spec = CrateSpec::default();
+        spec.requirements.base = BaseStyle::SubBase;
+        spec.requirements.lift_cleats = true;
+        let geom = calculate_crate(&spec);
+
+        // Runners flush with the sides, two notches each
+        let first = &geom.skids[0].bounds;
+        assert!((first.min.x + geom.overall_width / 2.0).abs() < 1e-4);
+        assert!(geom.skids.iter().all(|s| s.notches.len() == 2));
+        assert_eq!(geom.lift_cleats.len(), 4);
+        let left = &geom.panels.left.bounds;
+        assert!(geom.lift_cleats[0].bounds.max.x <= left.min.x + 1e-4);
+
+        // Nailed box: no cleats on the sides or top
+        spec.requirements.standard = CrateStandard::AstmD6251;
+        spec.requirements.style = CrateStyle::NailedBox;
+        let geom = calculate_crate(&spec);
+        assert!(geom
+            .cleats
+            .iter()
+            .all(|c| matches!(c.panel, PanelType::Front | PanelType::Back)));
+        assert!(!geom.cleats.is_empty());
+
+        // Tighter MIL-C-104 open-crate spacing adds intermediate cleats
+        let mut spec = CrateSpec::default();
+        spec.product.length = 60.0;
+        let d6039 = calculate_crate(&spec).panels.left.cleats.len();
+        spec.requirements.standard = CrateStandard::MilC104;
+        spec.requirements.style = CrateStyle::Open;
+        assert!(calculate_crate(&spec).panels.left.cleats.len() > d6039);
+    }
 }
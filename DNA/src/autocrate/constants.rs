@@ -1,7 +1,7 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: constants.rs | DNA/src/autocrate/constants.rs
 //! PURPOSE: Defines LumberSize enum with ASTM standard lumber dimensions (nominal and actual)
-//! MODIFIED: 2025-12-09
+//! MODIFIED: 2026-10-18
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
 
@@ -48,6 +48,31 @@ impl LumberSize {
         }
     }
 
+    /// Every size, smallest first
+    pub const ALL: [LumberSize; 13] = [
+        LumberSize::L1x4,
+        LumberSize::L2x3,
+        LumberSize::L2x4,
+        LumberSize::L2x6,
+        LumberSize::L2x8,
+        LumberSize::L2x10,
+        LumberSize::L2x12,
+        LumberSize::L3x3,
+        LumberSize::L3x4,
+        LumberSize::L4x4,
+        LumberSize::L4x6,
+        LumberSize::L6x6,
+        LumberSize::L8x8,
+    ];
+
+    /// Size from its nominal name ("2x4", case-insensitive)
+    pub fn from_name(name: &str) -> Option<LumberSize> {
+        let name = name.trim();
+        Self::ALL
+            .into_iter()
+            .find(|s| s.name().eq_ignore_ascii_case(name))
+    }
+
     /// Get nominal name
     pub fn name(&self) -> &'static str {
         match self {
@@ -100,6 +125,35 @@ pub mod skid {
     }
 }
 
+/// Sub-base (skid-less) runner standards
+pub mod sub_base {
+    /// Heaviest product on notched runners
+    pub const MAX_WEIGHT: f32 = 5000.0;
+    /// Forklift notch length along the runner
+    pub const NOTCH_LENGTH: f32 = 9.0;
+    /// Notch length for heavy runners (wider forks)
+    pub const HEAVY_NOTCH_LENGTH: f32 = 12.0;
+    /// Forklift notch depth (capped at half the runner height)
+    pub const NOTCH_DEPTH: f32 = 1.5;
+    /// Widest center spacing between runners across the product
+    pub const MAX_RUNNER_SPACING: f32 = 30.0;
+    /// Above this weight runners step up to 4x6 with heavy notches
+    pub const HEAVY_RUNNER_WEIGHT: f32 = 2500.0;
+
+    /// Runner count: one per spacing across the width plus one, at least
+    /// two and never fewer than the skid rule for the weight
+    pub fn runner_count(width: f32, weight: f32) -> u8 {
+        let spans = (width / MAX_RUNNER_SPACING).ceil().max(1.0) as u8;
+        (spans + 1).max(super::skid::recommended_count(weight))
+    }
+}
+
+/// Hoisting (lift) cleat standards
+pub mod lift {
+    /// Distance from the crate end to the lift cleat center
+    pub const END_INSET: f32 = 12.0;
+}
+
 /// Cleat standards
 pub mod cleat {
     pub const MAX_VERTICAL_SPACING: f32 = 24.0;
@@ -199,6 +253,8 @@ mod tests {
         let (h, w) = LumberSize::L2x4.actual();
         assert!((h - 1.5).abs() < 0.001);
         assert!((w - 3.5).abs() < 0.001);
+        assert_eq!(LumberSize::from_name("2X4"), Some(LumberSize::L2x4));
+        assert_eq!(LumberSize::from_name("5x5"), None);
     }
 
     #[test]
@@ -0,0 +1,916 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: cutting.rs | DNA/src/autocrate/cutting.rs
+//! PURPOSE: Lumber cutting-stock and plywood guillotine nesting (cutting plan, waste, purchase list)
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+//!
+//! Turns the cut pieces of a `CrateDesign` into a shop cutting plan:
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ LUMBER (1D cutting stock)                                                   │
+//! │   Per nominal size, repeatedly pick the stock length whose first-fit-       │
+//! │   decreasing fill uses it best, cut those pieces, repeat. Kerf is charged   │
+//! │   between pieces.                                                           │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ PLYWOOD (2D guillotine nesting)                                             │
+//! │   Per thickness, pieces by decreasing area into free rectangles (best area  │
+//! │   fit, shorter-leftover-axis split), so every cut runs edge to edge.        │
+//! │   Pieces larger than a sheet are split into sheet-sized sections.           │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! Units: inches.
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use super::constants::{plywood, LumberSize};
+use super::design::{CrateDesign, PartCategory, PartMaterial};
+use super::reports::{bbox_dims, csv_escape, face_dims_for_sheet_part};
+use crate::export::pdf::{PdfDocument, TextAlign};
+use std::fmt::Write as FmtWrite;
+
+/// Lumber piece (label, length)
+pub type LumberPiece = (String, f32);
+
+/// Plywood piece (label, longer side, shorter side)
+pub type SheetPiece = (String, f32, f32);
+
+/// Stock and saw settings for the cutting plan
+#[derive(Clone, Debug)]
+pub struct CuttingOptions {
+    /// Stock lengths available for every lumber size (inches)
+    pub stock_lengths: Vec<f32>,
+    /// Per-size replacements for `stock_lengths`
+    pub stock_overrides: Vec<(LumberSize, Vec<f32>)>,
+    /// Saw kerf (inches)
+    pub kerf: f32,
+    /// Plywood sheet (width, length) in inches
+    pub sheet_size: (f32, f32),
+    /// Allow plywood pieces to turn 90° (ignores face grain)
+    pub allow_rotation: bool,
+}
+
+impl Default for CuttingOptions {
+    fn default() -> Self {
+        Self {
+            // 8, 10, 12 and 16 ft
+            stock_lengths: vec![96.0, 120.0, 144.0, 192.0],
+            stock_overrides: Vec::new(),
+            kerf: 0.125,
+            sheet_size: (plywood::SHEET_WIDTH, plywood::SHEET_LENGTH),
+            allow_rotation: true,
+        }
+    }
+}
+
+impl CuttingOptions {
+    /// Stock lengths for a lumber size
+    pub fn stock_for(&self, size: LumberSize) -> &[f32] {
+        self.stock_overrides
+            .iter()
+            .find(|(s, _)| *s == size)
+            .map(|(_, lengths)| lengths.as_slice())
+            .unwrap_or(&self.stock_lengths)
+    }
+}
+
+/// One piece cut from a stock board
+#[derive(Clone, Debug, PartialEq)]
+pub struct LumberCut {
+    pub label: String,
+    pub length: f32,
+    /// Distance from the board end to the start of the piece
+    pub offset: f32,
+}
+
+/// A purchased stock board and the pieces cut from it
+#[derive(Clone, Debug, PartialEq)]
+pub struct StockBoard {
+    pub size: LumberSize,
+    pub stock_length: f32,
+    pub cuts: Vec<LumberCut>,
+}
+
+impl StockBoard {
+    /// Length consumed by pieces and the kerfs between them
+    pub fn used_length(&self, kerf: f32) -> f32 {
+        let pieces: f32 = self.cuts.iter().map(|c| c.length).sum();
+        pieces + kerf * self.cuts.len().saturating_sub(1) as f32
+    }
+
+    pub fn offcut(&self, kerf: f32) -> f32 {
+        (self.stock_length - self.used_length(kerf)).max(0.0)
+    }
+}
+
+/// One piece placed on a plywood sheet (x across the width, y along the length)
+#[derive(Clone, Debug, PartialEq)]
+pub struct SheetCut {
+    pub label: String,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    /// Turned 90° from the part's (longer side first) orientation
+    pub rotated: bool,
+}
+
+/// A purchased plywood sheet and the pieces nested on it
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlywoodSheet {
+    pub thickness: f32,
+    pub width: f32,
+    pub length: f32,
+    pub cuts: Vec<SheetCut>,
+}
+
+impl PlywoodSheet {
+    pub fn used_area(&self) -> f32 {
+        self.cuts.iter().map(|c| c.width * c.height).sum()
+    }
+}
+
+/// Purchase list line
+#[derive(Clone, Debug, PartialEq)]
+pub struct PurchaseItem {
+    pub material: String,
+    pub size: String,
+    pub quantity: u32,
+}
+
+/// Complete cutting plan for a design
+#[derive(Clone, Debug)]
+pub struct CuttingPlan {
+    pub boards: Vec<StockBoard>,
+    pub sheets: Vec<PlywoodSheet>,
+    /// Pieces longer than any stock board (need a splice)
+    pub unplaced: Vec<String>,
+    pub kerf: f32,
+}
+
+impl CuttingPlan {
+    /// Lumber offcut as a percentage of purchased length
+    pub fn lumber_waste_pct(&self) -> f32 {
+        let stock: f32 = self.boards.iter().map(|b| b.stock_length).sum();
+        let used: f32 = self
+            .boards
+            .iter()
+            .flat_map(|b| &b.cuts)
+            .map(|c| c.length)
+            .sum();
+        waste_pct(used, stock)
+    }
+
+    /// Plywood offcut as a percentage of purchased area
+    pub fn plywood_waste_pct(&self) -> f32 {
+        let stock: f32 = self.sheets.iter().map(|s| s.width * s.length).sum();
+        let used: f32 = self.sheets.iter().map(PlywoodSheet::used_area).sum();
+        waste_pct(used, stock)
+    }
+
+    /// Stock to buy, grouped by size
+    pub fn purchase_list(&self) -> Vec<PurchaseItem> {
+        use std::collections::BTreeMap;
+
+        let mut lumber: BTreeMap<(String, u32), u32> = BTreeMap::new();
+        for b in &self.boards {
+            let key = (
+                b.size.name().to_string(),
+                (b.stock_length * 100.0).round() as u32,
+            );
+            *lumber.entry(key).or_insert(0) += 1;
+        }
+        let mut sheets: BTreeMap<(u32, u32, u32), u32> = BTreeMap::new();
+        for s in &self.sheets {
+            let key = (
+                (s.thickness * 1000.0).round() as u32,
+                (s.width * 100.0).round() as u32,
+                (s.length * 100.0).round() as u32,
+            );
+            *sheets.entry(key).or_insert(0) += 1;
+        }
+
+        let mut items = Vec::new();
+        for ((name, length), quantity) in lumber {
+            let inches = length as f32 / 100.0;
+            let size = if (inches % 12.0).abs() < 1e-3 {
+                format!("{} x {}'", name, (inches / 12.0).round() as u32)
+            } else {
+                format!("{} x {:.2}\"", name, inches)
+            };
+            items.push(PurchaseItem {
+                material: "Lumber".to_string(),
+                size,
+                quantity,
+            });
+        }
+        for ((thickness, width, length), quantity) in sheets {
+            items.push(PurchaseItem {
+                material: "Plywood".to_string(),
+                size: format!(
+                    "{:.0}x{:.0} x {:.3}\"",
+                    width as f32 / 100.0,
+                    length as f32 / 100.0,
+                    thickness as f32 / 1000.0
+                ),
+                quantity,
+            });
+        }
+        items
+    }
+}
+
+fn waste_pct(used: f32, stock: f32) -> f32 {
+    if stock <= 0.0 {
+        0.0
+    } else {
+        100.0 * (1.0 - used / stock)
+    }
+}
+
+/// Cutting plan for every lumber and plywood part of `design`
+pub fn optimize_cutting(design: &CrateDesign, options: &CuttingOptions) -> CuttingPlan {
+    let mut lumber: Vec<(LumberSize, Vec<LumberPiece>)> = Vec::new();
+    let mut plywood: Vec<(f32, Vec<SheetPiece>)> = Vec::new();
+
+    for part in &design.parts {
+        match (&part.category, &part.material) {
+            (PartCategory::Lumber, PartMaterial::Lumber { nominal }) => {
+                let (dx, dy, dz) = bbox_dims(&part.bounds);
+                let length = dx.max(dy).max(dz);
+                match lumber.iter_mut().find(|(s, _)| s == nominal) {
+                    Some((_, pieces)) => pieces.push((part.name.clone(), length)),
+                    None => lumber.push((*nominal, vec![(part.name.clone(), length)])),
+                }
+            }
+            (PartCategory::Plywood, PartMaterial::Plywood { thickness_in }) => {
+                let (w, h) = face_dims_for_sheet_part(&part.bounds);
+                let piece = (part.name.clone(), w, h);
+                match plywood
+                    .iter_mut()
+                    .find(|(t, _)| (t - thickness_in).abs() < 1e-4)
+                {
+                    Some((_, pieces)) => pieces.push(piece),
+                    None => plywood.push((*thickness_in, vec![piece])),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut boards = Vec::new();
+    let mut unplaced = Vec::new();
+    for (size, pieces) in &lumber {
+        let (mut packed, mut left) =
+            cut_lumber(*size, pieces, options.stock_for(*size), options.kerf);
+        boards.append(&mut packed);
+        unplaced.append(&mut left);
+    }
+
+    let mut sheets = Vec::new();
+    for (thickness, pieces) in &plywood {
+        sheets.extend(nest_plywood(*thickness, pieces, options));
+    }
+
+    CuttingPlan {
+        boards,
+        sheets,
+        unplaced,
+        kerf: options.kerf,
+    }
+}
+
+/// Cut `pieces` of one lumber size from `stock` lengths.
+/// Returns the boards and the labels of pieces longer than every stock length.
+pub fn cut_lumber(
+    size: LumberSize,
+    pieces: &[LumberPiece],
+    stock: &[f32],
+    kerf: f32,
+) -> (Vec<StockBoard>, Vec<String>) {
+    let longest = stock.iter().copied().fold(0.0, f32::max);
+    let (mut remaining, oversize): (Vec<LumberPiece>, Vec<LumberPiece>) = pieces
+        .iter()
+        .cloned()
+        .partition(|(_, length)| *length <= longest + 1e-4);
+    remaining.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    let mut boards = Vec::new();
+    while !remaining.is_empty() {
+        // Stock length with the best first-fit-decreasing utilisation
+        // (ties go to the longer board, which leaves the more useful offcut)
+        let mut best: Option<(f32, f32, Vec<usize>)> = None;
+        for &length in stock {
+            let picks = first_fit(&remaining, length, kerf);
+            if picks.is_empty() {
+                continue;
+            }
+            let used: f32 = picks.iter().map(|&i| remaining[i].1).sum::<f32>()
+                + kerf * (picks.len() - 1) as f32;
+            let utilisation = used / length;
+            let better = match &best {
+                None => true,
+                Some((u, l, _)) => {
+                    utilisation > *u + 1e-6 || ((utilisation - *u).abs() <= 1e-6 && length > *l)
+                }
+            };
+            if better {
+                best = Some((utilisation, length, picks));
+            }
+        }
+        let Some((_, stock_length, picks)) = best else {
+            break;
+        };
+
+        let mut cuts = Vec::with_capacity(picks.len());
+        let mut offset = 0.0;
+        for &i in &picks {
+            let (label, length) = &remaining[i];
+            cuts.push(LumberCut {
+                label: label.clone(),
+                length: *length,
+                offset,
+            });
+            offset += length + kerf;
+        }
+        for &i in picks.iter().rev() {
+            remaining.remove(i);
+        }
+        boards.push(StockBoard {
+            size,
+            stock_length,
+            cuts,
+        });
+    }
+
+    (
+        boards,
+        oversize.into_iter().map(|(label, _)| label).collect(),
+    )
+}
+
+/// Indices of the pieces (sorted longest first) that first-fit into `length`
+fn first_fit(pieces: &[LumberPiece], length: f32, kerf: f32) -> Vec<usize> {
+    let mut used = 0.0;
+    let mut picks = Vec::new();
+    for (i, (_, piece)) in pieces.iter().enumerate() {
+        let need = if picks.is_empty() {
+            *piece
+        } else {
+            piece + kerf
+        };
+        if used + need <= length + 1e-4 {
+            used += need;
+            picks.push(i);
+        }
+    }
+    picks
+}
+
+#[derive(Clone, Copy, Debug)]
+struct FreeRect {
+    x: f32,
+    y: f32,
+    w: f32,
+    h: f32,
+}
+
+/// Nest plywood `pieces` of one thickness onto sheets with guillotine cuts
+pub fn nest_plywood(
+    thickness: f32,
+    pieces: &[SheetPiece],
+    options: &CuttingOptions,
+) -> Vec<PlywoodSheet> {
+    let (sheet_w, sheet_l) = options.sheet_size;
+    let kerf = options.kerf;
+
+    // Split pieces that cannot fit on a sheet into equal sections
+    let mut queue: Vec<SheetPiece> = Vec::new();
+    for (label, a, b) in pieces {
+        let fits = |w: f32, h: f32| w <= sheet_w + 1e-4 && h <= sheet_l + 1e-4;
+        if fits(*a, *b) || (options.allow_rotation && fits(*b, *a)) {
+            queue.push((label.clone(), *a, *b));
+            continue;
+        }
+        let tiles = |w: f32, h: f32| ((w / sheet_w).ceil().max(1.0), (h / sheet_l).ceil().max(1.0));
+        let (nx, ny, w, h) = {
+            let (nx, ny) = tiles(*a, *b);
+            let (rx, ry) = tiles(*b, *a);
+            if options.allow_rotation && rx * ry < nx * ny {
+                (rx, ry, *b, *a)
+            } else {
+                (nx, ny, *a, *b)
+            }
+        };
+        let count = (nx * ny) as usize;
+        for k in 0..count {
+            queue.push((format!("{} ({}/{})", label, k + 1, count), w / nx, h / ny));
+        }
+    }
+    queue.sort_by(|a, b| (b.1 * b.2).total_cmp(&(a.1 * a.2)));
+
+    let mut sheets: Vec<(PlywoodSheet, Vec<FreeRect>)> = Vec::new();
+    for (label, a, b) in queue {
+        // Orientations: as given (a across the width) and turned
+        let mut orientations = vec![(a, b, false)];
+        if options.allow_rotation && (a - b).abs() > 1e-4 {
+            orientations.push((b, a, true));
+        }
+
+        // Best area fit across every free rectangle of every open sheet
+        let mut best: Option<(usize, usize, f32, f32, f32, bool)> = None;
+        for (si, (_, free)) in sheets.iter().enumerate() {
+            for (ri, r) in free.iter().enumerate() {
+                for &(w, h, rotated) in &orientations {
+                    if w > r.w + 1e-4 || h > r.h + 1e-4 {
+                        continue;
+                    }
+                    let score = r.w * r.h - w * h;
+                    if best.is_none_or(|b| score < b.2) {
+                        best = Some((si, ri, score, w, h, rotated));
+                    }
+                }
+            }
+        }
+
+        let (si, ri, w, h, rotated) = match best {
+            Some((si, ri, _, w, h, rotated)) => (si, ri, w, h, rotated),
+            None => {
+                let Some(&(w, h, rotated)) = orientations
+                    .iter()
+                    .find(|(w, h, _)| *w <= sheet_w + 1e-4 && *h <= sheet_l + 1e-4)
+                else {
+                    continue;
+                };
+                sheets.push((
+                    PlywoodSheet {
+                        thickness,
+                        width: sheet_w,
+                        length: sheet_l,
+                        cuts: Vec::new(),
+                    },
+                    vec![FreeRect {
+                        x: 0.0,
+                        y: 0.0,
+                        w: sheet_w,
+                        h: sheet_l,
+                    }],
+                ));
+                (sheets.len() - 1, 0, w, h, rotated)
+            }
+        };
+
+        let (sheet, free) = &mut sheets[si];
+        let r = free.swap_remove(ri);
+        sheet.cuts.push(SheetCut {
+            label,
+            x: r.x,
+            y: r.y,
+            width: w,
+            height: h,
+            rotated,
+        });
+
+        // Guillotine split along the shorter leftover axis
+        let used_w = (w + kerf).min(r.w);
+        let used_h = (h + kerf).min(r.h);
+        let (right, top) = if r.w - used_w < r.h - used_h {
+            (
+                FreeRect {
+                    x: r.x + used_w,
+                    y: r.y,
+                    w: r.w - used_w,
+                    h: used_h,
+                },
+                FreeRect {
+                    x: r.x,
+                    y: r.y + used_h,
+                    w: r.w,
+                    h: r.h - used_h,
+                },
+            )
+        } else {
+            (
+                FreeRect {
+                    x: r.x + used_w,
+                    y: r.y,
+                    w: r.w - used_w,
+                    h: r.h,
+                },
+                FreeRect {
+                    x: r.x,
+                    y: r.y + used_h,
+                    w: used_w,
+                    h: r.h - used_h,
+                },
+            )
+        };
+        free.extend(
+            [right, top]
+                .into_iter()
+                .filter(|f| f.w > 1e-3 && f.h > 1e-3),
+        );
+    }
+
+    sheets.into_iter().map(|(sheet, _)| sheet).collect()
+}
+
+/// Purchase list as CSV
+pub fn purchase_list_to_csv(items: &[PurchaseItem]) -> String {
+    let mut out = String::new();
+    out.push_str("material,size,quantity\n");
+    for item in items {
+        out.push_str(&format!(
+            "{},{},{}\n",
+            csv_escape(&item.material),
+            csv_escape(&item.size),
+            item.quantity
+        ));
+    }
+    out
+}
+
+/// Pixels per inch in the SVG diagram
+const SVG_SCALE: f32 = 4.0;
+
+/// Cutting diagram as SVG: lumber boards as bars, then sheets side by side
+pub fn cutting_plan_to_svg(plan: &CuttingPlan) -> String {
+    let margin = 20.0;
+    let bar = 14.0;
+    let row = 28.0;
+    let longest = plan
+        .boards
+        .iter()
+        .map(|b| b.stock_length)
+        .fold(0.0, f32::max);
+    let sheet_px = plan
+        .sheets
+        .first()
+        .map(|s| (s.width * SVG_SCALE, s.length * SVG_SCALE))
+        .unwrap_or((0.0, 0.0));
+
+    let boards_h = plan.boards.len() as f32 * row;
+    let width =
+        (longest * SVG_SCALE).max(plan.sheets.len() as f32 * (sheet_px.0 + margin)) + 2.0 * margin;
+    let height = 3.0 * margin + boards_h + sheet_px.1 + 2.0 * margin;
+
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w:.0}" height="{h:.0}" viewBox="0 0 {w:.0} {h:.0}" font-family="sans-serif" font-size="9">"#,
+        w = width,
+        h = height
+    )
+    .ok();
+    writeln!(
+        svg,
+        r#"<text x="{}" y="{}" font-size="12">Lumber: {} boards, {:.1}% waste</text>"#,
+        margin,
+        margin,
+        plan.boards.len(),
+        plan.lumber_waste_pct()
+    )
+    .ok();
+
+    let mut y = margin * 1.5;
+    for board in &plan.boards {
+        writeln!(
+            svg,
+            r##"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" fill="#eee" stroke="#888"/>"##,
+            margin,
+            y,
+            board.stock_length * SVG_SCALE,
+            bar
+        )
+        .ok();
+        for cut in &board.cuts {
+            writeln!(
+                svg,
+                r##"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" fill="#d9b38c" stroke="#5a3e1b"/><text x="{:.1}" y="{:.1}">{}</text>"##,
+                margin + cut.offset * SVG_SCALE,
+                y,
+                cut.length * SVG_SCALE,
+                bar,
+                margin + cut.offset * SVG_SCALE + 2.0,
+                y + bar - 4.0,
+                xml_escape(&format!("{} {:.2}\"", cut.label, cut.length))
+            )
+            .ok();
+        }
+        writeln!(
+            svg,
+            r#"<text x="{:.1}" y="{:.1}">{} x {:.0}" (offcut {:.2}")</text>"#,
+            margin,
+            y + bar + 9.0,
+            board.size.name(),
+            board.stock_length,
+            board.offcut(plan.kerf)
+        )
+        .ok();
+        y += row;
+    }
+
+    y += margin;
+    writeln!(
+        svg,
+        r#"<text x="{}" y="{:.1}" font-size="12">Plywood: {} sheets, {:.1}% waste</text>"#,
+        margin,
+        y,
+        plan.sheets.len(),
+        plan.plywood_waste_pct()
+    )
+    .ok();
+    y += margin / 2.0;
+    for (i, sheet) in plan.sheets.iter().enumerate() {
+        let x0 = margin + i as f32 * (sheet_px.0 + margin);
+        writeln!(
+            svg,
+            r##"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" fill="#eee" stroke="#888"/>"##,
+            x0,
+            y,
+            sheet.width * SVG_SCALE,
+            sheet.length * SVG_SCALE
+        )
+        .ok();
+        for cut in &sheet.cuts {
+            let (cx, cy) = (x0 + cut.x * SVG_SCALE, y + cut.y * SVG_SCALE);
+            writeln!(
+                svg,
+                r##"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" fill="#e8d5a9" stroke="#5a3e1b"/><text x="{:.1}" y="{:.1}">{}</text>"##,
+                cx,
+                cy,
+                cut.width * SVG_SCALE,
+                cut.height * SVG_SCALE,
+                cx + 3.0,
+                cy + 11.0,
+                xml_escape(&format!("{} {:.2}x{:.2}", cut.label, cut.width, cut.height))
+            )
+            .ok();
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Cutting diagram as PDF (US Letter): lumber page(s), then two sheets per page
+pub fn cutting_plan_to_pdf(plan: &CuttingPlan) -> Vec<u8> {
+    let mut pdf = PdfDocument::default();
+    let (page_w, page_h) = (pdf.page_width, pdf.page_height);
+    let margin = 50.0;
+
+    let title = |pdf: &mut PdfDocument, text: &str| {
+        pdf.set_font_size(16.0);
+        pdf.set_fill_color(0.0, 0.0, 0.0);
+        pdf.draw_text_aligned(page_w / 2.0, page_h - margin, text, TextAlign::Center);
+        pdf.set_font_size(8.0);
+    };
+
+    // Lumber: one bar per board, scaled so the longest board fits the page
+    title(
+        &mut pdf,
+        &format!(
+            "Lumber Cutting Plan - {} boards, {:.1}% waste",
+            plan.boards.len(),
+            plan.lumber_waste_pct()
+        ),
+    );
+    let longest = plan
+        .boards
+        .iter()
+        .map(|b| b.stock_length)
+        .fold(1.0, f32::max) as f64;
+    let scale = (page_w - 2.0 * margin) / longest;
+    let row = 34.0;
+    let mut y = page_h - margin - 40.0;
+    for board in &plan.boards {
+        if y < margin {
+            pdf.add_page();
+            pdf.set_font_size(8.0);
+            y = page_h - margin;
+        }
+        pdf.set_stroke_color(0.5, 0.5, 0.5);
+        pdf.set_line_width(0.5);
+        pdf.draw_rect(margin, y, board.stock_length as f64 * scale, 12.0);
+        for cut in &board.cuts {
+            pdf.set_fill_color(0.85, 0.7, 0.55);
+            pdf.fill_stroke_rect(
+                margin + cut.offset as f64 * scale,
+                y,
+                cut.length as f64 * scale,
+                12.0,
+            );
+            pdf.set_fill_color(0.0, 0.0, 0.0);
+            pdf.draw_text(
+                margin + cut.offset as f64 * scale + 2.0,
+                y + 3.0,
+                &format!("{:.2}", cut.length),
+            );
+        }
+        pdf.draw_text(
+            margin,
+            y - 10.0,
+            &format!(
+                "{} x {:.0}\": {}",
+                board.size.name(),
+                board.stock_length,
+                board
+                    .cuts
+                    .iter()
+                    .map(|c| c.label.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        );
+        y -= row;
+    }
+
+    // Plywood: two sheets per page, scaled to half the page width
+    for (i, sheet) in plan.sheets.iter().enumerate() {
+        let column = i % 2;
+        if column == 0 {
+            pdf.add_page();
+            title(
+                &mut pdf,
+                &format!(
+                    "Plywood Nesting - {} sheets, {:.1}% waste",
+                    plan.sheets.len(),
+                    plan.plywood_waste_pct()
+                ),
+            );
+        }
+        let scale = ((page_w - 3.0 * margin) / 2.0 / sheet.width as f64)
+            .min((page_h - 3.0 * margin) / sheet.length as f64);
+        let x0 = margin + column as f64 * ((page_w - margin) / 2.0);
+        let top = page_h - 2.0 * margin;
+        let y0 = top - sheet.length as f64 * scale;
+
+        pdf.set_stroke_color(0.5, 0.5, 0.5);
+        pdf.draw_rect(
+            x0,
+            y0,
+            sheet.width as f64 * scale,
+            sheet.length as f64 * scale,
+        );
+        for cut in &sheet.cuts {
+            // Sheet y runs down the page from the top edge
+            let (x, w, h) = (
+                x0 + cut.x as f64 * scale,
+                cut.width as f64 * scale,
+                cut.height as f64 * scale,
+            );
+            let y = top - cut.y as f64 * scale - h;
+            pdf.set_fill_color(0.91, 0.84, 0.66);
+            pdf.fill_stroke_rect(x, y, w, h);
+            pdf.set_fill_color(0.0, 0.0, 0.0);
+            pdf.draw_text(x + 2.0, y + h - 10.0, &cut.label);
+            pdf.draw_text(
+                x + 2.0,
+                y + h - 20.0,
+                &format!("{:.2} x {:.2}", cut.width, cut.height),
+            );
+        }
+        pdf.draw_text(
+            x0,
+            y0 - 12.0,
+            &format!(
+                "Sheet {} ({:.0}x{:.0}, {:.3}\")",
+                i + 1,
+                sheet.width,
+                sheet.length,
+                sheet.thickness
+            ),
+        );
+    }
+
+    pdf.to_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::autocrate::CrateSpec;
+
+    fn pieces(lengths: &[f32]) -> Vec<LumberPiece> {
+        lengths
+            .iter()
+            .enumerate()
+            .map(|(i, l)| (format!("P{}", i + 1), *l))
+            .collect()
+    }
+
+    #[test]
+    fn test_lumber_cutting_stock() {
+        // Four 4' pieces exactly fill one 16' board (ties prefer the longer stock)
+        let (boards, left) = cut_lumber(
+            LumberSize::L2x4,
+            &pieces(&[48.0, 48.0, 48.0, 48.0]),
+            &[96.0, 192.0],
+            0.0,
+        );
+        assert!(left.is_empty());
+        assert_eq!(boards.len(), 1);
+        assert_eq!(boards[0].stock_length, 192.0);
+        assert_eq!(boards[0].cuts[3].offset, 144.0);
+
+        // With kerf every board stays within its stock length
+        let lengths = [70.0, 55.5, 55.5, 40.0, 33.25, 33.25, 20.0, 130.0, 200.0];
+        let (boards, left) = cut_lumber(
+            LumberSize::L2x4,
+            &pieces(&lengths),
+            &CuttingOptions::default().stock_lengths,
+            0.125,
+        );
+        assert_eq!(left, vec!["P9".to_string()]);
+        let placed: usize = boards.iter().map(|b| b.cuts.len()).sum();
+        assert_eq!(placed, 8);
+        for b in &boards {
+            assert!(b.used_length(0.125) <= b.stock_length + 1e-4);
+            let last = b.cuts.last().unwrap();
+            assert!((last.offset + last.length - b.used_length(0.125)).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_guillotine_nesting() {
+        let options = CuttingOptions {
+            kerf: 0.0,
+            ..Default::default()
+        };
+        // Eight 24x48 pieces fill exactly two sheets
+        let quarter: Vec<_> = (0..8).map(|i| (format!("Q{}", i), 48.0, 24.0)).collect();
+        let sheets = nest_plywood(0.5, &quarter, &options);
+        assert_eq!(sheets.len(), 2);
+        assert!(sheets.iter().all(|s| s.cuts.len() == 4));
+
+        // Mixed pieces with kerf: inside the sheet and non-overlapping
+        let options = CuttingOptions::default();
+        let mixed = vec![
+            ("A".to_string(), 52.0, 27.75),
+            ("B".to_string(), 52.0, 27.75),
+            ("C".to_string(), 42.0, 27.75),
+            ("D".to_string(), 40.0, 52.0),
+            ("E".to_string(), 20.0, 6.0),
+            ("Big".to_string(), 120.0, 60.0),
+        ];
+        let sheets = nest_plywood(0.25, &mixed, &options);
+        let cuts: Vec<&SheetCut> = sheets.iter().flat_map(|s| &s.cuts).collect();
+        // The oversize piece is split into three 40x60 sections
+        assert_eq!(
+            cuts.iter().filter(|c| c.label.starts_with("Big (")).count(),
+            3
+        );
+        for sheet in &sheets {
+            for (i, a) in sheet.cuts.iter().enumerate() {
+                assert!(a.x + a.width <= sheet.width + 1e-3);
+                assert!(a.y + a.height <= sheet.length + 1e-3);
+                for b in &sheet.cuts[i + 1..] {
+                    let overlap_x = a.x < b.x + b.width - 1e-3 && b.x < a.x + a.width - 1e-3;
+                    let overlap_y = a.y < b.y + b.height - 1e-3 && b.y < a.y + a.height - 1e-3;
+                    assert!(!(overlap_x && overlap_y), "{:?} overlaps {:?}", a, b);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_design_cutting_plan_and_outputs() {
+        let design = CrateDesign::from_spec(&CrateSpec::default());
+        let plan = optimize_cutting(&design, &CuttingOptions::default());
+
+        assert!(plan.unplaced.is_empty());
+        let lumber_parts = design
+            .parts
+            .iter()
+            .filter(|p| p.category == PartCategory::Lumber)
+            .count();
+        let cut: usize = plan.boards.iter().map(|b| b.cuts.len()).sum();
+        assert_eq!(cut, lumber_parts);
+        assert!(!plan.sheets.is_empty());
+        let waste = plan.lumber_waste_pct();
+        assert!((0.0..50.0).contains(&waste), "lumber waste {}", waste);
+        assert!((0.0..100.0).contains(&plan.plywood_waste_pct()));
+
+        let items = plan.purchase_list();
+        assert!(items.iter().any(|i| i.material == "Plywood"));
+        let boards: u32 = items
+            .iter()
+            .filter(|i| i.material == "Lumber")
+            .map(|i| i.quantity)
+            .sum();
+        assert_eq!(boards as usize, plan.boards.len());
+        assert!(purchase_list_to_csv(&items).starts_with("material,size,quantity\n"));
+
+        let svg = cutting_plan_to_svg(&plan);
+        assert!(svg.starts_with("<svg") && svg.trim_end().ends_with("</svg>"));
+        assert!(svg.contains("Skid 1"));
+        let pdf = cutting_plan_to_pdf(&plan);
+        assert!(pdf.starts_with(b"%PDF-1.4"));
+    }
+}
@@ -1,14 +1,15 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: design.rs | DNA/src/autocrate/design.rs
 //! PURPOSE: Canonical crate design graph (parts list) for export + visualization
-//! MODIFIED: 2025-12-11
+//! MODIFIED: 2026-10-18
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
 
 use super::calculator::calculate_crate;
 use super::constants::LumberSize;
 use super::geometry::{BoundingBox, PanelType, Point3};
-use super::types::{CrateGeometry, CrateSpec};
+use super::standards::{effective_spec, StandardProfile};
+use super::types::{BaseStyle, CrateGeometry, CrateSpec};
 
 /// High-level part category (used by exporters/viewers to color/group items).
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -26,6 +27,7 @@ pub enum CratePartKind {
     Floorboard { index: usize },
     Panel { panel: PanelType },
     Cleat { panel: PanelType, is_vertical: bool, index: usize },
+    LiftCleat { panel: PanelType, index: usize },
     PanelStop { location: String },
     Klimp { edge: String, index: usize },
     LagScrew { panel: PanelType, index: usize, component: String },
@@ -72,27 +74,45 @@ pub struct CrateDesign {
 
 impl CrateDesign {
     /// Build a canonical design from a spec by running the calculator.
+    /// The stored spec carries the member sizes that were built.
     pub fn from_spec(spec: &CrateSpec) -> Self {
-        let geometry = calculate_crate(spec);
-        Self::from_geometry(spec.clone(), geometry)
+        let spec = effective_spec(spec).into_owned();
+        let geometry = calculate_crate(&spec);
+        Self::from_geometry(spec, geometry)
     }
 
     /// Build a canonical design from an already computed geometry.
     pub fn from_geometry(spec: CrateSpec, geometry: CrateGeometry) -> Self {
         let mut parts: Vec<CratePart> = Vec::new();
 
-        // Skids
+        let profile = StandardProfile::for_requirements(&spec.requirements);
+
+        // Skids (sub-base runners carry their forklift notches in metadata)
+        let skid_name = match spec.requirements.base {
+            BaseStyle::Skids => "Skid",
+            BaseStyle::SubBase => "Runner",
+        };
         for (i, skid) in geometry.skids.iter().enumerate() {
+            let mut metadata = format!("Lumber {}", skid.lumber_size.name());
+            if let Some(notch) = skid.notches.first() {
+                let size = notch.size();
+                metadata.push_str(&format!(
+                    "; {} forklift notches {:.2}\" x {:.2}\" deep",
+                    skid.notches.len(),
+                    size.y,
+                    size.z
+                ));
+            }
             parts.push(CratePart {
-                id: format!("SKID-{:02}", i + 1),
-                name: format!("Skid {}", i + 1),
+                id: format!("{}-{:02}", skid_name.to_uppercase(), i + 1),
+                name: format!("{} {}", skid_name, i + 1),
                 category: PartCategory::Lumber,
                 kind: CratePartKind::Skid { index: i },
                 material: PartMaterial::Lumber {
                     nominal: skid.lumber_size,
                 },
                 bounds: skid.bounds,
-                metadata: Some(format!("Lumber {}", skid.lumber_size.name())),
+                metadata: Some(metadata),
             });
         }
 
@@ -113,14 +133,20 @@ impl CrateDesign {
 
         // Panels (plywood + cleat stack represented as a single box for now)
         // Note: We keep the semantic panel identity (front/back/left/right/top).
+        // Open crates are unsheathed: the cleat frame is the whole panel.
         let panel_thickness = spec.materials.panel_thickness;
-        for panel in [
-            &geometry.panels.front,
-            &geometry.panels.back,
-            &geometry.panels.left,
-            &geometry.panels.right,
-            &geometry.panels.top,
-        ] {
+        let sheathed_panels = if profile.sheathed {
+            vec![
+                &geometry.panels.front,
+                &geometry.panels.back,
+                &geometry.panels.left,
+                &geometry.panels.right,
+                &geometry.panels.top,
+            ]
+        } else {
+            Vec::new()
+        };
+        for panel in sheathed_panels {
             let panel_name = panel.panel_type.name();
             parts.push(CratePart {
                 id: format!("PANEL-{}", panel_name.to_uppercase()),
@@ -164,6 +190,31 @@ impl CrateDesign {
             cleat_index += 1;
         }
 
+        // Hoisting cleats
+        for (i, cleat) in geometry.lift_cleats.iter().enumerate() {
+            let p = cleat.lift_point;
+            parts.push(CratePart {
+                id: format!("LIFT-CLEAT-{:02}", i + 1),
+                name: format!("{} Lift Cleat {}", cleat.panel.name(), i + 1),
+                category: PartCategory::Lumber,
+                kind: CratePartKind::LiftCleat {
+                    panel: cleat.panel,
+                    index: i,
+                },
+                material: PartMaterial::Lumber {
+                    nominal: cleat.lumber_size,
+                },
+                bounds: cleat.bounds,
+                metadata: Some(format!(
+                    "Lumber {}; lift point ({:.2}, {:.2}, {:.2})",
+                    cleat.lumber_size.name(),
+                    p.x,
+                    p.y,
+                    p.z
+                )),
+            });
+        }
+
         // ─────────────────────────────────────────────────────────────────────────
         // Hardware + decals (fasteners, panel stops, markings)
         // ─────────────────────────────────────────────────────────────────────────
@@ -0,0 +1,1331 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: drawings.rs | DNA/src/autocrate/drawings.rs
+//! PURPOSE: Multi-sheet 2D shop drawings (orthographic, isometric, exploded) → PDF/DXF
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+//!
+//! Builds a drawing set from a `CrateDesign`. Sheets are composed of a few
+//! backend-neutral entities (lines, circles, text) in **sheet inches** with
+//! the origin at the bottom-left, then rendered to PDF or DXF.
+//!
+//! ```text
+//! ┌──────────┬───────────────────────────────────────────────────────────┐
+//! │ Sheet    │ Content                                                   │
+//! ├──────────┼───────────────────────────────────────────────────────────┤
+//! │ 1        │ Assembly: front/side elevation, plan, isometric, notes    │
+//! │ 2        │ Exploded assembly (isometric, panels pulled apart)        │
+//! │ 3        │ Base: skid/floorboard plan, end view, skid elevation      │
+//! │ 4..      │ One per panel: face view, cleats, hardware table, iso     │
+//! └──────────┴───────────────────────────────────────────────────────────┘
+//! ```
+//!
+//! `panel_layout_to_dxf` exports a single panel 1:1 for the laser cutter.
+//!
+//! Dimensions are shown in fractional inches rounded to 1/16". Views pick
+//! the largest standard scale that fits; isometric views are not to scale.
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use super::constants::to_fractional_inches;
+use super::design::{CrateDesign, CratePart, CratePartKind, PartCategory};
+use super::geometry::{BoundingBox, PanelType, Point3};
+use crate::export::dxf::{DxfDocument, DxfEntity, DxfUnits};
+use crate::export::pdf::{PdfDocument, TextAlign};
+
+/// Sheet-space point (inches)
+pub type SheetPoint = (f32, f32);
+
+/// Drawing layer (DXF layer / PDF pen)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DrawingLayer {
+    Border,
+    Outline,
+    Lumber,
+    Hardware,
+    Markings,
+    Dimensions,
+    Text,
+}
+
+impl DrawingLayer {
+    pub const ALL: [DrawingLayer; 7] = [
+        DrawingLayer::Border,
+        DrawingLayer::Outline,
+        DrawingLayer::Lumber,
+        DrawingLayer::Hardware,
+        DrawingLayer::Markings,
+        DrawingLayer::Dimensions,
+        DrawingLayer::Text,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DrawingLayer::Border => "BORDER",
+            DrawingLayer::Outline => "OUTLINE",
+            DrawingLayer::Lumber => "LUMBER",
+            DrawingLayer::Hardware => "HARDWARE",
+            DrawingLayer::Markings => "MARKINGS",
+            DrawingLayer::Dimensions => "DIMENSIONS",
+            DrawingLayer::Text => "TEXT",
+        }
+    }
+
+    /// AutoCAD color index
+    pub fn aci_color(&self) -> u8 {
+        match self {
+            DrawingLayer::Border | DrawingLayer::Outline | DrawingLayer::Text => 7,
+            DrawingLayer::Lumber => 3,
+            DrawingLayer::Hardware => 1,
+            DrawingLayer::Markings => 6,
+            DrawingLayer::Dimensions => 4,
+        }
+    }
+
+    /// PDF pen: (line width in points, RGB)
+    fn pen(&self) -> (f64, (f64, f64, f64)) {
+        match self {
+            DrawingLayer::Border => (1.2, (0.0, 0.0, 0.0)),
+            DrawingLayer::Outline => (0.9, (0.0, 0.0, 0.0)),
+            DrawingLayer::Lumber => (0.7, (0.45, 0.3, 0.1)),
+            DrawingLayer::Hardware => (0.5, (0.75, 0.1, 0.1)),
+            DrawingLayer::Markings => (0.4, (0.5, 0.2, 0.6)),
+            DrawingLayer::Dimensions => (0.35, (0.1, 0.3, 0.6)),
+            DrawingLayer::Text => (0.5, (0.0, 0.0, 0.0)),
+        }
+    }
+}
+
+/// Drawing primitive in sheet inches
+#[derive(Clone, Debug)]
+pub enum DrawingEntity {
+    Line {
+        layer: DrawingLayer,
+        from: SheetPoint,
+        to: SheetPoint,
+    },
+    Circle {
+        layer: DrawingLayer,
+        center: SheetPoint,
+        radius: f32,
+    },
+    Text {
+        layer: DrawingLayer,
+        /// Baseline anchor
+        at: SheetPoint,
+        height: f32,
+        text: String,
+        align: TextAlign,
+    },
+}
+
+/// One drawing sheet
+#[derive(Clone, Debug)]
+pub struct DrawingSheet {
+    pub number: usize,
+    pub title: String,
+    /// Scale of the principal view ("1:16", "NTS")
+    pub scale: String,
+    pub entities: Vec<DrawingEntity>,
+}
+
+/// Complete drawing set
+#[derive(Clone, Debug)]
+pub struct DrawingSet {
+    pub title: String,
+    pub drawing_number: String,
+    /// Sheet size (width, height) in inches
+    pub sheet_size: (f32, f32),
+    pub sheets: Vec<DrawingSheet>,
+}
+
+impl DrawingSet {
+    /// All text on a sheet (useful for checks and search)
+    pub fn sheet_text(&self, index: usize) -> Vec<&str> {
+        self.sheets
+            .get(index)
+            .map(|s| {
+                s.entities
+                    .iter()
+                    .filter_map(|e| match e {
+                        DrawingEntity::Text { text, .. } => Some(text.as_str()),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Drawing set options
+#[derive(Clone, Debug)]
+pub struct DrawingOptions {
+    /// Sheet size in inches (default ANSI B landscape, 17 x 11)
+    pub sheet_size: (f32, f32),
+    /// Title block title; empty = derived from the crate size
+    pub title: String,
+    pub drawing_number: String,
+    pub drawn_by: String,
+    pub date: String,
+    /// Distance parts are pulled apart in the exploded view (inches)
+    pub explode_distance: f32,
+}
+
+impl Default for DrawingOptions {
+    fn default() -> Self {
+        Self {
+            sheet_size: (17.0, 11.0),
+            title: String::new(),
+            drawing_number: "CRATE-001".to_string(),
+            drawn_by: "AUTOCRATE".to_string(),
+            date: String::new(),
+            explode_distance: 18.0,
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Projections and view fitting
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// How model space maps onto a view
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Projection {
+    /// Face view from outside the given panel
+    Face(PanelType),
+    /// Looking down (x right, y up the sheet)
+    Plan,
+    /// 30° isometric
+    Iso,
+}
+
+fn project(p: Point3, projection: Projection) -> SheetPoint {
+    match projection {
+        Projection::Face(PanelType::Front) => (p.x, p.z),
+        Projection::Face(PanelType::Back) => (-p.x, p.z),
+        Projection::Face(PanelType::Left) => (-p.y, p.z),
+        Projection::Face(PanelType::Right) => (p.y, p.z),
+        Projection::Face(PanelType::Top) | Projection::Plan => (p.x, p.y),
+        Projection::Iso => {
+            let (c, s) = (30f32.to_radians().cos(), 30f32.to_radians().sin());
+            ((p.x - p.y) * c, (p.x + p.y) * s + p.z)
+        }
+    }
+}
+
+fn corners(b: &BoundingBox) -> [Point3; 8] {
+    let (lo, hi) = (b.min, b.max);
+    [
+        Point3::new(lo.x, lo.y, lo.z),
+        Point3::new(hi.x, lo.y, lo.z),
+        Point3::new(hi.x, hi.y, lo.z),
+        Point3::new(lo.x, hi.y, lo.z),
+        Point3::new(lo.x, lo.y, hi.z),
+        Point3::new(hi.x, lo.y, hi.z),
+        Point3::new(hi.x, hi.y, hi.z),
+        Point3::new(lo.x, hi.y, hi.z),
+    ]
+}
+
+/// Projected 2D extent of a box
+fn projected_rect(b: &BoundingBox, projection: Projection) -> (SheetPoint, SheetPoint) {
+    let mut lo = (f32::MAX, f32::MAX);
+    let mut hi = (f32::MIN, f32::MIN);
+    for c in corners(b) {
+        let (u, v) = project(c, projection);
+        lo = (lo.0.min(u), lo.1.min(v));
+        hi = (hi.0.max(u), hi.1.max(v));
+    }
+    (lo, hi)
+}
+
+/// Standard drawing scales, largest first
+const SCALES: [(f32, &str); 12] = [
+    (1.0, "1:1"),
+    (0.5, "1:2"),
+    (0.25, "1:4"),
+    (1.0 / 6.0, "1:6"),
+    (0.125, "1:8"),
+    (1.0 / 12.0, "1:12"),
+    (1.0 / 16.0, "1:16"),
+    (1.0 / 24.0, "1:24"),
+    (1.0 / 32.0, "1:32"),
+    (1.0 / 48.0, "1:48"),
+    (1.0 / 64.0, "1:64"),
+    (1.0 / 96.0, "1:96"),
+];
+
+/// Largest standard scale that fits `extent` into `room`, else an exact fit ("NTS")
+fn choose_scale(extent: (f32, f32), room: (f32, f32)) -> (f32, &'static str) {
+    let exact = (room.0 / extent.0.max(1e-3)).min(room.1 / extent.1.max(1e-3));
+    SCALES
+        .iter()
+        .find(|(s, _)| *s <= exact)
+        .copied()
+        .unwrap_or((exact, "NTS"))
+}
+
+/// Model → sheet mapping for one view
+#[derive(Clone, Copy, Debug)]
+struct View {
+    projection: Projection,
+    scale: f32,
+    /// Model-space point mapped to `origin`
+    model_origin: SheetPoint,
+    origin: SheetPoint,
+}
+
+impl View {
+    /// Fit the projected `bounds` centred in the viewport (lo, hi), leaving `pad` for dimensions
+    fn fit(
+        projection: Projection,
+        bounds: &[BoundingBox],
+        viewport: (SheetPoint, SheetPoint),
+        pad: f32,
+        iso_nts: bool,
+    ) -> (View, &'static str) {
+        let mut lo = (f32::MAX, f32::MAX);
+        let mut hi = (f32::MIN, f32::MIN);
+        for b in bounds {
+            let (a, c) = projected_rect(b, projection);
+            lo = (lo.0.min(a.0), lo.1.min(a.1));
+            hi = (hi.0.max(c.0), hi.1.max(c.1));
+        }
+        let extent = ((hi.0 - lo.0).max(1e-3), (hi.1 - lo.1).max(1e-3));
+        let room = (
+            (viewport.1 .0 - viewport.0 .0 - 2.0 * pad).max(0.5),
+            (viewport.1 .1 - viewport.0 .1 - 2.0 * pad).max(0.5),
+        );
+        let (scale, label) = if iso_nts {
+            ((room.0 / extent.0).min(room.1 / extent.1), "NTS")
+        } else {
+            choose_scale(extent, room)
+        };
+        let centre = (
+            (viewport.0 .0 + viewport.1 .0) / 2.0,
+            (viewport.0 .1 + viewport.1 .1) / 2.0,
+        );
+        let view = View {
+            projection,
+            scale,
+            model_origin: ((lo.0 + hi.0) / 2.0, (lo.1 + hi.1) / 2.0),
+            origin: centre,
+        };
+        (view, label)
+    }
+
+    fn map2(&self, p: SheetPoint) -> SheetPoint {
+        (
+            self.origin.0 + (p.0 - self.model_origin.0) * self.scale,
+            self.origin.1 + (p.1 - self.model_origin.1) * self.scale,
+        )
+    }
+
+    fn map(&self, p: Point3) -> SheetPoint {
+        self.map2(project(p, self.projection))
+    }
+
+    /// Sheet rectangle of a box in an orthographic view
+    fn rect(&self, b: &BoundingBox) -> (SheetPoint, SheetPoint) {
+        let (lo, hi) = projected_rect(b, self.projection);
+        (self.map2(lo), self.map2(hi))
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Sheet composition helpers
+// ─────────────────────────────────────────────────────────────────────────────
+
+const TEXT_H: f32 = 0.12;
+const DIM_GAP: f32 = 0.06;
+const DIM_EXT: f32 = 0.1;
+
+/// Dimension text in fractional inches, rounded to 1/16"
+pub fn dimension_text(inches: f32) -> String {
+    format!("{}\"", to_fractional_inches((inches * 16.0).round() / 16.0))
+}
+
+impl DrawingSheet {
+    fn new(number: usize, title: &str) -> Self {
+        Self {
+            number,
+            title: title.to_string(),
+            scale: "NTS".to_string(),
+            entities: Vec::new(),
+        }
+    }
+
+    fn line(&mut self, layer: DrawingLayer, from: SheetPoint, to: SheetPoint) {
+        self.entities.push(DrawingEntity::Line { layer, from, to });
+    }
+
+    fn rect(&mut self, layer: DrawingLayer, lo: SheetPoint, hi: SheetPoint) {
+        self.line(layer, lo, (hi.0, lo.1));
+        self.line(layer, (hi.0, lo.1), hi);
+        self.line(layer, hi, (lo.0, hi.1));
+        self.line(layer, (lo.0, hi.1), lo);
+    }
+
+    fn circle(&mut self, layer: DrawingLayer, center: SheetPoint, radius: f32) {
+        self.entities.push(DrawingEntity::Circle {
+            layer,
+            center,
+            radius,
+        });
+    }
+
+    fn text(&mut self, at: SheetPoint, height: f32, text: &str, align: TextAlign) {
+        self.entities.push(DrawingEntity::Text {
+            layer: DrawingLayer::Text,
+            at,
+            height,
+            text: text.to_string(),
+            align,
+        });
+    }
+
+    /// Horizontal dimension between sheet x-coordinates `x0..x1`, measured from
+    /// reference line `y_ref`; negative `offset` places it below
+    fn dim_horizontal(&mut self, x0: f32, x1: f32, y_ref: f32, offset: f32, value: f32) {
+        let layer = DrawingLayer::Dimensions;
+        let sign = offset.signum();
+        let y = y_ref + offset;
+        for x in [x0, x1] {
+            self.line(layer, (x, y_ref + sign * DIM_GAP), (x, y + sign * DIM_EXT));
+            self.line(layer, (x - 0.05, y - 0.05), (x + 0.05, y + 0.05));
+        }
+        self.line(layer, (x0, y), (x1, y));
+        let baseline = if sign > 0.0 {
+            y + 0.04
+        } else {
+            y - 0.04 - TEXT_H
+        };
+        self.entities.push(DrawingEntity::Text {
+            layer,
+            at: ((x0 + x1) / 2.0, baseline),
+            height: TEXT_H,
+            text: dimension_text(value),
+            align: TextAlign::Center,
+        });
+    }
+
+    /// Vertical dimension between sheet y-coordinates `y0..y1`, measured from
+    /// reference line `x_ref`; negative `offset` places it to the left
+    fn dim_vertical(&mut self, y0: f32, y1: f32, x_ref: f32, offset: f32, value: f32) {
+        let layer = DrawingLayer::Dimensions;
+        let sign = offset.signum();
+        let x = x_ref + offset;
+        for y in [y0, y1] {
+            self.line(layer, (x_ref + sign * DIM_GAP, y), (x + sign * DIM_EXT, y));
+            self.line(layer, (x - 0.05, y - 0.05), (x + 0.05, y + 0.05));
+        }
+        self.line(layer, (x, y0), (x, y1));
+        let (dx, align) = if sign > 0.0 {
+            (0.05, TextAlign::Left)
+        } else {
+            (-0.05, TextAlign::Right)
+        };
+        self.entities.push(DrawingEntity::Text {
+            layer,
+            at: (x + dx, (y0 + y1) / 2.0 - TEXT_H / 2.0),
+            height: TEXT_H,
+            text: dimension_text(value),
+            align,
+        });
+    }
+
+    /// Chain of horizontal dimensions through sorted model-space stations
+    fn chain_horizontal(&mut self, view: &View, stations: &[f32], y_ref: f32, offset: f32) {
+        for pair in stations.windows(2) {
+            if pair[1] - pair[0] > 0.01 {
+                let x0 = view.map2((pair[0], 0.0)).0;
+                let x1 = view.map2((pair[1], 0.0)).0;
+                self.dim_horizontal(x0, x1, y_ref, offset, pair[1] - pair[0]);
+            }
+        }
+    }
+
+    fn chain_vertical(&mut self, view: &View, stations: &[f32], x_ref: f32, offset: f32) {
+        for pair in stations.windows(2) {
+            if pair[1] - pair[0] > 0.01 {
+                let y0 = view.map2((0.0, pair[0])).1;
+                let y1 = view.map2((0.0, pair[1])).1;
+                self.dim_vertical(y0, y1, x_ref, offset, pair[1] - pair[0]);
+            }
+        }
+    }
+
+    /// Box drawn in a view: rectangle (orthographic) or 12-edge wireframe (iso)
+    fn draw_box(&mut self, layer: DrawingLayer, view: &View, b: &BoundingBox) {
+        if view.projection == Projection::Iso {
+            let c = corners(b).map(|p| view.map(p));
+            for (i, j) in [
+                (0, 1),
+                (1, 2),
+                (2, 3),
+                (3, 0),
+                (4, 5),
+                (5, 6),
+                (6, 7),
+                (7, 4),
+                (0, 4),
+                (1, 5),
+                (2, 6),
+                (3, 7),
+            ] {
+                self.line(layer, c[i], c[j]);
+            }
+        } else {
+            let (lo, hi) = view.rect(b);
+            self.rect(layer, lo, hi);
+        }
+    }
+
+    fn view_label(&mut self, view_bottom: SheetPoint, name: &str, scale: &str) {
+        self.text(view_bottom, 0.16, name, TextAlign::Center);
+        self.text(
+            (view_bottom.0, view_bottom.1 - 0.2),
+            0.1,
+            &format!("SCALE {}", scale),
+            TextAlign::Center,
+        );
+    }
+}
+
+fn part_layer(part: &CratePart) -> DrawingLayer {
+    match (part.category, &part.kind) {
+        (_, CratePartKind::Panel { .. }) | (_, CratePartKind::PanelStop { .. }) => {
+            DrawingLayer::Outline
+        }
+        (PartCategory::Lumber, _) => DrawingLayer::Lumber,
+        (PartCategory::Hardware, _) => DrawingLayer::Hardware,
+        (PartCategory::Decal, _) => DrawingLayer::Markings,
+        (PartCategory::Plywood, _) => DrawingLayer::Outline,
+    }
+}
+
+/// Panel a part is mounted on (`None` for the base)
+fn part_panel(part: &CratePart) -> Option<PanelType> {
+    match &part.kind {
+        CratePartKind::Skid { .. } | CratePartKind::Floorboard { .. } => None,
+        CratePartKind::Panel { panel }
+        | CratePartKind::Cleat { panel, .. }
+        | CratePartKind::LiftCleat { panel, .. }
+        | CratePartKind::LagScrew { panel, .. }
+        | CratePartKind::Decal { panel, .. } => Some(*panel),
+        // Klimps join the front panel to its neighbours
+        CratePartKind::Klimp { .. } => Some(PanelType::Front),
+        CratePartKind::PanelStop { location } => Some(if location.contains("-TOP-") {
+            PanelType::Top
+        } else {
+            PanelType::Front
+        }),
+    }
+}
+
+fn is_base(part: &CratePart) -> bool {
+    part_panel(part).is_none()
+}
+
+/// Parts drawn in assembly views (hardware and decals are too small to read)
+fn is_structural(part: &CratePart) -> bool {
+    matches!(part.category, PartCategory::Lumber | PartCategory::Plywood)
+}
+
+fn panel_geometry(design: &CrateDesign, panel: PanelType) -> &super::geometry::PanelGeometry {
+    let panels = &design.geometry.panels;
+    match panel {
+        PanelType::Front => &panels.front,
+        PanelType::Back => &panels.back,
+        PanelType::Left => &panels.left,
+        PanelType::Right => &panels.right,
+        PanelType::Top => &panels.top,
+    }
+}
+
+fn explode_offset(part: &CratePart, d: f32) -> Point3 {
+    match part_panel(part) {
+        Some(PanelType::Front) => Point3::new(0.0, -d, 0.0),
+        Some(PanelType::Back) => Point3::new(0.0, d, 0.0),
+        Some(PanelType::Left) => Point3::new(-d, 0.0, 0.0),
+        Some(PanelType::Right) => Point3::new(d, 0.0, 0.0),
+        Some(PanelType::Top) => Point3::new(0.0, 0.0, 1.5 * d),
+        None => match part.kind {
+            CratePartKind::Skid { .. } => Point3::new(0.0, 0.0, -0.5 * d),
+            _ => Point3::new(0.0, 0.0, 0.0),
+        },
+    }
+}
+
+fn translated(b: &BoundingBox, d: Point3) -> BoundingBox {
+    BoundingBox::new(
+        Point3::new(b.min.x + d.x, b.min.y + d.y, b.min.z + d.z),
+        Point3::new(b.max.x + d.x, b.max.y + d.y, b.max.z + d.z),
+    )
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Drawing set generation
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Sheet frame geometry shared by all sheets
+struct Frame {
+    width: f32,
+    height: f32,
+    margin: f32,
+    title_block: (SheetPoint, SheetPoint),
+}
+
+impl Frame {
+    fn new(size: (f32, f32)) -> Self {
+        let margin = 0.5;
+        let tb_w = 7.0f32.min(size.0 - 2.0 * margin);
+        Self {
+            width: size.0,
+            height: size.1,
+            margin,
+            title_block: (
+                (size.0 - margin - tb_w, margin),
+                (size.0 - margin, margin + 1.6),
+            ),
+        }
+    }
+
+    /// Drawing area above the title block
+    fn area(&self) -> (SheetPoint, SheetPoint) {
+        (
+            (self.margin + 0.25, self.title_block.1 .1 + 0.25),
+            (
+                self.width - self.margin - 0.25,
+                self.height - self.margin - 0.25,
+            ),
+        )
+    }
+}
+
+/// Generate the full drawing set for a design
+pub fn generate_drawing_set(design: &CrateDesign, options: &DrawingOptions) -> DrawingSet {
+    let frame = Frame::new(options.sheet_size);
+    let g = &design.geometry;
+    let title = if options.title.is_empty() {
+        format!(
+            "CRATE {} x {} x {}",
+            dimension_text(g.overall_length),
+            dimension_text(g.overall_width),
+            dimension_text(g.overall_height)
+        )
+    } else {
+        options.title.clone()
+    };
+
+    let mut sheets = vec![
+        assembly_sheet(design, &frame),
+        exploded_sheet(design, &frame, options.explode_distance),
+        base_sheet(design, &frame),
+    ];
+    for panel in [
+        PanelType::Front,
+        PanelType::Back,
+        PanelType::Left,
+        PanelType::Right,
+        PanelType::Top,
+    ] {
+        sheets.push(panel_sheet(design, &frame, panel));
+    }
+
+    let count = sheets.len();
+    for (i, sheet) in sheets.iter_mut().enumerate() {
+        sheet.number = i + 1;
+        draw_frame(sheet, &frame, &title, options, count);
+    }
+
+    DrawingSet {
+        title,
+        drawing_number: options.drawing_number.clone(),
+        sheet_size: options.sheet_size,
+        sheets,
+    }
+}
+
+fn draw_frame(
+    sheet: &mut DrawingSheet,
+    frame: &Frame,
+    title: &str,
+    options: &DrawingOptions,
+    count: usize,
+) {
+    let m = frame.margin;
+    let layer = DrawingLayer::Border;
+    sheet.rect(layer, (m, m), (frame.width - m, frame.height - m));
+
+    let ((x0, y0), (x1, y1)) = frame.title_block;
+    sheet.rect(layer, (x0, y0), (x1, y1));
+    let row = (y1 - y0) / 4.0;
+    let split = x0 + (x1 - x0) * 0.6;
+    // Title row spans the block; remaining rows are split in two columns
+    sheet.line(layer, (x0, y1 - row), (x1, y1 - row));
+    sheet.line(layer, (x0, y0 + row), (x1, y0 + row));
+    sheet.line(layer, (x0, y0 + 2.0 * row), (x1, y0 + 2.0 * row));
+    sheet.line(layer, (split, y0), (split, y1 - row));
+
+    let pad = 0.08;
+    let cell = |r: usize| y0 + row * r as f32 + 0.12;
+    sheet.text((x0 + pad, y1 - row + 0.12), 0.2, title, TextAlign::Left);
+    let sheet_title = sheet.title.to_uppercase();
+    sheet.text((x0 + pad, cell(2)), 0.16, &sheet_title, TextAlign::Left);
+    let scale = format!("SCALE {}", sheet.scale);
+    sheet.text((split + pad, cell(2)), 0.12, &scale, TextAlign::Left);
+    let dwg = format!("DWG NO {}", options.drawing_number);
+    sheet.text((x0 + pad, cell(1)), 0.12, &dwg, TextAlign::Left);
+    let page = format!("SHEET {} OF {}", sheet.number, count);
+    sheet.text((split + pad, cell(1)), 0.12, &page, TextAlign::Left);
+    let drawn = format!("DRAWN {}  {}", options.drawn_by, options.date);
+    sheet.text((x0 + pad, cell(0)), 0.1, drawn.trim_end(), TextAlign::Left);
+    sheet.text(
+        (split + pad, cell(0)),
+        0.1,
+        "UNITS: INCHES",
+        TextAlign::Left,
+    );
+}
+
+/// Split a drawing area into a `cols` x `rows` grid; cell (c, r) from top-left
+fn grid_cell(
+    area: (SheetPoint, SheetPoint),
+    cols: usize,
+    rows: usize,
+    c: usize,
+    r: usize,
+) -> (SheetPoint, SheetPoint) {
+    let w = (area.1 .0 - area.0 .0) / cols as f32;
+    let h = (area.1 .1 - area.0 .1) / rows as f32;
+    let x0 = area.0 .0 + w * c as f32;
+    let y1 = area.1 .1 - h * r as f32;
+    ((x0, y1 - h), (x0 + w, y1))
+}
+
+/// Reserve room under a view for its label
+fn labelled(viewport: (SheetPoint, SheetPoint)) -> (SheetPoint, SheetPoint) {
+    ((viewport.0 .0, viewport.0 .1 + 0.45), viewport.1)
+}
+
+fn label_point(viewport: (SheetPoint, SheetPoint)) -> SheetPoint {
+    ((viewport.0 .0 + viewport.1 .0) / 2.0, viewport.0 .1 + 0.25)
+}
+
+fn assembly_sheet(design: &CrateDesign, frame: &Frame) -> DrawingSheet {
+    let mut sheet = DrawingSheet::new(0, "Crate Assembly");
+    let area = frame.area();
+    let parts: Vec<&CratePart> = design.parts.iter().filter(|p| is_structural(p)).collect();
+    let bounds: Vec<BoundingBox> = parts.iter().map(|p| p.bounds).collect();
+    let g = &design.geometry;
+
+    // Front elevation with overall width and height
+    let vp = grid_cell(area, 2, 2, 0, 0);
+    let (front, scale) = View::fit(
+        Projection::Face(PanelType::Front),
+        &bounds,
+        labelled(vp),
+        0.7,
+        false,
+    );
+    sheet.scale = scale.to_string();
+    for p in &parts {
+        sheet.draw_box(part_layer(p), &front, &p.bounds);
+    }
+    let (lo, hi) = projected_rect(&union(&bounds), Projection::Face(PanelType::Front));
+    let (slo, shi) = (front.map2(lo), front.map2(hi));
+    sheet.dim_horizontal(slo.0, shi.0, slo.1, -0.35, hi.0 - lo.0);
+    sheet.dim_vertical(slo.1, shi.1, slo.0, -0.35, hi.1 - lo.1);
+    sheet.view_label(label_point(vp), "FRONT ELEVATION", scale);
+
+    // Side elevation with overall length
+    let vp = grid_cell(area, 2, 2, 1, 0);
+    let (side, scale) = View::fit(
+        Projection::Face(PanelType::Right),
+        &bounds,
+        labelled(vp),
+        0.7,
+        false,
+    );
+    for p in &parts {
+        sheet.draw_box(part_layer(p), &side, &p.bounds);
+    }
+    let (lo, hi) = projected_rect(&union(&bounds), Projection::Face(PanelType::Right));
+    let (slo, shi) = (side.map2(lo), side.map2(hi));
+    sheet.dim_horizontal(slo.0, shi.0, slo.1, -0.35, hi.0 - lo.0);
+    let base_top = side.map2((0.0, g.base_height)).1;
+    sheet.dim_vertical(slo.1, base_top, shi.0, 0.35, g.base_height);
+    sheet.view_label(label_point(vp), "RIGHT SIDE ELEVATION", scale);
+
+    // Plan
+    let vp = grid_cell(area, 2, 2, 0, 1);
+    let (plan, scale) = View::fit(Projection::Plan, &bounds, labelled(vp), 0.5, false);
+    for p in &parts {
+        sheet.draw_box(part_layer(p), &plan, &p.bounds);
+    }
+    sheet.view_label(label_point(vp), "PLAN", scale);
+
+    // Isometric, with notes alongside
+    let vp = grid_cell(area, 2, 2, 1, 1);
+    let (iso, _) = View::fit(Projection::Iso, &bounds, labelled(vp), 0.3, true);
+    for p in &parts {
+        sheet.draw_box(part_layer(p), &iso, &p.bounds);
+    }
+    sheet.view_label(label_point(vp), "ISOMETRIC", "NTS");
+
+    let req = &design.spec.requirements;
+    let notes = [
+        "NOTES:".to_string(),
+        "1. ALL DIMENSIONS IN INCHES.".to_string(),
+        format!(
+            "2. BUILD TO {} ({} STYLE).",
+            req.standard.name().to_uppercase(),
+            req.style.name().to_uppercase()
+        ),
+        format!(
+            "3. PRODUCT {} x {} x {}, {:.0} LBS.",
+            dimension_text(design.spec.product.length),
+            dimension_text(design.spec.product.width),
+            dimension_text(design.spec.product.height),
+            design.spec.product.weight
+        ),
+        format!(
+            "4. OVERALL {} x {} x {}.",
+            dimension_text(g.overall_length),
+            dimension_text(g.overall_width),
+            dimension_text(g.overall_height)
+        ),
+    ];
+    let mut y = frame.title_block.1 .1 - 0.15;
+    for note in &notes {
+        sheet.text((frame.margin + 0.25, y), 0.12, note, TextAlign::Left);
+        y -= 0.2;
+    }
+
+    sheet
+}
+
+fn union(bounds: &[BoundingBox]) -> BoundingBox {
+    let mut lo = Point3::new(f32::MAX, f32::MAX, f32::MAX);
+    let mut hi = Point3::new(f32::MIN, f32::MIN, f32::MIN);
+    for b in bounds {
+        lo = Point3::new(lo.x.min(b.min.x), lo.y.min(b.min.y), lo.z.min(b.min.z));
+        hi = Point3::new(hi.x.max(b.max.x), hi.y.max(b.max.y), hi.z.max(b.max.z));
+    }
+    BoundingBox::new(lo, hi)
+}
+
+fn exploded_sheet(design: &CrateDesign, frame: &Frame, distance: f32) -> DrawingSheet {
+    let mut sheet = DrawingSheet::new(0, "Exploded Assembly");
+    let area = frame.area();
+    let parts: Vec<(&CratePart, BoundingBox)> = design
+        .parts
+        .iter()
+        .filter(|p| is_structural(p))
+        .map(|p| (p, translated(&p.bounds, explode_offset(p, distance))))
+        .collect();
+    let bounds: Vec<BoundingBox> = parts.iter().map(|(_, b)| *b).collect();
+    let (iso, _) = View::fit(Projection::Iso, &bounds, labelled(area), 0.4, true);
+    for (p, b) in &parts {
+        sheet.draw_box(part_layer(p), &iso, b);
+    }
+
+    // Callouts at the centre of each exploded group
+    let groups: [(Option<PanelType>, &str); 6] = [
+        (None, "BASE"),
+        (Some(PanelType::Front), "FRONT PANEL"),
+        (Some(PanelType::Back), "BACK PANEL"),
+        (Some(PanelType::Left), "LEFT PANEL"),
+        (Some(PanelType::Right), "RIGHT PANEL"),
+        (Some(PanelType::Top), "TOP PANEL"),
+    ];
+    for (panel, label) in groups {
+        let members: Vec<BoundingBox> = parts
+            .iter()
+            .filter(|(p, _)| part_panel(p) == panel)
+            .map(|(_, b)| *b)
+            .collect();
+        if members.is_empty() {
+            continue;
+        }
+        let c = union(&members).center();
+        let at = iso.map(c);
+        sheet.text(at, 0.14, label, TextAlign::Center);
+    }
+    sheet.view_label(label_point(area), "EXPLODED ISOMETRIC", "NTS");
+    sheet
+}
+
+fn base_sheet(design: &CrateDesign, frame: &Frame) -> DrawingSheet {
+    let mut sheet = DrawingSheet::new(0, "Base Assembly");
+    let area = frame.area();
+    let parts: Vec<&CratePart> = design.parts.iter().filter(|p| is_base(p)).collect();
+    let bounds: Vec<BoundingBox> = parts.iter().map(|p| p.bounds).collect();
+    if bounds.is_empty() {
+        return sheet;
+    }
+    let all = union(&bounds);
+    let mid_x = area.0 .0 + (area.1 .0 - area.0 .0) * 0.6;
+
+    // Plan with skid centreline chain and overall dimensions
+    let vp = (area.0, (mid_x, area.1 .1));
+    let (plan, scale) = View::fit(Projection::Plan, &bounds, labelled(vp), 0.9, false);
+    sheet.scale = scale.to_string();
+    for p in &parts {
+        sheet.draw_box(part_layer(p), &plan, &p.bounds);
+    }
+    let (lo, hi) = (
+        plan.map2((all.min.x, all.min.y)),
+        plan.map2((all.max.x, all.max.y)),
+    );
+    let mut stations = vec![all.min.x];
+    stations.extend(design.geometry.skids.iter().map(|s| s.bounds.center().x));
+    stations.push(all.max.x);
+    sheet.chain_horizontal(&plan, &stations, lo.1, -0.35);
+    sheet.dim_horizontal(lo.0, hi.0, lo.1, -0.75, all.max.x - all.min.x);
+    sheet.dim_vertical(lo.1, hi.1, lo.0, -0.4, all.max.y - all.min.y);
+    sheet.view_label(label_point(vp), "BASE PLAN", scale);
+
+    // End view (from the front) with base height
+    let vp = ((mid_x, (area.0 .1 + area.1 .1) / 2.0), area.1);
+    let front = Projection::Face(PanelType::Front);
+    let (end, scale) = View::fit(front, &bounds, labelled(vp), 0.6, false);
+    for p in &parts {
+        sheet.draw_box(part_layer(p), &end, &p.bounds);
+    }
+    let (lo, hi) = (
+        end.map2((all.min.x, all.min.z)),
+        end.map2((all.max.x, all.max.z)),
+    );
+    sheet.dim_vertical(lo.1, hi.1, hi.0, 0.35, all.max.z - all.min.z);
+    sheet.view_label(label_point(vp), "BASE END VIEW", scale);
+
+    // Skid side elevation showing forklift notches
+    if let Some(skid) = design.geometry.skids.first() {
+        let vp = (
+            (mid_x, area.0 .1),
+            (area.1 .0, (area.0 .1 + area.1 .1) / 2.0),
+        );
+        let side = Projection::Face(PanelType::Right);
+        let (elev, scale) = View::fit(side, &[skid.bounds], labelled(vp), 0.6, false);
+        sheet.draw_box(DrawingLayer::Lumber, &elev, &skid.bounds);
+        for notch in &skid.notches {
+            sheet.draw_box(DrawingLayer::Outline, &elev, notch);
+        }
+        let (lo, hi) = elev.rect(&skid.bounds);
+        let b = &skid.bounds;
+        let mut stations = vec![b.min.y];
+        for n in &skid.notches {
+            stations.extend([n.min.y, n.max.y]);
+        }
+        stations.push(b.max.y);
+        if skid.notches.is_empty() {
+            sheet.dim_horizontal(lo.0, hi.0, lo.1, -0.35, b.max.y - b.min.y);
+        } else {
+            sheet.chain_horizontal(&elev, &stations, lo.1, -0.35);
+            sheet.dim_horizontal(lo.0, hi.0, lo.1, -0.7, b.max.y - b.min.y);
+        }
+        sheet.dim_vertical(lo.1, hi.1, hi.0, 0.35, b.max.z - b.min.z);
+        let name = format!("SKID ELEVATION ({})", skid.lumber_size.name());
+        sheet.view_label(label_point(vp), &name, scale);
+    }
+    sheet
+}
+
+fn panel_sheet(design: &CrateDesign, frame: &Frame, panel: PanelType) -> DrawingSheet {
+    let mut sheet = DrawingSheet::new(0, &format!("{} Panel", panel.name()));
+    let area = frame.area();
+    let geom = panel_geometry(design, panel);
+    let parts: Vec<&CratePart> = design
+        .parts
+        .iter()
+        .filter(|p| part_panel(p) == Some(panel))
+        .collect();
+    let projection = Projection::Face(panel);
+    let split = area.0 .0 + (area.1 .0 - area.0 .0) * 0.65;
+
+    // Face view, viewed from outside
+    let vp = (area.0, (split, area.1 .1));
+    let (face, scale) = View::fit(projection, &[geom.bounds], labelled(vp), 1.0, false);
+    sheet.scale = scale.to_string();
+    sheet.draw_box(DrawingLayer::Outline, &face, &geom.bounds);
+    for p in &parts {
+        let layer = part_layer(p);
+        match &p.kind {
+            CratePartKind::LagScrew { .. } => {
+                let (lo, hi) = face.rect(&p.bounds);
+                let c = ((lo.0 + hi.0) / 2.0, (lo.1 + hi.1) / 2.0);
+                sheet.circle(layer, c, ((hi.0 - lo.0).min(hi.1 - lo.1) / 2.0).max(0.03));
+            }
+            CratePartKind::Decal { kind, .. } => {
+                let (lo, hi) = face.rect(&p.bounds);
+                sheet.rect(layer, lo, hi);
+                sheet.text(
+                    ((lo.0 + hi.0) / 2.0, (lo.1 + hi.1) / 2.0 - 0.04),
+                    0.08,
+                    kind,
+                    TextAlign::Center,
+                );
+            }
+            _ => sheet.draw_box(layer, &face, &p.bounds),
+        }
+    }
+
+    // Local panel coordinates start at the panel's lower-left corner
+    let (plo, phi) = projected_rect(&geom.bounds, projection);
+    let (slo, shi) = (face.map2(plo), face.map2(phi));
+    let cleats: Vec<(SheetPoint, SheetPoint)> = parts
+        .iter()
+        .filter(|p| matches!(p.kind, CratePartKind::Cleat { .. }))
+        .map(|p| projected_rect(&p.bounds, projection))
+        .collect();
+    let mut x_stations = vec![plo.0, phi.0];
+    let mut y_stations = vec![plo.1, phi.1];
+    for (lo, hi) in &cleats {
+        if hi.1 - lo.1 >= hi.0 - lo.0 {
+            x_stations.push((lo.0 + hi.0) / 2.0);
+        } else {
+            y_stations.push((lo.1 + hi.1) / 2.0);
+        }
+    }
+    for stations in [&mut x_stations, &mut y_stations] {
+        stations.sort_by(|a, b| a.total_cmp(b));
+        stations.dedup_by(|a, b| (*a - *b).abs() < 0.01);
+    }
+    sheet.chain_horizontal(&face, &x_stations, slo.1, -0.35);
+    sheet.dim_horizontal(slo.0, shi.0, slo.1, -0.75, phi.0 - plo.0);
+    sheet.chain_vertical(&face, &y_stations, slo.0, -0.35);
+    sheet.dim_vertical(slo.1, shi.1, slo.0, -0.75, phi.1 - plo.1);
+    let view_name = format!("{} PANEL - OUTSIDE FACE", panel.name().to_uppercase());
+    sheet.view_label(label_point(vp), &view_name, scale);
+
+    // Isometric of the panel and its cleats
+    let right = split + 0.3;
+    let vp = ((right, (area.0 .1 + area.1 .1) / 2.0 + 0.3), area.1);
+    let structural: Vec<&&CratePart> = parts.iter().filter(|p| is_structural(p)).collect();
+    let mut bounds: Vec<BoundingBox> = structural.iter().map(|p| p.bounds).collect();
+    bounds.push(geom.bounds);
+    let (iso, _) = View::fit(Projection::Iso, &bounds, labelled(vp), 0.2, true);
+    sheet.draw_box(DrawingLayer::Outline, &iso, &geom.bounds);
+    for p in &structural {
+        sheet.draw_box(part_layer(p), &iso, &p.bounds);
+    }
+    sheet.view_label(label_point(vp), "ISOMETRIC", "NTS");
+
+    // Hardware location table in panel coordinates
+    let rows: Vec<(String, SheetPoint)> = parts
+        .iter()
+        .filter(|p| {
+            matches!(
+                p.kind,
+                CratePartKind::Klimp { .. }
+                    | CratePartKind::LagScrew { .. }
+                    | CratePartKind::LiftCleat { .. }
+            )
+        })
+        .map(|p| {
+            let (lo, hi) = projected_rect(&p.bounds, projection);
+            let c = ((lo.0 + hi.0) / 2.0 - plo.0, (lo.1 + hi.1) / 2.0 - plo.1);
+            (p.id.clone(), c)
+        })
+        .collect();
+    let (tx, mut ty) = (right, (area.0 .1 + area.1 .1) / 2.0);
+    sheet.text((tx, ty), 0.14, "HARDWARE LOCATIONS", TextAlign::Left);
+    ty -= 0.22;
+    sheet.text((tx, ty), 0.1, "ITEM", TextAlign::Left);
+    sheet.text((tx + 2.6, ty), 0.1, "H", TextAlign::Right);
+    sheet.text((tx + 3.6, ty), 0.1, "V", TextAlign::Right);
+    sheet.line(DrawingLayer::Text, (tx, ty - 0.05), (tx + 3.8, ty - 0.05));
+    let max_rows = ((ty - area.0 .1 - 0.2) / 0.16).max(1.0) as usize;
+    for (i, (id, (u, v))) in rows.iter().enumerate() {
+        ty -= 0.16;
+        if i + 1 == max_rows && rows.len() > max_rows {
+            let more = format!("+ {} MORE", rows.len() - i);
+            sheet.text((tx, ty), 0.1, &more, TextAlign::Left);
+            break;
+        }
+        sheet.text((tx, ty), 0.1, id, TextAlign::Left);
+        sheet.text((tx + 2.6, ty), 0.1, &dimension_text(*u), TextAlign::Right);
+        sheet.text((tx + 3.6, ty), 0.1, &dimension_text(*v), TextAlign::Right);
+    }
+    if rows.is_empty() {
+        sheet.text((tx, ty - 0.16), 0.1, "NONE", TextAlign::Left);
+    }
+
+    sheet
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Output
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Render the drawing set as a multi-page PDF (one page per sheet)
+pub fn drawing_set_to_pdf(set: &DrawingSet) -> Vec<u8> {
+    const PT: f64 = 72.0;
+    let (w, h) = set.sheet_size;
+    let mut pdf = PdfDocument::new(w as f64 * PT, h as f64 * PT);
+    let p = |v: f32| v as f64 * PT;
+
+    for (i, sheet) in set.sheets.iter().enumerate() {
+        if i > 0 {
+            pdf.add_page();
+        }
+        let mut pen = None;
+        for entity in &sheet.entities {
+            let layer = match entity {
+                DrawingEntity::Line { layer, .. }
+                | DrawingEntity::Circle { layer, .. }
+                | DrawingEntity::Text { layer, .. } => *layer,
+            };
+            if pen != Some(layer) {
+                let (width, (r, g, b)) = layer.pen();
+                pdf.set_line_width(width);
+                pdf.set_stroke_color(r, g, b);
+                pdf.set_fill_color(r, g, b);
+                pen = Some(layer);
+            }
+            match entity {
+                DrawingEntity::Line { from, to, .. } => {
+                    pdf.draw_line(p(from.0), p(from.1), p(to.0), p(to.1));
+                }
+                DrawingEntity::Circle { center, radius, .. } => {
+                    const SEGMENTS: usize = 24;
+                    let point = |k: usize| {
+                        let a = k as f32 / SEGMENTS as f32 * std::f32::consts::TAU;
+                        (center.0 + radius * a.cos(), center.1 + radius * a.sin())
+                    };
+                    for k in 0..SEGMENTS {
+                        let (a, b) = (point(k), point(k + 1));
+                        pdf.draw_line(p(a.0), p(a.1), p(b.0), p(b.1));
+                    }
+                }
+                DrawingEntity::Text {
+                    at,
+                    height,
+                    text,
+                    align,
+                    ..
+                } => {
+                    pdf.set_font_size(p(*height));
+                    pdf.draw_text_aligned(p(at.0), p(at.1), text, *align);
+                }
+            }
+        }
+    }
+
+    pdf.to_bytes()
+}
+
+/// Drawing set as a DXF document (inches). Sheets are laid out left to right
+/// with a one-inch gap, one DXF layer per `DrawingLayer`.
+pub fn drawing_set_to_dxf_document(set: &DrawingSet) -> DxfDocument {
+    let mut doc = DxfDocument::new(DxfUnits::Inches);
+    for layer in DrawingLayer::ALL {
+        doc.add_layer(layer.name(), layer.aci_color() as i16);
+    }
+    let pt = |p: SheetPoint, dx: f32| ((p.0 + dx) as f64, p.1 as f64);
+    for (i, sheet) in set.sheets.iter().enumerate() {
+        let dx = i as f32 * (set.sheet_size.0 + 1.0);
+        for entity in &sheet.entities {
+            match entity {
+                DrawingEntity::Line { layer, from, to } => {
+                    doc.add_line(layer.name(), pt(*from, dx), pt(*to, dx));
+                }
+                DrawingEntity::Circle {
+                    layer,
+                    center,
+                    radius,
+                } => doc.add_circle(layer.name(), pt(*center, dx), *radius as f64),
+                DrawingEntity::Text {
+                    layer,
+                    at,
+                    height,
+                    text,
+                    align,
+                } => doc.add_entity(DxfEntity::Text {
+                    layer: layer.name().to_string(),
+                    position: pt(*at, dx),
+                    height: *height as f64,
+                    text: text.clone(),
+                    rotation: 0.0,
+                    align: *align,
+                }),
+            }
+        }
+    }
+    doc
+}
+
+/// Render the drawing set as an ASCII DXF (R12)
+pub fn drawing_set_to_dxf(set: &DrawingSet) -> String {
+    drawing_set_to_dxf_document(set).to_string()
+}
+
+/// Flat 1:1 layout of one panel for the laser cutter / vendors (inches).
+///
+/// Viewed from outside with the origin at the panel's lower-left corner:
+/// PANEL (sheathing outline), CLEATS, DRILL (lag-screw holes), HARDWARE
+/// (klimps), MARKINGS (stencils) and LABELS (cleat ids).
+pub fn panel_layout_to_dxf(design: &CrateDesign, panel: PanelType) -> DxfDocument {
+    let mut doc = DxfDocument::new(DxfUnits::Inches);
+    for (name, color) in [
+        ("PANEL", 7),
+        ("CLEATS", 3),
+        ("DRILL", 1),
+        ("HARDWARE", 5),
+        ("MARKINGS", 6),
+        ("LABELS", 2),
+    ] {
+        doc.add_layer(name, color);
+    }
+
+    let projection = Projection::Face(panel);
+    let geom = panel_geometry(design, panel);
+    let (origin, _) = projected_rect(&geom.bounds, projection);
+    let local = |p: SheetPoint| ((p.0 - origin.0) as f64, (p.1 - origin.1) as f64);
+    let outline = |b: &BoundingBox| {
+        let (lo, hi) = projected_rect(b, projection);
+        let (lo, hi) = (local(lo), local(hi));
+        vec![lo, (hi.0, lo.1), hi, (lo.0, hi.1)]
+    };
+
+    doc.add_polyline("PANEL", outline(&geom.bounds), true);
+    for part in design.parts.iter().filter(|p| part_panel(p) == Some(panel)) {
+        let (lo, hi) = projected_rect(&part.bounds, projection);
+        let centre = local(((lo.0 + hi.0) / 2.0, (lo.1 + hi.1) / 2.0));
+        match &part.kind {
+            CratePartKind::Cleat { .. } | CratePartKind::LiftCleat { .. } => {
+                doc.add_polyline("CLEATS", outline(&part.bounds), true);
+                doc.add_entity(DxfEntity::Text {
+                    layer: "LABELS".to_string(),
+                    position: centre,
+                    height: 0.5,
+                    text: part.id.clone(),
+                    rotation: if hi.1 - lo.1 > hi.0 - lo.0 { 90.0 } else { 0.0 },
+                    align: TextAlign::Center,
+                });
+            }
+            // Clearance hole for the 3/8" lag screw shank
+            CratePartKind::LagScrew { .. } => doc.add_circle("DRILL", centre, 0.1875),
+            CratePartKind::Klimp { .. } => {
+                doc.add_polyline("HARDWARE", outline(&part.bounds), true)
+            }
+            CratePartKind::Decal { .. } => {
+                doc.add_polyline("MARKINGS", outline(&part.bounds), true)
+            }
+            _ => {}
+        }
+    }
+    doc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::autocrate::CrateSpec;
+
+    #[test]
+    fn test_dimension_text_and_scale() {
+        assert_eq!(dimension_text(47.5), "47 1/2\"");
+        assert_eq!(dimension_text(10.03), "10\"");
+        assert_eq!(dimension_text(3.4375), "3 7/16\"");
+        // 100" into 7" of room: 1:16 gives 6.25", 1:12 would not fit
+        assert_eq!(choose_scale((100.0, 40.0), (7.0, 7.0)).1, "1:16");
+        assert_eq!(choose_scale((2000.0, 10.0), (7.0, 7.0)).1, "NTS");
+    }
+
+    #[test]
+    fn test_drawing_set_sheets_and_annotations() {
+        let design = CrateDesign::from_spec(&CrateSpec::default());
+        let set = generate_drawing_set(&design, &DrawingOptions::default());
+
+        assert_eq!(set.sheets.len(), 8);
+        for (i, sheet) in set.sheets.iter().enumerate() {
+            let text = set.sheet_text(i);
+            let page = format!("SHEET {} OF 8", i + 1);
+            assert!(text.contains(&page.as_str()), "{}", sheet.title);
+        }
+
+        // Front panel: overall width dimensioned, one circle per lag screw
+        let front = &set.sheets[3];
+        assert_eq!(front.title, "Front Panel");
+        let width = design.geometry.panels.front.bounds.size().x;
+        assert!(set.sheet_text(3).contains(&dimension_text(width).as_str()));
+        let circles = front
+            .entities
+            .iter()
+            .filter(|e| matches!(e, DrawingEntity::Circle { .. }))
+            .count();
+        let lags = design
+            .parts
+            .iter()
+            .filter(|p| {
+                matches!(
+                    p.kind,
+                    CratePartKind::LagScrew {
+                        panel: PanelType::Front,
+                        ..
+                    }
+                )
+            })
+            .count();
+        assert_eq!(circles, lags);
+
+        // Exploded view labels every panel group
+        let exploded = set.sheet_text(1);
+        for label in ["BASE", "FRONT PANEL", "TOP PANEL"] {
+            assert!(exploded.contains(&label));
+        }
+    }
+
+    #[test]
+    fn test_drawing_outputs() {
+        let design = CrateDesign::from_spec(&CrateSpec::default());
+        let set = generate_drawing_set(&design, &DrawingOptions::default());
+
+        let pdf = drawing_set_to_pdf(&set);
+        assert!(pdf.starts_with(b"%PDF-1.4"));
+        let text = String::from_utf8_lossy(&pdf);
+        assert_eq!(text.matches("/Type /Page ").count(), set.sheets.len());
+
+        let dxf = drawing_set_to_dxf(&set);
+        assert!(dxf.contains("AC1009"));
+        assert!(dxf.contains("DIMENSIONS"));
+        assert!(dxf.contains("LINE") && dxf.contains("CIRCLE") && dxf.contains("TEXT"));
+        assert!(dxf.trim_end().ends_with("EOF"));
+        let back = crate::export::dxf::parse_dxf(&dxf).unwrap();
+        let doc = drawing_set_to_dxf_document(&set);
+        assert_eq!(back.layers(), doc.layers());
+        assert_eq!(back.entities().len(), doc.entities().len());
+
+        // Front panel flat layout: sheathing outline at the origin, one hole per lag screw
+        let layout = panel_layout_to_dxf(&design, PanelType::Front);
+        let size = design.geometry.panels.front.bounds.size();
+        let DxfEntity::Polyline { points, .. } = &layout.entities()[0] else {
+            panic!("expected panel outline");
+        };
+        let (x1, y1) = points[2];
+        assert!(points[0].0.abs() < 1e-3 && points[0].1.abs() < 1e-3);
+        assert!((x1 - size.x as f64).abs() < 1e-3 && (y1 - size.z as f64).abs() < 1e-3);
+        let holes = layout
+            .entities()
+            .iter()
+            .filter(|e| e.layer() == "DRILL")
+            .count();
+        let lags = design
+            .parts
+            .iter()
+            .filter(|p| {
+                matches!(
+                    p.kind,
+                    CratePartKind::LagScrew {
+                        panel: PanelType::Front,
+                        ..
+                    }
+                )
+            })
+            .count();
+        assert_eq!(holes, lags);
+    }
+}
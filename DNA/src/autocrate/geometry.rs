@@ -1,7 +1,7 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: geometry.rs | DNA/src/autocrate/geometry.rs
 //! PURPOSE: Defines Point3, BoundingBox, SkidGeometry types
-//! MODIFIED: 2025-12-09
+//! MODIFIED: 2026-10-18
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
 
@@ -74,6 +74,8 @@ pub struct SkidGeometry {
     pub bounds: BoundingBox,
     pub lumber_size: LumberSize,
     pub index: usize,
+    /// Forklift notches cut from the underside (sub-base runners only)
+    pub notches: Vec<BoundingBox>,
 }
 
 /// Floorboard/board geometry
@@ -134,6 +136,16 @@ pub struct PanelSet {
     pub top: PanelGeometry,
 }
 
+/// Hoisting cleat on the outside of a side panel
+#[derive(Clone, Debug)]
+pub struct LiftCleatGeometry {
+    pub bounds: BoundingBox,
+    pub lumber_size: LumberSize,
+    pub panel: PanelType,
+    /// Sling bearing point (bottom outside edge of the cleat)
+    pub lift_point: Point3,
+}
+
 /// Klimp fastener position
 #[derive(Clone, Debug)]
 pub struct KlimpPosition {
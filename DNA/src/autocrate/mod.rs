@@ -1,20 +1,30 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: mod.rs | DNA/src/autocrate/mod.rs
 //! PURPOSE: Module exports for autocrate
-//! MODIFIED: 2025-12-09
+//! MODIFIED: 2026-10-18
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
 
 pub mod calculator;
 pub mod constants;
+pub mod cutting;
 pub mod design;
+pub mod drawings;
 pub mod geometry;
+pub mod pricing;
 pub mod reports;
+pub mod standards;
+pub mod structural;
 pub mod types;
 
 pub use calculator::*;
 pub use constants::*;
+pub use cutting::*;
 pub use design::*;
+pub use drawings::*;
 pub use geometry::*;
+pub use pricing::*;
 pub use reports::*;
+pub use standards::*;
+pub use structural::*;
 pub use types::*;
@@ -0,0 +1,632 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: pricing.rs | DNA/src/autocrate/pricing.rs
+//! PURPOSE: Supplier price lists, labor estimates and crate quotes (CSV/PDF)
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+//!
+//! Prices what the shop actually buys: stock boards and plywood sheets from
+//! the cutting plan (see `cutting.rs`), plus hardware and decals by SKU.
+//! Labor is estimated per part kind; margin is gross margin on the price.
+//!
+//! Price list CSV (one price per row, `#` comments allowed):
+//!
+//! ```text
+//! kind,key,length_in,price
+//! lumber,2x4,96,4.25        # 8 ft stock board
+//! lumber,2x4,,0.55          # per linear foot (fallback for other lengths)
+//! plywood,0.25,,32.00       # per 48x96 sheet of the given thickness
+//! hardware,KLIMP_FASTENER,,1.85
+//! ```
+//!
+//! The JSON form is the serde representation of `PriceList`.
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use super::constants::LumberSize;
+use super::cutting::{optimize_cutting, CuttingOptions};
+use super::design::{CrateDesign, CratePartKind, PartMaterial};
+use super::reports::csv_escape;
+use crate::export::pdf::{PdfDocument, TextAlign};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Error type for price list loading
+#[derive(Debug, Clone, PartialEq)]
+pub enum PricingError {
+    /// Malformed CSV at a (1-based) line
+    Csv { line: usize, message: String },
+    /// Malformed JSON
+    Json(String),
+}
+
+impl std::fmt::Display for PricingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PricingError::Csv { line, message } => {
+                write!(f, "price list error on line {}: {}", line, message)
+            }
+            PricingError::Json(message) => write!(f, "price list JSON error: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for PricingError {}
+
+/// Lumber price: per stock board of `length` inches, or per linear foot
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LumberPrice {
+    pub size: LumberSize,
+    /// Stock length (inches); `None` = price per linear foot
+    #[serde(default)]
+    pub length: Option<f32>,
+    pub price: f32,
+}
+
+/// Plywood price per sheet
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PlywoodPrice {
+    /// Sheet thickness (inches)
+    pub thickness: f32,
+    pub sheet_price: f32,
+}
+
+/// Hardware or decal price per unit
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HardwarePrice {
+    pub sku: String,
+    pub price: f32,
+}
+
+/// Supplier price list
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PriceList {
+    pub supplier: String,
+    #[serde(default)]
+    pub lumber: Vec<LumberPrice>,
+    #[serde(default)]
+    pub plywood: Vec<PlywoodPrice>,
+    #[serde(default)]
+    pub hardware: Vec<HardwarePrice>,
+}
+
+impl PriceList {
+    pub fn from_json(text: &str) -> Result<Self, PricingError> {
+        serde_json::from_str(text).map_err(|e| PricingError::Json(e.to_string()))
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Parse the `kind,key,length_in,price` CSV format
+    pub fn from_csv(supplier: &str, text: &str) -> Result<Self, PricingError> {
+        let mut list = PriceList {
+            supplier: supplier.to_string(),
+            ..Default::default()
+        };
+
+        for (i, raw) in text.lines().enumerate() {
+            let line = i + 1;
+            let row = raw.split('#').next().unwrap_or("").trim();
+            if row.is_empty() || row.to_ascii_lowercase().starts_with("kind,") {
+                continue;
+            }
+            let err = |message: String| PricingError::Csv { line, message };
+            let fields: Vec<&str> = row.split(',').map(str::trim).collect();
+            if fields.len() != 4 {
+                return Err(err(format!("expected 4 fields, found {}", fields.len())));
+            }
+            let price: f32 = fields[3]
+                .parse()
+                .map_err(|_| err(format!("invalid price '{}'", fields[3])))?;
+            if price < 0.0 {
+                return Err(err(format!("negative price {}", price)));
+            }
+
+            match fields[0].to_ascii_lowercase().as_str() {
+                "lumber" => {
+                    let size = LumberSize::from_name(fields[1])
+                        .ok_or_else(|| err(format!("unknown lumber size '{}'", fields[1])))?;
+                    let length = if fields[2].is_empty() {
+                        None
+                    } else {
+                        Some(
+                            fields[2]
+                                .parse()
+                                .map_err(|_| err(format!("invalid length '{}'", fields[2])))?,
+                        )
+                    };
+                    list.lumber.push(LumberPrice {
+                        size,
+                        length,
+                        price,
+                    });
+                }
+                "plywood" => {
+                    let thickness = fields[1]
+                        .parse()
+                        .map_err(|_| err(format!("invalid thickness '{}'", fields[1])))?;
+                    list.plywood.push(PlywoodPrice {
+                        thickness,
+                        sheet_price: price,
+                    });
+                }
+                "hardware" => list.hardware.push(HardwarePrice {
+                    sku: fields[1].to_string(),
+                    price,
+                }),
+                other => return Err(err(format!("unknown kind '{}'", other))),
+            }
+        }
+
+        Ok(list)
+    }
+
+    /// Price of one stock board: exact stock length, else per-foot price
+    pub fn lumber_price(&self, size: LumberSize, length: f32) -> Option<f32> {
+        let rows = || self.lumber.iter().filter(move |p| p.size == size);
+        rows()
+            .find(|p| p.length.is_some_and(|l| (l - length).abs() < 0.01))
+            .map(|p| p.price)
+            .or_else(|| {
+                rows()
+                    .find(|p| p.length.is_none())
+                    .map(|p| p.price * length / 12.0)
+            })
+    }
+
+    pub fn plywood_price(&self, thickness: f32) -> Option<f32> {
+        self.plywood
+            .iter()
+            .find(|p| (p.thickness - thickness).abs() < 1e-3)
+            .map(|p| p.sheet_price)
+    }
+
+    pub fn hardware_price(&self, sku: &str) -> Option<f32> {
+        self.hardware.iter().find(|p| p.sku == sku).map(|p| p.price)
+    }
+}
+
+/// Labor rates, margin and stock settings for a quote
+#[derive(Clone, Debug)]
+pub struct QuoteOptions {
+    /// Shop labor rate ($/hour)
+    pub hourly_rate: f32,
+    /// Per lumber piece (skids, floorboards, cleats)
+    pub minutes_per_lumber_cut: f32,
+    /// Per plywood piece (panels, panel stops)
+    pub minutes_per_sheet_cut: f32,
+    /// Per klimp / lag screw
+    pub minutes_per_fastener: f32,
+    /// Per stencil or decal
+    pub minutes_per_stencil: f32,
+    /// Fixed assembly and handling time per crate
+    pub assembly_minutes: f32,
+    /// Gross margin as a percentage of the selling price
+    pub margin_pct: f32,
+    pub cutting: CuttingOptions,
+}
+
+impl Default for QuoteOptions {
+    fn default() -> Self {
+        Self {
+            hourly_rate: 55.0,
+            minutes_per_lumber_cut: 1.5,
+            minutes_per_sheet_cut: 4.0,
+            minutes_per_fastener: 0.5,
+            minutes_per_stencil: 3.0,
+            assembly_minutes: 30.0,
+            margin_pct: 25.0,
+            cutting: CuttingOptions::default(),
+        }
+    }
+}
+
+/// Quote section
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QuoteCategory {
+    Material,
+    Labor,
+    Margin,
+}
+
+impl QuoteCategory {
+    pub fn name(&self) -> &'static str {
+        match self {
+            QuoteCategory::Material => "Material",
+            QuoteCategory::Labor => "Labor",
+            QuoteCategory::Margin => "Margin",
+        }
+    }
+}
+
+/// One priced line
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuoteLine {
+    pub category: QuoteCategory,
+    pub description: String,
+    pub quantity: f32,
+    /// "ea" or "hr"
+    pub unit: &'static str,
+    pub unit_price: f32,
+    pub amount: f32,
+}
+
+/// Crate quote
+#[derive(Clone, Debug)]
+pub struct Quote {
+    pub supplier: String,
+    pub lines: Vec<QuoteLine>,
+    /// Items with no price in the list (priced at zero)
+    pub missing_prices: Vec<String>,
+}
+
+impl Quote {
+    fn subtotal(&self, category: QuoteCategory) -> f32 {
+        self.lines
+            .iter()
+            .filter(|l| l.category == category)
+            .map(|l| l.amount)
+            .sum()
+    }
+
+    pub fn material_total(&self) -> f32 {
+        self.subtotal(QuoteCategory::Material)
+    }
+
+    pub fn labor_total(&self) -> f32 {
+        self.subtotal(QuoteCategory::Labor)
+    }
+
+    pub fn margin_total(&self) -> f32 {
+        self.subtotal(QuoteCategory::Margin)
+    }
+
+    pub fn total(&self) -> f32 {
+        self.lines.iter().map(|l| l.amount).sum()
+    }
+}
+
+/// Price `design` against `prices`
+pub fn quote_design(design: &CrateDesign, prices: &PriceList, options: &QuoteOptions) -> Quote {
+    let mut lines = Vec::new();
+    let mut missing = Vec::new();
+    let mut material = |description: String, quantity: u32, unit_price: Option<f32>| {
+        if unit_price.is_none() {
+            missing.push(description.clone());
+        }
+        let unit_price = unit_price.unwrap_or(0.0);
+        lines.push(QuoteLine {
+            category: QuoteCategory::Material,
+            description,
+            quantity: quantity as f32,
+            unit: "ea",
+            unit_price,
+            amount: unit_price * quantity as f32,
+        });
+    };
+
+    // Stock from the cutting plan
+    let plan = optimize_cutting(design, &options.cutting);
+    let mut boards: BTreeMap<(&'static str, u32), (LumberSize, u32)> = BTreeMap::new();
+    for board in &plan.boards {
+        let key = (
+            board.size.name(),
+            (board.stock_length * 100.0).round() as u32,
+        );
+        boards.entry(key).or_insert((board.size, 0)).1 += 1;
+    }
+    for ((name, length), (size, count)) in boards {
+        let length = length as f32 / 100.0;
+        material(
+            format!("Lumber {} x {:.0}\"", name, length),
+            count,
+            prices.lumber_price(size, length),
+        );
+    }
+    let mut sheets: BTreeMap<u32, u32> = BTreeMap::new();
+    for sheet in &plan.sheets {
+        *sheets
+            .entry((sheet.thickness * 1000.0).round() as u32)
+            .or_insert(0) += 1;
+    }
+    let (sheet_w, sheet_l) = options.cutting.sheet_size;
+    for (thickness, count) in sheets {
+        let thickness = thickness as f32 / 1000.0;
+        material(
+            format!("Plywood {:.0}x{:.0} x {:.3}\"", sheet_w, sheet_l, thickness),
+            count,
+            prices.plywood_price(thickness),
+        );
+    }
+    // Hardware and decals by SKU, labor counts by part kind
+    let mut skus: BTreeMap<&str, u32> = BTreeMap::new();
+    let (mut lumber_cuts, mut sheet_cuts, mut fasteners, mut stencils) = (0u32, 0u32, 0u32, 0u32);
+    for part in &design.parts {
+        match &part.material {
+            PartMaterial::Hardware { sku } | PartMaterial::Decal { sku, .. } => {
+                *skus.entry(sku.as_str()).or_insert(0) += 1;
+            }
+            _ => {}
+        }
+        match part.kind {
+            CratePartKind::Skid { .. }
+            | CratePartKind::Floorboard { .. }
+            | CratePartKind::Cleat { .. }
+            | CratePartKind::LiftCleat { .. } => lumber_cuts += 1,
+            CratePartKind::Panel { .. } | CratePartKind::PanelStop { .. } => sheet_cuts += 1,
+            CratePartKind::Klimp { .. } | CratePartKind::LagScrew { .. } => fasteners += 1,
+            CratePartKind::Decal { .. } => stencils += 1,
+        }
+    }
+    for (sku, count) in skus {
+        material(sku.to_string(), count, prices.hardware_price(sku));
+    }
+
+    let rate = options.hourly_rate;
+    let mut labor = |description: &str, count: u32, minutes_each: f32| {
+        let hours = count as f32 * minutes_each / 60.0;
+        if hours <= 0.0 {
+            return;
+        }
+        lines.push(QuoteLine {
+            category: QuoteCategory::Labor,
+            description: format!("{} ({} x {:.1} min)", description, count, minutes_each),
+            quantity: hours,
+            unit: "hr",
+            unit_price: rate,
+            amount: hours * rate,
+        });
+    };
+    labor("Lumber cuts", lumber_cuts, options.minutes_per_lumber_cut);
+    labor("Plywood cuts", sheet_cuts, options.minutes_per_sheet_cut);
+    labor("Fasteners", fasteners, options.minutes_per_fastener);
+    labor("Stencils and decals", stencils, options.minutes_per_stencil);
+    labor("Assembly", 1, options.assembly_minutes);
+
+    // Gross margin: price = cost / (1 - m)
+    let cost: f32 = lines.iter().map(|l| l.amount).sum();
+    let m = (options.margin_pct / 100.0).clamp(0.0, 0.95);
+    if m > 0.0 {
+        let amount = cost * m / (1.0 - m);
+        lines.push(QuoteLine {
+            category: QuoteCategory::Margin,
+            description: format!("Margin ({:.1}% of price)", options.margin_pct),
+            quantity: 1.0,
+            unit: "ea",
+            unit_price: amount,
+            amount,
+        });
+    }
+
+    for piece in &plan.unplaced {
+        missing.push(format!("{} (longer than stock, not priced)", piece));
+    }
+
+    Quote {
+        supplier: prices.supplier.clone(),
+        lines,
+        missing_prices: missing,
+    }
+}
+
+/// Quote as CSV (lines, then subtotals and total)
+pub fn quote_to_csv(quote: &Quote) -> String {
+    let mut out = String::new();
+    out.push_str("category,description,quantity,unit,unit_price,amount\n");
+    for l in &quote.lines {
+        out.push_str(&format!(
+            "{},{},{:.2},{},{:.2},{:.2}\n",
+            l.category.name(),
+            csv_escape(&l.description),
+            l.quantity,
+            l.unit,
+            l.unit_price,
+            l.amount
+        ));
+    }
+    for (label, amount) in [
+        ("Material subtotal", quote.material_total()),
+        ("Labor subtotal", quote.labor_total()),
+        ("Total", quote.total()),
+    ] {
+        out.push_str(&format!("Total,{},,,,{:.2}\n", label, amount));
+    }
+    out
+}
+
+/// Quote as a one-or-more page PDF
+pub fn quote_to_pdf(quote: &Quote, title: &str) -> Vec<u8> {
+    let mut pdf = PdfDocument::default();
+    let (page_w, page_h) = (pdf.page_width, pdf.page_height);
+    let margin = 50.0;
+
+    pdf.set_font_size(20.0);
+    pdf.set_fill_color(0.0, 0.0, 0.0);
+    pdf.draw_text_aligned(page_w / 2.0, page_h - margin, title, TextAlign::Center);
+    pdf.set_font_size(10.0);
+    pdf.set_fill_color(0.4, 0.4, 0.4);
+    pdf.draw_text_aligned(
+        page_w / 2.0,
+        page_h - margin - 18.0,
+        &format!("Prices: {}", quote.supplier),
+        TextAlign::Center,
+    );
+
+    let columns = [margin, 330.0, 390.0, 430.0, 500.0];
+    let header = |pdf: &mut PdfDocument, y: f64| {
+        pdf.set_fill_color(0.0, 0.0, 0.0);
+        pdf.set_font_size(10.0);
+        for (x, text) in columns
+            .iter()
+            .zip(["Description", "Qty", "Unit", "Price", "Amount"])
+        {
+            pdf.draw_text(*x, y, text);
+        }
+        pdf.set_line_width(0.5);
+        pdf.draw_line(margin, y - 4.0, page_w - margin, y - 4.0);
+        pdf.set_font_size(9.0);
+    };
+
+    let mut y = page_h - margin - 50.0;
+    header(&mut pdf, y);
+    y -= 18.0;
+    let mut section = None;
+    for line in &quote.lines {
+        if y < margin + 60.0 {
+            pdf.add_page();
+            y = page_h - margin;
+            header(&mut pdf, y);
+            y -= 18.0;
+        }
+        if section != Some(line.category) {
+            section = Some(line.category);
+            pdf.set_fill_color(0.0, 0.5, 0.4);
+            pdf.draw_text(margin, y, line.category.name());
+            pdf.set_fill_color(0.0, 0.0, 0.0);
+            y -= 14.0;
+        }
+        pdf.draw_text(margin + 10.0, y, &line.description);
+        pdf.draw_text(columns[1], y, &format!("{:.2}", line.quantity));
+        pdf.draw_text(columns[2], y, line.unit);
+        pdf.draw_text(columns[3], y, &format!("{:.2}", line.unit_price));
+        pdf.draw_text(columns[4], y, &format!("{:.2}", line.amount));
+        y -= 13.0;
+    }
+
+    pdf.draw_line(margin, y + 4.0, page_w - margin, y + 4.0);
+    y -= 10.0;
+    pdf.set_font_size(10.0);
+    for (label, amount) in [
+        ("Material", quote.material_total()),
+        ("Labor", quote.labor_total()),
+        ("Margin", quote.margin_total()),
+        ("Total", quote.total()),
+    ] {
+        pdf.draw_text(columns[3] - 40.0, y, label);
+        pdf.draw_text(columns[4], y, &format!("{:.2}", amount));
+        y -= 14.0;
+    }
+
+    if !quote.missing_prices.is_empty() {
+        y -= 10.0;
+        pdf.set_fill_color(0.7, 0.1, 0.1);
+        pdf.set_font_size(9.0);
+        pdf.draw_text(
+            margin,
+            y,
+            &format!("No price for: {}", quote.missing_prices.join(", ")),
+        );
+    }
+
+    pdf.to_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::autocrate::CrateSpec;
+
+    const PRICES: &str = "\
+kind,key,length_in,price
+# Lumber: stock boards, plus per-foot fallbacks
+lumber,1x4,96,3.10
+lumber,1x4,,0.45
+lumber,2x6,,0.95
+lumber,4x4,,1.80
+plywood,0.375,,38.50
+plywood,1.0,,72.00
+";
+
+    fn complete_prices(design: &CrateDesign) -> PriceList {
+        let mut prices = PriceList::from_csv("Test Lumber Co", PRICES).unwrap();
+        for part in &design.parts {
+            if let PartMaterial::Hardware { sku } | PartMaterial::Decal { sku, .. } = &part.material
+            {
+                if prices.hardware_price(sku).is_none() {
+                    prices.hardware.push(HardwarePrice {
+                        sku: sku.clone(),
+                        price: 2.0,
+                    });
+                }
+            }
+        }
+        prices
+    }
+
+    #[test]
+    fn test_price_list_formats() {
+        let prices = PriceList::from_csv("Test Lumber Co", PRICES).unwrap();
+        assert_eq!(prices.lumber.len(), 4);
+        assert_eq!(prices.lumber_price(LumberSize::L1x4, 96.0), Some(3.10));
+        // Other lengths fall back to the per-foot price
+        assert!((prices.lumber_price(LumberSize::L1x4, 120.0).unwrap() - 4.5).abs() < 1e-4);
+        assert_eq!(prices.lumber_price(LumberSize::L2x4, 96.0), None);
+        assert_eq!(prices.plywood_price(0.375), Some(38.5));
+
+        let json = prices.to_json();
+        assert_eq!(PriceList::from_json(&json).unwrap(), prices);
+
+        let err = PriceList::from_csv("x", "lumber,2x4,96,4.00\nlumber,9x9,,1.0\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "price list error on line 2: unknown lumber size '9x9'"
+        );
+        assert!(matches!(
+            PriceList::from_csv("x", "plywood,0.5,,abc"),
+            Err(PricingError::Csv { line: 1, .. })
+        ));
+        assert!(PriceList::from_json("{").is_err());
+    }
+
+    #[test]
+    fn test_quote_totals_and_margin() {
+        let design = CrateDesign::from_spec(&CrateSpec::default());
+        let prices = complete_prices(&design);
+        let options = QuoteOptions::default();
+        let quote = quote_design(&design, &prices, &options);
+
+        assert!(
+            quote.missing_prices.is_empty(),
+            "{:?}",
+            quote.missing_prices
+        );
+        assert!(quote.material_total() > 0.0 && quote.labor_total() > 0.0);
+        let total = quote.total();
+        assert!(
+            (quote.material_total() + quote.labor_total() + quote.margin_total() - total).abs()
+                < 1e-3
+        );
+        // Gross margin is 25% of the selling price
+        assert!((quote.margin_total() / total - 0.25).abs() < 1e-4);
+        // Assembly alone is 30 min at $55/hr
+        assert!(quote
+            .lines
+            .iter()
+            .any(|l| l.description.starts_with("Assembly") && (l.amount - 27.5).abs() < 1e-3));
+
+        let csv = quote_to_csv(&quote);
+        assert!(csv.starts_with("category,description,quantity,unit,unit_price,amount\n"));
+        assert!(csv.contains(&format!("Total,Total,,,,{:.2}", total)));
+        assert!(quote_to_pdf(&quote, "Crate Quote").starts_with(b"%PDF-1.4"));
+    }
+
+    #[test]
+    fn test_missing_prices_are_reported() {
+        let design = CrateDesign::from_spec(&CrateSpec::default());
+        let prices =
+            PriceList::from_csv("Partial", "plywood,0.375,,38.50\nplywood,1.0,,72.00\n").unwrap();
+        let quote = quote_design(&design, &prices, &QuoteOptions::default());
+
+        assert!(quote
+            .missing_prices
+            .iter()
+            .any(|m| m.starts_with("Lumber 4x4")));
+        assert!(quote.missing_prices.iter().any(|m| m == "KLIMP_FASTENER"));
+        assert!(!quote
+            .missing_prices
+            .iter()
+            .any(|m| m.starts_with("Plywood")));
+    }
+}
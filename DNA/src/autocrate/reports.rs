@@ -1,7 +1,7 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: reports.rs | DNA/src/autocrate/reports.rs
 //! PURPOSE: BOM + Cut List generation (CSV) from canonical `CrateDesign`
-//! MODIFIED: 2025-12-11
+//! MODIFIED: 2026-10-18
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
 
@@ -29,7 +29,7 @@ pub struct CutListRow {
     pub note: Option<String>,
 }
 
-fn csv_escape(value: &str) -> String {
+pub(crate) fn csv_escape(value: &str) -> String {
     if value.contains(',') || value.contains('"') || value.contains('\n') || value.contains('\r') {
         format!("\"{}\"", value.replace('"', "\"\""))
     } else {
@@ -41,12 +41,12 @@ fn round2(v: f32) -> f32 {
     (v * 100.0).round() / 100.0
 }
 
-fn bbox_dims(desc: &super::geometry::BoundingBox) -> (f32, f32, f32) {
+pub(crate) fn bbox_dims(desc: &super::geometry::BoundingBox) -> (f32, f32, f32) {
     let s = desc.size();
     (s.x.abs(), s.y.abs(), s.z.abs())
 }
 
-fn face_dims_for_sheet_part(desc: &super::geometry::BoundingBox) -> (f32, f32) {
+pub(crate) fn face_dims_for_sheet_part(desc: &super::geometry::BoundingBox) -> (f32, f32) {
     // Treat the two largest dimensions as the "cut face" dimensions.
     let (a, b, c) = bbox_dims(desc);
     let mut dims = [a, b, c];
@@ -111,6 +111,7 @@ pub fn generate_bom(design: &CrateDesign) -> Vec<BomRow> {
                     CratePartKind::Skid { .. } => "Skid",
                     CratePartKind::Floorboard { .. } => "Floorboard",
                     CratePartKind::Cleat { .. } => "Cleat",
+                    CratePartKind::LiftCleat { .. } => "Lift Cleat",
                     _ => "Lumber",
                 }
                 .to_string();
@@ -192,6 +193,7 @@ pub fn generate_cut_list(design: &CrateDesign) -> Vec<CutListRow> {
                     CratePartKind::Skid { .. } => "Skid",
                     CratePartKind::Floorboard { .. } => "Floorboard",
                     CratePartKind::Cleat { .. } => "Cleat",
+                    CratePartKind::LiftCleat { .. } => "Lift Cleat",
                     _ => "Lumber",
                 }
                 .to_string();
@@ -339,6 +341,18 @@ mod tests {
         assert!(bom_csv.contains("FRAGILE_STENCIL"));
         assert!(bom_csv.contains("HANDLING_SYMBOLS"));
     }
+
+    #[test]
+    fn open_crate_with_lift_cleats_has_no_panel_sheathing() {
+        let mut spec = CrateSpec::default();
+        spec.requirements.style = crate::autocrate::CrateStyle::Open;
+        spec.requirements.lift_cleats = true;
+        let design = CrateDesign::from_spec(&spec);
+
+        let cut_csv = cut_list_to_csv(&generate_cut_list(&design));
+        assert!(!cut_csv.contains("Panel Front"));
+        assert!(bom_to_csv(&generate_bom(&design)).contains("Lift Cleat"));
+    }
 }
 
 
@@ -0,0 +1,477 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: standards.rs | DNA/src/autocrate/standards.rs
+//! PURPOSE: Standard/style profiles (ASTM D6039, MIL-C-104, ASTM D6251) and member sizing rules
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+//!
+//! Standards are modeled as parameterized profiles (rules + limits), not as
+//! copied text. `RequirementsSpec` selects the profile; the calculator reads
+//! the geometric rules (cleat spacing, sheathing, base, lift cleats) and
+//! `apply_standard` sizes members from the product weight. With
+//! `RequirementsSpec::apply_standard` set, `effective_spec` resizes before
+//! the calculator, design, structural and requirement checks run.
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ PROFILES                                                                    │
+//! ├──────────────┬────────────┬──────────┬──────────────┬───────────────────────┤
+//! │ Standard     │ Style      │ Max (lb) │ Cleat o.c.   │ Min sheathing         │
+//! ├──────────────┼────────────┼──────────┼──────────────┼───────────────────────┤
+//! │ ASTM D6039   │ Sheathed   │ 30000    │ 24"          │ 1/4"                  │
+//! │ ASTM D6039   │ Open       │ 20000    │ 24"          │ -                     │
+//! │ MIL-C-104    │ Sheathed   │ 30000    │ 24"          │ 3/8"                  │
+//! │ MIL-C-104    │ Open       │ 10000    │ 20"          │ -                     │
+//! │ MIL-C-104    │ Nailed box │ 1000     │ 20"          │ 1/2"                  │
+//! │ ASTM D6251   │ Sheathed   │ 1000     │ 24"          │ 1/4"                  │
+//! │ ASTM D6251   │ Nailed box │ 500      │ 18"          │ 3/8"                  │
+//! └──────────────┴────────────┴──────────┴──────────────┴───────────────────────┘
+//!
+//! Combinations not listed fall back to the style's generic rules and are
+//! reported by `check_requirements`.
+//!
+//! Sub-bases size runners instead of skids: one runner per 30" of product
+//! width plus one, 4x4 (3x4 when allowed) up to 2500 lbs and 4x6 above, with
+//! 9" forklift notches (12" above 2500 lbs) cut at most half the runner deep.
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use std::borrow::Cow;
+
+use super::constants::{self, LumberSize};
+use super::types::{BaseStyle, CrateSpec, CrateStandard, CrateStyle, RequirementsSpec};
+
+/// Rules and limits of a (standard, style) pair
+#[derive(Clone, Debug, PartialEq)]
+pub struct StandardProfile {
+    pub standard: CrateStandard,
+    pub style: CrateStyle,
+    /// Whether the standard covers the style
+    pub covered: bool,
+    /// Heaviest product the profile covers (lbs)
+    pub max_weight: f32,
+    /// Maximum center spacing of intermediate cleats (inches)
+    pub max_cleat_spacing: f32,
+    /// Wall and top panels carry plywood sheathing
+    pub sheathed: bool,
+    /// Only the end (front/back) panels are cleated
+    pub end_cleats_only: bool,
+    /// Thinnest allowed plywood sheathing (inches, 0 when unsheathed)
+    pub min_sheathing: f32,
+}
+
+impl StandardProfile {
+    /// Profile selected by the requirements
+    pub fn for_requirements(requirements: &RequirementsSpec) -> Self {
+        Self::lookup(requirements.standard, requirements.style)
+    }
+
+    pub fn lookup(standard: CrateStandard, style: CrateStyle) -> Self {
+        use CrateStandard::*;
+        use CrateStyle::*;
+
+        let (covered, max_weight, max_cleat_spacing, min_sheathing) = match (standard, style) {
+            (AstmD6039, Sheathed) => (true, 30000.0, 24.0, 0.25),
+            (AstmD6039, Open) => (true, 20000.0, 24.0, 0.0),
+            (MilC104, Sheathed) => (true, 30000.0, 24.0, 0.375),
+            (MilC104, Open) => (true, 10000.0, 20.0, 0.0),
+            (MilC104, NailedBox) => (true, 1000.0, 20.0, 0.5),
+            (AstmD6251, Sheathed) => (true, 1000.0, 24.0, 0.25),
+            (AstmD6251, NailedBox) => (true, 500.0, 18.0, 0.375),
+            // Not covered: generic rules for the style
+            (_, Open) => (false, 10000.0, 24.0, 0.0),
+            (_, NailedBox) => (false, 500.0, 18.0, 0.375),
+        };
+
+        Self {
+            standard,
+            style,
+            covered,
+            max_weight,
+            max_cleat_spacing,
+            sheathed: style != Open,
+            end_cleats_only: style == NailedBox,
+            min_sheathing,
+        }
+    }
+}
+
+/// Member sizes required by a profile for a product weight
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MemberSizing {
+    pub skid_count: u8,
+    pub skid_size: LumberSize,
+    pub floorboard_size: LumberSize,
+    pub cleat_size: LumberSize,
+    pub lift_cleat_size: LumberSize,
+    /// Sub-base runners (used in place of skids when the base is a sub-base)
+    pub runner_count: u8,
+    pub runner_size: LumberSize,
+    /// Forklift notch length along the runner and depth (inches)
+    pub notch_length: f32,
+    pub notch_depth: f32,
+}
+
+/// Member sizes for `spec` under its standard's sizing rules
+pub fn size_members(spec: &CrateSpec) -> MemberSizing {
+    let requirements = &spec.requirements;
+    let weight = spec.product.weight;
+    let light_skid = if spec.materials.allow_3x4_lumber {
+        LumberSize::L3x4
+    } else {
+        LumberSize::L4x4
+    };
+
+    let skid_count = match requirements.standard {
+        CrateStandard::AstmD6251 => 2,
+        _ => constants::skid::recommended_count(weight),
+    };
+
+    let skid_size = match requirements.standard {
+        CrateStandard::AstmD6039 => {
+            if constants::skid::is_lightweight(weight) {
+                light_skid
+            } else if weight <= 20000.0 {
+                LumberSize::L4x6
+            } else {
+                LumberSize::L6x6
+            }
+        }
+        CrateStandard::MilC104 => {
+            if weight <= 2000.0 {
+                LumberSize::L4x4
+            } else if weight <= 10000.0 {
+                LumberSize::L4x6
+            } else if weight <= 20000.0 {
+                LumberSize::L6x6
+            } else {
+                LumberSize::L8x8
+            }
+        }
+        CrateStandard::AstmD6251 => light_skid,
+    };
+
+    let floorboard_rule = if weight <= 4500.0 {
+        LumberSize::L2x6
+    } else if weight <= 15000.0 {
+        LumberSize::L2x8
+    } else {
+        LumberSize::L2x10
+    };
+    let floorboard_size = match &spec.materials.available_floorboard_sizes {
+        Some(available) if !available.is_empty() => {
+            // Smallest available board at least as strong as the rule,
+            // else the strongest one available
+            let needed = section_modulus(floorboard_rule);
+            available
+                .iter()
+                .copied()
+                .filter(|s| section_modulus(*s) >= needed)
+                .min_by(|a, b| section_modulus(*a).total_cmp(&section_modulus(*b)))
+                .or_else(|| {
+                    available
+                        .iter()
+                        .copied()
+                        .max_by(|a, b| section_modulus(*a).total_cmp(&section_modulus(*b)))
+                })
+                .unwrap_or(floorboard_rule)
+        }
+        _ => floorboard_rule,
+    };
+
+    let cleat_size = match (requirements.standard, requirements.style) {
+        (CrateStandard::AstmD6251, _) | (_, CrateStyle::NailedBox) => LumberSize::L1x4,
+        (CrateStandard::MilC104, _) | (_, CrateStyle::Open) => {
+            if weight <= 10000.0 {
+                LumberSize::L2x4
+            } else {
+                LumberSize::L2x6
+            }
+        }
+        (CrateStandard::AstmD6039, CrateStyle::Sheathed) => {
+            if weight <= 2500.0 {
+                LumberSize::L1x4
+            } else if weight <= 10000.0 {
+                LumberSize::L2x4
+            } else {
+                LumberSize::L2x6
+            }
+        }
+    };
+
+    let runner_size = if weight <= constants::sub_base::HEAVY_RUNNER_WEIGHT {
+        light_skid
+    } else {
+        LumberSize::L4x6
+    };
+    let (notch_length, notch_depth) = notch_size(weight, runner_size);
+
+    MemberSizing {
+        skid_count,
+        skid_size,
+        floorboard_size,
+        cleat_size,
+        lift_cleat_size: lift_cleat_size(weight),
+        runner_count: constants::sub_base::runner_count(spec.product.width, weight),
+        runner_size,
+        notch_length,
+        notch_depth,
+    }
+}
+
+/// Forklift notch (length, depth) in a sub-base runner for a product weight
+pub fn notch_size(weight: f32, runner: LumberSize) -> (f32, f32) {
+    let length = if weight <= constants::sub_base::HEAVY_RUNNER_WEIGHT {
+        constants::sub_base::NOTCH_LENGTH
+    } else {
+        constants::sub_base::HEAVY_NOTCH_LENGTH
+    };
+    let depth = constants::sub_base::NOTCH_DEPTH.min(runner.actual().0 / 2.0);
+    (length, depth)
+}
+
+/// Hoisting cleat lumber for a product weight
+pub fn lift_cleat_size(weight: f32) -> LumberSize {
+    if weight <= 2000.0 {
+        LumberSize::L2x4
+    } else if weight <= 8000.0 {
+        LumberSize::L2x6
+    } else {
+        LumberSize::L4x4
+    }
+}
+
+/// Resize members of `spec` to its standard's rules (and raise plywood to
+/// the minimum sheathing thickness)
+pub fn apply_standard(spec: &mut CrateSpec) {
+    let sizing = size_members(spec);
+    if spec.requirements.base == BaseStyle::SubBase {
+        spec.skid_count = sizing.runner_count;
+        spec.skid_size = sizing.runner_size;
+    } else {
+        spec.skid_count = sizing.skid_count;
+        spec.skid_size = sizing.skid_size;
+    }
+    spec.floorboard_size = sizing.floorboard_size;
+    spec.cleat_size = sizing.cleat_size;
+
+    let profile = StandardProfile::for_requirements(&spec.requirements);
+    if profile.sheathed {
+        spec.materials.plywood_thickness =
+            spec.materials.plywood_thickness.max(profile.min_sheathing);
+    }
+}
+
+/// The spec that is actually built: resized by `apply_standard` when the
+/// requirements opt in, unchanged otherwise
+pub fn effective_spec(spec: &CrateSpec) -> Cow<'_, CrateSpec> {
+    if spec.requirements.apply_standard {
+        let mut resized = spec.clone();
+        apply_standard(&mut resized);
+        Cow::Owned(resized)
+    } else {
+        Cow::Borrowed(spec)
+    }
+}
+
+/// Ways `spec` falls short of its selected standard (empty = compliant)
+pub fn check_requirements(spec: &CrateSpec) -> Vec<String> {
+    let spec = &*effective_spec(spec);
+    let requirements = &spec.requirements;
+    let profile = StandardProfile::for_requirements(requirements);
+    let weight = spec.product.weight;
+    let mut issues = Vec::new();
+
+    if !profile.covered {
+        issues.push(format!(
+            "{} does not cover {} construction",
+            profile.standard.name(),
+            profile.style.name()
+        ));
+    }
+    if weight > profile.max_weight {
+        issues.push(format!(
+            "Product weight {:.0} lbs exceeds {} {} limit of {:.0} lbs",
+            weight,
+            profile.standard.name(),
+            profile.style.name(),
+            profile.max_weight
+        ));
+    }
+    if requirements.base == BaseStyle::SubBase && weight > constants::sub_base::MAX_WEIGHT {
+        issues.push(format!(
+            "Sub-base limited to {:.0} lbs; use skids for {:.0} lbs",
+            constants::sub_base::MAX_WEIGHT,
+            weight
+        ));
+    }
+
+    let sizing = size_members(spec);
+    let (base_member, base_count, base_size) = if requirements.base == BaseStyle::SubBase {
+        ("Runner", sizing.runner_count, sizing.runner_size)
+    } else {
+        ("Skid", sizing.skid_count, sizing.skid_size)
+    };
+    let mut undersized = |member: &str, actual: LumberSize, required: LumberSize| {
+        if section_modulus(actual) < section_modulus(required) - 1e-3 {
+            issues.push(format!(
+                "{} {} smaller than required {}",
+                member,
+                actual.name(),
+                required.name()
+            ));
+        }
+    };
+    undersized(base_member, spec.skid_size, base_size);
+    undersized("Floorboard", spec.floorboard_size, sizing.floorboard_size);
+    undersized("Cleat", spec.cleat_size, sizing.cleat_size);
+    if spec.skid_count < base_count {
+        issues.push(format!(
+            "{} {}s fewer than required {}",
+            spec.skid_count,
+            base_member.to_lowercase(),
+            base_count
+        ));
+    }
+
+    if profile.sheathed && spec.materials.plywood_thickness < profile.min_sheathing - 1e-4 {
+        issues.push(format!(
+            "Plywood {:.3}\" thinner than {} minimum {:.3}\"",
+            spec.materials.plywood_thickness,
+            profile.standard.name(),
+            profile.min_sheathing
+        ));
+    }
+
+    issues
+}
+
+/// Section modulus of a lumber size as installed, width x height² / 6 (in³)
+fn section_modulus(size: LumberSize) -> f32 {
+    let (h, w) = size.actual();
+    w * h * h / 6.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::autocrate::calculator::calculate_crate;
+    use crate::autocrate::design::CrateDesign;
+
+    #[test]
+    fn test_profiles_per_standard() {
+        let d6039 = StandardProfile::lookup(CrateStandard::AstmD6039, CrateStyle::Sheathed);
+        assert!(d6039.covered && d6039.sheathed && !d6039.end_cleats_only);
+        assert_eq!(
+            d6039.max_cleat_spacing,
+            constants::cleat::MAX_VERTICAL_SPACING
+        );
+
+        let open = StandardProfile::lookup(CrateStandard::MilC104, CrateStyle::Open);
+        assert!(open.covered && !open.sheathed);
+        assert_eq!(open.max_cleat_spacing, 20.0);
+
+        let boxed = StandardProfile::lookup(CrateStandard::AstmD6251, CrateStyle::NailedBox);
+        assert!(boxed.end_cleats_only && boxed.sheathed);
+
+        assert!(!StandardProfile::lookup(CrateStandard::AstmD6251, CrateStyle::Open).covered);
+    }
+
+    #[test]
+    fn test_sizing_rules_and_compliance() {
+        let mut spec = CrateSpec::default();
+        // The default spec already satisfies D6039 for a 500 lb product
+        assert!(
+            check_requirements(&spec).is_empty(),
+            "{:?}",
+            check_requirements(&spec)
+        );
+
+        spec.product.weight = 12000.0;
+        spec.requirements.standard = CrateStandard::MilC104;
+        let issues = check_requirements(&spec);
+        assert!(issues.iter().any(|i| i.starts_with("Skid 4x4")));
+        assert!(issues.iter().any(|i| i.starts_with("Plywood")));
+
+        apply_standard(&mut spec);
+        assert_eq!(spec.skid_size, LumberSize::L6x6);
+        assert_eq!(spec.skid_count, 4);
+        assert_eq!(spec.floorboard_size, LumberSize::L2x8);
+        assert_eq!(spec.cleat_size, LumberSize::L2x6);
+        assert_eq!(spec.materials.plywood_thickness, 0.375);
+        assert!(check_requirements(&spec).is_empty());
+
+        // Light box standard: weight and style limits
+        spec.requirements.standard = CrateStandard::AstmD6251;
+        spec.requirements.style = CrateStyle::Open;
+        spec.requirements.base = BaseStyle::SubBase;
+        let issues = check_requirements(&spec);
+        assert!(issues.iter().any(|i| i.contains("does not cover Open")));
+        assert!(issues.iter().any(|i| i.contains("exceeds ASTM D6251")));
+        assert!(issues.iter().any(|i| i.starts_with("Sub-base")));
+
+        spec.materials.available_floorboard_sizes = Some(vec![LumberSize::L2x4, LumberSize::L2x12]);
+        spec.product.weight = 5000.0;
+        assert_eq!(size_members(&spec).floorboard_size, LumberSize::L2x12);
+        assert_eq!(size_members(&spec).lift_cleat_size, LumberSize::L2x6);
+    }
+
+    #[test]
+    fn test_sub_base_runner_and_notch_sizing() {
+        let mut spec = CrateSpec::default();
+        spec.requirements.base = BaseStyle::SubBase;
+
+        // 36" wide, 500 lbs: three light runners, standard notches
+        let sizing = size_members(&spec);
+        assert_eq!(sizing.runner_count, 3);
+        assert_eq!(sizing.runner_size, LumberSize::L4x4);
+        assert_eq!(
+            (sizing.notch_length, sizing.notch_depth),
+            (
+                constants::sub_base::NOTCH_LENGTH,
+                constants::sub_base::NOTCH_DEPTH
+            )
+        );
+        assert!(check_requirements(&spec).is_empty());
+
+        // Heavy and wide: 4x6 runners, longer notches, and 2x4s are too small
+        spec.product.width = 72.0;
+        spec.product.weight = 4000.0;
+        let sizing = size_members(&spec);
+        assert_eq!(sizing.runner_count, 4);
+        assert_eq!(sizing.runner_size, LumberSize::L4x6);
+        assert_eq!(sizing.notch_length, constants::sub_base::HEAVY_NOTCH_LENGTH);
+        let issues = check_requirements(&spec);
+        assert!(issues.iter().any(|i| i.starts_with("Runner 4x4")));
+        assert!(issues
+            .iter()
+            .any(|i| i == "3 runners fewer than required 4"));
+
+        // Notches never take more than half a shallow runner
+        assert_eq!(notch_size(500.0, LumberSize::L2x4).1, 0.75);
+
+        apply_standard(&mut spec);
+        assert_eq!((spec.skid_count, spec.skid_size), (4, LumberSize::L4x6));
+        assert!(check_requirements(&spec).is_empty());
+        let geom = calculate_crate(&spec);
+        let notch = &geom.skids[0].notches[0];
+        assert!((notch.max.y - notch.min.y - constants::sub_base::HEAVY_NOTCH_LENGTH).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_apply_standard_flag_drives_pipeline() {
+        let mut spec = CrateSpec::default();
+        spec.product.weight = 12000.0;
+        spec.requirements.standard = CrateStandard::MilC104;
+        assert!(!check_requirements(&spec).is_empty());
+        assert_eq!(calculate_crate(&spec).skids.len(), 3);
+
+        spec.requirements.apply_standard = true;
+        assert!(check_requirements(&spec).is_empty());
+        assert_eq!(calculate_crate(&spec).skids.len(), 4);
+
+        let design = CrateDesign::from_spec(&spec);
+        assert_eq!(design.spec.skid_size, LumberSize::L6x6);
+        assert_eq!(design.spec.cleat_size, LumberSize::L2x6);
+        assert_eq!(design.geometry.skids.len(), 4);
+    }
+}
@@ -0,0 +1,817 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: structural.rs | DNA/src/autocrate/structural.rs
+//! PURPOSE: Structural verification of crate members (skids, floorboards, cleated panels)
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+//!
+//! Checks a calculated `CrateGeometry` against loads instead of lookup rules:
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ CHECKS                                                                      │
+//! ├──────────────────────┬──────────────────────────────────────────────────────┤
+//! │ Skid bending/shear   │ Each skid as a simple beam over its full length      │
+//! │ Skid deflection      │ (lifted at the ends), carrying its share of the      │
+//! │                      │ factored product weight as a trapezoidal load        │
+//! │ Floorboard bending   │ Board spanning between skid centerlines under the    │
+//! │ Floorboard deflect.  │ peak product bearing pressure                        │
+//! │ Panel buckling       │ Vertical cleats as pinned columns (weak axis)        │
+//! │                      │ carrying the stacking load, NDS-style column factor  │
+//! │ Stacking capacity    │ Top load the weakest wall can carry vs applied load  │
+//! └──────────────────────┴──────────────────────────────────────────────────────┘
+//!
+//! Product weight is distributed across skids with the rigid-base lever rule
+//! and along them so the load resultant sits at the product's center of
+//! gravity. Plywood sheathing is ignored in the column checks (conservative).
+//!
+//! Allowables follow the repo's ASTM D6039 profile approach: parameterized
+//! design values per `WoodMemberClass`, not text copied from the standard.
+//! Margins are reported as capacity / demand - 1 (>= 0 passes).
+//!
+//! Units: inches, pounds, psi.
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use super::geometry::{CleatGeometry, PanelGeometry};
+use super::standards::{effective_spec, StandardProfile};
+use super::types::{CrateGeometry, CrateSpec, WoodMemberClass};
+
+/// Beam integration stations per span
+const BEAM_STATIONS: usize = 400;
+
+/// Maximum column slenderness (le/d) for solid sawn members
+const MAX_SLENDERNESS: f32 = 50.0;
+
+/// NDS column interaction constant for sawn lumber
+const SAWN_LUMBER_C: f32 = 0.8;
+
+/// Design values for a wood member class (psi)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WoodAllowables {
+    /// Extreme fiber stress in bending (Fb)
+    pub bending: f32,
+    /// Shear parallel to grain (Fv)
+    pub shear: f32,
+    /// Compression parallel to grain (Fc)
+    pub compression_parallel: f32,
+    /// Modulus of elasticity for deflection (E)
+    pub modulus: f32,
+    /// Modulus of elasticity for stability (Emin)
+    pub modulus_min: f32,
+}
+
+impl WoodAllowables {
+    /// Profile values for an ASTM D6199-style member class
+    pub fn for_class(class: WoodMemberClass) -> Self {
+        match class {
+            WoodMemberClass::Class1 => Self {
+                bending: 1200.0,
+                shear: 135.0,
+                compression_parallel: 1150.0,
+                modulus: 1_400_000.0,
+                modulus_min: 510_000.0,
+            },
+            WoodMemberClass::Class2 => Self {
+                bending: 1000.0,
+                shear: 125.0,
+                compression_parallel: 950.0,
+                modulus: 1_300_000.0,
+                modulus_min: 470_000.0,
+            },
+            WoodMemberClass::Class3 => Self {
+                bending: 700.0,
+                shear: 115.0,
+                compression_parallel: 700.0,
+                modulus: 1_100_000.0,
+                modulus_min: 400_000.0,
+            },
+        }
+    }
+}
+
+/// Load cases and limits for the structural checks
+#[derive(Clone, Debug)]
+pub struct StructuralOptions {
+    /// Multiplier on product weight for handling shock (lifting, fork entry)
+    pub dynamic_factor: f32,
+    /// Deflection limit as span / ratio
+    pub deflection_ratio: f32,
+    /// Number of identical loaded crates stacked on top of this one
+    pub stack_count: u32,
+    /// Wood density for the tare weight estimate (lb/ft³)
+    pub wood_density_pcf: f32,
+    /// Member class when the spec does not name one
+    pub default_class: WoodMemberClass,
+    /// Override the class design values
+    pub allowables: Option<WoodAllowables>,
+}
+
+impl Default for StructuralOptions {
+    fn default() -> Self {
+        Self {
+            dynamic_factor: 2.0,
+            deflection_ratio: 240.0,
+            stack_count: 1,
+            wood_density_pcf: 35.0,
+            default_class: WoodMemberClass::Class2,
+            allowables: None,
+        }
+    }
+}
+
+/// What a check verifies
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CheckKind {
+    SkidBending,
+    SkidShear,
+    SkidDeflection,
+    FloorboardBending,
+    FloorboardDeflection,
+    PanelBuckling,
+    StackingCapacity,
+}
+
+impl CheckKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CheckKind::SkidBending => "Skid bending",
+            CheckKind::SkidShear => "Skid shear",
+            CheckKind::SkidDeflection => "Skid deflection",
+            CheckKind::FloorboardBending => "Floorboard bending",
+            CheckKind::FloorboardDeflection => "Floorboard deflection",
+            CheckKind::PanelBuckling => "Panel cleat buckling",
+            CheckKind::StackingCapacity => "Stacking capacity",
+        }
+    }
+}
+
+/// One demand/capacity comparison
+#[derive(Clone, Debug, PartialEq)]
+pub struct StructuralCheck {
+    pub kind: CheckKind,
+    /// Member description ("Skid 2 (4x4)", "Left panel", ...)
+    pub member: String,
+    pub demand: f32,
+    pub capacity: f32,
+    /// Units of demand and capacity ("psi", "in", "lb")
+    pub units: &'static str,
+}
+
+impl StructuralCheck {
+    /// Margin of safety: capacity / demand - 1
+    pub fn margin(&self) -> f32 {
+        if self.demand <= 0.0 {
+            f32::INFINITY
+        } else {
+            self.capacity / self.demand - 1.0
+        }
+    }
+
+    pub fn passes(&self) -> bool {
+        self.margin() >= 0.0
+    }
+}
+
+/// Result of `analyze_structure`
+#[derive(Clone, Debug)]
+pub struct StructuralReport {
+    pub member_class: WoodMemberClass,
+    pub allowables: WoodAllowables,
+    /// Product weight times the dynamic factor (lb)
+    pub design_load: f32,
+    /// Estimated empty crate weight (lb)
+    pub tare_weight: f32,
+    /// Load applied to the crate top by stacking (lb)
+    pub stacking_load: f32,
+    pub checks: Vec<StructuralCheck>,
+}
+
+impl StructuralReport {
+    pub fn passes(&self) -> bool {
+        self.checks.iter().all(StructuralCheck::passes)
+    }
+
+    /// Check with the smallest margin
+    pub fn governing(&self) -> Option<&StructuralCheck> {
+        self.checks.iter().min_by(|a, b| {
+            a.margin()
+                .partial_cmp(&b.margin())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &StructuralCheck> {
+        self.checks.iter().filter(|c| !c.passes())
+    }
+}
+
+/// Check skids, floorboards and cleated panels of `geometry` against loads
+pub fn analyze_structure(
+    spec: &CrateSpec,
+    geometry: &CrateGeometry,
+    options: &StructuralOptions,
+) -> StructuralReport {
+    let spec = &*effective_spec(spec);
+    let member_class = spec
+        .materials
+        .wood_member_class
+        .unwrap_or(options.default_class);
+    let allowables = options
+        .allowables
+        .unwrap_or_else(|| WoodAllowables::for_class(member_class));
+    let product = &spec.product;
+    let cg = product.cg_offset();
+    let design_load = product.weight * options.dynamic_factor;
+    let tare_weight = estimate_tare_weight(spec, geometry, options.wood_density_pcf);
+    let stacking_load = options.stack_count as f32 * (product.weight + tare_weight);
+
+    let mut checks = Vec::new();
+
+    // ── Skids ──────────────────────────────────────────────────────────────
+    let centers: Vec<f32> = geometry.skids.iter().map(|s| s.bounds.center().x).collect();
+    let shares = lever_rule_shares(&centers, cg.x);
+    for (skid, share) in geometry.skids.iter().zip(&shares) {
+        let (depth, breadth) = skid.lumber_size.actual();
+        // Forklift notches: check the net section over the whole length
+        let notch = skid.notches.iter().map(|n| n.size().z).fold(0.0, f32::max);
+        let depth = depth - notch;
+        let span = skid.bounds.size().y;
+        let member = format!("Skid {} ({})", skid.index + 1, skid.lumber_size.name());
+
+        // Product footprint centered on the crate, in beam coordinates
+        let load = footprint_load(product.length, cg.y, design_load * share)
+            .shifted(skid.bounds.center().y - skid.bounds.min.y);
+        let section = Section::rectangle(breadth, depth);
+        let response = simply_supported(span, &[load], allowables.modulus * section.inertia);
+
+        checks.push(StructuralCheck {
+            kind: CheckKind::SkidBending,
+            member: member.clone(),
+            demand: response.max_moment / section.modulus,
+            capacity: allowables.bending,
+            units: "psi",
+        });
+        checks.push(StructuralCheck {
+            kind: CheckKind::SkidShear,
+            member: member.clone(),
+            demand: 1.5 * response.max_shear / section.area,
+            capacity: allowables.shear,
+            units: "psi",
+        });
+        checks.push(StructuralCheck {
+            kind: CheckKind::SkidDeflection,
+            member,
+            demand: response.max_deflection,
+            capacity: span / options.deflection_ratio,
+            units: "in",
+        });
+    }
+
+    // ── Floorboards ────────────────────────────────────────────────────────
+    let mut sorted = centers.clone();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let span = sorted.windows(2).map(|w| w[1] - w[0]).fold(0.0, f32::max);
+    if let (Some(board), true) = (geometry.floorboards.first(), span > 0.0) {
+        let (thickness, width) = board.lumber_size.actual();
+        let footprint = (product.length * product.width).max(f32::EPSILON);
+        let peak = footprint_load(product.length, cg.y, 1.0).peak_factor(product.length);
+        let pressure = design_load / footprint * peak;
+        let load = LinearLoad::uniform(0.0, span, pressure * width);
+        let section = Section::rectangle(width, thickness);
+        let response = simply_supported(span, &[load], allowables.modulus * section.inertia);
+        let member = format!(
+            "Floorboard ({}, {:.2} in span)",
+            board.lumber_size.name(),
+            span
+        );
+
+        checks.push(StructuralCheck {
+            kind: CheckKind::FloorboardBending,
+            member: member.clone(),
+            demand: response.max_moment / section.modulus,
+            capacity: allowables.bending,
+            units: "psi",
+        });
+        checks.push(StructuralCheck {
+            kind: CheckKind::FloorboardDeflection,
+            member,
+            demand: response.max_deflection,
+            capacity: span / options.deflection_ratio,
+            units: "in",
+        });
+    }
+
+    // ── Cleated panels and stacking ────────────────────────────────────────
+    let walls = [
+        (
+            &geometry.panels.front,
+            geometry.panels.front.bounds.size().x,
+        ),
+        (&geometry.panels.back, geometry.panels.back.bounds.size().x),
+        (&geometry.panels.left, geometry.panels.left.bounds.size().y),
+        (
+            &geometry.panels.right,
+            geometry.panels.right.bounds.size().y,
+        ),
+    ];
+    let perimeter: f32 = walls.iter().map(|w| w.1).sum();
+    let mut stack_capacity = f32::INFINITY;
+    for (panel, wall_length) in walls {
+        let Some(capacity) = panel_column_capacity(panel, &allowables) else {
+            continue;
+        };
+        let fraction = wall_length / perimeter.max(f32::EPSILON);
+        let count = capacity.count as f32;
+        checks.push(StructuralCheck {
+            kind: CheckKind::PanelBuckling,
+            member: format!(
+                "{} panel ({} vertical {} cleats, {:.2} in)",
+                panel.panel_type.name(),
+                capacity.count,
+                capacity.lumber,
+                capacity.length
+            ),
+            demand: stacking_load * fraction / count,
+            capacity: capacity.per_cleat,
+            units: "lb",
+        });
+        stack_capacity = stack_capacity.min(capacity.per_cleat * count / fraction);
+    }
+    if stack_capacity.is_finite() {
+        checks.push(StructuralCheck {
+            kind: CheckKind::StackingCapacity,
+            member: format!("Crate top ({} high)", options.stack_count + 1),
+            demand: stacking_load,
+            capacity: stack_capacity,
+            units: "lb",
+        });
+    }
+
+    StructuralReport {
+        member_class,
+        allowables,
+        design_load,
+        tare_weight,
+        stacking_load,
+        checks,
+    }
+}
+
+/// Estimated weight of the empty crate (lumber plus plywood sheathing, when
+/// the crate style is sheathed)
+pub fn estimate_tare_weight(spec: &CrateSpec, geometry: &CrateGeometry, density_pcf: f32) -> f32 {
+    let volume = |b: &super::geometry::BoundingBox| {
+        let s = b.size();
+        s.x * s.y * s.z
+    };
+    let mut cubic_inches: f32 = geometry.skids.iter().map(|s| volume(&s.bounds)).sum();
+    cubic_inches += geometry
+        .floorboards
+        .iter()
+        .map(|b| volume(&b.bounds))
+        .sum::<f32>();
+    cubic_inches += geometry
+        .cleats
+        .iter()
+        .map(|c| volume(&c.bounds))
+        .sum::<f32>();
+    let panels = &geometry.panels;
+    // Open crates carry no sheathing (same rule as the design's part list)
+    if !StandardProfile::for_requirements(&spec.requirements).sheathed {
+        return cubic_inches * density_pcf / 1728.0;
+    }
+    for panel in [
+        &panels.front,
+        &panels.back,
+        &panels.left,
+        &panels.right,
+        &panels.top,
+    ] {
+        // Sheet face = the two largest extents of the panel box
+        let s = panel.bounds.size();
+        let mut dims = [s.x, s.y, s.z];
+        dims.sort_by(|a, b| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+        cubic_inches += dims[0] * dims[1] * spec.materials.plywood_thickness;
+    }
+    cubic_inches * density_pcf / 1728.0
+}
+
+/// Structural checks as CSV (one row per check)
+pub fn structural_checks_to_csv(report: &StructuralReport) -> String {
+    let mut out = String::new();
+    out.push_str("check,member,demand,capacity,units,margin_pct,result\n");
+    for c in &report.checks {
+        let margin = c.margin();
+        let margin = if margin.is_finite() {
+            format!("{:.1}", margin * 100.0)
+        } else {
+            String::new()
+        };
+        out.push_str(&format!(
+            "{},\"{}\",{:.3},{:.3},{},{},{}\n",
+            c.kind.name(),
+            c.member.replace('"', "\"\""),
+            c.demand,
+            c.capacity,
+            c.units,
+            margin,
+            if c.passes() { "PASS" } else { "FAIL" }
+        ));
+    }
+    out
+}
+
+/// Fraction of the load carried by each skid (rigid base, lever rule)
+fn lever_rule_shares(centers: &[f32], cg_x: f32) -> Vec<f32> {
+    let n = centers.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let mean = centers.iter().sum::<f32>() / n as f32;
+    let spread: f32 = centers.iter().map(|x| (x - mean).powi(2)).sum();
+    let raw: Vec<f32> = centers
+        .iter()
+        .map(|x| {
+            let moment = if spread > 0.0 {
+                (cg_x - mean) * (x - mean) / spread
+            } else {
+                0.0
+            };
+            (1.0 / n as f32 + moment).max(0.0)
+        })
+        .collect();
+    // Skids that would go into tension unload; the rest share the weight
+    let total: f32 = raw.iter().sum();
+    raw.iter().map(|r| r / total.max(f32::EPSILON)).collect()
+}
+
+/// Rectangular section properties
+struct Section {
+    area: f32,
+    inertia: f32,
+    modulus: f32,
+}
+
+impl Section {
+    /// `breadth` across the bending plane, `depth` in it
+    fn rectangle(breadth: f32, depth: f32) -> Self {
+        Self {
+            area: breadth * depth,
+            inertia: breadth * depth.powi(3) / 12.0,
+            modulus: breadth * depth.powi(2) / 6.0,
+        }
+    }
+}
+
+/// Linearly varying line load (lb/in) between `start` and `end`
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct LinearLoad {
+    start: f32,
+    end: f32,
+    q_start: f32,
+    q_end: f32,
+}
+
+impl LinearLoad {
+    fn uniform(start: f32, end: f32, q: f32) -> Self {
+        Self {
+            start,
+            end,
+            q_start: q,
+            q_end: q,
+        }
+    }
+
+    fn shifted(self, dx: f32) -> Self {
+        Self {
+            start: self.start + dx,
+            end: self.end + dx,
+            ..self
+        }
+    }
+
+    fn at(&self, x: f64) -> f64 {
+        let (start, end) = (self.start as f64, self.end as f64);
+        if x < start || x > end || end <= start {
+            return 0.0;
+        }
+        let t = (x - start) / (end - start);
+        self.q_start as f64 + t * (self.q_end as f64 - self.q_start as f64)
+    }
+
+    /// Peak intensity relative to the same total spread evenly over `length`
+    fn peak_factor(&self, length: f32) -> f32 {
+        let total = 0.5 * (self.q_start + self.q_end) * (self.end - self.start);
+        if total <= 0.0 {
+            return 1.0;
+        }
+        self.q_start.max(self.q_end) * length / total
+    }
+}
+
+/// Load of `total` over a footprint of `length` (centered on 0) whose
+/// resultant sits at `offset`: trapezoidal while the offset stays in the
+/// middle third, triangular over the loaded part beyond that
+fn footprint_load(length: f32, offset: f32, total: f32) -> LinearLoad {
+    let half = length / 2.0;
+    let e = offset.clamp(-half, half);
+    if e.abs() <= length / 6.0 {
+        let q = total / length;
+        return LinearLoad {
+            start: -half,
+            end: half,
+            q_start: q * (1.0 - 6.0 * e / length),
+            q_end: q * (1.0 + 6.0 * e / length),
+        };
+    }
+    let loaded = (3.0 * (half - e.abs())).max(length * 1e-3);
+    let peak = 2.0 * total / loaded;
+    if e > 0.0 {
+        LinearLoad {
+            start: half - loaded,
+            end: half,
+            q_start: 0.0,
+            q_end: peak,
+        }
+    } else {
+        LinearLoad {
+            start: -half,
+            end: -half + loaded,
+            q_start: peak,
+            q_end: 0.0,
+        }
+    }
+}
+
+/// Peak internal forces and deflection of a beam
+#[derive(Clone, Copy, Debug)]
+struct BeamResponse {
+    max_moment: f32,
+    max_shear: f32,
+    max_deflection: f32,
+}
+
+/// Simply supported beam of `span` under line loads (x from the left support)
+fn simply_supported(span: f32, loads: &[LinearLoad], ei: f32) -> BeamResponse {
+    let n = BEAM_STATIONS;
+    let length = span as f64;
+    let dx = length / n as f64;
+    let xs: Vec<f64> = (0..=n).map(|i| i as f64 * dx).collect();
+    let q: Vec<f64> = xs
+        .iter()
+        .map(|&x| loads.iter().map(|l| l.at(x)).sum())
+        .collect();
+
+    // Left reaction from moments about the right support
+    let trapezoid =
+        |f: &dyn Fn(usize) -> f64| -> f64 { (0..n).map(|i| 0.5 * (f(i) + f(i + 1)) * dx).sum() };
+    let reaction = trapezoid(&|i| q[i] * (length - xs[i])) / length;
+
+    // Shear and moment by integration from the left support
+    let mut shear = vec![reaction; n + 1];
+    let mut moment = vec![0.0; n + 1];
+    for i in 1..=n {
+        shear[i] = shear[i - 1] - 0.5 * (q[i - 1] + q[i]) * dx;
+        moment[i] = moment[i - 1] + 0.5 * (shear[i - 1] + shear[i]) * dx;
+    }
+
+    // Deflection: integrate curvature twice, then fix the slope so v(L) = 0
+    let ei = ei as f64;
+    let mut slope = vec![0.0; n + 1];
+    let mut deflection = vec![0.0; n + 1];
+    for i in 1..=n {
+        slope[i] = slope[i - 1] + 0.5 * (moment[i - 1] + moment[i]) / ei * dx;
+        deflection[i] = deflection[i - 1] + 0.5 * (slope[i - 1] + slope[i]) * dx;
+    }
+    let correction = deflection[n] / length;
+    let max_deflection = (0..=n)
+        .map(|i| (deflection[i] - correction * xs[i]).abs())
+        .fold(0.0, f64::max);
+
+    BeamResponse {
+        max_moment: moment.iter().fold(0.0f64, |m, v| m.max(v.abs())) as f32,
+        max_shear: shear.iter().fold(0.0f64, |m, v| m.max(v.abs())) as f32,
+        max_deflection: max_deflection as f32,
+    }
+}
+
+/// Axial capacity of the governing vertical cleat in a panel
+struct ColumnCapacity {
+    per_cleat: f32,
+    count: usize,
+    length: f32,
+    lumber: &'static str,
+}
+
+fn panel_column_capacity(
+    panel: &PanelGeometry,
+    allowables: &WoodAllowables,
+) -> Option<ColumnCapacity> {
+    let verticals: Vec<&CleatGeometry> = panel.cleats.iter().filter(|c| c.is_vertical).collect();
+    let longest = verticals.iter().max_by(|a, b| {
+        a.bounds
+            .size()
+            .z
+            .partial_cmp(&b.bounds.size().z)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    })?;
+    let length = longest.bounds.size().z;
+    let (thickness, width) = longest.lumber_size.actual();
+    let area = thickness * width;
+    let slenderness = length / thickness;
+
+    let per_cleat = if slenderness > MAX_SLENDERNESS {
+        0.0
+    } else {
+        // NDS column stability factor (pinned ends, weak axis)
+        let fc = allowables.compression_parallel;
+        let fce = 0.822 * allowables.modulus_min / slenderness.powi(2);
+        let alpha = fce / fc;
+        let a = (1.0 + alpha) / (2.0 * SAWN_LUMBER_C);
+        let cp = a - (a * a - alpha / SAWN_LUMBER_C).max(0.0).sqrt();
+        cp * fc * area
+    };
+    Some(ColumnCapacity {
+        per_cleat,
+        count: verticals.len(),
+        length,
+        lumber: longest.lumber_size.name(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::autocrate::{calculate_crate, CrateStyle, LumberSize, Point3};
+
+    #[test]
+    fn test_beam_matches_closed_form() {
+        let (span, w, ei) = (60.0f32, 10.0f32, 1.0e7f32);
+        let r = simply_supported(span, &[LinearLoad::uniform(0.0, span, w)], ei);
+        let moment = w * span * span / 8.0;
+        let deflection = 5.0 * w * span.powi(4) / (384.0 * ei);
+        assert!((r.max_moment - moment).abs() / moment < 1e-3);
+        assert!((r.max_shear - w * span / 2.0).abs() < 1e-2);
+        assert!((r.max_deflection - deflection).abs() / deflection < 1e-3);
+
+        // Resultant of an off-center footprint load sits at the CG offset
+        for offset in [0.0f32, 5.0, -20.0] {
+            let l = footprint_load(48.0, offset, 100.0);
+            let total = 0.5 * (l.q_start + l.q_end) * (l.end - l.start);
+            let centroid = l.start
+                + (l.end - l.start) * (l.q_start + 2.0 * l.q_end) / (3.0 * (l.q_start + l.q_end));
+            assert!((total - 100.0).abs() < 1e-3);
+            assert!((centroid - offset).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_default_crate_passes_with_margins() {
+        let spec = CrateSpec::default();
+        let geometry = calculate_crate(&spec);
+        let report = analyze_structure(&spec, &geometry, &StructuralOptions::default());
+
+        assert!(report.passes(), "{}", structural_checks_to_csv(&report));
+        assert_eq!(report.member_class, WoodMemberClass::Class2);
+        assert!(report.tare_weight > 0.0);
+        for kind in [
+            CheckKind::SkidBending,
+            CheckKind::FloorboardBending,
+            CheckKind::PanelBuckling,
+            CheckKind::StackingCapacity,
+        ] {
+            assert!(report.checks.iter().any(|c| c.kind == kind));
+        }
+        assert_eq!(
+            report
+                .checks
+                .iter()
+                .filter(|c| c.kind == CheckKind::SkidBending)
+                .count(),
+            3
+        );
+        let governing = report.governing().unwrap();
+        assert!(governing.margin().is_finite() && governing.margin() >= 0.0);
+
+        let csv = structural_checks_to_csv(&report);
+        assert!(csv.starts_with("check,member,demand,capacity,units,margin_pct,result\n"));
+        assert!(!csv.contains("FAIL"));
+    }
+
+    #[test]
+    fn test_heavy_load_fails_and_class_matters() {
+        let mut spec = CrateSpec::default();
+        spec.product.weight = 8000.0;
+        spec.skid_count = 2;
+        let geometry = calculate_crate(&spec);
+        let options = StructuralOptions::default();
+
+        let report = analyze_structure(&spec, &geometry, &options);
+        assert!(!report.passes());
+        assert!(report.failures().any(|c| c.kind == CheckKind::SkidBending));
+
+        let mut margin = |class| {
+            spec.materials.wood_member_class = Some(class);
+            let report = analyze_structure(&spec, &geometry, &options);
+            report
+                .checks
+                .iter()
+                .find(|c| c.kind == CheckKind::SkidBending)
+                .unwrap()
+                .margin()
+        };
+        let (class1, class3) = (
+            margin(WoodMemberClass::Class1),
+            margin(WoodMemberClass::Class3),
+        );
+        assert!(class1 > class3);
+
+        // More, bigger skids bring bending back within the allowable
+        spec.materials.wood_member_class = None;
+        spec.skid_count = 6;
+        spec.skid_size = LumberSize::L6x6;
+        let geometry = calculate_crate(&spec);
+        let report = analyze_structure(&spec, &geometry, &options);
+        assert!(report
+            .checks
+            .iter()
+            .filter(|c| c.kind == CheckKind::SkidBending)
+            .all(StructuralCheck::passes));
+    }
+
+    #[test]
+    fn test_center_of_gravity_and_stacking() {
+        let mut spec = CrateSpec::default();
+        let geometry = calculate_crate(&spec);
+        let options = StructuralOptions::default();
+        let stress = |report: &StructuralReport, skid: usize| {
+            report
+                .checks
+                .iter()
+                .filter(|c| c.kind == CheckKind::SkidBending)
+                .nth(skid)
+                .unwrap()
+                .demand
+        };
+
+        let centered = analyze_structure(&spec, &geometry, &options);
+        spec.product.center_of_gravity = Some(Point3::new(8.0, 0.0, 0.0));
+        let shifted = analyze_structure(&spec, &geometry, &options);
+        // CG toward +x loads the right-hand skid and relieves the left one
+        assert!(stress(&shifted, 2) > stress(&centered, 2));
+        assert!(stress(&shifted, 0) < stress(&centered, 0));
+
+        let stacked = StructuralOptions {
+            stack_count: 40,
+            ..Default::default()
+        };
+        let report = analyze_structure(&spec, &geometry, &stacked);
+        assert!(report
+            .failures()
+            .any(|c| c.kind == CheckKind::StackingCapacity));
+
+        let unstacked = StructuralOptions {
+            stack_count: 0,
+            ..Default::default()
+        };
+        let report = analyze_structure(&spec, &geometry, &unstacked);
+        assert_eq!(report.stacking_load, 0.0);
+        assert!(report
+            .checks
+            .iter()
+            .filter(|c| c.kind == CheckKind::PanelBuckling)
+            .all(StructuralCheck::passes));
+    }
+
+    #[test]
+    fn test_open_crate_tare_excludes_sheathing() {
+        let mut spec = CrateSpec::default();
+        let geometry = calculate_crate(&spec);
+        let options = StructuralOptions::default();
+        let sheathed = analyze_structure(&spec, &geometry, &options);
+
+        spec.requirements.style = CrateStyle::Open;
+        let open = analyze_structure(&spec, &geometry, &options);
+        let panels = &geometry.panels;
+        let plywood: f32 = [
+            &panels.front,
+            &panels.back,
+            &panels.left,
+            &panels.right,
+            &panels.top,
+        ]
+        .iter()
+        .map(|p| {
+            let s = p.bounds.size();
+            let mut dims = [s.x, s.y, s.z];
+            dims.sort_by(|a, b| b.total_cmp(a));
+            dims[0] * dims[1] * spec.materials.plywood_thickness
+        })
+        .sum::<f32>()
+            * options.wood_density_pcf
+            / 1728.0;
+        assert!((sheathed.tare_weight - open.tare_weight - plywood).abs() < 1e-2);
+        assert!(open.stacking_load < sheathed.stacking_load);
+        assert!(open.tare_weight > 0.0);
+    }
+}
@@ -1,7 +1,7 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: types.rs | DNA/src/autocrate/types.rs
 //! PURPOSE: Defines ProductDimensions, Clearances, CrateSpec types
-//! MODIFIED: 2025-12-09
+//! MODIFIED: 2026-10-18
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
 
@@ -18,6 +18,17 @@ pub struct ProductDimensions {
     pub width: f32,
     pub height: f32,
     pub weight: f32,
+    /// Center of gravity as an offset from the product's geometric center
+    /// (inches, crate axes: x = width, y = length, z = up). `None` = centered.
+    #[serde(default)]
+    pub center_of_gravity: Option<Point3>,
+}
+
+impl ProductDimensions {
+    /// Center of gravity offset (zero when not specified)
+    pub fn cg_offset(&self) -> Point3 {
+        self.center_of_gravity.unwrap_or_default()
+    }
 }
 
 impl Default for ProductDimensions {
@@ -27,6 +38,7 @@ impl Default for ProductDimensions {
             width: 36.0,
             height: 24.0,
             weight: 500.0,
+            center_of_gravity: None,
         }
     }
 }
@@ -54,8 +66,54 @@ impl Default for Clearances {
 /// Note: We model standards as **parameterized profiles** (rules + limits), not as copied text.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CrateStandard {
-    /// ASTM D6039-style wood crates (v1 scope).
+    /// ASTM D6039-style open and covered wood crates (v1 scope).
     AstmD6039,
+    /// MIL-C-104-style lumber and plywood sheathed, nailed and bolted crates.
+    MilC104,
+    /// ASTM D6251-style wood-cleated panelboard boxes (light loads).
+    AstmD6251,
+}
+
+impl CrateStandard {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CrateStandard::AstmD6039 => "ASTM D6039",
+            CrateStandard::MilC104 => "MIL-C-104",
+            CrateStandard::AstmD6251 => "ASTM D6251",
+        }
+    }
+}
+
+/// Crate construction style within a standard.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CrateStyle {
+    /// Cleated frame covered with plywood sheathing on all faces.
+    #[default]
+    Sheathed,
+    /// Open (framed) crate: cleat frame only, no sheathing.
+    Open,
+    /// Nailed wood box: sheathing carries the load, cleats on the ends only.
+    NailedBox,
+}
+
+impl CrateStyle {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CrateStyle::Sheathed => "Sheathed",
+            CrateStyle::Open => "Open",
+            CrateStyle::NailedBox => "Nailed box",
+        }
+    }
+}
+
+/// Base construction.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BaseStyle {
+    /// Skids evenly spaced under the floorboards.
+    #[default]
+    Skids,
+    /// Skid-less sub-base: edge-flush runners with forklift notches.
+    SubBase,
 }
 
 /// Shipping mode impacts compliance and marking requirements.
@@ -171,6 +229,17 @@ impl Default for MarkingsSpec {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RequirementsSpec {
     pub standard: CrateStandard,
+    #[serde(default)]
+    pub style: CrateStyle,
+    #[serde(default)]
+    pub base: BaseStyle,
+    /// Add hoisting cleats (sling points) to the side panels.
+    #[serde(default)]
+    pub lift_cleats: bool,
+    /// Size skids, floorboards and cleats from the standard's rules instead
+    /// of the spec's own member sizes.
+    #[serde(default)]
+    pub apply_standard: bool,
     pub shipping_mode: ShippingMode,
     pub ispm15: Ismp15Config,
 }
@@ -179,6 +248,10 @@ impl Default for RequirementsSpec {
     fn default() -> Self {
         Self {
             standard: CrateStandard::AstmD6039,
+            style: CrateStyle::Sheathed,
+            base: BaseStyle::Skids,
+            lift_cleats: false,
+            apply_standard: false,
             shipping_mode: ShippingMode::Domestic,
             ispm15: Ismp15Config::default(),
         }
@@ -228,4 +301,6 @@ pub struct CrateGeometry {
     pub floorboards: Vec<BoardGeometry>,
     pub panels: PanelSet,
     pub cleats: Vec<CleatGeometry>,
+    /// Hoisting cleats (empty unless `RequirementsSpec::lift_cleats`)
+    pub lift_cleats: Vec<LiftCleatGeometry>,
 }
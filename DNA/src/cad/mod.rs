@@ -1,7 +1,7 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: mod.rs | DNA/src/cad/mod.rs
 //! PURPOSE: B-Rep (Boundary Representation) CAD kernel for solid modeling
-//! MODIFIED: 2025-12-09
+//! MODIFIED: 2026-10-18
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
 
@@ -17,6 +17,7 @@
 //! │   cad/                                                                      │
 //! │   ├── geometry.rs    3D geometry primitives (Point3, Vector3, Plane, etc.) │
 //! │   ├── topology.rs    B-Rep topology (Vertex, Edge, Face, Shell, Solid)     │
+//! │   ├── nurbs.rs       NURBS kernel (evaluation, refinement, inversion)      │
 //! │   └── primitives.rs  Solid generators (box, cylinder, sphere, cone)        │
 //! │                                                                             │
 //! └─────────────────────────────────────────────────────────────────────────────┘
@@ -32,6 +33,7 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 
 pub mod geometry;
+pub mod nurbs;
 pub mod primitives;
 pub mod topology;
 
@@ -39,6 +41,7 @@ pub mod topology;
 pub use geometry::{
     BoundingBox3, Line, Plane, Point3, Ray, Segment, Transform3, Vector3, TOLERANCE,
 };
+pub use nurbs::{NurbsCurve, NurbsSurface};
 pub use primitives::{
     make_box, make_box_at, make_cone, make_cone_at, make_cylinder, make_cylinder_at, make_sphere,
     make_sphere_at,
@@ -0,0 +1,955 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: nurbs.rs | DNA/src/cad/nurbs.rs
+//! PURPOSE: NURBS curve/surface kernel (evaluation, derivatives, refinement, inversion)
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! PURPOSE: NURBS curve/surface kernel (evaluation, derivatives, refinement, inversion)
+//!
+//! LAYER: DNA → CAD
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ OPERATIONS                                                                  │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ point_at()        De Boor evaluation in homogeneous space                   │
+//! │ derivatives()     Rational derivatives (quotient rule on Aw / w)            │
+//! │ insert_knot()     Boehm knot insertion (shape preserving)                   │
+//! │ refine_knots()    Insert a sorted list of knots                             │
+//! │ elevate_degree()  Bezier decomposition + per-segment elevation              │
+//! │ closest_point()   Point inversion (sampling + Newton iteration)             │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! Algorithms follow Piegl & Tiller, "The NURBS Book" (A2.1-A2.3, A3.x, A4.x, A5.1).
+//! Control nets are indexed `control_points[i][j]` with `i` along u and `j` along v.
+//!
+//! DEPENDS ON:
+//!   • DNA/src/cad/geometry.rs → Point3, Vector3
+//!
+//! USED BY:
+//!   • DNA/src/cad/topology.rs → CurveType::point_at, SurfaceType::normal_at
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use glam::{Vec3, Vec4};
+
+use super::geometry::{Point3, Vector3, TOLERANCE};
+use super::topology::{CurveType, SurfaceType};
+
+/// Non-uniform rational B-spline curve
+#[derive(Clone, Debug, PartialEq)]
+pub struct NurbsCurve {
+    pub control_points: Vec<Point3>,
+    pub weights: Vec<f32>,
+    /// Full knot vector (length = control points + degree + 1)
+    pub knots: Vec<f32>,
+    pub degree: usize,
+}
+
+/// Non-uniform rational B-spline surface
+#[derive(Clone, Debug, PartialEq)]
+pub struct NurbsSurface {
+    pub control_points: Vec<Vec<Point3>>,
+    pub weights: Vec<Vec<f32>>,
+    pub u_knots: Vec<f32>,
+    pub v_knots: Vec<f32>,
+    pub u_degree: usize,
+    pub v_degree: usize,
+}
+
+impl NurbsCurve {
+    /// Create a curve, validating array lengths and knot ordering
+    pub fn new(
+        control_points: Vec<Point3>,
+        weights: Vec<f32>,
+        knots: Vec<f32>,
+        degree: usize,
+    ) -> Option<Self> {
+        let n = control_points.len();
+        if n == 0 || degree == 0 || n <= degree || weights.len() != n {
+            return None;
+        }
+        if knots.len() != n + degree + 1 || knots.windows(2).any(|w| w[1] < w[0]) {
+            return None;
+        }
+        Some(Self {
+            control_points,
+            weights,
+            knots,
+            degree,
+        })
+    }
+
+    /// Read the NURBS data of a `CurveType::Nurbs` edge
+    pub fn from_curve_type(curve: &CurveType) -> Option<Self> {
+        match curve {
+            CurveType::Nurbs {
+                control_points,
+                weights,
+                knots,
+                degree,
+            } => Self::new(
+                control_points.clone(),
+                weights.clone(),
+                knots.clone(),
+                *degree as usize,
+            ),
+            _ => None,
+        }
+    }
+
+    pub fn to_curve_type(&self) -> CurveType {
+        CurveType::Nurbs {
+            control_points: self.control_points.clone(),
+            weights: self.weights.clone(),
+            knots: self.knots.clone(),
+            degree: self.degree as u32,
+        }
+    }
+
+    /// Valid parameter range [u_min, u_max]
+    pub fn domain(&self) -> (f32, f32) {
+        (
+            self.knots[self.degree],
+            self.knots[self.control_points.len()],
+        )
+    }
+
+    fn homogeneous(&self) -> Vec<Vec4> {
+        to_homogeneous(&self.control_points, &self.weights)
+    }
+
+    fn from_homogeneous(points: &[Vec4], knots: Vec<f32>, degree: usize) -> Self {
+        let (control_points, weights) = from_homogeneous(points);
+        Self {
+            control_points,
+            weights,
+            knots,
+            degree,
+        }
+    }
+
+    /// Evaluate the curve at parameter `u` (De Boor's algorithm)
+    pub fn point_at(&self, u: f32) -> Point3 {
+        let (a, b) = self.domain();
+        let u = u.clamp(a, b);
+        let pw = self.homogeneous();
+        let span = find_span(pw.len(), self.degree, u, &self.knots);
+        project(de_boor(&pw, &self.knots, self.degree, span, u))
+    }
+
+    /// Point and derivatives up to order `order` (index 0 is the point as a vector)
+    pub fn derivatives(&self, u: f32, order: usize) -> Vec<Vector3> {
+        let (a, b) = self.domain();
+        let u = u.clamp(a, b);
+        let pw = self.homogeneous();
+        let span = find_span(pw.len(), self.degree, u, &self.knots);
+        let ders = ders_basis_funs(span, u, self.degree, order, &self.knots);
+        let mut aw = vec![Vec4::ZERO; order + 1];
+        for (k, row) in ders.iter().enumerate().take(order + 1) {
+            for (j, n) in row.iter().enumerate() {
+                aw[k] += pw[span - self.degree + j] * *n;
+            }
+        }
+        rational_curve_derivs(&aw)
+            .into_iter()
+            .map(Vector3::from_vec3)
+            .collect()
+    }
+
+    /// Unit tangent at `u` (falls back to +X where the curve is degenerate)
+    pub fn tangent_at(&self, u: f32) -> Vector3 {
+        self.derivatives(u, 1)[1].normalize().unwrap_or(Vector3::X)
+    }
+
+    /// Insert knot `u` up to `times` times (limited so multiplicity stays <= degree)
+    pub fn insert_knot(&self, u: f32, times: usize) -> Self {
+        let (pw, knots) = insert_knot_h(&self.homogeneous(), &self.knots, self.degree, u, times);
+        Self::from_homogeneous(&pw, knots, self.degree)
+    }
+
+    /// Insert every knot of `new_knots` once (knot refinement)
+    pub fn refine_knots(&self, new_knots: &[f32]) -> Self {
+        let mut pw = self.homogeneous();
+        let mut knots = self.knots.clone();
+        let mut sorted = new_knots.to_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        for u in sorted {
+            let (p, k) = insert_knot_h(&pw, &knots, self.degree, u, 1);
+            pw = p;
+            knots = k;
+        }
+        Self::from_homogeneous(&pw, knots, self.degree)
+    }
+
+    /// Raise the degree by `times` without changing the shape.
+    ///
+    /// Requires a clamped knot vector. Interior knots keep full multiplicity
+    /// (the curve is not knot-reduced afterwards).
+    pub fn elevate_degree(&self, times: usize) -> Option<Self> {
+        if times == 0 {
+            return Some(self.clone());
+        }
+        let (pw, knots) = elevate_degree_h(&self.homogeneous(), &self.knots, self.degree, times)?;
+        Some(Self::from_homogeneous(&pw, knots, self.degree + times))
+    }
+
+    /// Closest point on the curve to `point`; returns (parameter, point)
+    pub fn closest_point(&self, point: Point3) -> (f32, Point3) {
+        let (a, b) = self.domain();
+        let samples = (self.control_points.len() * 8).max(32);
+        let target = point.to_vec3();
+
+        let mut best_u = a;
+        let mut best_d = f32::INFINITY;
+        for i in 0..=samples {
+            let u = a + (b - a) * i as f32 / samples as f32;
+            let d = self.point_at(u).to_vec3().distance_squared(target);
+            if d < best_d {
+                best_d = d;
+                best_u = u;
+            }
+        }
+
+        let mut u = best_u;
+        for _ in 0..32 {
+            let d = self.derivatives(u, 2);
+            let diff = d[0].to_vec3() - target;
+            let c1 = d[1].to_vec3();
+            let f = c1.dot(diff);
+            let fp = d[2].to_vec3().dot(diff) + c1.length_squared();
+            if fp.abs() < TOLERANCE {
+                break;
+            }
+            let next = (u - f / fp).clamp(a, b);
+            let step = (next - u).abs() * c1.length();
+            u = next;
+            if step < TOLERANCE {
+                break;
+            }
+        }
+        (u, self.point_at(u))
+    }
+}
+
+impl NurbsSurface {
+    /// Create a surface, validating net dimensions and knot vectors
+    pub fn new(
+        control_points: Vec<Vec<Point3>>,
+        weights: Vec<Vec<f32>>,
+        u_knots: Vec<f32>,
+        v_knots: Vec<f32>,
+        u_degree: usize,
+        v_degree: usize,
+    ) -> Option<Self> {
+        let nu = control_points.len();
+        let nv = control_points.first()?.len();
+        if u_degree == 0 || v_degree == 0 || nu <= u_degree || nv <= v_degree {
+            return None;
+        }
+        if control_points.iter().any(|row| row.len() != nv)
+            || weights.len() != nu
+            || weights.iter().any(|row| row.len() != nv)
+        {
+            return None;
+        }
+        if u_knots.len() != nu + u_degree + 1 || v_knots.len() != nv + v_degree + 1 {
+            return None;
+        }
+        Some(Self {
+            control_points,
+            weights,
+            u_knots,
+            v_knots,
+            u_degree,
+            v_degree,
+        })
+    }
+
+    /// Read the NURBS data of a `SurfaceType::Nurbs` face
+    pub fn from_surface_type(surface: &SurfaceType) -> Option<Self> {
+        match surface {
+            SurfaceType::Nurbs {
+                control_points,
+                weights,
+                u_knots,
+                v_knots,
+                u_degree,
+                v_degree,
+            } => Self::new(
+                control_points.clone(),
+                weights.clone(),
+                u_knots.clone(),
+                v_knots.clone(),
+                *u_degree as usize,
+                *v_degree as usize,
+            ),
+            _ => None,
+        }
+    }
+
+    pub fn to_surface_type(&self) -> SurfaceType {
+        SurfaceType::Nurbs {
+            control_points: self.control_points.clone(),
+            weights: self.weights.clone(),
+            u_knots: self.u_knots.clone(),
+            v_knots: self.v_knots.clone(),
+            u_degree: self.u_degree as u32,
+            v_degree: self.v_degree as u32,
+        }
+    }
+
+    /// Valid parameter ranges ((u_min, u_max), (v_min, v_max))
+    pub fn domain(&self) -> ((f32, f32), (f32, f32)) {
+        let nu = self.control_points.len();
+        let nv = self.control_points[0].len();
+        (
+            (self.u_knots[self.u_degree], self.u_knots[nu]),
+            (self.v_knots[self.v_degree], self.v_knots[nv]),
+        )
+    }
+
+    fn homogeneous(&self) -> Vec<Vec<Vec4>> {
+        self.control_points
+            .iter()
+            .zip(self.weights.iter())
+            .map(|(row, w)| to_homogeneous(row, w))
+            .collect()
+    }
+
+    fn with_net(
+        &self,
+        net: &[Vec<Vec4>],
+        u_knots: Vec<f32>,
+        v_knots: Vec<f32>,
+        u_degree: usize,
+        v_degree: usize,
+    ) -> Self {
+        let (control_points, weights) = net.iter().map(|row| from_homogeneous(row)).unzip();
+        Self {
+            control_points,
+            weights,
+            u_knots,
+            v_knots,
+            u_degree,
+            v_degree,
+        }
+    }
+
+    /// Homogeneous partial derivatives SKL[k][l] for k + l <= order
+    fn derivatives_h(&self, u: f32, v: f32, order: usize) -> Vec<Vec<Vec4>> {
+        let ((u0, u1), (v0, v1)) = self.domain();
+        let (u, v) = (u.clamp(u0, u1), v.clamp(v0, v1));
+        let net = self.homogeneous();
+        let (p, q) = (self.u_degree, self.v_degree);
+        let uspan = find_span(net.len(), p, u, &self.u_knots);
+        let vspan = find_span(net[0].len(), q, v, &self.v_knots);
+        let nu = ders_basis_funs(uspan, u, p, order, &self.u_knots);
+        let nv = ders_basis_funs(vspan, v, q, order, &self.v_knots);
+
+        let mut skl = vec![vec![Vec4::ZERO; order + 1]; order + 1];
+        for k in 0..=order.min(p) {
+            let mut temp = vec![Vec4::ZERO; q + 1];
+            for (s, t) in temp.iter_mut().enumerate() {
+                for r in 0..=p {
+                    *t += net[uspan - p + r][vspan - q + s] * nu[k][r];
+                }
+            }
+            for l in 0..=(order - k).min(q) {
+                for (s, t) in temp.iter().enumerate() {
+                    skl[k][l] += *t * nv[l][s];
+                }
+            }
+        }
+        skl
+    }
+
+    /// Evaluate the surface at (u, v)
+    pub fn point_at(&self, u: f32, v: f32) -> Point3 {
+        project(self.derivatives_h(u, v, 0)[0][0])
+    }
+
+    /// Rational partial derivatives SKL[k][l] = ∂^(k+l) S / ∂u^k ∂v^l for k + l <= order
+    pub fn derivatives(&self, u: f32, v: f32, order: usize) -> Vec<Vec<Vector3>> {
+        let aw = self.derivatives_h(u, v, order);
+        let mut skl = vec![vec![Vec3::ZERO; order + 1]; order + 1];
+        let w00 = aw[0][0].w;
+        for k in 0..=order {
+            for l in 0..=(order - k) {
+                let mut val = aw[k][l].truncate();
+                for j in 1..=l {
+                    val -= skl[k][l - j] * (binomial(l, j) * aw[0][j].w);
+                }
+                for i in 1..=k {
+                    val -= skl[k - i][l] * (binomial(k, i) * aw[i][0].w);
+                    let mut v2 = Vec3::ZERO;
+                    for j in 1..=l {
+                        v2 += skl[k - i][l - j] * (binomial(l, j) * aw[i][j].w);
+                    }
+                    val -= v2 * binomial(k, i);
+                }
+                skl[k][l] = val / w00;
+            }
+        }
+        skl.into_iter()
+            .map(|row| row.into_iter().map(Vector3::from_vec3).collect())
+            .collect()
+    }
+
+    /// Unit normal Su × Sv at (u, v)
+    pub fn normal_at(&self, u: f32, v: f32) -> Vector3 {
+        let d = self.derivatives(u, v, 1);
+        d[1][0].cross(d[0][1]).normalize_or_z()
+    }
+
+    /// Insert knot `u` (in the u direction) up to `times` times
+    pub fn insert_knot_u(&self, u: f32, times: usize) -> Self {
+        let net = self.homogeneous();
+        let columns = transpose(&net);
+        let mut u_knots = self.u_knots.clone();
+        let new_columns: Vec<Vec<Vec4>> = columns
+            .iter()
+            .map(|col| {
+                let (c, k) = insert_knot_h(col, &self.u_knots, self.u_degree, u, times);
+                u_knots = k;
+                c
+            })
+            .collect();
+        self.with_net(
+            &transpose(&new_columns),
+            u_knots,
+            self.v_knots.clone(),
+            self.u_degree,
+            self.v_degree,
+        )
+    }
+
+    /// Insert knot `v` (in the v direction) up to `times` times
+    pub fn insert_knot_v(&self, v: f32, times: usize) -> Self {
+        let mut v_knots = self.v_knots.clone();
+        let rows: Vec<Vec<Vec4>> = self
+            .homogeneous()
+            .iter()
+            .map(|row| {
+                let (r, k) = insert_knot_h(row, &self.v_knots, self.v_degree, v, times);
+                v_knots = k;
+                r
+            })
+            .collect();
+        self.with_net(
+            &rows,
+            self.u_knots.clone(),
+            v_knots,
+            self.u_degree,
+            self.v_degree,
+        )
+    }
+
+    /// Raise the degree in u and v without changing the shape (clamped knots required)
+    pub fn elevate_degree(&self, u_times: usize, v_times: usize) -> Option<Self> {
+        let mut net = self.homogeneous();
+        let mut u_knots = self.u_knots.clone();
+        let mut v_knots = self.v_knots.clone();
+        if u_times > 0 {
+            let mut columns = Vec::new();
+            for col in transpose(&net) {
+                let (c, k) = elevate_degree_h(&col, &self.u_knots, self.u_degree, u_times)?;
+                columns.push(c);
+                u_knots = k;
+            }
+            net = transpose(&columns);
+        }
+        if v_times > 0 {
+            let mut rows = Vec::new();
+            for row in &net {
+                let (r, k) = elevate_degree_h(row, &self.v_knots, self.v_degree, v_times)?;
+                rows.push(r);
+                v_knots = k;
+            }
+            net = rows;
+        }
+        Some(self.with_net(
+            &net,
+            u_knots,
+            v_knots,
+            self.u_degree + u_times,
+            self.v_degree + v_times,
+        ))
+    }
+
+    /// Closest point on the surface to `point`; returns (u, v, point)
+    pub fn closest_point(&self, point: Point3) -> (f32, f32, Point3) {
+        let ((u0, u1), (v0, v1)) = self.domain();
+        let target = point.to_vec3();
+        let nu = (self.control_points.len() * 4).max(16);
+        let nv = (self.control_points[0].len() * 4).max(16);
+
+        let (mut u, mut v) = (u0, v0);
+        let mut best_d = f32::INFINITY;
+        for i in 0..=nu {
+            for j in 0..=nv {
+                let su = u0 + (u1 - u0) * i as f32 / nu as f32;
+                let sv = v0 + (v1 - v0) * j as f32 / nv as f32;
+                let d = self.point_at(su, sv).to_vec3().distance_squared(target);
+                if d < best_d {
+                    best_d = d;
+                    u = su;
+                    v = sv;
+                }
+            }
+        }
+
+        for _ in 0..32 {
+            let d = self.derivatives(u, v, 2);
+            let r = d[0][0].to_vec3() - target;
+            let (su, sv) = (d[1][0].to_vec3(), d[0][1].to_vec3());
+            let (suu, suv, svv) = (d[2][0].to_vec3(), d[1][1].to_vec3(), d[0][2].to_vec3());
+            let f = su.dot(r);
+            let g = sv.dot(r);
+            let a = su.length_squared() + r.dot(suu);
+            let b = su.dot(sv) + r.dot(suv);
+            let c = sv.length_squared() + r.dot(svv);
+            let det = a * c - b * b;
+            if det.abs() < TOLERANCE * TOLERANCE {
+                break;
+            }
+            let du = (c * f - b * g) / det;
+            let dv = (a * g - b * f) / det;
+            let nu_ = (u - du).clamp(u0, u1);
+            let nv_ = (v - dv).clamp(v0, v1);
+            let step = (su * (nu_ - u) + sv * (nv_ - v)).length();
+            u = nu_;
+            v = nv_;
+            if step < TOLERANCE {
+                break;
+            }
+        }
+        (u, v, self.point_at(u, v))
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Core algorithms (homogeneous control points: (w·x, w·y, w·z, w))
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn to_homogeneous(points: &[Point3], weights: &[f32]) -> Vec<Vec4> {
+    points
+        .iter()
+        .zip(weights.iter())
+        .map(|(p, w)| (p.to_vec3() * *w).extend(*w))
+        .collect()
+}
+
+fn from_homogeneous(points: &[Vec4]) -> (Vec<Point3>, Vec<f32>) {
+    points.iter().map(|h| (project(*h), h.w)).unzip()
+}
+
+fn project(h: Vec4) -> Point3 {
+    if h.w.abs() < TOLERANCE {
+        Point3::from_vec3(h.truncate())
+    } else {
+        Point3::from_vec3(h.truncate() / h.w)
+    }
+}
+
+fn binomial(n: usize, k: usize) -> f32 {
+    (0..k).fold(1.0, |acc, i| acc * (n - i) as f32 / (i + 1) as f32)
+}
+
+fn transpose(net: &[Vec<Vec4>]) -> Vec<Vec<Vec4>> {
+    if net.is_empty() {
+        return Vec::new();
+    }
+    (0..net[0].len())
+        .map(|j| net.iter().map(|row| row[j]).collect())
+        .collect()
+}
+
+/// Knot span index containing `u` (A2.1)
+pub(crate) fn find_span(n_ctrl: usize, degree: usize, u: f32, knots: &[f32]) -> usize {
+    let n = n_ctrl - 1;
+    if u >= knots[n + 1] {
+        // Last non-empty span
+        let mut span = n;
+        while span > degree && knots[span] >= knots[n + 1] {
+            span -= 1;
+        }
+        return span;
+    }
+    if u <= knots[degree] {
+        let mut span = degree;
+        while span < n && knots[span + 1] <= u {
+            span += 1;
+        }
+        return span;
+    }
+    let (mut low, mut high) = (degree, n + 1);
+    let mut mid = (low + high) / 2;
+    while u < knots[mid] || u >= knots[mid + 1] {
+        if u < knots[mid] {
+            high = mid;
+        } else {
+            low = mid;
+        }
+        mid = (low + high) / 2;
+    }
+    mid
+}
+
+/// Basis functions and their derivatives up to `order` (A2.3)
+fn ders_basis_funs(span: usize, u: f32, p: usize, order: usize, knots: &[f32]) -> Vec<Vec<f32>> {
+    let mut ndu = vec![vec![0.0f32; p + 1]; p + 1];
+    let mut left = vec![0.0f32; p + 1];
+    let mut right = vec![0.0f32; p + 1];
+    ndu[0][0] = 1.0;
+    for j in 1..=p {
+        left[j] = u - knots[span + 1 - j];
+        right[j] = knots[span + j] - u;
+        let mut saved = 0.0;
+        for r in 0..j {
+            ndu[j][r] = right[r + 1] + left[j - r];
+            let temp = if ndu[j][r].abs() < f32::EPSILON {
+                0.0
+            } else {
+                ndu[r][j - 1] / ndu[j][r]
+            };
+            ndu[r][j] = saved + right[r + 1] * temp;
+            saved = left[j - r] * temp;
+        }
+        ndu[j][j] = saved;
+    }
+
+    let mut ders = vec![vec![0.0f32; p + 1]; order + 1];
+    for j in 0..=p {
+        ders[0][j] = ndu[j][p];
+    }
+    let mut a = vec![vec![0.0f32; p + 1]; 2];
+    for r in 0..=p {
+        let (mut s1, mut s2) = (0usize, 1usize);
+        a[0][0] = 1.0;
+        for k in 1..=order.min(p) {
+            let mut d = 0.0;
+            let rk = r as isize - k as isize;
+            let pk = p - k;
+            if r >= k {
+                let rk = rk as usize;
+                a[s2][0] = a[s1][0] / ndu[pk + 1][rk];
+                d = a[s2][0] * ndu[rk][pk];
+            }
+            let j1 = if rk >= -1 { 1 } else { (-rk) as usize };
+            let j2 = if r as isize - 1 <= pk as isize {
+                k - 1
+            } else {
+                p - r
+            };
+            for j in j1..=j2 {
+                let idx = (rk + j as isize) as usize;
+                a[s2][j] = (a[s1][j] - a[s1][j - 1]) / ndu[pk + 1][idx];
+                d += a[s2][j] * ndu[idx][pk];
+            }
+            if r <= pk {
+                a[s2][k] = -a[s1][k - 1] / ndu[pk + 1][r];
+                d += a[s2][k] * ndu[r][pk];
+            }
+            ders[k][r] = d;
+            std::mem::swap(&mut s1, &mut s2);
+        }
+    }
+    let mut factor = p as f32;
+    for (k, row) in ders.iter_mut().enumerate().take(order.min(p) + 1).skip(1) {
+        for v in row.iter_mut() {
+            *v *= factor;
+        }
+        factor *= (p - k) as f32;
+    }
+    // Derivatives above the degree vanish (rows beyond p stay zero).
+    ders
+}
+
+/// De Boor's triangular scheme on homogeneous points
+fn de_boor(pw: &[Vec4], knots: &[f32], p: usize, span: usize, u: f32) -> Vec4 {
+    let mut d: Vec<Vec4> = (0..=p).map(|j| pw[span - p + j]).collect();
+    for r in 1..=p {
+        for j in (r..=p).rev() {
+            let i = span - p + j;
+            let denom = knots[i + p + 1 - r] - knots[i];
+            let alpha = if denom.abs() < f32::EPSILON {
+                0.0
+            } else {
+                (u - knots[i]) / denom
+            };
+            d[j] = d[j - 1] * (1.0 - alpha) + d[j] * alpha;
+        }
+    }
+    d[p]
+}
+
+/// Cartesian derivatives from homogeneous ones (A4.2)
+fn rational_curve_derivs(aw: &[Vec4]) -> Vec<Vec3> {
+    let mut ck: Vec<Vec3> = Vec::with_capacity(aw.len());
+    for k in 0..aw.len() {
+        let mut v = aw[k].truncate();
+        for i in 1..=k {
+            v -= ck[k - i] * (binomial(k, i) * aw[i].w);
+        }
+        ck.push(v / aw[0].w);
+    }
+    ck
+}
+
+fn knot_multiplicity(knots: &[f32], u: f32) -> usize {
+    knots.iter().filter(|k| (*k - u).abs() <= TOLERANCE).count()
+}
+
+/// Boehm knot insertion (A5.1)
+fn insert_knot_h(
+    pw: &[Vec4],
+    knots: &[f32],
+    p: usize,
+    u: f32,
+    times: usize,
+) -> (Vec<Vec4>, Vec<f32>) {
+    let n = pw.len() - 1;
+    let (a, b) = (knots[p], knots[n + 1]);
+    if u <= a || u >= b {
+        return (pw.to_vec(), knots.to_vec());
+    }
+    let s = knot_multiplicity(knots, u);
+    if s >= p {
+        return (pw.to_vec(), knots.to_vec());
+    }
+    let r = times.min(p - s);
+    if r == 0 {
+        return (pw.to_vec(), knots.to_vec());
+    }
+    let k = find_span(pw.len(), p, u, knots);
+
+    let mut new_knots = Vec::with_capacity(knots.len() + r);
+    new_knots.extend_from_slice(&knots[..=k]);
+    new_knots.extend(std::iter::repeat_n(u, r));
+    new_knots.extend_from_slice(&knots[k + 1..]);
+
+    let mut q = vec![Vec4::ZERO; n + 1 + r];
+    q[..=(k - p)].copy_from_slice(&pw[..=(k - p)]);
+    q[(k - s + r)..].copy_from_slice(&pw[(k - s)..]);
+    let mut rw: Vec<Vec4> = (0..=(p - s)).map(|i| pw[k - p + i]).collect();
+    let mut l = k - p;
+    for j in 1..=r {
+        l = k - p + j;
+        for i in 0..=(p - j - s) {
+            let alpha = (u - knots[l + i]) / (knots[i + k + 1] - knots[l + i]);
+            rw[i] = rw[i + 1] * alpha + rw[i] * (1.0 - alpha);
+        }
+        q[l] = rw[0];
+        q[k + r - j - s] = rw[p - j - s];
+    }
+    if l + 1 < k - s {
+        q[(l + 1)..(k - s)].copy_from_slice(&rw[1..(k - s - l)]);
+    }
+    (q, new_knots)
+}
+
+/// Degree elevation via Bezier decomposition
+fn elevate_degree_h(
+    pw: &[Vec4],
+    knots: &[f32],
+    p: usize,
+    t: usize,
+) -> Option<(Vec<Vec4>, Vec<f32>)> {
+    let n = pw.len() - 1;
+    let (a, b) = (knots[p], knots[n + 1]);
+    let clamped = knots[..=p].iter().all(|k| (k - a).abs() <= TOLERANCE)
+        && knots[n + 1..].iter().all(|k| (k - b).abs() <= TOLERANCE);
+    if !clamped {
+        return None;
+    }
+
+    // Distinct interior knots
+    let mut interior: Vec<f32> = Vec::new();
+    for &k in &knots[p + 1..=n] {
+        if interior
+            .last()
+            .is_none_or(|last| (k - last).abs() > TOLERANCE)
+        {
+            interior.push(k);
+        }
+    }
+
+    // Split into Bezier segments (interior multiplicity p)
+    let mut pts = pw.to_vec();
+    let mut ks = knots.to_vec();
+    for &u in &interior {
+        let s = knot_multiplicity(&ks, u);
+        if s < p {
+            let (np, nk) = insert_knot_h(&pts, &ks, p, u, p - s);
+            pts = np;
+            ks = nk;
+        }
+    }
+
+    let q = p + t;
+    let segments = interior.len() + 1;
+    let mut out: Vec<Vec4> = Vec::with_capacity(segments * q + 1);
+    for seg in 0..segments {
+        let ctrl = &pts[seg * p..=seg * p + p];
+        let elevated: Vec<Vec4> = (0..=q)
+            .map(|i| {
+                let lo = i.saturating_sub(t);
+                let hi = i.min(p);
+                (lo..=hi).fold(Vec4::ZERO, |acc, j| {
+                    acc + ctrl[j] * (binomial(p, j) * binomial(t, i - j) / binomial(q, i))
+                })
+            })
+            .collect();
+        let skip = if seg == 0 { 0 } else { 1 };
+        out.extend_from_slice(&elevated[skip..]);
+    }
+
+    let mut new_knots = vec![a; q + 1];
+    for &u in &interior {
+        new_knots.extend(std::iter::repeat_n(u, q));
+    }
+    new_knots.extend(std::iter::repeat_n(b, q + 1));
+    Some((out, new_knots))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Quarter circle of radius 1 in the XY plane (exact rational quadratic)
+    fn quarter_circle() -> NurbsCurve {
+        let w = std::f32::consts::FRAC_1_SQRT_2;
+        NurbsCurve::new(
+            vec![
+                Point3::new(1.0, 0.0, 0.0),
+                Point3::new(1.0, 1.0, 0.0),
+                Point3::new(0.0, 1.0, 0.0),
+            ],
+            vec![1.0, w, 1.0],
+            vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0],
+            2,
+        )
+        .unwrap()
+    }
+
+    /// Cubic with one interior knot
+    fn cubic() -> NurbsCurve {
+        NurbsCurve::new(
+            vec![
+                Point3::new(0.0, 0.0, 0.0),
+                Point3::new(1.0, 2.0, 0.0),
+                Point3::new(3.0, 2.0, 1.0),
+                Point3::new(4.0, 0.0, 1.0),
+                Point3::new(5.0, -1.0, 0.0),
+            ],
+            vec![1.0, 2.0, 1.0, 1.0, 1.0],
+            vec![0.0, 0.0, 0.0, 0.0, 0.5, 1.0, 1.0, 1.0, 1.0],
+            3,
+        )
+        .unwrap()
+    }
+
+    fn saddle() -> NurbsSurface {
+        let mut pts = Vec::new();
+        for i in 0..4 {
+            let mut row = Vec::new();
+            for j in 0..4 {
+                let (x, y) = (i as f32, j as f32);
+                row.push(Point3::new(x, y, 0.2 * (x - 1.5) * (y - 1.5)));
+            }
+            pts.push(row);
+        }
+        let knots = vec![0.0, 0.0, 0.0, 0.5, 1.0, 1.0, 1.0];
+        NurbsSurface::new(pts, vec![vec![1.0; 4]; 4], knots.clone(), knots, 2, 2).unwrap()
+    }
+
+    #[test]
+    fn test_rational_circle_is_exact() {
+        let c = quarter_circle();
+        for i in 0..=10 {
+            let u = i as f32 / 10.0;
+            let p = c.point_at(u);
+            assert!((p.to_vec3().length() - 1.0).abs() < 1e-5);
+            // Tangent is perpendicular to the radius
+            assert!(c.tangent_at(u).dot(p - Point3::ORIGIN).abs() < 1e-4);
+        }
+        assert!(c.point_at(1.0).approx_eq(Point3::new(0.0, 1.0, 0.0), 1e-6));
+    }
+
+    #[test]
+    fn test_derivatives_match_finite_differences() {
+        let c = cubic();
+        let h = 1e-3;
+        for &u in &[0.1f32, 0.45, 0.7] {
+            let d = c.derivatives(u, 2);
+            let fd = (c.point_at(u + h) - c.point_at(u - h)) * (0.5 / h);
+            assert!((d[1] - fd).length() < 1e-2 * d[1].length().max(1.0));
+        }
+    }
+
+    #[test]
+    fn test_knot_insertion_and_refinement_preserve_shape() {
+        let c = cubic();
+        let inserted = c.insert_knot(0.3, 2);
+        assert_eq!(inserted.control_points.len(), c.control_points.len() + 2);
+        let refined = c.refine_knots(&[0.25, 0.75, 0.9]);
+        assert_eq!(refined.knots.len(), c.knots.len() + 3);
+        for i in 0..=20 {
+            let u = i as f32 / 20.0;
+            assert!(c.point_at(u).approx_eq(inserted.point_at(u), 1e-4));
+            assert!(c.point_at(u).approx_eq(refined.point_at(u), 1e-4));
+        }
+    }
+
+    #[test]
+    fn test_degree_elevation_preserves_shape() {
+        for c in [cubic(), quarter_circle()] {
+            let e = c.elevate_degree(2).unwrap();
+            assert_eq!(e.degree, c.degree + 2);
+            assert_eq!(e.knots.len(), e.control_points.len() + e.degree + 1);
+            for i in 0..=20 {
+                let u = i as f32 / 20.0;
+                assert!(c.point_at(u).approx_eq(e.point_at(u), 1e-4));
+            }
+        }
+    }
+
+    #[test]
+    fn test_curve_point_inversion() {
+        let c = quarter_circle();
+        let (u, p) = c.closest_point(Point3::new(2.0, 2.0, 0.5));
+        let expected = std::f32::consts::FRAC_1_SQRT_2;
+        assert!(p.approx_eq(Point3::new(expected, expected, 0.0), 1e-4));
+        assert!(c.point_at(u).approx_eq(p, 1e-6));
+    }
+
+    #[test]
+    fn test_surface_evaluation_and_refinement() {
+        let s = saddle();
+        let refined = s.insert_knot_u(0.25, 1).insert_knot_v(0.8, 2);
+        let elevated = s.elevate_degree(1, 1).unwrap();
+        for i in 0..=5 {
+            for j in 0..=5 {
+                let (u, v) = (i as f32 / 5.0, j as f32 / 5.0);
+                let p = s.point_at(u, v);
+                assert!(p.approx_eq(refined.point_at(u, v), 1e-4));
+                assert!(p.approx_eq(elevated.point_at(u, v), 1e-4));
+            }
+        }
+        // Flat centre of the saddle faces +Z
+        let n = s.normal_at(0.5, 0.5);
+        assert!(n.z > 0.99);
+    }
+
+    #[test]
+    fn test_surface_point_inversion() {
+        let s = saddle();
+        let on_surface = s.point_at(0.3, 0.6);
+        let n = s.normal_at(0.3, 0.6);
+        let (u, v, p) = s.closest_point(on_surface + n * 0.25);
+        assert!((u - 0.3).abs() < 1e-3 && (v - 0.6).abs() < 1e-3);
+        assert!(p.approx_eq(on_surface, 1e-3));
+    }
+}
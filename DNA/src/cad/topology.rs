@@ -1,7 +1,7 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: topology.rs | DNA/src/cad/topology.rs
 //! PURPOSE: B-Rep topology primitives (Vertex, Edge, Face, Shell, Solid)
-//! MODIFIED: 2025-12-09
+//! MODIFIED: 2026-10-18
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
 
@@ -36,6 +36,7 @@
 //!
 //! DEPENDS ON:
 //!   • DNA/src/cad/geometry.rs → Point3, Vector3, etc.
+//!   • DNA/src/cad/nurbs.rs    → NURBS evaluation
 //!
 //! USED BY:
 //!   • CORE/CAD_ENGINE → Solid modeling operations
@@ -43,6 +44,7 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 
 use super::geometry::{BoundingBox3, Point3, Segment, Vector3, TOLERANCE};
+use super::nurbs::{NurbsCurve, NurbsSurface};
 
 /// Handle to a vertex in a B-Rep structure
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -131,72 +133,16 @@ impl CurveType {
                 let z = center.z + radius * (angle.cos() * u.z + angle.sin() * v.z);
                 Point3::new(x, y, z)
             }
-            CurveType::Nurbs {
-                control_points,
-                weights,
-                knots,
-                degree,
-            } => {
-                // De Boor's algorithm for NURBS evaluation
-                nurbs_eval(control_points, weights, knots, *degree, t)
-            }
-        }
-    }
-}
-
-/// Evaluate NURBS curve at parameter t using De Boor's algorithm
-fn nurbs_eval(
-    control_points: &[Point3],
-    weights: &[f32],
-    knots: &[f32],
-    degree: u32,
-    t: f32,
-) -> Point3 {
-    let n = control_points.len();
-    if n == 0 {
-        return Point3::ORIGIN;
-    }
-
-    // Find knot span
-    let mut span = degree as usize;
-    for i in (degree as usize)..n {
-        if t >= knots[i] && t < knots[i + 1] {
-            span = i;
-            break;
-        }
-    }
-
-    // De Boor's algorithm
-    let mut d: Vec<(Point3, f32)> = (0..=degree as usize)
-        .map(|j| {
-            let idx = span - degree as usize + j;
-            (control_points[idx.min(n - 1)], weights[idx.min(n - 1)])
-        })
-        .collect();
-
-    for r in 1..=degree as usize {
-        for j in (r..=degree as usize).rev() {
-            let i = span - degree as usize + j;
-            let denom = knots[i + degree as usize + 1 - r] - knots[i];
-            let alpha = if denom.abs() < TOLERANCE {
-                0.0
-            } else {
-                (t - knots[i]) / denom
-            };
-
-            let w = (1.0 - alpha) * d[j - 1].1 + alpha * d[j].1;
-            let p = if w.abs() < TOLERANCE {
-                d[j].0
-            } else {
-                let p1 = d[j - 1].0.to_vec3() * d[j - 1].1;
-                let p2 = d[j].0.to_vec3() * d[j].1;
-                Point3::from_vec3((p1 * (1.0 - alpha) + p2 * alpha) / w)
-            };
-            d[j] = (p, w);
+            CurveType::Nurbs { .. } => match NurbsCurve::from_curve_type(self) {
+                Some(curve) => {
+                    // Map t onto the knot domain
+                    let (a, b) = curve.domain();
+                    curve.point_at(a + t * (b - a))
+                }
+                None => start.lerp(end, t),
+            },
         }
     }
-
-    d[degree as usize].0
 }
 
 /// Topological edge - bounded curve between two vertices
@@ -272,30 +218,64 @@ pub enum SurfaceType {
 }
 
 impl SurfaceType {
-    /// Get surface normal at a point (approximate for complex surfaces)
-    pub fn normal_at(&self, _point: Point3) -> Vector3 {
+    /// Get outward surface normal at a point on (or near) the surface
+    ///
+    /// Cones use the `make_cone` convention: `axis` points from the base towards the apex.
+    pub fn normal_at(&self, point: Point3) -> Vector3 {
         match self {
             SurfaceType::Planar { normal } => *normal,
-            SurfaceType::Cylindrical {
+            SurfaceType::Cylindrical { axis, center, .. } => {
+                // Radial direction from the axis
+                radial(point - *center, *axis).unwrap_or(*axis)
+            }
+            SurfaceType::Spherical { center, .. } => {
+                (point - *center).normalize().unwrap_or(Vector3::Z)
+            }
+            SurfaceType::Conical {
+                apex,
                 axis,
-                center: _,
-                radius: _,
+                half_angle,
             } => {
-                // Normal points radially outward from axis
-                *axis // Simplified - should compute radial direction
+                // Perpendicular to the generator line, tilted towards the apex
+                let axis = axis.normalize_or_z();
+                match radial(point - *apex, axis) {
+                    Some(r) => (r * half_angle.cos() + axis * half_angle.sin()).normalize_or_z(),
+                    None => -axis,
+                }
             }
-            SurfaceType::Spherical {
-                center: _,
-                radius: _,
+            SurfaceType::Toroidal {
+                center,
+                axis,
+                major_radius,
+                ..
             } => {
-                // Normal points radially outward from center
-                Vector3::Z // Simplified - should compute from point to center
+                // Away from the nearest point on the spine circle
+                let axis = axis.normalize_or_z();
+                match radial(point - *center, axis) {
+                    Some(r) => {
+                        let spine = *center + r * *major_radius;
+                        (point - spine).normalize().unwrap_or(r)
+                    }
+                    None => axis,
+                }
             }
-            _ => Vector3::Z, // Placeholder for complex surfaces
+            SurfaceType::Nurbs { .. } => match NurbsSurface::from_surface_type(self) {
+                Some(surface) => {
+                    let (u, v, _) = surface.closest_point(point);
+                    surface.normal_at(u, v)
+                }
+                None => Vector3::Z,
+            },
         }
     }
 }
 
+/// Unit component of `offset` perpendicular to `axis` (None on the axis)
+fn radial(offset: Vector3, axis: Vector3) -> Option<Vector3> {
+    let axis = axis.normalize()?;
+    (offset - axis * offset.dot(axis)).normalize()
+}
+
 /// Loop - closed sequence of half-edges bounding a face
 #[derive(Clone, Debug)]
 pub struct Loop {
@@ -496,6 +476,61 @@ impl Solid {
         self.faces.iter_mut().find(|f| f.id == id)
     }
 
+    /// Boundary points of a loop in traversal order
+    ///
+    /// Edges are chained head-to-tail through shared vertices (loops are not
+    /// guaranteed to list them in order). Curved edges contribute
+    /// `curve_samples` points each, linear edges one.
+    pub fn loop_points(&self, loop_: &Loop, curve_samples: usize) -> Vec<Point3> {
+        let edges: Vec<(&Edge, bool)> = loop_
+            .edges
+            .iter()
+            .zip(loop_.directions.iter())
+            .filter_map(|(&id, &forward)| self.edge(id).map(|e| (e, forward)))
+            .collect();
+        let Some(&(first, forward)) = edges.first() else {
+            return Vec::new();
+        };
+
+        let mut points = Vec::new();
+        let mut used = vec![false; edges.len()];
+        used[0] = true;
+        let mut current = self.sample_edge(first, forward, curve_samples, &mut points);
+        while let Some(i) = (0..edges.len())
+            .find(|&i| !used[i] && (edges[i].0.start == current || edges[i].0.end == current))
+        {
+            used[i] = true;
+            let edge = edges[i].0;
+            let forward = edge.start == current;
+            current = self.sample_edge(edge, forward, curve_samples, &mut points);
+        }
+        points
+    }
+
+    /// Append edge samples (excluding the far end); returns the far vertex
+    fn sample_edge(
+        &self,
+        edge: &Edge,
+        forward: bool,
+        curve_samples: usize,
+        out: &mut Vec<Point3>,
+    ) -> VertexId {
+        let far = if forward { edge.end } else { edge.start };
+        let (Some(start), Some(end)) = (self.vertex(edge.start), self.vertex(edge.end)) else {
+            return far;
+        };
+        let samples = match edge.curve {
+            CurveType::Linear => 1,
+            _ => curve_samples.max(1),
+        };
+        for k in 0..samples {
+            let s = k as f32 / samples as f32;
+            let t = if forward { s } else { 1.0 - s };
+            out.push(edge.curve.point_at(start.point, end.point, t));
+        }
+        far
+    }
+
     /// Check if solid is valid (basic topology checks)
     pub fn is_valid(&self) -> bool {
         // Check all edges reference valid vertices
@@ -572,6 +607,63 @@ mod tests {
         assert!((mid.x - 5.0).abs() < TOLERANCE);
     }
 
+    #[test]
+    fn test_nurbs_curve_uses_normalized_parameter() {
+        // Straight quadratic on knots [2, 4] - t = 0.5 maps to the domain midpoint
+        let curve = CurveType::Nurbs {
+            control_points: vec![
+                Point3::new(0.0, 0.0, 0.0),
+                Point3::new(1.0, 0.0, 0.0),
+                Point3::new(2.0, 0.0, 0.0),
+            ],
+            weights: vec![1.0; 3],
+            knots: vec![2.0, 2.0, 2.0, 4.0, 4.0, 4.0],
+            degree: 2,
+        };
+        let start = Point3::new(0.0, 0.0, 0.0);
+        let end = Point3::new(2.0, 0.0, 0.0);
+        assert!(curve
+            .point_at(start, end, 0.5)
+            .approx_eq(Point3::new(1.0, 0.0, 0.0), 1e-6));
+        assert!(curve.point_at(start, end, 1.0).approx_eq(end, 1e-6));
+    }
+
+    #[test]
+    fn test_analytic_surface_normals() {
+        let p = Point3::new(3.0, 0.0, 5.0);
+        let cylinder = SurfaceType::Cylindrical {
+            axis: Vector3::Z,
+            center: Point3::ORIGIN,
+            radius: 3.0,
+        };
+        assert!((cylinder.normal_at(p) - Vector3::X).length() < 1e-6);
+
+        let sphere = SurfaceType::Spherical {
+            center: Point3::new(0.0, 0.0, 1.0),
+            radius: 2.0,
+        };
+        assert!((sphere.normal_at(Point3::new(0.0, 0.0, -1.0)) - Vector3::NEG_Z).length() < 1e-6);
+
+        // 45° cone, apex above the base: normal tilts up and out
+        let cone = SurfaceType::Conical {
+            apex: Point3::new(0.0, 0.0, 1.0),
+            axis: Vector3::Z,
+            half_angle: std::f32::consts::FRAC_PI_4,
+        };
+        let n = cone.normal_at(Point3::new(1.0, 0.0, 0.0));
+        let h = std::f32::consts::FRAC_1_SQRT_2;
+        assert!((n - Vector3::new(h, 0.0, h)).length() < 1e-6);
+
+        let torus = SurfaceType::Toroidal {
+            center: Point3::ORIGIN,
+            axis: Vector3::Z,
+            major_radius: 5.0,
+            minor_radius: 1.0,
+        };
+        assert!((torus.normal_at(Point3::new(0.0, 5.0, 1.0)) - Vector3::Z).length() < 1e-6);
+        assert!((torus.normal_at(Point3::new(-4.0, 0.0, 0.0)) - Vector3::X).length() < 1e-6);
+    }
+
     #[test]
     fn test_loop() {
         let mut loop_ = Loop::new();
@@ -0,0 +1,45 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: mod.rs | DNA/src/cam/mod.rs
+//! PURPOSE: 2.5D CAM - profiles, toolpaths and G-code post-processors
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! PURPOSE: 2.5D CAM - profiles, toolpaths and G-code post-processors
+//!
+//! LAYER: DNA → CAM
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ MODULE STRUCTURE                                                            │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │                                                                             │
+//! │   cam/                                                                      │
+//! │   ├── profile.rs   Closed 2D profiles, regions, offsetting, planar faces    │
+//! │   ├── toolpath.rs  Tools, feeds/speeds, contour / pocket / drill ops        │
+//! │   └── post.rs      CamProgram → G-code (GRBL, LinuxCNC)                     │
+//! │                                                                             │
+//! │   Profile / Region ──► Operation (moves) ──► CamProgram ──► G-code text     │
+//! │                                                                             │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! Coordinates are in program units (mm or inches); Z is up and the stock top
+//! is given per operation. Tool radius compensation is computed here, so the
+//! emitted G-code never relies on controller G41/G42.
+//!
+//! DEPENDS ON:
+//!   • glam                  → DVec2 / DVec3
+//!   • DNA/src/cad/topology  → planar faces of a Solid
+//!
+//! USED BY:
+//!   • CORE/EXPORT_ENGINE → ExportFormat::GCode
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+pub mod post;
+pub mod profile;
+pub mod toolpath;
+
+pub use post::*;
+pub use profile::*;
+pub use toolpath::*;
@@ -0,0 +1,453 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: post.rs | DNA/src/cam/post.rs
+//! PURPOSE: CamProgram and G-code post-processors (GRBL, LinuxCNC)
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ DIALECT DIFFERENCES                                                         │
+//! ├──────────────────┬─────────────────────────┬────────────────────────────────┤
+//! │                  │ GRBL                    │ LinuxCNC                       │
+//! ├──────────────────┼─────────────────────────┼────────────────────────────────┤
+//! │ Tool change      │ M5 + M0 pause, comment  │ Tn M6 + G43 Hn                 │
+//! │ Drill cycles     │ Expanded to G0/G1       │ G81 / G83 (G98), G80 cancel    │
+//! │ Path blending    │ -                       │ G64 P<tolerance>               │
+//! │ Program wrapper  │ M30                     │ % ... M2 %                     │
+//! └──────────────────┴─────────────────────────┴────────────────────────────────┘
+//!
+//! Output is modal: unchanged axis words and feed rates are not repeated.
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use std::fmt::Write;
+
+use glam::{DVec2, DVec3};
+
+use super::toolpath::{Move, Operation};
+
+/// Program units (G20 / G21)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GCodeUnits {
+    Millimetres,
+    Inches,
+}
+
+/// Target controller dialect
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PostProcessor {
+    Grbl,
+    LinuxCnc,
+}
+
+impl PostProcessor {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PostProcessor::Grbl => "GRBL",
+            PostProcessor::LinuxCnc => "LinuxCNC",
+        }
+    }
+}
+
+/// Ordered list of operations making up one CNC program
+#[derive(Clone, Debug, PartialEq)]
+pub struct CamProgram {
+    pub name: String,
+    pub units: GCodeUnits,
+    pub operations: Vec<Operation>,
+}
+
+impl CamProgram {
+    pub fn new(name: &str, units: GCodeUnits) -> Self {
+        Self {
+            name: name.to_string(),
+            units,
+            operations: Vec::new(),
+        }
+    }
+
+    pub fn add_operation(&mut self, operation: Operation) {
+        self.operations.push(operation);
+    }
+
+    /// Post-process to G-code text
+    pub fn to_gcode(&self, post: PostProcessor) -> String {
+        let mut w = GCodeWriter::new(post, self.units);
+        w.header(&self.name);
+        let mut current_tool = None;
+        for op in &self.operations {
+            w.operation(op, &mut current_tool);
+        }
+        w.footer();
+        w.out
+    }
+
+    pub fn to_bytes(&self, post: PostProcessor) -> Vec<u8> {
+        self.to_gcode(post).into_bytes()
+    }
+}
+
+/// Rapid re-entry gap above the previous peck when expanding G83
+const PECK_CLEARANCE: f64 = 0.25;
+
+/// Modal G-code emitter
+struct GCodeWriter {
+    post: PostProcessor,
+    units: GCodeUnits,
+    out: String,
+    /// Last emitted X, Y, Z words
+    axes: [Option<String>; 3],
+    feed: Option<String>,
+    position: DVec3,
+    in_cycle: bool,
+}
+
+impl GCodeWriter {
+    fn new(post: PostProcessor, units: GCodeUnits) -> Self {
+        Self {
+            post,
+            units,
+            out: String::new(),
+            axes: [None, None, None],
+            feed: None,
+            position: DVec3::ZERO,
+            in_cycle: false,
+        }
+    }
+
+    fn line(&mut self, text: &str) {
+        self.out.push_str(text);
+        self.out.push('\n');
+    }
+
+    fn comment(&mut self, text: &str) {
+        let mut clean: String = text
+            .chars()
+            .map(|c| match c {
+                '(' => '[',
+                ')' => ']',
+                c if c.is_control() => ' ',
+                c => c,
+            })
+            .collect();
+        // GRBL's line buffer is small
+        if self.post == PostProcessor::Grbl && clean.len() > 70 {
+            clean = clean.chars().take(70).collect();
+        }
+        self.line(&format!("({})", clean));
+    }
+
+    fn num(&self, value: f64) -> String {
+        let decimals = match self.units {
+            GCodeUnits::Millimetres => 3,
+            GCodeUnits::Inches => 4,
+        };
+        format_number(value, decimals)
+    }
+
+    fn header(&mut self, name: &str) {
+        if self.post == PostProcessor::LinuxCnc {
+            self.line("%");
+        }
+        self.comment(name);
+        self.comment(&format!("Post: {}", self.post.name()));
+        let units = match self.units {
+            GCodeUnits::Millimetres => "G21",
+            GCodeUnits::Inches => "G20",
+        };
+        match self.post {
+            PostProcessor::Grbl => {
+                self.line("G90 G94 G17");
+                self.line(units);
+            }
+            PostProcessor::LinuxCnc => {
+                self.line("G17 G40 G49 G54 G80 G90 G94");
+                self.line(units);
+                let blend = match self.units {
+                    GCodeUnits::Millimetres => "G64 P0.01",
+                    GCodeUnits::Inches => "G64 P0.0005",
+                };
+                self.line(blend);
+            }
+        }
+    }
+
+    fn footer(&mut self) {
+        self.end_cycle();
+        self.line("M5");
+        match self.post {
+            PostProcessor::Grbl => self.line("M30"),
+            PostProcessor::LinuxCnc => {
+                self.line("M2");
+                self.line("%");
+            }
+        }
+    }
+
+    fn operation(&mut self, op: &Operation, current_tool: &mut Option<u32>) {
+        self.end_cycle();
+        self.comment(&format!("Operation: {} - {}", op.name, op.tool.describe()));
+        if *current_tool != Some(op.tool.number) {
+            match self.post {
+                PostProcessor::Grbl => {
+                    // No tool changer: stop the spindle and pause for a manual change
+                    if current_tool.is_some() {
+                        self.line("M5");
+                        self.comment(&format!("Change to {}", op.tool.describe()));
+                        self.line("M0");
+                    }
+                }
+                PostProcessor::LinuxCnc => {
+                    self.line(&format!("T{} M6", op.tool.number));
+                    self.line(&format!("G43 H{}", op.tool.number));
+                }
+            }
+            *current_tool = Some(op.tool.number);
+        }
+        self.line(&format!("M3 S{}", op.spindle_rpm.round() as i64));
+        let safe = self.num(op.safe_z);
+        self.line(&format!("G0 Z{}", safe));
+        self.axes[2] = Some(safe);
+        self.position.z = op.safe_z;
+
+        for m in &op.moves {
+            self.motion(m);
+        }
+    }
+
+    fn end_cycle(&mut self) {
+        if self.in_cycle {
+            self.line("G80");
+            self.in_cycle = false;
+        }
+    }
+
+    /// Axis words for a move, skipping unchanged ones (arcs always carry X/Y)
+    fn axis_words(&mut self, to: DVec3, force_xy: bool) -> String {
+        let mut words = String::new();
+        for (i, (letter, value)) in [('X', to.x), ('Y', to.y), ('Z', to.z)]
+            .into_iter()
+            .enumerate()
+        {
+            let text = self.num(value);
+            if (force_xy && i < 2) || self.axes[i].as_deref() != Some(text.as_str()) {
+                let _ = write!(words, " {}{}", letter, text);
+                self.axes[i] = Some(text);
+            }
+        }
+        self.position = to;
+        words
+    }
+
+    fn feed_word(&mut self, feed: f64) -> String {
+        let text = format_number(feed, 1);
+        if self.feed.as_deref() == Some(text.as_str()) {
+            String::new()
+        } else {
+            self.feed = Some(text.clone());
+            format!(" F{}", text)
+        }
+    }
+
+    fn motion(&mut self, m: &Move) {
+        if !matches!(m, Move::Drill { .. }) {
+            self.end_cycle();
+        }
+        match *m {
+            Move::Rapid(to) => {
+                let words = self.axis_words(to, false);
+                if !words.is_empty() {
+                    self.line(&format!("G0{}", words));
+                }
+            }
+            Move::Linear { to, feed } => {
+                let words = self.axis_words(to, false);
+                if !words.is_empty() {
+                    let f = self.feed_word(feed);
+                    self.line(&format!("G1{}{}", words, f));
+                }
+            }
+            Move::Arc {
+                to,
+                center,
+                clockwise,
+                feed,
+            } => {
+                let offset = center - self.position.truncate();
+                let (i, j) = (self.num(offset.x), self.num(offset.y));
+                let words = self.axis_words(to, true);
+                let f = self.feed_word(feed);
+                let g = if clockwise { "G2" } else { "G3" };
+                self.line(&format!("{}{} I{} J{}{}", g, words, i, j, f));
+            }
+            Move::Drill {
+                at,
+                retract_z,
+                bottom_z,
+                peck,
+                feed,
+            } => match self.post {
+                PostProcessor::LinuxCnc => self.canned_drill(at, retract_z, bottom_z, peck, feed),
+                PostProcessor::Grbl => self.expanded_drill(at, retract_z, bottom_z, peck, feed),
+            },
+        }
+    }
+
+    fn canned_drill(
+        &mut self,
+        at: DVec2,
+        retract_z: f64,
+        bottom_z: f64,
+        peck: Option<f64>,
+        feed: f64,
+    ) {
+        let (x, y) = (self.num(at.x), self.num(at.y));
+        let (z, r) = (self.num(bottom_z), self.num(retract_z));
+        let f = format_number(feed, 1);
+        let cycle = match peck {
+            Some(q) => format!(
+                "G98 G83 X{} Y{} Z{} R{} Q{} F{}",
+                x,
+                y,
+                z,
+                r,
+                self.num(q),
+                f
+            ),
+            None => format!("G98 G81 X{} Y{} Z{} R{} F{}", x, y, z, r, f),
+        };
+        self.line(&cycle);
+        self.in_cycle = true;
+        // G98 returns to the initial level (or R, if that is higher)
+        let initial_z = self.position.z.max(retract_z);
+        self.axes = [Some(x), Some(y), Some(self.num(initial_z))];
+        self.feed = Some(f);
+        self.position = at.extend(initial_z);
+    }
+
+    fn expanded_drill(
+        &mut self,
+        at: DVec2,
+        retract_z: f64,
+        bottom_z: f64,
+        peck: Option<f64>,
+        feed: f64,
+    ) {
+        let initial_z = self.position.z.max(retract_z);
+        self.motion(&Move::Rapid(at.extend(initial_z)));
+        self.motion(&Move::Rapid(at.extend(retract_z)));
+        // G81 is a single stroke; G83 pecks back out to R between strokes
+        let step = peck.unwrap_or(f64::INFINITY);
+        let mut depth = retract_z;
+        while depth > bottom_z + 1e-9 {
+            if depth < retract_z {
+                // Rapid back down to just above the previous peck
+                self.motion(&Move::Rapid(at.extend(depth + PECK_CLEARANCE.min(step))));
+            }
+            depth = (depth - step).max(bottom_z);
+            self.motion(&Move::Linear {
+                to: at.extend(depth),
+                feed,
+            });
+            self.motion(&Move::Rapid(at.extend(retract_z)));
+        }
+        self.motion(&Move::Rapid(at.extend(initial_z)));
+    }
+}
+
+/// Fixed-point number with trailing zeros trimmed ("-0" normalised to "0")
+fn format_number(value: f64, decimals: usize) -> String {
+    let mut text = format!("{:.*}", decimals, value);
+    if text.contains('.') {
+        while text.ends_with('0') {
+            text.pop();
+        }
+        if text.ends_with('.') {
+            text.pop();
+        }
+    }
+    if text == "-0" {
+        text = "0".to_string();
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cam::{contour, drill, CutParams, CutSide, FeedsAndSpeeds, Profile, Tool};
+
+    fn program(units: GCodeUnits) -> CamProgram {
+        let feeds = FeedsAndSpeeds::new(18000.0, 1000.0, 300.0);
+        let params = CutParams::new(feeds, 3.0).with_step_down(1.5);
+        let mut program = CamProgram::new("Panel (A)", units);
+        program.add_operation(
+            contour(
+                "Outline",
+                &Profile::rectangle(0.0, 0.0, 100.0, 50.0),
+                CutSide::Outside,
+                &Tool::flat_end_mill(1, 6.0, 2),
+                &params,
+            )
+            .unwrap(),
+        );
+        program.add_operation(
+            drill(
+                "Holes",
+                &[DVec2::new(10.0, 10.0), DVec2::new(90.0, 10.0)],
+                &Tool::drill(2, 5.0),
+                &params,
+                Some(1.0),
+            )
+            .unwrap(),
+        );
+        program
+    }
+
+    #[test]
+    fn test_grbl_dialect() {
+        let gcode = program(GCodeUnits::Millimetres).to_gcode(PostProcessor::Grbl);
+        assert!(gcode.starts_with("(Panel [A])\n(Post: GRBL)\nG90 G94 G17\nG21\n"));
+        assert!(gcode.contains("M3 S18000"));
+        // No tool changer or canned cycles on GRBL
+        assert!(!gcode.contains("M6") && !gcode.contains("G83") && !gcode.contains("G43"));
+        assert!(gcode.contains("M5\n(Change to T2 D5.000 drill)\nM0\n"));
+        // Pecks expanded: 1 mm strokes from the R plane (1 above stock) to -3
+        assert!(gcode.contains("G0 X10 Y10\nG0 Z1\nG1 Z0 F300\nG0 Z1\nG0 Z0.25\nG1 Z-1\n"));
+        assert!(gcode.contains("G1 Z-3\nG0 Z1\nG0 Z5\n"));
+        assert!(gcode.trim_end().ends_with("M5\nM30"));
+    }
+
+    #[test]
+    fn test_linuxcnc_dialect() {
+        let gcode = program(GCodeUnits::Millimetres).to_gcode(PostProcessor::LinuxCnc);
+        assert!(gcode.starts_with("%\n"));
+        assert!(gcode.contains("G17 G40 G49 G54 G80 G90 G94\nG21\nG64 P0.01\n"));
+        assert!(gcode.contains("T1 M6\nG43 H1\n"));
+        assert!(gcode.contains("T2 M6\nG43 H2\n"));
+        assert!(gcode.contains("G98 G83 X10 Y10 Z-3 R1 Q1 F300"));
+        assert!(gcode.contains("G98 G83 X90 Y10 Z-3 R1 Q1 F300\nG80\n"));
+        assert!(gcode.trim_end().ends_with("M2\n%"));
+    }
+
+    #[test]
+    fn test_arcs_and_modal_words() {
+        let gcode = program(GCodeUnits::Millimetres).to_gcode(PostProcessor::Grbl);
+        // Climb outside: CW corner arcs with centre offsets relative to the arc start
+        assert!(gcode.contains("G2 X-3 Y0 I0 J3"));
+        // Straight edges repeat only the axis that changes
+        assert!(gcode.contains("G1 Y50\n"));
+        assert!(!gcode.contains("G3"));
+
+        let inches = program(GCodeUnits::Inches).to_gcode(PostProcessor::LinuxCnc);
+        assert!(inches.contains("G20\nG64 P0.0005"));
+    }
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!(format_number(1.5, 3), "1.5");
+        assert_eq!(format_number(-0.0001, 3), "0");
+        assert_eq!(format_number(12.0, 4), "12");
+        assert_eq!(format_number(-1.23456, 4), "-1.2346");
+    }
+}
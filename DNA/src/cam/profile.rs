@@ -0,0 +1,599 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: profile.rs | DNA/src/cam/profile.rs
+//! PURPOSE: Closed 2D profiles and regions with tool-radius offsetting
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+//!
+//! Offsetting moves every edge along its outward normal and re-joins neighbours:
+//! corners that open a gap get an arc around the original vertex (constant
+//! distance, as the cutter sees it), corners that overlap get a miter. Edges
+//! that flip direction are dropped and the joins recomputed. Offsets that
+//! would split the profile into several islands are rejected rather than
+//! guessed at.
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use glam::DVec2;
+
+use crate::cad::{FaceId, Solid, SurfaceType, Vector3};
+
+use super::toolpath::CamError;
+
+/// Samples per curved edge when flattening B-Rep loops
+const FACE_CURVE_SAMPLES: usize = 32;
+
+/// Closed 2D polygon (the closing edge is implicit)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Profile {
+    pub points: Vec<DVec2>,
+}
+
+/// Piece of an offset path
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PathSegment {
+    Line { to: DVec2 },
+    Arc { to: DVec2, center: DVec2, ccw: bool },
+}
+
+/// Closed path of lines and arcs starting (and ending) at `start`
+#[derive(Clone, Debug, PartialEq)]
+pub struct OffsetPath {
+    pub start: DVec2,
+    pub segments: Vec<PathSegment>,
+}
+
+/// Outer boundary with optional holes (outer CCW, holes CW)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Region {
+    pub outer: Profile,
+    pub holes: Vec<Profile>,
+}
+
+impl Profile {
+    /// Create a profile, dropping repeated points and an explicit closing point
+    pub fn new(points: Vec<DVec2>) -> Self {
+        let mut cleaned: Vec<DVec2> = Vec::with_capacity(points.len());
+        for p in points {
+            if cleaned.last().is_none_or(|last| last.distance(p) > 1e-9) {
+                cleaned.push(p);
+            }
+        }
+        while cleaned.len() > 1 && cleaned[0].distance(cleaned[cleaned.len() - 1]) <= 1e-9 {
+            cleaned.pop();
+        }
+        Self { points: cleaned }
+    }
+
+    /// Axis-aligned rectangle with its lower-left corner at (x, y)
+    pub fn rectangle(x: f64, y: f64, width: f64, height: f64) -> Self {
+        Self::new(vec![
+            DVec2::new(x, y),
+            DVec2::new(x + width, y),
+            DVec2::new(x + width, y + height),
+            DVec2::new(x, y + height),
+        ])
+    }
+
+    /// Regular polygon approximating a circle
+    pub fn circle(center: DVec2, radius: f64, segments: usize) -> Self {
+        let n = segments.max(3);
+        Self::new(
+            (0..n)
+                .map(|i| {
+                    let a = std::f64::consts::TAU * i as f64 / n as f64;
+                    center + DVec2::new(a.cos(), a.sin()) * radius
+                })
+                .collect(),
+        )
+    }
+
+    /// Signed area (positive for counter-clockwise)
+    pub fn signed_area(&self) -> f64 {
+        let n = self.points.len();
+        (0..n)
+            .map(|i| self.points[i].perp_dot(self.points[(i + 1) % n]))
+            .sum::<f64>()
+            * 0.5
+    }
+
+    pub fn is_ccw(&self) -> bool {
+        self.signed_area() > 0.0
+    }
+
+    pub fn reversed(&self) -> Self {
+        let mut points = self.points.clone();
+        points.reverse();
+        Self { points }
+    }
+
+    /// Same profile wound counter-clockwise (`true`) or clockwise (`false`)
+    pub fn oriented(&self, ccw: bool) -> Self {
+        if self.is_ccw() == ccw {
+            self.clone()
+        } else {
+            self.reversed()
+        }
+    }
+
+    /// Even-odd point containment
+    pub fn contains(&self, p: DVec2) -> bool {
+        let n = self.points.len();
+        let mut inside = false;
+        for i in 0..n {
+            let a = self.points[i];
+            let b = self.points[(i + 1) % n];
+            if (a.y > p.y) != (b.y > p.y) && p.x < a.x + (p.y - a.y) * (b.x - a.x) / (b.y - a.y) {
+                inside = !inside;
+            }
+        }
+        inside
+    }
+
+    /// (min, max) corners
+    pub fn bounds(&self) -> (DVec2, DVec2) {
+        self.points.iter().fold(
+            (DVec2::splat(f64::INFINITY), DVec2::splat(f64::NEG_INFINITY)),
+            |(lo, hi), p| (lo.min(*p), hi.max(*p)),
+        )
+    }
+
+    /// Distance from `p` to the nearest edge
+    pub fn distance_to(&self, p: DVec2) -> f64 {
+        let n = self.points.len();
+        (0..n)
+            .map(|i| segment_distance(p, self.points[i], self.points[(i + 1) % n]))
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    /// Offset outward by `distance` (negative shrinks) with arcs at opening corners.
+    ///
+    /// The result runs counter-clockwise; None when the profile vanishes or would split.
+    pub fn offset_path(&self, distance: f64) -> Option<OffsetPath> {
+        let base = self.oriented(true);
+        let pts = &base.points;
+        let n = pts.len();
+        if n < 3 || base.signed_area().abs() < 1e-12 {
+            return None;
+        }
+        if distance.abs() < 1e-12 {
+            return Some(OffsetPath::from_profile(&base));
+        }
+
+        let dir = |i: usize| (pts[(i + 1) % n] - pts[i]).normalize_or_zero();
+        let normal = |i: usize| {
+            let d = dir(i);
+            DVec2::new(d.y, -d.x)
+        };
+
+        // Surviving edges; drop reversed ones until the joins are consistent
+        let mut lines: Vec<usize> = (0..n)
+            .filter(|&i| pts[i].distance(pts[(i + 1) % n]) > 1e-12)
+            .collect();
+        let joins = loop {
+            if lines.len() < 3 {
+                return None;
+            }
+            let m = lines.len();
+            let joins: Vec<Option<Join>> = (0..m)
+                .map(|k| {
+                    join(
+                        pts,
+                        lines[(k + m - 1) % m],
+                        lines[k],
+                        distance,
+                        &dir,
+                        &normal,
+                    )
+                })
+                .collect();
+            // Anti-parallel neighbours can never meet: drop the first of the pair
+            if let Some(k) = joins.iter().position(Option::is_none) {
+                lines.remove((k + m - 1) % m);
+                continue;
+            }
+            let joins: Vec<Join> = joins.into_iter().flatten().collect();
+            // Edge k runs from the end of join k to the start of join k+1
+            let worst = (0..m)
+                .map(|k| {
+                    let from = joins[k].exit();
+                    let to = joins[(k + 1) % m].entry();
+                    (k, (to - from).dot(dir(lines[k])))
+                })
+                .filter(|(_, along)| *along < -1e-9)
+                .min_by(|a, b| a.1.total_cmp(&b.1));
+            match worst {
+                Some((k, _)) => {
+                    lines.remove(k);
+                }
+                None => break joins,
+            }
+        };
+
+        let mut path = OffsetPath {
+            start: joins[0].exit(),
+            segments: Vec::new(),
+        };
+        for k in 0..joins.len() {
+            let next = &joins[(k + 1) % joins.len()];
+            path.segments.push(PathSegment::Line { to: next.entry() });
+            if let Join::Arc {
+                to, center, ccw, ..
+            } = *next
+            {
+                path.segments.push(PathSegment::Arc { to, center, ccw });
+            }
+        }
+
+        // Reject offsets that folded over themselves
+        let flat = path.to_profile(0.1_f64.to_radians());
+        if flat.signed_area() <= 1e-12 {
+            return None;
+        }
+        let tolerance = distance.abs() * 1e-6 + 1e-9;
+        if flat
+            .points
+            .iter()
+            .any(|p| base.distance_to(*p) < distance.abs() - tolerance)
+        {
+            return None;
+        }
+        if distance < 0.0
+            && flat
+                .points
+                .iter()
+                .any(|p| !base.contains(*p) && base.distance_to(*p) > tolerance)
+        {
+            return None;
+        }
+        Some(path)
+    }
+
+    /// Offset flattened back to a polygon (arcs split every ~5°)
+    pub fn offset(&self, distance: f64) -> Option<Profile> {
+        self.offset_path(distance)
+            .map(|path| path.to_profile(5f64.to_radians()))
+    }
+}
+
+/// How two neighbouring offset edges meet
+#[derive(Clone, Copy, Debug)]
+enum Join {
+    Point(DVec2),
+    Arc {
+        from: DVec2,
+        to: DVec2,
+        center: DVec2,
+        ccw: bool,
+    },
+}
+
+impl Join {
+    fn entry(&self) -> DVec2 {
+        match *self {
+            Join::Point(p) => p,
+            Join::Arc { from, .. } => from,
+        }
+    }
+
+    fn exit(&self) -> DVec2 {
+        match *self {
+            Join::Point(p) => p,
+            Join::Arc { to, .. } => to,
+        }
+    }
+}
+
+/// Join between offset edges `prev` and `next`; None if they run anti-parallel apart
+fn join(
+    pts: &[DVec2],
+    prev: usize,
+    next: usize,
+    distance: f64,
+    dir: &dyn Fn(usize) -> DVec2,
+    normal: &dyn Fn(usize) -> DVec2,
+) -> Option<Join> {
+    let n = pts.len();
+    let (d0, d1) = (dir(prev), dir(next));
+    let turn = d0.perp_dot(d1);
+    let parallel = turn.abs() < 1e-12;
+    if (prev + 1) % n == next {
+        let vertex = pts[next];
+        let from = vertex + normal(prev) * distance;
+        let to = vertex + normal(next) * distance;
+        if parallel && d0.dot(d1) > 0.0 {
+            return Some(Join::Point(from));
+        }
+        // Offset edges pull apart: bridge with an arc around the vertex
+        if turn * distance > 0.0 || parallel {
+            return Some(Join::Arc {
+                from,
+                to,
+                center: vertex,
+                ccw: if parallel { distance > 0.0 } else { turn > 0.0 },
+            });
+        }
+    }
+    if parallel {
+        if d0.dot(d1) < 0.0 {
+            return None;
+        }
+        // Collinear after removing the edges in between
+        let mid = (pts[(prev + 1) % n] + pts[next]) * 0.5;
+        return Some(Join::Point(mid + normal(next) * distance));
+    }
+    // Offset lines overlap (or edges were removed between them): miter
+    let p0 = pts[prev] + normal(prev) * distance;
+    let p1 = pts[next] + normal(next) * distance;
+    let t = (p1 - p0).perp_dot(d1) / turn;
+    Some(Join::Point(p0 + d0 * t))
+}
+
+fn segment_distance(p: DVec2, a: DVec2, b: DVec2) -> f64 {
+    let ab = b - a;
+    let len2 = ab.length_squared();
+    let t = if len2 > 0.0 {
+        ((p - a).dot(ab) / len2).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    p.distance(a + ab * t)
+}
+
+impl OffsetPath {
+    /// Straight-edged path around a profile
+    pub fn from_profile(profile: &Profile) -> Self {
+        let mut segments: Vec<PathSegment> = profile
+            .points
+            .iter()
+            .skip(1)
+            .map(|&to| PathSegment::Line { to })
+            .collect();
+        segments.push(PathSegment::Line {
+            to: profile.points[0],
+        });
+        Self {
+            start: profile.points[0],
+            segments,
+        }
+    }
+
+    /// Same path traversed the other way
+    pub fn reversed(&self) -> Self {
+        let mut starts = vec![self.start];
+        for s in &self.segments[..self.segments.len().saturating_sub(1)] {
+            starts.push(s.end());
+        }
+        let end = self.segments.last().map_or(self.start, |s| s.end());
+        let segments = self
+            .segments
+            .iter()
+            .zip(starts)
+            .rev()
+            .map(|(s, from)| match *s {
+                PathSegment::Line { .. } => PathSegment::Line { to: from },
+                PathSegment::Arc { center, ccw, .. } => PathSegment::Arc {
+                    to: from,
+                    center,
+                    ccw: !ccw,
+                },
+            })
+            .collect();
+        Self {
+            start: end,
+            segments,
+        }
+    }
+
+    /// Flatten to a polygon, splitting arcs into steps of at most `max_angle` radians
+    pub fn to_profile(&self, max_angle: f64) -> Profile {
+        let mut points = vec![self.start];
+        let mut current = self.start;
+        for s in &self.segments {
+            match *s {
+                PathSegment::Line { to } => points.push(to),
+                PathSegment::Arc { to, center, ccw } => {
+                    let sweep = arc_sweep(current, to, center, ccw);
+                    let steps = (sweep.abs() / max_angle.max(1e-3)).ceil().max(1.0) as usize;
+                    let r = current.distance(center);
+                    let a0 = (current - center).to_angle();
+                    for i in 1..=steps {
+                        let a = a0 + sweep * i as f64 / steps as f64;
+                        points.push(center + DVec2::from_angle(a) * r);
+                    }
+                }
+            }
+            current = s.end();
+        }
+        Profile::new(points)
+    }
+}
+
+impl PathSegment {
+    pub fn end(&self) -> DVec2 {
+        match *self {
+            PathSegment::Line { to } | PathSegment::Arc { to, .. } => to,
+        }
+    }
+}
+
+/// Signed sweep angle from `from` to `to` around `center`
+pub(crate) fn arc_sweep(from: DVec2, to: DVec2, center: DVec2, ccw: bool) -> f64 {
+    let a0 = (from - center).to_angle();
+    let a1 = (to - center).to_angle();
+    let mut sweep = a1 - a0;
+    if ccw {
+        while sweep <= 0.0 {
+            sweep += std::f64::consts::TAU;
+        }
+    } else {
+        while sweep >= 0.0 {
+            sweep -= std::f64::consts::TAU;
+        }
+    }
+    sweep
+}
+
+impl Region {
+    /// Normalise winding: outer counter-clockwise, holes clockwise
+    pub fn new(outer: Profile, holes: Vec<Profile>) -> Self {
+        Self {
+            outer: outer.oriented(true),
+            holes: holes.into_iter().map(|h| h.oriented(false)).collect(),
+        }
+    }
+
+    pub fn contains(&self, p: DVec2) -> bool {
+        self.outer.contains(p) && !self.holes.iter().any(|h| h.contains(p))
+    }
+
+    /// Region of a planar face.
+    ///
+    /// Faces whose normal is within ~25° of ±Z keep world X/Y coordinates;
+    /// other faces are unrolled into a local frame (u horizontal, v up).
+    pub fn from_planar_face(solid: &Solid, face: FaceId) -> Result<Self, CamError> {
+        let face = solid
+            .face(face)
+            .ok_or_else(|| CamError::InvalidGeometry(format!("face {} not found", face.0)))?;
+        let SurfaceType::Planar { normal } = face.surface else {
+            return Err(CamError::InvalidGeometry(format!(
+                "face {} is not planar",
+                face.id.0
+            )));
+        };
+        let n = normal.normalize_or_z();
+        let (u, v) = if n.z.abs() > 0.9 {
+            (Vector3::X, Vector3::Y)
+        } else {
+            let u = Vector3::Z.cross(n).normalize_or_z();
+            (u, n.cross(u))
+        };
+        let flatten = |loop_| {
+            Profile::new(
+                solid
+                    .loop_points(loop_, FACE_CURVE_SAMPLES)
+                    .into_iter()
+                    .map(|p| {
+                        let p = Vector3::new(p.x, p.y, p.z);
+                        DVec2::new(p.dot(u) as f64, p.dot(v) as f64)
+                    })
+                    .collect(),
+            )
+        };
+        let outer = flatten(&face.outer_loop);
+        if outer.points.len() < 3 {
+            return Err(CamError::InvalidGeometry(format!(
+                "face {} has a degenerate outer loop",
+                face.id.0
+            )));
+        }
+        let holes = face
+            .inner_loops
+            .iter()
+            .map(flatten)
+            .filter(|h| h.points.len() >= 3)
+            .collect();
+        Ok(Self::new(outer, holes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cad::{make_box, make_cylinder};
+
+    #[test]
+    fn test_outward_offset_rounds_convex_corners() {
+        let square = Profile::rectangle(0.0, 0.0, 10.0, 10.0);
+        let path = square.offset_path(2.0).unwrap();
+        let arcs = path
+            .segments
+            .iter()
+            .filter(|s| matches!(s, PathSegment::Arc { ccw: true, .. }))
+            .count();
+        assert_eq!(arcs, 4);
+
+        // Area of a square grown by r with rounded corners: a² + 4ar + πr²
+        let flat = path.to_profile(0.5f64.to_radians());
+        let expected = 100.0 + 80.0 + std::f64::consts::PI * 4.0;
+        assert!((flat.signed_area() - expected).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_inward_offset_and_collapse() {
+        let square = Profile::rectangle(0.0, 0.0, 10.0, 10.0).reversed();
+        let inner = square.offset(-3.0).unwrap();
+        assert!((inner.signed_area() - 16.0).abs() < 1e-9);
+        assert!(square.offset(-5.5).is_none());
+    }
+
+    #[test]
+    fn test_l_shape_inward_offset_keeps_distance() {
+        let l = Profile::new(vec![
+            DVec2::new(0.0, 0.0),
+            DVec2::new(20.0, 0.0),
+            DVec2::new(20.0, 8.0),
+            DVec2::new(8.0, 8.0),
+            DVec2::new(8.0, 20.0),
+            DVec2::new(0.0, 20.0),
+        ]);
+        let path = l.offset_path(-2.0).unwrap();
+        // The reflex corner becomes a clockwise arc
+        assert!(path
+            .segments
+            .iter()
+            .any(|s| matches!(s, PathSegment::Arc { ccw: false, .. })));
+        for p in path.to_profile(0.05).points {
+            assert!((l.distance_to(p) - 2.0).abs() < 1e-6);
+        }
+        // A narrow notch disappears instead of producing a loop
+        let notched = Profile::new(vec![
+            DVec2::new(0.0, 0.0),
+            DVec2::new(10.0, 0.0),
+            DVec2::new(10.0, 10.0),
+            DVec2::new(5.5, 10.0),
+            DVec2::new(5.5, 5.0),
+            DVec2::new(4.5, 5.0),
+            DVec2::new(4.5, 10.0),
+            DVec2::new(0.0, 10.0),
+        ]);
+        let grown = notched.offset(1.0).unwrap();
+        assert!(grown.contains(DVec2::new(5.0, 9.0)));
+    }
+
+    #[test]
+    fn test_reversed_path_round_trip() {
+        let path = Profile::rectangle(0.0, 0.0, 4.0, 2.0)
+            .offset_path(1.0)
+            .unwrap();
+        let back = path.reversed();
+        assert!(back.to_profile(0.1).signed_area() < 0.0);
+        assert_eq!(back.reversed(), path);
+    }
+
+    #[test]
+    fn test_region_from_planar_faces() {
+        let solid = make_box(10.0, 6.0, 2.0);
+        // Top face (+Z) keeps world XY
+        let top = solid
+            .faces
+            .iter()
+            .find(|f| matches!(f.surface, SurfaceType::Planar { normal } if normal.z > 0.9))
+            .unwrap();
+        let region = Region::from_planar_face(&solid, top.id).unwrap();
+        assert!((region.outer.signed_area() - 60.0).abs() < 1e-4);
+        assert!(region.contains(DVec2::new(4.9, -2.9)));
+
+        // Side face is unrolled into its own plane
+        let side = solid
+            .faces
+            .iter()
+            .find(|f| matches!(f.surface, SurfaceType::Planar { normal } if normal.y < -0.9))
+            .unwrap();
+        let region = Region::from_planar_face(&solid, side.id).unwrap();
+        assert!((region.outer.signed_area() - 20.0).abs() < 1e-4);
+
+        let cylinder = make_cylinder(1.0, 1.0, 8);
+        assert!(Region::from_planar_face(&cylinder, FaceId(999)).is_err());
+    }
+}
@@ -0,0 +1,798 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: toolpath.rs | DNA/src/cam/toolpath.rs
+//! PURPOSE: Tools, feeds/speeds and contour / pocket / drilling toolpaths
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ OPERATIONS                                                                  │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ contour()  Profile offset by the tool radius (outside / inside / on)        │
+//! │ pocket()   ZigZag: scanlines clipped to the inset region + finishing pass   │
+//! │            Offset: concentric inward offsets, innermost first               │
+//! │ drill()    Point list → drill cycles (optionally pecked)                    │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! Milling direction assumes a clockwise (M3) spindle: climb milling keeps the
+//! material on the right of the direction of travel.
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use glam::{DVec2, DVec3};
+
+use super::profile::{OffsetPath, PathSegment, Profile, Region};
+
+/// CAM error
+#[derive(Debug, Clone, PartialEq)]
+pub enum CamError {
+    /// Geometry cannot be machined as given
+    InvalidGeometry(String),
+    /// Tool does not fit the feature
+    ToolTooLarge { diameter: f64 },
+    /// Parameter out of range
+    InvalidParameter(String),
+    /// Operation not supported for this input
+    Unsupported(String),
+}
+
+impl std::fmt::Display for CamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CamError::InvalidGeometry(msg) => write!(f, "Invalid geometry: {}", msg),
+            CamError::ToolTooLarge { diameter } => {
+                write!(f, "Tool diameter {} does not fit the feature", diameter)
+            }
+            CamError::InvalidParameter(msg) => write!(f, "Invalid parameter: {}", msg),
+            CamError::Unsupported(msg) => write!(f, "Unsupported: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for CamError {}
+
+/// Cutter geometry class
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ToolKind {
+    FlatEndMill,
+    BallEndMill,
+    Drill,
+}
+
+/// Cutting tool
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tool {
+    /// Tool table number (T word)
+    pub number: u32,
+    pub kind: ToolKind,
+    pub diameter: f64,
+    pub flutes: u32,
+}
+
+impl Tool {
+    pub fn flat_end_mill(number: u32, diameter: f64, flutes: u32) -> Self {
+        Self {
+            number,
+            kind: ToolKind::FlatEndMill,
+            diameter,
+            flutes,
+        }
+    }
+
+    pub fn ball_end_mill(number: u32, diameter: f64, flutes: u32) -> Self {
+        Self {
+            number,
+            kind: ToolKind::BallEndMill,
+            diameter,
+            flutes,
+        }
+    }
+
+    pub fn drill(number: u32, diameter: f64) -> Self {
+        Self {
+            number,
+            kind: ToolKind::Drill,
+            diameter,
+            flutes: 2,
+        }
+    }
+
+    pub fn radius(&self) -> f64 {
+        self.diameter * 0.5
+    }
+
+    /// Short description for program comments
+    pub fn describe(&self) -> String {
+        let kind = match self.kind {
+            ToolKind::FlatEndMill => "flat end mill",
+            ToolKind::BallEndMill => "ball end mill",
+            ToolKind::Drill => "drill",
+        };
+        format!("T{} D{:.3} {}", self.number, self.diameter, kind)
+    }
+}
+
+/// Spindle speed and feed rates (units per minute)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FeedsAndSpeeds {
+    pub spindle_rpm: f64,
+    pub feed_rate: f64,
+    pub plunge_rate: f64,
+}
+
+impl FeedsAndSpeeds {
+    pub fn new(spindle_rpm: f64, feed_rate: f64, plunge_rate: f64) -> Self {
+        Self {
+            spindle_rpm,
+            feed_rate,
+            plunge_rate,
+        }
+    }
+
+    /// Derive from surface speed (units/min) and chip load (units/tooth).
+    ///
+    /// rpm = Vc / (π·D), feed = rpm · flutes · chip load, plunge = feed / 2
+    pub fn from_chip_load(tool: &Tool, surface_speed: f64, chip_load: f64) -> Self {
+        let spindle_rpm = surface_speed / (std::f64::consts::PI * tool.diameter);
+        let feed_rate = spindle_rpm * tool.flutes.max(1) as f64 * chip_load;
+        Self {
+            spindle_rpm,
+            feed_rate,
+            plunge_rate: feed_rate * 0.5,
+        }
+    }
+}
+
+/// Cut direction relative to a clockwise spindle
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum MillingDirection {
+    #[default]
+    Climb,
+    Conventional,
+}
+
+/// Which side of the profile the cutter runs on
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CutSide {
+    /// Cut the part out (tool outside the profile)
+    Outside,
+    /// Cut a hole (tool inside the profile)
+    Inside,
+    /// Tool centre on the profile (engraving, slotting)
+    On,
+}
+
+/// Pocket clearing strategy
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PocketStrategy {
+    ZigZag,
+    Offset,
+}
+
+/// Depths, clearances and feeds shared by every operation
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CutParams {
+    pub feeds: FeedsAndSpeeds,
+    /// Total cut depth below the stock top (positive)
+    pub depth: f64,
+    /// Maximum depth per pass
+    pub step_down: f64,
+    /// Step-over as a fraction of the tool diameter
+    pub step_over: f64,
+    /// Z of the stock top
+    pub stock_top: f64,
+    /// Absolute Z for rapid moves
+    pub safe_z: f64,
+    pub direction: MillingDirection,
+}
+
+impl CutParams {
+    /// Single pass to `depth` from Z = 0, 40% step-over, climb milling, 5 units clearance
+    pub fn new(feeds: FeedsAndSpeeds, depth: f64) -> Self {
+        Self {
+            feeds,
+            depth,
+            step_down: depth,
+            step_over: 0.4,
+            stock_top: 0.0,
+            safe_z: 5.0,
+            direction: MillingDirection::Climb,
+        }
+    }
+
+    pub fn with_step_down(mut self, step_down: f64) -> Self {
+        self.step_down = step_down;
+        self
+    }
+
+    pub fn with_step_over(mut self, step_over: f64) -> Self {
+        self.step_over = step_over;
+        self
+    }
+
+    pub fn with_stock_top(mut self, stock_top: f64) -> Self {
+        self.stock_top = stock_top;
+        self
+    }
+
+    pub fn with_safe_z(mut self, safe_z: f64) -> Self {
+        self.safe_z = safe_z;
+        self
+    }
+
+    pub fn with_direction(mut self, direction: MillingDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    fn validate(&self) -> Result<(), CamError> {
+        if self.depth <= 0.0 {
+            return Err(CamError::InvalidParameter("depth must be positive".into()));
+        }
+        if self.step_down <= 0.0 {
+            return Err(CamError::InvalidParameter(
+                "step-down must be positive".into(),
+            ));
+        }
+        if !(self.step_over > 0.0 && self.step_over <= 1.0) {
+            return Err(CamError::InvalidParameter(
+                "step-over must be in (0, 1] of the tool diameter".into(),
+            ));
+        }
+        if self.safe_z <= self.stock_top {
+            return Err(CamError::InvalidParameter(
+                "safe Z must be above the stock top".into(),
+            ));
+        }
+        if self.feeds.feed_rate <= 0.0 || self.feeds.plunge_rate <= 0.0 {
+            return Err(CamError::InvalidParameter(
+                "feed rates must be positive".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Z levels of successive passes, top to bottom
+    fn levels(&self) -> Vec<f64> {
+        let passes = (self.depth / self.step_down - 1e-9).ceil().max(1.0) as usize;
+        (1..=passes)
+            .map(|i| self.stock_top - (self.depth * i as f64 / passes as f64))
+            .collect()
+    }
+}
+
+/// Single machine motion
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Move {
+    /// G0 to the target
+    Rapid(DVec3),
+    /// G1 at `feed`
+    Linear { to: DVec3, feed: f64 },
+    /// G2 (clockwise) / G3 in the XY plane; `center` is absolute
+    Arc {
+        to: DVec3,
+        center: DVec2,
+        clockwise: bool,
+        feed: f64,
+    },
+    /// Drill cycle at `at`: rapid to the `retract_z` plane, feed to `bottom_z`,
+    /// then return to the Z level the cycle started from
+    Drill {
+        at: DVec2,
+        retract_z: f64,
+        bottom_z: f64,
+        /// Peck increment (None for a straight plunge)
+        peck: Option<f64>,
+        feed: f64,
+    },
+}
+
+/// Toolpath for one tool
+#[derive(Clone, Debug, PartialEq)]
+pub struct Operation {
+    pub name: String,
+    pub tool: Tool,
+    pub spindle_rpm: f64,
+    /// Clearance height the operation starts and ends at
+    pub safe_z: f64,
+    pub moves: Vec<Move>,
+}
+
+impl Operation {
+    fn new(name: &str, tool: &Tool, params: &CutParams) -> Self {
+        Self {
+            name: name.to_string(),
+            tool: tool.clone(),
+            spindle_rpm: params.feeds.spindle_rpm,
+            safe_z: params.safe_z,
+            moves: Vec::new(),
+        }
+    }
+
+    /// Cutting distance (feed moves; drill cycles count their stroke from the retract plane)
+    pub fn cut_length(&self) -> f64 {
+        let mut length = 0.0;
+        let mut pos: Option<DVec3> = None;
+        for m in &self.moves {
+            match *m {
+                Move::Rapid(to) => pos = Some(to),
+                Move::Linear { to, .. } => {
+                    if let Some(p) = pos {
+                        length += p.distance(to);
+                    }
+                    pos = Some(to);
+                }
+                Move::Arc {
+                    to,
+                    center,
+                    clockwise,
+                    ..
+                } => {
+                    if let Some(p) = pos {
+                        let sweep = super::profile::arc_sweep(
+                            p.truncate(),
+                            to.truncate(),
+                            center,
+                            !clockwise,
+                        );
+                        let planar = sweep.abs() * p.truncate().distance(center);
+                        length += (planar * planar + (to.z - p.z).powi(2)).sqrt();
+                    }
+                    pos = Some(to);
+                }
+                Move::Drill {
+                    at,
+                    retract_z,
+                    bottom_z,
+                    ..
+                } => {
+                    length += retract_z - bottom_z;
+                    pos = Some(at.extend(retract_z));
+                }
+            }
+        }
+        length
+    }
+
+    fn retract(&mut self) {
+        self.moves
+            .push(Move::Rapid(self.position().truncate().extend(self.safe_z)));
+    }
+
+    fn position(&self) -> DVec3 {
+        self.moves
+            .iter()
+            .rev()
+            .map(|m| match *m {
+                Move::Rapid(p) | Move::Linear { to: p, .. } | Move::Arc { to: p, .. } => p,
+                Move::Drill { at, retract_z, .. } => at.extend(retract_z.max(self.safe_z)),
+            })
+            .next()
+            .unwrap_or(DVec3::new(0.0, 0.0, self.safe_z))
+    }
+
+    /// Retract, rapid over `xy`, plunge to `z`
+    fn enter(&mut self, xy: DVec2, z: f64, params: &CutParams) {
+        if !self.moves.is_empty() {
+            self.retract();
+        }
+        self.moves.push(Move::Rapid(xy.extend(self.safe_z)));
+        self.moves.push(Move::Linear {
+            to: xy.extend(z),
+            feed: params.feeds.plunge_rate,
+        });
+    }
+
+    /// Follow a closed path at depth `z` (the tool is already at its start)
+    fn follow(&mut self, path: &OffsetPath, z: f64, feed: f64) {
+        for s in &path.segments {
+            match *s {
+                PathSegment::Line { to } => self.moves.push(Move::Linear {
+                    to: to.extend(z),
+                    feed,
+                }),
+                PathSegment::Arc { to, center, ccw } => self.moves.push(Move::Arc {
+                    to: to.extend(z),
+                    center,
+                    clockwise: !ccw,
+                    feed,
+                }),
+            }
+        }
+    }
+}
+
+/// Profile contour with tool radius compensation and multiple depth passes
+pub fn contour(
+    name: &str,
+    profile: &Profile,
+    side: CutSide,
+    tool: &Tool,
+    params: &CutParams,
+) -> Result<Operation, CamError> {
+    params.validate()?;
+    if profile.points.len() < 3 {
+        return Err(CamError::InvalidGeometry("profile needs 3+ points".into()));
+    }
+    let offset = match side {
+        CutSide::Outside => tool.radius(),
+        CutSide::Inside => -tool.radius(),
+        CutSide::On => 0.0,
+    };
+    let path = profile.offset_path(offset).ok_or(CamError::ToolTooLarge {
+        diameter: tool.diameter,
+    })?;
+    // Offset paths run CCW; climb milling wants CW outside, CCW inside
+    let ccw = match (side, params.direction) {
+        (CutSide::Inside, MillingDirection::Climb) => true,
+        (CutSide::Inside, MillingDirection::Conventional) => false,
+        (_, MillingDirection::Climb) => false,
+        (_, MillingDirection::Conventional) => true,
+    };
+    let path = if ccw { path } else { path.reversed() };
+
+    let mut op = Operation::new(name, tool, params);
+    for (i, z) in params.levels().into_iter().enumerate() {
+        if i == 0 {
+            op.enter(path.start, z, params);
+        } else {
+            // Closed path: step straight down at the start point
+            op.moves.push(Move::Linear {
+                to: path.start.extend(z),
+                feed: params.feeds.plunge_rate,
+            });
+        }
+        op.follow(&path, z, params.feeds.feed_rate);
+    }
+    op.retract();
+    Ok(op)
+}
+
+/// Clear the inside of a region
+pub fn pocket(
+    name: &str,
+    region: &Region,
+    strategy: PocketStrategy,
+    tool: &Tool,
+    params: &CutParams,
+) -> Result<Operation, CamError> {
+    params.validate()?;
+    let too_large = CamError::ToolTooLarge {
+        diameter: tool.diameter,
+    };
+    let step = tool.diameter * params.step_over;
+    let mut op = Operation::new(name, tool, params);
+
+    match strategy {
+        PocketStrategy::Offset => {
+            if !region.holes.is_empty() {
+                return Err(CamError::Unsupported(
+                    "offset pocketing with islands (use ZigZag)".into(),
+                ));
+            }
+            let mut rings = Vec::new();
+            let mut distance = tool.radius();
+            while let Some(ring) = region.outer.offset_path(-distance) {
+                rings.push(ring);
+                distance += step;
+            }
+            if rings.is_empty() {
+                return Err(too_large);
+            }
+            rings.reverse();
+            let rings: Vec<OffsetPath> = rings
+                .into_iter()
+                .map(|r| match params.direction {
+                    MillingDirection::Climb => r,
+                    MillingDirection::Conventional => r.reversed(),
+                })
+                .collect();
+            for z in params.levels() {
+                let mut first = true;
+                for ring in &rings {
+                    let here = op.position().truncate();
+                    if first || here.distance(ring.start) > step * 1.5 {
+                        op.enter(ring.start, z, params);
+                        first = false;
+                    } else {
+                        op.moves.push(Move::Linear {
+                            to: ring.start.extend(z),
+                            feed: params.feeds.feed_rate,
+                        });
+                    }
+                    op.follow(ring, z, params.feeds.feed_rate);
+                }
+            }
+        }
+        PocketStrategy::ZigZag => {
+            let outer = region.outer.offset_path(-tool.radius()).ok_or(too_large)?;
+            let islands: Vec<OffsetPath> = region
+                .holes
+                .iter()
+                .map(|h| h.reversed().offset_path(tool.radius()))
+                .collect::<Option<_>>()
+                .ok_or_else(|| CamError::InvalidGeometry("island cannot be offset".into()))?;
+            let max_angle = 5f64.to_radians();
+            let mut boundary = vec![outer.to_profile(max_angle)];
+            boundary.extend(islands.iter().map(|i| i.to_profile(max_angle)));
+            let rows = zigzag_rows(&boundary, step);
+
+            for z in params.levels() {
+                let mut first = true;
+                for &(a, b) in &rows {
+                    let here = op.position().truncate();
+                    if first || !link_inside(&boundary, here, a) {
+                        op.enter(a, z, params);
+                        first = false;
+                    } else {
+                        op.moves.push(Move::Linear {
+                            to: a.extend(z),
+                            feed: params.feeds.feed_rate,
+                        });
+                    }
+                    op.moves.push(Move::Linear {
+                        to: b.extend(z),
+                        feed: params.feeds.feed_rate,
+                    });
+                }
+                // Finishing pass around the walls and islands
+                let walls = match params.direction {
+                    MillingDirection::Climb => outer.clone(),
+                    MillingDirection::Conventional => outer.reversed(),
+                };
+                op.enter(walls.start, z, params);
+                op.follow(&walls, z, params.feeds.feed_rate);
+                for island in &islands {
+                    let path = match params.direction {
+                        MillingDirection::Climb => island.reversed(),
+                        MillingDirection::Conventional => island.clone(),
+                    };
+                    op.enter(path.start, z, params);
+                    op.follow(&path, z, params.feeds.feed_rate);
+                }
+            }
+        }
+    }
+    op.retract();
+    Ok(op)
+}
+
+/// Scanline intervals clipped to the boundary loops, alternating direction
+fn zigzag_rows(boundary: &[Profile], step: f64) -> Vec<(DVec2, DVec2)> {
+    let (lo, hi) = boundary[0].bounds();
+    let span = hi.y - lo.y;
+    let count = (span / step).ceil().max(1.0) as usize;
+    let mut rows = Vec::new();
+    for i in 0..=count {
+        // Stay a hair inside the extreme rows so they still intersect
+        let y = (lo.y + span * i as f64 / count as f64).clamp(lo.y + 1e-7, hi.y - 1e-7);
+        let mut xs: Vec<f64> = Vec::new();
+        for profile in boundary {
+            let n = profile.points.len();
+            for k in 0..n {
+                let (a, b) = (profile.points[k], profile.points[(k + 1) % n]);
+                if (a.y <= y) != (b.y <= y) {
+                    xs.push(a.x + (y - a.y) * (b.x - a.x) / (b.y - a.y));
+                }
+            }
+        }
+        xs.sort_by(|a, b| a.total_cmp(b));
+        let mut spans: Vec<(DVec2, DVec2)> = xs
+            .as_chunks::<2>()
+            .0
+            .iter()
+            .map(|&[a, b]| (DVec2::new(a, y), DVec2::new(b, y)))
+            .collect();
+        if i % 2 == 1 {
+            spans.reverse();
+            for s in spans.iter_mut() {
+                *s = (s.1, s.0);
+            }
+        }
+        rows.extend(spans);
+    }
+    rows
+}
+
+/// True if the straight move a→b stays inside the machinable area
+fn link_inside(boundary: &[Profile], a: DVec2, b: DVec2) -> bool {
+    let crosses = boundary.iter().any(|profile| {
+        let n = profile.points.len();
+        (0..n).any(|k| segments_cross(a, b, profile.points[k], profile.points[(k + 1) % n]))
+    });
+    if crosses {
+        return false;
+    }
+    let mid = (a + b) * 0.5;
+    boundary[0].contains(mid) && !boundary[1..].iter().any(|p| p.contains(mid))
+}
+
+fn segments_cross(a: DVec2, b: DVec2, c: DVec2, d: DVec2) -> bool {
+    let d1 = (b - a).perp_dot(c - a);
+    let d2 = (b - a).perp_dot(d - a);
+    let d3 = (d - c).perp_dot(a - c);
+    let d4 = (d - c).perp_dot(b - c);
+    d1 * d2 < -1e-12 && d3 * d4 < -1e-12
+}
+
+/// Height of the drill R plane above the stock top
+const DRILL_CLEARANCE: f64 = 1.0;
+
+/// Drill every point to `params.depth` (pecking when `peck` is set)
+pub fn drill(
+    name: &str,
+    points: &[DVec2],
+    tool: &Tool,
+    params: &CutParams,
+    peck: Option<f64>,
+) -> Result<Operation, CamError> {
+    params.validate()?;
+    if peck.is_some_and(|q| q <= 0.0) {
+        return Err(CamError::InvalidParameter("peck must be positive".into()));
+    }
+    let mut op = Operation::new(name, tool, params);
+    let bottom_z = params.stock_top - params.depth;
+    let retract_z = (params.stock_top + DRILL_CLEARANCE).min(params.safe_z);
+    // Nearest-neighbour ordering keeps rapids short
+    let mut remaining: Vec<DVec2> = points.to_vec();
+    let mut here = DVec2::ZERO;
+    while !remaining.is_empty() {
+        let (i, _) = remaining
+            .iter()
+            .enumerate()
+            .min_by(|a, b| a.1.distance(here).total_cmp(&b.1.distance(here)))
+            .expect("non-empty");
+        let at = remaining.swap_remove(i);
+        op.moves.push(Move::Rapid(at.extend(params.safe_z)));
+        op.moves.push(Move::Drill {
+            at,
+            retract_z,
+            bottom_z,
+            peck,
+            feed: params.feeds.plunge_rate,
+        });
+        here = at;
+    }
+    Ok(op)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params() -> CutParams {
+        CutParams::new(FeedsAndSpeeds::new(12000.0, 800.0, 200.0), 6.0).with_step_down(2.0)
+    }
+
+    fn feed_points(op: &Operation) -> Vec<DVec3> {
+        op.moves
+            .iter()
+            .filter_map(|m| match *m {
+                Move::Linear { to, .. } | Move::Arc { to, .. } => Some(to),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_chip_load_feeds() {
+        let tool = Tool::flat_end_mill(1, 6.0, 2);
+        // 150 m/min = 150 000 mm/min
+        let f = FeedsAndSpeeds::from_chip_load(&tool, 150_000.0, 0.05);
+        assert!((f.spindle_rpm - 7957.7).abs() < 0.1);
+        assert!((f.feed_rate - 795.77).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_outside_contour_compensates_and_climbs() {
+        let tool = Tool::flat_end_mill(1, 6.0, 2);
+        let part = Profile::rectangle(0.0, 0.0, 50.0, 30.0);
+        let op = contour("Outline", &part, CutSide::Outside, &tool, &params()).unwrap();
+
+        // Three passes to -6 and every cutting point exactly one radius off the part
+        let points = feed_points(&op);
+        let depths: Vec<f64> = points.iter().map(|p| p.z).collect();
+        assert!(depths.contains(&-2.0) && depths.contains(&-4.0) && depths.contains(&-6.0));
+        for p in points.iter().filter(|p| p.z < -1.9) {
+            assert!((part.distance_to(p.truncate()) - 3.0).abs() < 1e-9);
+        }
+        // Climb milling outside a part runs clockwise: corner arcs are G2
+        assert!(op.moves.iter().any(|m| matches!(
+            m,
+            Move::Arc {
+                clockwise: true,
+                ..
+            }
+        )));
+        assert!(!op.moves.iter().any(|m| matches!(
+            m,
+            Move::Arc {
+                clockwise: false,
+                ..
+            }
+        )));
+        // Ends retracted
+        assert!(matches!(op.moves.last(), Some(Move::Rapid(p)) if p.z == 5.0));
+    }
+
+    #[test]
+    fn test_inside_contour_rejects_large_tool() {
+        let tool = Tool::flat_end_mill(1, 12.0, 2);
+        let hole = Profile::circle(DVec2::ZERO, 5.0, 32);
+        assert_eq!(
+            contour("Hole", &hole, CutSide::Inside, &tool, &params()),
+            Err(CamError::ToolTooLarge { diameter: 12.0 })
+        );
+    }
+
+    #[test]
+    fn test_zigzag_pocket_avoids_island() {
+        let tool = Tool::flat_end_mill(2, 4.0, 2);
+        let region = Region::new(
+            Profile::rectangle(0.0, 0.0, 40.0, 40.0),
+            vec![Profile::rectangle(15.0, 15.0, 10.0, 10.0)],
+        );
+        let op = pocket("Pocket", &region, PocketStrategy::ZigZag, &tool, &params()).unwrap();
+        let points = feed_points(&op);
+        assert!(!points.is_empty());
+        for p in &points {
+            let xy = p.truncate();
+            assert!(xy.x >= 2.0 - 1e-6 && xy.x <= 38.0 + 1e-6);
+            // Tool edge never enters the island (up to the 5° chord error)
+            let island = &region.holes[0];
+            assert!(!island.contains(xy), "{xy:?}");
+            assert!(island.distance_to(xy) >= 2.0 - 5e-3, "{xy:?}");
+        }
+    }
+
+    #[test]
+    fn test_offset_pocket_rings() {
+        let tool = Tool::flat_end_mill(2, 4.0, 2);
+        let region = Region::new(Profile::rectangle(0.0, 0.0, 20.0, 20.0), vec![]);
+        let op = pocket("Pocket", &region, PocketStrategy::Offset, &tool, &params()).unwrap();
+        // Last ring at each level is the wall pass, one radius in
+        let wall = feed_points(&op)
+            .into_iter()
+            .filter(|p| p.z == -6.0)
+            .map(|p| region.outer.distance_to(p.truncate()))
+            .fold(f64::INFINITY, f64::min);
+        assert!((wall - 2.0).abs() < 1e-9);
+
+        let with_island = Region::new(
+            Profile::rectangle(0.0, 0.0, 20.0, 20.0),
+            vec![Profile::rectangle(5.0, 5.0, 2.0, 2.0)],
+        );
+        assert!(matches!(
+            pocket("P", &with_island, PocketStrategy::Offset, &tool, &params()),
+            Err(CamError::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn test_drill_orders_points_and_pecks() {
+        let tool = Tool::drill(3, 3.0);
+        let points = [
+            DVec2::new(50.0, 0.0),
+            DVec2::new(0.0, 0.0),
+            DVec2::new(10.0, 0.0),
+        ];
+        let op = drill("Holes", &points, &tool, &params(), Some(1.5)).unwrap();
+        let order: Vec<f64> = op
+            .moves
+            .iter()
+            .filter_map(|m| match m {
+                Move::Drill {
+                    at, peck, bottom_z, ..
+                } => {
+                    assert_eq!(*peck, Some(1.5));
+                    assert_eq!(*bottom_z, -6.0);
+                    Some(at.x)
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(order, vec![0.0, 10.0, 50.0]);
+        // Each hole is fed from the R plane (1 above stock) to -6
+        assert!((op.cut_length() - 21.0).abs() < 1e-9);
+    }
+}
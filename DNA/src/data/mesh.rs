@@ -1,7 +1,7 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: mesh.rs | DNA/src/data/mesh.rs
 //! PURPOSE: Triangle/quad mesh representation for CAD and rendering
-//! MODIFIED: 2025-12-09
+//! MODIFIED: 2026-10-18
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
 
@@ -11,28 +11,381 @@
 //! LAYER: DNA → DATA
 //!
 //! ┌─────────────────────────────────────────────────────────────────────────────┐
-//! │ DATA DEFINED (Future)                                                       │
+//! │ DATA DEFINED                                                                │
 //! ├─────────────────────────────────────────────────────────────────────────────┤
-//! │ TriangleMesh      Indexed triangle mesh with normals                        │
-//! │ QuadMesh          Quad mesh (for structured grids)                          │
-//! │ HalfEdgeMesh      Half-edge structure for topology queries                  │
+//! │ TriangleMesh      Indexed triangle mesh, counter-clockwise = front face     │
+//! │ MeshError         Triangle referencing a missing vertex                     │
+//! │ QuadMesh          Quad mesh (for structured grids)          (future)        │
+//! │ HalfEdgeMesh      Half-edge structure for topology queries  (future)        │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ TOPOLOGY QUERIES                                                            │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ edges()     Unique undirected edges [a, b] with a < b, sorted               │
+//! │ hinges()    Interior edges with both opposite vertices [a, b, c, d]:        │
+//! │             triangles (a, b, c) and (b, a, d) share the edge a → b          │
+//! │ is_closed() Every edge is shared by exactly two triangles                   │
+//! │ volume()    Σ a·(b × c)/6 (divergence theorem, closed meshes only)          │
 //! └─────────────────────────────────────────────────────────────────────────────┘
 //!
 //! DEPENDS ON:
 //!   • glam::Vec3 → 3D positions, normals
 //!
 //! USED BY:
+//!   • PHYSICS/MECHANICS   → Cloth and soft body builders
 //!   • CORE/CAD_ENGINE     → B-rep solid modeling
 //!   • CORE/EXPORT_ENGINE  → STL, OBJ export
 //!
-//! TODO: Implement mesh data structures for CAD engine
-//!
 //! ═══════════════════════════════════════════════════════════════════════════════
 
+use glam::Vec3;
+use std::collections::HashMap;
+
 // ─────────────────────────────────────────────────────────────────────────────────
-// CODE BELOW - Scaffold for future implementation
+// ERRORS
 // ─────────────────────────────────────────────────────────────────────────────────
 
-// TODO: Implement TriangleMesh
+#[derive(Debug, Clone, PartialEq)]
+pub enum MeshError {
+    /// Triangle `triangle` references vertex `vertex`, which does not exist
+    MissingVertex { triangle: usize, vertex: usize },
+}
+
+impl std::fmt::Display for MeshError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MeshError::MissingVertex { triangle, vertex } => write!(
+                f,
+                "Triangle {} references missing vertex {}",
+                triangle, vertex
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MeshError {}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// TRIANGLE MESH
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// Indexed triangle mesh; triangles wind counter-clockwise seen from the front
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TriangleMesh {
+    pub vertices: Vec<Vec3>,
+    pub triangles: Vec<[usize; 3]>,
+}
+
+impl TriangleMesh {
+    pub fn new(vertices: Vec<Vec3>, triangles: Vec<[usize; 3]>) -> Result<Self, MeshError> {
+        for (triangle, t) in triangles.iter().enumerate() {
+            if let Some(&vertex) = t.iter().find(|&&v| v >= vertices.len()) {
+                return Err(MeshError::MissingVertex { triangle, vertex });
+            }
+        }
+        Ok(Self {
+            vertices,
+            triangles,
+        })
+    }
+
+    /// Rectangle [0, width] × [0, depth] in the XZ plane facing +Y, split into
+    /// `nx` × `nz` quads with alternating diagonals
+    pub fn grid(width: f32, depth: f32, nx: usize, nz: usize) -> Self {
+        let (nx, nz) = (nx.max(1), nz.max(1));
+        let mut vertices = Vec::with_capacity((nx + 1) * (nz + 1));
+        for i in 0..=nx {
+            for k in 0..=nz {
+                vertices.push(Vec3::new(
+                    width * i as f32 / nx as f32,
+                    0.0,
+                    depth * k as f32 / nz as f32,
+                ));
+            }
+        }
+        let index = |i: usize, k: usize| i * (nz + 1) + k;
+        let mut triangles = Vec::with_capacity(2 * nx * nz);
+        for i in 0..nx {
+            for k in 0..nz {
+                let (v00, v01, v10, v11) = (
+                    index(i, k),
+                    index(i, k + 1),
+                    index(i + 1, k),
+                    index(i + 1, k + 1),
+                );
+                if (i + k) % 2 == 0 {
+                    triangles.push([v00, v01, v11]);
+                    triangles.push([v00, v11, v10]);
+                } else {
+                    triangles.push([v00, v01, v10]);
+                    triangles.push([v10, v01, v11]);
+                }
+            }
+        }
+        Self {
+            vertices,
+            triangles,
+        }
+    }
+
+    /// Closed box centred on the origin with outward-facing triangles
+    pub fn cuboid(half_extents: Vec3) -> Self {
+        let h = half_extents;
+        let vertices = (0..8)
+            .map(|i| {
+                Vec3::new(
+                    if i & 1 == 0 { -h.x } else { h.x },
+                    if i & 2 == 0 { -h.y } else { h.y },
+                    if i & 4 == 0 { -h.z } else { h.z },
+                )
+            })
+            .collect();
+        let triangles = vec![
+            [0, 4, 6],
+            [0, 6, 2], // -x
+            [1, 3, 7],
+            [1, 7, 5], // +x
+            [0, 1, 5],
+            [0, 5, 4], // -y
+            [2, 6, 7],
+            [2, 7, 3], // +y
+            [0, 2, 3],
+            [0, 3, 1], // -z
+            [4, 5, 7],
+            [4, 7, 6], // +z
+        ];
+        Self {
+            vertices,
+            triangles,
+        }
+    }
+
+    /// Sphere centred on the origin from an icosahedron split `subdivisions`
+    /// times (20·4ⁿ triangles)
+    pub fn icosphere(radius: f32, subdivisions: usize) -> Self {
+        let t = (1.0 + 5.0f32.sqrt()) / 2.0;
+        let mut vertices: Vec<Vec3> = [
+            (-1.0, t, 0.0),
+            (1.0, t, 0.0),
+            (-1.0, -t, 0.0),
+            (1.0, -t, 0.0),
+            (0.0, -1.0, t),
+            (0.0, 1.0, t),
+            (0.0, -1.0, -t),
+            (0.0, 1.0, -t),
+            (t, 0.0, -1.0),
+            (t, 0.0, 1.0),
+            (-t, 0.0, -1.0),
+            (-t, 0.0, 1.0),
+        ]
+        .iter()
+        .map(|&(x, y, z)| Vec3::new(x, y, z).normalize())
+        .collect();
+        let mut triangles = vec![
+            [0, 11, 5],
+            [0, 5, 1],
+            [0, 1, 7],
+            [0, 7, 10],
+            [0, 10, 11],
+            [1, 5, 9],
+            [5, 11, 4],
+            [11, 10, 2],
+            [10, 7, 6],
+            [7, 1, 8],
+            [3, 9, 4],
+            [3, 4, 2],
+            [3, 2, 6],
+            [3, 6, 8],
+            [3, 8, 9],
+            [4, 9, 5],
+            [2, 4, 11],
+            [6, 2, 10],
+            [8, 6, 7],
+            [9, 8, 1],
+        ];
+        for _ in 0..subdivisions {
+            let mut midpoints: HashMap<(usize, usize), usize> = HashMap::new();
+            let mut midpoint = |a: usize, b: usize, vertices: &mut Vec<Vec3>| {
+                *midpoints.entry((a.min(b), a.max(b))).or_insert_with(|| {
+                    vertices.push((vertices[a] + vertices[b]).normalize());
+                    vertices.len() - 1
+                })
+            };
+            triangles = triangles
+                .iter()
+                .flat_map(|&[a, b, c]| {
+                    let ab = midpoint(a, b, &mut vertices);
+                    let bc = midpoint(b, c, &mut vertices);
+                    let ca = midpoint(c, a, &mut vertices);
+                    [[a, ab, ca], [b, bc, ab], [c, ca, bc], [ab, bc, ca]]
+                })
+                .collect();
+        }
+        for v in &mut vertices {
+            *v *= radius;
+        }
+        Self {
+            vertices,
+            triangles,
+        }
+    }
+
+    pub fn translate(&mut self, offset: Vec3) {
+        for v in &mut self.vertices {
+            *v += offset;
+        }
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    pub fn triangle_count(&self) -> usize {
+        self.triangles.len()
+    }
+
+    /// (b - a) × (c - a): normal scaled by twice the triangle area
+    pub fn area_normal(&self, triangle: usize) -> Vec3 {
+        let [a, b, c] = self.triangles[triangle].map(|i| self.vertices[i]);
+        (b - a).cross(c - a)
+    }
+
+    pub fn face_normal(&self, triangle: usize) -> Vec3 {
+        self.area_normal(triangle).normalize_or_zero()
+    }
+
+    pub fn triangle_area(&self, triangle: usize) -> f32 {
+        0.5 * self.area_normal(triangle).length()
+    }
+
+    pub fn area(&self) -> f32 {
+        (0..self.triangles.len())
+            .map(|t| self.triangle_area(t))
+            .sum()
+    }
+
+    /// Enclosed volume; positive for closed outward-facing meshes
+    pub fn volume(&self) -> f32 {
+        self.triangles
+            .iter()
+            .map(|t| {
+                let [a, b, c] = t.map(|i| self.vertices[i]);
+                a.dot(b.cross(c)) / 6.0
+            })
+            .sum()
+    }
+
+    /// Area-weighted average of adjacent face normals
+    pub fn vertex_normals(&self) -> Vec<Vec3> {
+        let mut normals = vec![Vec3::ZERO; self.vertices.len()];
+        for (t, triangle) in self.triangles.iter().enumerate() {
+            let n = self.area_normal(t);
+            for &i in triangle {
+                normals[i] += n;
+            }
+        }
+        normals.iter().map(|n| n.normalize_or_zero()).collect()
+    }
+
+    /// Unique undirected edges [a, b] with a < b, sorted
+    pub fn edges(&self) -> Vec<[usize; 2]> {
+        let mut edges: Vec<[usize; 2]> = self
+            .triangles
+            .iter()
+            .flat_map(|&[a, b, c]| [[a, b], [b, c], [c, a]])
+            .map(|[a, b]| [a.min(b), a.max(b)])
+            .collect();
+        edges.sort_unstable();
+        edges.dedup();
+        edges
+    }
+
+    /// Interior edges [a, b, c, d]: triangle (a, b, c) and the neighbour
+    /// (b, a, d) across a → b. Edges shared by more than two triangles pair
+    /// the first two found.
+    pub fn hinges(&self) -> Vec<[usize; 4]> {
+        let mut opposite: HashMap<(usize, usize), usize> = HashMap::new();
+        let mut hinges = Vec::new();
+        for &[a, b, c] in &self.triangles {
+            for (p, q, r) in [(a, b, c), (b, c, a), (c, a, b)] {
+                // The neighbour traverses the shared edge in reverse
+                if let Some(s) = opposite.remove(&(q, p)) {
+                    hinges.push([q, p, s, r]);
+                } else {
+                    opposite.insert((p, q), r);
+                }
+            }
+        }
+        hinges.sort_unstable();
+        hinges
+    }
+
+    /// Every edge is shared by exactly two consistently wound triangles
+    pub fn is_closed(&self) -> bool {
+        let mut directed: HashMap<(usize, usize), usize> = HashMap::new();
+        for &[a, b, c] in &self.triangles {
+            for edge in [(a, b), (b, c), (c, a)] {
+                *directed.entry(edge).or_default() += 1;
+            }
+        }
+        !self.triangles.is_empty()
+            && directed
+                .iter()
+                .all(|(&(a, b), &n)| n == 1 && directed.get(&(b, a)) == Some(&1))
+    }
+}
+
 // TODO: Implement QuadMesh
 // TODO: Implement HalfEdgeMesh for topology operations
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_topology() {
+        let mesh = TriangleMesh::grid(2.0, 1.0, 4, 2);
+        assert_eq!(mesh.vertex_count(), 15);
+        assert_eq!(mesh.triangle_count(), 16);
+        // V - E + F = 1 for a disc
+        assert_eq!(15 - mesh.edges().len() as i64 + 16, 1);
+        assert_eq!(mesh.hinges().len(), mesh.edges().len() - 12);
+        assert!((mesh.area() - 2.0).abs() < 1e-6);
+        assert!(mesh
+            .vertex_normals()
+            .iter()
+            .all(|n| (*n - Vec3::Y).length() < 1e-6));
+        assert!(!mesh.is_closed());
+    }
+
+    #[test]
+    fn test_closed_meshes() {
+        let cube = TriangleMesh::cuboid(Vec3::new(0.5, 1.0, 1.5));
+        assert!(cube.is_closed());
+        assert!((cube.volume() - 6.0).abs() < 1e-5);
+        assert_eq!(cube.hinges().len(), cube.edges().len());
+
+        let sphere = TriangleMesh::icosphere(2.0, 3);
+        assert_eq!(sphere.triangle_count(), 20 * 64);
+        assert_eq!(sphere.vertex_count(), 642);
+        assert!(sphere.is_closed());
+        let exact = 4.0 / 3.0 * std::f32::consts::PI * 8.0;
+        assert!((sphere.volume() - exact).abs() / exact < 0.02);
+        for hinge in sphere.hinges() {
+            let [a, b, c, d] = hinge;
+            assert!(a != b && c != d && ![a, b].contains(&c) && ![a, b].contains(&d));
+        }
+    }
+
+    #[test]
+    fn test_missing_vertex() {
+        assert_eq!(
+            TriangleMesh::new(vec![Vec3::ZERO; 3], vec![[0, 1, 3]]),
+            Err(MeshError::MissingVertex {
+                triangle: 0,
+                vertex: 3
+            })
+        );
+    }
+}
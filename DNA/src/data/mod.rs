@@ -9,7 +9,7 @@
 //! DATA provides efficient data structures:
 //! - arena.rs        - Generic arena allocator with generational indices
 //! - spatial_grid.rs - Uniform spatial grid for O(1) neighbor queries
-//! - mesh.rs         - Triangle/quad mesh (scaffold)
+//! - mesh.rs         - Indexed triangle mesh (quad / half-edge to come)
 //! - graph.rs        - Node/edge graph (scaffold)
 //!
 //! Future:
@@ -28,10 +28,11 @@ pub use arena::{Arena, Handle};
 
 /// Uniform spatial grid for O(1) neighbor queries
 pub mod spatial_grid;
-pub use spatial_grid::UniformGrid;
+pub use spatial_grid::{UniformGrid, UniformGrid3};
 
-/// Triangle/quad mesh (scaffold for future CAD)
+/// Indexed triangle mesh with topology queries
 pub mod mesh;
+pub use mesh::{MeshError, TriangleMesh};
 
 /// Node/edge graph (scaffold for circuits, pathfinding)
 pub mod graph;
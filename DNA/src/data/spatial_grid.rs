@@ -1,7 +1,7 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: spatial_grid.rs | DNA/src/data/spatial_grid.rs
 //! PURPOSE: Generic uniform spatial grid for O(1) neighbor queries
-//! MODIFIED: 2025-12-09
+//! MODIFIED: 2026-10-18
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
 
@@ -14,12 +14,13 @@
 //! │ DATA DEFINED                                                                │
 //! ├─────────────────────────────────────────────────────────────────────────────┤
 //! │ UniformGrid<CAP>  Fixed-size grid for spatial partitioning                  │
+//! │ UniformGrid3<CAP> Same for 3D space (3x3x3 neighborhood queries)            │
 //! └─────────────────────────────────────────────────────────────────────────────┘
 //!
 //! ┌─────────────────────────────────────────────────────────────────────────────┐
 //! │ DATA FLOW                                                                   │
 //! ├─────────────────────────────────────────────────────────────────────────────┤
-//! │ CONSUMES:  (x, y) positions, entity indices                                 │
+//! │ CONSUMES:  (x, y) or (x, y, z) positions, entity indices                    │
 //! │ PRODUCES:  Neighbor lists (indices within radius)                           │
 //! └─────────────────────────────────────────────────────────────────────────────┘
 //!
@@ -28,6 +29,7 @@
 //!
 //! USED BY:
 //!   • DNA/src/lib.rs  → Boid flocking (domain-specific version)
+//!   • PHYSICS/FLUIDS  → SPH neighbor search (2D and 3D)
 //!   • Future: Collision detection, particle systems, spatial queries
 //!
 //! ALGORITHM: Uniform spatial hashing
@@ -154,6 +156,121 @@ impl<const CELL_CAPACITY: usize> UniformGrid<CELL_CAPACITY> {
     }
 }
 
+/// Generic uniform spatial grid for 3D space
+///
+/// Same layout as `UniformGrid` with a third axis; queries scan the 3x3x3
+/// cell neighborhood, so radius must not exceed `cell_size`.
+pub struct UniformGrid3<const CELL_CAPACITY: usize> {
+    cell_size: f32,
+    cols: usize,
+    rows: usize,
+    layers: usize,
+    /// Each cell stores up to CELL_CAPACITY entity indices
+    cells: Vec<[u16; CELL_CAPACITY]>,
+    /// Number of entities in each cell
+    cell_counts: Vec<usize>,
+}
+
+impl<const CELL_CAPACITY: usize> UniformGrid3<CELL_CAPACITY> {
+    /// Create a new 3D spatial grid covering [0, width] × [0, height] × [0, depth]
+    pub fn new(width: f32, height: f32, depth: f32, cell_size: f32) -> Self {
+        let cols = ((width / cell_size).ceil() as usize).max(1);
+        let rows = ((height / cell_size).ceil() as usize).max(1);
+        let layers = ((depth / cell_size).ceil() as usize).max(1);
+        let num_cells = cols * rows * layers;
+
+        Self {
+            cell_size,
+            cols,
+            rows,
+            layers,
+            cells: vec![[0; CELL_CAPACITY]; num_cells],
+            cell_counts: vec![0; num_cells],
+        }
+    }
+
+    /// Clear all cells
+    #[inline]
+    pub fn clear(&mut self) {
+        self.cell_counts.fill(0);
+    }
+
+    /// Insert an entity at position (x, y, z)
+    ///
+    /// Returns true if successfully inserted, false if cell is full.
+    pub fn insert(&mut self, x: f32, y: f32, z: f32, entity_index: u16) -> bool {
+        let cell_idx = self.cell_index(x, y, z);
+
+        let count = self.cell_counts[cell_idx];
+        if count >= CELL_CAPACITY {
+            return false; // Cell is full
+        }
+
+        self.cells[cell_idx][count] = entity_index;
+        self.cell_counts[cell_idx] += 1;
+        true
+    }
+
+    /// Query entities in the cells around (x, y, z)
+    ///
+    /// Writes candidates to output buffer, returns count. Callers filter by
+    /// exact distance.
+    pub fn query_radius(&self, x: f32, y: f32, z: f32, _radius: f32, output: &mut [u16]) -> usize {
+        let mut count = 0;
+
+        let cell_x = (x / self.cell_size) as isize;
+        let cell_y = (y / self.cell_size) as isize;
+        let cell_z = (z / self.cell_size) as isize;
+
+        for dz in -1..=1 {
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    let cx = cell_x + dx;
+                    let cy = cell_y + dy;
+                    let cz = cell_z + dz;
+
+                    if cx < 0
+                        || cy < 0
+                        || cz < 0
+                        || cx >= self.cols as isize
+                        || cy >= self.rows as isize
+                        || cz >= self.layers as isize
+                    {
+                        continue;
+                    }
+
+                    let cell_idx =
+                        ((cz as usize) * self.rows + cy as usize) * self.cols + cx as usize;
+                    for &entity_idx in &self.cells[cell_idx][..self.cell_counts[cell_idx]] {
+                        if count >= output.len() {
+                            return count; // Output buffer full
+                        }
+                        output[count] = entity_idx;
+                        count += 1;
+                    }
+                }
+            }
+        }
+
+        count
+    }
+
+    /// Get cell index for world position (x, y, z)
+    #[inline]
+    fn cell_index(&self, x: f32, y: f32, z: f32) -> usize {
+        let col = ((x / self.cell_size) as usize).min(self.cols - 1);
+        let row = ((y / self.cell_size) as usize).min(self.rows - 1);
+        let layer = ((z / self.cell_size) as usize).min(self.layers - 1);
+        (layer * self.rows + row) * self.cols + col
+    }
+
+    /// Get grid dimensions
+    #[inline]
+    pub fn dimensions(&self) -> (usize, usize, usize) {
+        (self.cols, self.rows, self.layers)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -206,4 +323,23 @@ mod tests {
         // Cell should be full
         assert!(!grid.insert(7.0, 7.0, 2));
     }
+
+    #[test]
+    fn test_grid3_insert_and_query() {
+        let mut grid: UniformGrid3<4> = UniformGrid3::new(10.0, 10.0, 10.0, 2.0);
+        assert_eq!(grid.dimensions(), (5, 5, 5));
+
+        grid.insert(1.0, 1.0, 1.0, 0);
+        grid.insert(2.5, 1.0, 3.5, 1);
+        grid.insert(9.0, 9.0, 9.0, 2);
+
+        let mut output = [0u16; 16];
+        let count = grid.query_radius(1.0, 1.0, 1.0, 2.0, &mut output);
+        let mut found = output[..count].to_vec();
+        found.sort_unstable();
+        assert_eq!(found, vec![0, 1]);
+
+        grid.clear();
+        assert_eq!(grid.query_radius(1.0, 1.0, 1.0, 2.0, &mut output), 0);
+    }
 }
@@ -0,0 +1,448 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: drc.rs | DNA/src/export/drc.rs
+//! PURPOSE: Design-rule checks on Gerber copper layers and Excellon drill files
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+//!
+//! Checks run on fabrication output (not on a layout database), so they catch
+//! exporter bugs as well as layout mistakes:
+//!
+//! - Trace width:     every stroke's aperture ≥ `min_trace_width`
+//! - Clearance:       copper of different nets ≥ `min_clearance` apart
+//!   (overlap between different nets is reported as 0 mm)
+//! - Annular ring:    copper around each plated hole ≥ `min_annular_ring`
+//! - Drill-to-copper: unrelated copper ≥ `min_drill_to_copper` from hole walls
+//!
+//! Connectivity comes from X2 `.N` net attributes where present; copper
+//! without a net is connected to whatever it touches.
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use super::excellon::{DrillHit, ExcellonDocument};
+use super::gerber::{GerberDocument, GerberUnit};
+use super::gerber_render::{layer_objects, LayerObject, ObjectKind, Shape};
+
+/// Design-rule limits in millimetres
+#[derive(Clone, Debug, PartialEq)]
+pub struct DrcRules {
+    pub min_trace_width: f64,
+    pub min_clearance: f64,
+    pub min_annular_ring: f64,
+    pub min_drill_to_copper: f64,
+}
+
+impl Default for DrcRules {
+    /// Typical limits for a two-layer prototype service
+    fn default() -> Self {
+        Self {
+            min_trace_width: 0.127,
+            min_clearance: 0.127,
+            min_annular_ring: 0.13,
+            min_drill_to_copper: 0.2,
+        }
+    }
+}
+
+/// Which rule a violation breaks
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DrcRule {
+    TraceWidth,
+    Clearance,
+    AnnularRing,
+    DrillToCopper,
+}
+
+impl DrcRule {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DrcRule::TraceWidth => "Trace width",
+            DrcRule::Clearance => "Clearance",
+            DrcRule::AnnularRing => "Annular ring",
+            DrcRule::DrillToCopper => "Drill to copper",
+        }
+    }
+}
+
+/// One design-rule violation
+#[derive(Clone, Debug, PartialEq)]
+pub struct DrcViolation {
+    pub rule: DrcRule,
+    /// X2 file function of the copper layer (e.g. "Copper,L1,Top")
+    pub layer: String,
+    /// Location on the board (mm)
+    pub location: (f64, f64),
+    pub measured: f64,
+    pub required: f64,
+}
+
+impl std::fmt::Display for DrcViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} on {} at ({:.3}, {:.3}): {:.3} mm < {:.3} mm",
+            self.rule.name(),
+            self.layer,
+            self.location.0,
+            self.location.1,
+            self.measured,
+            self.required
+        )
+    }
+}
+
+/// Copper layer prepared for checking
+struct CopperLayer {
+    name: String,
+    objects: Vec<LayerObject>,
+    /// Connectivity group of each object
+    groups: Vec<usize>,
+}
+
+impl CopperLayer {
+    fn new(doc: &GerberDocument) -> Self {
+        let objects = layer_objects(doc);
+        let groups = connectivity(&objects);
+        Self {
+            name: doc.file_function().to_string(),
+            objects,
+            groups,
+        }
+    }
+}
+
+fn find(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
+fn union(parent: &mut [usize], a: usize, b: usize) {
+    let (ra, rb) = (find(parent, a), find(parent, b));
+    if ra != rb {
+        parent[ra] = rb;
+    }
+}
+
+fn bounds_overlap(a: ((f64, f64), (f64, f64)), b: ((f64, f64), (f64, f64)), margin: f64) -> bool {
+    a.0 .0 - margin <= b.1 .0
+        && b.0 .0 - margin <= a.1 .0
+        && a.0 .1 - margin <= b.1 .1
+        && b.0 .1 - margin <= a.1 .1
+}
+
+/// Same-net objects are connected; unnamed copper joins whatever it touches
+fn connectivity(objects: &[LayerObject]) -> Vec<usize> {
+    let mut parent: Vec<usize> = (0..objects.len()).collect();
+    let mut first_of_net: Vec<(&str, usize)> = Vec::new();
+    for (i, o) in objects.iter().enumerate() {
+        if let Some(net) = o.net() {
+            match first_of_net.iter().find(|(n, _)| *n == net) {
+                Some(&(_, j)) => union(&mut parent, i, j),
+                None => first_of_net.push((net, i)),
+            }
+        }
+    }
+    let bounds: Vec<_> = objects.iter().map(|o| o.shape.bounds()).collect();
+    for i in 0..objects.len() {
+        for j in i + 1..objects.len() {
+            let named_pair = objects[i].net().is_some() && objects[j].net().is_some();
+            if named_pair || !bounds_overlap(bounds[i], bounds[j], 0.0) {
+                continue;
+            }
+            if objects[i].shape.gap(&objects[j].shape).0 <= 0.0 {
+                union(&mut parent, i, j);
+            }
+        }
+    }
+    (0..objects.len()).map(|i| find(&mut parent, i)).collect()
+}
+
+/// Run all checks; `copper` are copper layers, `drills` PTH/NPTH files
+pub fn run_drc(
+    copper: &[&GerberDocument],
+    drills: &[&ExcellonDocument],
+    rules: &DrcRules,
+) -> Vec<DrcViolation> {
+    let layers: Vec<CopperLayer> = copper.iter().map(|d| CopperLayer::new(d)).collect();
+    let mut violations = Vec::new();
+    for layer in &layers {
+        check_trace_width(layer, rules, &mut violations);
+        check_clearance(layer, rules, &mut violations);
+    }
+    for drill in drills {
+        check_holes(&layers, drill, rules, &mut violations);
+    }
+    violations
+}
+
+fn check_trace_width(layer: &CopperLayer, rules: &DrcRules, out: &mut Vec<DrcViolation>) {
+    for o in layer.objects.iter().filter(|o| o.kind == ObjectKind::Draw) {
+        if o.width < rules.min_trace_width - 1e-9 {
+            let ((x0, y0), (x1, y1)) = o.shape.bounds();
+            out.push(DrcViolation {
+                rule: DrcRule::TraceWidth,
+                layer: layer.name.clone(),
+                location: ((x0 + x1) / 2.0, (y0 + y1) / 2.0),
+                measured: o.width,
+                required: rules.min_trace_width,
+            });
+        }
+    }
+}
+
+fn check_clearance(layer: &CopperLayer, rules: &DrcRules, out: &mut Vec<DrcViolation>) {
+    let objects = &layer.objects;
+    let bounds: Vec<_> = objects.iter().map(|o| o.shape.bounds()).collect();
+    for i in 0..objects.len() {
+        for j in i + 1..objects.len() {
+            if layer.groups[i] == layer.groups[j]
+                || !bounds_overlap(bounds[i], bounds[j], rules.min_clearance)
+            {
+                continue;
+            }
+            let (gap, at) = objects[i].shape.gap(&objects[j].shape);
+            if gap < rules.min_clearance - 1e-9 {
+                out.push(DrcViolation {
+                    rule: DrcRule::Clearance,
+                    layer: layer.name.clone(),
+                    location: at,
+                    measured: gap,
+                    required: rules.min_clearance,
+                });
+            }
+        }
+    }
+}
+
+fn check_holes(
+    layers: &[CopperLayer],
+    drill: &ExcellonDocument,
+    rules: &DrcRules,
+    out: &mut Vec<DrcViolation>,
+) {
+    let scale = match drill.unit() {
+        GerberUnit::Millimeters => 1.0,
+        GerberUnit::Inches => 25.4,
+    };
+    let plated = drill.is_plated();
+    for hit in drill.hits() {
+        let Some(tool) = drill.tools().iter().find(|t| t.number == hit.tool()) else {
+            continue;
+        };
+        let radius = tool.diameter * scale / 2.0;
+        let (hole, center) = match *hit {
+            DrillHit::Hole { x, y, .. } => {
+                let c = (x * scale, y * scale);
+                (Shape::disk(c, radius), c)
+            }
+            DrillHit::Slot { start, end, .. } => {
+                let (a, b) = (
+                    (start.0 * scale, start.1 * scale),
+                    (end.0 * scale, end.1 * scale),
+                );
+                (Shape::capsule(a, b, radius), a)
+            }
+        };
+
+        for layer in layers {
+            // Copper the hole passes through (its pad and everything on that net)
+            let pads: Vec<usize> = (0..layer.objects.len())
+                .filter(|&i| layer.objects[i].shape.contains(center))
+                .collect();
+
+            if plated && matches!(hit, DrillHit::Hole { .. }) {
+                let ring = pads
+                    .iter()
+                    .map(|&i| -layer.objects[i].shape.signed_distance(center) - radius)
+                    .fold(f64::NEG_INFINITY, f64::max)
+                    .max(0.0);
+                if ring < rules.min_annular_ring - 1e-9 {
+                    out.push(DrcViolation {
+                        rule: DrcRule::AnnularRing,
+                        layer: layer.name.clone(),
+                        location: center,
+                        measured: ring,
+                        required: rules.min_annular_ring,
+                    });
+                }
+            }
+
+            let connected: Vec<usize> = if plated {
+                pads.iter().map(|&i| layer.groups[i]).collect()
+            } else {
+                Vec::new()
+            };
+            let hole_bounds = hole.bounds();
+            for (i, o) in layer.objects.iter().enumerate() {
+                if connected.contains(&layer.groups[i])
+                    || !bounds_overlap(o.shape.bounds(), hole_bounds, rules.min_drill_to_copper)
+                {
+                    continue;
+                }
+                let (gap, at) = o.shape.gap(&hole);
+                if gap < rules.min_drill_to_copper - 1e-9 {
+                    out.push(DrcViolation {
+                        rule: DrcRule::DrillToCopper,
+                        layer: layer.name.clone(),
+                        location: at,
+                        measured: gap,
+                        required: rules.min_drill_to_copper,
+                    });
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::fabrication::{
+        generate_fabrication_package, BoardSide, FabBoard, FabHole, FabOptions, FabPad, FabTrack,
+        FabVia, PadShape, TrackSegment,
+    };
+    use crate::export::gerber_import::{parse_excellon, parse_gerber};
+
+    fn pad(component: &str, net: &str, at: (f64, f64)) -> FabPad {
+        FabPad {
+            component: component.into(),
+            pin: "1".into(),
+            net: Some(net.into()),
+            position: at,
+            shape: PadShape::Rect {
+                width: 1.0,
+                height: 1.0,
+            },
+            side: BoardSide::Top,
+            drill: None,
+        }
+    }
+
+    fn track(net: &str, width: f64, start: (f64, f64), end: (f64, f64)) -> FabTrack {
+        FabTrack {
+            side: BoardSide::Top,
+            width,
+            net: Some(net.into()),
+            segment: TrackSegment::Line { start, end },
+        }
+    }
+
+    /// Export the board, parse it back and check the parsed files
+    fn check(board: &FabBoard) -> Vec<DrcViolation> {
+        let package = generate_fabrication_package(board, &FabOptions::default());
+        let text = |name: &str| {
+            String::from_utf8(
+                package
+                    .file(&format!("DRC-{}", name))
+                    .unwrap()
+                    .contents
+                    .clone(),
+            )
+            .unwrap()
+        };
+        let top = parse_gerber(&text("F_Cu.gbr")).unwrap();
+        let bottom = parse_gerber(&text("B_Cu.gbr")).unwrap();
+        let pth = parse_excellon(&text("PTH.drl")).unwrap();
+        let npth = parse_excellon(&text("NPTH.drl")).unwrap();
+        run_drc(&[&top, &bottom], &[&pth, &npth], &DrcRules::default())
+    }
+
+    #[test]
+    fn test_clean_board_passes() {
+        let mut board = FabBoard::rectangle("DRC", 20.0, 10.0);
+        board.pads.push(pad("R1", "A", (2.0, 5.0)));
+        board.pads.push(pad("R1", "B", (8.0, 5.0)));
+        board.tracks.push(track("A", 0.25, (2.0, 5.0), (5.0, 5.0)));
+        board.vias.push(FabVia {
+            position: (5.0, 5.0),
+            diameter: 0.8,
+            drill: 0.4,
+            net: Some("A".into()),
+        });
+        board.holes.push(FabHole {
+            position: (18.0, 8.0),
+            diameter: 3.0,
+        });
+        assert_eq!(check(&board), vec![]);
+    }
+
+    #[test]
+    fn test_width_and_clearance_violations() {
+        let mut board = FabBoard::rectangle("DRC", 20.0, 10.0);
+        board.pads.push(pad("R1", "A", (2.0, 5.0)));
+        board.pads.push(pad("R2", "B", (2.0, 6.1)));
+        board.tracks.push(track("A", 0.1, (2.0, 5.0), (10.0, 5.0)));
+        // Crosses net A's track: a short
+        board.tracks.push(track("C", 0.2, (6.0, 3.0), (6.0, 7.0)));
+
+        let violations = check(&board);
+        let width: Vec<_> = violations
+            .iter()
+            .filter(|v| v.rule == DrcRule::TraceWidth)
+            .collect();
+        assert_eq!(width.len(), 1);
+        assert!((width[0].measured - 0.1).abs() < 1e-9);
+
+        let clearance: Vec<_> = violations
+            .iter()
+            .filter(|v| v.rule == DrcRule::Clearance)
+            .collect();
+        assert_eq!(clearance.len(), 2);
+        // Pads 0.1 mm apart
+        assert!(clearance
+            .iter()
+            .any(|v| (v.measured - 0.1).abs() < 1e-6 && (v.location.1 - 5.55).abs() < 1e-6));
+        // Short reported as zero clearance at the crossing
+        assert!(clearance
+            .iter()
+            .any(|v| v.measured == 0.0 && (v.location.0 - 6.0).abs() < 1e-6));
+        assert!(clearance[0]
+            .to_string()
+            .starts_with("Clearance on Copper,L1,Top at"));
+    }
+
+    #[test]
+    fn test_hole_violations() {
+        let mut board = FabBoard::rectangle("DRC", 20.0, 10.0);
+        // 0.5 mm via drill in a 0.6 mm pad leaves a 0.05 mm ring
+        board.vias.push(FabVia {
+            position: (5.0, 5.0),
+            diameter: 0.6,
+            drill: 0.5,
+            net: Some("A".into()),
+        });
+        // Track of another net passing 0.1 mm from a mounting hole wall
+        board.holes.push(FabHole {
+            position: (15.0, 5.0),
+            diameter: 2.0,
+        });
+        board.tracks.push(track("B", 0.2, (14.0, 6.2), (16.0, 6.2)));
+        // Through-hole pad with good ring, same net as the via: no violation
+        board.pads.push(FabPad {
+            drill: Some(0.8),
+            shape: PadShape::Circle { diameter: 1.6 },
+            ..pad("J1", "A", (8.0, 5.0))
+        });
+
+        let violations = check(&board);
+        let rings: Vec<_> = violations
+            .iter()
+            .filter(|v| v.rule == DrcRule::AnnularRing)
+            .collect();
+        // Via fails on both copper layers
+        assert_eq!(rings.len(), 2);
+        assert!(rings.iter().all(|v| (v.measured - 0.05).abs() < 1e-6));
+        assert!(rings.iter().all(|v| v.location == (5.0, 5.0)));
+
+        let drill: Vec<_> = violations
+            .iter()
+            .filter(|v| v.rule == DrcRule::DrillToCopper)
+            .collect();
+        assert_eq!(drill.len(), 1);
+        assert!((drill[0].measured - 0.1).abs() < 1e-6);
+        assert_eq!(drill[0].layer, "Copper,L1,Top");
+    }
+}
@@ -0,0 +1,1271 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: dxf.rs | DNA/src/export/dxf.rs
+//! PURPOSE: DXF R12 / R2000 writer and reader, with CAD-face and PCB-outline adapters
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+//!
+//! ASCII DXF for 2D exchange (laser cutters, vendor panel drawings):
+//!
+//! ```text
+//! ┌──────────────┬────────────────────────────┬────────────────────────────┐
+//! │ Entity       │ R12 (AC1009)               │ R2000 (AC1015)             │
+//! ├──────────────┼────────────────────────────┼────────────────────────────┤
+//! │ Line         │ LINE                       │ LINE                       │
+//! │ Polyline     │ POLYLINE + VERTEX + SEQEND │ LWPOLYLINE                 │
+//! │ Arc / Circle │ ARC / CIRCLE               │ ARC / CIRCLE               │
+//! │ Text         │ TEXT                       │ TEXT                       │
+//! │ Dimension    │ DIMENSION + *D block       │ DIMENSION + *D block       │
+//! └──────────────┴────────────────────────────┴────────────────────────────┘
+//! ```
+//!
+//! R2000 output carries handles, owner references, subclass markers, the
+//! BLOCK_RECORD table and a root dictionary. Dimensions are aligned
+//! dimensions whose rendered geometry lives in an anonymous `*D` block, so
+//! viewers that do not regenerate dimensions still show them.
+//!
+//! The reader accepts both versions (and LWPOLYLINE/MTEXT from newer files);
+//! block contents and other entity types (INSERT, SPLINE, HATCH, ...) are
+//! skipped. Arc angles are degrees, counter-clockwise from +X.
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use super::fabrication::FabBoard;
+use super::pdf::TextAlign;
+use crate::cad::{CurveType, FaceId, Point3, Solid, SurfaceType, Vector3};
+use std::fmt::Write as FmtWrite;
+
+/// 2D point in drawing units
+pub type DxfPoint = (f64, f64);
+
+/// Output format version
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DxfVersion {
+    #[default]
+    R12,
+    R2000,
+}
+
+impl DxfVersion {
+    pub fn acad_version(&self) -> &'static str {
+        match self {
+            DxfVersion::R12 => "AC1009",
+            DxfVersion::R2000 => "AC1015",
+        }
+    }
+}
+
+/// Drawing units ($INSUNITS)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DxfUnits {
+    Unitless,
+    #[default]
+    Inches,
+    Millimeters,
+}
+
+impl DxfUnits {
+    fn code(&self) -> i32 {
+        match self {
+            DxfUnits::Unitless => 0,
+            DxfUnits::Inches => 1,
+            DxfUnits::Millimeters => 4,
+        }
+    }
+
+    fn from_code(code: i32) -> Self {
+        match code {
+            1 => DxfUnits::Inches,
+            4 => DxfUnits::Millimeters,
+            _ => DxfUnits::Unitless,
+        }
+    }
+}
+
+/// Layer table entry
+#[derive(Clone, Debug, PartialEq)]
+pub struct DxfLayer {
+    pub name: String,
+    /// AutoCAD color index (1-255, 7 = white/black)
+    pub color: i16,
+}
+
+/// Drawing entity
+#[derive(Clone, Debug, PartialEq)]
+pub enum DxfEntity {
+    Line {
+        layer: String,
+        start: DxfPoint,
+        end: DxfPoint,
+    },
+    Polyline {
+        layer: String,
+        points: Vec<DxfPoint>,
+        closed: bool,
+    },
+    Arc {
+        layer: String,
+        center: DxfPoint,
+        radius: f64,
+        /// Degrees, counter-clockwise from +X
+        start_angle: f64,
+        end_angle: f64,
+    },
+    Circle {
+        layer: String,
+        center: DxfPoint,
+        radius: f64,
+    },
+    Text {
+        layer: String,
+        /// Insertion point (baseline; alignment point when not left-aligned)
+        position: DxfPoint,
+        height: f64,
+        text: String,
+        /// Degrees
+        rotation: f64,
+        align: TextAlign,
+    },
+    /// Aligned dimension between `start` and `end`
+    Dimension {
+        layer: String,
+        start: DxfPoint,
+        end: DxfPoint,
+        /// Signed distance of the dimension line, to the left of start → end
+        offset: f64,
+        /// Override text (`None` = measured value)
+        text: Option<String>,
+    },
+}
+
+impl DxfEntity {
+    pub fn layer(&self) -> &str {
+        match self {
+            DxfEntity::Line { layer, .. }
+            | DxfEntity::Polyline { layer, .. }
+            | DxfEntity::Arc { layer, .. }
+            | DxfEntity::Circle { layer, .. }
+            | DxfEntity::Text { layer, .. }
+            | DxfEntity::Dimension { layer, .. } => layer,
+        }
+    }
+
+    /// DXF entity type name (as written in R2000)
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            DxfEntity::Line { .. } => "LINE",
+            DxfEntity::Polyline { .. } => "LWPOLYLINE",
+            DxfEntity::Arc { .. } => "ARC",
+            DxfEntity::Circle { .. } => "CIRCLE",
+            DxfEntity::Text { .. } => "TEXT",
+            DxfEntity::Dimension { .. } => "DIMENSION",
+        }
+    }
+}
+
+/// DXF drawing: layers + entities in model space
+#[derive(Clone, Debug, PartialEq)]
+pub struct DxfDocument {
+    pub version: DxfVersion,
+    pub units: DxfUnits,
+    layers: Vec<DxfLayer>,
+    entities: Vec<DxfEntity>,
+}
+
+impl Default for DxfDocument {
+    fn default() -> Self {
+        Self::new(DxfUnits::default())
+    }
+}
+
+impl DxfDocument {
+    /// Empty R12 document with the mandatory layer "0"
+    pub fn new(units: DxfUnits) -> Self {
+        Self {
+            version: DxfVersion::R12,
+            units,
+            layers: vec![DxfLayer {
+                name: "0".to_string(),
+                color: 7,
+            }],
+            entities: Vec::new(),
+        }
+    }
+
+    pub fn layers(&self) -> &[DxfLayer] {
+        &self.layers
+    }
+
+    pub fn layer(&self, name: &str) -> Option<&DxfLayer> {
+        self.layers.iter().find(|l| l.name == name)
+    }
+
+    pub fn entities(&self) -> &[DxfEntity] {
+        &self.entities
+    }
+
+    /// Add a layer, or recolor it if it already exists
+    pub fn add_layer(&mut self, name: &str, color: i16) {
+        match self.layers.iter_mut().find(|l| l.name == name) {
+            Some(layer) => layer.color = color,
+            None => self.layers.push(DxfLayer {
+                name: name.to_string(),
+                color,
+            }),
+        }
+    }
+
+    /// Add an entity; unknown layers are created with color 7
+    pub fn add_entity(&mut self, entity: DxfEntity) {
+        if self.layer(entity.layer()).is_none() {
+            self.add_layer(entity.layer(), 7);
+        }
+        self.entities.push(entity);
+    }
+
+    pub fn add_line(&mut self, layer: &str, start: DxfPoint, end: DxfPoint) {
+        self.add_entity(DxfEntity::Line {
+            layer: layer.to_string(),
+            start,
+            end,
+        });
+    }
+
+    pub fn add_polyline(&mut self, layer: &str, points: Vec<DxfPoint>, closed: bool) {
+        self.add_entity(DxfEntity::Polyline {
+            layer: layer.to_string(),
+            points,
+            closed,
+        });
+    }
+
+    pub fn add_arc(
+        &mut self,
+        layer: &str,
+        center: DxfPoint,
+        radius: f64,
+        start_angle: f64,
+        end_angle: f64,
+    ) {
+        self.add_entity(DxfEntity::Arc {
+            layer: layer.to_string(),
+            center,
+            radius,
+            start_angle,
+            end_angle,
+        });
+    }
+
+    pub fn add_circle(&mut self, layer: &str, center: DxfPoint, radius: f64) {
+        self.add_entity(DxfEntity::Circle {
+            layer: layer.to_string(),
+            center,
+            radius,
+        });
+    }
+
+    /// Left-aligned, unrotated text
+    pub fn add_text(&mut self, layer: &str, position: DxfPoint, height: f64, text: &str) {
+        self.add_entity(DxfEntity::Text {
+            layer: layer.to_string(),
+            position,
+            height,
+            text: text.to_string(),
+            rotation: 0.0,
+            align: TextAlign::Left,
+        });
+    }
+
+    /// Aligned dimension showing the measured distance
+    pub fn add_dimension(&mut self, layer: &str, start: DxfPoint, end: DxfPoint, offset: f64) {
+        self.add_entity(DxfEntity::Dimension {
+            layer: layer.to_string(),
+            start,
+            end,
+            offset,
+            text: None,
+        });
+    }
+
+    /// Extents of all entity geometry (min, max)
+    pub fn bounds(&self) -> Option<(DxfPoint, DxfPoint)> {
+        let mut lo = (f64::INFINITY, f64::INFINITY);
+        let mut hi = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+        let mut add = |p: DxfPoint| {
+            lo = (lo.0.min(p.0), lo.1.min(p.1));
+            hi = (hi.0.max(p.0), hi.1.max(p.1));
+        };
+        for e in &self.entities {
+            match e {
+                DxfEntity::Line { start, end, .. } => {
+                    add(*start);
+                    add(*end);
+                }
+                DxfEntity::Polyline { points, .. } => points.iter().for_each(|p| add(*p)),
+                DxfEntity::Arc { center, radius, .. }
+                | DxfEntity::Circle { center, radius, .. } => {
+                    add((center.0 - radius, center.1 - radius));
+                    add((center.0 + radius, center.1 + radius));
+                }
+                DxfEntity::Text { position, .. } => add(*position),
+                DxfEntity::Dimension { start, end, .. } => {
+                    add(*start);
+                    add(*end);
+                }
+            }
+        }
+        (lo.0 <= hi.0).then_some((lo, hi))
+    }
+
+    /// Text height used inside dimension blocks
+    fn dimension_text_height(&self) -> f64 {
+        match self.units {
+            DxfUnits::Millimeters => 2.5,
+            _ => 0.125,
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.to_string().into_bytes()
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Writer
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Number without trailing zeros (DXF readers accept any decimal form)
+fn num(v: f64) -> String {
+    let s = format!("{:.6}", v);
+    let s = s.trim_end_matches('0').trim_end_matches('.');
+    if s == "-0" || s.is_empty() {
+        "0".to_string()
+    } else {
+        s.to_string()
+    }
+}
+
+/// Measured dimension value (up to 3 decimals)
+fn measurement_text(v: f64) -> String {
+    let s = format!("{:.3}", v);
+    s.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+struct DxfWriter {
+    out: String,
+    r2000: bool,
+    next_handle: u32,
+}
+
+impl DxfWriter {
+    fn pair(&mut self, code: i32, value: &str) {
+        let _ = writeln!(self.out, "{:>3}\n{}", code, value);
+    }
+
+    fn num(&mut self, code: i32, value: f64) {
+        self.pair(code, &num(value));
+    }
+
+    fn point(&mut self, code: i32, p: DxfPoint) {
+        self.num(code, p.0);
+        self.num(code + 10, p.1);
+        self.pair(code + 20, "0");
+    }
+
+    fn handle(&mut self) -> String {
+        let h = format!("{:X}", self.next_handle);
+        self.next_handle += 1;
+        h
+    }
+
+    /// Handle + owner + subclass (R2000 only); returns the handle
+    fn object(&mut self, owner: &str, subclass: &str) -> String {
+        if !self.r2000 {
+            return String::new();
+        }
+        let h = self.handle();
+        self.pair(5, &h);
+        self.pair(330, owner);
+        self.pair(100, subclass);
+        h
+    }
+
+    fn subclass(&mut self, name: &str) {
+        if self.r2000 {
+            self.pair(100, name);
+        }
+    }
+
+    fn entity_head(&mut self, kind: &str, layer: &str, owner: &str) {
+        self.pair(0, kind);
+        self.object(owner, "AcDbEntity");
+        self.pair(8, layer);
+    }
+
+    fn table(&mut self, name: &str, count: usize) -> String {
+        self.pair(0, "TABLE");
+        self.pair(2, name);
+        let h = self.object("0", "AcDbSymbolTable");
+        self.pair(70, &count.to_string());
+        h
+    }
+
+    fn table_record(&mut self, kind: &str, owner: &str, subclass: &str) -> String {
+        self.pair(0, kind);
+        let h = self.object(owner, "AcDbSymbolTableRecord");
+        self.subclass(subclass);
+        h
+    }
+
+    fn block(&mut self, name: &str, owner: &str, flags: i32, entities: &[DxfEntity], text_h: f64) {
+        self.pair(0, "BLOCK");
+        self.object(owner, "AcDbEntity");
+        self.pair(8, "0");
+        self.subclass("AcDbBlockBegin");
+        self.pair(2, name);
+        self.pair(70, &flags.to_string());
+        self.point(10, (0.0, 0.0));
+        self.pair(3, name);
+        self.pair(1, "");
+        for e in entities {
+            self.entity(e, owner, None, text_h);
+        }
+        self.pair(0, "ENDBLK");
+        self.object(owner, "AcDbEntity");
+        self.pair(8, "0");
+        self.subclass("AcDbBlockEnd");
+    }
+
+    fn entity(&mut self, e: &DxfEntity, owner: &str, dim_block: Option<&str>, text_h: f64) {
+        match e {
+            DxfEntity::Line { layer, start, end } => {
+                self.entity_head("LINE", layer, owner);
+                self.subclass("AcDbLine");
+                self.point(10, *start);
+                self.point(11, *end);
+            }
+            DxfEntity::Polyline {
+                layer,
+                points,
+                closed,
+            } => {
+                if self.r2000 {
+                    self.entity_head("LWPOLYLINE", layer, owner);
+                    self.subclass("AcDbPolyline");
+                    self.pair(90, &points.len().to_string());
+                    self.pair(70, if *closed { "1" } else { "0" });
+                    for p in points {
+                        self.num(10, p.0);
+                        self.num(20, p.1);
+                    }
+                } else {
+                    self.entity_head("POLYLINE", layer, owner);
+                    self.pair(66, "1");
+                    self.point(10, (0.0, 0.0));
+                    self.pair(70, if *closed { "1" } else { "0" });
+                    for p in points {
+                        self.entity_head("VERTEX", layer, owner);
+                        self.point(10, *p);
+                    }
+                    self.entity_head("SEQEND", layer, owner);
+                }
+            }
+            DxfEntity::Arc {
+                layer,
+                center,
+                radius,
+                start_angle,
+                end_angle,
+            } => {
+                self.entity_head("ARC", layer, owner);
+                self.subclass("AcDbCircle");
+                self.point(10, *center);
+                self.num(40, *radius);
+                self.subclass("AcDbArc");
+                self.num(50, *start_angle);
+                self.num(51, *end_angle);
+            }
+            DxfEntity::Circle {
+                layer,
+                center,
+                radius,
+            } => {
+                self.entity_head("CIRCLE", layer, owner);
+                self.subclass("AcDbCircle");
+                self.point(10, *center);
+                self.num(40, *radius);
+            }
+            DxfEntity::Text {
+                layer,
+                position,
+                height,
+                text,
+                rotation,
+                align,
+            } => {
+                self.entity_head("TEXT", layer, owner);
+                self.subclass("AcDbText");
+                self.point(10, *position);
+                self.num(40, *height);
+                self.pair(1, text);
+                if *rotation != 0.0 {
+                    self.num(50, *rotation);
+                }
+                let justify = match align {
+                    TextAlign::Left => 0,
+                    TextAlign::Center => 1,
+                    TextAlign::Right => 2,
+                };
+                if justify != 0 {
+                    self.pair(72, &justify.to_string());
+                    self.point(11, *position);
+                }
+                self.subclass("AcDbText");
+            }
+            DxfEntity::Dimension {
+                layer,
+                start,
+                end,
+                offset,
+                text,
+            } => {
+                let g = DimensionGeometry::new(*start, *end, *offset, text_h);
+                self.entity_head("DIMENSION", layer, owner);
+                self.subclass("AcDbDimension");
+                self.pair(2, dim_block.unwrap_or(""));
+                self.point(10, g.line_end);
+                self.point(11, g.text_at);
+                // Aligned dimension; block used by this dimension only
+                self.pair(70, "33");
+                if let Some(text) = text {
+                    self.pair(1, text);
+                }
+                if self.r2000 {
+                    self.num(42, g.length);
+                }
+                self.subclass("AcDbAlignedDimension");
+                self.point(13, *start);
+                self.point(14, *end);
+            }
+        }
+    }
+}
+
+/// Rendered geometry of an aligned dimension
+struct DimensionGeometry {
+    length: f64,
+    line_start: DxfPoint,
+    line_end: DxfPoint,
+    text_at: DxfPoint,
+    angle: f64,
+    normal: DxfPoint,
+    dir: DxfPoint,
+    text_h: f64,
+}
+
+impl DimensionGeometry {
+    fn new(start: DxfPoint, end: DxfPoint, offset: f64, text_h: f64) -> Self {
+        let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+        let length = (dx * dx + dy * dy).sqrt();
+        let dir = if length > 0.0 {
+            (dx / length, dy / length)
+        } else {
+            (1.0, 0.0)
+        };
+        let normal = (-dir.1, dir.0);
+        let at = |p: DxfPoint, d: f64| (p.0 + normal.0 * d, p.1 + normal.1 * d);
+        let line_start = at(start, offset);
+        let line_end = at(end, offset);
+        let mid = (
+            (line_start.0 + line_end.0) / 2.0,
+            (line_start.1 + line_end.1) / 2.0,
+        );
+        Self {
+            length,
+            line_start,
+            line_end,
+            text_at: at(mid, text_h * 0.6),
+            angle: dir.1.atan2(dir.0).to_degrees(),
+            normal,
+            dir,
+            text_h,
+        }
+    }
+
+    /// Extension lines, dimension line, oblique ticks and text
+    fn entities(&self, start: DxfPoint, end: DxfPoint, text: &str) -> Vec<DxfEntity> {
+        let layer = "0".to_string();
+        let gap = self.text_h * 0.5;
+        let side = {
+            let d = (self.line_start.0 - start.0) * self.normal.0
+                + (self.line_start.1 - start.1) * self.normal.1;
+            if d >= 0.0 {
+                1.0
+            } else {
+                -1.0
+            }
+        };
+        let mut out = Vec::new();
+        for (origin, foot) in [(start, self.line_start), (end, self.line_end)] {
+            let s = side * gap;
+            out.push(DxfEntity::Line {
+                layer: layer.clone(),
+                start: (origin.0 + self.normal.0 * s, origin.1 + self.normal.1 * s),
+                end: (foot.0 + self.normal.0 * s, foot.1 + self.normal.1 * s),
+            });
+            let t = self.text_h * 0.4;
+            let (ux, uy) = (
+                (self.dir.0 + self.normal.0) * t,
+                (self.dir.1 + self.normal.1) * t,
+            );
+            out.push(DxfEntity::Line {
+                layer: layer.clone(),
+                start: (foot.0 - ux, foot.1 - uy),
+                end: (foot.0 + ux, foot.1 + uy),
+            });
+        }
+        out.push(DxfEntity::Line {
+            layer: layer.clone(),
+            start: self.line_start,
+            end: self.line_end,
+        });
+        out.push(DxfEntity::Text {
+            layer,
+            position: self.text_at,
+            height: self.text_h,
+            text: text.to_string(),
+            rotation: self.angle,
+            align: TextAlign::Center,
+        });
+        out
+    }
+}
+
+impl std::fmt::Display for DxfDocument {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let r2000 = self.version == DxfVersion::R2000;
+        let mut w = DxfWriter {
+            out: String::new(),
+            r2000,
+            next_handle: 0x10,
+        };
+        let text_h = self.dimension_text_height();
+        let dims: Vec<(String, &DxfEntity)> = self
+            .entities
+            .iter()
+            .filter(|e| matches!(e, DxfEntity::Dimension { .. }))
+            .enumerate()
+            .map(|(i, e)| (format!("*D{}", i + 1), e))
+            .collect();
+
+        // Tables
+        w.pair(0, "SECTION");
+        w.pair(2, "TABLES");
+        let owner = w.table("LTYPE", 1);
+        w.table_record("LTYPE", &owner, "AcDbLinetypeTableRecord");
+        w.pair(2, "CONTINUOUS");
+        w.pair(70, "0");
+        w.pair(3, "Solid line");
+        w.pair(72, "65");
+        w.pair(73, "0");
+        w.pair(40, "0");
+        w.pair(0, "ENDTAB");
+
+        let owner = w.table("LAYER", self.layers.len());
+        for layer in &self.layers {
+            w.table_record("LAYER", &owner, "AcDbLayerTableRecord");
+            w.pair(2, &layer.name);
+            w.pair(70, "0");
+            w.pair(62, &layer.color.to_string());
+            w.pair(6, "CONTINUOUS");
+        }
+        w.pair(0, "ENDTAB");
+
+        let owner = w.table("STYLE", 1);
+        w.table_record("STYLE", &owner, "AcDbTextStyleTableRecord");
+        w.pair(2, "STANDARD");
+        w.pair(70, "0");
+        w.pair(40, "0");
+        w.pair(41, "1");
+        w.pair(50, "0");
+        w.pair(71, "0");
+        w.pair(42, "0.2");
+        w.pair(3, "txt");
+        w.pair(4, "");
+        w.pair(0, "ENDTAB");
+
+        // Block records own the entities of each block (R2000)
+        let mut records: Vec<String> = Vec::new();
+        if r2000 {
+            let owner = w.table("BLOCK_RECORD", 2 + dims.len());
+            let names = ["*Model_Space", "*Paper_Space"]
+                .into_iter()
+                .map(str::to_string)
+                .chain(dims.iter().map(|(n, _)| n.clone()));
+            for name in names {
+                let h = w.table_record("BLOCK_RECORD", &owner, "AcDbBlockTableRecord");
+                w.pair(2, &name);
+                records.push(h);
+            }
+            w.pair(0, "ENDTAB");
+        }
+        w.pair(0, "ENDSEC");
+
+        // Blocks: model/paper space (R2000) and rendered dimensions
+        w.pair(0, "SECTION");
+        w.pair(2, "BLOCKS");
+        if r2000 {
+            w.block("*Model_Space", &records[0], 0, &[], text_h);
+            w.block("*Paper_Space", &records[1], 0, &[], text_h);
+        }
+        for (i, (name, e)) in dims.iter().enumerate() {
+            if let DxfEntity::Dimension {
+                start,
+                end,
+                offset,
+                text,
+                ..
+            } = e
+            {
+                let g = DimensionGeometry::new(*start, *end, *offset, text_h);
+                let label = text.clone().unwrap_or_else(|| measurement_text(g.length));
+                let owner = records.get(i + 2).cloned().unwrap_or_default();
+                w.block(name, &owner, 1, &g.entities(*start, *end, &label), text_h);
+            }
+        }
+        w.pair(0, "ENDSEC");
+
+        // Entities
+        w.pair(0, "SECTION");
+        w.pair(2, "ENTITIES");
+        let model = records.first().cloned().unwrap_or_default();
+        let mut dim_names = dims.iter().map(|(n, _)| n.as_str());
+        for e in &self.entities {
+            let block = match e {
+                DxfEntity::Dimension { .. } => dim_names.next(),
+                _ => None,
+            };
+            w.entity(e, &model, block, text_h);
+        }
+        w.pair(0, "ENDSEC");
+
+        if r2000 {
+            w.pair(0, "SECTION");
+            w.pair(2, "OBJECTS");
+            let root = w.handle();
+            let group = w.handle();
+            w.pair(0, "DICTIONARY");
+            w.pair(5, &root);
+            w.pair(330, "0");
+            w.pair(100, "AcDbDictionary");
+            w.pair(3, "ACAD_GROUP");
+            w.pair(350, &group);
+            w.pair(0, "DICTIONARY");
+            w.pair(5, &group);
+            w.pair(330, &root);
+            w.pair(100, "AcDbDictionary");
+            w.pair(0, "ENDSEC");
+        }
+        w.pair(0, "EOF");
+
+        // Header last: $HANDSEED must exceed every handle written above
+        let mut h = DxfWriter {
+            out: String::new(),
+            r2000,
+            next_handle: 0,
+        };
+        h.pair(0, "SECTION");
+        h.pair(2, "HEADER");
+        h.pair(9, "$ACADVER");
+        h.pair(1, self.version.acad_version());
+        if r2000 {
+            h.pair(9, "$HANDSEED");
+            h.pair(5, &format!("{:X}", w.next_handle));
+        }
+        h.pair(9, "$INSUNITS");
+        h.pair(70, &self.units.code().to_string());
+        h.pair(9, "$MEASUREMENT");
+        let metric = self.units == DxfUnits::Millimeters;
+        h.pair(70, if metric { "1" } else { "0" });
+        h.pair(0, "ENDSEC");
+
+        f.write_str(&h.out)?;
+        f.write_str(&w.out)
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Reader
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Error type for DXF parsing
+#[derive(Debug, Clone, PartialEq)]
+pub enum DxfParseError {
+    /// Malformed input at a (1-based) line
+    Syntax { line: usize, message: String },
+    /// Valid but unsupported input at a (1-based) line
+    Unsupported { line: usize, feature: String },
+}
+
+impl std::fmt::Display for DxfParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DxfParseError::Syntax { line, message } => {
+                write!(f, "syntax error on line {}: {}", line, message)
+            }
+            DxfParseError::Unsupported { line, feature } => {
+                write!(f, "unsupported on line {}: {}", line, feature)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DxfParseError {}
+
+fn syntax<T>(line: usize, message: &str) -> Result<T, DxfParseError> {
+    Err(DxfParseError::Syntax {
+        line,
+        message: message.to_string(),
+    })
+}
+
+/// One group: (line of the code, code, value)
+type Group<'a> = (usize, i32, &'a str);
+
+fn tokenize(text: &str) -> Result<Vec<Group<'_>>, DxfParseError> {
+    if text.starts_with("AutoCAD Binary DXF") {
+        return Err(DxfParseError::Unsupported {
+            line: 1,
+            feature: "binary DXF".to_string(),
+        });
+    }
+    let lines: Vec<&str> = text.lines().collect();
+    let mut groups = Vec::with_capacity(lines.len() / 2);
+    let mut i = 0;
+    while i < lines.len() {
+        let code_line = lines[i].trim();
+        if code_line.is_empty() && i + 1 == lines.len() {
+            break;
+        }
+        let Ok(code) = code_line.parse::<i32>() else {
+            return syntax(i + 1, &format!("invalid group code '{}'", code_line));
+        };
+        let Some(value) = lines.get(i + 1) else {
+            return syntax(i + 1, "group code without value");
+        };
+        groups.push((i + 1, code, value.trim()));
+        i += 2;
+    }
+    Ok(groups)
+}
+
+/// Groups of one entity (everything up to the next code 0)
+struct Record<'a> {
+    kind: &'a str,
+    groups: &'a [Group<'a>],
+}
+
+impl<'a> Record<'a> {
+    fn str(&self, code: i32) -> Option<&'a str> {
+        self.groups.iter().find(|g| g.1 == code).map(|g| g.2)
+    }
+
+    fn f64(&self, code: i32) -> Result<f64, DxfParseError> {
+        match self.groups.iter().find(|g| g.1 == code) {
+            None => Ok(0.0),
+            Some(&(line, _, v)) => v
+                .parse()
+                .or_else(|_| syntax(line + 1, &format!("invalid number '{}'", v))),
+        }
+    }
+
+    fn int(&self, code: i32) -> Result<i32, DxfParseError> {
+        self.f64(code).map(|v| v as i32)
+    }
+
+    fn point(&self, code: i32) -> Result<DxfPoint, DxfParseError> {
+        Ok((self.f64(code)?, self.f64(code + 10)?))
+    }
+
+    fn layer(&self) -> String {
+        self.str(8).unwrap_or("0").to_string()
+    }
+}
+
+/// Split groups into records at each code 0
+fn records<'a>(groups: &'a [Group<'a>]) -> Vec<Record<'a>> {
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < groups.len() {
+        let start = i;
+        i += 1;
+        while i < groups.len() && groups[i].1 != 0 {
+            i += 1;
+        }
+        out.push(Record {
+            kind: groups[start].2,
+            groups: &groups[start + 1..i],
+        });
+    }
+    out
+}
+
+/// Parse an ASCII DXF file
+pub fn parse_dxf(text: &str) -> Result<DxfDocument, DxfParseError> {
+    let groups = tokenize(text)?;
+    let records = records(&groups);
+    let mut doc = DxfDocument::new(DxfUnits::Unitless);
+    let mut section = "";
+    let mut seen_eof = false;
+    let mut i = 0;
+
+    while i < records.len() {
+        let r = &records[i];
+        i += 1;
+        match r.kind {
+            "SECTION" => section = r.str(2).unwrap_or(""),
+            "ENDSEC" => section = "",
+            "EOF" => {
+                seen_eof = true;
+                break;
+            }
+            _ if section == "HEADER" => {}
+            "LAYER" if section == "TABLES" => {
+                if let Some(name) = r.str(2) {
+                    doc.add_layer(name, r.int(62)?.unsigned_abs() as i16);
+                }
+            }
+            "POLYLINE" if section == "ENTITIES" => {
+                let closed = r.int(70)? & 1 == 1;
+                let mut points = Vec::new();
+                while i < records.len() && records[i].kind == "VERTEX" {
+                    points.push(records[i].point(10)?);
+                    i += 1;
+                }
+                if i < records.len() && records[i].kind == "SEQEND" {
+                    i += 1;
+                }
+                doc.add_entity(DxfEntity::Polyline {
+                    layer: r.layer(),
+                    points,
+                    closed,
+                });
+            }
+            _ if section == "ENTITIES" => {
+                if let Some(entity) = parse_entity(r)? {
+                    doc.add_entity(entity);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if !seen_eof {
+        return syntax(text.lines().count(), "missing EOF");
+    }
+
+    // Header variables are pairs (9 name, value)
+    for (k, g) in groups.iter().enumerate() {
+        if g.1 != 9 {
+            continue;
+        }
+        let Some(&(line, _, value)) = groups.get(k + 1) else {
+            continue;
+        };
+        match g.2 {
+            "$ACADVER" => {
+                doc.version = match value {
+                    "AC1009" | "AC1006" | "AC1004" => DxfVersion::R12,
+                    _ => DxfVersion::R2000,
+                }
+            }
+            "$INSUNITS" => {
+                let code = value
+                    .parse()
+                    .or_else(|_| syntax(line + 1, &format!("invalid $INSUNITS '{}'", value)))?;
+                doc.units = DxfUnits::from_code(code);
+            }
+            _ => {}
+        }
+    }
+
+    Ok(doc)
+}
+
+fn parse_entity(r: &Record) -> Result<Option<DxfEntity>, DxfParseError> {
+    let layer = r.layer();
+    let entity = match r.kind {
+        "LINE" => DxfEntity::Line {
+            layer,
+            start: r.point(10)?,
+            end: r.point(11)?,
+        },
+        "LWPOLYLINE" => {
+            let xs = r.groups.iter().filter(|g| g.1 == 10);
+            let ys = r.groups.iter().filter(|g| g.1 == 20);
+            let mut points = Vec::new();
+            for (x, y) in xs.zip(ys) {
+                let parse = |g: &Group| {
+                    g.2.parse::<f64>()
+                        .or_else(|_| syntax(g.0 + 1, &format!("invalid number '{}'", g.2)))
+                };
+                points.push((parse(x)?, parse(y)?));
+            }
+            DxfEntity::Polyline {
+                layer,
+                points,
+                closed: r.int(70)? & 1 == 1,
+            }
+        }
+        "ARC" => DxfEntity::Arc {
+            layer,
+            center: r.point(10)?,
+            radius: r.f64(40)?,
+            start_angle: r.f64(50)?,
+            end_angle: r.f64(51)?,
+        },
+        "CIRCLE" => DxfEntity::Circle {
+            layer,
+            center: r.point(10)?,
+            radius: r.f64(40)?,
+        },
+        "TEXT" | "MTEXT" => {
+            let align = match r.int(72)? {
+                1 | 4 => TextAlign::Center,
+                2 => TextAlign::Right,
+                _ => TextAlign::Left,
+            };
+            let position = if r.kind == "TEXT" && align != TextAlign::Left {
+                r.point(11)?
+            } else {
+                r.point(10)?
+            };
+            DxfEntity::Text {
+                layer,
+                position,
+                height: r.f64(40)?,
+                text: r.str(1).unwrap_or("").to_string(),
+                rotation: r.f64(50)?,
+                align,
+            }
+        }
+        "DIMENSION" => {
+            let (start, end, line_end) = (r.point(13)?, r.point(14)?, r.point(10)?);
+            let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+            let length = (dx * dx + dy * dy).sqrt().max(1e-12);
+            let normal = (-dy / length, dx / length);
+            let offset = (line_end.0 - end.0) * normal.0 + (line_end.1 - end.1) * normal.1;
+            DxfEntity::Dimension {
+                layer,
+                start,
+                end,
+                offset,
+                text: r
+                    .str(1)
+                    .filter(|t| !t.is_empty() && *t != "<>")
+                    .map(str::to_string),
+            }
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(entity))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Adapters
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Planar B-Rep face in its own 2D frame (viewed from outside, along -normal).
+///
+/// The outer loop goes on layer OUTLINE and holes on CUTOUTS. A loop made of a
+/// single closed arc edge becomes a CIRCLE; other loops become closed
+/// polylines with curved edges sampled `curve_samples` times. Returns `None`
+/// for missing or non-planar faces.
+pub fn planar_face_to_dxf(
+    solid: &Solid,
+    face: FaceId,
+    curve_samples: usize,
+) -> Option<DxfDocument> {
+    let face = solid.face(face)?;
+    let SurfaceType::Planar { normal } = face.surface else {
+        return None;
+    };
+    let n = normal.normalize()?;
+    let u = if n.z.abs() > 0.9 {
+        Vector3::X
+    } else {
+        Vector3::Z.cross(n).normalize_or_z()
+    };
+    let v = n.cross(u);
+    let origin = solid
+        .loop_points(&face.outer_loop, 1)
+        .first()
+        .copied()
+        .unwrap_or(Point3::ORIGIN);
+    let to_2d = |p: Point3| {
+        let d = p - origin;
+        (d.dot(u) as f64, d.dot(v) as f64)
+    };
+
+    let mut doc = DxfDocument::new(DxfUnits::Unitless);
+    doc.add_layer("OUTLINE", 7);
+    let loops = std::iter::once((&face.outer_loop, "OUTLINE"))
+        .chain(face.inner_loops.iter().map(|l| (l, "CUTOUTS")));
+    for (loop_, layer) in loops {
+        let single_circle = match loop_.edges.as_slice() {
+            [id] => solid.edge(*id).and_then(|e| match e.curve {
+                CurveType::Arc { center, radius, .. } if e.start == e.end => Some((center, radius)),
+                _ => None,
+            }),
+            _ => None,
+        };
+        if let Some((center, radius)) = single_circle {
+            doc.add_circle(layer, to_2d(center), radius as f64);
+            continue;
+        }
+        let points: Vec<DxfPoint> = solid
+            .loop_points(loop_, curve_samples)
+            .into_iter()
+            .map(to_2d)
+            .collect();
+        if points.len() >= 2 {
+            doc.add_polyline(layer, points, true);
+        }
+    }
+    Some(doc)
+}
+
+/// PCB outline and drill holes (millimetres) for mechanical CAD and enclosures
+pub fn fab_board_to_dxf(board: &FabBoard) -> DxfDocument {
+    let mut doc = DxfDocument::new(DxfUnits::Millimeters);
+    doc.add_layer("BOARD_OUTLINE", 7);
+    doc.add_layer("DRILL", 1);
+    if board.outline.len() >= 2 {
+        doc.add_polyline("BOARD_OUTLINE", board.outline.clone(), true);
+    }
+    for hole in &board.holes {
+        doc.add_circle("DRILL", hole.position, hole.diameter / 2.0);
+    }
+    for via in &board.vias {
+        doc.add_circle("DRILL", via.position, via.drill / 2.0);
+    }
+    for pad in &board.pads {
+        if let Some(drill) = pad.drill {
+            doc.add_circle("DRILL", pad.position, drill / 2.0);
+        }
+    }
+    doc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cad::make_box;
+    use crate::export::fabrication::FabHole;
+
+    fn sample() -> DxfDocument {
+        let mut doc = DxfDocument::new(DxfUnits::Inches);
+        doc.add_layer("CUT", 1);
+        doc.add_line("CUT", (0.0, 0.0), (10.0, 0.0));
+        doc.add_polyline("CUT", vec![(0.0, 0.0), (4.0, 0.0), (4.0, 2.5)], true);
+        doc.add_arc("CUT", (1.0, 1.0), 0.5, 0.0, 90.0);
+        doc.add_circle("DRILL", (2.0, 2.0), 0.1875);
+        doc.add_text("LABELS", (1.0, 3.0), 0.25, "PANEL A");
+        doc.add_entity(DxfEntity::Text {
+            layer: "LABELS".to_string(),
+            position: (5.0, 3.0),
+            height: 0.125,
+            text: "CENTER".to_string(),
+            rotation: 90.0,
+            align: TextAlign::Center,
+        });
+        doc.add_dimension("DIMS", (0.0, 0.0), (10.0, 0.0), -1.5);
+        doc
+    }
+
+    #[test]
+    fn test_round_trip_both_versions() {
+        for version in [DxfVersion::R12, DxfVersion::R2000] {
+            let mut doc = sample();
+            doc.version = version;
+            let text = doc.to_string();
+            assert!(text.contains(version.acad_version()));
+            assert!(text.trim_end().ends_with("EOF"));
+
+            let back = parse_dxf(&text).unwrap();
+            assert_eq!(back, doc, "{:?}", version);
+        }
+
+        let mut doc = sample();
+        assert!(doc.to_string().contains("POLYLINE\n  8\nCUT\n 66\n1"));
+        doc.version = DxfVersion::R2000;
+        let text = doc.to_string();
+        assert!(text.contains("LWPOLYLINE") && text.contains("AcDbAlignedDimension"));
+        assert!(text.contains("*Model_Space") && text.contains("$HANDSEED"));
+        // Dimension geometry is rendered into its anonymous block
+        assert!(text.contains("  2\n*D1"));
+        assert!(text.contains("  1\n10\n"));
+    }
+
+    #[test]
+    fn test_parse_errors_and_lenient_input() {
+        let err = parse_dxf("  0\nSECTION\nabc\nENTITIES\n").unwrap_err();
+        assert_eq!(
+            err,
+            DxfParseError::Syntax {
+                line: 3,
+                message: "invalid group code 'abc'".to_string(),
+            }
+        );
+        assert!(matches!(
+            parse_dxf(
+                "  0\nSECTION\n  2\nENTITIES\n  0\nCIRCLE\n 40\nwide\n  0\nENDSEC\n  0\nEOF\n"
+            ),
+            Err(DxfParseError::Syntax { line: 8, .. })
+        ));
+        assert!(matches!(
+            parse_dxf("  0\nSECTION\n  2\nENTITIES\n  0\nENDSEC\n"),
+            Err(DxfParseError::Syntax { .. })
+        ));
+
+        // Unknown entities are skipped; entity layers missing from the table are created
+        let doc = parse_dxf(
+            "0\nSECTION\n2\nENTITIES\n0\nSPLINE\n8\nX\n0\nCIRCLE\n8\nHOLES\n10\n1\n20\n2\n40\n3\n0\nENDSEC\n0\nEOF\n",
+        )
+        .unwrap();
+        assert_eq!(doc.entities().len(), 1);
+        assert!(doc.layer("HOLES").is_some());
+        assert_eq!(doc.units, DxfUnits::Unitless);
+    }
+
+    #[test]
+    fn test_adapters() {
+        let solid = make_box(4.0, 2.0, 1.0);
+        let top = solid
+            .faces
+            .iter()
+            .find(|f| matches!(f.surface, SurfaceType::Planar { normal } if normal.z > 0.9))
+            .unwrap();
+        let doc = planar_face_to_dxf(&solid, top.id, 8).unwrap();
+        let DxfEntity::Polyline { points, closed, .. } = &doc.entities()[0] else {
+            panic!("expected polyline");
+        };
+        assert!(*closed);
+        assert_eq!(points.len(), 4);
+        let ((x0, y0), (x1, y1)) = doc.bounds().unwrap();
+        assert!(((x1 - x0) - 4.0).abs() < 1e-4 && ((y1 - y0) - 2.0).abs() < 1e-4);
+
+        let mut board = FabBoard::rectangle("demo", 50.0, 30.0);
+        board.holes.push(FabHole {
+            position: (3.0, 3.0),
+            diameter: 3.2,
+        });
+        let doc = fab_board_to_dxf(&board);
+        assert_eq!(doc.units, DxfUnits::Millimeters);
+        assert_eq!(doc.entities().len(), 2);
+        assert!(matches!(
+            doc.entities()[1],
+            DxfEntity::Circle { radius, .. } if (radius - 1.6).abs() < 1e-12
+        ));
+    }
+}
@@ -0,0 +1,268 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: excellon.rs | DNA/src/export/excellon.rs
+//! PURPOSE: Defines ExcellonDocument, DrillTool, DrillHit types (NC drill files)
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//! Excellon NC Drill Generator - From Scratch
+//!
+//! Writes the Excellon dialect accepted by fab houses (and by KiCad/gerbv):
+//! decimal coordinates, absolute positioning, with X2-style attributes in
+//! `; #@!` comments so drill files carry the same metadata as Gerber layers.
+//!
+//! Format overview:
+//! ```text
+//! M48
+//! ; #@! TF.FileFunction,Plated,1,2,PTH
+//! FMAT,2
+//! METRIC
+//! ; #@! TA.AperFunction,Plated,PTH,ViaDrill
+//! T1C0.3
+//! %
+//! G90
+//! G05
+//! T1
+//! X10.0Y5.0
+//! M30
+//! ```
+
+use super::gerber::{attribute_field, GerberUnit};
+
+/// Excellon drill file builder
+#[derive(Debug, Clone)]
+pub struct ExcellonDocument {
+    /// X2 file function (e.g. "Plated,1,2,PTH" or "NonPlated,1,2,NPTH")
+    pub(crate) file_function: String,
+    pub(crate) unit: GerberUnit,
+    pub(crate) tools: Vec<DrillTool>,
+    pub(crate) hits: Vec<DrillHit>,
+    pub(crate) generation_software: String,
+}
+
+/// Drill tool (T-code)
+#[derive(Debug, Clone, PartialEq)]
+pub struct DrillTool {
+    pub number: u32,
+    pub diameter: f64,
+    /// X2 .AperFunction (e.g. "Plated,PTH,ViaDrill")
+    pub function: Option<String>,
+}
+
+/// Drilled feature
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DrillHit {
+    /// Round hole
+    Hole { tool: u32, x: f64, y: f64 },
+    /// Routed slot (G85) between two points
+    Slot {
+        tool: u32,
+        start: (f64, f64),
+        end: (f64, f64),
+    },
+}
+
+impl DrillHit {
+    pub fn tool(&self) -> u32 {
+        match *self {
+            DrillHit::Hole { tool, .. } | DrillHit::Slot { tool, .. } => tool,
+        }
+    }
+}
+
+impl ExcellonDocument {
+    /// Create a new drill file
+    pub fn new(file_function: &str) -> Self {
+        Self {
+            file_function: file_function.to_string(),
+            unit: GerberUnit::Millimeters,
+            tools: Vec::new(),
+            hits: Vec::new(),
+            generation_software: "too.foo,PLL Designer,1.0".to_string(),
+        }
+    }
+
+    /// Set the GenerationSoftware attribute (vendor, application, version)
+    pub fn set_generation_software(&mut self, vendor: &str, application: &str, version: &str) {
+        self.generation_software = format!(
+            "{},{},{}",
+            attribute_field(vendor),
+            attribute_field(application),
+            attribute_field(version)
+        );
+    }
+
+    /// Set unit to millimeters
+    pub fn set_unit_mm(&mut self) {
+        self.unit = GerberUnit::Millimeters;
+    }
+
+    /// Set unit to inches
+    pub fn set_unit_inches(&mut self) {
+        self.unit = GerberUnit::Inches;
+    }
+
+    /// Tool for `diameter`, reusing an existing tool with the same size and function
+    pub fn tool(&mut self, diameter: f64, function: Option<&str>) -> u32 {
+        if let Some(t) = self
+            .tools
+            .iter()
+            .find(|t| (t.diameter - diameter).abs() < 1e-9 && t.function.as_deref() == function)
+        {
+            return t.number;
+        }
+        let number = self.tools.len() as u32 + 1;
+        self.tools.push(DrillTool {
+            number,
+            diameter,
+            function: function.map(str::to_string),
+        });
+        number
+    }
+
+    /// Drill a round hole
+    pub fn drill(&mut self, tool: u32, x: f64, y: f64) {
+        self.hits.push(DrillHit::Hole { tool, x, y });
+    }
+
+    /// Route a slot from `start` to `end`
+    pub fn slot(&mut self, tool: u32, start: (f64, f64), end: (f64, f64)) {
+        self.hits.push(DrillHit::Slot { tool, start, end });
+    }
+
+    pub fn file_function(&self) -> &str {
+        &self.file_function
+    }
+
+    pub fn unit(&self) -> GerberUnit {
+        self.unit
+    }
+
+    /// True for plated (PTH) drill files
+    pub fn is_plated(&self) -> bool {
+        self.file_function.starts_with("Plated")
+    }
+
+    pub fn tools(&self) -> &[DrillTool] {
+        &self.tools
+    }
+
+    pub fn hits(&self) -> &[DrillHit] {
+        &self.hits
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hits.is_empty()
+    }
+
+    /// Generate the drill file content as bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.to_string().into_bytes()
+    }
+
+    fn coord(&self, value: f64) -> String {
+        let decimals = match self.unit {
+            GerberUnit::Millimeters => 3,
+            GerberUnit::Inches => 4,
+        };
+        let mut text = format!("{:.*}", decimals, value);
+        // Keep one decimal so the reader never applies zero suppression
+        while text.ends_with('0') && !text.ends_with(".0") {
+            text.pop();
+        }
+        if text == "-0.0" {
+            text = "0.0".to_string();
+        }
+        text
+    }
+}
+
+impl std::fmt::Display for ExcellonDocument {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "M48")?;
+        writeln!(f, "; DRILL file {{{}}}", self.generation_software)?;
+        let unit_name = match self.unit {
+            GerberUnit::Millimeters => "metric",
+            GerberUnit::Inches => "inch",
+        };
+        writeln!(f, "; FORMAT={{-:-/ absolute / {} / decimal}}", unit_name)?;
+        writeln!(
+            f,
+            "; #@! TF.GenerationSoftware,{}",
+            self.generation_software
+        )?;
+        writeln!(f, "; #@! TF.FileFunction,{}", self.file_function)?;
+        writeln!(f, "FMAT,2")?;
+        match self.unit {
+            GerberUnit::Millimeters => writeln!(f, "METRIC")?,
+            GerberUnit::Inches => writeln!(f, "INCH")?,
+        }
+
+        // Tool table
+        for tool in &self.tools {
+            if let Some(function) = &tool.function {
+                writeln!(f, "; #@! TA.AperFunction,{}", function)?;
+            }
+            writeln!(f, "T{}C{}", tool.number, self.coord(tool.diameter))?;
+        }
+        writeln!(f, "%")?;
+        writeln!(f, "G90")?;
+        writeln!(f, "G05")?;
+
+        // Hits grouped by tool, in tool order
+        for tool in &self.tools {
+            let mut selected = false;
+            for hit in self.hits.iter().filter(|h| h.tool() == tool.number) {
+                if !selected {
+                    writeln!(f, "T{}", tool.number)?;
+                    selected = true;
+                }
+                match *hit {
+                    DrillHit::Hole { x, y, .. } => {
+                        writeln!(f, "X{}Y{}", self.coord(x), self.coord(y))?;
+                    }
+                    DrillHit::Slot { start, end, .. } => {
+                        writeln!(
+                            f,
+                            "X{}Y{}G85X{}Y{}",
+                            self.coord(start.0),
+                            self.coord(start.1),
+                            self.coord(end.0),
+                            self.coord(end.1)
+                        )?;
+                        // G85 leaves the drill in routing mode on some readers
+                        writeln!(f, "G05")?;
+                    }
+                }
+            }
+        }
+        writeln!(f, "M30")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_excellon_output() {
+        let mut drl = ExcellonDocument::new("Plated,1,2,PTH");
+        let via = drl.tool(0.3, Some("Plated,PTH,ViaDrill"));
+        let pin = drl.tool(1.0, Some("Plated,PTH,ComponentDrill"));
+        assert_eq!(drl.tool(0.3, Some("Plated,PTH,ViaDrill")), via);
+
+        drl.drill(pin, 2.54, 0.0);
+        drl.drill(via, 10.0, 5.25);
+        drl.slot(pin, (0.0, 0.0), (0.0, 2.0));
+
+        let output = drl.to_string();
+        assert!(output.starts_with("M48\n"));
+        assert!(output.contains("; #@! TF.FileFunction,Plated,1,2,PTH\n"));
+        assert!(output.contains("; #@! TA.AperFunction,Plated,PTH,ViaDrill\nT1C0.3\n"));
+        assert!(output.contains("METRIC\n"));
+        // Vias (T1) come first regardless of insertion order
+        assert!(output.contains("T1\nX10.0Y5.25\nT2\nX2.54Y0.0\nX0.0Y0.0G85X0.0Y2.0\n"));
+        assert!(output.trim_end().ends_with("M30"));
+    }
+}
@@ -0,0 +1,927 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: fabrication.rs | DNA/src/export/fabrication.rs
+//! PURPOSE: Two-layer PCB fabrication package (Gerber X2 layers, Excellon, job file, zip)
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ PACKAGE CONTENTS                                                            │
+//! ├──────────────────────────┬──────────────────────────────────────────────────┤
+//! │ <name>-F_Cu.gbr          │ Copper,L1,Top       pads, tracks, vias           │
+//! │ <name>-B_Cu.gbr          │ Copper,L2,Bot                                    │
+//! │ <name>-F_Mask.gbr        │ Soldermask,Top      negative, pad openings       │
+//! │ <name>-B_Mask.gbr        │ Soldermask,Bot                                   │
+//! │ <name>-F_Paste.gbr       │ Paste,Top           SMD pads only                │
+//! │ <name>-B_Paste.gbr       │ Paste,Bot                                        │
+//! │ <name>-F_Silkscreen.gbr  │ Legend,Top                                       │
+//! │ <name>-B_Silkscreen.gbr  │ Legend,Bot                                       │
+//! │ <name>-Edge_Cuts.gbr     │ Profile,NP          board outline                │
+//! │ <name>-PTH.drl           │ Plated,1,2,PTH      vias + component holes       │
+//! │ <name>-NPTH.drl          │ NonPlated,1,2,NPTH  mounting holes               │
+//! │ <name>-job.gbrjob        │ Gerber job file (JSON)                           │
+//! └──────────────────────────┴──────────────────────────────────────────────────┘
+//!
+//! Copper objects carry X2 object attributes: `.N` (net) on pads, tracks and
+//! vias, plus `.P` / `.C` (component pin / reference) on pads. All coordinates
+//! are millimetres, board viewed from the top (bottom layers are not mirrored).
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use serde_json::json;
+
+use super::excellon::ExcellonDocument;
+use super::gerber::{attribute_field, ApertureType, GerberDocument};
+
+/// Board side for pads, tracks and graphics
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BoardSide {
+    Top,
+    Bottom,
+}
+
+/// Pad outline (dimensions in mm, already rotated into board orientation)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PadShape {
+    Circle {
+        diameter: f64,
+    },
+    Rect {
+        width: f64,
+        height: f64,
+    },
+    RoundRect {
+        width: f64,
+        height: f64,
+        radius: f64,
+    },
+    Obround {
+        width: f64,
+        height: f64,
+    },
+}
+
+impl PadShape {
+    /// Shape grown (positive) or shrunk (negative) by `margin` on every side
+    pub fn expanded(&self, margin: f64) -> PadShape {
+        let grow = |v: f64| (v + 2.0 * margin).max(0.0);
+        match *self {
+            PadShape::Circle { diameter } => PadShape::Circle {
+                diameter: grow(diameter),
+            },
+            PadShape::Rect { width, height } => PadShape::Rect {
+                width: grow(width),
+                height: grow(height),
+            },
+            PadShape::RoundRect {
+                width,
+                height,
+                radius,
+            } => PadShape::RoundRect {
+                width: grow(width),
+                height: grow(height),
+                radius: (radius + margin).max(0.0),
+            },
+            PadShape::Obround { width, height } => PadShape::Obround {
+                width: grow(width),
+                height: grow(height),
+            },
+        }
+    }
+
+    /// Bounding box size (width, height)
+    pub fn size(&self) -> (f64, f64) {
+        match *self {
+            PadShape::Circle { diameter } => (diameter, diameter),
+            PadShape::Rect { width, height }
+            | PadShape::RoundRect { width, height, .. }
+            | PadShape::Obround { width, height } => (width, height),
+        }
+    }
+
+    pub fn aperture_type(&self) -> ApertureType {
+        match *self {
+            PadShape::Circle { diameter } => ApertureType::Circle { diameter },
+            PadShape::Rect { width, height } => ApertureType::Rectangle { width, height },
+            PadShape::RoundRect {
+                width,
+                height,
+                radius,
+            } => ApertureType::RoundRect {
+                width,
+                height,
+                radius: radius.clamp(0.0, width.min(height) / 2.0),
+            },
+            PadShape::Obround { width, height } => ApertureType::Obround { width, height },
+        }
+    }
+}
+
+/// Straight or circular piece of a track, outline or silkscreen stroke
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TrackSegment {
+    Line {
+        start: (f64, f64),
+        end: (f64, f64),
+    },
+    Arc {
+        start: (f64, f64),
+        end: (f64, f64),
+        center: (f64, f64),
+        clockwise: bool,
+    },
+}
+
+impl TrackSegment {
+    pub fn start(&self) -> (f64, f64) {
+        match *self {
+            TrackSegment::Line { start, .. } | TrackSegment::Arc { start, .. } => start,
+        }
+    }
+
+    pub fn end(&self) -> (f64, f64) {
+        match *self {
+            TrackSegment::Line { end, .. } | TrackSegment::Arc { end, .. } => end,
+        }
+    }
+}
+
+/// Component pad; through-hole pads (with `drill`) appear on both copper layers
+#[derive(Clone, Debug, PartialEq)]
+pub struct FabPad {
+    /// Reference designator (e.g. "R1")
+    pub component: String,
+    /// Pin number or name
+    pub pin: String,
+    pub net: Option<String>,
+    pub position: (f64, f64),
+    pub shape: PadShape,
+    pub side: BoardSide,
+    pub drill: Option<f64>,
+}
+
+/// Copper track
+#[derive(Clone, Debug, PartialEq)]
+pub struct FabTrack {
+    pub side: BoardSide,
+    pub width: f64,
+    pub net: Option<String>,
+    pub segment: TrackSegment,
+}
+
+/// Plated via between top and bottom copper
+#[derive(Clone, Debug, PartialEq)]
+pub struct FabVia {
+    pub position: (f64, f64),
+    pub diameter: f64,
+    pub drill: f64,
+    pub net: Option<String>,
+}
+
+/// Non-plated (mounting) hole
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FabHole {
+    pub position: (f64, f64),
+    pub diameter: f64,
+}
+
+/// Silkscreen stroke
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FabGraphic {
+    pub side: BoardSide,
+    pub width: f64,
+    pub segment: TrackSegment,
+}
+
+/// Everything needed to fabricate a two-layer board
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FabBoard {
+    pub name: String,
+    /// Closed board outline (last point connects back to the first)
+    pub outline: Vec<(f64, f64)>,
+    pub pads: Vec<FabPad>,
+    pub tracks: Vec<FabTrack>,
+    pub vias: Vec<FabVia>,
+    pub holes: Vec<FabHole>,
+    pub silkscreen: Vec<FabGraphic>,
+}
+
+impl FabBoard {
+    pub fn new(name: &str, outline: Vec<(f64, f64)>) -> Self {
+        Self {
+            name: name.to_string(),
+            outline,
+            ..Default::default()
+        }
+    }
+
+    /// Rectangular board with its lower-left corner at the origin
+    pub fn rectangle(name: &str, width: f64, height: f64) -> Self {
+        Self::new(
+            name,
+            vec![(0.0, 0.0), (width, 0.0), (width, height), (0.0, height)],
+        )
+    }
+
+    /// Outline bounding box (min, max)
+    pub fn bounds(&self) -> ((f64, f64), (f64, f64)) {
+        let mut min = (f64::INFINITY, f64::INFINITY);
+        let mut max = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+        for &(x, y) in &self.outline {
+            min = (min.0.min(x), min.1.min(y));
+            max = (max.0.max(x), max.1.max(y));
+        }
+        if self.outline.is_empty() {
+            ((0.0, 0.0), (0.0, 0.0))
+        } else {
+            (min, max)
+        }
+    }
+}
+
+/// Fabrication package options
+#[derive(Clone, Debug)]
+pub struct FabOptions {
+    pub revision: String,
+    /// Finished board thickness (mm)
+    pub board_thickness: f64,
+    /// Solder mask opening growth around pads (mm)
+    pub mask_expansion: f64,
+    /// Paste stencil aperture shrink on each side (mm)
+    pub paste_reduction: f64,
+    /// Cover vias with solder mask
+    pub tent_vias: bool,
+    /// Width of the board outline stroke (mm)
+    pub outline_width: f64,
+    /// ISO-8601 timestamp for the job file (omitted when None, keeping output reproducible)
+    pub creation_date: Option<String>,
+}
+
+impl Default for FabOptions {
+    fn default() -> Self {
+        Self {
+            revision: "1".to_string(),
+            board_thickness: 1.6,
+            mask_expansion: 0.05,
+            paste_reduction: 0.0,
+            tent_vias: true,
+            outline_width: 0.1,
+            creation_date: None,
+        }
+    }
+}
+
+/// One file of a fabrication package
+#[derive(Clone, Debug, PartialEq)]
+pub struct FabFile {
+    pub name: String,
+    /// X2 file function (e.g. "Copper,L1,Top"); empty for the job file
+    pub file_function: String,
+    pub polarity: Option<&'static str>,
+    pub contents: Vec<u8>,
+}
+
+/// Complete fabrication output
+#[derive(Clone, Debug, PartialEq)]
+pub struct FabricationPackage {
+    pub files: Vec<FabFile>,
+}
+
+impl FabricationPackage {
+    pub fn file(&self, name: &str) -> Option<&FabFile> {
+        self.files.iter().find(|f| f.name == name)
+    }
+
+    /// Find a file by X2 file function (e.g. "Soldermask,Top")
+    pub fn file_by_function(&self, function: &str) -> Option<&FabFile> {
+        self.files.iter().find(|f| f.file_function == function)
+    }
+
+    /// All files in one uncompressed (stored) ZIP archive
+    pub fn to_zip(&self) -> Vec<u8> {
+        let entries: Vec<(&str, &[u8])> = self
+            .files
+            .iter()
+            .map(|f| (f.name.as_str(), f.contents.as_slice()))
+            .collect();
+        zip_store(&entries)
+    }
+}
+
+const VENDOR: &str = "too.foo";
+const APPLICATION: &str = "S3M2P PCB";
+const VERSION: &str = "1.0";
+
+/// Generate Gerber X2 layers, Excellon drill files and a Gerber job file
+pub fn generate_fabrication_package(board: &FabBoard, options: &FabOptions) -> FabricationPackage {
+    let base = file_stem(&board.name);
+    let mut files = Vec::new();
+
+    for side in [BoardSide::Top, BoardSide::Bottom] {
+        let (tag, layer) = match side {
+            BoardSide::Top => ("F", "Top"),
+            BoardSide::Bottom => ("B", "Bot"),
+        };
+        let copper = copper_function(side);
+        files.push(gerber_file(
+            format!("{}-{}_Cu.gbr", base, tag),
+            copper,
+            "Positive",
+            copper_layer(board, side, copper),
+        ));
+        let mask = format!("Soldermask,{}", layer);
+        files.push(gerber_file(
+            format!("{}-{}_Mask.gbr", base, tag),
+            &mask,
+            "Negative",
+            mask_layer(board, side, &mask, options),
+        ));
+        let paste = format!("Paste,{}", layer);
+        files.push(gerber_file(
+            format!("{}-{}_Paste.gbr", base, tag),
+            &paste,
+            "Positive",
+            paste_layer(board, side, &paste, options),
+        ));
+        let legend = format!("Legend,{}", layer);
+        files.push(gerber_file(
+            format!("{}-{}_Silkscreen.gbr", base, tag),
+            &legend,
+            "Positive",
+            legend_layer(board, side, &legend),
+        ));
+    }
+    files.push(gerber_file(
+        format!("{}-Edge_Cuts.gbr", base),
+        "Profile,NP",
+        "Positive",
+        profile_layer(board, options),
+    ));
+
+    let (pth, npth) = drill_files(board);
+    files.push(FabFile {
+        name: format!("{}-PTH.drl", base),
+        file_function: "Plated,1,2,PTH".to_string(),
+        polarity: None,
+        contents: pth.to_bytes(),
+    });
+    files.push(FabFile {
+        name: format!("{}-NPTH.drl", base),
+        file_function: "NonPlated,1,2,NPTH".to_string(),
+        polarity: None,
+        contents: npth.to_bytes(),
+    });
+
+    let job = job_file(board, options, &files);
+    files.push(FabFile {
+        name: format!("{}-job.gbrjob", base),
+        file_function: String::new(),
+        polarity: None,
+        contents: job.into_bytes(),
+    });
+
+    FabricationPackage { files }
+}
+
+/// Copper layer of `board` as a standalone Gerber document
+pub fn copper_gerber(board: &FabBoard, side: BoardSide) -> GerberDocument {
+    copper_layer(board, side, copper_function(side))
+}
+
+fn copper_function(side: BoardSide) -> &'static str {
+    match side {
+        BoardSide::Top => "Copper,L1,Top",
+        BoardSide::Bottom => "Copper,L2,Bot",
+    }
+}
+
+fn gerber_file(
+    name: String,
+    function: &str,
+    polarity: &'static str,
+    doc: GerberDocument,
+) -> FabFile {
+    FabFile {
+        name,
+        file_function: function.to_string(),
+        polarity: Some(polarity),
+        contents: doc.to_bytes(),
+    }
+}
+
+fn new_layer(function: &str, polarity: &str) -> GerberDocument {
+    let mut doc = GerberDocument::new(function);
+    doc.set_unit_mm();
+    doc.set_generation_software(VENDOR, APPLICATION, VERSION);
+    doc.add_file_attribute(".Part", "Single");
+    doc.add_file_attribute(".FilePolarity", polarity);
+    doc.add_file_attribute(".SameCoordinates", "Original");
+    doc
+}
+
+/// Tracks the selected aperture and object attributes so they are only
+/// re-emitted when they change
+struct LayerWriter {
+    doc: GerberDocument,
+    aperture: Option<u32>,
+    attributes: Vec<(&'static str, String)>,
+}
+
+impl LayerWriter {
+    fn new(doc: GerberDocument) -> Self {
+        Self {
+            doc,
+            aperture: None,
+            attributes: Vec::new(),
+        }
+    }
+
+    fn select(&mut self, aperture_type: ApertureType, function: Option<&str>) {
+        let number = self.doc.aperture(aperture_type, function);
+        if self.aperture != Some(number) {
+            self.doc.select_aperture(number);
+            self.aperture = Some(number);
+        }
+    }
+
+    fn attributes(&mut self, attributes: Vec<(&'static str, String)>) {
+        if attributes != self.attributes {
+            if !self.attributes.is_empty() {
+                self.doc.delete_object_attributes();
+            }
+            for (name, value) in &attributes {
+                self.doc.set_object_attribute(name, value);
+            }
+            self.attributes = attributes;
+        }
+    }
+
+    fn draw(&mut self, segment: &TrackSegment) {
+        let (sx, sy) = segment.start();
+        self.doc.move_to(sx, sy);
+        match *segment {
+            TrackSegment::Line { end, .. } => self.doc.line_to(end.0, end.1),
+            TrackSegment::Arc {
+                end,
+                center,
+                clockwise,
+                ..
+            } => self.doc.arc_to(end.0, end.1, center.0, center.1, clockwise),
+        }
+    }
+
+    fn finish(mut self) -> GerberDocument {
+        self.attributes(Vec::new());
+        self.doc
+    }
+}
+
+fn net_attribute(net: &Option<String>) -> Vec<(&'static str, String)> {
+    // An empty .N marks a copper object that is deliberately not connected
+    vec![(
+        ".N",
+        net.as_deref().map(attribute_field).unwrap_or_default(),
+    )]
+}
+
+fn pad_on_side(pad: &FabPad, side: BoardSide) -> bool {
+    pad.drill.is_some() || pad.side == side
+}
+
+fn pad_function(pad: &FabPad) -> &'static str {
+    if pad.drill.is_some() {
+        "ComponentPad"
+    } else {
+        "SMDPad,CuDef"
+    }
+}
+
+fn copper_layer(board: &FabBoard, side: BoardSide, function: &str) -> GerberDocument {
+    let mut w = LayerWriter::new(new_layer(function, "Positive"));
+
+    for track in board.tracks.iter().filter(|t| t.side == side) {
+        w.select(
+            ApertureType::Circle {
+                diameter: track.width,
+            },
+            Some("Conductor"),
+        );
+        w.attributes(net_attribute(&track.net));
+        w.draw(&track.segment);
+    }
+    for via in &board.vias {
+        w.select(
+            ApertureType::Circle {
+                diameter: via.diameter,
+            },
+            Some("ViaPad"),
+        );
+        w.attributes(net_attribute(&via.net));
+        w.doc.flash(via.position.0, via.position.1);
+    }
+    for pad in board.pads.iter().filter(|p| pad_on_side(p, side)) {
+        w.select(pad.shape.aperture_type(), Some(pad_function(pad)));
+        let mut attributes = net_attribute(&pad.net);
+        attributes.push((
+            ".P",
+            format!(
+                "{},{}",
+                attribute_field(&pad.component),
+                attribute_field(&pad.pin)
+            ),
+        ));
+        attributes.push((".C", attribute_field(&pad.component)));
+        w.attributes(attributes);
+        w.doc.flash(pad.position.0, pad.position.1);
+    }
+    w.finish()
+}
+
+fn mask_layer(
+    board: &FabBoard,
+    side: BoardSide,
+    function: &str,
+    options: &FabOptions,
+) -> GerberDocument {
+    let mut w = LayerWriter::new(new_layer(function, "Negative"));
+    for pad in board.pads.iter().filter(|p| pad_on_side(p, side)) {
+        let opening = pad.shape.expanded(options.mask_expansion);
+        w.select(opening.aperture_type(), Some(pad_function(pad)));
+        w.doc.flash(pad.position.0, pad.position.1);
+    }
+    if !options.tent_vias {
+        for via in &board.vias {
+            w.select(
+                ApertureType::Circle {
+                    diameter: via.diameter + 2.0 * options.mask_expansion,
+                },
+                Some("ViaPad"),
+            );
+            w.doc.flash(via.position.0, via.position.1);
+        }
+    }
+    w.finish()
+}
+
+fn paste_layer(
+    board: &FabBoard,
+    side: BoardSide,
+    function: &str,
+    options: &FabOptions,
+) -> GerberDocument {
+    let mut w = LayerWriter::new(new_layer(function, "Positive"));
+    for pad in board
+        .pads
+        .iter()
+        .filter(|p| p.drill.is_none() && p.side == side)
+    {
+        let stencil = pad.shape.expanded(-options.paste_reduction);
+        let (width, height) = stencil.size();
+        if width <= 0.0 || height <= 0.0 {
+            continue;
+        }
+        w.select(stencil.aperture_type(), Some("SMDPad,CuDef"));
+        w.doc.flash(pad.position.0, pad.position.1);
+    }
+    w.finish()
+}
+
+fn legend_layer(board: &FabBoard, side: BoardSide, function: &str) -> GerberDocument {
+    let mut w = LayerWriter::new(new_layer(function, "Positive"));
+    for graphic in board.silkscreen.iter().filter(|g| g.side == side) {
+        w.select(
+            ApertureType::Circle {
+                diameter: graphic.width,
+            },
+            None,
+        );
+        w.draw(&graphic.segment);
+    }
+    w.finish()
+}
+
+fn profile_layer(board: &FabBoard, options: &FabOptions) -> GerberDocument {
+    let mut w = LayerWriter::new(new_layer("Profile,NP", "Positive"));
+    if let Some(&(x0, y0)) = board.outline.first() {
+        w.select(
+            ApertureType::Circle {
+                diameter: options.outline_width,
+            },
+            Some("Profile"),
+        );
+        w.doc.move_to(x0, y0);
+        for &(x, y) in board.outline.iter().skip(1) {
+            w.doc.line_to(x, y);
+        }
+        w.doc.line_to(x0, y0);
+    }
+    w.finish()
+}
+
+fn drill_files(board: &FabBoard) -> (ExcellonDocument, ExcellonDocument) {
+    let mut pth = ExcellonDocument::new("Plated,1,2,PTH");
+    pth.set_generation_software(VENDOR, APPLICATION, VERSION);
+    for via in &board.vias {
+        let tool = pth.tool(via.drill, Some("Plated,PTH,ViaDrill"));
+        pth.drill(tool, via.position.0, via.position.1);
+    }
+    for pad in &board.pads {
+        if let Some(drill) = pad.drill {
+            let tool = pth.tool(drill, Some("Plated,PTH,ComponentDrill"));
+            pth.drill(tool, pad.position.0, pad.position.1);
+        }
+    }
+
+    let mut npth = ExcellonDocument::new("NonPlated,1,2,NPTH");
+    npth.set_generation_software(VENDOR, APPLICATION, VERSION);
+    for hole in &board.holes {
+        let tool = npth.tool(hole.diameter, Some("NonPlated,NPTH,Drill"));
+        npth.drill(tool, hole.position.0, hole.position.1);
+    }
+    (pth, npth)
+}
+
+fn job_file(board: &FabBoard, options: &FabOptions, files: &[FabFile]) -> String {
+    let ((x0, y0), (x1, y1)) = board.bounds();
+    let mut header = json!({
+        "GenerationSoftware": {
+            "Vendor": VENDOR,
+            "Application": APPLICATION,
+            "Version": VERSION
+        }
+    });
+    if let Some(date) = &options.creation_date {
+        header["CreationDate"] = json!(date);
+    }
+    let min_track = board
+        .tracks
+        .iter()
+        .map(|t| t.width)
+        .fold(f64::INFINITY, f64::min);
+    let mut design_rules = json!({ "Layers": "Outer" });
+    if min_track.is_finite() {
+        design_rules["MinLineWidth"] = json!(min_track);
+    }
+    let files_attributes: Vec<_> = files
+        .iter()
+        .filter(|f| f.polarity.is_some())
+        .map(|f| {
+            json!({
+                "Path": f.name,
+                "FileFunction": f.file_function,
+                "FilePolarity": f.polarity
+            })
+        })
+        .collect();
+    let job = json!({
+        "Header": header,
+        "GeneralSpecs": {
+            "ProjectId": {
+                "Name": board.name,
+                "Revision": options.revision
+            },
+            "Size": { "X": x1 - x0, "Y": y1 - y0 },
+            "LayerNumber": 2,
+            "BoardThickness": options.board_thickness
+        },
+        "DesignRules": [design_rules],
+        "FilesAttributes": files_attributes
+    });
+    serde_json::to_string_pretty(&job).unwrap_or_default()
+}
+
+/// File-system friendly version of a board name
+fn file_stem(name: &str) -> String {
+    let stem: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if stem.is_empty() {
+        "board".to_string()
+    } else {
+        stem
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Minimal ZIP writer (stored entries, no compression)
+// ─────────────────────────────────────────────────────────────────────────────
+
+pub(crate) fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+fn zip_store(entries: &[(&str, &[u8])]) -> Vec<u8> {
+    // Fixed 1980-01-01 00:00 timestamp keeps archives reproducible
+    const DOS_TIME: u16 = 0;
+    const DOS_DATE: u16 = (1 << 5) | 1;
+
+    let mut out = Vec::new();
+    let mut central = Vec::new();
+    for (name, data) in entries {
+        let offset = out.len() as u32;
+        let crc = crc32(data);
+        let size = data.len() as u32;
+
+        let mut header = Vec::new();
+        header.extend_from_slice(&10u16.to_le_bytes()); // version needed
+        header.extend_from_slice(&0u16.to_le_bytes()); // flags
+        header.extend_from_slice(&0u16.to_le_bytes()); // method: stored
+        header.extend_from_slice(&DOS_TIME.to_le_bytes());
+        header.extend_from_slice(&DOS_DATE.to_le_bytes());
+        header.extend_from_slice(&crc.to_le_bytes());
+        header.extend_from_slice(&size.to_le_bytes()); // compressed
+        header.extend_from_slice(&size.to_le_bytes()); // uncompressed
+        header.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        header.extend_from_slice(&0u16.to_le_bytes()); // extra length
+
+        out.extend_from_slice(&0x0403_4B50u32.to_le_bytes());
+        out.extend_from_slice(&header);
+        out.extend_from_slice(name.as_bytes());
+        out.extend_from_slice(data);
+
+        central.extend_from_slice(&0x0201_4B50u32.to_le_bytes());
+        central.extend_from_slice(&20u16.to_le_bytes()); // version made by
+        central.extend_from_slice(&header);
+        central.extend_from_slice(&0u16.to_le_bytes()); // comment length
+        central.extend_from_slice(&0u16.to_le_bytes()); // disk number
+        central.extend_from_slice(&0u16.to_le_bytes()); // internal attributes
+        central.extend_from_slice(&0u32.to_le_bytes()); // external attributes
+        central.extend_from_slice(&offset.to_le_bytes());
+        central.extend_from_slice(name.as_bytes());
+    }
+
+    let central_offset = out.len() as u32;
+    let count = entries.len() as u16;
+    out.extend_from_slice(&central);
+    out.extend_from_slice(&0x0605_4B50u32.to_le_bytes());
+    out.extend_from_slice(&0u16.to_le_bytes()); // this disk
+    out.extend_from_slice(&0u16.to_le_bytes()); // central directory disk
+    out.extend_from_slice(&count.to_le_bytes());
+    out.extend_from_slice(&count.to_le_bytes());
+    out.extend_from_slice(&(central.len() as u32).to_le_bytes());
+    out.extend_from_slice(&central_offset.to_le_bytes());
+    out.extend_from_slice(&0u16.to_le_bytes()); // comment length
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_board() -> FabBoard {
+        let mut board = FabBoard::rectangle("Loop Filter", 20.0, 10.0);
+        for (i, x) in [4.1, 5.9].into_iter().enumerate() {
+            board.pads.push(FabPad {
+                component: "R1".into(),
+                pin: (i + 1).to_string(),
+                net: Some(if i == 0 { "CP_OUT" } else { "GND" }.into()),
+                position: (x, 5.0),
+                shape: PadShape::RoundRect {
+                    width: 1.0,
+                    height: 1.3,
+                    radius: 0.25,
+                },
+                side: BoardSide::Top,
+                drill: None,
+            });
+        }
+        board.pads.push(FabPad {
+            component: "J1".into(),
+            pin: "1".into(),
+            net: Some("CP_OUT".into()),
+            position: (1.5, 5.0),
+            shape: PadShape::Circle { diameter: 1.7 },
+            side: BoardSide::Top,
+            drill: Some(1.0),
+        });
+        board.tracks.push(FabTrack {
+            side: BoardSide::Top,
+            width: 0.25,
+            net: Some("CP_OUT".into()),
+            segment: TrackSegment::Line {
+                start: (1.5, 5.0),
+                end: (4.1, 5.0),
+            },
+        });
+        board.tracks.push(FabTrack {
+            side: BoardSide::Bottom,
+            width: 0.3,
+            net: Some("GND".into()),
+            segment: TrackSegment::Arc {
+                start: (10.0, 2.0),
+                end: (12.0, 2.0),
+                center: (11.0, 2.0),
+                clockwise: true,
+            },
+        });
+        board.vias.push(FabVia {
+            position: (10.0, 2.0),
+            diameter: 0.6,
+            drill: 0.3,
+            net: Some("GND".into()),
+        });
+        board.holes.push(FabHole {
+            position: (18.0, 8.0),
+            diameter: 3.2,
+        });
+        board
+    }
+
+    fn text(package: &FabricationPackage, function: &str) -> String {
+        String::from_utf8(package.file_by_function(function).unwrap().contents.clone()).unwrap()
+    }
+
+    #[test]
+    fn test_package_layers_and_attributes() {
+        let package = generate_fabrication_package(&sample_board(), &FabOptions::default());
+        assert_eq!(package.files.len(), 12);
+        assert!(package.file("Loop_Filter-F_Cu.gbr").is_some());
+        assert!(package.file("Loop_Filter-job.gbrjob").is_some());
+
+        let top = text(&package, "Copper,L1,Top");
+        assert!(top.contains("%TF.FileFunction,Copper,L1,Top*%"));
+        assert!(top.contains("%TF.GenerationSoftware,too.foo,S3M2P PCB,1.0*%"));
+        assert!(top.contains("%AMRoundRect*"));
+        assert!(top.contains("%TA.AperFunction,SMDPad,CuDef*%"));
+        assert!(top.contains("%TA.AperFunction,ComponentPad*%"));
+        assert!(top.contains("%TO.N,CP_OUT*%\n%TO.P,R1,1*%\n%TO.C,R1*%\n"));
+        assert!(top.contains("%TA.AperFunction,ViaPad*%"));
+
+        // Through-hole pad and via on the bottom; arcs use G02
+        let bottom = text(&package, "Copper,L2,Bot");
+        assert!(bottom.contains("%TO.P,J1,1*%"));
+        assert!(!bottom.contains("%TO.P,R1"));
+        assert!(bottom.contains("G02*\nX12000000Y2000000I1000000J0D01*"));
+
+        // Mask openings grow by 0.05 mm; vias are tented
+        let mask = text(&package, "Soldermask,Top");
+        assert!(mask.contains("%TF.FilePolarity,Negative*%"));
+        assert!(mask.contains("RoundRect,1.100000X1.400000X0.300000"));
+        assert!(!mask.contains("ViaPad"));
+
+        // Paste only on SMD pads
+        let paste = text(&package, "Paste,Top");
+        assert_eq!(paste.matches("D03*").count(), 2);
+        assert_eq!(text(&package, "Paste,Bot").matches("D03*").count(), 0);
+
+        let outline = text(&package, "Profile,NP");
+        assert!(outline.contains("%TA.AperFunction,Profile*%"));
+        assert!(outline.contains("X20000000Y10000000D01*"));
+    }
+
+    #[test]
+    fn test_drill_and_job_files() {
+        let package = generate_fabrication_package(&sample_board(), &FabOptions::default());
+        let pth = text(&package, "Plated,1,2,PTH");
+        assert!(pth.contains("; #@! TA.AperFunction,Plated,PTH,ViaDrill\nT1C0.3"));
+        assert!(pth.contains("; #@! TA.AperFunction,Plated,PTH,ComponentDrill\nT2C1.0"));
+        let npth = text(&package, "NonPlated,1,2,NPTH");
+        assert!(npth.contains("X18.0Y8.0"));
+
+        let job: serde_json::Value =
+            serde_json::from_slice(&package.file("Loop_Filter-job.gbrjob").unwrap().contents)
+                .unwrap();
+        assert_eq!(job["GeneralSpecs"]["Size"]["X"], 20.0);
+        assert_eq!(job["GeneralSpecs"]["LayerNumber"], 2);
+        assert_eq!(job["DesignRules"][0]["MinLineWidth"], 0.25);
+        assert_eq!(job["FilesAttributes"].as_array().unwrap().len(), 9);
+        assert!(job["Header"].get("CreationDate").is_none());
+    }
+
+    #[test]
+    fn test_zip_archive() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+
+        let package = generate_fabrication_package(&sample_board(), &FabOptions::default());
+        let zip = package.to_zip();
+        assert_eq!(&zip[..4], b"PK\x03\x04");
+        // End-of-central-directory record lists every file
+        let eocd = zip.len() - 22;
+        assert_eq!(&zip[eocd..eocd + 4], b"PK\x05\x06");
+        let count = u16::from_le_bytes([zip[eocd + 10], zip[eocd + 11]]);
+        assert_eq!(count as usize, package.files.len());
+        // First entry is stored verbatim after its header
+        let first = &package.files[0];
+        let name_len = first.name.len();
+        assert_eq!(&zip[30..30 + name_len], first.name.as_bytes());
+        assert_eq!(
+            &zip[30 + name_len..30 + name_len + first.contents.len()],
+            first.contents.as_slice()
+        );
+    }
+}
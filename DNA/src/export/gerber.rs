@@ -1,14 +1,15 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: gerber.rs | DNA/src/export/gerber.rs
 //! PURPOSE: Defines GerberDocument, GerberCommand, ApertureDef types
-//! MODIFIED: 2025-12-09
+//! MODIFIED: 2026-10-18
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
 
 //! Gerber X2 Generator - From Scratch
 //!
 //! Generates Gerber RS-274X / X2 files without external dependencies.
-//! Used for PCB fabrication of loop filter circuits.
+//! Used for PCB fabrication of loop filter circuits; multi-layer fabrication
+//! packages are assembled in `fabrication.rs`.
 //!
 //! Gerber X2 Reference: UCAMCO specification rev. 2023.08
 //!
@@ -17,31 +18,43 @@
 //! %TF.FileFunction,Copper,L1,Top*%
 //! %FSLAX36Y36*%
 //! %MOIN*%
+//! %TA.AperFunction,SMDPad,CuDef*%
+//! %ADD10C,0.500000*%
+//! %TD*%
+//! G75*
+//! G01*
 //! D10*
+//! %TO.N,GND*%
 //! X0Y0D03*
 //! X1000000Y0D01*
 //! M02*
 //! ```
 
 /// Gerber document builder
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct GerberDocument {
     /// File function (e.g., "Copper,L1,Top")
-    file_function: String,
+    pub(crate) file_function: String,
     /// Commands in the document
-    commands: Vec<GerberCommand>,
+    pub(crate) commands: Vec<GerberCommand>,
     /// Aperture definitions
-    apertures: Vec<ApertureDef>,
+    pub(crate) apertures: Vec<ApertureDef>,
     /// Current aperture number
-    current_aperture: u32,
+    pub(crate) current_aperture: u32,
     /// Unit (inches or mm)
-    unit: GerberUnit,
+    pub(crate) unit: GerberUnit,
     /// Format: integer digits, decimal digits
-    format: (u8, u8),
+    pub(crate) format: (u8, u8),
+    /// Extra file attributes (%TF), e.g. (".FilePolarity", "Positive")
+    pub(crate) file_attributes: Vec<(String, String)>,
+    /// Value of %TF.GenerationSoftware
+    pub(crate) generation_software: String,
+    /// Current point in Gerber units (needed for arc centre offsets)
+    pub(crate) position: (i64, i64),
 }
 
 /// Gerber command types
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum GerberCommand {
     /// Move to position (D02)
     Move { x: i64, y: i64 },
@@ -49,6 +62,19 @@ pub enum GerberCommand {
     Line { x: i64, y: i64 },
     /// Flash aperture at position (D03)
     Flash { x: i64, y: i64 },
+    /// Circular arc to position (G02/G03 + D01); `i`/`j` are the centre
+    /// offset from the start point
+    Arc {
+        x: i64,
+        y: i64,
+        i: i64,
+        j: i64,
+        clockwise: bool,
+    },
+    /// Object attribute (%TO), e.g. name ".N", value "GND"
+    ObjectAttribute { name: String, value: String },
+    /// Delete all object attributes (%TD*%)
+    DeleteAttributes,
     /// Select aperture
     SelectAperture(u32),
     /// Region start
@@ -58,16 +84,32 @@ pub enum GerberCommand {
 }
 
 /// Aperture definition
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ApertureDef {
     /// Aperture number (D10+)
-    number: u32,
+    pub(crate) number: u32,
     /// Aperture type
-    aperture_type: ApertureType,
+    pub(crate) aperture_type: ApertureType,
+    /// X2 .AperFunction (e.g. "SMDPad,CuDef", "Conductor")
+    pub(crate) function: Option<String>,
+}
+
+impl ApertureDef {
+    pub fn number(&self) -> u32 {
+        self.number
+    }
+
+    pub fn aperture_type(&self) -> &ApertureType {
+        &self.aperture_type
+    }
+
+    pub fn function(&self) -> Option<&str> {
+        self.function.as_deref()
+    }
 }
 
 /// Aperture types
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum ApertureType {
     /// Circle with diameter
     Circle { diameter: f64 },
@@ -75,10 +117,26 @@ pub enum ApertureType {
     Rectangle { width: f64, height: f64 },
     /// Obround (pill shape)
     Obround { width: f64, height: f64 },
+    /// Rectangle with rounded corners (RoundRect aperture macro)
+    RoundRect {
+        width: f64,
+        height: f64,
+        radius: f64,
+    },
 }
 
+/// Aperture macro for rounded rectangles: $1 width, $2 height, $3 corner radius
+pub(crate) const ROUND_RECT_MACRO: &str = "%AMRoundRect*
+0 Rectangle with rounded corners*
+21,1,$1,$2-$3-$3,0,0,0*
+21,1,$1-$3-$3,$2,0,0,0*
+1,1,$3+$3,$1/2-$3,$2/2-$3*
+1,1,$3+$3,-$1/2+$3,$2/2-$3*
+1,1,$3+$3,-$1/2+$3,-$2/2+$3*
+1,1,$3+$3,$1/2-$3,-$2/2+$3*%";
+
 /// Unit system
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GerberUnit {
     Inches,
     Millimeters,
@@ -100,9 +158,53 @@ impl GerberDocument {
             current_aperture: 10,
             unit: GerberUnit::Millimeters,
             format: (3, 6), // 3 integer, 6 decimal digits
+            file_attributes: Vec::new(),
+            generation_software: "too.foo,PLL Designer,1.0".to_string(),
+            position: (0, 0),
         }
     }
 
+    /// Set %TF.GenerationSoftware (vendor, application, version)
+    pub fn set_generation_software(&mut self, vendor: &str, application: &str, version: &str) {
+        self.generation_software = format!(
+            "{},{},{}",
+            attribute_field(vendor),
+            attribute_field(application),
+            attribute_field(version)
+        );
+    }
+
+    /// Add a file attribute, e.g. `add_file_attribute(".FilePolarity", "Positive")`
+    pub fn add_file_attribute(&mut self, name: &str, value: &str) {
+        self.file_attributes
+            .push((name.to_string(), value.to_string()));
+    }
+
+    pub fn file_function(&self) -> &str {
+        &self.file_function
+    }
+
+    pub fn file_attributes(&self) -> &[(String, String)] {
+        &self.file_attributes
+    }
+
+    pub fn unit(&self) -> GerberUnit {
+        self.unit
+    }
+
+    pub fn commands(&self) -> &[GerberCommand] {
+        &self.commands
+    }
+
+    pub fn apertures(&self) -> &[ApertureDef] {
+        &self.apertures
+    }
+
+    /// Aperture definition for a D-code
+    pub fn aperture_def(&self, number: u32) -> Option<&ApertureDef> {
+        self.apertures.iter().find(|a| a.number == number)
+    }
+
     /// Set unit to millimeters
     pub fn set_unit_mm(&mut self) {
         self.unit = GerberUnit::Millimeters;
@@ -115,21 +217,50 @@ impl GerberDocument {
 
     /// Add a circular aperture
     pub fn add_circle_aperture(&mut self, diameter: f64) -> u32 {
-        let number = self.current_aperture;
-        self.apertures.push(ApertureDef {
-            number,
-            aperture_type: ApertureType::Circle { diameter },
-        });
-        self.current_aperture += 1;
-        number
+        self.push_aperture(ApertureType::Circle { diameter }, None)
     }
 
     /// Add a rectangular aperture
     pub fn add_rect_aperture(&mut self, width: f64, height: f64) -> u32 {
+        self.push_aperture(ApertureType::Rectangle { width, height }, None)
+    }
+
+    /// Add an obround aperture
+    pub fn add_obround_aperture(&mut self, width: f64, height: f64) -> u32 {
+        self.push_aperture(ApertureType::Obround { width, height }, None)
+    }
+
+    /// Add a rounded-rectangle aperture (radius is clamped to half the short side)
+    pub fn add_round_rect_aperture(&mut self, width: f64, height: f64, radius: f64) -> u32 {
+        let radius = radius.clamp(0.0, width.min(height) / 2.0);
+        self.push_aperture(
+            ApertureType::RoundRect {
+                width,
+                height,
+                radius,
+            },
+            None,
+        )
+    }
+
+    /// Aperture with an X2 .AperFunction, reusing an identical existing definition
+    pub fn aperture(&mut self, aperture_type: ApertureType, function: Option<&str>) -> u32 {
+        let existing = self
+            .apertures
+            .iter()
+            .find(|a| a.aperture_type == aperture_type && a.function.as_deref() == function);
+        match existing {
+            Some(a) => a.number,
+            None => self.push_aperture(aperture_type, function.map(str::to_string)),
+        }
+    }
+
+    fn push_aperture(&mut self, aperture_type: ApertureType, function: Option<String>) -> u32 {
         let number = self.current_aperture;
         self.apertures.push(ApertureDef {
             number,
-            aperture_type: ApertureType::Rectangle { width, height },
+            aperture_type,
+            function,
         });
         self.current_aperture += 1;
         number
@@ -141,35 +272,60 @@ impl GerberDocument {
     }
 
     /// Convert coordinate to Gerber integer format
-    fn coord_to_gerber(&self, coord: f64) -> i64 {
+    pub(crate) fn coord_to_gerber(&self, coord: f64) -> i64 {
         // Format is X.XXXXXX (6 decimal places)
         (coord * 1_000_000.0).round() as i64
     }
 
     /// Move to position (without drawing)
     pub fn move_to(&mut self, x: f64, y: f64) {
-        self.commands.push(GerberCommand::Move {
-            x: self.coord_to_gerber(x),
-            y: self.coord_to_gerber(y),
-        });
+        let (x, y) = (self.coord_to_gerber(x), self.coord_to_gerber(y));
+        self.position = (x, y);
+        self.commands.push(GerberCommand::Move { x, y });
     }
 
     /// Draw line to position
     pub fn line_to(&mut self, x: f64, y: f64) {
-        self.commands.push(GerberCommand::Line {
-            x: self.coord_to_gerber(x),
-            y: self.coord_to_gerber(y),
+        let (x, y) = (self.coord_to_gerber(x), self.coord_to_gerber(y));
+        self.position = (x, y);
+        self.commands.push(GerberCommand::Line { x, y });
+    }
+
+    /// Draw a circular arc from the current point to (x, y) around (cx, cy)
+    pub fn arc_to(&mut self, x: f64, y: f64, cx: f64, cy: f64, clockwise: bool) {
+        let (x, y) = (self.coord_to_gerber(x), self.coord_to_gerber(y));
+        let (cx, cy) = (self.coord_to_gerber(cx), self.coord_to_gerber(cy));
+        let (i, j) = (cx - self.position.0, cy - self.position.1);
+        self.position = (x, y);
+        self.commands.push(GerberCommand::Arc {
+            x,
+            y,
+            i,
+            j,
+            clockwise,
         });
     }
 
     /// Flash aperture at position
     pub fn flash(&mut self, x: f64, y: f64) {
-        self.commands.push(GerberCommand::Flash {
-            x: self.coord_to_gerber(x),
-            y: self.coord_to_gerber(y),
+        let (x, y) = (self.coord_to_gerber(x), self.coord_to_gerber(y));
+        self.position = (x, y);
+        self.commands.push(GerberCommand::Flash { x, y });
+    }
+
+    /// Attach an object attribute (e.g. ".N" net name) to subsequent objects
+    pub fn set_object_attribute(&mut self, name: &str, value: &str) {
+        self.commands.push(GerberCommand::ObjectAttribute {
+            name: name.to_string(),
+            value: value.to_string(),
         });
     }
 
+    /// Clear all object attributes
+    pub fn delete_object_attributes(&mut self) {
+        self.commands.push(GerberCommand::DeleteAttributes);
+    }
+
     /// Start a region (filled polygon)
     pub fn region_start(&mut self) {
         self.commands.push(GerberCommand::RegionStart);
@@ -204,8 +360,11 @@ impl GerberDocument {
 impl std::fmt::Display for GerberDocument {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         // X2 attributes
-        writeln!(f, "%TF.GenerationSoftware,too.foo,PLL Designer,1.0*%")?;
+        writeln!(f, "%TF.GenerationSoftware,{}*%", self.generation_software)?;
         writeln!(f, "%TF.FileFunction,{}*%", self.file_function)?;
+        for (name, value) in &self.file_attributes {
+            writeln!(f, "%TF{},{}*%", name, value)?;
+        }
 
         // Format specification
         let (int_digits, dec_digits) = self.format;
@@ -221,22 +380,49 @@ impl std::fmt::Display for GerberDocument {
             GerberUnit::Millimeters => writeln!(f, "%MOMM*%")?,
         };
 
-        // Aperture definitions
+        // Aperture macros
+        if self
+            .apertures
+            .iter()
+            .any(|a| matches!(a.aperture_type, ApertureType::RoundRect { .. }))
+        {
+            writeln!(f, "{}", ROUND_RECT_MACRO)?;
+        }
+
+        // Aperture definitions (with .AperFunction attributes)
+        let mut function: Option<&str> = None;
         for aperture in &self.apertures {
+            if aperture.function.as_deref() != function {
+                match &aperture.function {
+                    Some(func) => writeln!(f, "%TA.AperFunction,{}*%", func)?,
+                    None => writeln!(f, "%TD.AperFunction*%")?,
+                }
+                function = aperture.function.as_deref();
+            }
             let def = match &aperture.aperture_type {
                 ApertureType::Circle { diameter } => format!("C,{:.6}", diameter),
                 ApertureType::Rectangle { width, height } => {
                     format!("R,{:.6}X{:.6}", width, height)
                 }
                 ApertureType::Obround { width, height } => format!("O,{:.6}X{:.6}", width, height),
+                ApertureType::RoundRect {
+                    width,
+                    height,
+                    radius,
+                } => format!("RoundRect,{:.6}X{:.6}X{:.6}", width, height, radius),
             };
             writeln!(f, "%ADD{:02}{}*%", aperture.number, def)?;
         }
+        if function.is_some() {
+            writeln!(f, "%TD*%")?;
+        }
 
-        // Set linear interpolation mode
+        // Multi-quadrant arcs, then linear interpolation mode
+        writeln!(f, "G75*")?;
         writeln!(f, "G01*")?;
 
         // Commands
+        let mut clockwise_mode: Option<bool> = None;
         for cmd in &self.commands {
             match cmd {
                 GerberCommand::SelectAperture(n) => {
@@ -246,8 +432,24 @@ impl std::fmt::Display for GerberDocument {
                     writeln!(f, "X{}Y{}D02*", x, y)?;
                 }
                 GerberCommand::Line { x, y } => {
+                    if clockwise_mode.take().is_some() {
+                        writeln!(f, "G01*")?;
+                    }
                     writeln!(f, "X{}Y{}D01*", x, y)?;
                 }
+                GerberCommand::Arc {
+                    x,
+                    y,
+                    i,
+                    j,
+                    clockwise,
+                } => {
+                    if clockwise_mode != Some(*clockwise) {
+                        writeln!(f, "{}*", if *clockwise { "G02" } else { "G03" })?;
+                        clockwise_mode = Some(*clockwise);
+                    }
+                    writeln!(f, "X{}Y{}I{}J{}D01*", x, y, i, j)?;
+                }
                 GerberCommand::Flash { x, y } => {
                     writeln!(f, "X{}Y{}D03*", x, y)?;
                 }
@@ -257,6 +459,12 @@ impl std::fmt::Display for GerberDocument {
                 GerberCommand::RegionEnd => {
                     writeln!(f, "G37*")?;
                 }
+                GerberCommand::ObjectAttribute { name, value } => {
+                    writeln!(f, "%TO{},{}*%", name, value)?;
+                }
+                GerberCommand::DeleteAttributes => {
+                    writeln!(f, "%TD*%")?;
+                }
             }
         }
 
@@ -267,6 +475,18 @@ impl std::fmt::Display for GerberDocument {
     }
 }
 
+/// Strip characters that are reserved inside attribute fields
+pub fn attribute_field(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| match c {
+            '*' | '%' | ',' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect()
+}
+
 impl GerberDocument {
     /// Generate the Gerber file content as bytes
     pub fn to_bytes(&self) -> Vec<u8> {
@@ -278,88 +498,35 @@ impl GerberDocument {
 // PLL Loop Filter Gerber Generation
 // ============================================================================
 
+use crate::export::fabrication::{copper_gerber, BoardSide};
+use crate::pcb::{layout_netlist, NetlistOptions, PcbOptions};
+use crate::pll::circuit::build_pll_netlist;
 use crate::pll::PLLDesign;
 
-/// Generate a Gerber file for a PLL loop filter footprint
+/// Loop filter elements of `build_pll_netlist` that go on the board
+const LOOP_FILTER_PARTS: [&str; 3] = ["C1", "R1", "C2"];
+
+/// Generate the top copper Gerber for a PLL loop filter board
 ///
-/// Creates a simple 2-layer PCB footprint with:
-/// - SMD pads for C1, R1, C2 (0805 footprint)
-/// - Traces connecting them
-/// - Via to ground plane
+/// The board comes from the design's own netlist: C1, R1 and C2 as 0805
+/// chips, test points for the charge pump output, VCO tuning input and
+/// ground, placed and routed by `crate::pcb`. Use `crate::pcb` together with
+/// `generate_fabrication_package` for the full set of layers.
 pub fn generate_loop_filter_gerber(design: &PLLDesign) -> GerberDocument {
-    let mut gerber = GerberDocument::new("Copper,L1,Top");
-    gerber.set_unit_mm();
-
-    // Define apertures
-    let trace_ap = gerber.add_circle_aperture(0.3); // 0.3mm trace
-    let pad_ap = gerber.add_rect_aperture(1.2, 1.4); // 0805 pad
-    let via_ap = gerber.add_circle_aperture(0.6); // Via pad
-
-    // Component positions (mm)
-    // Layout: [CP_OUT]--[C1]--+--[R1]--[C2]--[VCO_IN]
-    //                        |
-    //                       GND
-
-    let y_center = 5.0;
-    let cp_out_x = 2.0;
-    let c1_x = 5.0;
-    let r1_x = 10.0;
-    let c2_x = 15.0;
-    let vco_in_x = 18.0;
-    let gnd_y = 2.0;
-
-    // Draw C1 pads
-    gerber.select_aperture(pad_ap);
-    gerber.flash(c1_x - 0.9, y_center); // Left pad
-    gerber.flash(c1_x + 0.9, y_center); // Right pad
-
-    // Draw R1 pads
-    gerber.flash(r1_x - 0.9, y_center);
-    gerber.flash(r1_x + 0.9, y_center);
-
-    // Draw C2 pads
-    gerber.flash(c2_x - 0.9, y_center);
-    gerber.flash(c2_x + 0.9, y_center);
-
-    // Draw traces
-    gerber.select_aperture(trace_ap);
-
-    // CP_OUT to C1 left pad
-    gerber.move_to(cp_out_x, y_center);
-    gerber.line_to(c1_x - 0.9, y_center);
-
-    // C1 right pad to junction point
-    let junction_x = c1_x + 0.9 + 1.0;
-    gerber.move_to(c1_x + 0.9, y_center);
-    gerber.line_to(junction_x, y_center);
-
-    // Junction to R1 left pad
-    gerber.line_to(r1_x - 0.9, y_center);
-
-    // R1 right pad to C2 left pad
-    gerber.move_to(r1_x + 0.9, y_center);
-    gerber.line_to(c2_x - 0.9, y_center);
-
-    // C2 right pad to VCO_IN
-    gerber.move_to(c2_x + 0.9, y_center);
-    gerber.line_to(vco_in_x, y_center);
-
-    // Junction to ground via
-    gerber.move_to(junction_x, y_center);
-    gerber.line_to(junction_x, gnd_y);
-
-    // Ground via
-    gerber.select_aperture(via_ap);
-    gerber.flash(junction_x, gnd_y);
-
-    // Add component designators as comment attributes
-    // (In real Gerber X2, we'd add %TO.C attributes)
-
-    // Add component values as X2 attributes would go here
-    let _c1_val = design.loop_filter.c1_pf;
-    let _r1_val = design.loop_filter.r1_ohms;
-    let _c2_val = design.loop_filter.c2_pf;
-
+    let netlist = build_pll_netlist(design);
+    let options = PcbOptions {
+        netlist: NetlistOptions {
+            package: "0805".to_string(),
+            parts: Some(LOOP_FILTER_PARTS.iter().map(|p| p.to_string()).collect()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let (layout, _) = layout_netlist("PLL Loop Filter", &netlist, &options)
+        .expect("PLL netlist always contains the loop filter parts");
+
+    let mut gerber = copper_gerber(&layout.to_fab_board(), BoardSide::Top);
+    gerber.set_generation_software("too.foo", "PLL Designer", "1.0");
     gerber
 }
 
@@ -413,6 +580,54 @@ mod tests {
         assert!(output.contains("D03*")); // Flash commands
         assert!(output.contains("D01*")); // Line commands
         assert!(output.contains("M02*")); // End of file
+
+        // Pads come from the netlist's loop filter parts and test points
+        for reference in ["C1", "R1", "C2", "TP1", "TP2", "TP3"] {
+            assert!(output.contains(&format!("%TO.C,{}*%", reference)));
+        }
+        assert!(output.contains("%TO.N,filter_in*%"));
+        assert!(output.contains("%TO.N,filter_out*%"));
+        assert!(output.contains("%TO.N,GND*%"));
+    }
+
+    #[test]
+    fn test_x2_attributes_macros_and_arcs() {
+        let mut gerber = GerberDocument::new("Copper,L1,Top");
+        gerber.add_file_attribute(".FilePolarity", "Positive");
+        let pad = gerber.aperture(
+            ApertureType::RoundRect {
+                width: 1.0,
+                height: 1.2,
+                radius: 0.25,
+            },
+            Some("SMDPad,CuDef"),
+        );
+        let track = gerber.aperture(ApertureType::Circle { diameter: 0.25 }, Some("Conductor"));
+        // Identical definitions are shared
+        assert_eq!(
+            gerber.aperture(ApertureType::Circle { diameter: 0.25 }, Some("Conductor")),
+            track
+        );
+
+        gerber.select_aperture(pad);
+        gerber.set_object_attribute(".N", "GND");
+        gerber.flash(1.0, 1.0);
+        gerber.delete_object_attributes();
+        gerber.select_aperture(track);
+        gerber.move_to(0.0, 0.0);
+        gerber.arc_to(2.0, 0.0, 1.0, 0.0, true);
+        gerber.line_to(3.0, 0.0);
+
+        let output = gerber.to_string();
+        assert!(output.contains("%TF.FilePolarity,Positive*%"));
+        assert!(output.contains("%AMRoundRect*"));
+        assert!(output.contains(
+            "%TA.AperFunction,SMDPad,CuDef*%\n%ADD10RoundRect,1.000000X1.200000X0.250000*%"
+        ));
+        assert!(output.contains("%TA.AperFunction,Conductor*%\n%ADD11C,0.250000*%\n%TD*%"));
+        assert!(output.contains("%TO.N,GND*%\nX1000000Y1000000D03*\n%TD*%"));
+        assert!(output.contains("G02*\nX2000000Y0I1000000J0D01*\nG01*\nX3000000Y0D01*"));
+        assert_eq!(attribute_field("A,B*C"), "A_B_C");
     }
 
     #[test]
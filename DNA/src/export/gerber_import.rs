@@ -0,0 +1,841 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: gerber_import.rs | DNA/src/export/gerber_import.rs
+//! PURPOSE: Gerber X2 and Excellon readers rebuilding GerberDocument / ExcellonDocument
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+//!
+//! Reads back the subset of RS-274X / X2 and Excellon that fab tools emit for
+//! two-layer boards, so exported packages can be regression-tested and third
+//! party files sanity-checked:
+//!
+//! - Gerber: FS (leading-zero omission, absolute), MO, AD (C/R/O and the
+//!   RoundRect macro written by `gerber.rs`), TF/TA/TO/TD attributes,
+//!   G01/G02/G03 with G75, regions (G36/G37), D01/D02/D03, modal coordinates.
+//! - Excellon: METRIC/INCH with LZ/TZ or decimal coordinates, tool tables,
+//!   X2 comment attributes, drill hits and G85 slots.
+//!
+//! Coordinates are rescaled to the writer's 6-decimal fixed point, so a file
+//! produced by `GerberDocument` parses back into an identical document.
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use super::excellon::{DrillHit, DrillTool, ExcellonDocument};
+use super::gerber::{ApertureDef, ApertureType, GerberCommand, GerberDocument, GerberUnit};
+
+/// Error type for Gerber / Excellon parsing
+#[derive(Debug, Clone, PartialEq)]
+pub enum GerberParseError {
+    /// Malformed input at a (1-based) line
+    Syntax { line: usize, message: String },
+    /// Valid but unsupported construct at a (1-based) line
+    Unsupported { line: usize, feature: String },
+}
+
+impl std::fmt::Display for GerberParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GerberParseError::Syntax { line, message } => {
+                write!(f, "syntax error on line {}: {}", line, message)
+            }
+            GerberParseError::Unsupported { line, feature } => {
+                write!(f, "unsupported on line {}: {}", line, feature)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GerberParseError {}
+
+fn syntax<T>(line: usize, message: &str) -> Result<T, GerberParseError> {
+    Err(GerberParseError::Syntax {
+        line,
+        message: message.to_string(),
+    })
+}
+
+fn unsupported<T>(line: usize, feature: &str) -> Result<T, GerberParseError> {
+    Err(GerberParseError::Unsupported {
+        line,
+        feature: feature.to_string(),
+    })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Gerber
+// ─────────────────────────────────────────────────────────────────────────────
+
+enum Block {
+    /// %...% parameter block, split into its `*`-terminated words
+    Extended(Vec<String>),
+    /// Ordinary `*`-terminated word
+    Word(String),
+}
+
+fn tokenize(text: &str) -> Result<Vec<(usize, Block)>, GerberParseError> {
+    let mut blocks = Vec::new();
+    let mut line = 1;
+    let mut chars = text.chars().peekable();
+    let mut word = String::new();
+    let mut word_line = 1;
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => line += 1,
+            '\r' | ' ' | '\t' => {}
+            '%' => {
+                if !word.trim().is_empty() {
+                    return syntax(line, "unterminated word before '%'");
+                }
+                let start = line;
+                let mut words = Vec::new();
+                let mut current = String::new();
+                loop {
+                    match chars.next() {
+                        None => return syntax(start, "unterminated '%' block"),
+                        Some('%') => break,
+                        Some('*') => words.push(std::mem::take(&mut current)),
+                        Some('\n') => line += 1,
+                        Some('\r') => {}
+                        Some(ch) => current.push(ch),
+                    }
+                }
+                if !current.trim().is_empty() {
+                    return syntax(start, "'%' block without closing '*'");
+                }
+                blocks.push((start, Block::Extended(words)));
+            }
+            '*' => {
+                blocks.push((word_line, Block::Word(std::mem::take(&mut word))));
+            }
+            _ => {
+                if word.is_empty() {
+                    word_line = line;
+                }
+                word.push(c);
+            }
+        }
+    }
+    if !word.trim().is_empty() {
+        return syntax(line, "missing '*' at end of file");
+    }
+    Ok(blocks)
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Interpolation {
+    Linear,
+    Clockwise,
+    CounterClockwise,
+}
+
+struct GerberParser {
+    doc: GerberDocument,
+    decimals: u32,
+    format_set: bool,
+    macros: Vec<(String, Vec<String>)>,
+    pending_function: Option<String>,
+    objects_attributed: bool,
+    interpolation: Interpolation,
+    multi_quadrant: bool,
+    last_op: Option<u32>,
+    x: i64,
+    y: i64,
+    ended: bool,
+}
+
+/// Parse a Gerber (RS-274X / X2) file into a [`GerberDocument`]
+pub fn parse_gerber(text: &str) -> Result<GerberDocument, GerberParseError> {
+    let mut parser = GerberParser {
+        doc: GerberDocument::new(""),
+        decimals: 6,
+        format_set: false,
+        macros: Vec::new(),
+        pending_function: None,
+        objects_attributed: false,
+        interpolation: Interpolation::Linear,
+        multi_quadrant: false,
+        last_op: None,
+        x: 0,
+        y: 0,
+        ended: false,
+    };
+    parser.doc.generation_software.clear();
+    for (line, block) in tokenize(text)? {
+        if parser.ended {
+            break;
+        }
+        match block {
+            Block::Extended(words) => parser.extended(line, &words)?,
+            Block::Word(word) => parser.word(line, &word)?,
+        }
+    }
+    if !parser.ended {
+        return syntax(text.lines().count().max(1), "missing M02");
+    }
+    parser.doc.current_aperture = parser
+        .doc
+        .apertures
+        .iter()
+        .map(|a| a.number + 1)
+        .max()
+        .unwrap_or(10);
+    parser.doc.position = (parser.x, parser.y);
+    Ok(parser.doc)
+}
+
+impl GerberParser {
+    fn extended(&mut self, line: usize, words: &[String]) -> Result<(), GerberParseError> {
+        let Some(first) = words.first() else {
+            return Ok(());
+        };
+        let code = first.get(..2).unwrap_or("");
+        let rest = first.get(2..).unwrap_or("");
+        match code {
+            "FS" => self.format(line, rest),
+            "MO" => {
+                self.doc.unit = match rest {
+                    "MM" => GerberUnit::Millimeters,
+                    "IN" => GerberUnit::Inches,
+                    _ => return syntax(line, "unknown unit"),
+                };
+                Ok(())
+            }
+            "AM" => {
+                self.macros.push((rest.to_string(), words[1..].to_vec()));
+                Ok(())
+            }
+            "AD" => self.aperture(line, rest),
+            "TF" => {
+                let (name, value) = split_attribute(rest);
+                match name {
+                    ".FileFunction" => self.doc.file_function = value.to_string(),
+                    ".GenerationSoftware" => self.doc.generation_software = value.to_string(),
+                    _ => self
+                        .doc
+                        .file_attributes
+                        .push((name.to_string(), value.to_string())),
+                }
+                Ok(())
+            }
+            "TA" => {
+                let (name, value) = split_attribute(rest);
+                if name == ".AperFunction" {
+                    self.pending_function = Some(value.to_string());
+                }
+                Ok(())
+            }
+            "TO" => {
+                let (name, value) = split_attribute(rest);
+                self.doc.commands.push(GerberCommand::ObjectAttribute {
+                    name: name.to_string(),
+                    value: value.to_string(),
+                });
+                self.objects_attributed = true;
+                Ok(())
+            }
+            "TD" => {
+                if rest.is_empty() || rest == ".AperFunction" {
+                    self.pending_function = None;
+                }
+                if rest != ".AperFunction" && self.objects_attributed {
+                    self.doc.commands.push(GerberCommand::DeleteAttributes);
+                    self.objects_attributed = false;
+                }
+                Ok(())
+            }
+            "LP" if rest == "D" => Ok(()),
+            "LM" if rest == "N" => Ok(()),
+            "LR" if rest.parse::<f64>() == Ok(0.0) => Ok(()),
+            "LS" if rest.parse::<f64>() == Ok(1.0) => Ok(()),
+            "IP" if rest == "POS" => Ok(()),
+            "IN" | "LN" => Ok(()),
+            _ => unsupported(line, &format!("%{}*%", first)),
+        }
+    }
+
+    fn format(&mut self, line: usize, spec: &str) -> Result<(), GerberParseError> {
+        let bytes = spec.as_bytes();
+        if bytes.len() != 8 || &spec[..3] != "LAX" || bytes[5] != b'Y' {
+            if spec.starts_with('T') || spec.get(1..2) == Some("I") {
+                return unsupported(line, &format!("format {}", spec));
+            }
+            return syntax(line, "expected FSLAXnnYnn");
+        }
+        let digit = |i: usize| (bytes[i] as char).to_digit(10);
+        match (digit(3), digit(4), digit(6), digit(7)) {
+            (Some(_), Some(xd), Some(_), Some(yd)) if xd == yd => {
+                self.decimals = xd;
+                self.format_set = true;
+                Ok(())
+            }
+            _ => syntax(line, "bad coordinate format"),
+        }
+    }
+
+    fn aperture(&mut self, line: usize, def: &str) -> Result<(), GerberParseError> {
+        let Some(def) = def.strip_prefix('D') else {
+            return syntax(line, "expected ADD");
+        };
+        let digits = def.chars().take_while(|c| c.is_ascii_digit()).count();
+        let number: u32 = def[..digits]
+            .parse()
+            .or_else(|_| syntax(line, "missing aperture number"))?;
+        let body = &def[digits..];
+        let (name, params) = body.split_once(',').unwrap_or((body, ""));
+        let values: Vec<f64> = if params.is_empty() {
+            Vec::new()
+        } else {
+            params
+                .split('X')
+                .map(|v| v.parse::<f64>())
+                .collect::<Result<_, _>>()
+                .or_else(|_| syntax(line, "bad aperture parameter"))?
+        };
+        let need = |n: usize| -> Result<(), GerberParseError> {
+            if values.len() < n {
+                syntax(line, "too few aperture parameters")
+            } else {
+                Ok(())
+            }
+        };
+        let aperture_type = match name {
+            "C" => {
+                need(1)?;
+                ApertureType::Circle {
+                    diameter: values[0],
+                }
+            }
+            "R" => {
+                need(2)?;
+                ApertureType::Rectangle {
+                    width: values[0],
+                    height: values[1],
+                }
+            }
+            "O" => {
+                need(2)?;
+                ApertureType::Obround {
+                    width: values[0],
+                    height: values[1],
+                }
+            }
+            _ => {
+                let is_round_rect = self
+                    .macros
+                    .iter()
+                    .any(|(m, body)| m == name && is_round_rect_macro(body));
+                if !is_round_rect {
+                    return unsupported(line, &format!("aperture template {}", name));
+                }
+                need(3)?;
+                ApertureType::RoundRect {
+                    width: values[0],
+                    height: values[1],
+                    radius: values[2],
+                }
+            }
+        };
+        self.doc.apertures.push(ApertureDef {
+            number,
+            aperture_type,
+            function: self.pending_function.clone(),
+        });
+        Ok(())
+    }
+
+    fn word(&mut self, line: usize, word: &str) -> Result<(), GerberParseError> {
+        let mut rest = word;
+        // Leading G codes (possibly combined with a D code, deprecated style)
+        while let Some(g) = rest.strip_prefix('G') {
+            let digits = g.chars().take_while(|c| c.is_ascii_digit()).count();
+            let code: u32 = g[..digits]
+                .parse()
+                .or_else(|_| syntax(line, "bad G code"))?;
+            rest = &g[digits..];
+            match code {
+                1 => self.interpolation = Interpolation::Linear,
+                2 => self.interpolation = Interpolation::Clockwise,
+                3 => self.interpolation = Interpolation::CounterClockwise,
+                4 => return Ok(()),
+                36 => self.doc.commands.push(GerberCommand::RegionStart),
+                37 => self.doc.commands.push(GerberCommand::RegionEnd),
+                54 | 55 | 90 => {}
+                70 => self.doc.unit = GerberUnit::Inches,
+                71 => self.doc.unit = GerberUnit::Millimeters,
+                74 => self.multi_quadrant = false,
+                75 => self.multi_quadrant = true,
+                _ => return unsupported(line, &format!("G{:02}", code)),
+            }
+        }
+        if rest.is_empty() {
+            return Ok(());
+        }
+        match rest {
+            "M02" => {
+                self.ended = true;
+                return Ok(());
+            }
+            "M00" | "M01" => return Ok(()),
+            _ => {}
+        }
+
+        let mut x = None;
+        let mut y = None;
+        let mut i = None;
+        let mut j = None;
+        let mut d = None;
+        let mut chars = rest.char_indices().peekable();
+        while let Some((start, letter)) = chars.next() {
+            let mut end = start + 1;
+            while let Some(&(k, c)) = chars.peek() {
+                if c.is_ascii_digit() || c == '-' || c == '+' {
+                    end = k + c.len_utf8();
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            let digits = &rest[start + 1..end];
+            match letter {
+                'X' => x = Some(self.coordinate(line, digits)?),
+                'Y' => y = Some(self.coordinate(line, digits)?),
+                'I' => i = Some(self.coordinate(line, digits)?),
+                'J' => j = Some(self.coordinate(line, digits)?),
+                'D' => {
+                    d = Some(
+                        digits
+                            .parse::<u32>()
+                            .or_else(|_| syntax(line, "bad D code"))?,
+                    )
+                }
+                _ => return syntax(line, &format!("unexpected '{}'", letter)),
+            }
+        }
+
+        let has_coordinates = x.is_some() || y.is_some() || i.is_some() || j.is_some();
+        match d {
+            Some(n) if n >= 10 && !has_coordinates => {
+                if self.doc.aperture_def(n).is_none() {
+                    return syntax(line, &format!("undefined aperture D{}", n));
+                }
+                self.doc.commands.push(GerberCommand::SelectAperture(n));
+                Ok(())
+            }
+            Some(n) if n >= 10 => syntax(line, "aperture selection with coordinates"),
+            Some(op) => self.operation(line, op, x, y, i, j),
+            None if has_coordinates => match self.last_op {
+                Some(op) => self.operation(line, op, x, y, i, j),
+                None => syntax(line, "coordinates without D code"),
+            },
+            None => syntax(line, &format!("unknown word '{}'", word)),
+        }
+    }
+
+    fn operation(
+        &mut self,
+        line: usize,
+        op: u32,
+        x: Option<i64>,
+        y: Option<i64>,
+        i: Option<i64>,
+        j: Option<i64>,
+    ) -> Result<(), GerberParseError> {
+        let (x, y) = (x.unwrap_or(self.x), y.unwrap_or(self.y));
+        let command = match op {
+            1 => match self.interpolation {
+                Interpolation::Linear => GerberCommand::Line { x, y },
+                arc => {
+                    if !self.multi_quadrant {
+                        return unsupported(line, "single-quadrant arcs (G74)");
+                    }
+                    GerberCommand::Arc {
+                        x,
+                        y,
+                        i: i.unwrap_or(0),
+                        j: j.unwrap_or(0),
+                        clockwise: arc == Interpolation::Clockwise,
+                    }
+                }
+            },
+            2 => GerberCommand::Move { x, y },
+            3 => GerberCommand::Flash { x, y },
+            _ => return syntax(line, &format!("bad operation D{:02}", op)),
+        };
+        self.doc.commands.push(command);
+        self.last_op = Some(op);
+        self.x = x;
+        self.y = y;
+        Ok(())
+    }
+
+    /// Integer coordinate in the file's format → 6-decimal fixed point
+    fn coordinate(&self, line: usize, digits: &str) -> Result<i64, GerberParseError> {
+        if !self.format_set {
+            return syntax(line, "coordinate before %FS");
+        }
+        let value: i64 = digits.parse().or_else(|_| syntax(line, "bad coordinate"))?;
+        Ok(rescale(value, self.decimals, 6))
+    }
+}
+
+fn rescale(value: i64, from: u32, to: u32) -> i64 {
+    if from <= to {
+        value * 10i64.pow(to - from)
+    } else {
+        let div = 10i64.pow(from - to);
+        (value as f64 / div as f64).round() as i64
+    }
+}
+
+fn split_attribute(text: &str) -> (&str, &str) {
+    text.split_once(',').unwrap_or((text, ""))
+}
+
+fn is_round_rect_macro(body: &[String]) -> bool {
+    let ours: Vec<&str> = super::gerber::ROUND_RECT_MACRO
+        .trim_start_matches('%')
+        .trim_end_matches('%')
+        .split('*')
+        .skip(1)
+        .map(str::trim)
+        .filter(|w| !w.is_empty() && !w.starts_with('0'))
+        .collect();
+    let theirs: Vec<&str> = body
+        .iter()
+        .map(|w| w.trim())
+        .filter(|w| !w.is_empty() && !w.starts_with('0'))
+        .collect();
+    ours == theirs
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Excellon
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Parse an Excellon NC drill file into an [`ExcellonDocument`]
+pub fn parse_excellon(text: &str) -> Result<ExcellonDocument, GerberParseError> {
+    let mut doc = ExcellonDocument::new("");
+    doc.generation_software.clear();
+    let mut pending_function: Option<String> = None;
+    let mut leading_zeros = true;
+    let mut digits = (3u32, 3u32);
+    let mut tool: Option<u32> = None;
+    let mut position = (0.0, 0.0);
+
+    for (index, raw) in text.lines().enumerate() {
+        let line = index + 1;
+        let l = raw.trim();
+        if l.is_empty() {
+            continue;
+        }
+        if let Some(comment) = l.strip_prefix(';') {
+            if let Some(attr) = comment.trim().strip_prefix("#@!") {
+                let attr = attr.trim();
+                if let Some(v) = attr.strip_prefix("TF.FileFunction,") {
+                    doc.file_function = v.to_string();
+                } else if let Some(v) = attr.strip_prefix("TF.GenerationSoftware,") {
+                    doc.generation_software = v.to_string();
+                } else if let Some(v) = attr.strip_prefix("TA.AperFunction,") {
+                    pending_function = Some(v.to_string());
+                }
+            }
+            continue;
+        }
+        let upper = l.to_ascii_uppercase();
+        if upper.starts_with("METRIC") || upper.starts_with("INCH") {
+            let metric = upper.starts_with("METRIC");
+            doc.unit = if metric {
+                GerberUnit::Millimeters
+            } else {
+                GerberUnit::Inches
+            };
+            digits = if metric { (3, 3) } else { (2, 4) };
+            for option in upper.split(',').skip(1) {
+                match option {
+                    "LZ" => leading_zeros = true,
+                    "TZ" => leading_zeros = false,
+                    f if f.contains('.') => {
+                        let (int, dec) = f.split_once('.').unwrap_or((f, ""));
+                        digits = (int.len() as u32, dec.len() as u32);
+                    }
+                    _ => {}
+                }
+            }
+            continue;
+        }
+        match upper.as_str() {
+            "M48" | "%" | "M95" | "G90" | "G05" | "FMAT,2" | "VER,1" | "ICI,OFF" => continue,
+            "M71" => {
+                doc.unit = GerberUnit::Millimeters;
+                continue;
+            }
+            "M72" => {
+                doc.unit = GerberUnit::Inches;
+                continue;
+            }
+            "M30" | "M00" => break,
+            "G91" | "ICI,ON" => return unsupported(line, "incremental coordinates"),
+            "FMAT,1" => return unsupported(line, "Excellon format 1"),
+            _ => {}
+        }
+        if let Some(t) = upper.strip_prefix('T') {
+            let n_len = t.chars().take_while(|c| c.is_ascii_digit()).count();
+            let number: u32 = t[..n_len]
+                .parse()
+                .or_else(|_| syntax(line, "bad tool number"))?;
+            let params = &t[n_len..];
+            if let Some(c) = params.find('C') {
+                let value: String = params[c + 1..]
+                    .chars()
+                    .take_while(|ch| ch.is_ascii_digit() || *ch == '.')
+                    .collect();
+                let diameter = value
+                    .parse::<f64>()
+                    .or_else(|_| syntax(line, "bad tool diameter"))?;
+                doc.tools.retain(|t| t.number != number);
+                doc.tools.push(DrillTool {
+                    number,
+                    diameter,
+                    function: pending_function.take(),
+                });
+            } else if number == 0 {
+                tool = None;
+            } else {
+                if !doc.tools.iter().any(|t| t.number == number) {
+                    return syntax(line, &format!("undefined tool T{}", number));
+                }
+                tool = Some(number);
+            }
+            continue;
+        }
+        if upper.starts_with('X') || upper.starts_with('Y') {
+            let Some(current) = tool else {
+                return syntax(line, "drill hit before tool selection");
+            };
+            let (first, second) = match upper.split_once("G85") {
+                Some((a, b)) => (a, Some(b)),
+                None => (upper.as_str(), None),
+            };
+            let start = excellon_xy(line, first, position, leading_zeros, digits)?;
+            match second {
+                None => {
+                    doc.hits.push(DrillHit::Hole {
+                        tool: current,
+                        x: start.0,
+                        y: start.1,
+                    });
+                    position = start;
+                }
+                Some(b) => {
+                    let end = excellon_xy(line, b, start, leading_zeros, digits)?;
+                    doc.hits.push(DrillHit::Slot {
+                        tool: current,
+                        start,
+                        end,
+                    });
+                    position = end;
+                }
+            }
+            continue;
+        }
+        if upper.starts_with("G00") || upper.starts_with("G01") || upper.starts_with("M15") {
+            return unsupported(line, "routed paths");
+        }
+        // Remaining header directives (e.g. ATC, FMAT, OF) are informational
+    }
+    Ok(doc)
+}
+
+fn excellon_xy(
+    line: usize,
+    text: &str,
+    previous: (f64, f64),
+    leading_zeros: bool,
+    digits: (u32, u32),
+) -> Result<(f64, f64), GerberParseError> {
+    let mut x = previous.0;
+    let mut y = previous.1;
+    let mut rest = text;
+    while !rest.is_empty() {
+        let axis = rest.as_bytes()[0];
+        let len = rest[1..]
+            .chars()
+            .take_while(|c| c.is_ascii_digit() || matches!(c, '.' | '-' | '+'))
+            .count();
+        let value = excellon_number(&rest[1..1 + len], leading_zeros, digits).ok_or_else(|| {
+            GerberParseError::Syntax {
+                line,
+                message: format!("bad coordinate '{}'", text),
+            }
+        })?;
+        match axis {
+            b'X' => x = value,
+            b'Y' => y = value,
+            _ => return syntax(line, &format!("unexpected '{}'", axis as char)),
+        }
+        rest = &rest[1 + len..];
+    }
+    Ok((x, y))
+}
+
+/// Excellon number: decimal if it has a point, otherwise fixed-digit with
+/// zero suppression (LZ keeps leading zeros, TZ keeps trailing zeros)
+fn excellon_number(text: &str, leading_zeros: bool, digits: (u32, u32)) -> Option<f64> {
+    if text.contains('.') {
+        return text.parse().ok();
+    }
+    let (sign, body) = match text.strip_prefix('-') {
+        Some(b) => (-1.0, b),
+        None => (1.0, text.trim_start_matches('+')),
+    };
+    if body.is_empty() || !body.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let raw: f64 = if leading_zeros {
+        // Trailing zeros were suppressed: pad on the right
+        let total = (digits.0 + digits.1) as usize;
+        let padded = format!("{:0<width$}", body, width = total.max(body.len()));
+        padded.parse().ok()?
+    } else {
+        body.parse().ok()?
+    };
+    Some(sign * raw / 10f64.powi(digits.1 as i32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::fabrication::{
+        generate_fabrication_package, BoardSide, FabBoard, FabOptions, FabPad, FabTrack, FabVia,
+        PadShape, TrackSegment,
+    };
+
+    fn board() -> FabBoard {
+        let mut board = FabBoard::rectangle("RT", 10.0, 10.0);
+        board.pads.push(FabPad {
+            component: "U1".into(),
+            pin: "1".into(),
+            net: Some("VCC".into()),
+            position: (2.0, 2.0),
+            shape: PadShape::RoundRect {
+                width: 1.0,
+                height: 0.6,
+                radius: 0.15,
+            },
+            side: BoardSide::Top,
+            drill: None,
+        });
+        board.tracks.push(FabTrack {
+            side: BoardSide::Top,
+            width: 0.2,
+            net: Some("VCC".into()),
+            segment: TrackSegment::Arc {
+                start: (2.0, 2.0),
+                end: (4.0, 4.0),
+                center: (4.0, 2.0),
+                clockwise: true,
+            },
+        });
+        board.vias.push(FabVia {
+            position: (4.0, 4.0),
+            diameter: 0.6,
+            drill: 0.3,
+            net: Some("VCC".into()),
+        });
+        board
+    }
+
+    #[test]
+    fn test_gerber_round_trip() {
+        let package = generate_fabrication_package(&board(), &FabOptions::default());
+        for file in package.files.iter().filter(|f| f.name.ends_with(".gbr")) {
+            let text = String::from_utf8(file.contents.clone()).unwrap();
+            let doc = parse_gerber(&text).unwrap();
+            assert_eq!(doc.file_function(), file.file_function);
+            assert_eq!(doc.to_string(), text, "{}", file.name);
+        }
+    }
+
+    #[test]
+    fn test_foreign_gerber_format() {
+        // 2.4 inch format, modal coordinates, deprecated G54 and combined words
+        let text = "G04 test*\n%FSLAX24Y24*%\n%MOIN*%\n%ADD10C,0.010*%\n%ADD11R,0.05X0.06*%\n\
+                    G54D10*\nX0Y0D02*\nG01X10000D01*\nY5000*\nD11*\nX2500Y2500D03*\n\
+                    G36*\nX0Y0D02*\nX1000D01*\nY1000D01*\nX0D01*\nG37*\nM02*\n";
+        let doc = parse_gerber(text).unwrap();
+        assert_eq!(doc.unit(), GerberUnit::Inches);
+        assert_eq!(doc.apertures().len(), 2);
+        assert_eq!(
+            doc.commands()[..4],
+            [
+                GerberCommand::SelectAperture(10),
+                GerberCommand::Move { x: 0, y: 0 },
+                GerberCommand::Line { x: 1_000_000, y: 0 },
+                GerberCommand::Line {
+                    x: 1_000_000,
+                    y: 500_000
+                },
+            ]
+        );
+        assert!(doc.commands().contains(&GerberCommand::Flash {
+            x: 250_000,
+            y: 250_000
+        }));
+        assert!(doc.commands().contains(&GerberCommand::RegionEnd));
+    }
+
+    #[test]
+    fn test_gerber_errors() {
+        assert!(matches!(
+            parse_gerber("%FSLAX36Y36*%\n%MOMM*%\nD10*\nM02*\n"),
+            Err(GerberParseError::Syntax { line: 3, .. })
+        ));
+        assert!(matches!(
+            parse_gerber("%FSLAX36Y36*%\n%MOMM*%\n%ADD10P,1X6*%\nM02*\n"),
+            Err(GerberParseError::Unsupported { line: 3, .. })
+        ));
+        assert!(matches!(
+            parse_gerber("%FSLAX36Y36*%\n%LPC*%\nM02*\n"),
+            Err(GerberParseError::Unsupported { line: 2, .. })
+        ));
+        assert!(parse_gerber("%FSLAX36Y36*%\n").is_err());
+    }
+
+    #[test]
+    fn test_excellon_round_trip_and_zero_suppression() {
+        let package = generate_fabrication_package(&board(), &FabOptions::default());
+        let pth = package.file("RT-PTH.drl").unwrap();
+        let text = String::from_utf8(pth.contents.clone()).unwrap();
+        let drl = parse_excellon(&text).unwrap();
+        assert!(drl.is_plated());
+        assert_eq!(drl.to_string(), text);
+
+        let legacy = "M48\nINCH,TZ\nT1C0.0320\n%\nT1\nX005Y01\nX015\nM30\n";
+        let drl = parse_excellon(legacy).unwrap();
+        assert_eq!(drl.unit(), GerberUnit::Inches);
+        assert_eq!(
+            drl.hits(),
+            [
+                DrillHit::Hole {
+                    tool: 1,
+                    x: 0.0005,
+                    y: 0.0001
+                },
+                DrillHit::Hole {
+                    tool: 1,
+                    x: 0.0015,
+                    y: 0.0001
+                },
+            ]
+        );
+        let lz = parse_excellon("M48\nMETRIC,LZ\nT2C0.8\n%\nT2\nX0125Y-005\nM30\n").unwrap();
+        assert_eq!(
+            lz.hits()[0],
+            DrillHit::Hole {
+                tool: 2,
+                x: 12.5,
+                y: -5.0
+            }
+        );
+    }
+}
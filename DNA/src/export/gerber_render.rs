@@ -0,0 +1,784 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: gerber_render.rs | DNA/src/export/gerber_render.rs
+//! PURPOSE: Gerber layer geometry (Shape, LayerObject) and headless rasterisation
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+//!
+//! Every graphical object of a layer becomes a [`Shape`]: a core polyline or
+//! filled polygon swept by a disk. That one representation covers flashes
+//! (circle = point + disk, round-rect = rectangle + disk), strokes, arcs and
+//! regions, and gives exact signed distances for both the rasteriser and the
+//! design-rule checker.
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │   GerberDocument ──► layer_objects() ──► Vec<LayerObject { Shape, attrs }>  │
+//! │                                             │                               │
+//! │                           ┌─────────────────┴──────────────┐                │
+//! │                           ▼                                ▼                │
+//! │                  Raster (PGM / PNG)                  drc::run_drc           │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! Units are millimetres; inch files are converted.
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use super::gerber::{ApertureType, GerberCommand, GerberDocument, GerberUnit};
+
+/// Maximum deviation of flattened arcs from the true arc (mm)
+const ARC_TOLERANCE: f64 = 0.001;
+
+/// Minkowski sum of a core polyline (or filled polygon when `closed`) and a disk
+#[derive(Clone, Debug, PartialEq)]
+pub struct Shape {
+    pub points: Vec<(f64, f64)>,
+    pub closed: bool,
+    pub radius: f64,
+}
+
+impl Shape {
+    pub fn disk(center: (f64, f64), radius: f64) -> Self {
+        Self {
+            points: vec![center],
+            closed: false,
+            radius,
+        }
+    }
+
+    pub fn capsule(a: (f64, f64), b: (f64, f64), radius: f64) -> Self {
+        Self {
+            points: vec![a, b],
+            closed: false,
+            radius,
+        }
+    }
+
+    pub fn polygon(points: Vec<(f64, f64)>, radius: f64) -> Self {
+        let closed = points.len() >= 3;
+        Self {
+            points,
+            closed,
+            radius,
+        }
+    }
+
+    /// Axis-aligned bounds (min, max)
+    pub fn bounds(&self) -> ((f64, f64), (f64, f64)) {
+        let mut min = (f64::INFINITY, f64::INFINITY);
+        let mut max = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+        for &(x, y) in &self.points {
+            min = (min.0.min(x), min.1.min(y));
+            max = (max.0.max(x), max.1.max(y));
+        }
+        let r = self.radius;
+        ((min.0 - r, min.1 - r), (max.0 + r, max.1 + r))
+    }
+
+    fn segments(&self) -> impl Iterator<Item = ((f64, f64), (f64, f64))> + '_ {
+        let n = self.points.len();
+        let count = match (n, self.closed) {
+            (0, _) => 0,
+            (1, _) => 1,
+            (_, true) => n,
+            (_, false) => n - 1,
+        };
+        (0..count).map(move |i| (self.points[i], self.points[(i + 1) % n]))
+    }
+
+    fn core_contains(&self, p: (f64, f64)) -> bool {
+        self.closed && point_in_polygon(&self.points, p)
+    }
+
+    /// Signed distance from `p` to the shape boundary (negative inside)
+    pub fn signed_distance(&self, p: (f64, f64)) -> f64 {
+        let d = self
+            .segments()
+            .map(|(a, b)| point_segment_distance(p, a, b))
+            .fold(f64::INFINITY, f64::min);
+        let core = if self.core_contains(p) { -d } else { d };
+        core - self.radius
+    }
+
+    pub fn contains(&self, p: (f64, f64)) -> bool {
+        self.signed_distance(p) <= 0.0
+    }
+
+    /// Gap between two shapes (0 when they touch or overlap) and the point
+    /// halfway across it
+    pub fn gap(&self, other: &Shape) -> (f64, (f64, f64)) {
+        // Core containment makes the cores overlap
+        for (shape, inner) in [(self, other), (other, self)] {
+            if let Some(&p) = inner.points.iter().find(|&&p| shape.core_contains(p)) {
+                return (0.0, p);
+            }
+        }
+        let mut best = (f64::INFINITY, (0.0, 0.0));
+        for (a, b) in self.segments() {
+            for (c, d) in other.segments() {
+                let (dist, pa, pb) = segment_segment_distance(a, b, c, d);
+                if dist < best.0 {
+                    best = (dist, ((pa.0 + pb.0) / 2.0, (pa.1 + pb.1) / 2.0));
+                }
+            }
+        }
+        ((best.0 - self.radius - other.radius).max(0.0), best.1)
+    }
+}
+
+/// How a layer object was produced
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ObjectKind {
+    Flash,
+    Draw,
+    Region,
+}
+
+/// One graphical object of a Gerber layer with its X2 attributes
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayerObject {
+    pub kind: ObjectKind,
+    pub shape: Shape,
+    pub aperture: Option<u32>,
+    /// Stroke width (draws) or smallest aperture dimension (flashes), mm
+    pub width: f64,
+    /// .AperFunction of the aperture
+    pub function: Option<String>,
+    /// Object attributes in force (e.g. (".N", "GND"))
+    pub attributes: Vec<(String, String)>,
+}
+
+impl LayerObject {
+    /// Net name from the .N attribute (None when absent or empty)
+    pub fn net(&self) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(name, _)| name == ".N")
+            .map(|(_, value)| value.as_str())
+            .filter(|v| !v.is_empty())
+    }
+}
+
+/// Core points (relative to the aperture centre) and sweep radius of an aperture
+fn aperture_core(aperture: &ApertureType) -> (Vec<(f64, f64)>, f64) {
+    let rect = |w: f64, h: f64| {
+        let (hw, hh) = (w / 2.0, h / 2.0);
+        vec![(-hw, -hh), (hw, -hh), (hw, hh), (-hw, hh)]
+    };
+    match *aperture {
+        ApertureType::Circle { diameter } => (vec![(0.0, 0.0)], diameter / 2.0),
+        ApertureType::Rectangle { width, height } => (rect(width, height), 0.0),
+        ApertureType::Obround { width, height } => {
+            if width >= height {
+                let d = (width - height) / 2.0;
+                (vec![(-d, 0.0), (d, 0.0)], height / 2.0)
+            } else {
+                let d = (height - width) / 2.0;
+                (vec![(0.0, -d), (0.0, d)], width / 2.0)
+            }
+        }
+        ApertureType::RoundRect {
+            width,
+            height,
+            radius,
+        } => (rect(width - 2.0 * radius, height - 2.0 * radius), radius),
+    }
+}
+
+fn aperture_min_size(aperture: &ApertureType) -> f64 {
+    match *aperture {
+        ApertureType::Circle { diameter } => diameter,
+        ApertureType::Rectangle { width, height }
+        | ApertureType::Obround { width, height }
+        | ApertureType::RoundRect { width, height, .. } => width.min(height),
+    }
+}
+
+fn scale_aperture(aperture: &ApertureType, s: f64) -> ApertureType {
+    match *aperture {
+        ApertureType::Circle { diameter } => ApertureType::Circle {
+            diameter: diameter * s,
+        },
+        ApertureType::Rectangle { width, height } => ApertureType::Rectangle {
+            width: width * s,
+            height: height * s,
+        },
+        ApertureType::Obround { width, height } => ApertureType::Obround {
+            width: width * s,
+            height: height * s,
+        },
+        ApertureType::RoundRect {
+            width,
+            height,
+            radius,
+        } => ApertureType::RoundRect {
+            width: width * s,
+            height: height * s,
+            radius: radius * s,
+        },
+    }
+}
+
+fn core_at(core: &[(f64, f64)], at: (f64, f64)) -> Vec<(f64, f64)> {
+    core.iter().map(|&(x, y)| (x + at.0, y + at.1)).collect()
+}
+
+/// Shape swept by an aperture moving in a straight line from `a` to `b`
+fn stroke(core: &[(f64, f64)], radius: f64, a: (f64, f64), b: (f64, f64)) -> Shape {
+    if core.len() == 1 {
+        return Shape::capsule(core_at(core, a)[0], core_at(core, b)[0], radius);
+    }
+    let mut points = core_at(core, a);
+    points.extend(core_at(core, b));
+    Shape::polygon(convex_hull(points), radius)
+}
+
+/// Points along an arc from `start` to `end` around `center` (endpoints included)
+fn arc_points(
+    start: (f64, f64),
+    end: (f64, f64),
+    center: (f64, f64),
+    clockwise: bool,
+) -> Vec<(f64, f64)> {
+    let r = ((start.0 - center.0).powi(2) + (start.1 - center.1).powi(2)).sqrt();
+    let a0 = (start.1 - center.1).atan2(start.0 - center.0);
+    let a1 = (end.1 - center.1).atan2(end.0 - center.0);
+    let tau = std::f64::consts::TAU;
+    let mut sweep = a1 - a0;
+    if clockwise {
+        while sweep >= 0.0 {
+            sweep -= tau;
+        }
+    } else {
+        while sweep <= 0.0 {
+            sweep += tau;
+        }
+    }
+    // Coincident end points on a real arc mean a full circle
+    let same = (start.0 - end.0).abs() < 1e-9 && (start.1 - end.1).abs() < 1e-9;
+    if !same && sweep.abs() > tau - 1e-9 {
+        sweep = 0.0;
+    }
+    let step = if r > ARC_TOLERANCE {
+        2.0 * (1.0 - ARC_TOLERANCE / r).acos()
+    } else {
+        sweep.abs().max(1e-3)
+    };
+    let n = ((sweep.abs() / step.max(1e-3)).ceil() as usize).clamp(1, 3600);
+    let mut points: Vec<(f64, f64)> = (0..n)
+        .map(|i| {
+            let a = a0 + sweep * i as f64 / n as f64;
+            (center.0 + r * a.cos(), center.1 + r * a.sin())
+        })
+        .collect();
+    points.push(end);
+    points
+}
+
+/// Geometry of every object on a layer, in millimetres
+pub fn layer_objects(doc: &GerberDocument) -> Vec<LayerObject> {
+    let s = match doc.unit() {
+        GerberUnit::Millimeters => 1e-6,
+        GerberUnit::Inches => 25.4e-6,
+    };
+    let unit_scale = s * 1e6;
+    let pt = |x: i64, y: i64| (x as f64 * s, y as f64 * s);
+
+    let mut objects = Vec::new();
+    let mut attributes: Vec<(String, String)> = Vec::new();
+    let mut aperture: Option<(u32, ApertureType, Option<String>)> = None;
+    let mut current = (0.0, 0.0);
+    let mut in_region = false;
+    let mut contour: Vec<(f64, f64)> = Vec::new();
+
+    let push = |objects: &mut Vec<LayerObject>,
+                kind: ObjectKind,
+                shape: Shape,
+                aperture: &Option<(u32, ApertureType, Option<String>)>,
+                attributes: &[(String, String)]| {
+        objects.push(LayerObject {
+            kind,
+            shape,
+            aperture: aperture.as_ref().map(|a| a.0),
+            width: aperture.as_ref().map_or(0.0, |a| aperture_min_size(&a.1)),
+            function: aperture.as_ref().and_then(|a| a.2.clone()),
+            attributes: attributes.to_vec(),
+        });
+    };
+    let close_contour = |objects: &mut Vec<LayerObject>,
+                         contour: &mut Vec<(f64, f64)>,
+                         attributes: &[(String, String)]| {
+        if contour.len() >= 3 {
+            objects.push(LayerObject {
+                kind: ObjectKind::Region,
+                shape: Shape::polygon(std::mem::take(contour), 0.0),
+                aperture: None,
+                width: 0.0,
+                function: None,
+                attributes: attributes.to_vec(),
+            });
+        }
+        contour.clear();
+    };
+
+    for cmd in doc.commands() {
+        match cmd {
+            GerberCommand::SelectAperture(n) => {
+                aperture = doc.aperture_def(*n).map(|def| {
+                    (
+                        *n,
+                        scale_aperture(def.aperture_type(), unit_scale),
+                        def.function().map(str::to_string),
+                    )
+                });
+            }
+            GerberCommand::ObjectAttribute { name, value } => {
+                attributes.retain(|(n, _)| n != name);
+                attributes.push((name.clone(), value.clone()));
+            }
+            GerberCommand::DeleteAttributes => attributes.clear(),
+            GerberCommand::RegionStart => {
+                in_region = true;
+                contour.clear();
+            }
+            GerberCommand::RegionEnd => {
+                close_contour(&mut objects, &mut contour, &attributes);
+                in_region = false;
+            }
+            GerberCommand::Move { x, y } => {
+                current = pt(*x, *y);
+                if in_region {
+                    close_contour(&mut objects, &mut contour, &attributes);
+                    contour.push(current);
+                }
+            }
+            GerberCommand::Line { x, y } => {
+                let to = pt(*x, *y);
+                if in_region {
+                    if contour.is_empty() {
+                        contour.push(current);
+                    }
+                    contour.push(to);
+                } else if let Some(ap) = &aperture {
+                    let (core, r) = aperture_core(&ap.1);
+                    push(
+                        &mut objects,
+                        ObjectKind::Draw,
+                        stroke(&core, r, current, to),
+                        &aperture,
+                        &attributes,
+                    );
+                }
+                current = to;
+            }
+            GerberCommand::Arc {
+                x,
+                y,
+                i,
+                j,
+                clockwise,
+            } => {
+                let to = pt(*x, *y);
+                let center = (current.0 + *i as f64 * s, current.1 + *j as f64 * s);
+                let points = arc_points(current, to, center, *clockwise);
+                if in_region {
+                    if contour.is_empty() {
+                        contour.push(current);
+                    }
+                    contour.extend(points.into_iter().skip(1));
+                } else if let Some(ap) = &aperture {
+                    let (core, r) = aperture_core(&ap.1);
+                    if core.len() == 1 {
+                        let shape = Shape {
+                            points,
+                            closed: false,
+                            radius: r,
+                        };
+                        push(
+                            &mut objects,
+                            ObjectKind::Draw,
+                            shape,
+                            &aperture,
+                            &attributes,
+                        );
+                    } else {
+                        for w in points.windows(2) {
+                            push(
+                                &mut objects,
+                                ObjectKind::Draw,
+                                stroke(&core, r, w[0], w[1]),
+                                &aperture,
+                                &attributes,
+                            );
+                        }
+                    }
+                }
+                current = to;
+            }
+            GerberCommand::Flash { x, y } => {
+                current = pt(*x, *y);
+                if let Some(ap) = &aperture {
+                    let (core, r) = aperture_core(&ap.1);
+                    let points = core_at(&core, current);
+                    let shape = if points.len() >= 3 {
+                        Shape::polygon(points, r)
+                    } else {
+                        Shape {
+                            points,
+                            closed: false,
+                            radius: r,
+                        }
+                    };
+                    push(
+                        &mut objects,
+                        ObjectKind::Flash,
+                        shape,
+                        &aperture,
+                        &attributes,
+                    );
+                }
+            }
+        }
+    }
+    objects
+}
+
+/// Grayscale coverage image of one or more layers (row 0 is the top edge)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Raster {
+    pub width: usize,
+    pub height: usize,
+    /// Board coordinate of the lower-left pixel corner (mm)
+    pub origin: (f64, f64),
+    /// Pixel edge length (mm)
+    pub pixel_size: f64,
+    pub pixels: Vec<u8>,
+}
+
+impl Raster {
+    /// Blank raster covering `bounds` (min, max in mm)
+    pub fn new(bounds: ((f64, f64), (f64, f64)), pixel_size: f64) -> Self {
+        let ((x0, y0), (x1, y1)) = bounds;
+        let width = (((x1 - x0) / pixel_size).ceil().max(1.0)) as usize;
+        let height = (((y1 - y0) / pixel_size).ceil().max(1.0)) as usize;
+        Self {
+            width,
+            height,
+            origin: (x0, y0),
+            pixel_size,
+            pixels: vec![0; width * height],
+        }
+    }
+
+    /// Board coordinate of a pixel centre
+    pub fn pixel_center(&self, col: usize, row: usize) -> (f64, f64) {
+        (
+            self.origin.0 + (col as f64 + 0.5) * self.pixel_size,
+            self.origin.1 + (self.height - row) as f64 * self.pixel_size - 0.5 * self.pixel_size,
+        )
+    }
+
+    /// Set every pixel whose centre lies inside `shape`
+    pub fn fill(&mut self, shape: &Shape, value: u8) {
+        let ((x0, y0), (x1, y1)) = shape.bounds();
+        let col0 = ((x0 - self.origin.0) / self.pixel_size).floor().max(0.0) as usize;
+        let col1 = (((x1 - self.origin.0) / self.pixel_size).ceil() as usize).min(self.width);
+        let top = self.origin.1 + self.height as f64 * self.pixel_size;
+        let row0 = ((top - y1) / self.pixel_size).floor().max(0.0) as usize;
+        let row1 = (((top - y0) / self.pixel_size).ceil() as usize).min(self.height);
+        for row in row0..row1 {
+            for col in col0..col1 {
+                if shape.contains(self.pixel_center(col, row)) {
+                    self.pixels[row * self.width + col] = value;
+                }
+            }
+        }
+    }
+
+    /// Pixel value at a board coordinate (0 outside the raster)
+    pub fn value_at(&self, p: (f64, f64)) -> u8 {
+        let col = ((p.0 - self.origin.0) / self.pixel_size).floor();
+        let row = self.height as f64 - ((p.1 - self.origin.1) / self.pixel_size).floor() - 1.0;
+        if col < 0.0 || row < 0.0 || col >= self.width as f64 || row >= self.height as f64 {
+            return 0;
+        }
+        self.pixels[row as usize * self.width + col as usize]
+    }
+
+    /// Fraction of non-zero pixels
+    pub fn coverage(&self) -> f64 {
+        let set = self.pixels.iter().filter(|&&v| v != 0).count();
+        set as f64 / self.pixels.len().max(1) as f64
+    }
+
+    /// Binary PGM (P5)
+    pub fn to_pgm(&self) -> Vec<u8> {
+        let mut out = format!("P5\n{} {}\n255\n", self.width, self.height).into_bytes();
+        out.extend_from_slice(&self.pixels);
+        out
+    }
+
+    /// 8-bit grayscale PNG (uncompressed deflate blocks)
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut raw = Vec::with_capacity((self.width + 1) * self.height);
+        for row in self.pixels.chunks(self.width.max(1)) {
+            raw.push(0); // filter: none
+            raw.extend_from_slice(row);
+        }
+
+        let mut zlib = vec![0x78, 0x01];
+        let mut blocks = raw.chunks(65_535).peekable();
+        if blocks.peek().is_none() {
+            zlib.extend_from_slice(&[1, 0, 0, 0xFF, 0xFF]);
+        }
+        while let Some(block) = blocks.next() {
+            let last = blocks.peek().is_none();
+            let len = block.len() as u16;
+            zlib.push(last as u8);
+            zlib.extend_from_slice(&len.to_le_bytes());
+            zlib.extend_from_slice(&(!len).to_le_bytes());
+            zlib.extend_from_slice(block);
+        }
+        zlib.extend_from_slice(&adler32(&raw).to_be_bytes());
+
+        let mut ihdr = Vec::new();
+        ihdr.extend_from_slice(&(self.width as u32).to_be_bytes());
+        ihdr.extend_from_slice(&(self.height as u32).to_be_bytes());
+        ihdr.extend_from_slice(&[8, 0, 0, 0, 0]); // 8-bit grayscale
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        for (kind, data) in [(b"IHDR", ihdr), (b"IDAT", zlib), (b"IEND", Vec::new())] {
+            png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+            let mut chunk = kind.to_vec();
+            chunk.extend_from_slice(&data);
+            png.extend_from_slice(&chunk);
+            png.extend_from_slice(&super::fabrication::crc32(&chunk).to_be_bytes());
+        }
+        png
+    }
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65_521;
+        b = (b + a) % 65_521;
+    }
+    (b << 16) | a
+}
+
+/// Union of the bounds of all objects on the given layers
+fn objects_bounds<'a>(objects: impl Iterator<Item = &'a LayerObject>) -> ((f64, f64), (f64, f64)) {
+    let mut min = (f64::INFINITY, f64::INFINITY);
+    let mut max = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+    for o in objects {
+        let (lo, hi) = o.shape.bounds();
+        min = (min.0.min(lo.0), min.1.min(lo.1));
+        max = (max.0.max(hi.0), max.1.max(hi.1));
+    }
+    if min.0.is_finite() {
+        (min, max)
+    } else {
+        ((0.0, 0.0), (0.0, 0.0))
+    }
+}
+
+/// Rasterise a single layer at `pixel_size` mm per pixel
+pub fn render_layer(doc: &GerberDocument, pixel_size: f64) -> Raster {
+    render_layers(&[doc], pixel_size).remove(0)
+}
+
+/// Rasterise several layers onto a common grid (one raster per layer)
+pub fn render_layers(docs: &[&GerberDocument], pixel_size: f64) -> Vec<Raster> {
+    let layers: Vec<Vec<LayerObject>> = docs.iter().map(|d| layer_objects(d)).collect();
+    let ((x0, y0), (x1, y1)) = objects_bounds(layers.iter().flatten());
+    let margin = pixel_size;
+    let bounds = ((x0 - margin, y0 - margin), (x1 + margin, y1 + margin));
+    let mut rasters: Vec<Raster> = layers
+        .iter()
+        .map(|objects| {
+            let mut raster = Raster::new(bounds, pixel_size);
+            for o in objects {
+                raster.fill(&o.shape, 255);
+            }
+            raster
+        })
+        .collect();
+    if rasters.is_empty() {
+        rasters.push(Raster::new(bounds, pixel_size));
+    }
+    rasters
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// 2D geometry helpers
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn point_in_polygon(poly: &[(f64, f64)], p: (f64, f64)) -> bool {
+    let mut inside = false;
+    let n = poly.len();
+    for i in 0..n {
+        let (a, b) = (poly[i], poly[(i + 1) % n]);
+        if (a.1 > p.1) != (b.1 > p.1) {
+            let x = a.0 + (p.1 - a.1) / (b.1 - a.1) * (b.0 - a.0);
+            if p.0 < x {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+fn closest_on_segment(p: (f64, f64), a: (f64, f64), b: (f64, f64)) -> (f64, f64) {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let len2 = dx * dx + dy * dy;
+    if len2 <= f64::EPSILON {
+        return a;
+    }
+    let t = (((p.0 - a.0) * dx + (p.1 - a.1) * dy) / len2).clamp(0.0, 1.0);
+    (a.0 + t * dx, a.1 + t * dy)
+}
+
+fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+    ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt()
+}
+
+fn point_segment_distance(p: (f64, f64), a: (f64, f64), b: (f64, f64)) -> f64 {
+    distance(p, closest_on_segment(p, a, b))
+}
+
+fn cross(o: (f64, f64), a: (f64, f64), b: (f64, f64)) -> f64 {
+    (a.0 - o.0) * (b.1 - o.1) - (a.1 - o.1) * (b.0 - o.0)
+}
+
+/// Distance between segments ab and cd with the closest points on each
+fn segment_segment_distance(
+    a: (f64, f64),
+    b: (f64, f64),
+    c: (f64, f64),
+    d: (f64, f64),
+) -> (f64, (f64, f64), (f64, f64)) {
+    let (d1, d2) = (cross(a, b, c), cross(a, b, d));
+    let (d3, d4) = (cross(c, d, a), cross(c, d, b));
+    if d1 * d2 < 0.0 && d3 * d4 < 0.0 {
+        let t = d1 / (d1 - d2);
+        let p = (c.0 + t * (d.0 - c.0), c.1 + t * (d.1 - c.1));
+        return (0.0, p, p);
+    }
+    let candidates = [
+        (a, closest_on_segment(a, c, d)),
+        (b, closest_on_segment(b, c, d)),
+        (closest_on_segment(c, a, b), c),
+        (closest_on_segment(d, a, b), d),
+    ];
+    candidates
+        .into_iter()
+        .map(|(p, q)| (distance(p, q), p, q))
+        .min_by(|x, y| x.0.total_cmp(&y.0))
+        .expect("four candidates")
+}
+
+/// Convex hull (counter-clockwise, monotone chain)
+fn convex_hull(mut points: Vec<(f64, f64)>) -> Vec<(f64, f64)> {
+    points.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)));
+    points.dedup();
+    if points.len() < 3 {
+        return points;
+    }
+    let half = |iter: &mut dyn Iterator<Item = (f64, f64)>| {
+        let mut chain: Vec<(f64, f64)> = Vec::new();
+        for p in iter {
+            while chain.len() >= 2
+                && cross(chain[chain.len() - 2], chain[chain.len() - 1], p) <= 0.0
+            {
+                chain.pop();
+            }
+            chain.push(p);
+        }
+        chain.pop();
+        chain
+    };
+    let mut hull = half(&mut points.iter().copied());
+    hull.extend(half(&mut points.iter().rev().copied()));
+    hull
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shape_distances() {
+        let pad = Shape::polygon(vec![(0.0, 0.0), (2.0, 0.0), (2.0, 1.0), (0.0, 1.0)], 0.0);
+        assert!((pad.signed_distance((1.0, 0.5)) + 0.5).abs() < 1e-12);
+        assert!((pad.signed_distance((3.0, 0.5)) - 1.0).abs() < 1e-12);
+
+        let track = Shape::capsule((0.0, 3.0), (5.0, 3.0), 0.25);
+        let (gap, at) = pad.gap(&track);
+        assert!((gap - 1.75).abs() < 1e-12);
+        assert!((at.1 - 2.0).abs() < 1e-12);
+
+        // A disk fully inside a polygon overlaps it
+        assert_eq!(pad.gap(&Shape::disk((1.0, 0.5), 0.1)).0, 0.0);
+    }
+
+    #[test]
+    fn test_layer_objects_and_arcs() {
+        let mut doc = GerberDocument::new("Copper,L1,Top");
+        let round = doc.add_round_rect_aperture(1.0, 0.6, 0.1);
+        let track = doc.add_circle_aperture(0.2);
+        doc.select_aperture(round);
+        doc.set_object_attribute(".N", "VCC");
+        doc.flash(0.0, 0.0);
+        doc.delete_object_attributes();
+        doc.select_aperture(track);
+        doc.move_to(1.0, 0.0);
+        doc.arc_to(-1.0, 0.0, 0.0, 0.0, false);
+
+        let objects = layer_objects(&doc);
+        assert_eq!(objects.len(), 2);
+        assert_eq!(objects[0].kind, ObjectKind::Flash);
+        assert_eq!(objects[0].net(), Some("VCC"));
+        assert!((objects[0].width - 0.6).abs() < 1e-12);
+        // Round-rect corner is rounded: the exact corner is outside
+        assert!(objects[0].shape.contains((0.45, 0.25)));
+        assert!(!objects[0].shape.contains((0.5, 0.3)));
+
+        // Upper half circle of radius 1
+        let arc = &objects[1];
+        assert_eq!(arc.net(), None);
+        assert!(arc.shape.contains((0.0, 1.0)));
+        assert!(!arc.shape.contains((0.0, -1.0)));
+        assert!(arc.shape.signed_distance((0.0, 1.1)).abs() < 2e-3);
+    }
+
+    #[test]
+    fn test_render_layer() {
+        let mut doc = GerberDocument::new("Copper,L1,Top");
+        let square = doc.add_rect_aperture(2.0, 2.0);
+        doc.select_aperture(square);
+        doc.flash(0.0, 0.0);
+        doc.region_start();
+        doc.move_to(4.0, 0.0);
+        doc.line_to(6.0, 0.0);
+        doc.line_to(6.0, 2.0);
+        doc.region_end();
+
+        let raster = render_layer(&doc, 0.1);
+        assert_eq!(raster.value_at((0.0, 0.0)), 255);
+        assert_eq!(raster.value_at((2.5, 0.5)), 0);
+        // Triangle region: below the diagonal is filled, above it is not
+        assert_eq!(raster.value_at((5.8, 0.5)), 255);
+        assert_eq!(raster.value_at((4.5, 1.5)), 0);
+        // 4 mm² square + 2 mm² triangle over a 7.2 x 3.2 mm canvas
+        assert!((raster.coverage() - 6.0 / (7.2 * 3.2)).abs() < 0.02);
+
+        let pgm = raster.to_pgm();
+        assert!(
+            pgm.starts_with(format!("P5\n{} {}\n255\n", raster.width, raster.height).as_bytes())
+        );
+        let png = raster.to_png();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[12..16], b"IHDR");
+        assert!(png.ends_with(&[0xAE, 0x42, 0x60, 0x82]));
+    }
+}
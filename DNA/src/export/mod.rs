@@ -1,19 +1,36 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: mod.rs | DNA/src/export/mod.rs
-//! PURPOSE: Module exports: pdf, gerber
-//! MODIFIED: 2025-12-02
+//! PURPOSE: Module exports: pdf, gerber (+import/render/drc), excellon, fabrication, step, part21, step_import, dxf
+//! MODIFIED: 2026-10-18
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
 
-//! Export module for generating PDF and Gerber X2 files
+//! Export module for generating PDF, Gerber X2 / Excellon fabrication packages,
+//! STEP files (and reading STEP back) and DXF drawings (read and write)
 //!
 //! This module implements PDF and Gerber generation from scratch,
 //! following the CLAUDE.md philosophy of minimizing external dependencies.
 
+pub mod drc;
+pub mod dxf;
+pub mod excellon;
+pub mod fabrication;
 pub mod gerber;
+pub mod gerber_import;
+pub mod gerber_render;
+pub mod part21;
 pub mod pdf;
 pub mod step;
+pub mod step_import;
 
+pub use drc::*;
+pub use dxf::*;
+pub use excellon::*;
+pub use fabrication::*;
 pub use gerber::*;
+pub use gerber_import::*;
+pub use gerber_render::*;
+pub use part21::*;
 pub use pdf::*;
 pub use step::*;
+pub use step_import::*;
@@ -0,0 +1,595 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: part21.rs | DNA/src/export/part21.rs
+//! PURPOSE: ISO-10303-21 (STEP Part 21) exchange-file parser
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//! STEP Part 21 Parser - From Scratch
+//!
+//! Reads the clear-text encoding of STEP files into a flat table of entity
+//! instances. No schema knowledge lives here; the B-Rep translator in
+//! `step_import.rs` interprets the records.
+//!
+//! Format overview:
+//! ```text
+//! ISO-10303-21;
+//! HEADER;
+//! FILE_SCHEMA(('AUTOMOTIVE_DESIGN'));
+//! ENDSEC;
+//! DATA;
+//! #1=CARTESIAN_POINT('',(0.,0.,0.));
+//! #2=(LENGTH_UNIT()NAMED_UNIT(*)SI_UNIT(.MILLI.,.METRE.));
+//! ENDSEC;
+//! END-ISO-10303-21;
+//! ```
+
+use std::collections::BTreeMap;
+
+/// Application protocol declared in FILE_SCHEMA
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StepSchema {
+    /// CONFIG_CONTROL_DESIGN
+    Ap203,
+    /// AUTOMOTIVE_DESIGN
+    Ap214,
+    /// AP242_MANAGED_MODEL_BASED_3D_ENGINEERING
+    Ap242,
+    /// Anything else (schema name as written)
+    Other(String),
+}
+
+impl StepSchema {
+    fn from_name(name: &str) -> Self {
+        let upper = name.to_ascii_uppercase();
+        if upper.starts_with("CONFIG_CONTROL_DESIGN") || upper.starts_with("AP203") {
+            StepSchema::Ap203
+        } else if upper.starts_with("AUTOMOTIVE_DESIGN") || upper.starts_with("AP214") {
+            StepSchema::Ap214
+        } else if upper.starts_with("AP242") {
+            StepSchema::Ap242
+        } else {
+            StepSchema::Other(name.to_string())
+        }
+    }
+}
+
+/// Parameter value of an entity instance
+#[derive(Clone, Debug, PartialEq)]
+pub enum StepValue {
+    /// Instance reference (#123)
+    Ref(u64),
+    Integer(i64),
+    Real(f64),
+    String(String),
+    /// Enumeration or logical (.T., .UNSPECIFIED.), stored without dots
+    Enum(String),
+    List(Vec<StepValue>),
+    /// Typed value such as LENGTH_MEASURE(25.4)
+    Typed(String, Box<StepValue>),
+    /// Unset optional value ($)
+    Null,
+    /// Value derived by a supertype (*)
+    Derived,
+}
+
+impl StepValue {
+    pub fn as_ref_id(&self) -> Option<u64> {
+        match self {
+            StepValue::Ref(id) => Some(*id),
+            _ => None,
+        }
+    }
+
+    /// Numeric value (integers are widened, typed measures are unwrapped)
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            StepValue::Real(v) => Some(*v),
+            StepValue::Integer(v) => Some(*v as f64),
+            StepValue::Typed(_, inner) => inner.as_f64(),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            StepValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_enum(&self) -> Option<&str> {
+        match self {
+            StepValue::Enum(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// Logical value (.T. / .F.)
+    pub fn as_bool(&self) -> Option<bool> {
+        match self.as_enum()? {
+            "T" => Some(true),
+            "F" => Some(false),
+            _ => None,
+        }
+    }
+
+    pub fn as_list(&self) -> Option<&[StepValue]> {
+        match self {
+            StepValue::List(items) => Some(items),
+            _ => None,
+        }
+    }
+}
+
+/// One `NAME(params)` record
+#[derive(Clone, Debug, PartialEq)]
+pub struct StepRecord {
+    pub name: String,
+    pub params: Vec<StepValue>,
+}
+
+impl StepRecord {
+    pub fn param(&self, index: usize) -> Option<&StepValue> {
+        self.params.get(index)
+    }
+}
+
+/// Entity instance: a simple record or a complex (multi-record) instance
+#[derive(Clone, Debug, PartialEq)]
+pub enum StepEntity {
+    Simple(StepRecord),
+    Complex(Vec<StepRecord>),
+}
+
+impl StepEntity {
+    /// Record with the given entity name (the whole entity for simple instances)
+    pub fn record(&self, name: &str) -> Option<&StepRecord> {
+        match self {
+            StepEntity::Simple(r) if r.name == name => Some(r),
+            StepEntity::Simple(_) => None,
+            StepEntity::Complex(records) => records.iter().find(|r| r.name == name),
+        }
+    }
+
+    pub fn is(&self, name: &str) -> bool {
+        self.record(name).is_some()
+    }
+
+    /// Entity name for reporting (complex instances list every record)
+    pub fn type_name(&self) -> String {
+        match self {
+            StepEntity::Simple(r) => r.name.clone(),
+            StepEntity::Complex(records) => records
+                .iter()
+                .map(|r| r.name.as_str())
+                .collect::<Vec<_>>()
+                .join("+"),
+        }
+    }
+}
+
+/// Parsed exchange file
+#[derive(Clone, Debug)]
+pub struct StepFile {
+    pub schema: StepSchema,
+    pub entities: BTreeMap<u64, StepEntity>,
+}
+
+impl StepFile {
+    pub fn get(&self, id: u64) -> Option<&StepEntity> {
+        self.entities.get(&id)
+    }
+
+    /// Ids of every instance containing a record with the given name, in file order
+    pub fn ids_of(&self, name: &str) -> Vec<u64> {
+        self.entities
+            .iter()
+            .filter(|(_, e)| e.is(name))
+            .map(|(id, _)| *id)
+            .collect()
+    }
+}
+
+/// Error type for Part 21 parsing and STEP translation
+#[derive(Debug)]
+pub enum StepError {
+    /// Malformed exchange structure (byte offset, message)
+    Syntax(usize, String),
+    /// Well-formed file that cannot be translated
+    Translation(String),
+}
+
+impl std::fmt::Display for StepError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StepError::Syntax(pos, s) => write!(f, "STEP syntax error at byte {}: {}", pos, s),
+            StepError::Translation(s) => write!(f, "STEP translation error: {}", s),
+        }
+    }
+}
+
+impl std::error::Error for StepError {}
+
+/// Parse a STEP Part 21 file
+pub fn parse_part21(text: &str) -> Result<StepFile, StepError> {
+    let mut p = Parser::new(text);
+    p.expect_keyword("ISO-10303-21")?;
+    p.expect(b';')?;
+    p.expect_keyword("HEADER")?;
+    p.expect(b';')?;
+
+    let mut schema = StepSchema::Other(String::new());
+    loop {
+        p.skip_ws();
+        if p.peek_keyword("ENDSEC") {
+            p.expect_keyword("ENDSEC")?;
+            p.expect(b';')?;
+            break;
+        }
+        let record = p.record()?;
+        p.expect(b';')?;
+        if record.name == "FILE_SCHEMA" {
+            let first = record
+                .param(0)
+                .and_then(|v| v.as_list())
+                .and_then(|l| l.first())
+                .and_then(|v| v.as_str());
+            if let Some(name) = first {
+                schema = StepSchema::from_name(name);
+            }
+        }
+    }
+
+    let mut entities = BTreeMap::new();
+    p.expect_keyword("DATA")?;
+    p.skip_ws();
+    // DATA sections may carry an optional name/schema list (Part 21 edition 3).
+    if p.peek() == Some(b'(') {
+        p.value()?;
+    }
+    p.expect(b';')?;
+    loop {
+        p.skip_ws();
+        if p.peek_keyword("ENDSEC") {
+            p.expect_keyword("ENDSEC")?;
+            p.expect(b';')?;
+            break;
+        }
+        p.expect(b'#')?;
+        let id = p.unsigned()?;
+        p.expect(b'=')?;
+        p.skip_ws();
+        let entity = if p.peek() == Some(b'(') {
+            p.bump();
+            let mut records = Vec::new();
+            loop {
+                p.skip_ws();
+                if p.peek() == Some(b')') {
+                    p.bump();
+                    break;
+                }
+                records.push(p.record()?);
+            }
+            StepEntity::Complex(records)
+        } else {
+            StepEntity::Simple(p.record()?)
+        };
+        p.expect(b';')?;
+        entities.insert(id, entity);
+    }
+
+    Ok(StepFile { schema, entities })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tokenizer / recursive-descent parser
+// ─────────────────────────────────────────────────────────────────────────────
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(text: &'a str) -> Self {
+        Self {
+            bytes: text.as_bytes(),
+            pos: 0,
+        }
+    }
+
+    fn error<T>(&self, msg: &str) -> Result<T, StepError> {
+        Err(StepError::Syntax(self.pos, msg.to_string()))
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn bump(&mut self) {
+        self.pos += 1;
+    }
+
+    /// Skip whitespace and /* comments */
+    fn skip_ws(&mut self) {
+        loop {
+            match self.peek() {
+                Some(c) if c.is_ascii_whitespace() => self.bump(),
+                Some(b'/') if self.bytes.get(self.pos + 1) == Some(&b'*') => {
+                    self.pos += 2;
+                    while self.pos < self.bytes.len()
+                        && !(self.bytes[self.pos] == b'*'
+                            && self.bytes.get(self.pos + 1) == Some(&b'/'))
+                    {
+                        self.pos += 1;
+                    }
+                    self.pos = (self.pos + 2).min(self.bytes.len());
+                }
+                _ => break,
+            }
+        }
+    }
+
+    fn expect(&mut self, c: u8) -> Result<(), StepError> {
+        self.skip_ws();
+        if self.peek() == Some(c) {
+            self.bump();
+            Ok(())
+        } else {
+            self.error(&format!("expected '{}'", c as char))
+        }
+    }
+
+    fn peek_keyword(&self, kw: &str) -> bool {
+        self.bytes[self.pos..].starts_with(kw.as_bytes())
+    }
+
+    fn expect_keyword(&mut self, kw: &str) -> Result<(), StepError> {
+        self.skip_ws();
+        if self.peek_keyword(kw) {
+            self.pos += kw.len();
+            Ok(())
+        } else {
+            self.error(&format!("expected {}", kw))
+        }
+    }
+
+    fn keyword(&mut self) -> Result<String, StepError> {
+        self.skip_ws();
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if c.is_ascii_alphanumeric() || c == b'_' || c == b'-' {
+                self.bump();
+            } else {
+                break;
+            }
+        }
+        if start == self.pos {
+            return self.error("expected keyword");
+        }
+        Ok(String::from_utf8_lossy(&self.bytes[start..self.pos]).to_ascii_uppercase())
+    }
+
+    fn unsigned(&mut self) -> Result<u64, StepError> {
+        self.skip_ws();
+        let start = self.pos;
+        while matches!(self.peek(), Some(c) if c.is_ascii_digit()) {
+            self.bump();
+        }
+        std::str::from_utf8(&self.bytes[start..self.pos])
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .map_or_else(|| self.error("expected instance number"), Ok)
+    }
+
+    /// `NAME(param, ...)`
+    fn record(&mut self) -> Result<StepRecord, StepError> {
+        let name = self.keyword()?;
+        let params = match self.value()? {
+            StepValue::List(items) => items,
+            _ => return self.error("expected parameter list"),
+        };
+        Ok(StepRecord { name, params })
+    }
+
+    fn value(&mut self) -> Result<StepValue, StepError> {
+        self.skip_ws();
+        match self.peek() {
+            Some(b'(') => {
+                self.bump();
+                let mut items = Vec::new();
+                self.skip_ws();
+                if self.peek() == Some(b')') {
+                    self.bump();
+                    return Ok(StepValue::List(items));
+                }
+                loop {
+                    items.push(self.value()?);
+                    self.skip_ws();
+                    match self.peek() {
+                        Some(b',') => self.bump(),
+                        Some(b')') => {
+                            self.bump();
+                            return Ok(StepValue::List(items));
+                        }
+                        _ => return self.error("expected ',' or ')'"),
+                    }
+                }
+            }
+            Some(b'#') => {
+                self.bump();
+                Ok(StepValue::Ref(self.unsigned()?))
+            }
+            Some(b'$') => {
+                self.bump();
+                Ok(StepValue::Null)
+            }
+            Some(b'*') => {
+                self.bump();
+                Ok(StepValue::Derived)
+            }
+            Some(b'\'') => self.string(),
+            Some(b'.') => {
+                self.bump();
+                let start = self.pos;
+                while matches!(self.peek(), Some(c) if c != b'.') {
+                    self.bump();
+                }
+                let e = String::from_utf8_lossy(&self.bytes[start..self.pos]).to_ascii_uppercase();
+                self.expect(b'.')?;
+                Ok(StepValue::Enum(e))
+            }
+            Some(b'"') => {
+                // Binary literal: kept as an opaque string
+                self.bump();
+                let start = self.pos;
+                while matches!(self.peek(), Some(c) if c != b'"') {
+                    self.bump();
+                }
+                let s = String::from_utf8_lossy(&self.bytes[start..self.pos]).into_owned();
+                self.expect(b'"')?;
+                Ok(StepValue::String(s))
+            }
+            Some(c) if c == b'-' || c == b'+' || c.is_ascii_digit() => self.number(),
+            Some(c) if c.is_ascii_alphabetic() => {
+                let name = self.keyword()?;
+                let mut inner = match self.value()? {
+                    StepValue::List(items) => items,
+                    _ => return self.error("expected typed parameter"),
+                };
+                let inner = if inner.len() == 1 {
+                    inner.remove(0)
+                } else {
+                    StepValue::List(inner)
+                };
+                Ok(StepValue::Typed(name, Box::new(inner)))
+            }
+            _ => self.error("unexpected character"),
+        }
+    }
+
+    fn string(&mut self) -> Result<StepValue, StepError> {
+        self.bump(); // opening quote
+        let mut out = Vec::new();
+        loop {
+            match self.peek() {
+                Some(b'\'') => {
+                    self.bump();
+                    if self.peek() == Some(b'\'') {
+                        out.push(b'\'');
+                        self.bump();
+                    } else {
+                        break;
+                    }
+                }
+                Some(c) => {
+                    out.push(c);
+                    self.bump();
+                }
+                None => return self.error("unterminated string"),
+            }
+        }
+        Ok(StepValue::String(
+            String::from_utf8_lossy(&out).into_owned(),
+        ))
+    }
+
+    fn number(&mut self) -> Result<StepValue, StepError> {
+        let start = self.pos;
+        let mut is_real = false;
+        if matches!(self.peek(), Some(b'-') | Some(b'+')) {
+            self.bump();
+        }
+        while let Some(c) = self.peek() {
+            match c {
+                b'0'..=b'9' => self.bump(),
+                b'.' | b'E' | b'e' => {
+                    is_real = true;
+                    self.bump();
+                    if (c == b'E' || c == b'e') && matches!(self.peek(), Some(b'-') | Some(b'+')) {
+                        self.bump();
+                    }
+                }
+                _ => break,
+            }
+        }
+        let text = std::str::from_utf8(&self.bytes[start..self.pos]).unwrap_or("");
+        if is_real {
+            // Part 21 allows "1." and "1.E-3", which Rust's parser handles once a
+            // trailing-dot exponent is normalized.
+            let normalized = text.replace(".E", ".0E").replace(".e", ".0e");
+            normalized
+                .parse::<f64>()
+                .map(StepValue::Real)
+                .map_or_else(|_| self.error("invalid real"), Ok)
+        } else {
+            text.parse::<i64>()
+                .map(StepValue::Integer)
+                .map_or_else(|_| self.error("invalid integer"), Ok)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "ISO-10303-21;
+HEADER;
+FILE_DESCRIPTION(('test'),'2;1');
+FILE_SCHEMA(('CONFIG_CONTROL_DESIGN'));
+ENDSEC;
+DATA;
+/* a comment */
+#1=CARTESIAN_POINT('it''s',(1.,-2.5E-1,3));
+#2=(LENGTH_UNIT()NAMED_UNIT(*)SI_UNIT(.MILLI.,.METRE.));
+#3=LENGTH_MEASURE_WITH_UNIT(LENGTH_MEASURE(25.4),#2);
+#4=EDGE_CURVE('',#5,$,#1,.T.);
+ENDSEC;
+END-ISO-10303-21;
+";
+
+    #[test]
+    fn test_parse_sample() {
+        let file = parse_part21(SAMPLE).unwrap();
+        assert_eq!(file.schema, StepSchema::Ap203);
+        assert_eq!(file.entities.len(), 4);
+
+        let point = file.get(1).unwrap().record("CARTESIAN_POINT").unwrap();
+        assert_eq!(point.param(0).unwrap().as_str(), Some("it's"));
+        let coords: Vec<f64> = point.params[1]
+            .as_list()
+            .unwrap()
+            .iter()
+            .filter_map(|v| v.as_f64())
+            .collect();
+        assert_eq!(coords, vec![1.0, -0.25, 3.0]);
+
+        let unit = file.get(2).unwrap();
+        assert!(unit.is("SI_UNIT") && unit.is("LENGTH_UNIT"));
+        assert_eq!(
+            unit.record("SI_UNIT").unwrap().params[0].as_enum(),
+            Some("MILLI")
+        );
+
+        let measure = file
+            .get(3)
+            .unwrap()
+            .record("LENGTH_MEASURE_WITH_UNIT")
+            .unwrap();
+        assert_eq!(measure.params[0].as_f64(), Some(25.4));
+        assert_eq!(
+            file.get(4).unwrap().record("EDGE_CURVE").unwrap().params[2],
+            StepValue::Null
+        );
+        assert_eq!(file.ids_of("SI_UNIT"), vec![2]);
+    }
+
+    #[test]
+    fn test_parse_error_reports_position() {
+        let err =
+            parse_part21("ISO-10303-21;\nHEADER;\nENDSEC;\nDATA;\n#1=FOO(;\nENDSEC;").unwrap_err();
+        assert!(matches!(err, StepError::Syntax(_, _)));
+    }
+}
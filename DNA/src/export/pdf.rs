@@ -1,7 +1,7 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: pdf.rs | DNA/src/export/pdf.rs
 //! PURPOSE: Defines PdfDocument, PdfPage, TextAlign types
-//! MODIFIED: 2025-12-09
+//! MODIFIED: 2026-10-18
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
 
@@ -50,7 +50,7 @@ pub struct PdfPage {
 }
 
 /// Text alignment options
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum TextAlign {
     Left,
     Center,
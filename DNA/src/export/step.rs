@@ -1,12 +1,16 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: step.rs | DNA/src/export/step.rs
-//! PURPOSE: Minimal STEP (ISO-10303-21) writer for NX-importable assemblies (inches)
-//! MODIFIED: 2025-12-11
+//! PURPOSE: STEP (ISO-10303-21) writer for crate assemblies and generic B-Rep solids
+//! MODIFIED: 2026-10-18
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
 
+use std::collections::HashMap;
+
 use crate::autocrate::design::{CrateDesign, CratePart};
 use crate::autocrate::geometry::BoundingBox;
+use crate::cad::geometry::{BoundingBox3, Point3, Transform3, Vector3, TOLERANCE};
+use crate::cad::topology::{CurveType, FaceOrientation, Loop, Solid, SurfaceType};
 
 /// STEP export options.
 #[derive(Clone, Debug)]
@@ -14,6 +18,24 @@ pub struct StepExportOptions {
     pub product_name: String,
     /// If true, embeds basic PROPERTY_DEFINITION PMI for overall crate bounding box (inches).
     pub include_pmi: bool,
+    /// Length unit for generic solid export. Crate designs are always written in inches.
+    pub length_unit: StepLengthUnit,
+}
+
+/// Length unit declared in the STEP geometric representation context.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StepLengthUnit {
+    Millimetre,
+    Inch,
+}
+
+impl StepLengthUnit {
+    fn pmi_suffix(self) -> &'static str {
+        match self {
+            StepLengthUnit::Millimetre => "mm",
+            StepLengthUnit::Inch => "in",
+        }
+    }
 }
 
 impl Default for StepExportOptions {
@@ -21,6 +43,7 @@ impl Default for StepExportOptions {
         Self {
             product_name: "AUTOCRATE CRATE ASSEMBLY".to_string(),
             include_pmi: true,
+            length_unit: StepLengthUnit::Inch,
         }
     }
 }
@@ -32,24 +55,56 @@ impl Default for StepExportOptions {
 /// - Assembly is built via SHAPE_REPRESENTATION relationships + transformations.
 /// - Units: **inches** (conversion-based unit in STEP context).
 pub fn export_step_ap242(design: &CrateDesign, options: &StepExportOptions) -> String {
-    StepWriter::new(design, options.clone()).generate()
+    StepWriter::new(options.clone()).generate_crate(design)
+}
+
+/// One component of a generic STEP assembly.
+#[derive(Clone, Debug)]
+pub struct StepSolidPart<'a> {
+    pub name: String,
+    pub solid: &'a Solid,
+    /// Placement of the part in the assembly (rotation + translation; scale is ignored)
+    pub placement: Transform3,
+}
+
+/// Export a single B-Rep solid to an AP242 STEP Part-21 file.
+///
+/// The solid is wrapped in a one-component assembly so that the product
+/// structure matches [`export_step_assembly`].
+pub fn export_step_solid(name: &str, solid: &Solid, options: &StepExportOptions) -> String {
+    let part = StepSolidPart {
+        name: name.to_string(),
+        solid,
+        placement: Transform3::IDENTITY,
+    };
+    export_step_assembly(&[part], options)
+}
+
+/// Export an assembly of arbitrary B-Rep solids to an AP242 STEP Part-21 file.
+///
+/// Notes:
+/// - Closed shells become MANIFOLD_SOLID_BREP, open shells SHELL_BASED_SURFACE_MODEL.
+/// - Surfaces map to PLANE, CYLINDRICAL_SURFACE, CONICAL_SURFACE, SPHERICAL_SURFACE,
+///   TOROIDAL_SURFACE and (rational) B_SPLINE_SURFACE_WITH_KNOTS.
+/// - Edge curves map to LINE, CIRCLE and (rational) B_SPLINE_CURVE_WITH_KNOTS.
+/// - Coordinates are written as-is in `options.length_unit`.
+pub fn export_step_assembly(parts: &[StepSolidPart<'_>], options: &StepExportOptions) -> String {
+    StepWriter::new(options.clone()).generate_assembly(parts)
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Internal writer (based on the proven approach in the reference AutoCrate TS repo)
 // ─────────────────────────────────────────────────────────────────────────────
 
-struct StepWriter<'a> {
-    design: &'a CrateDesign,
+struct StepWriter {
     options: StepExportOptions,
     id: u32,
     data: Vec<String>,
 }
 
-impl<'a> StepWriter<'a> {
-    fn new(design: &'a CrateDesign, options: StepExportOptions) -> Self {
+impl StepWriter {
+    fn new(options: StepExportOptions) -> Self {
         Self {
-            design,
             options,
             id: 1,
             data: Vec::new(),
@@ -72,15 +127,15 @@ impl<'a> StepWriter<'a> {
         s.replace('\'', "''")
     }
 
-    fn header(&self) -> String {
+    fn header(&self, description: &str, file_name: &str, author: &str) -> String {
         // Keep header deterministic (important for golden tests + reproducible manufacturing artifacts).
         let now = "1970-01-01T00:00:00Z".to_string();
         [
             "ISO-10303-21;".to_string(),
             "HEADER;".to_string(),
-            "FILE_DESCRIPTION(('AutoCrate crate model'),'2;1');".to_string(),
+            format!("FILE_DESCRIPTION(('{description}'),'2;1');"),
             format!(
-                "FILE_NAME('crate_model.step','{}',('AutoCrate'),('Antimony Labs'), 'S3M2P STEP Writer','S3M2P','');",
+                "FILE_NAME('{file_name}','{}',('{author}'),('Antimony Labs'), 'S3M2P STEP Writer','S3M2P','');",
                 now
             ),
             "FILE_SCHEMA(('AP242_MANAGED_MODEL_BASED_3D_ENGINEERING_MIM_LATEST'));".to_string(),
@@ -105,7 +160,27 @@ impl<'a> StepWriter<'a> {
         self.add(format!("AXIS2_PLACEMENT_3D('{label}',{p},{z},{x})"))
     }
 
-    fn create_contexts(&mut self, product_name: &str) -> StepContexts {
+    fn direction_vec(&mut self, v: Vector3) -> String {
+        self.add(format!("DIRECTION('',({:.6},{:.6},{:.6}))", v.x, v.y, v.z))
+    }
+
+    fn point3(&mut self, p: Point3) -> String {
+        self.cartesian_point((p.x as f64, p.y as f64, p.z as f64))
+    }
+
+    fn placement(&mut self, origin: Point3, axis: Vector3, ref_dir: Vector3) -> String {
+        let p = self.point3(origin);
+        let z = self.direction_vec(axis);
+        let x = self.direction_vec(ref_dir);
+        self.add(format!("AXIS2_PLACEMENT_3D('',{p},{z},{x})"))
+    }
+
+    fn create_contexts(
+        &mut self,
+        product_name: &str,
+        definition_name: &str,
+        unit: StepLengthUnit,
+    ) -> StepContexts {
         let escaped = Self::escape(product_name);
         let app = self.add("APPLICATION_CONTEXT('mechanical design')".to_string());
         let _protocol = self.add(format!("APPLICATION_PROTOCOL_DEFINITION('international standard','ap242_managed_model_based_3d_engineering_mim_latest',2020,{app})"));
@@ -115,16 +190,22 @@ impl<'a> StepWriter<'a> {
 
         let plane_angle = self.add("(NAMED_UNIT(*)PLANE_ANGLE_UNIT()SI_UNIT($,.RADIAN.))".to_string());
         let solid_angle = self.add("(NAMED_UNIT(*)SI_UNIT($,.STERADIAN.)SOLID_ANGLE_UNIT())".to_string());
-        // Inches via conversion-based unit (1 in = 25.4 mm).
         let base_mm = self.add("(LENGTH_UNIT()NAMED_UNIT(*)SI_UNIT(.MILLI.,.METRE.))".to_string());
-        let inch_measure = self.add(format!(
-            "LENGTH_MEASURE_WITH_UNIT(LENGTH_MEASURE(25.4),{base_mm})"
-        ));
-        let length_unit = self.add(format!(
-            "(NAMED_UNIT(*)LENGTH_UNIT()CONVERSION_BASED_UNIT('INCH',{inch_measure}))"
-        ));
+        let (length_unit, accuracy) = match unit {
+            StepLengthUnit::Millimetre => (base_mm, "0.001"),
+            StepLengthUnit::Inch => {
+                // Inches via conversion-based unit (1 in = 25.4 mm).
+                let inch_measure = self.add(format!(
+                    "LENGTH_MEASURE_WITH_UNIT(LENGTH_MEASURE(25.4),{base_mm})"
+                ));
+                let inch = self.add(format!(
+                    "(NAMED_UNIT(*)LENGTH_UNIT()CONVERSION_BASED_UNIT('INCH',{inch_measure}))"
+                ));
+                (inch, "0.01")
+            }
+        };
         let uncertainty = self.add(format!(
-            "UNCERTAINTY_MEASURE_WITH_UNIT(LENGTH_MEASURE(0.01),{length_unit},'distance accuracy','')"
+            "UNCERTAINTY_MEASURE_WITH_UNIT(LENGTH_MEASURE({accuracy}),{length_unit},'distance accuracy','')"
         ));
 
         let geom_ctx = self.add(format!(
@@ -133,7 +214,8 @@ impl<'a> StepWriter<'a> {
 
         let product = self.add(format!("PRODUCT('{escaped}','{escaped}','',({mech}))"));
         let formation = self.add(format!("PRODUCT_DEFINITION_FORMATION('','',{product})"));
-        let prod_def = self.add(format!("PRODUCT_DEFINITION('crate definition','',{formation},{design_ctx})"));
+        let definition_name = Self::escape(definition_name);
+        let prod_def = self.add(format!("PRODUCT_DEFINITION('{definition_name}','',{formation},{design_ctx})"));
         let prod_def_shape = self.add(format!("PRODUCT_DEFINITION_SHAPE('','',{prod_def})"));
 
         let _ = app;
@@ -337,8 +419,7 @@ impl<'a> StepWriter<'a> {
         Some(BoundingBox::new(min, max))
     }
 
-    fn add_bbox_pmi_in(&mut self, ctx: &StepContexts, bbox_in: &BoundingBox) {
-        let size = bbox_in.size();
+    fn add_bbox_pmi(&mut self, ctx: &StepContexts, size: (f64, f64, f64), unit: StepLengthUnit) {
         let add_len = |label: &str, value: f64, this: &mut StepWriter| {
             let label = Self::escape(label);
             let measure = this.add(format!("LENGTH_MEASURE_WITH_UNIT(LENGTH_MEASURE({:.3}),{})", value, ctx.length_unit));
@@ -347,29 +428,24 @@ impl<'a> StepWriter<'a> {
             let prop = this.add(format!("PROPERTY_DEFINITION('{label}','product characteristic',{})", ctx.assembly_product_def));
             this.add(format!("PROPERTY_DEFINITION_REPRESENTATION({prop},{rep})"));
         };
-        add_len("overall_width_in", size.x as f64, self);
-        add_len("overall_length_in", size.y as f64, self);
-        add_len("overall_height_in", size.z as f64, self);
+        let suffix = unit.pmi_suffix();
+        add_len(&format!("overall_width_{suffix}"), size.0, self);
+        add_len(&format!("overall_length_{suffix}"), size.1, self);
+        add_len(&format!("overall_height_{suffix}"), size.2, self);
     }
 
-    fn generate(mut self) -> String {
-        let header = self.header();
+    fn generate_crate(mut self, design: &CrateDesign) -> String {
+        let header = self.header("AutoCrate crate model", "crate_model.step", "AutoCrate");
         self.data.push("DATA;".to_string());
 
         let product_name = self.options.product_name.clone();
-        let ctx = self.create_contexts(&product_name);
+        let ctx = self.create_contexts(&product_name, "crate definition", StepLengthUnit::Inch);
 
         // Build part products + shape representations.
         // For v1 we avoid aggressive grouping: each `CratePart` is its own component.
-        let assembly_name = self.options.product_name.clone();
-        let assembly_shape_name = Self::escape(&assembly_name);
-
-        let mut child_placements: Vec<String> = Vec::new();
-        let mut child_shape_reps: Vec<String> = Vec::new();
-        let mut child_products: Vec<ProductDefinition> = Vec::new();
-        let mut child_local_placements: Vec<String> = Vec::new();
+        let mut children: Vec<ChildComponent> = Vec::new();
 
-        let mut parts: Vec<&CratePart> = self.design.parts.iter().collect();
+        let mut parts: Vec<&CratePart> = design.parts.iter().collect();
         parts.sort_by(|a, b| a.id.cmp(&b.id));
 
         for (i, part) in parts.into_iter().enumerate() {
@@ -400,16 +476,110 @@ impl<'a> StepWriter<'a> {
             let local = self.axis2_placement(&format!("{}_LOCAL", part.id), (0.0, 0.0, 0.0));
             let global = self.axis2_placement(&format!("{}_ASM_{}", part.id, i + 1), origin);
 
-            child_products.push(product);
-            child_shape_reps.push(shape_rep);
-            child_local_placements.push(local);
-            child_placements.push(global);
+            children.push(ChildComponent {
+                product,
+                shape_rep,
+                local_placement: local,
+                global_placement: global,
+            });
+        }
+
+        self.link_children(&ctx, &children);
+
+        if self.options.include_pmi {
+            if let Some(bbox) = Self::compute_bbox(&design.parts) {
+                let size = bbox.size();
+                let size = (size.x as f64, size.y as f64, size.z as f64);
+                self.add_bbox_pmi(&ctx, size, StepLengthUnit::Inch);
+            }
+        }
+
+        self.finish(header)
+    }
+
+    fn generate_assembly(mut self, parts: &[StepSolidPart<'_>]) -> String {
+        let header = self.header("S3M2P B-Rep model", "model.step", "S3M2P");
+        self.data.push("DATA;".to_string());
+
+        let unit = self.options.length_unit;
+        let product_name = self.options.product_name.clone();
+        let ctx = self.create_contexts(&product_name, "assembly definition", unit);
+
+        let mut children: Vec<ChildComponent> = Vec::new();
+        let mut overall = BoundingBox3::EMPTY;
+
+        for (i, part) in parts.iter().enumerate() {
+            let items = self.create_brep_items(&part.name, part.solid);
+            if items.is_empty() {
+                continue;
+            }
+            let all_solid = items.iter().all(|item| item.is_solid);
+
+            let local = self.axis2_placement(&format!("{}_LOCAL", part.name), (0.0, 0.0, 0.0));
+            let mut refs: Vec<String> = items.into_iter().map(|item| item.id).collect();
+            refs.push(local.clone());
+
+            let product = self.create_component_product(&part.name, &ctx);
+            let rep_type = if all_solid {
+                "ADVANCED_BREP_SHAPE_REPRESENTATION"
+            } else {
+                "SHAPE_REPRESENTATION"
+            };
+            let shape_rep = self.add(format!(
+                "{rep_type}('{}',({}),{})",
+                Self::escape(&part.name),
+                refs.join(","),
+                ctx.geom_context
+            ));
+            self.add(format!(
+                "SHAPE_DEFINITION_REPRESENTATION({}, {})",
+                product.product_def_shape, shape_rep
+            ));
+
+            let global = self
+                .placement_from_transform(&format!("{}_ASM_{}", part.name, i + 1), &part.placement);
+
+            for vertex in &part.solid.vertices {
+                overall = overall.expand_by_point(vertex.point.transform(&part.placement));
+            }
+
+            children.push(ChildComponent {
+                product,
+                shape_rep,
+                local_placement: local,
+                global_placement: global,
+            });
+        }
+
+        self.link_children(&ctx, &children);
+
+        if self.options.include_pmi && !children.is_empty() {
+            let size = overall.size();
+            let size = (size.x as f64, size.y as f64, size.z as f64);
+            self.add_bbox_pmi(&ctx, size, unit);
         }
 
-        let items = if child_placements.is_empty() {
+        self.finish(header)
+    }
+
+    fn finish(mut self, header: String) -> String {
+        self.data.push("ENDSEC;".to_string());
+        self.data.push("END-ISO-10303-21;".to_string());
+
+        [header, self.data.join("\n")].join("\n")
+    }
+
+    /// Root shape representation + NAUO wiring shared by crate and generic assemblies.
+    fn link_children(&mut self, ctx: &StepContexts, children: &[ChildComponent]) {
+        let assembly_shape_name = Self::escape(&self.options.product_name);
+        let placements: Vec<&str> = children
+            .iter()
+            .map(|c| c.global_placement.as_str())
+            .collect();
+        let items = if placements.is_empty() {
             "()".to_string()
         } else {
-            format!("({})", child_placements.join(","))
+            format!("({})", placements.join(","))
         };
 
         let root_shape_rep = self.add(format!(
@@ -422,21 +592,21 @@ impl<'a> StepWriter<'a> {
         ));
 
         // Wire each child into root via REPRESENTATION_RELATIONSHIP + transformation + NAUO.
-        for idx in 0..child_products.len() {
-            let prod = &child_products[idx];
-            let child_shape = &child_shape_reps[idx];
-            let child_local = &child_local_placements[idx];
-            let child_global = &child_placements[idx];
+        for (idx, child) in children.iter().enumerate() {
+            let prod = &child.product;
 
             let transform = self.add(format!(
                 "ITEM_DEFINED_TRANSFORMATION('{}_TRANSFORM_{}','',{},{})",
-                prod.product_def, idx + 1, child_local, child_global
+                prod.product_def,
+                idx + 1,
+                child.local_placement,
+                child.global_placement
             ));
             let rel = self.add(format!(
                 "( REPRESENTATION_RELATIONSHIP('{}','',{},{}) REPRESENTATION_RELATIONSHIP_WITH_TRANSFORMATION({}) SHAPE_REPRESENTATION_RELATIONSHIP() )",
                 Self::escape(&prod.product_def),
                 root_shape_rep,
-                child_shape,
+                child.shape_rep,
                 transform
             ));
             let occurrence_name = format!("NAUO_{}", idx + 1);
@@ -448,20 +618,345 @@ impl<'a> StepWriter<'a> {
                 prod.product_def
             ));
             let usage_shape = self.add(format!("PRODUCT_DEFINITION_SHAPE('','',{usage})"));
-            self.add(format!("CONTEXT_DEPENDENT_SHAPE_REPRESENTATION({rel},{usage_shape})"));
+            self.add(format!(
+                "CONTEXT_DEPENDENT_SHAPE_REPRESENTATION({rel},{usage_shape})"
+            ));
         }
+    }
 
-        if self.options.include_pmi {
-            if let Some(bbox) = Self::compute_bbox(&self.design.parts) {
-                self.add_bbox_pmi_in(&ctx, &bbox);
+    /// Rigid placement from the rotation/translation part of an affine transform.
+    fn placement_from_transform(&mut self, label: &str, transform: &Transform3) -> String {
+        let m = transform.0;
+        let origin = Point3::from_vec3(m.w_axis.truncate());
+        let z = Vector3::from_vec3(m.z_axis.truncate()).normalize_or_z();
+        let x = orthogonal_ref(z, Vector3::from_vec3(m.x_axis.truncate()));
+        let label = Self::escape(label);
+        let p = self.point3(origin);
+        let z = self.direction_vec(z);
+        let x = self.direction_vec(x);
+        self.add(format!("AXIS2_PLACEMENT_3D('{label}',{p},{z},{x})"))
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Generic B-Rep emission
+    // ─────────────────────────────────────────────────────────────────────────
+
+    /// Emit every shell of `solid` as a STEP representation item.
+    fn create_brep_items(&mut self, name: &str, solid: &Solid) -> Vec<BrepItem> {
+        let solid_name = Self::escape(name);
+
+        let mut vertices: HashMap<u32, (String, Point3)> = HashMap::new();
+        for vertex in &solid.vertices {
+            let p = self.point3(vertex.point);
+            let v = self.add(format!("VERTEX_POINT('',{p})"));
+            vertices.insert(vertex.id.0, (v, vertex.point));
+        }
+
+        let mut edges: HashMap<u32, String> = HashMap::new();
+        for edge in &solid.edges {
+            let (Some((vs, ps)), Some((ve, pe))) =
+                (vertices.get(&edge.start.0), vertices.get(&edge.end.0))
+            else {
+                continue;
+            };
+            let (vs, ve, ps, pe) = (vs.clone(), ve.clone(), *ps, *pe);
+            let (curve, same_sense) = self.curve(&edge.curve, ps, pe);
+            let sense = if same_sense { ".T." } else { ".F." };
+            let id = self.add(format!("EDGE_CURVE('',{vs},{ve},{curve},{sense})"));
+            edges.insert(edge.id.0, id);
+        }
+
+        let mut faces: HashMap<u32, String> = HashMap::new();
+        for face in &solid.faces {
+            let Some(outer) = self.edge_loop(&face.outer_loop, &edges) else {
+                continue;
+            };
+            let mut bounds = vec![self.add(format!("FACE_OUTER_BOUND('',{outer},.T.)"))];
+            for inner in &face.inner_loops {
+                if let Some(inner) = self.edge_loop(inner, &edges) {
+                    bounds.push(self.add(format!("FACE_BOUND('',{inner},.T.)")));
+                }
             }
+
+            let origin = first_loop_point(solid, &face.outer_loop).unwrap_or(Point3::ORIGIN);
+            let surface = self.surface(&face.surface, origin);
+            let sense = match face.orientation {
+                FaceOrientation::Outward => ".T.",
+                FaceOrientation::Inward => ".F.",
+            };
+            let id = self.add(format!(
+                "ADVANCED_FACE('',({}),{surface},{sense})",
+                bounds.join(",")
+            ));
+            faces.insert(face.id.0, id);
         }
 
-        self.data.push("ENDSEC;".to_string());
-        self.data.push("END-ISO-10303-21;".to_string());
+        // Faces without a shell (hand-built solids) are collected into one open shell.
+        let mut shells: Vec<(Vec<String>, bool)> = solid
+            .shells
+            .iter()
+            .map(|shell| {
+                let ids = shell
+                    .faces
+                    .iter()
+                    .filter_map(|f| faces.get(&f.0).cloned())
+                    .collect();
+                (ids, shell.is_closed)
+            })
+            .collect();
+        if solid.shells.is_empty() && !faces.is_empty() {
+            let ids = solid
+                .faces
+                .iter()
+                .filter_map(|f| faces.get(&f.id.0).cloned())
+                .collect();
+            shells.push((ids, false));
+        }
 
-        [header, self.data.join("\n")].join("\n")
+        let mut items = Vec::new();
+        for (face_ids, is_closed) in shells {
+            if face_ids.is_empty() {
+                continue;
+            }
+            let face_list = face_ids.join(",");
+            let item = if is_closed {
+                let shell = self.add(format!("CLOSED_SHELL('',({face_list}))"));
+                BrepItem {
+                    id: self.add(format!("MANIFOLD_SOLID_BREP('{solid_name}',{shell})")),
+                    is_solid: true,
+                }
+            } else {
+                let shell = self.add(format!("OPEN_SHELL('',({face_list}))"));
+                BrepItem {
+                    id: self.add(format!(
+                        "SHELL_BASED_SURFACE_MODEL('{solid_name}',({shell}))"
+                    )),
+                    is_solid: false,
+                }
+            };
+            items.push(item);
+        }
+        items
     }
+
+    fn edge_loop(&mut self, loop_: &Loop, edges: &HashMap<u32, String>) -> Option<String> {
+        let mut oriented = Vec::with_capacity(loop_.len());
+        for (edge, forward) in loop_.edges.iter().zip(loop_.directions.iter()) {
+            let edge = edges.get(&edge.0)?;
+            let ori = if *forward { ".T." } else { ".F." };
+            oriented.push(self.add(format!("ORIENTED_EDGE('',*,*,{edge},{ori})")));
+        }
+        if oriented.is_empty() {
+            return None;
+        }
+        Some(self.add(format!("EDGE_LOOP('',({}))", oriented.join(","))))
+    }
+
+    /// Emit the geometry of an edge. Returns the curve id and the EDGE_CURVE same_sense flag.
+    fn curve(&mut self, curve: &CurveType, start: Point3, end: Point3) -> (String, bool) {
+        match curve {
+            CurveType::Linear => {
+                let delta = end - start;
+                let dir = delta.normalize().unwrap_or(Vector3::X);
+                let p = self.point3(start);
+                let d = self.direction_vec(dir);
+                let vec = self.add(format!("VECTOR('',{d},{:.6})", delta.length()));
+                (self.add(format!("LINE('',{p},{vec})")), true)
+            }
+            CurveType::Arc {
+                center,
+                radius,
+                normal,
+                start_angle,
+                end_angle,
+            } => {
+                let axis = normal.normalize_or_z();
+                let ref_dir = orthogonal_ref(axis, (start - *center).normalize_or_z());
+                let placement = self.placement(*center, axis, ref_dir);
+                let circle = self.add(format!("CIRCLE('',{placement},{radius:.6})"));
+                (circle, end_angle >= start_angle)
+            }
+            CurveType::Nurbs {
+                control_points,
+                weights,
+                knots,
+                degree,
+            } => {
+                let points: Vec<String> = control_points.iter().map(|p| self.point3(*p)).collect();
+                let (mults, distinct) = knot_multiplicities(knots);
+                let form = format!("{degree},({}),.UNSPECIFIED.,.F.,.F.", points.join(","));
+                let knot_part = format!("({}),({}),.UNSPECIFIED.", mults, distinct);
+                let id = if is_rational(weights) {
+                    self.add(format!(
+                        "(BOUNDED_CURVE() B_SPLINE_CURVE({form}) B_SPLINE_CURVE_WITH_KNOTS({knot_part}) CURVE() GEOMETRIC_REPRESENTATION_ITEM() RATIONAL_B_SPLINE_CURVE(({})) REPRESENTATION_ITEM(''))",
+                        format_reals(weights)
+                    ))
+                } else {
+                    self.add(format!("B_SPLINE_CURVE_WITH_KNOTS('',{form},{knot_part})"))
+                };
+                (id, true)
+            }
+        }
+    }
+
+    /// Emit the geometry of a face. `origin` is a point on the face boundary (used for planes).
+    fn surface(&mut self, surface: &SurfaceType, origin: Point3) -> String {
+        match surface {
+            SurfaceType::Planar { normal } => {
+                let axis = normal.normalize_or_z();
+                let placement = self.placement(origin, axis, orthogonal_ref(axis, Vector3::X));
+                self.add(format!("PLANE('',{placement})"))
+            }
+            SurfaceType::Cylindrical {
+                axis,
+                center,
+                radius,
+            } => {
+                let axis = axis.normalize_or_z();
+                let placement = self.placement(*center, axis, orthogonal_ref(axis, Vector3::X));
+                self.add(format!("CYLINDRICAL_SURFACE('',{placement},{radius:.6})"))
+            }
+            SurfaceType::Spherical { center, radius } => {
+                let placement = self.placement(*center, Vector3::Z, Vector3::X);
+                self.add(format!("SPHERICAL_SURFACE('',{placement},{radius:.6})"))
+            }
+            SurfaceType::Conical {
+                apex,
+                axis,
+                half_angle,
+            } => {
+                // `axis` points from the base toward the apex (see `make_cone`), while STEP
+                // cones open along their placement axis, so the placement axis is reversed.
+                let axis = -axis.normalize_or_z();
+                let placement = self.placement(*apex, axis, orthogonal_ref(axis, Vector3::X));
+                self.add(format!(
+                    "CONICAL_SURFACE('',{placement},{:.6},{half_angle:.6})",
+                    0.0
+                ))
+            }
+            SurfaceType::Toroidal {
+                center,
+                axis,
+                major_radius,
+                minor_radius,
+            } => {
+                let axis = axis.normalize_or_z();
+                let placement = self.placement(*center, axis, orthogonal_ref(axis, Vector3::X));
+                self.add(format!(
+                    "TOROIDAL_SURFACE('',{placement},{major_radius:.6},{minor_radius:.6})"
+                ))
+            }
+            SurfaceType::Nurbs {
+                control_points,
+                weights,
+                u_knots,
+                v_knots,
+                u_degree,
+                v_degree,
+            } => {
+                let rows: Vec<String> = control_points
+                    .iter()
+                    .map(|row| {
+                        let ids: Vec<String> = row.iter().map(|p| self.point3(*p)).collect();
+                        format!("({})", ids.join(","))
+                    })
+                    .collect();
+                let (u_mults, u_distinct) = knot_multiplicities(u_knots);
+                let (v_mults, v_distinct) = knot_multiplicities(v_knots);
+                let form = format!(
+                    "{u_degree},{v_degree},({}),.UNSPECIFIED.,.F.,.F.,.F.",
+                    rows.join(",")
+                );
+                let knot_part =
+                    format!("({u_mults}),({v_mults}),({u_distinct}),({v_distinct}),.UNSPECIFIED.");
+                if weights.iter().any(|row| is_rational(row)) {
+                    let weight_rows: Vec<String> = weights
+                        .iter()
+                        .map(|row| format!("({})", format_reals(row)))
+                        .collect();
+                    self.add(format!(
+                        "(BOUNDED_SURFACE() B_SPLINE_SURFACE({form}) B_SPLINE_SURFACE_WITH_KNOTS({knot_part}) GEOMETRIC_REPRESENTATION_ITEM() RATIONAL_B_SPLINE_SURFACE(({})) REPRESENTATION_ITEM('') SURFACE())",
+                        weight_rows.join(",")
+                    ))
+                } else {
+                    self.add(format!(
+                        "B_SPLINE_SURFACE_WITH_KNOTS('',{form},{knot_part})"
+                    ))
+                }
+            }
+        }
+    }
+}
+
+/// Unit reference direction perpendicular to `axis`, preferring `hint`.
+fn orthogonal_ref(axis: Vector3, hint: Vector3) -> Vector3 {
+    let candidate = hint - axis * hint.dot(axis);
+    if let Some(dir) = candidate.normalize() {
+        return dir;
+    }
+    let fallback = if axis.x.abs() < 0.9 {
+        Vector3::X
+    } else {
+        Vector3::Y
+    };
+    (fallback - axis * fallback.dot(axis)).normalize_or_z()
+}
+
+/// Start point of the first edge of a loop, respecting the edge direction.
+fn first_loop_point(solid: &Solid, loop_: &Loop) -> Option<Point3> {
+    let edge = solid.edge(*loop_.edges.first()?)?;
+    let forward = loop_.directions.first().copied().unwrap_or(true);
+    let vertex = if forward { edge.start } else { edge.end };
+    solid.vertex(vertex).map(|v| v.point)
+}
+
+/// Split a full knot vector into STEP's (multiplicities, distinct knots) lists.
+fn knot_multiplicities(knots: &[f32]) -> (String, String) {
+    let mut mults: Vec<u32> = Vec::new();
+    let mut distinct: Vec<f32> = Vec::new();
+    for &k in knots {
+        match distinct.last() {
+            Some(&last) if (k - last).abs() <= TOLERANCE => {
+                if let Some(m) = mults.last_mut() {
+                    *m += 1;
+                }
+            }
+            _ => {
+                distinct.push(k);
+                mults.push(1);
+            }
+        }
+    }
+    let mults = mults
+        .iter()
+        .map(|m| m.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+    (mults, format_reals(&distinct))
+}
+
+fn is_rational(weights: &[f32]) -> bool {
+    weights.iter().any(|w| (w - 1.0).abs() > TOLERANCE)
+}
+
+fn format_reals(values: &[f32]) -> String {
+    values
+        .iter()
+        .map(|v| format!("{:.6}", v))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+struct BrepItem {
+    id: String,
+    is_solid: bool,
+}
+
+struct ChildComponent {
+    product: ProductDefinition,
+    shape_rep: String,
+    local_placement: String,
+    global_placement: String,
 }
 
 #[derive(Clone)]
@@ -484,6 +979,8 @@ struct ProductDefinition {
 mod tests {
     use super::*;
     use crate::autocrate::{CrateDesign, CrateSpec};
+    use crate::cad::primitives::make_box;
+    use crate::cad::topology::EdgeId;
 
     #[test]
     fn step_export_contains_header_and_some_entities() {
@@ -511,6 +1008,139 @@ mod tests {
 
         assert_eq!(a, b);
     }
-}
 
+    /// Cylinder with true circular edges and a CYLINDRICAL_SURFACE side face.
+    fn analytic_cylinder(radius: f32, height: f32) -> Solid {
+        let mut solid = Solid::new();
+        let v0 = solid.add_vertex(Point3::new(radius, 0.0, 0.0));
+        let v1 = solid.add_vertex(Point3::new(radius, 0.0, height));
+        let circle = |z: f32| CurveType::Arc {
+            center: Point3::new(0.0, 0.0, z),
+            radius,
+            normal: Vector3::Z,
+            start_angle: 0.0,
+            end_angle: std::f32::consts::TAU,
+        };
+        let bottom = solid.add_edge(v0, v0);
+        solid.edges[bottom.0 as usize].curve = circle(0.0);
+        let top = solid.add_edge(v1, v1);
+        solid.edges[top.0 as usize].curve = circle(height);
+        let seam = solid.add_edge(v0, v1);
+
+        let mut add_face = |surface: SurfaceType, edges: &[(EdgeId, bool)]| {
+            let id = solid.add_face(surface);
+            let mut loop_ = Loop::new();
+            for &(e, forward) in edges {
+                loop_.add_edge(e, forward);
+            }
+            solid.face_mut(id).unwrap().outer_loop = loop_;
+            id
+        };
+        let f0 = add_face(
+            SurfaceType::Planar {
+                normal: Vector3::NEG_Z,
+            },
+            &[(bottom, false)],
+        );
+        let f1 = add_face(SurfaceType::Planar { normal: Vector3::Z }, &[(top, true)]);
+        let f2 = add_face(
+            SurfaceType::Cylindrical {
+                axis: Vector3::Z,
+                center: Point3::ORIGIN,
+                radius,
+            },
+            &[(bottom, true), (seam, true), (top, false), (seam, false)],
+        );
+
+        let shell = solid.add_shell();
+        solid.shells[shell.0 as usize].faces = vec![f0, f1, f2];
+        solid.shells[shell.0 as usize].is_closed = true;
+        solid
+    }
+
+    #[test]
+    fn solid_export_writes_box_brep() {
+        let solid = make_box(2.0, 3.0, 4.0);
+        let step = export_step_solid("BOX", &solid, &StepExportOptions::default());
+
+        assert!(step.contains("MANIFOLD_SOLID_BREP('BOX'"));
+        assert!(step.contains("ADVANCED_BREP_SHAPE_REPRESENTATION"));
+        assert_eq!(step.matches("ADVANCED_FACE(").count(), 6);
+        assert_eq!(step.matches("EDGE_CURVE(").count(), 12);
+        assert_eq!(step.matches("PLANE(").count(), 6);
+        assert!(step.contains("overall_height_in"));
+    }
+
+    #[test]
+    fn solid_export_writes_analytic_surfaces_and_curves() {
+        let solid = analytic_cylinder(1.0, 2.0);
+        let options = StepExportOptions {
+            length_unit: StepLengthUnit::Millimetre,
+            ..StepExportOptions::default()
+        };
+        let step = export_step_solid("PIN", &solid, &options);
 
+        assert!(step.contains("CYLINDRICAL_SURFACE("));
+        assert_eq!(step.matches("CIRCLE(").count(), 2);
+        assert!(!step.contains("CONVERSION_BASED_UNIT('INCH'"));
+        assert!(step.contains("overall_height_mm"));
+    }
+
+    #[test]
+    fn solid_export_writes_rational_bspline() {
+        let mut solid = make_box(1.0, 1.0, 1.0);
+        solid.faces[0].surface = SurfaceType::Nurbs {
+            control_points: vec![
+                vec![Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 1.0, 0.0)],
+                vec![Point3::new(1.0, 0.0, 0.0), Point3::new(1.0, 1.0, 0.0)],
+            ],
+            weights: vec![vec![1.0, 1.0], vec![1.0, 2.0]],
+            u_knots: vec![0.0, 0.0, 1.0, 1.0],
+            v_knots: vec![0.0, 0.0, 1.0, 1.0],
+            u_degree: 1,
+            v_degree: 1,
+        };
+        solid.edges[0].curve = CurveType::Nurbs {
+            control_points: vec![Point3::new(-0.5, -0.5, -0.5), Point3::new(0.5, -0.5, -0.5)],
+            weights: vec![1.0, 1.0],
+            knots: vec![0.0, 0.0, 1.0, 1.0],
+            degree: 1,
+        };
+        let step = export_step_solid("PATCH", &solid, &StepExportOptions::default());
+
+        assert!(
+            step.contains("RATIONAL_B_SPLINE_SURFACE(((1.000000,1.000000),(1.000000,2.000000)))")
+        );
+        assert!(step.contains("B_SPLINE_SURFACE_WITH_KNOTS((2,2),(2,2),(0.000000,1.000000)"));
+        assert!(step.contains("B_SPLINE_CURVE_WITH_KNOTS('',1,"));
+    }
+
+    #[test]
+    fn assembly_export_places_each_part() {
+        let a = make_box(1.0, 1.0, 1.0);
+        let b = analytic_cylinder(0.5, 1.0);
+        let parts = [
+            StepSolidPart {
+                name: "BLOCK".to_string(),
+                solid: &a,
+                placement: Transform3::IDENTITY,
+            },
+            StepSolidPart {
+                name: "PIN".to_string(),
+                solid: &b,
+                placement: Transform3::from_translation(Vector3::new(5.0, 0.0, 0.0)),
+            },
+        ];
+        let options = StepExportOptions {
+            product_name: "FIXTURE".to_string(),
+            ..StepExportOptions::default()
+        };
+        let step = export_step_assembly(&parts, &options);
+
+        assert_eq!(step.matches("NEXT_ASSEMBLY_USAGE_OCCURRENCE").count(), 2);
+        assert_eq!(step.matches("MANIFOLD_SOLID_BREP").count(), 2);
+        assert!(step.contains("AXIS2_PLACEMENT_3D('PIN_ASM_2',"));
+        assert!(step.contains("CARTESIAN_POINT('',(5.000000,0.000000,0.000000))"));
+        assert_eq!(step, export_step_assembly(&parts, &options));
+    }
+}
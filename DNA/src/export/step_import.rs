@@ -0,0 +1,1420 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: step_import.rs | DNA/src/export/step_import.rs
+//! PURPOSE: STEP (AP203/AP214/AP242) B-Rep and assembly import into cad::Solid
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//! Translates the entity table produced by `part21.rs` into B-Rep solids.
+//!
+//! Notes:
+//! - Coordinates stay in the file's length unit; `StepImport::length_unit` says which.
+//! - Angles are converted to radians (DEGREE conversion-based units are honoured).
+//! - Product structure (PRODUCT_DEFINITION + NEXT_ASSEMBLY_USAGE_OCCURRENCE) is kept
+//!   as products + occurrences; `placed_solids` flattens it into world placements.
+//!   Cyclic product structures (a product used inside itself) are a translation error.
+//! - Entities the kernel cannot represent are listed in `StepImport::unsupported`
+//!   together with the fallback that was used.
+
+use std::collections::{HashMap, HashSet};
+
+use glam::{Mat4, Vec4};
+
+use super::part21::{
+    parse_part21, StepEntity, StepError, StepFile, StepRecord, StepSchema, StepValue,
+};
+use crate::cad::geometry::{Point3, Transform3, Vector3};
+use crate::cad::topology::{
+    CurveType, EdgeId, FaceId, FaceOrientation, Loop, Solid, SurfaceType, VertexId,
+};
+
+/// Length unit of the imported file
+#[derive(Clone, Debug, PartialEq)]
+pub struct StepUnit {
+    /// Unit name as declared (e.g. "MILLIMETRE", "INCH")
+    pub name: String,
+    /// Size of one file unit in millimetres
+    pub millimetres: f64,
+}
+
+/// Entity that was skipped or approximated during translation
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnsupportedEntity {
+    pub id: u64,
+    pub entity: String,
+    /// What was done instead
+    pub note: String,
+}
+
+/// Product (part or sub-assembly) with the solids of its own shape representation
+#[derive(Clone, Debug)]
+pub struct ImportedProduct {
+    pub name: String,
+    /// Solids in the product's local coordinates
+    pub solids: Vec<Solid>,
+}
+
+/// Placement of a child product inside a parent product
+#[derive(Clone, Debug)]
+pub struct ImportedOccurrence {
+    pub name: String,
+    pub parent: usize,
+    pub child: usize,
+    /// Maps child coordinates into parent coordinates
+    pub placement: Transform3,
+}
+
+/// Solid instance with its accumulated world placement
+#[derive(Clone, Debug)]
+pub struct PlacedSolid {
+    pub product: usize,
+    pub solid: usize,
+    pub placement: Transform3,
+}
+
+/// Result of a STEP import
+#[derive(Clone, Debug)]
+pub struct StepImport {
+    pub schema: StepSchema,
+    pub length_unit: StepUnit,
+    pub products: Vec<ImportedProduct>,
+    pub occurrences: Vec<ImportedOccurrence>,
+    pub unsupported: Vec<UnsupportedEntity>,
+}
+
+impl StepImport {
+    /// Products that are not used as a child of another product
+    pub fn roots(&self) -> Vec<usize> {
+        let children: HashSet<usize> = self.occurrences.iter().map(|o| o.child).collect();
+        (0..self.products.len())
+            .filter(|i| !children.contains(i))
+            .collect()
+    }
+
+    /// Flatten the assembly tree into solid instances with world placements
+    pub fn placed_solids(&self) -> Vec<PlacedSolid> {
+        let mut out = Vec::new();
+        let mut path = Vec::new();
+        for root in self.roots() {
+            self.collect_placed(root, Transform3::IDENTITY, &mut path, &mut out);
+        }
+        out
+    }
+
+    fn collect_placed(
+        &self,
+        product: usize,
+        world: Transform3,
+        path: &mut Vec<usize>,
+        out: &mut Vec<PlacedSolid>,
+    ) {
+        path.push(product);
+        for solid in 0..self.products[product].solids.len() {
+            out.push(PlacedSolid {
+                product,
+                solid,
+                placement: world,
+            });
+        }
+        // `import_step` rejects cycles; skip them here for hand-built imports.
+        for occ in self.occurrences.iter().filter(|o| o.parent == product) {
+            if !path.contains(&occ.child) {
+                self.collect_placed(occ.child, occ.placement.then(world), path, out);
+            }
+        }
+        path.pop();
+    }
+}
+
+/// First occurrence that places a product inside one of its own ancestors
+fn find_cycle(product_count: usize, occurrences: &[ImportedOccurrence]) -> Option<usize> {
+    // 0 = unvisited, 1 = on the current path, 2 = finished
+    fn visit(
+        product: usize,
+        occurrences: &[ImportedOccurrence],
+        state: &mut [u8],
+    ) -> Option<usize> {
+        state[product] = 1;
+        for (i, occ) in occurrences.iter().enumerate() {
+            if occ.parent != product {
+                continue;
+            }
+            match state[occ.child] {
+                1 => return Some(i),
+                0 => {
+                    if let Some(found) = visit(occ.child, occurrences, state) {
+                        return Some(found);
+                    }
+                }
+                _ => {}
+            }
+        }
+        state[product] = 2;
+        None
+    }
+
+    let mut state = vec![0u8; product_count];
+    (0..product_count).find_map(|p| {
+        if state[p] == 0 {
+            visit(p, occurrences, &mut state)
+        } else {
+            None
+        }
+    })
+}
+
+/// Parse and translate a STEP file into B-Rep solids
+pub fn import_step(text: &str) -> Result<StepImport, StepError> {
+    let file = parse_part21(text)?;
+    Translator::new(&file).run()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Translator
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Local coordinate frame from AXIS2_PLACEMENT_3D
+#[derive(Clone, Copy)]
+struct Frame {
+    origin: Point3,
+    z: Vector3,
+    x: Vector3,
+}
+
+impl Frame {
+    fn to_transform(self) -> Transform3 {
+        let y = self.z.cross(self.x);
+        Transform3(Mat4::from_cols(
+            self.x.to_vec3().extend(0.0),
+            y.to_vec3().extend(0.0),
+            self.z.to_vec3().extend(0.0),
+            Vec4::new(self.origin.x, self.origin.y, self.origin.z, 1.0),
+        ))
+    }
+}
+
+struct Translator<'a> {
+    file: &'a StepFile,
+    /// Radians per file plane-angle unit
+    angle_factor: f64,
+    unsupported: Vec<UnsupportedEntity>,
+    reported: HashSet<u64>,
+}
+
+/// Per-solid id maps so shared STEP vertices/edges stay shared in the B-Rep
+#[derive(Default)]
+struct BrepBuilder {
+    solid: Solid,
+    vertices: HashMap<u64, VertexId>,
+    edges: HashMap<u64, EdgeId>,
+}
+
+impl<'a> Translator<'a> {
+    fn new(file: &'a StepFile) -> Self {
+        Self {
+            file,
+            angle_factor: 1.0,
+            unsupported: Vec::new(),
+            reported: HashSet::new(),
+        }
+    }
+
+    fn report(&mut self, id: u64, note: &str) {
+        if self.reported.insert(id) {
+            let entity = self
+                .file
+                .get(id)
+                .map(|e| e.type_name())
+                .unwrap_or_else(|| "<missing>".to_string());
+            self.unsupported.push(UnsupportedEntity {
+                id,
+                entity,
+                note: note.to_string(),
+            });
+        }
+    }
+
+    fn run(mut self) -> Result<StepImport, StepError> {
+        let length_unit = self.units();
+
+        let pd_ids = self.file.ids_of("PRODUCT_DEFINITION");
+        let mut product_index: HashMap<u64, usize> = HashMap::new();
+        let mut products = Vec::new();
+        for id in pd_ids {
+            product_index.insert(id, products.len());
+            products.push(ImportedProduct {
+                name: self.product_name(id),
+                solids: Vec::new(),
+            });
+        }
+
+        // Representation → product, via SHAPE_DEFINITION_REPRESENTATION.
+        let mut rep_product: HashMap<u64, usize> = HashMap::new();
+        for id in self.file.ids_of("SHAPE_DEFINITION_REPRESENTATION") {
+            let rec = self.record(id, "SHAPE_DEFINITION_REPRESENTATION")?;
+            let (Some(pds), Some(rep)) = (ref_at(rec, 0), ref_at(rec, 1)) else {
+                continue;
+            };
+            let definition = self
+                .simple(pds)
+                .filter(|r| r.name == "PRODUCT_DEFINITION_SHAPE")
+                .and_then(|r| ref_at(r, 2));
+            if let Some(product) = definition.and_then(|d| product_index.get(&d)) {
+                rep_product.insert(rep, *product);
+            }
+        }
+
+        // Plain SHAPE_REPRESENTATION_RELATIONSHIPs join a placement-only shape
+        // representation to the B-Rep representation carrying the geometry.
+        for _ in 0..2 {
+            for id in self.file.ids_of("SHAPE_REPRESENTATION_RELATIONSHIP") {
+                let Some(StepEntity::Simple(rec)) = self.file.get(id) else {
+                    continue;
+                };
+                let (Some(a), Some(b)) = (ref_at(rec, 2), ref_at(rec, 3)) else {
+                    continue;
+                };
+                match (rep_product.get(&a).copied(), rep_product.get(&b).copied()) {
+                    (Some(p), None) => {
+                        rep_product.insert(b, p);
+                    }
+                    (None, Some(p)) => {
+                        rep_product.insert(a, p);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let mut reps: Vec<(u64, usize)> = rep_product.into_iter().collect();
+        reps.sort();
+        let mut done_items: HashSet<u64> = HashSet::new();
+        for (rep, product) in reps {
+            for item in self.rep_items(rep) {
+                if !done_items.insert(item) {
+                    continue;
+                }
+                if let Some(solid) = self.representation_item(item)? {
+                    products[product].solids.push(solid);
+                }
+            }
+        }
+
+        // Files without product structure: gather every B-Rep into one product.
+        if products.is_empty() {
+            let mut solids = Vec::new();
+            let mut items: Vec<u64> = Vec::new();
+            for name in [
+                "MANIFOLD_SOLID_BREP",
+                "BREP_WITH_VOIDS",
+                "SHELL_BASED_SURFACE_MODEL",
+            ] {
+                items.extend(self.file.ids_of(name));
+            }
+            items.sort();
+            for item in items {
+                if let Some(solid) = self.representation_item(item)? {
+                    solids.push(solid);
+                }
+            }
+            if !solids.is_empty() {
+                products.push(ImportedProduct {
+                    name: "STEP".to_string(),
+                    solids,
+                });
+            }
+        }
+
+        let occurrences = self.occurrences(&product_index)?;
+        if let Some(i) = find_cycle(products.len(), &occurrences) {
+            let occ = &occurrences[i];
+            return Err(StepError::Translation(format!(
+                "cyclic product structure: occurrence '{}' places '{}' inside its own sub-assembly '{}'",
+                occ.name, products[occ.child].name, products[occ.parent].name
+            )));
+        }
+
+        Ok(StepImport {
+            schema: self.file.schema.clone(),
+            length_unit,
+            products,
+            occurrences,
+            unsupported: self.unsupported,
+        })
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Entity access
+    // ─────────────────────────────────────────────────────────────────────────
+
+    fn record(&self, id: u64, name: &str) -> Result<&'a StepRecord, StepError> {
+        self.file
+            .get(id)
+            .and_then(|e| e.record(name))
+            .ok_or_else(|| StepError::Translation(format!("#{} is not {}", id, name)))
+    }
+
+    /// The single record of a simple instance
+    fn simple(&self, id: u64) -> Option<&'a StepRecord> {
+        match self.file.get(id)? {
+            StepEntity::Simple(r) => Some(r),
+            StepEntity::Complex(_) => None,
+        }
+    }
+
+    fn point(&self, id: u64) -> Option<Point3> {
+        let coords = reals(self.file.get(id)?.record("CARTESIAN_POINT")?.param(1)?)?;
+        Some(Point3::new(
+            *coords.first()? as f32,
+            coords.get(1).copied().unwrap_or(0.0) as f32,
+            coords.get(2).copied().unwrap_or(0.0) as f32,
+        ))
+    }
+
+    fn direction(&self, id: u64) -> Option<Vector3> {
+        let ratios = reals(self.file.get(id)?.record("DIRECTION")?.param(1)?)?;
+        Vector3::new(
+            *ratios.first()? as f32,
+            ratios.get(1).copied().unwrap_or(0.0) as f32,
+            ratios.get(2).copied().unwrap_or(0.0) as f32,
+        )
+        .normalize()
+    }
+
+    fn frame(&self, id: u64) -> Option<Frame> {
+        let rec = self.file.get(id)?.record("AXIS2_PLACEMENT_3D")?;
+        let origin = self.point(ref_at(rec, 1)?)?;
+        let z = ref_at(rec, 2)
+            .and_then(|d| self.direction(d))
+            .unwrap_or(Vector3::Z);
+        let hint = ref_at(rec, 3)
+            .and_then(|d| self.direction(d))
+            .unwrap_or(Vector3::X);
+        let x = (hint - z * hint.dot(z))
+            .normalize()
+            .or_else(|| z.cross(Vector3::Y).normalize())
+            .unwrap_or(Vector3::X);
+        Some(Frame { origin, z, x })
+    }
+
+    fn product_name(&self, pd: u64) -> String {
+        let product = self
+            .simple(pd)
+            .and_then(|r| ref_at(r, 2))
+            .and_then(|f| self.simple(f))
+            .and_then(|r| ref_at(r, 2))
+            .and_then(|p| self.simple(p));
+        product
+            .and_then(|r| {
+                r.param(1)
+                    .and_then(|v| v.as_str())
+                    .filter(|s| !s.is_empty())
+                    .or_else(|| r.param(0).and_then(|v| v.as_str()))
+            })
+            .unwrap_or("")
+            .to_string()
+    }
+
+    fn rep_items(&self, rep: u64) -> Vec<u64> {
+        let record = match self.file.get(rep) {
+            Some(StepEntity::Simple(r)) => Some(r),
+            Some(StepEntity::Complex(records)) => {
+                records.iter().find(|r| r.name.ends_with("REPRESENTATION"))
+            }
+            None => None,
+        };
+        record
+            .and_then(|r| r.param(1))
+            .map(refs)
+            .unwrap_or_default()
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Units
+    // ─────────────────────────────────────────────────────────────────────────
+
+    fn units(&mut self) -> StepUnit {
+        let mut length = StepUnit {
+            name: "MILLIMETRE".to_string(),
+            millimetres: 1.0,
+        };
+        let Some(ctx) = self
+            .file
+            .ids_of("GLOBAL_UNIT_ASSIGNED_CONTEXT")
+            .first()
+            .copied()
+        else {
+            return length;
+        };
+        let units = self
+            .file
+            .get(ctx)
+            .and_then(|e| e.record("GLOBAL_UNIT_ASSIGNED_CONTEXT"))
+            .and_then(|r| r.param(0))
+            .map(refs)
+            .unwrap_or_default();
+        for unit in units {
+            let Some(entity) = self.file.get(unit) else {
+                continue;
+            };
+            if entity.is("LENGTH_UNIT") {
+                if let Some((name, mm)) = self.unit_factor(unit, 0) {
+                    length = StepUnit {
+                        name,
+                        millimetres: mm,
+                    };
+                }
+            } else if entity.is("PLANE_ANGLE_UNIT") {
+                if let Some((_, rad)) = self.unit_factor(unit, 0) {
+                    self.angle_factor = rad;
+                }
+            }
+        }
+        length
+    }
+
+    /// (name, factor) of a unit: millimetres for lengths, radians for angles
+    fn unit_factor(&self, id: u64, depth: usize) -> Option<(String, f64)> {
+        let entity = self.file.get(id)?;
+        if depth > 8 {
+            return None;
+        }
+        if let Some(si) = entity.record("SI_UNIT") {
+            // Complex form: SI_UNIT(prefix, name); simple form prepends dimensions.
+            let n = si.params.len();
+            let prefix = si.params.get(n.wrapping_sub(2)).and_then(|v| v.as_enum());
+            let name = si.params.last()?.as_enum()?;
+            let scale = match prefix {
+                Some("MICRO") => 1e-6,
+                Some("MILLI") => 1e-3,
+                Some("CENTI") => 1e-2,
+                Some("DECI") => 1e-1,
+                Some("KILO") => 1e3,
+                _ => 1.0,
+            };
+            return match name {
+                "METRE" => Some((format!("{}METRE", prefix.unwrap_or("")), scale * 1000.0)),
+                "RADIAN" => Some(("RADIAN".to_string(), scale)),
+                _ => None,
+            };
+        }
+        if let Some(conv) = entity.record("CONVERSION_BASED_UNIT") {
+            let n = conv.params.len();
+            let name = conv
+                .params
+                .get(n.wrapping_sub(2))?
+                .as_str()?
+                .to_ascii_uppercase();
+            let measure = self.file.get(conv.params.last()?.as_ref_id()?)?;
+            let rec = match measure {
+                StepEntity::Simple(r) => r,
+                StepEntity::Complex(records) => records
+                    .iter()
+                    .find(|r| r.name == "MEASURE_WITH_UNIT")
+                    .or_else(|| records.first())?,
+            };
+            let value = rec.param(0)?.as_f64()?;
+            let (_, base) = self.unit_factor(rec.param(1)?.as_ref_id()?, depth + 1)?;
+            return Some((name, value * base));
+        }
+        None
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Assembly structure
+    // ─────────────────────────────────────────────────────────────────────────
+
+    fn occurrences(
+        &mut self,
+        product_index: &HashMap<u64, usize>,
+    ) -> Result<Vec<ImportedOccurrence>, StepError> {
+        let mut placements: HashMap<u64, Transform3> = HashMap::new();
+        for id in self.file.ids_of("CONTEXT_DEPENDENT_SHAPE_REPRESENTATION") {
+            let rec = self.record(id, "CONTEXT_DEPENDENT_SHAPE_REPRESENTATION")?;
+            let (Some(rel), Some(pds)) = (ref_at(rec, 0), ref_at(rec, 1)) else {
+                continue;
+            };
+            let Some(nauo) = self.simple(pds).and_then(|r| ref_at(r, 2)) else {
+                continue;
+            };
+            let transform = self
+                .file
+                .get(rel)
+                .and_then(|e| e.record("REPRESENTATION_RELATIONSHIP_WITH_TRANSFORMATION"))
+                .and_then(|r| ref_at(r, 0));
+            let placement = match transform {
+                Some(t) => self.item_transform(t),
+                None => None,
+            };
+            match placement {
+                Some(p) => {
+                    placements.insert(nauo, p);
+                }
+                None => self.report(rel, "placement not understood; identity used"),
+            }
+        }
+
+        let mut out = Vec::new();
+        for id in self.file.ids_of("NEXT_ASSEMBLY_USAGE_OCCURRENCE") {
+            let rec = self.record(id, "NEXT_ASSEMBLY_USAGE_OCCURRENCE")?;
+            let parent = ref_at(rec, 3).and_then(|p| product_index.get(&p));
+            let child = ref_at(rec, 4).and_then(|p| product_index.get(&p));
+            let (Some(&parent), Some(&child)) = (parent, child) else {
+                self.report(id, "occurrence references unknown product; skipped");
+                continue;
+            };
+            let name = rec
+                .param(1)
+                .and_then(|v| v.as_str())
+                .or_else(|| rec.param(0).and_then(|v| v.as_str()))
+                .unwrap_or("")
+                .to_string();
+            out.push(ImportedOccurrence {
+                name,
+                parent,
+                child,
+                placement: placements.get(&id).copied().unwrap_or(Transform3::IDENTITY),
+            });
+        }
+        Ok(out)
+    }
+
+    /// ITEM_DEFINED_TRANSFORMATION(name, desc, from, to): maps `from` onto `to`
+    fn item_transform(&self, id: u64) -> Option<Transform3> {
+        let rec = self.file.get(id)?.record("ITEM_DEFINED_TRANSFORMATION")?;
+        let from = self.frame(ref_at(rec, 2)?)?.to_transform();
+        let to = self.frame(ref_at(rec, 3)?)?.to_transform();
+        Some(from.inverse().then(to))
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Topology
+    // ─────────────────────────────────────────────────────────────────────────
+
+    fn representation_item(&mut self, id: u64) -> Result<Option<Solid>, StepError> {
+        let Some(entity) = self.file.get(id) else {
+            return Err(StepError::Translation(format!(
+                "dangling reference #{}",
+                id
+            )));
+        };
+        let mut b = BrepBuilder::default();
+        if let Some(rec) = entity.record("MANIFOLD_SOLID_BREP") {
+            if let Some(outer) = ref_at(rec, 1) {
+                self.shell(&mut b, outer, true, false)?;
+            }
+            if let Some(voids) = entity.record("BREP_WITH_VOIDS").and_then(|r| r.param(2)) {
+                for v in refs(voids) {
+                    self.shell(&mut b, v, true, false)?;
+                }
+            }
+        } else if let Some(rec) = entity.record("BREP_WITH_VOIDS") {
+            if let Some(outer) = ref_at(rec, 1) {
+                self.shell(&mut b, outer, true, false)?;
+            }
+            for v in rec.param(2).map(refs).unwrap_or_default() {
+                self.shell(&mut b, v, true, false)?;
+            }
+        } else if let Some(rec) = entity.record("SHELL_BASED_SURFACE_MODEL") {
+            for s in rec.param(1).map(refs).unwrap_or_default() {
+                let closed = self.file.get(s).is_some_and(|e| e.is("CLOSED_SHELL"));
+                self.shell(&mut b, s, closed, false)?;
+            }
+        } else {
+            if !entity.is("AXIS2_PLACEMENT_3D") {
+                self.report(id, "representation item not translated");
+            }
+            return Ok(None);
+        }
+        Ok(Some(b.solid))
+    }
+
+    fn shell(
+        &mut self,
+        b: &mut BrepBuilder,
+        id: u64,
+        closed: bool,
+        flip: bool,
+    ) -> Result<(), StepError> {
+        let Some(entity) = self.file.get(id) else {
+            return Err(StepError::Translation(format!(
+                "dangling reference #{}",
+                id
+            )));
+        };
+        if let Some(rec) = entity.record("ORIENTED_CLOSED_SHELL") {
+            let reversed = rec.param(3).and_then(|v| v.as_bool()) == Some(false);
+            if let Some(inner) = ref_at(rec, 2) {
+                return self.shell(b, inner, closed, flip ^ reversed);
+            }
+            return Ok(());
+        }
+        let Some(rec) = entity
+            .record("CLOSED_SHELL")
+            .or_else(|| entity.record("OPEN_SHELL"))
+        else {
+            self.report(id, "shell type not translated");
+            return Ok(());
+        };
+
+        let shell = b.solid.add_shell();
+        let mut faces = Vec::new();
+        for f in rec.param(1).map(refs).unwrap_or_default() {
+            if let Some(face) = self.face(b, f, flip)? {
+                faces.push(face);
+            }
+        }
+        for face in &faces {
+            if let Some(f) = b.solid.face_mut(*face) {
+                f.shell = Some(shell);
+            }
+        }
+        if let Some(s) = b.solid.shells.iter_mut().find(|s| s.id == shell) {
+            s.faces = faces;
+            s.is_closed = closed;
+        }
+        Ok(())
+    }
+
+    fn face(
+        &mut self,
+        b: &mut BrepBuilder,
+        id: u64,
+        flip: bool,
+    ) -> Result<Option<FaceId>, StepError> {
+        let Some(entity) = self.file.get(id) else {
+            return Err(StepError::Translation(format!(
+                "dangling reference #{}",
+                id
+            )));
+        };
+        let Some(rec) = entity
+            .record("ADVANCED_FACE")
+            .or_else(|| entity.record("FACE_SURFACE"))
+        else {
+            self.report(id, "face type not translated; face dropped");
+            return Ok(None);
+        };
+        let same_sense = rec.param(3).and_then(|v| v.as_bool()).unwrap_or(true);
+
+        let mut outer: Option<Loop> = None;
+        let mut inner: Vec<Loop> = Vec::new();
+        for bound in rec.param(1).map(refs).unwrap_or_default() {
+            let Some(bound_entity) = self.file.get(bound) else {
+                continue;
+            };
+            let is_outer = bound_entity.is("FACE_OUTER_BOUND");
+            let Some(bound_rec) = bound_entity
+                .record("FACE_OUTER_BOUND")
+                .or_else(|| bound_entity.record("FACE_BOUND"))
+            else {
+                continue;
+            };
+            let orientation = bound_rec.param(2).and_then(|v| v.as_bool()).unwrap_or(true);
+            let Some(loop_id) = ref_at(bound_rec, 1) else {
+                continue;
+            };
+            let Some(loop_) = self.edge_loop(b, loop_id, orientation)? else {
+                continue;
+            };
+            if is_outer && outer.is_none() {
+                outer = Some(loop_);
+            } else {
+                inner.push(loop_);
+            }
+        }
+        // Without an explicit FACE_OUTER_BOUND the first bound is the outer one.
+        if outer.is_none() && !inner.is_empty() {
+            outer = Some(inner.remove(0));
+        }
+        let outer = outer.unwrap_or_default();
+
+        let surface_id = ref_at(rec, 2).unwrap_or(0);
+        let surface = match self.surface(surface_id) {
+            Some(s) => s,
+            None => {
+                self.report(
+                    surface_id,
+                    "surface not translated; planar approximation used",
+                );
+                SurfaceType::Planar {
+                    normal: loop_normal(&b.solid, &outer),
+                }
+            }
+        };
+
+        let face_id = b.solid.add_face(surface);
+        let all_edges: Vec<EdgeId> = outer
+            .edges
+            .iter()
+            .chain(inner.iter().flat_map(|l| l.edges.iter()))
+            .copied()
+            .collect();
+        if let Some(face) = b.solid.face_mut(face_id) {
+            face.outer_loop = outer;
+            face.inner_loops = inner;
+            face.orientation = if same_sense ^ flip {
+                FaceOrientation::Outward
+            } else {
+                FaceOrientation::Inward
+            };
+        }
+        for e in all_edges {
+            if let Some(edge) = b.solid.edges.iter_mut().find(|x| x.id == e) {
+                if !edge.faces.contains(&face_id) {
+                    edge.faces.push(face_id);
+                }
+            }
+        }
+        Ok(Some(face_id))
+    }
+
+    fn edge_loop(
+        &mut self,
+        b: &mut BrepBuilder,
+        id: u64,
+        orientation: bool,
+    ) -> Result<Option<Loop>, StepError> {
+        let Some(entity) = self.file.get(id) else {
+            return Err(StepError::Translation(format!(
+                "dangling reference #{}",
+                id
+            )));
+        };
+        if let Some(rec) = entity.record("VERTEX_LOOP") {
+            // Degenerate boundary (cone apex, sphere pole): keep the vertex only.
+            if let Some(v) = ref_at(rec, 1) {
+                self.vertex(b, v);
+            }
+            return Ok(None);
+        }
+        let Some(rec) = entity.record("EDGE_LOOP") else {
+            self.report(id, "loop type not translated; bound dropped");
+            return Ok(None);
+        };
+
+        let mut pairs = Vec::new();
+        for oe in rec.param(1).map(refs).unwrap_or_default() {
+            let Some(oe_rec) = self.file.get(oe).and_then(|e| e.record("ORIENTED_EDGE")) else {
+                self.report(oe, "loop member is not an ORIENTED_EDGE; skipped");
+                continue;
+            };
+            let forward = oe_rec.param(4).and_then(|v| v.as_bool()).unwrap_or(true);
+            let Some(edge_id) = ref_at(oe_rec, 3) else {
+                continue;
+            };
+            if let Some(edge) = self.edge(b, edge_id)? {
+                pairs.push((edge, forward));
+            }
+        }
+        if !orientation {
+            pairs.reverse();
+            for pair in &mut pairs {
+                pair.1 = !pair.1;
+            }
+        }
+        let mut loop_ = Loop::new();
+        for (edge, forward) in pairs {
+            loop_.add_edge(edge, forward);
+        }
+        Ok(Some(loop_))
+    }
+
+    fn vertex(&mut self, b: &mut BrepBuilder, id: u64) -> Option<VertexId> {
+        if let Some(v) = b.vertices.get(&id) {
+            return Some(*v);
+        }
+        let point = self
+            .file
+            .get(id)
+            .and_then(|e| e.record("VERTEX_POINT"))
+            .and_then(|r| ref_at(r, 1))
+            .and_then(|p| self.point(p));
+        let Some(point) = point else {
+            self.report(id, "vertex without a cartesian point; skipped");
+            return None;
+        };
+        let v = b.solid.add_vertex(point);
+        b.vertices.insert(id, v);
+        Some(v)
+    }
+
+    fn edge(&mut self, b: &mut BrepBuilder, id: u64) -> Result<Option<EdgeId>, StepError> {
+        if let Some(e) = b.edges.get(&id) {
+            return Ok(Some(*e));
+        }
+        let rec = self.record(id, "EDGE_CURVE")?;
+        let (Some(v1), Some(v2)) = (ref_at(rec, 1), ref_at(rec, 2)) else {
+            return Ok(None);
+        };
+        let (Some(start), Some(end)) = (self.vertex(b, v1), self.vertex(b, v2)) else {
+            return Ok(None);
+        };
+        let same_sense = rec.param(4).and_then(|v| v.as_bool()).unwrap_or(true);
+        let p1 = b.solid.vertex(start).map(|v| v.point).unwrap_or_default();
+        let p2 = b.solid.vertex(end).map(|v| v.point).unwrap_or_default();
+        let curve_id = ref_at(rec, 3).unwrap_or(0);
+        let curve = match self.curve(curve_id, p1, p2, same_sense, 0) {
+            Some(c) => c,
+            None => {
+                self.report(curve_id, "curve not translated; straight edge used");
+                CurveType::Linear
+            }
+        };
+
+        let edge = b.solid.add_edge(start, end);
+        if let Some(e) = b.solid.edges.iter_mut().find(|e| e.id == edge) {
+            e.curve = curve;
+        }
+        b.edges.insert(id, edge);
+        Ok(Some(edge))
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // Geometry
+    // ─────────────────────────────────────────────────────────────────────────
+
+    fn curve(
+        &mut self,
+        id: u64,
+        start: Point3,
+        end: Point3,
+        same_sense: bool,
+        depth: usize,
+    ) -> Option<CurveType> {
+        let entity = self.file.get(id)?;
+        if depth > 8 {
+            return None;
+        }
+        if entity.is("LINE") {
+            return Some(CurveType::Linear);
+        }
+        if let Some(rec) = entity.record("CIRCLE") {
+            let frame = self.frame(ref_at(rec, 1)?)?;
+            let radius = rec.param(2)?.as_f64()? as f32;
+            let normal = if same_sense { frame.z } else { -frame.z };
+            let (start_angle, end_angle) = arc_angles(frame.origin, normal, start, end);
+            return Some(CurveType::Arc {
+                center: frame.origin,
+                radius,
+                normal,
+                start_angle,
+                end_angle,
+            });
+        }
+        // Edge geometry wrapped in a surface/seam/trimmed curve: use the 3D basis.
+        for wrapper in [
+            "SURFACE_CURVE",
+            "SEAM_CURVE",
+            "INTERSECTION_CURVE",
+            "TRIMMED_CURVE",
+        ] {
+            if let Some(rec) = entity.record(wrapper) {
+                return self.curve(ref_at(rec, 1)?, start, end, same_sense, depth + 1);
+            }
+        }
+        if entity.is("B_SPLINE_CURVE_WITH_KNOTS") {
+            let mut curve = self.bspline_curve(entity)?;
+            if !same_sense {
+                reverse_nurbs_curve(&mut curve);
+            }
+            return Some(curve);
+        }
+        None
+    }
+
+    fn bspline_curve(&self, entity: &StepEntity) -> Option<CurveType> {
+        // Simple instances carry every inherited attribute after the name;
+        // complex instances split them across B_SPLINE_CURVE / _WITH_KNOTS records.
+        let (degree, points, mults, knots, weights) = match entity {
+            StepEntity::Simple(r) => (r.param(1)?, r.param(2)?, r.param(6)?, r.param(7)?, None),
+            StepEntity::Complex(_) => {
+                let base = entity.record("B_SPLINE_CURVE")?;
+                let k = entity.record("B_SPLINE_CURVE_WITH_KNOTS")?;
+                let w = entity
+                    .record("RATIONAL_B_SPLINE_CURVE")
+                    .and_then(|r| r.param(0));
+                (base.param(0)?, base.param(1)?, k.param(0)?, k.param(1)?, w)
+            }
+        };
+        let control_points: Vec<Point3> = refs(points)
+            .into_iter()
+            .map(|p| self.point(p))
+            .collect::<Option<_>>()?;
+        let weights = match weights {
+            Some(w) => reals(w)?.into_iter().map(|w| w as f32).collect(),
+            None => vec![1.0; control_points.len()],
+        };
+        Some(CurveType::Nurbs {
+            knots: expand_knots(mults, knots)?,
+            weights,
+            degree: degree.as_f64()? as u32,
+            control_points,
+        })
+    }
+
+    fn surface(&mut self, id: u64) -> Option<SurfaceType> {
+        let entity = self.file.get(id)?;
+        if let Some(rec) = entity.record("PLANE") {
+            let frame = self.frame(ref_at(rec, 1)?)?;
+            return Some(SurfaceType::Planar { normal: frame.z });
+        }
+        if let Some(rec) = entity.record("CYLINDRICAL_SURFACE") {
+            let frame = self.frame(ref_at(rec, 1)?)?;
+            return Some(SurfaceType::Cylindrical {
+                axis: frame.z,
+                center: frame.origin,
+                radius: rec.param(2)?.as_f64()? as f32,
+            });
+        }
+        if let Some(rec) = entity.record("CONICAL_SURFACE") {
+            let frame = self.frame(ref_at(rec, 1)?)?;
+            let radius = rec.param(2)?.as_f64()?;
+            let semi_angle = rec.param(3)?.as_f64()? * self.angle_factor;
+            // STEP places the cone where its radius equals `radius` and opens it along
+            // +axis; the kernel stores the apex and an axis pointing toward the apex.
+            let offset = if semi_angle.tan().abs() > 1e-12 {
+                radius / semi_angle.tan()
+            } else {
+                0.0
+            };
+            return Some(SurfaceType::Conical {
+                apex: frame.origin + frame.z * (-offset as f32),
+                axis: -frame.z,
+                half_angle: semi_angle as f32,
+            });
+        }
+        if let Some(rec) = entity.record("SPHERICAL_SURFACE") {
+            let frame = self.frame(ref_at(rec, 1)?)?;
+            return Some(SurfaceType::Spherical {
+                center: frame.origin,
+                radius: rec.param(2)?.as_f64()? as f32,
+            });
+        }
+        if let Some(rec) = entity
+            .record("TOROIDAL_SURFACE")
+            .or_else(|| entity.record("DEGENERATE_TOROIDAL_SURFACE"))
+        {
+            let frame = self.frame(ref_at(rec, 1)?)?;
+            return Some(SurfaceType::Toroidal {
+                center: frame.origin,
+                axis: frame.z,
+                major_radius: rec.param(2)?.as_f64()? as f32,
+                minor_radius: rec.param(3)?.as_f64()? as f32,
+            });
+        }
+        if entity.is("B_SPLINE_SURFACE_WITH_KNOTS") {
+            return self.bspline_surface(entity);
+        }
+        None
+    }
+
+    fn bspline_surface(&self, entity: &StepEntity) -> Option<SurfaceType> {
+        let (u_degree, v_degree, net, u_mults, v_mults, u_knots, v_knots, weights) = match entity {
+            StepEntity::Simple(r) => (
+                r.param(1)?,
+                r.param(2)?,
+                r.param(3)?,
+                r.param(8)?,
+                r.param(9)?,
+                r.param(10)?,
+                r.param(11)?,
+                None,
+            ),
+            StepEntity::Complex(_) => {
+                let base = entity.record("B_SPLINE_SURFACE")?;
+                let k = entity.record("B_SPLINE_SURFACE_WITH_KNOTS")?;
+                let w = entity
+                    .record("RATIONAL_B_SPLINE_SURFACE")
+                    .and_then(|r| r.param(0));
+                (
+                    base.param(0)?,
+                    base.param(1)?,
+                    base.param(2)?,
+                    k.param(0)?,
+                    k.param(1)?,
+                    k.param(2)?,
+                    k.param(3)?,
+                    w,
+                )
+            }
+        };
+        let control_points: Vec<Vec<Point3>> = net
+            .as_list()?
+            .iter()
+            .map(|row| refs(row).into_iter().map(|p| self.point(p)).collect())
+            .collect::<Option<_>>()?;
+        let weights = match weights {
+            Some(w) => w
+                .as_list()?
+                .iter()
+                .map(|row| reals(row).map(|r| r.into_iter().map(|w| w as f32).collect()))
+                .collect::<Option<_>>()?,
+            None => control_points
+                .iter()
+                .map(|row| vec![1.0; row.len()])
+                .collect(),
+        };
+        Some(SurfaceType::Nurbs {
+            control_points,
+            weights,
+            u_knots: expand_knots(u_mults, u_knots)?,
+            v_knots: expand_knots(v_mults, v_knots)?,
+            u_degree: u_degree.as_f64()? as u32,
+            v_degree: v_degree.as_f64()? as u32,
+        })
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Helpers
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn ref_at(rec: &StepRecord, index: usize) -> Option<u64> {
+    rec.param(index)?.as_ref_id()
+}
+
+fn refs(value: &StepValue) -> Vec<u64> {
+    value
+        .as_list()
+        .map(|l| l.iter().filter_map(|v| v.as_ref_id()).collect())
+        .unwrap_or_default()
+}
+
+fn reals(value: &StepValue) -> Option<Vec<f64>> {
+    value.as_list()?.iter().map(|v| v.as_f64()).collect()
+}
+
+/// Expand STEP (multiplicities, distinct knots) into a full knot vector
+fn expand_knots(mults: &StepValue, knots: &StepValue) -> Option<Vec<f32>> {
+    let mults = reals(mults)?;
+    let knots = reals(knots)?;
+    let mut out = Vec::new();
+    for (m, k) in mults.iter().zip(knots.iter()) {
+        for _ in 0..(*m as usize) {
+            out.push(*k as f32);
+        }
+    }
+    Some(out)
+}
+
+fn reverse_nurbs_curve(curve: &mut CurveType) {
+    if let CurveType::Nurbs {
+        control_points,
+        weights,
+        knots,
+        ..
+    } = curve
+    {
+        control_points.reverse();
+        weights.reverse();
+        if let (Some(&a), Some(&b)) = (knots.first(), knots.last()) {
+            knots.reverse();
+            for k in knots.iter_mut() {
+                *k = a + b - *k;
+            }
+        }
+    }
+}
+
+/// Start/end angles of an arc in the frame used by `CurveType::point_at`
+fn arc_angles(center: Point3, normal: Vector3, start: Point3, end: Point3) -> (f32, f32) {
+    let u = if normal.z.abs() > 0.9 {
+        Vector3::X
+    } else {
+        Vector3::Z.cross(normal).normalize_or_z()
+    };
+    let v = normal.cross(u);
+    let angle = |p: Point3| {
+        let d = p - center;
+        d.dot(v).atan2(d.dot(u))
+    };
+    let a0 = angle(start);
+    let mut a1 = angle(end);
+    while a1 <= a0 + 1e-6 {
+        a1 += std::f32::consts::TAU;
+    }
+    (a0, a1)
+}
+
+/// Newell normal of a loop's vertices (fallback for untranslated surfaces)
+fn loop_normal(solid: &Solid, loop_: &Loop) -> Vector3 {
+    let points: Vec<Point3> = loop_
+        .edges
+        .iter()
+        .zip(loop_.directions.iter())
+        .filter_map(|(e, forward)| {
+            let edge = solid.edge(*e)?;
+            let v = if *forward { edge.start } else { edge.end };
+            solid.vertex(v).map(|v| v.point)
+        })
+        .collect();
+    let mut n = Vector3::ZERO;
+    for (i, p) in points.iter().enumerate() {
+        let q = points[(i + 1) % points.len()];
+        n = n + Vector3::new(
+            (p.y - q.y) * (p.z + q.z),
+            (p.z - q.z) * (p.x + q.x),
+            (p.x - q.x) * (p.y + q.y),
+        );
+    }
+    n.normalize_or_z()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cad::primitives::make_box;
+    use crate::export::step::{
+        export_step_assembly, export_step_solid, StepExportOptions, StepLengthUnit, StepSolidPart,
+    };
+
+    #[test]
+    fn test_round_trip_box() {
+        let solid = make_box(2.0, 3.0, 4.0);
+        let step = export_step_solid("BOX", &solid, &StepExportOptions::default());
+        let import = import_step(&step).unwrap();
+
+        assert_eq!(import.schema, StepSchema::Ap242);
+        assert_eq!(import.length_unit.name, "INCH");
+        assert!((import.length_unit.millimetres - 25.4).abs() < 1e-9);
+        assert!(import.unsupported.is_empty());
+
+        let placed = import.placed_solids();
+        assert_eq!(placed.len(), 1);
+        let part = &import.products[placed[0].product];
+        assert_eq!(part.name, "BOX");
+        let imported = &part.solids[placed[0].solid];
+        assert_eq!(imported.vertices.len(), 8);
+        assert_eq!(imported.edges.len(), 12);
+        assert_eq!(imported.faces.len(), 6);
+        assert!(imported.shells[0].is_closed);
+        assert!(imported.is_valid());
+        assert!(imported.edges.iter().all(|e| e.faces.len() == 2));
+        for (a, b) in solid.faces.iter().zip(imported.faces.iter()) {
+            let (SurfaceType::Planar { normal: n1 }, SurfaceType::Planar { normal: n2 }) =
+                (&a.surface, &b.surface)
+            else {
+                panic!("expected planar faces");
+            };
+            assert!((*n1 - *n2).length() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_round_trip_assembly_with_curves() {
+        let block = make_box(1.0, 1.0, 1.0);
+        let mut curved = make_box(1.0, 1.0, 1.0);
+        curved.edges[0].curve = CurveType::Arc {
+            center: Point3::new(0.0, -0.5, -0.5),
+            radius: 0.5,
+            normal: Vector3::Y,
+            start_angle: 0.0,
+            end_angle: std::f32::consts::PI,
+        };
+        curved.faces[1].surface = SurfaceType::Cylindrical {
+            axis: Vector3::Z,
+            center: Point3::ORIGIN,
+            radius: 0.5,
+        };
+        curved.faces[2].surface = SurfaceType::Nurbs {
+            control_points: vec![
+                vec![Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 1.0, 0.0)],
+                vec![Point3::new(1.0, 0.0, 0.0), Point3::new(1.0, 1.0, 0.0)],
+            ],
+            weights: vec![vec![1.0, 1.0], vec![1.0, 2.0]],
+            u_knots: vec![0.0, 0.0, 1.0, 1.0],
+            v_knots: vec![0.0, 0.0, 1.0, 1.0],
+            u_degree: 1,
+            v_degree: 1,
+        };
+        let parts = [
+            StepSolidPart {
+                name: "BLOCK".to_string(),
+                solid: &block,
+                placement: Transform3::IDENTITY,
+            },
+            StepSolidPart {
+                name: "CURVED".to_string(),
+                solid: &curved,
+                placement: Transform3::from_rotation_z(std::f32::consts::FRAC_PI_2)
+                    .then(Transform3::from_translation(Vector3::new(10.0, 0.0, 0.0))),
+            },
+        ];
+        let options = StepExportOptions {
+            length_unit: StepLengthUnit::Millimetre,
+            ..StepExportOptions::default()
+        };
+        let import = import_step(&export_step_assembly(&parts, &options)).unwrap();
+
+        assert!((import.length_unit.millimetres - 1.0).abs() < 1e-12);
+        assert_eq!(import.roots().len(), 1);
+        assert_eq!(import.occurrences.len(), 2);
+
+        let placed = import.placed_solids();
+        assert_eq!(placed.len(), 2);
+        let curved_instance = placed
+            .iter()
+            .find(|p| import.products[p.product].name == "CURVED")
+            .unwrap();
+        let origin = Point3::ORIGIN.transform(&curved_instance.placement);
+        assert!(origin.approx_eq(Point3::new(10.0, 0.0, 0.0), 1e-4));
+        let x = Point3::new(1.0, 0.0, 0.0).transform(&curved_instance.placement);
+        assert!(x.approx_eq(Point3::new(10.0, 1.0, 0.0), 1e-4));
+
+        let solid = &import.products[curved_instance.product].solids[0];
+        match &solid.edges[0].curve {
+            CurveType::Arc {
+                radius,
+                start_angle,
+                end_angle,
+                ..
+            } => {
+                assert!((radius - 0.5).abs() < 1e-6);
+                assert!(start_angle.abs() < 1e-5);
+                assert!((end_angle - std::f32::consts::PI).abs() < 1e-5);
+            }
+            other => panic!("expected arc, got {:?}", other),
+        }
+        assert!(matches!(
+            solid.faces[1].surface,
+            SurfaceType::Cylindrical { .. }
+        ));
+        match &solid.faces[2].surface {
+            SurfaceType::Nurbs {
+                weights, u_knots, ..
+            } => {
+                assert_eq!(weights[1][1], 2.0);
+                assert_eq!(u_knots, &vec![0.0, 0.0, 1.0, 1.0]);
+            }
+            other => panic!("expected NURBS, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_import_ap214_units_cone_and_unsupported() {
+        let text = "ISO-10303-21;
+HEADER;
+FILE_SCHEMA(('AUTOMOTIVE_DESIGN { 1 0 10303 214 1 1 1 1 }'));
+ENDSEC;
+DATA;
+#1=(LENGTH_UNIT()NAMED_UNIT(*)SI_UNIT($,.METRE.));
+#2=(NAMED_UNIT(*)PLANE_ANGLE_UNIT()SI_UNIT($,.RADIAN.));
+#3=PLANE_ANGLE_MEASURE_WITH_UNIT(PLANE_ANGLE_MEASURE(0.0174532925),#2);
+#4=(CONVERSION_BASED_UNIT('DEGREE',#3)NAMED_UNIT(*)PLANE_ANGLE_UNIT());
+#5=(GEOMETRIC_REPRESENTATION_CONTEXT(3)GLOBAL_UNIT_ASSIGNED_CONTEXT((#1,#4))REPRESENTATION_CONTEXT('',''));
+#10=CARTESIAN_POINT('',(0.,0.,0.));
+#11=DIRECTION('',(0.,0.,1.));
+#12=DIRECTION('',(1.,0.,0.));
+#13=AXIS2_PLACEMENT_3D('',#10,#11,#12);
+#14=CONICAL_SURFACE('',#13,1.,45.);
+#15=CARTESIAN_POINT('',(1.,0.,0.));
+#16=VERTEX_POINT('',#15);
+#17=CIRCLE('',#13,1.);
+#18=EDGE_CURVE('',#16,#16,#17,.T.);
+#19=ORIENTED_EDGE('',*,*,#18,.T.);
+#20=EDGE_LOOP('',(#19));
+#21=FACE_OUTER_BOUND('',#20,.T.);
+#22=ADVANCED_FACE('',(#21),#14,.T.);
+#23=SURFACE_OF_REVOLUTION('',#30,#31);
+#24=ADVANCED_FACE('',(#21),#23,.T.);
+#25=OPEN_SHELL('',(#22,#24));
+#26=SHELL_BASED_SURFACE_MODEL('',(#25));
+ENDSEC;
+END-ISO-10303-21;
+";
+        let import = import_step(text).unwrap();
+        assert_eq!(import.schema, StepSchema::Ap214);
+        assert_eq!(import.length_unit.name, "METRE");
+        assert!((import.length_unit.millimetres - 1000.0).abs() < 1e-9);
+
+        assert_eq!(import.products.len(), 1);
+        let solid = &import.products[0].solids[0];
+        assert!(!solid.shells[0].is_closed);
+        match &solid.faces[0].surface {
+            SurfaceType::Conical {
+                apex,
+                axis,
+                half_angle,
+            } => {
+                assert!((half_angle - std::f32::consts::FRAC_PI_4).abs() < 1e-5);
+                assert!(apex.approx_eq(Point3::new(0.0, 0.0, -1.0), 1e-5));
+                assert!((axis.z + 1.0).abs() < 1e-6);
+            }
+            other => panic!("expected cone, got {:?}", other),
+        }
+        match &solid.edges[0].curve {
+            CurveType::Arc { end_angle, .. } => {
+                assert!((end_angle - std::f32::consts::TAU).abs() < 1e-5)
+            }
+            other => panic!("expected full circle, got {:?}", other),
+        }
+
+        assert_eq!(import.unsupported.len(), 1);
+        assert_eq!(import.unsupported[0].id, 23);
+        assert_eq!(import.unsupported[0].entity, "SURFACE_OF_REVOLUTION");
+        assert!(matches!(solid.faces[1].surface, SurfaceType::Planar { .. }));
+    }
+
+    #[test]
+    fn test_import_rejects_cyclic_assembly() {
+        let text = "ISO-10303-21;
+HEADER;
+FILE_SCHEMA(('AP242_MANAGED_MODEL_BASED_3D_ENGINEERING_MIM_LF'));
+ENDSEC;
+DATA;
+#1=PRODUCT('ROOT','ROOT','',());
+#2=PRODUCT_DEFINITION_FORMATION('','',#1);
+#3=PRODUCT_DEFINITION('design','',#2,$);
+#4=PRODUCT('A','A','',());
+#5=PRODUCT_DEFINITION_FORMATION('','',#4);
+#6=PRODUCT_DEFINITION('design','',#5,$);
+#7=NEXT_ASSEMBLY_USAGE_OCCURRENCE('1','A1','',#3,#6,$);
+#8=NEXT_ASSEMBLY_USAGE_OCCURRENCE('2','A2','',#6,#6,$);
+#9=NEXT_ASSEMBLY_USAGE_OCCURRENCE('3','A3','',#6,#6,$);
+ENDSEC;
+END-ISO-10303-21;
+";
+        match import_step(text) {
+            Err(StepError::Translation(message)) => {
+                assert!(message.contains("cyclic"), "{}", message)
+            }
+            other => panic!(
+                "expected cycle error, got {:?}",
+                other.map(|i| i.occurrences)
+            ),
+        }
+
+        // Hand-built imports still flatten without recursing into the cycle
+        let import = StepImport {
+            schema: StepSchema::Ap242,
+            length_unit: StepUnit {
+                name: "MILLIMETRE".to_string(),
+                millimetres: 1.0,
+            },
+            products: ["ROOT", "A"]
+                .iter()
+                .map(|name| ImportedProduct {
+                    name: name.to_string(),
+                    solids: vec![make_box(1.0, 1.0, 1.0)],
+                })
+                .collect(),
+            occurrences: [(0, 1), (1, 1), (1, 1)]
+                .iter()
+                .map(|&(parent, child)| ImportedOccurrence {
+                    name: String::new(),
+                    parent,
+                    child,
+                    placement: Transform3::IDENTITY,
+                })
+                .collect(),
+            unsupported: Vec::new(),
+        };
+        assert_eq!(import.placed_solids().len(), 2);
+    }
+
+    #[test]
+    fn test_import_rejects_garbage() {
+        assert!(import_step("not a step file").is_err());
+    }
+}
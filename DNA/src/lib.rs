@@ -118,6 +118,12 @@ pub mod export;
 /// CAD module (B-Rep solid modeling)
 pub mod cad;
 
+/// CAM module (2.5D toolpaths and G-code)
+pub mod cam;
+
+/// PCB layout (footprints, placement, maze routing)
+pub mod pcb;
+
 /// Security module (secrets and PII detection)
 pub mod security;
 
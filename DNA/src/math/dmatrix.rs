@@ -0,0 +1,397 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: dmatrix.rs | DNA/src/math/dmatrix.rs
+//! PURPOSE: Dynamically sized dense matrix (row-major) over real or complex scalars
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! PURPOSE: Dense matrix storage and arithmetic for any size
+//!
+//! LAYER: DNA → MATH
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ DATA DEFINED                                                                │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ Scalar            Field element: f64 here, Complex in lumped/ac.rs          │
+//! │ DMatrix<T>        rows × cols, row-major Vec<T>                             │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! Indexing: `m[(i, j)]` for an element, `m[i]` for row i as a slice (so the
+//! `m[i][j]` style of the nested-Vec code it replaces keeps working).
+//!
+//! Decompositions live with the solvers:
+//!   • physics/solvers/linear/dense.rs       → LU, Cholesky, QR, least squares
+//!   • physics/solvers/linear/eigensolver.rs → symmetric eigen, SVD
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// CODE BELOW - Optimized for ML development
+// ─────────────────────────────────────────────────────────────────────────────────
+
+use std::fmt::Debug;
+use std::ops::{Add, Div, Index, IndexMut, Mul, Sub};
+
+/// Matrix element type (a field with a magnitude for pivoting)
+pub trait Scalar:
+    Copy
+    + Debug
+    + PartialEq
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+{
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn from_f64(x: f64) -> Self;
+    /// |x| (used for pivot selection and norms)
+    fn modulus(self) -> f64;
+}
+
+impl Scalar for f64 {
+    #[inline]
+    fn zero() -> Self {
+        0.0
+    }
+
+    #[inline]
+    fn one() -> Self {
+        1.0
+    }
+
+    #[inline]
+    fn from_f64(x: f64) -> Self {
+        x
+    }
+
+    #[inline]
+    fn modulus(self) -> f64 {
+        self.abs()
+    }
+}
+
+/// Dense row-major matrix
+#[derive(Clone, Debug, PartialEq)]
+pub struct DMatrix<T = f64> {
+    rows: usize,
+    cols: usize,
+    data: Vec<T>,
+}
+
+impl<T: Scalar> DMatrix<T> {
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            data: vec![T::zero(); rows * cols],
+        }
+    }
+
+    pub fn identity(n: usize) -> Self {
+        let mut m = Self::zeros(n, n);
+        for i in 0..n {
+            m[(i, i)] = T::one();
+        }
+        m
+    }
+
+    pub fn from_fn(rows: usize, cols: usize, f: impl Fn(usize, usize) -> T) -> Self {
+        let mut data = Vec::with_capacity(rows * cols);
+        for i in 0..rows {
+            for j in 0..cols {
+                data.push(f(i, j));
+            }
+        }
+        Self { rows, cols, data }
+    }
+
+    /// From row-major data; panics if `data.len() != rows * cols`
+    pub fn from_row_slice(rows: usize, cols: usize, data: &[T]) -> Self {
+        assert_eq!(data.len(), rows * cols, "DMatrix data length");
+        Self {
+            rows,
+            cols,
+            data: data.to_vec(),
+        }
+    }
+
+    /// From equal-length rows; panics on ragged input
+    pub fn from_rows(rows: &[Vec<T>]) -> Self {
+        let cols = rows.first().map_or(0, Vec::len);
+        assert!(
+            rows.iter().all(|r| r.len() == cols),
+            "DMatrix rows must have equal length"
+        );
+        Self {
+            rows: rows.len(),
+            cols,
+            data: rows.concat(),
+        }
+    }
+
+    pub fn from_diagonal(diagonal: &[T]) -> Self {
+        let mut m = Self::zeros(diagonal.len(), diagonal.len());
+        for (i, &d) in diagonal.iter().enumerate() {
+            m[(i, i)] = d;
+        }
+        m
+    }
+
+    /// Column vector (n × 1)
+    pub fn from_column(values: &[T]) -> Self {
+        Self::from_row_slice(values.len(), 1, values)
+    }
+
+    #[inline]
+    pub fn nrows(&self) -> usize {
+        self.rows
+    }
+
+    #[inline]
+    pub fn ncols(&self) -> usize {
+        self.cols
+    }
+
+    #[inline]
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    #[inline]
+    pub fn is_square(&self) -> bool {
+        self.rows == self.cols
+    }
+
+    /// Row-major elements
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.data
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    #[inline]
+    pub fn row(&self, i: usize) -> &[T] {
+        &self.data[i * self.cols..(i + 1) * self.cols]
+    }
+
+    pub fn column(&self, j: usize) -> Vec<T> {
+        (0..self.rows).map(|i| self[(i, j)]).collect()
+    }
+
+    pub fn diagonal(&self) -> Vec<T> {
+        (0..self.rows.min(self.cols))
+            .map(|i| self[(i, i)])
+            .collect()
+    }
+
+    pub fn swap_rows(&mut self, a: usize, b: usize) {
+        if a != b {
+            for j in 0..self.cols {
+                self.data.swap(a * self.cols + j, b * self.cols + j);
+            }
+        }
+    }
+
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.cols, self.rows, |i, j| self[(j, i)])
+    }
+
+    /// Sum of diagonal elements
+    pub fn trace(&self) -> T {
+        self.diagonal()
+            .into_iter()
+            .fold(T::zero(), |acc, d| acc + d)
+    }
+
+    /// Scalar multiplication
+    pub fn scale(&self, s: T) -> Self {
+        Self {
+            rows: self.rows,
+            cols: self.cols,
+            data: self.data.iter().map(|&x| x * s).collect(),
+        }
+    }
+
+    /// Matrix × vector; panics if `v.len() != ncols()`
+    pub fn mul_vec(&self, v: &[T]) -> Vec<T> {
+        assert_eq!(v.len(), self.cols, "DMatrix::mul_vec dimension");
+        (0..self.rows)
+            .map(|i| {
+                self.row(i)
+                    .iter()
+                    .zip(v)
+                    .fold(T::zero(), |acc, (&a, &b)| acc + a * b)
+            })
+            .collect()
+    }
+
+    /// Matrix product; panics if `self.ncols() != other.nrows()`
+    pub fn matmul(&self, other: &DMatrix<T>) -> DMatrix<T> {
+        assert_eq!(self.cols, other.rows, "DMatrix::matmul dimension");
+        let mut out = DMatrix::zeros(self.rows, other.cols);
+        for i in 0..self.rows {
+            for k in 0..self.cols {
+                let a = self[(i, k)];
+                if a == T::zero() {
+                    continue;
+                }
+                for j in 0..other.cols {
+                    out[(i, j)] = out[(i, j)] + a * other[(k, j)];
+                }
+            }
+        }
+        out
+    }
+
+    fn zip_with(&self, other: &DMatrix<T>, f: impl Fn(T, T) -> T) -> DMatrix<T> {
+        assert_eq!(self.shape(), other.shape(), "DMatrix shapes differ");
+        DMatrix {
+            rows: self.rows,
+            cols: self.cols,
+            data: self
+                .data
+                .iter()
+                .zip(&other.data)
+                .map(|(&a, &b)| f(a, b))
+                .collect(),
+        }
+    }
+
+    /// Largest element modulus
+    pub fn max_modulus(&self) -> f64 {
+        self.data.iter().map(|x| x.modulus()).fold(0.0, f64::max)
+    }
+
+    /// Maximum absolute column sum ‖A‖₁
+    pub fn norm_1(&self) -> f64 {
+        (0..self.cols)
+            .map(|j| (0..self.rows).map(|i| self[(i, j)].modulus()).sum::<f64>())
+            .fold(0.0, f64::max)
+    }
+
+    /// Maximum absolute row sum ‖A‖∞
+    pub fn norm_inf(&self) -> f64 {
+        (0..self.rows)
+            .map(|i| self.row(i).iter().map(|x| x.modulus()).sum::<f64>())
+            .fold(0.0, f64::max)
+    }
+
+    /// Frobenius norm
+    pub fn norm_frobenius(&self) -> f64 {
+        self.data
+            .iter()
+            .map(|x| x.modulus().powi(2))
+            .sum::<f64>()
+            .sqrt()
+    }
+}
+
+impl DMatrix<f64> {
+    /// True if |aᵢⱼ - aⱼᵢ| ≤ tol·max|a|
+    pub fn is_symmetric(&self, tol: f64) -> bool {
+        if !self.is_square() {
+            return false;
+        }
+        let limit = tol * self.max_modulus().max(f64::MIN_POSITIVE);
+        (0..self.rows).all(|i| (0..i).all(|j| (self[(i, j)] - self[(j, i)]).abs() <= limit))
+    }
+}
+
+impl<T> Index<(usize, usize)> for DMatrix<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        debug_assert!(i < self.rows && j < self.cols);
+        &self.data[i * self.cols + j]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for DMatrix<T> {
+    #[inline]
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        debug_assert!(i < self.rows && j < self.cols);
+        &mut self.data[i * self.cols + j]
+    }
+}
+
+impl<T> Index<usize> for DMatrix<T> {
+    type Output = [T];
+
+    /// Row `i`
+    #[inline]
+    fn index(&self, i: usize) -> &[T] {
+        &self.data[i * self.cols..(i + 1) * self.cols]
+    }
+}
+
+impl<T> IndexMut<usize> for DMatrix<T> {
+    #[inline]
+    fn index_mut(&mut self, i: usize) -> &mut [T] {
+        &mut self.data[i * self.cols..(i + 1) * self.cols]
+    }
+}
+
+impl<T: Scalar> Add for &DMatrix<T> {
+    type Output = DMatrix<T>;
+    fn add(self, rhs: &DMatrix<T>) -> DMatrix<T> {
+        self.zip_with(rhs, |a, b| a + b)
+    }
+}
+
+impl<T: Scalar> Sub for &DMatrix<T> {
+    type Output = DMatrix<T>;
+    fn sub(self, rhs: &DMatrix<T>) -> DMatrix<T> {
+        self.zip_with(rhs, |a, b| a - b)
+    }
+}
+
+impl<T: Scalar> Mul for &DMatrix<T> {
+    type Output = DMatrix<T>;
+    fn mul(self, rhs: &DMatrix<T>) -> DMatrix<T> {
+        self.matmul(rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_construction_and_indexing() {
+        let mut m = DMatrix::from_rows(&[vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+        assert_eq!(m.shape(), (2, 3));
+        assert_eq!(m[(1, 2)], 6.0);
+        assert_eq!(m[1][0], 4.0);
+        m[0][1] += 10.0;
+        assert_eq!(m[(0, 1)], 12.0);
+        assert_eq!(m.column(1), vec![12.0, 5.0]);
+        assert_eq!(m.transpose().shape(), (3, 2));
+    }
+
+    #[test]
+    fn test_arithmetic_and_norms() {
+        let a = DMatrix::from_rows(&[vec![1.0, 2.0], vec![3.0, 4.0]]);
+        let i = DMatrix::identity(2);
+        assert_eq!(&a * &i, a);
+        assert_eq!((&a + &a).as_slice(), &[2.0, 4.0, 6.0, 8.0]);
+        assert_eq!((&a - &a).max_modulus(), 0.0);
+        assert_eq!(a.mul_vec(&[1.0, 1.0]), vec![3.0, 7.0]);
+        assert_eq!(a.trace(), 5.0);
+        assert_eq!(a.norm_1(), 6.0);
+        assert_eq!(a.norm_inf(), 7.0);
+        assert!((a.norm_frobenius() - 30f64.sqrt()).abs() < 1e-15);
+        assert!(!a.is_symmetric(1e-12));
+        assert!((&a + &a.transpose()).is_symmetric(1e-12));
+    }
+}
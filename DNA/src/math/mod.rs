@@ -1,13 +1,14 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: mod.rs | DNA/src/math/mod.rs
-//! PURPOSE: Module exports: mat, random
-//! MODIFIED: 2025-12-09
+//! PURPOSE: Module exports: mat, dmatrix, random
+//! MODIFIED: 2026-10-18
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
 
 //!
 //! MATH provides the language for describing physics and geometry:
 //! - mat.rs           - Mat2 (2x2 matrix operations)
+//! - dmatrix.rs       - DMatrix (dynamically sized dense matrix)
 //! - random.rs        - Random number generation utilities
 //! - vec.rs           - Vec2, Vec3, Vec4 (future: or glam re-exports)
 //! - quaternion.rs    - Rotation representation (future)
@@ -25,6 +26,10 @@
 pub mod mat;
 pub use mat::Mat2;
 
+/// Dynamically sized dense matrix (decompositions in physics::solvers::linear)
+pub mod dmatrix;
+pub use dmatrix::{DMatrix, Scalar};
+
 /// Random number generation utilities
 pub mod random;
 pub use random::*;
@@ -1,7 +1,7 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: pathfinding.rs | DNA/src/pathfinding.rs
-//! PURPOSE: Implements A* pathfinding algorithm with GridMap, Heuristic, and PathResult types for grid-based navigation
-//! MODIFIED: 2025-12-09
+//! PURPOSE: Implements A* pathfinding (single-layer and layered multi-source) with GridMap, Heuristic, and PathResult types
+//! MODIFIED: 2026-10-18
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
 
@@ -329,6 +329,186 @@ pub fn astar(
     }
 }
 
+/// Multi-layer A* result (cells are (x, y, layer))
+#[derive(Clone, Debug)]
+pub struct LayeredPathResult {
+    /// Path from a start cell to a goal cell (empty if no path found)
+    pub path: Vec<(i32, i32, usize)>,
+    /// Total cost of path
+    pub cost: f32,
+    /// Number of nodes explored
+    pub nodes_explored: usize,
+}
+
+/// Node for the layered search (flat index into layer-major storage)
+#[derive(Clone, Copy, Debug)]
+struct LayeredNode {
+    idx: usize,
+    g: f32,
+    f: f32,
+}
+
+impl PartialEq for LayeredNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.idx == other.idx
+    }
+}
+
+impl Eq for LayeredNode {}
+
+impl PartialOrd for LayeredNode {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for LayeredNode {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        // Reverse ordering for min-heap behavior
+        other
+            .f
+            .partial_cmp(&self.f)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    }
+}
+
+/// A* over a stack of equally sized grid layers joined by vias
+///
+/// Moves are 4-connected within a layer; stepping to the same cell on the
+/// adjacent layer costs `via_cost` and is only allowed where `vias` is
+/// passable. Search starts from every cell in `starts` and stops at the first
+/// cell in `goals` (multi-source / multi-target, as used by maze routers
+/// growing a net from its partial tree). The heuristic is measured to the
+/// bounding box of the goals, so it stays admissible.
+pub fn astar_layered(
+    layers: &[GridMap],
+    vias: &GridMap,
+    via_cost: f32,
+    starts: &[(i32, i32, usize)],
+    goals: &[(i32, i32, usize)],
+    heuristic: Heuristic,
+) -> LayeredPathResult {
+    use std::collections::BinaryHeap;
+
+    let no_path = |nodes_explored| LayeredPathResult {
+        path: Vec::new(),
+        cost: f32::INFINITY,
+        nodes_explored,
+    };
+    let Some(first) = layers.first() else {
+        return no_path(0);
+    };
+    let (width, height) = (first.width, first.height);
+    if layers
+        .iter()
+        .any(|l| l.width != width || l.height != height)
+    {
+        return no_path(0);
+    }
+    let layer_size = width * height;
+    let passable = |&(x, y, layer): &(i32, i32, usize)| {
+        layer < layers.len() && layers[layer].is_passable(x, y)
+    };
+    let index =
+        |(x, y, layer): (i32, i32, usize)| layer * layer_size + y as usize * width + x as usize;
+    let cell = |idx: usize| {
+        let layer = idx / layer_size;
+        let rem = idx % layer_size;
+        ((rem % width) as i32, (rem / width) as i32, layer)
+    };
+
+    let goals: Vec<_> = goals.iter().copied().filter(passable).collect();
+    let starts: Vec<_> = starts.iter().copied().filter(passable).collect();
+    if goals.is_empty() || starts.is_empty() {
+        return no_path(0);
+    }
+
+    // Goal bounding box for the heuristic
+    let (mut min_x, mut min_y, mut max_x, mut max_y) = (i32::MAX, i32::MAX, i32::MIN, i32::MIN);
+    for &(x, y, _) in &goals {
+        min_x = min_x.min(x);
+        min_y = min_y.min(y);
+        max_x = max_x.max(x);
+        max_y = max_y.max(y);
+    }
+    let h = |x: i32, y: i32| heuristic.compute(x, y, x.clamp(min_x, max_x), y.clamp(min_y, max_y));
+
+    let size = layer_size * layers.len();
+    let mut is_goal = vec![false; size];
+    for &g in &goals {
+        is_goal[index(g)] = true;
+    }
+
+    let mut g_score = vec![f32::INFINITY; size];
+    let mut came_from: Vec<Option<usize>> = vec![None; size];
+    let mut open = BinaryHeap::new();
+    for &s in &starts {
+        let idx = index(s);
+        if g_score[idx] > 0.0 {
+            g_score[idx] = 0.0;
+            open.push(LayeredNode {
+                idx,
+                g: 0.0,
+                f: h(s.0, s.1),
+            });
+        }
+    }
+
+    let mut nodes_explored = 0;
+    while let Some(current) = open.pop() {
+        if current.g > g_score[current.idx] {
+            continue;
+        }
+        nodes_explored += 1;
+
+        if is_goal[current.idx] {
+            let mut path = vec![cell(current.idx)];
+            let mut curr_idx = current.idx;
+            while let Some(parent_idx) = came_from[curr_idx] {
+                curr_idx = parent_idx;
+                path.push(cell(curr_idx));
+            }
+            path.reverse();
+            return LayeredPathResult {
+                path,
+                cost: current.g,
+                nodes_explored,
+            };
+        }
+
+        let (cx, cy, layer) = cell(current.idx);
+        let map = &layers[layer];
+        let mut step = |next: (i32, i32, usize), move_cost: f32| {
+            let n_idx = index(next);
+            let tentative_g = current.g + move_cost;
+            if tentative_g < g_score[n_idx] {
+                came_from[n_idx] = Some(current.idx);
+                g_score[n_idx] = tentative_g;
+                open.push(LayeredNode {
+                    idx: n_idx,
+                    g: tentative_g,
+                    f: tentative_g + h(next.0, next.1),
+                });
+            }
+        };
+
+        for (nx, ny) in map.neighbors_4(cx, cy) {
+            step((nx, ny, layer), map.cost(nx as usize, ny as usize));
+        }
+        if vias.is_passable(cx, cy) {
+            let via_step = via_cost * vias.cost(cx as usize, cy as usize);
+            for next_layer in [layer.wrapping_sub(1), layer + 1] {
+                let next = (cx, cy, next_layer);
+                if passable(&next) {
+                    step(next, via_step);
+                }
+            }
+        }
+    }
+
+    no_path(nodes_explored)
+}
+
 /// Convert grid path to world coordinates
 pub fn path_to_world(path: &[(i32, i32)], cell_size: f32, offset: Vec2) -> Vec<Vec2> {
     path.iter()
@@ -403,6 +583,52 @@ mod tests {
         assert!(result.cost < 14.0);
     }
 
+    #[test]
+    fn test_layered_via_detour() {
+        // Layer 0 is cut by a full wall; the path must drop to layer 1
+        let mut top = GridMap::new(7, 3);
+        for y in 0..3 {
+            top.set_obstacle(3, y, true);
+        }
+        let bottom = GridMap::new(7, 3);
+        let vias = GridMap::new(7, 3);
+
+        let result = astar_layered(
+            &[top, bottom],
+            &vias,
+            5.0,
+            &[(0, 1, 0)],
+            &[(6, 1, 0)],
+            Heuristic::Manhattan,
+        );
+        assert_eq!(result.path.first(), Some(&(0, 1, 0)));
+        assert_eq!(result.path.last(), Some(&(6, 1, 0)));
+        assert!(result.path.iter().any(|&(_, _, layer)| layer == 1));
+        // 6 steps plus two layer changes
+        assert!((result.cost - 16.0).abs() < 1e-4);
+
+        // No via sites: unreachable
+        let mut blocked = GridMap::new(7, 3);
+        for x in 0..7 {
+            for y in 0..3 {
+                blocked.set_obstacle(x, y, true);
+            }
+        }
+        let mut top = GridMap::new(7, 3);
+        for y in 0..3 {
+            top.set_obstacle(3, y, true);
+        }
+        let result = astar_layered(
+            &[top, GridMap::new(7, 3)],
+            &blocked,
+            5.0,
+            &[(0, 1, 0)],
+            &[(6, 1, 0)],
+            Heuristic::Manhattan,
+        );
+        assert!(result.path.is_empty());
+    }
+
     #[test]
     fn test_start_equals_goal() {
         let map = GridMap::new(5, 5);
@@ -0,0 +1,286 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: footprint.rs | DNA/src/pcb/footprint.rs
+//! PURPOSE: Footprint library (0402/0603/0805 chips, SOT-23, SOIC, test points)
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+//!
+//! Land patterns follow IPC-7351 nominal density (the same numbers KiCad's
+//! standard library uses). Footprint coordinates are millimetres relative to
+//! the part origin, Y up, unrotated; pin 1 of multi-pin parts is top-left.
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use crate::export::fabrication::PadShape;
+
+/// Gap between pad/body extents and the courtyard outline (mm)
+const COURTYARD_MARGIN: f64 = 0.25;
+
+/// Pad of a footprint, relative to the part origin
+#[derive(Clone, Debug, PartialEq)]
+pub struct FootprintPad {
+    /// Pin number as printed in the datasheet ("1", "2", ...)
+    pub number: String,
+    pub offset: (f64, f64),
+    pub shape: PadShape,
+    /// Plated hole diameter for through-hole pads
+    pub drill: Option<f64>,
+}
+
+/// Land pattern with pads, silkscreen strokes and courtyard
+#[derive(Clone, Debug, PartialEq)]
+pub struct Footprint {
+    pub name: String,
+    pub pads: Vec<FootprintPad>,
+    /// Silkscreen strokes (start, end)
+    pub silkscreen: Vec<((f64, f64), (f64, f64))>,
+    /// Courtyard size (width, height), centred on the origin
+    pub courtyard: (f64, f64),
+}
+
+impl Footprint {
+    /// Footprint from its library name ("0402", "0603", "0805", "SOT-23", "SOIC-8", "TP")
+    pub fn from_name(name: &str) -> Option<Footprint> {
+        let upper = name.trim().to_ascii_uppercase();
+        match upper.as_str() {
+            "0402" => Some(Self::chip_0402()),
+            "0603" => Some(Self::chip_0603()),
+            "0805" => Some(Self::chip_0805()),
+            "SOT-23" | "SOT23" => Some(Self::sot23()),
+            "TP" | "TESTPOINT" => Some(Self::test_point()),
+            _ => {
+                let pins = upper
+                    .strip_prefix("SOIC-")
+                    .or_else(|| upper.strip_prefix("SOIC"))?;
+                let pins: usize = pins.parse().ok()?;
+                (pins >= 4 && pins.is_multiple_of(2)).then(|| Self::soic(pins))
+            }
+        }
+    }
+
+    /// 0402 (1005 metric) resistor/capacitor
+    pub fn chip_0402() -> Footprint {
+        Self::chip("0402", 0.54, 0.64, 0.51)
+    }
+
+    /// 0603 (1608 metric) resistor/capacitor
+    pub fn chip_0603() -> Footprint {
+        Self::chip("0603", 0.8, 0.95, 0.825)
+    }
+
+    /// 0805 (2012 metric) resistor/capacitor
+    pub fn chip_0805() -> Footprint {
+        Self::chip("0805", 1.025, 1.4, 0.9125)
+    }
+
+    /// Two-terminal chip: pads of `width` x `height` at x = ±`pitch_half`
+    fn chip(name: &str, width: f64, height: f64, pitch_half: f64) -> Footprint {
+        let pad = |number: &str, x: f64| FootprintPad {
+            number: number.to_string(),
+            offset: (x, 0.0),
+            shape: PadShape::RoundRect {
+                width,
+                height,
+                radius: 0.25 * width.min(height),
+            },
+            drill: None,
+        };
+        // Short strokes above and below the body, between the pads
+        let inner = (pitch_half - width / 2.0 - 0.1).max(0.05);
+        let y = height / 2.0 + 0.1;
+        Self::with_courtyard(
+            name,
+            vec![pad("1", -pitch_half), pad("2", pitch_half)],
+            vec![((-inner, y), (inner, y)), ((-inner, -y), (inner, -y))],
+        )
+    }
+
+    /// SOT-23 (3 pins: 1 and 2 on the left, 3 on the right)
+    pub fn sot23() -> Footprint {
+        let pad = |number: &str, offset: (f64, f64)| FootprintPad {
+            number: number.to_string(),
+            offset,
+            shape: PadShape::RoundRect {
+                width: 1.325,
+                height: 0.6,
+                radius: 0.15,
+            },
+            drill: None,
+        };
+        let (x, y) = (0.7, 1.56);
+        Self::with_courtyard(
+            "SOT-23",
+            vec![
+                pad("1", (-1.1375, 0.95)),
+                pad("2", (-1.1375, -0.95)),
+                pad("3", (1.1375, 0.0)),
+            ],
+            vec![
+                ((-1.675, y), (x, y)),
+                ((-x, -y), (x, -y)),
+                ((x, y), (x, 0.56)),
+                ((x, -y), (x, -0.56)),
+            ],
+        )
+    }
+
+    /// SOIC with 1.27 mm pitch and 3.9 mm body; pins counted down the left
+    /// side then up the right side
+    pub fn soic(pins: usize) -> Footprint {
+        let per_side = pins / 2;
+        let pitch = 1.27;
+        let top = (per_side as f64 - 1.0) * pitch / 2.0;
+        let pad = |number: usize, offset: (f64, f64)| FootprintPad {
+            number: number.to_string(),
+            offset,
+            shape: PadShape::RoundRect {
+                width: 1.95,
+                height: 0.6,
+                radius: 0.15,
+            },
+            drill: None,
+        };
+        let mut pads = Vec::with_capacity(per_side * 2);
+        for i in 0..per_side {
+            pads.push(pad(i + 1, (-2.475, top - i as f64 * pitch)));
+        }
+        for i in 0..per_side {
+            pads.push(pad(per_side + i + 1, (2.475, -top + i as f64 * pitch)));
+        }
+        // Body edges above and below the pad rows; the top one runs out to
+        // pin 1 as an orientation mark
+        let half_body = 1.95;
+        let y = top + pitch / 2.0 + 0.3;
+        Self::with_courtyard(
+            &format!("SOIC-{}", per_side * 2),
+            pads,
+            vec![
+                ((-3.45, y), (half_body, y)),
+                ((-half_body, -y), (half_body, -y)),
+            ],
+        )
+    }
+
+    /// Plated test point / wire pad (1.7 mm pad, 1.0 mm hole)
+    pub fn test_point() -> Footprint {
+        Self::with_courtyard(
+            "TP",
+            vec![FootprintPad {
+                number: "1".to_string(),
+                offset: (0.0, 0.0),
+                shape: PadShape::Circle { diameter: 1.7 },
+                drill: Some(1.0),
+            }],
+            Vec::new(),
+        )
+    }
+
+    fn with_courtyard(
+        name: &str,
+        pads: Vec<FootprintPad>,
+        silkscreen: Vec<((f64, f64), (f64, f64))>,
+    ) -> Footprint {
+        let mut half = (0.0f64, 0.0f64);
+        for pad in &pads {
+            let (w, h) = pad.shape.size();
+            half.0 = half.0.max(pad.offset.0.abs() + w / 2.0);
+            half.1 = half.1.max(pad.offset.1.abs() + h / 2.0);
+        }
+        for &(a, b) in &silkscreen {
+            half.0 = half.0.max(a.0.abs()).max(b.0.abs());
+            half.1 = half.1.max(a.1.abs()).max(b.1.abs());
+        }
+        Footprint {
+            name: name.to_string(),
+            pads,
+            silkscreen,
+            courtyard: (
+                2.0 * (half.0 + COURTYARD_MARGIN),
+                2.0 * (half.1 + COURTYARD_MARGIN),
+            ),
+        }
+    }
+
+    pub fn pad(&self, number: &str) -> Option<&FootprintPad> {
+        self.pads.iter().find(|p| p.number == number)
+    }
+}
+
+/// Rotate `offset` by `rotation` quarter turns counter-clockwise
+pub fn rotate_offset(offset: (f64, f64), rotation: u8) -> (f64, f64) {
+    let (x, y) = offset;
+    match rotation % 4 {
+        0 => (x, y),
+        1 => (-y, x),
+        2 => (-x, -y),
+        _ => (y, -x),
+    }
+}
+
+/// Pad shape after `rotation` quarter turns (odd turns swap width and height)
+pub fn rotate_shape(shape: PadShape, rotation: u8) -> PadShape {
+    if rotation.is_multiple_of(2) {
+        return shape;
+    }
+    match shape {
+        PadShape::Circle { .. } => shape,
+        PadShape::Rect { width, height } => PadShape::Rect {
+            width: height,
+            height: width,
+        },
+        PadShape::RoundRect {
+            width,
+            height,
+            radius,
+        } => PadShape::RoundRect {
+            width: height,
+            height: width,
+            radius,
+        },
+        PadShape::Obround { width, height } => PadShape::Obround {
+            width: height,
+            height: width,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_library_lookup_and_geometry() {
+        let r = Footprint::from_name("0603").unwrap();
+        assert_eq!(r.pads.len(), 2);
+        assert_eq!(r.pad("2").unwrap().offset, (0.825, 0.0));
+        // Pads (0.825 + 0.4) plus courtyard margin
+        assert!((r.courtyard.0 - 2.0 * (1.225 + 0.25)).abs() < 1e-9);
+
+        let soic = Footprint::from_name("soic-8").unwrap();
+        assert_eq!(soic.pads.len(), 8);
+        assert_eq!(soic.pad("1").unwrap().offset, (-2.475, 1.905));
+        assert_eq!(soic.pad("4").unwrap().offset, (-2.475, -1.905));
+        assert_eq!(soic.pad("5").unwrap().offset, (2.475, -1.905));
+        assert_eq!(soic.pad("8").unwrap().offset, (2.475, 1.905));
+
+        assert_eq!(Footprint::from_name("SOT-23").unwrap().pads.len(), 3);
+        assert!(Footprint::from_name("TP").unwrap().pads[0].drill.is_some());
+        assert!(Footprint::from_name("SOIC-7").is_none());
+        assert!(Footprint::from_name("1206").is_none());
+
+        assert_eq!(rotate_offset((1.0, 0.5), 1), (-0.5, 1.0));
+        assert_eq!(
+            rotate_shape(
+                PadShape::Rect {
+                    width: 2.0,
+                    height: 1.0
+                },
+                3
+            ),
+            PadShape::Rect {
+                width: 1.0,
+                height: 2.0
+            }
+        );
+    }
+}
@@ -0,0 +1,624 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: layout.rs | DNA/src/pcb/layout.rs
+//! PURPOSE: Board layout model, netlist import and connectivity-driven placement
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+//!
+//! A `Layout` is a rectangular two-layer board holding placed components, the
+//! nets joining their pads and (after routing) tracks and vias. It converts
+//! to a `FabBoard`, so every layout goes out through the Gerber X2 writer.
+//!
+//! Placement puts components on a grid of square slots: a greedy pass orders
+//! them by connectivity, then slot swaps and quarter-turn rotations are kept
+//! while they reduce half-perimeter wirelength (HPWL).
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use std::collections::BTreeSet;
+
+use super::footprint::{rotate_offset, rotate_shape, Footprint, FootprintPad};
+use super::PcbError;
+use crate::export::fabrication::{
+    BoardSide, FabBoard, FabGraphic, FabPad, FabTrack, FabVia, PadShape, TrackSegment,
+};
+use crate::physics::electromagnetics::lumped::{Element, Netlist};
+
+/// Silkscreen stroke width (mm)
+const SILK_WIDTH: f64 = 0.12;
+
+/// Placed part
+#[derive(Clone, Debug, PartialEq)]
+pub struct Component {
+    /// Reference designator ("R1", "U3", ...)
+    pub reference: String,
+    pub footprint: Footprint,
+    /// Origin on the board (mm)
+    pub position: (f64, f64),
+    /// Quarter turns counter-clockwise
+    pub rotation: u8,
+}
+
+impl Component {
+    /// Board position of a footprint pad
+    pub fn pad_position(&self, pad: &FootprintPad) -> (f64, f64) {
+        let (dx, dy) = rotate_offset(pad.offset, self.rotation);
+        (self.position.0 + dx, self.position.1 + dy)
+    }
+
+    /// Pad outline in board orientation
+    pub fn pad_shape(&self, pad: &FootprintPad) -> PadShape {
+        rotate_shape(pad.shape, self.rotation)
+    }
+}
+
+/// Pad reference: component index and pin number
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PinRef {
+    pub component: usize,
+    pub pad: String,
+}
+
+/// Electrical net
+#[derive(Clone, Debug, PartialEq)]
+pub struct PcbNet {
+    pub name: String,
+    pub pins: Vec<PinRef>,
+}
+
+/// Two-layer board layout
+#[derive(Clone, Debug, Default)]
+pub struct Layout {
+    pub name: String,
+    /// Board size (mm); the outline runs from (0, 0) to (width, height)
+    pub width: f64,
+    pub height: f64,
+    pub components: Vec<Component>,
+    pub nets: Vec<PcbNet>,
+    pub tracks: Vec<FabTrack>,
+    pub vias: Vec<FabVia>,
+}
+
+/// How netlist elements become board parts
+#[derive(Clone, Debug)]
+pub struct NetlistOptions {
+    /// Footprint for resistors, capacitors and inductors
+    pub package: String,
+    /// Elements to put on the board (`None` = every R, L and C)
+    pub parts: Option<Vec<String>>,
+    /// Board name for the SPICE ground node "0"
+    pub ground_net: String,
+}
+
+impl Default for NetlistOptions {
+    fn default() -> Self {
+        Self {
+            package: "0603".to_string(),
+            parts: None,
+            ground_net: "GND".to_string(),
+        }
+    }
+}
+
+/// Placement parameters
+#[derive(Clone, Debug)]
+pub struct PlacementOptions {
+    /// Gap between neighbouring courtyards (mm)
+    pub spacing: f64,
+    /// Gap between courtyards and the board edge (mm)
+    pub edge_margin: f64,
+    /// Maximum improvement passes
+    pub passes: usize,
+}
+
+impl Default for PlacementOptions {
+    fn default() -> Self {
+        Self {
+            spacing: 1.5,
+            edge_margin: 1.0,
+            passes: 20,
+        }
+    }
+}
+
+impl Layout {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Add an unplaced component; returns its index
+    pub fn add_component(&mut self, reference: &str, footprint: Footprint) -> usize {
+        self.components.push(Component {
+            reference: reference.to_string(),
+            footprint,
+            position: (0.0, 0.0),
+            rotation: 0,
+        });
+        self.components.len() - 1
+    }
+
+    pub fn component(&self, reference: &str) -> Option<&Component> {
+        self.components.iter().find(|c| c.reference == reference)
+    }
+
+    /// Connect a component pad to `net` (created on first use)
+    pub fn connect(&mut self, net: &str, component: usize, pad: &str) -> Result<(), PcbError> {
+        let part = self
+            .components
+            .get(component)
+            .ok_or_else(|| PcbError::UnknownPart(format!("#{}", component)))?;
+        if part.footprint.pad(pad).is_none() {
+            return Err(PcbError::UnknownPad {
+                component: part.reference.clone(),
+                pad: pad.to_string(),
+            });
+        }
+        if let Some(other) = self.net_of(component, pad) {
+            if other != net {
+                return Err(PcbError::PadAlreadyConnected {
+                    component: part.reference.clone(),
+                    pad: pad.to_string(),
+                });
+            }
+            return Ok(());
+        }
+        let pin = PinRef {
+            component,
+            pad: pad.to_string(),
+        };
+        match self.nets.iter_mut().find(|n| n.name == net) {
+            Some(existing) => existing.pins.push(pin),
+            None => self.nets.push(PcbNet {
+                name: net.to_string(),
+                pins: vec![pin],
+            }),
+        }
+        Ok(())
+    }
+
+    /// Name of the net a pad is connected to
+    pub fn net_of(&self, component: usize, pad: &str) -> Option<&str> {
+        self.nets
+            .iter()
+            .find(|n| {
+                n.pins
+                    .iter()
+                    .any(|p| p.component == component && p.pad == pad)
+            })
+            .map(|n| n.name.as_str())
+    }
+
+    /// Board position of a pin
+    pub fn pin_position(&self, pin: &PinRef) -> Option<(f64, f64)> {
+        let part = self.components.get(pin.component)?;
+        Some(part.pad_position(part.footprint.pad(&pin.pad)?))
+    }
+
+    /// Build an unplaced layout from a SPICE netlist
+    ///
+    /// Resistors, capacitors and inductors become two-pin chips (pin 1 =
+    /// positive node). Nets shared with elements that stay off the board
+    /// (sources, controlled sources, excluded parts) and the ground net get a
+    /// test point, so the board can be wired into the rest of the circuit.
+    pub fn from_netlist(
+        name: &str,
+        netlist: &Netlist,
+        options: &NetlistOptions,
+    ) -> Result<Self, PcbError> {
+        let footprint = Footprint::from_name(&options.package)
+            .ok_or_else(|| PcbError::UnknownFootprint(options.package.clone()))?;
+
+        let wanted = |element_name: &str| {
+            options
+                .parts
+                .as_ref()
+                .is_none_or(|parts| parts.iter().any(|p| p == element_name))
+        };
+        let mut layout = Layout::new(name);
+        let mut on_board: Vec<(String, String, String)> = Vec::new();
+        let mut external: BTreeSet<String> = BTreeSet::new();
+        for element in &netlist.elements {
+            match element {
+                Element::Resistor {
+                    name,
+                    node_p,
+                    node_n,
+                    ..
+                }
+                | Element::Capacitor {
+                    name,
+                    node_p,
+                    node_n,
+                    ..
+                }
+                | Element::Inductor {
+                    name,
+                    node_p,
+                    node_n,
+                    ..
+                } if wanted(name) => {
+                    on_board.push((name.clone(), node_p.clone(), node_n.clone()));
+                }
+                other => external.extend(element_nodes(other)),
+            }
+        }
+        if let Some(parts) = &options.parts {
+            if let Some(missing) = parts.iter().find(|p| !on_board.iter().any(|b| &b.0 == *p)) {
+                return Err(PcbError::UnknownPart(missing.clone()));
+            }
+        }
+        if on_board.is_empty() {
+            return Err(PcbError::EmptyDesign);
+        }
+
+        let net_name = |node: &str| {
+            if node == netlist.ground_node {
+                options.ground_net.clone()
+            } else {
+                node.to_string()
+            }
+        };
+        for (reference, node_p, node_n) in &on_board {
+            let index = layout.add_component(reference, footprint.clone());
+            layout.connect(&net_name(node_p), index, "1")?;
+            layout.connect(&net_name(node_n), index, "2")?;
+        }
+
+        // Test points, in first-use order
+        let mut ports: Vec<String> = Vec::new();
+        for (_, node_p, node_n) in &on_board {
+            for node in [node_p, node_n] {
+                let is_port = *node == netlist.ground_node || external.contains(node);
+                if is_port && !ports.contains(node) {
+                    ports.push(node.clone());
+                }
+            }
+        }
+        for (i, node) in ports.iter().enumerate() {
+            let index = layout.add_component(&format!("TP{}", i + 1), Footprint::test_point());
+            layout.connect(&net_name(node), index, "1")?;
+        }
+        Ok(layout)
+    }
+
+    /// Half-perimeter wirelength over all nets (mm)
+    pub fn wirelength(&self) -> f64 {
+        self.nets
+            .iter()
+            .map(|net| {
+                let mut min = (f64::INFINITY, f64::INFINITY);
+                let mut max = (f64::NEG_INFINITY, f64::NEG_INFINITY);
+                for p in net.pins.iter().filter_map(|pin| self.pin_position(pin)) {
+                    min = (min.0.min(p.0), min.1.min(p.1));
+                    max = (max.0.max(p.0), max.1.max(p.1));
+                }
+                if net.pins.len() < 2 {
+                    0.0
+                } else {
+                    (max.0 - min.0) + (max.1 - min.1)
+                }
+            })
+            .sum()
+    }
+
+    /// Convert to a fabrication board (pads, routed copper, silkscreen)
+    pub fn to_fab_board(&self) -> FabBoard {
+        let mut board = FabBoard::rectangle(&self.name, self.width, self.height);
+        for (index, part) in self.components.iter().enumerate() {
+            for pad in &part.footprint.pads {
+                board.pads.push(FabPad {
+                    component: part.reference.clone(),
+                    pin: pad.number.clone(),
+                    net: self.net_of(index, &pad.number).map(str::to_string),
+                    position: part.pad_position(pad),
+                    shape: part.pad_shape(pad),
+                    side: BoardSide::Top,
+                    drill: pad.drill,
+                });
+            }
+            for &(a, b) in &part.footprint.silkscreen {
+                let (ax, ay) = rotate_offset(a, part.rotation);
+                let (bx, by) = rotate_offset(b, part.rotation);
+                board.silkscreen.push(FabGraphic {
+                    side: BoardSide::Top,
+                    width: SILK_WIDTH,
+                    segment: TrackSegment::Line {
+                        start: (part.position.0 + ax, part.position.1 + ay),
+                        end: (part.position.0 + bx, part.position.1 + by),
+                    },
+                });
+            }
+        }
+        board.tracks = self.tracks.clone();
+        board.vias = self.vias.clone();
+        board
+    }
+}
+
+fn element_nodes(element: &Element) -> Vec<String> {
+    match element {
+        Element::Resistor { node_p, node_n, .. }
+        | Element::Capacitor { node_p, node_n, .. }
+        | Element::Inductor { node_p, node_n, .. }
+        | Element::VoltageSource { node_p, node_n, .. }
+        | Element::CurrentSource { node_p, node_n, .. }
+        | Element::BehavioralV { node_p, node_n, .. }
+        | Element::BehavioralI { node_p, node_n, .. } => vec![node_p.clone(), node_n.clone()],
+        Element::VCVS {
+            node_out_p,
+            node_out_n,
+            node_ctrl_p,
+            node_ctrl_n,
+            ..
+        }
+        | Element::VCCS {
+            node_out_p,
+            node_out_n,
+            node_ctrl_p,
+            node_ctrl_n,
+            ..
+        } => vec![
+            node_out_p.clone(),
+            node_out_n.clone(),
+            node_ctrl_p.clone(),
+            node_ctrl_n.clone(),
+        ],
+    }
+}
+
+/// Place every component and size the board around them
+pub fn place(layout: &mut Layout, options: &PlacementOptions) {
+    let n = layout.components.len();
+    if n == 0 {
+        layout.width = 2.0 * options.edge_margin;
+        layout.height = 2.0 * options.edge_margin;
+        return;
+    }
+
+    // Square slots fit any quarter-turn rotation
+    let slot = layout
+        .components
+        .iter()
+        .map(|c| c.footprint.courtyard.0.max(c.footprint.courtyard.1))
+        .fold(0.0, f64::max)
+        + options.spacing;
+    let cols = (n as f64).sqrt().ceil() as usize;
+    let rows = n.div_ceil(cols);
+    let slot_center = |s: usize| {
+        let (row, col) = (s / cols, s % cols);
+        (
+            options.edge_margin + slot * (col as f64 + 0.5),
+            options.edge_margin + slot * (rows as f64 - row as f64 - 0.5),
+        )
+    };
+    layout.width = 2.0 * options.edge_margin + slot * cols as f64;
+    layout.height = 2.0 * options.edge_margin + slot * rows as f64;
+
+    // Greedy order: most connected first, then whatever is most attached to
+    // the parts already placed; laid out in a serpentine so neighbours in the
+    // order stay adjacent on the board
+    let weight = connection_weights(layout);
+    let mut order: Vec<usize> = Vec::with_capacity(n);
+    let mut placed = vec![false; n];
+    while order.len() < n {
+        let next = (0..n)
+            .filter(|&i| !placed[i])
+            .max_by(|&a, &b| {
+                let score = |i: usize| -> f64 {
+                    if order.is_empty() {
+                        weight[i].iter().sum()
+                    } else {
+                        order.iter().map(|&j| weight[i][j]).sum()
+                    }
+                };
+                score(a)
+                    .partial_cmp(&score(b))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then(b.cmp(&a))
+            })
+            .unwrap_or(0);
+        placed[next] = true;
+        order.push(next);
+    }
+    let serpentine = |k: usize| {
+        let (row, col) = (k / cols, k % cols);
+        if row % 2 == 0 {
+            row * cols + col
+        } else {
+            row * cols + (cols - 1 - col)
+        }
+    };
+    // slots[s] = component in slot s
+    let mut slots: Vec<Option<usize>> = vec![None; rows * cols];
+    for (k, &component) in order.iter().enumerate() {
+        slots[serpentine(k)] = Some(component);
+    }
+    let apply = |layout: &mut Layout, slots: &[Option<usize>]| {
+        for (s, c) in slots.iter().enumerate() {
+            if let Some(c) = *c {
+                layout.components[c].position = slot_center(s);
+            }
+        }
+    };
+    apply(layout, &slots);
+
+    // Improvement: pairwise slot swaps and rotations while HPWL drops
+    let mut best = layout.wirelength();
+    for _ in 0..options.passes {
+        let mut improved = false;
+        for a in 0..slots.len() {
+            for b in (a + 1)..slots.len() {
+                if slots[a].is_none() && slots[b].is_none() {
+                    continue;
+                }
+                slots.swap(a, b);
+                apply(layout, &slots);
+                let length = layout.wirelength();
+                if length < best - 1e-9 {
+                    best = length;
+                    improved = true;
+                } else {
+                    slots.swap(a, b);
+                    apply(layout, &slots);
+                }
+            }
+        }
+        for c in 0..n {
+            let original = layout.components[c].rotation;
+            let mut best_rotation = original;
+            for rotation in 1..4u8 {
+                layout.components[c].rotation = (original + rotation) % 4;
+                let length = layout.wirelength();
+                if length < best - 1e-9 {
+                    best = length;
+                    best_rotation = layout.components[c].rotation;
+                    improved = true;
+                }
+            }
+            layout.components[c].rotation = best_rotation;
+        }
+        if !improved {
+            break;
+        }
+    }
+}
+
+/// Pairwise attraction: each net spreads one unit over its pin pairs
+fn connection_weights(layout: &Layout) -> Vec<Vec<f64>> {
+    let n = layout.components.len();
+    let mut weight = vec![vec![0.0; n]; n];
+    for net in &layout.nets {
+        let parts: BTreeSet<usize> = net.pins.iter().map(|p| p.component).collect();
+        if parts.len() < 2 {
+            continue;
+        }
+        let w = 1.0 / (parts.len() - 1) as f64;
+        for &a in &parts {
+            for &b in &parts {
+                if a != b {
+                    weight[a][b] += w;
+                }
+            }
+        }
+    }
+    weight
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physics::electromagnetics::lumped::SourceValue;
+
+    fn rc_netlist() -> Netlist {
+        let mut netlist = Netlist::new("RC".to_string());
+        netlist.add_element(Element::VoltageSource {
+            name: "V1".to_string(),
+            node_p: "in".to_string(),
+            node_n: "0".to_string(),
+            value: SourceValue::DC(1.0),
+        });
+        netlist.add_element(Element::Resistor {
+            name: "R1".to_string(),
+            node_p: "in".to_string(),
+            node_n: "out".to_string(),
+            value: 1e3,
+        });
+        netlist.add_element(Element::Capacitor {
+            name: "C1".to_string(),
+            node_p: "out".to_string(),
+            node_n: "0".to_string(),
+            value: 1e-9,
+        });
+        netlist.add_element(Element::Resistor {
+            name: "R_load".to_string(),
+            node_p: "out".to_string(),
+            node_n: "0".to_string(),
+            value: 1e6,
+        });
+        netlist
+    }
+
+    #[test]
+    fn test_netlist_import() {
+        let options = NetlistOptions {
+            parts: Some(vec!["R1".to_string(), "C1".to_string()]),
+            ..Default::default()
+        };
+        let layout = Layout::from_netlist("rc", &rc_netlist(), &options).unwrap();
+        let refs: Vec<_> = layout
+            .components
+            .iter()
+            .map(|c| c.reference.as_str())
+            .collect();
+        // "in" (source), "out" (excluded R_load) and ground become test points
+        assert_eq!(refs, ["R1", "C1", "TP1", "TP2", "TP3"]);
+        assert_eq!(layout.net_of(0, "2"), Some("out"));
+        assert_eq!(layout.net_of(1, "2"), Some("GND"));
+        let gnd = layout.nets.iter().find(|n| n.name == "GND").unwrap();
+        assert_eq!(gnd.pins.len(), 2);
+
+        let missing = NetlistOptions {
+            parts: Some(vec!["R9".to_string()]),
+            ..Default::default()
+        };
+        assert_eq!(
+            Layout::from_netlist("rc", &rc_netlist(), &missing).unwrap_err(),
+            PcbError::UnknownPart("R9".to_string())
+        );
+
+        let mut manual = Layout::new("manual");
+        let u1 = manual.add_component("U1", Footprint::sot23());
+        manual.connect("VCC", u1, "3").unwrap();
+        assert!(manual.connect("GND", u1, "3").is_err());
+        assert!(manual.connect("GND", u1, "4").is_err());
+    }
+
+    #[test]
+    fn test_placement_reduces_wirelength() {
+        let mut layout = Layout::from_netlist("rc", &rc_netlist(), &Default::default()).unwrap();
+        place(&mut layout, &PlacementOptions::default());
+
+        // Every courtyard inside the board and no two overlapping
+        let boxes: Vec<_> = layout
+            .components
+            .iter()
+            .map(|c| {
+                let (w, h) = if c.rotation % 2 == 0 {
+                    c.footprint.courtyard
+                } else {
+                    (c.footprint.courtyard.1, c.footprint.courtyard.0)
+                };
+                (
+                    (c.position.0 - w / 2.0, c.position.1 - h / 2.0),
+                    (c.position.0 + w / 2.0, c.position.1 + h / 2.0),
+                )
+            })
+            .collect();
+        for (i, (min, max)) in boxes.iter().enumerate() {
+            assert!(min.0 >= 0.0 && min.1 >= 0.0);
+            assert!(max.0 <= layout.width && max.1 <= layout.height);
+            for (other_min, other_max) in &boxes[i + 1..] {
+                let apart = max.0 <= other_min.0
+                    || other_max.0 <= min.0
+                    || max.1 <= other_min.1
+                    || other_max.1 <= min.1;
+                assert!(apart, "courtyards overlap");
+            }
+        }
+
+        // Improvement never worsens the greedy start
+        let mut greedy = Layout::from_netlist("rc", &rc_netlist(), &Default::default()).unwrap();
+        place(
+            &mut greedy,
+            &PlacementOptions {
+                passes: 0,
+                ..Default::default()
+            },
+        );
+        assert!(layout.wirelength() <= greedy.wirelength() + 1e-9);
+        assert!(layout.wirelength() > 0.0);
+    }
+}
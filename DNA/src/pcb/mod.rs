@@ -0,0 +1,140 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: mod.rs | DNA/src/pcb/mod.rs
+//! PURPOSE: PCB layout - footprints, netlist-driven placement, maze routing
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! PURPOSE: PCB layout - footprints, netlist-driven placement, maze routing
+//!
+//! LAYER: DNA → PCB
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ MODULE STRUCTURE                                                            │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │                                                                             │
+//! │   pcb/                                                                      │
+//! │   ├── footprint.rs  Land patterns (0402/0603/0805, SOT-23, SOIC, TP)        │
+//! │   ├── layout.rs     Layout, netlist import, HPWL placement                  │
+//! │   └── router.rs     Two-layer grid maze router with vias                    │
+//! │                                                                             │
+//! │   Netlist ──► Layout ──► place ──► route ──► FabBoard ──► Gerber/Excellon   │
+//! │                                                                             │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! All dimensions are millimetres, board viewed from the top; components are
+//! placed on the top side.
+//!
+//! DEPENDS ON:
+//!   • physics/electromagnetics/lumped → Netlist, Element
+//!   • pathfinding                    → GridMap, astar_layered
+//!   • export/fabrication             → FabBoard, PadShape, tracks and vias
+//!   • export/gerber_render           → Shape (clearance geometry)
+//!
+//! USED BY:
+//!   • export/gerber → generate_loop_filter_gerber
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+pub mod footprint;
+pub mod layout;
+pub mod router;
+
+pub use footprint::*;
+pub use layout::*;
+pub use router::*;
+
+use crate::physics::electromagnetics::lumped::Netlist;
+
+/// Layout errors
+#[derive(Debug, Clone, PartialEq)]
+pub enum PcbError {
+    /// Footprint name not in the library
+    UnknownFootprint(String),
+    /// Component or netlist element not found
+    UnknownPart(String),
+    /// Pin number not on the component's footprint
+    UnknownPad { component: String, pad: String },
+    /// Pad already belongs to another net
+    PadAlreadyConnected { component: String, pad: String },
+    /// Nothing to put on the board
+    EmptyDesign,
+}
+
+impl std::fmt::Display for PcbError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PcbError::UnknownFootprint(name) => write!(f, "Unknown footprint: {}", name),
+            PcbError::UnknownPart(name) => write!(f, "Unknown part: {}", name),
+            PcbError::UnknownPad { component, pad } => {
+                write!(f, "{} has no pad {}", component, pad)
+            }
+            PcbError::PadAlreadyConnected { component, pad } => {
+                write!(f, "{} pad {} is already on another net", component, pad)
+            }
+            PcbError::EmptyDesign => write!(f, "Design has no board parts"),
+        }
+    }
+}
+
+impl std::error::Error for PcbError {}
+
+/// Options for the netlist → routed board flow
+#[derive(Clone, Debug, Default)]
+pub struct PcbOptions {
+    pub netlist: NetlistOptions,
+    pub placement: PlacementOptions,
+    pub router: RouterOptions,
+}
+
+/// Import, place and route a netlist in one go
+pub fn layout_netlist(
+    name: &str,
+    netlist: &Netlist,
+    options: &PcbOptions,
+) -> Result<(Layout, RouteReport), PcbError> {
+    let mut layout = Layout::from_netlist(name, netlist, &options.netlist)?;
+    place(&mut layout, &options.placement);
+    let report = route(&mut layout, &options.router);
+    Ok((layout, report))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pll::circuit::build_pll_netlist;
+    use crate::pll::{design_pll, PLLArchitecture, PLLRequirements};
+
+    #[test]
+    fn test_pll_loop_filter_board() {
+        let design = design_pll(&PLLRequirements {
+            ref_freq_hz: 10e6,
+            output_freq_min_hz: 2.4e9,
+            output_freq_max_hz: 2.5e9,
+            loop_bandwidth_hz: 100e3,
+            phase_margin_deg: 45.0,
+            architecture: PLLArchitecture::IntegerN,
+            supply_voltage: 3.3,
+        })
+        .unwrap();
+        let options = PcbOptions {
+            netlist: NetlistOptions {
+                parts: Some(vec!["C1".into(), "R1".into(), "C2".into()]),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let (layout, report) =
+            layout_netlist("loop filter", &build_pll_netlist(&design), &options).unwrap();
+
+        assert_eq!(layout.components.len(), 6);
+        assert!(report.is_complete(), "unrouted: {:?}", report.unrouted);
+        let mut nets = report.routed.clone();
+        nets.sort();
+        assert_eq!(nets, ["GND", "filter_in", "filter_out"]);
+        // The model-only elements (C_int, R_load) stay off the board
+        assert!(layout.component("C_int").is_none());
+        assert!(layout.component("R_load").is_none());
+    }
+}
@@ -0,0 +1,574 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: router.rs | DNA/src/pcb/router.rs
+//! PURPOSE: Grid maze router for two-layer boards (layered A*, vias, clearances)
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+//!
+//! Nets are routed one at a time, shortest first. For each net the board is
+//! sampled on a square grid per copper layer; a cell is blocked when a track
+//! centred there would come closer than the clearance to copper of another
+//! net or to the board edge. A separate via map marks where a via fits.
+//! Multi-pin nets grow as a tree: every connection searches from all cells of
+//! the partial tree to the nearest unconnected pad (`astar_layered`).
+//!
+//! Keep-outs are widened by the worst-case sag between two grid samples, so
+//! the straight segments joining free cells keep the full clearance too.
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use super::layout::Layout;
+use crate::export::fabrication::{BoardSide, FabTrack, FabVia, PadShape, TrackSegment};
+use crate::export::gerber_render::Shape;
+use crate::pathfinding::{astar_layered, GridMap, Heuristic};
+
+/// Routing grid cell (x, y, layer)
+type Cell = (i32, i32, usize);
+
+/// Copper layers: index 0 = top, 1 = bottom
+const LAYERS: [BoardSide; 2] = [BoardSide::Top, BoardSide::Bottom];
+
+/// Router parameters (mm)
+#[derive(Clone, Debug)]
+pub struct RouterOptions {
+    /// Routing grid pitch
+    pub grid: f64,
+    pub trace_width: f64,
+    /// Minimum copper-to-copper gap between different nets
+    pub clearance: f64,
+    /// Minimum gap between copper and the board edge
+    pub edge_clearance: f64,
+    pub via_diameter: f64,
+    pub via_drill: f64,
+    /// Cost of a via in grid steps
+    pub via_cost: f32,
+}
+
+impl Default for RouterOptions {
+    fn default() -> Self {
+        Self {
+            grid: 0.1,
+            trace_width: 0.25,
+            clearance: 0.2,
+            edge_clearance: 0.3,
+            via_diameter: 0.6,
+            via_drill: 0.3,
+            via_cost: 20.0,
+        }
+    }
+}
+
+/// Routing outcome
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RouteReport {
+    /// Nets with every pin connected
+    pub routed: Vec<String>,
+    /// Nets with at least one pin left unconnected
+    pub unrouted: Vec<String>,
+    /// Total track length (mm)
+    pub length: f64,
+    pub vias: usize,
+}
+
+impl RouteReport {
+    pub fn is_complete(&self) -> bool {
+        self.unrouted.is_empty()
+    }
+}
+
+/// Copper already on the board, for clearance checks
+struct Copper {
+    net: Option<usize>,
+    layers: [bool; 2],
+    shape: Shape,
+    /// Pads accept tracks of their own net ending inside them
+    is_pad: bool,
+}
+
+/// Route every net of a placed layout; existing tracks and vias are replaced
+pub fn route(layout: &mut Layout, options: &RouterOptions) -> RouteReport {
+    layout.tracks.clear();
+    layout.vias.clear();
+
+    let grid = options.grid;
+    let cols = (layout.width / grid).floor() as usize + 1;
+    let rows = (layout.height / grid).floor() as usize + 1;
+    let cell_center = |x: i32, y: i32| (x as f64 * grid, y as f64 * grid);
+
+    // Pads (with their owning net)
+    let mut copper: Vec<Copper> = Vec::new();
+    let mut pins: Vec<Vec<(usize, (f64, f64))>> = vec![Vec::new(); layout.nets.len()];
+    for (index, part) in layout.components.iter().enumerate() {
+        for pad in &part.footprint.pads {
+            let net = layout.nets.iter().position(|n| {
+                n.pins
+                    .iter()
+                    .any(|p| p.component == index && p.pad == pad.number)
+            });
+            let position = part.pad_position(pad);
+            if let Some(net) = net {
+                pins[net].push((copper.len(), position));
+            }
+            copper.push(Copper {
+                net,
+                layers: [true, pad.drill.is_some()],
+                shape: pad_geometry(position, part.pad_shape(pad)),
+                is_pad: true,
+            });
+        }
+    }
+
+    // Shortest nets first: they have the fewest detours available
+    let mut order: Vec<usize> = (0..layout.nets.len())
+        .filter(|&n| pins[n].len() >= 2)
+        .collect();
+    let span = |n: usize| {
+        let xs = pins[n].iter().map(|p| p.1 .0);
+        let ys = pins[n].iter().map(|p| p.1 .1);
+        let (lo_x, hi_x) = xs.fold((f64::INFINITY, f64::NEG_INFINITY), |a, v| {
+            (a.0.min(v), a.1.max(v))
+        });
+        let (lo_y, hi_y) = ys.fold((f64::INFINITY, f64::NEG_INFINITY), |a, v| {
+            (a.0.min(v), a.1.max(v))
+        });
+        (hi_x - lo_x) + (hi_y - lo_y)
+    };
+    order.sort_by(|&a, &b| {
+        span(a)
+            .partial_cmp(&span(b))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let half_width = options.trace_width / 2.0;
+    let via_radius = options.via_diameter / 2.0;
+    let mut report = RouteReport::default();
+
+    for net in order {
+        let name = layout.nets[net].name.clone();
+
+        // Per-layer track maps and the via map
+        let track_keepout = sampled(options.clearance + half_width, grid);
+        let via_keepout = sampled(options.clearance + via_radius, grid);
+        let mut maps = vec![GridMap::new(cols, rows), GridMap::new(cols, rows)];
+        let mut vias = GridMap::new(cols, rows);
+        for y in 0..rows {
+            for x in 0..cols {
+                let (px, py) = cell_center(x as i32, y as i32);
+                let edge = px.min(py).min(layout.width - px).min(layout.height - py);
+                if edge < options.edge_clearance + half_width {
+                    for map in &mut maps {
+                        map.set_obstacle(x, y, true);
+                    }
+                }
+                if edge < options.edge_clearance + via_radius {
+                    vias.set_obstacle(x, y, true);
+                }
+            }
+        }
+        for item in &copper {
+            if item.net == Some(net) {
+                // No via-in-pad: vias must clear their own net's pads
+                if item.is_pad {
+                    block(&mut vias, &item.shape, via_radius, grid);
+                }
+                continue;
+            }
+            for (layer, map) in maps.iter_mut().enumerate() {
+                if item.layers[layer] {
+                    block(map, &item.shape, track_keepout, grid);
+                }
+            }
+            block(&mut vias, &item.shape, via_keepout, grid);
+        }
+
+        // Terminal cells: centres inside each pad, on the pad's layers
+        let terminals: Vec<Vec<Cell>> = pins[net]
+            .iter()
+            .map(|&(pad, position)| pad_cells(&copper[pad], position, grid, &maps))
+            .collect();
+
+        // Grow the tree from the first pin, always joining the nearest pin
+        let mut tree: Vec<Cell> = terminals[0].clone();
+        let mut joined = vec![false; pins[net].len()];
+        joined[0] = true;
+        let mut complete = true;
+        while let Some(next) = (0..pins[net].len())
+            .filter(|&i| !joined[i])
+            .min_by(|&a, &b| {
+                let distance = |i: usize| {
+                    let p = pins[net][i].1;
+                    (0..pins[net].len())
+                        .filter(|&j| joined[j])
+                        .map(|j| {
+                            let q = pins[net][j].1;
+                            (p.0 - q.0).abs() + (p.1 - q.1).abs()
+                        })
+                        .fold(f64::INFINITY, f64::min)
+                };
+                distance(a)
+                    .partial_cmp(&distance(b))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+        {
+            joined[next] = true;
+            let result = astar_layered(
+                &maps,
+                &vias,
+                options.via_cost,
+                &tree,
+                &terminals[next],
+                Heuristic::Manhattan,
+            );
+            if result.path.is_empty() {
+                complete = false;
+                continue;
+            }
+
+            // Emit tracks per layer run, a via at every layer change
+            let mut run_start = 0;
+            for i in 1..=result.path.len() {
+                let layer_change =
+                    i < result.path.len() && result.path[i].2 != result.path[i - 1].2;
+                if i == result.path.len() || layer_change {
+                    let run = &result.path[run_start..i];
+                    for (a, b) in straight_runs(run) {
+                        let start = cell_center(a.0, a.1);
+                        let end = cell_center(b.0, b.1);
+                        report.length += (end.0 - start.0).abs() + (end.1 - start.1).abs();
+                        layout.tracks.push(FabTrack {
+                            side: LAYERS[a.2],
+                            width: options.trace_width,
+                            net: Some(name.clone()),
+                            segment: TrackSegment::Line { start, end },
+                        });
+                        copper.push(Copper {
+                            net: Some(net),
+                            layers: [a.2 == 0, a.2 == 1],
+                            shape: Shape::capsule(start, end, half_width),
+                            is_pad: false,
+                        });
+                    }
+                    if layer_change {
+                        let (x, y, _) = result.path[i];
+                        let position = cell_center(x, y);
+                        layout.vias.push(FabVia {
+                            position,
+                            diameter: options.via_diameter,
+                            drill: options.via_drill,
+                            net: Some(name.clone()),
+                        });
+                        copper.push(Copper {
+                            net: Some(net),
+                            layers: [true, true],
+                            shape: Shape::disk(position, via_radius),
+                            is_pad: false,
+                        });
+                    }
+                    run_start = i;
+                }
+            }
+            tree.extend(result.path);
+            tree.extend(terminals[next].iter().copied());
+        }
+
+        if complete {
+            report.routed.push(name);
+        } else {
+            report.unrouted.push(name);
+        }
+    }
+    report.vias = layout.vias.len();
+    report
+}
+
+/// Keep-out radius widened by the largest gap between a segment joining two
+/// neighbouring samples and a disk of `radius` that both samples clear
+fn sampled(radius: f64, grid: f64) -> f64 {
+    let half = grid / 2.0;
+    radius + radius - (radius * radius - half * half).max(0.0).sqrt()
+}
+
+/// Mark every cell whose centre lies within `keepout` of `shape`
+fn block(map: &mut GridMap, shape: &Shape, keepout: f64, grid: f64) {
+    let (min, max) = shape.bounds();
+    let x0 = ((min.0 - keepout) / grid).floor().max(0.0) as usize;
+    let y0 = ((min.1 - keepout) / grid).floor().max(0.0) as usize;
+    let x1 = (((max.0 + keepout) / grid).ceil().max(0.0) as usize).min(map.width.saturating_sub(1));
+    let y1 =
+        (((max.1 + keepout) / grid).ceil().max(0.0) as usize).min(map.height.saturating_sub(1));
+    for y in y0..=y1 {
+        for x in x0..=x1 {
+            if shape.signed_distance((x as f64 * grid, y as f64 * grid)) < keepout {
+                map.set_obstacle(x, y, true);
+            }
+        }
+    }
+}
+
+/// Free cells inside a pad on each layer it occupies (nearest cell if none)
+fn pad_cells(pad: &Copper, position: (f64, f64), grid: f64, maps: &[GridMap]) -> Vec<Cell> {
+    let (min, max) = pad.shape.bounds();
+    let mut cells = Vec::new();
+    for (layer, map) in maps.iter().enumerate() {
+        if !pad.layers[layer] {
+            continue;
+        }
+        let mut inside = false;
+        let x0 = (min.0 / grid).ceil() as i32;
+        let y0 = (min.1 / grid).ceil() as i32;
+        let x1 = (max.0 / grid).floor() as i32;
+        let y1 = (max.1 / grid).floor() as i32;
+        for y in y0..=y1 {
+            for x in x0..=x1 {
+                let p = (x as f64 * grid, y as f64 * grid);
+                if pad.shape.signed_distance(p) <= 0.0 && map.is_passable(x, y) {
+                    cells.push((x, y, layer));
+                    inside = true;
+                }
+            }
+        }
+        if !inside {
+            let x = (position.0 / grid).round() as i32;
+            let y = (position.1 / grid).round() as i32;
+            cells.push((x, y, layer));
+        }
+    }
+    cells
+}
+
+/// Collapse a single-layer cell path into maximal straight segments
+fn straight_runs(run: &[Cell]) -> Vec<(Cell, Cell)> {
+    let mut segments = Vec::new();
+    if run.len() < 2 {
+        return segments;
+    }
+    let mut start = run[0];
+    let direction = |a: Cell, b: Cell| (b.0 - a.0, b.1 - a.1);
+    for i in 1..run.len() - 1 {
+        if direction(run[i - 1], run[i]) != direction(run[i], run[i + 1]) {
+            segments.push((start, run[i]));
+            start = run[i];
+        }
+    }
+    segments.push((start, run[run.len() - 1]));
+    segments
+}
+
+/// Copper outline of a pad as a clearance shape
+fn pad_geometry(center: (f64, f64), shape: PadShape) -> Shape {
+    let rect = |w: f64, h: f64, r: f64| {
+        let (hw, hh) = ((w / 2.0 - r).max(0.0), (h / 2.0 - r).max(0.0));
+        let (cx, cy) = center;
+        Shape::polygon(
+            vec![
+                (cx - hw, cy - hh),
+                (cx + hw, cy - hh),
+                (cx + hw, cy + hh),
+                (cx - hw, cy + hh),
+            ],
+            r,
+        )
+    };
+    match shape {
+        PadShape::Circle { diameter } => Shape::disk(center, diameter / 2.0),
+        PadShape::Rect { width, height } => rect(width, height, 0.0),
+        PadShape::RoundRect {
+            width,
+            height,
+            radius,
+        } => rect(width, height, radius.clamp(0.0, width.min(height) / 2.0)),
+        PadShape::Obround { width, height } => {
+            let r = width.min(height) / 2.0;
+            let (dx, dy) = (width / 2.0 - r, height / 2.0 - r);
+            Shape::capsule(
+                (center.0 - dx, center.1 - dy),
+                (center.0 + dx, center.1 + dy),
+                r,
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::drc::{run_drc, DrcRules};
+    use crate::export::fabrication::{generate_fabrication_package, FabOptions};
+    use crate::export::gerber_import::{parse_excellon, parse_gerber};
+    use crate::pcb::footprint::Footprint;
+    use crate::pcb::layout::{place, PlacementOptions};
+
+    /// SOT-23 regulator breakout with two 0603 decoupling caps and wire pads
+    fn breakout() -> Layout {
+        let mut layout = Layout::new("ldo breakout");
+        let u1 = layout.add_component("U1", Footprint::sot23());
+        let c1 = layout.add_component("C1", Footprint::chip_0603());
+        let c2 = layout.add_component("C2", Footprint::chip_0603());
+        let tps: Vec<usize> = (1..=3)
+            .map(|i| layout.add_component(&format!("TP{}", i), Footprint::test_point()))
+            .collect();
+        for (net, pins) in [
+            ("GND", vec![(u1, "1"), (c1, "2"), (c2, "2"), (tps[0], "1")]),
+            ("VOUT", vec![(u1, "2"), (c2, "1"), (tps[1], "1")]),
+            ("VIN", vec![(u1, "3"), (c1, "1"), (tps[2], "1")]),
+        ] {
+            for (component, pad) in pins {
+                layout.connect(net, component, pad).unwrap();
+            }
+        }
+        place(&mut layout, &PlacementOptions::default());
+        layout
+    }
+
+    /// Pads and copper of each net form one connected group on the board
+    fn assert_connected(layout: &Layout, options: &RouterOptions) {
+        let board = layout.to_fab_board();
+        for net in &layout.nets {
+            // Shapes per layer: pads, tracks, vias
+            let mut shapes: Vec<([bool; 2], Shape)> = Vec::new();
+            for pad in board
+                .pads
+                .iter()
+                .filter(|p| p.net.as_deref() == Some(&net.name))
+            {
+                shapes.push((
+                    [true, pad.drill.is_some()],
+                    pad_geometry(pad.position, pad.shape),
+                ));
+            }
+            let pad_count = shapes.len();
+            for track in board
+                .tracks
+                .iter()
+                .filter(|t| t.net.as_deref() == Some(&net.name))
+            {
+                let layer = [
+                    track.side == BoardSide::Top,
+                    track.side == BoardSide::Bottom,
+                ];
+                let shape = Shape::capsule(
+                    track.segment.start(),
+                    track.segment.end(),
+                    options.trace_width / 2.0,
+                );
+                shapes.push((layer, shape));
+            }
+            for via in board
+                .vias
+                .iter()
+                .filter(|v| v.net.as_deref() == Some(&net.name))
+            {
+                shapes.push(([true, true], Shape::disk(via.position, via.diameter / 2.0)));
+            }
+            let mut reached = vec![false; shapes.len()];
+            let mut stack = vec![0];
+            reached[0] = true;
+            while let Some(i) = stack.pop() {
+                for j in 0..shapes.len() {
+                    let shares_layer = (0..2).any(|l| shapes[i].0[l] && shapes[j].0[l]);
+                    if !reached[j] && shares_layer && shapes[i].1.gap(&shapes[j].1).0 <= 1e-9 {
+                        reached[j] = true;
+                        stack.push(j);
+                    }
+                }
+            }
+            assert!(
+                reached[..pad_count].iter().all(|&r| r),
+                "net {} is not fully connected",
+                net.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_straight_runs() {
+        let path = [
+            (0, 0, 0),
+            (1, 0, 0),
+            (2, 0, 0),
+            (2, 1, 0),
+            (2, 2, 0),
+            (3, 2, 0),
+        ];
+        assert_eq!(
+            straight_runs(&path),
+            vec![
+                ((0, 0, 0), (2, 0, 0)),
+                ((2, 0, 0), (2, 2, 0)),
+                ((2, 2, 0), (3, 2, 0))
+            ]
+        );
+        assert!(straight_runs(&path[..1]).is_empty());
+    }
+
+    #[test]
+    fn test_route_breakout_passes_drc() {
+        let mut layout = breakout();
+        let options = RouterOptions::default();
+        let report = route(&mut layout, &options);
+        assert!(report.is_complete(), "unrouted: {:?}", report.unrouted);
+        assert_eq!(report.routed.len(), 3);
+        assert!(report.length > 0.0);
+        assert_connected(&layout, &options);
+
+        // Through the fabrication writer and back, then DRC at the router's rules
+        let package = generate_fabrication_package(&layout.to_fab_board(), &FabOptions::default());
+        let text =
+            |name: &str| String::from_utf8(package.file(name).unwrap().contents.clone()).unwrap();
+        let top = parse_gerber(&text("ldo_breakout-F_Cu.gbr")).unwrap();
+        let bottom = parse_gerber(&text("ldo_breakout-B_Cu.gbr")).unwrap();
+        let pth = parse_excellon(&text("ldo_breakout-PTH.drl")).unwrap();
+        let rules = DrcRules {
+            min_trace_width: options.trace_width,
+            min_clearance: options.clearance,
+            ..Default::default()
+        };
+        let violations = run_drc(&[&top, &bottom], &[&pth], &rules);
+        assert!(violations.is_empty(), "{:?}", violations);
+    }
+
+    #[test]
+    fn test_crossing_smd_nets_use_vias() {
+        // Top-only pads in an X: one net has to dive under the other
+        let mut layout = Layout::new("cross");
+        let parts: Vec<usize> = (1..=4)
+            .map(|i| layout.add_component(&format!("R{}", i), Footprint::chip_0805()))
+            .collect();
+        layout.width = 14.0;
+        layout.height = 14.0;
+        let spots = [
+            ((2.0, 7.0), 0),
+            ((12.0, 7.0), 2),
+            ((7.0, 2.0), 1),
+            ((7.0, 12.0), 3),
+        ];
+        for (&part, &(position, rotation)) in parts.iter().zip(&spots) {
+            layout.components[part].position = position;
+            layout.components[part].rotation = rotation;
+        }
+        // Pad 2 of each part points at the centre of the board
+        layout.connect("A", parts[0], "2").unwrap();
+        layout.connect("A", parts[1], "2").unwrap();
+        layout.connect("B", parts[2], "2").unwrap();
+        layout.connect("B", parts[3], "2").unwrap();
+
+        let options = RouterOptions::default();
+        let report = route(&mut layout, &options);
+        assert!(report.is_complete(), "unrouted: {:?}", report.unrouted);
+        assert!(report.vias >= 2);
+        assert!(layout.tracks.iter().any(|t| t.side == BoardSide::Bottom));
+        assert_connected(&layout, &options);
+
+        // Without via sites (and no room between the 0805 pads or past the
+        // board edge) the second net cannot cross
+        let strict = RouterOptions {
+            clearance: 0.3,
+            via_diameter: 20.0,
+            ..options
+        };
+        let report = route(&mut layout, &strict);
+        assert_eq!(report.routed.len(), 1);
+        assert_eq!(report.unrouted.len(), 1);
+        assert_eq!(report.vias, 0);
+    }
+}
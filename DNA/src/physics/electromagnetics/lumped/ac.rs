@@ -1,7 +1,7 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: ac.rs | DNA/src/physics/electromagnetics/lumped/ac.rs
 //! PURPOSE: AC (frequency-domain) circuit analysis using complex MNA
-//! MODIFIED: 2025-12-09
+//! MODIFIED: 2026-10-18
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
 
@@ -35,6 +35,8 @@
 //!
 //! DEPENDS ON:
 //!   • super::netlist → Netlist, Element, SourceValue
+//!   • math/dmatrix.rs → DMatrix storage (Complex implements Scalar)
+//!   • physics/solvers/linear/dense.rs → Complex LU solve
 //!
 //! USED BY:
 //!   • TOOLS/PLL → Frequency response, Bode plots
@@ -47,10 +49,11 @@
 // ─────────────────────────────────────────────────────────────────────────────────
 
 use super::netlist::{Element, Netlist, SourceValue};
+use crate::math::dmatrix::{DMatrix, Scalar};
 use std::f64::consts::PI;
 
 /// Complex number for AC analysis
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Complex {
     pub real: f64,
     pub imag: f64,
@@ -92,6 +95,24 @@ impl Complex {
     }
 }
 
+impl Scalar for Complex {
+    fn zero() -> Self {
+        Complex::zero()
+    }
+
+    fn one() -> Self {
+        Complex::new(1.0, 0.0)
+    }
+
+    fn from_f64(x: f64) -> Self {
+        Complex::new(x, 0.0)
+    }
+
+    fn modulus(self) -> f64 {
+        self.magnitude()
+    }
+}
+
 impl std::ops::Add for Complex {
     type Output = Complex;
     fn add(self, other: Complex) -> Complex {
@@ -140,14 +161,14 @@ pub struct ComplexMNAMatrix {
     pub size: usize,
     pub num_nodes: usize,
     pub num_vsources: usize,
-    pub matrix: Vec<Vec<Complex>>,
+    pub matrix: DMatrix<Complex>,
     pub rhs: Vec<Complex>,
 }
 
 impl ComplexMNAMatrix {
     pub fn new(num_nodes: usize, num_vsources: usize) -> Self {
         let size = num_nodes + num_vsources;
-        let matrix = vec![vec![Complex::zero(); size]; size];
+        let matrix = DMatrix::zeros(size, size);
         let rhs = vec![Complex::zero(); size];
 
         Self {
@@ -316,76 +337,10 @@ impl ComplexMNAMatrix {
 
     /// Solve using complex LU decomposition with partial pivoting
     pub fn solve(&self) -> Result<Vec<Complex>, String> {
-        let mut a = self.matrix.clone();
-        let mut b = self.rhs.clone();
-        let n = self.size;
-
-        // Track row permutations
-        let mut perm: Vec<usize> = (0..n).collect();
-
-        // LU decomposition with partial pivoting
-        for k in 0..n {
-            // Find pivot (row with largest element in column k)
-            let mut max_val = a[k][k].magnitude();
-            let mut max_row = k;
-            for (i, row) in a.iter().enumerate().skip(k + 1) {
-                let val = row[k].magnitude();
-                if val > max_val {
-                    max_val = val;
-                    max_row = i;
-                }
-            }
-
-            if max_val < 1e-14 {
-                return Err(format!("Matrix is singular at column {}", k));
-            }
-
-            // Swap rows if needed
-            if max_row != k {
-                a.swap(k, max_row);
-                b.swap(k, max_row);
-                perm.swap(k, max_row);
-            }
-
-            // Eliminate column k below diagonal
-            for i in (k + 1)..n {
-                let factor = a[i][k] / a[k][k];
-                a[i][k] = factor;
-
-                #[allow(clippy::needless_range_loop)]
-                for j in (k + 1)..n {
-                    a[i][j] = a[i][j] - factor * a[k][j];
-                }
-            }
-        }
-
-        // Forward substitution (Ly = Pb)
-        let mut y = vec![Complex::zero(); n];
-        for i in 0..n {
-            let mut sum = b[i];
-            for j in 0..i {
-                sum = sum - a[i][j] * y[j];
-            }
-            y[i] = sum;
-        }
-
-        // Back substitution (Ux = y)
-        let mut x = vec![Complex::zero(); n];
-        for i in (0..n).rev() {
-            let mut sum = y[i];
-            for j in (i + 1)..n {
-                sum = sum - a[i][j] * x[j];
-            }
-            if a[i][i].magnitude() < 1e-14 {
-                return Err(format!(
-                    "Matrix is singular at row {} during back substitution",
-                    i
-                ));
-            }
-            x[i] = sum / a[i][i];
-        }
-
-        Ok(x)
+        self.matrix
+            .lu_with_tolerance(1e-14)
+            .and_then(|lu| lu.solve(&self.rhs))
+            .map_err(|e| e.to_string())
     }
 }
 
@@ -1,7 +1,7 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: matrix.rs | DNA/src/physics/electromagnetics/lumped/matrix.rs
 //! PURPOSE: Modified Nodal Analysis (MNA) matrix for DC circuit analysis
-//! MODIFIED: 2025-12-09
+//! MODIFIED: 2026-10-18
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
 
@@ -33,7 +33,8 @@
 //! └─────────────────────────────────────────────────────────────────────────────┘
 //!
 //! DEPENDS ON:
-//!   • std → Basic types
+//!   • math/dmatrix.rs → DMatrix storage
+//!   • physics/solvers/linear/dense.rs → LU solve
 //!
 //! USED BY:
 //!   • physics/electromagnetics/lumped/ac.rs → Extends for AC
@@ -45,6 +46,9 @@
 // CODE BELOW - Optimized for ML development
 // ─────────────────────────────────────────────────────────────────────────────────
 
+use crate::math::dmatrix::DMatrix;
+use crate::physics::solvers::linear::dense::LinalgError;
+
 /// Modified Nodal Analysis (MNA) Matrix
 ///
 /// The MNA formulation creates a system of linear equations:
@@ -63,7 +67,7 @@ pub struct MNAMatrix {
     /// Number of voltage sources
     pub num_vsources: usize,
     /// Matrix entries [size x size]
-    pub matrix: Vec<Vec<f64>>,
+    pub matrix: DMatrix<f64>,
     /// Right-hand side vector [size]
     pub rhs: Vec<f64>,
 }
@@ -72,7 +76,7 @@ impl MNAMatrix {
     /// Create a new MNA matrix
     pub fn new(num_nodes: usize, num_vsources: usize) -> Self {
         let size = num_nodes + num_vsources;
-        let matrix = vec![vec![0.0; size]; size];
+        let matrix = DMatrix::zeros(size, size);
         let rhs = vec![0.0; size];
 
         Self {
@@ -235,76 +239,15 @@ impl MNAMatrix {
     ///
     /// Returns the solution vector [V1, V2, ..., Vn, I_vs1, I_vs2, ...]
     pub fn solve(&self) -> Result<Vec<f64>, String> {
-        // Create copies for decomposition
-        let mut a = self.matrix.clone();
-        let b = self.rhs.clone();
-        let n = self.size;
-
-        // LU decomposition with partial pivoting
-        let mut pivot: Vec<usize> = (0..n).collect();
-
-        for k in 0..n {
-            // Find pivot
-            let mut max_val = a[k][k].abs();
-            let mut max_row = k;
-
-            for (i, row) in a.iter().enumerate().skip(k + 1) {
-                let val = row[k].abs();
-                if val > max_val {
-                    max_val = val;
-                    max_row = i;
-                }
-            }
-
-            if max_val < 1e-12 {
-                return Err(format!("Matrix is singular at row {}", k));
-            }
-
-            // Swap rows in matrix and pivot array
-            if max_row != k {
-                a.swap(k, max_row);
-                pivot.swap(k, max_row);
-            }
-
-            // Elimination
-            for i in (k + 1)..n {
-                let factor = a[i][k] / a[k][k];
-                a[i][k] = factor;
-
-                #[allow(clippy::needless_range_loop)]
-                for j in (k + 1)..n {
-                    a[i][j] -= factor * a[k][j];
+        self.matrix
+            .lu_with_tolerance(1e-12)
+            .and_then(|lu| lu.solve(&self.rhs))
+            .map_err(|e| match e {
+                LinalgError::Singular { column } => {
+                    format!("Matrix is singular at row {}", column)
                 }
-            }
-        }
-
-        // Apply pivoting to RHS
-        let mut b_pivot = vec![0.0; n];
-        for i in 0..n {
-            b_pivot[i] = b[pivot[i]];
-        }
-
-        // Forward substitution (Ly = b)
-        let mut y = vec![0.0; n];
-        for i in 0..n {
-            let mut sum = b_pivot[i];
-            for j in 0..i {
-                sum -= a[i][j] * y[j];
-            }
-            y[i] = sum;
-        }
-
-        // Back substitution (Ux = y)
-        let mut x = vec![0.0; n];
-        for i in (0..n).rev() {
-            let mut sum = y[i];
-            for j in (i + 1)..n {
-                sum -= a[i][j] * x[j];
-            }
-            x[i] = sum / a[i][i];
-        }
-
-        Ok(x)
+                other => other.to_string(),
+            })
     }
 }
 
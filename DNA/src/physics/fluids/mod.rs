@@ -1,13 +1,20 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: mod.rs | DNA/src/physics/fluids/mod.rs
-//! PURPOSE: Module exports: sph
-//! MODIFIED: 2025-12-09
+//! PURPOSE: Module exports: sph, lattice_boltzmann, navier_stokes
+//! MODIFIED: 2026-10-18
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
 
-/// Smoothed Particle Hydrodynamics
+/// Smoothed Particle Hydrodynamics (weakly compressible, 2D and 3D)
 pub mod sph;
+pub use sph::{Forcing, Kernel, KernelKind, SphConfig, SphError, SphFluid, SphStats, SphVector};
+
+/// D2Q9 lattice Boltzmann (BGK / MRT, bounce-back, inlet / outlet)
+pub mod lattice_boltzmann;
+pub use lattice_boltzmann::{Collision, LatticeBoltzmann, LbmError, LbmNode};
+
+/// 2D incompressible Navier-Stokes (projection on a staggered grid)
+pub mod navier_stokes;
+pub use navier_stokes::{FlowBoundary, NavierStokes2D, NavierStokesError, PoissonMethod, Side};
 
 // pub mod euler;            // TODO: Inviscid compressible flow
-// pub mod navier_stokes;    // TODO: Viscous flow (CFD)
-// pub mod lattice_boltzmann; // TODO: Mesoscale fluid
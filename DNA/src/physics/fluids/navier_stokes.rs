@@ -0,0 +1,1213 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: navier_stokes.rs | DNA/src/physics/fluids/navier_stokes.rs
+//! PURPOSE: 2D incompressible Navier-Stokes (projection method, MAC grid)
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! PURPOSE: Viscous incompressible flow in a rectangle with obstacles
+//!
+//! LAYER: DNA → PHYSICS → FLUIDS
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ ALGORITHM (Chorin projection, kinematic pressure p/ρ)                       │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ Staggered grid: p at cell centres, u on x-faces, v on y-faces, one ghost    │
+//! │ layer holding the boundary values                                           │
+//! │                                                                             │
+//! │ 1. F = u + Δt(ν∇²u - ∂(u²)/∂x - ∂(uv)/∂y + gₓ)   (G likewise for v)         │
+//! │    convection blends central and donor-cell differences by                  │
+//! │    γ ≥ |u|Δt/Δx - 2ν/(|u|Δx)  (smallest γ keeping the explicit step stable) │
+//! │ 2. ∇²p = (∂F/∂x + ∂G/∂y)/Δt                                                 │
+//! │      periodic in x and y, square 2ⁿ grid:  FFT2D, p̂ = r̂/λ(k)                │
+//! │      otherwise: CG + incomplete Cholesky on the 5-point Laplacian           │
+//! │ 3. u = F - Δt ∂p/∂x,  v = G - Δt ∂p/∂y   (∇·u = 0 to solver tolerance)      │
+//! │                                                                             │
+//! │ Δt = τ·min(1/(2ν(Δx⁻² + Δy⁻²)), Δx/|u|max, Δy/|v|max)                       │
+//! │ Walls, inflow: Neumann p;  outflow: ∂u/∂n = 0, p = 0;  obstacles: no-slip   │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ DATA DEFINED                                                                │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ Side               Left, Right, Bottom, Top                                 │
+//! │ FlowBoundary       Wall, Inflow, ParabolicInflow, Outflow, Periodic         │
+//! │ PoissonMethod      Spectral (FFT) or conjugate gradient                     │
+//! │ NavierStokes2D     Velocity / pressure fields, obstacles, boundaries        │
+//! │ NavierStokesError  Bad input, blow-up, pressure solve failure               │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! Cell (i, j), i = 1..=nx, j = 1..=ny, spans [(i-1)Δx, iΔx] × [(j-1)Δy, jΔy];
+//! u(i, j) sits on its right face and v(i, j) on its top face.
+//!
+//! REFERENCE: Griebel, Dornseifer & Neunhoeffer, "Numerical Simulation in Fluid
+//!            Dynamics" (SIAM, 1998), ch. 3 and 5
+//!            Chorin, Math. Comp. 22 (1968) 745-762
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use crate::physics::solvers::linear::{
+    conjugate_gradient, CsrMatrix, IncompleteCholesky, IterativeOptions, LinalgError, TripletMatrix,
+};
+use crate::physics::solvers::pde::FFT2D;
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// BOUNDARIES
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// Edge of the rectangular domain
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+    Bottom,
+    Top,
+}
+
+impl Side {
+    pub const ALL: [Side; 4] = [Side::Left, Side::Right, Side::Bottom, Side::Top];
+
+    pub fn opposite(self) -> Side {
+        match self {
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+            Side::Bottom => Side::Top,
+            Side::Top => Side::Bottom,
+        }
+    }
+}
+
+/// Velocity condition on one side (pressure condition follows from it)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FlowBoundary {
+    /// No penetration; `tangential` is the wall speed along the side
+    /// (+x on bottom/top, +y on left/right)
+    Wall { tangential: f64 },
+    /// Uniform normal speed into the domain
+    Inflow(f64),
+    /// Poiseuille profile into the domain with the given mean speed
+    ParabolicInflow(f64),
+    /// Zero normal gradient of velocity, p = 0
+    Outflow,
+    /// Wraps to the opposite side (which must be periodic too)
+    Periodic,
+}
+
+impl FlowBoundary {
+    pub const NO_SLIP: FlowBoundary = FlowBoundary::Wall { tangential: 0.0 };
+}
+
+/// How the pressure Poisson equation is solved
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PoissonMethod {
+    Spectral,
+    ConjugateGradient,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// ERRORS
+// ─────────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum NavierStokesError {
+    InvalidParameter(String),
+    /// Non-finite velocity after `step` steps
+    Diverged {
+        step: usize,
+    },
+    Linear(LinalgError),
+}
+
+impl std::fmt::Display for NavierStokesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NavierStokesError::InvalidParameter(msg) => write!(f, "Invalid parameter: {}", msg),
+            NavierStokesError::Diverged { step } => {
+                write!(f, "Velocity field diverged at step {}", step)
+            }
+            NavierStokesError::Linear(e) => write!(f, "Pressure solve failed: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for NavierStokesError {}
+
+impl From<LinalgError> for NavierStokesError {
+    fn from(e: LinalgError) -> Self {
+        NavierStokesError::Linear(e)
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// PRESSURE SOLVER
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// Factorised Poisson operator, rebuilt when the topology changes
+enum Poisson {
+    Spectral {
+        fft: FFT2D,
+        /// -λ(k) of the discrete Laplacian per wavenumber (0 for the mean)
+        inverse_eigenvalues: Vec<f32>,
+    },
+    Sparse {
+        /// Negative Laplacian over fluid cells (SPD)
+        matrix: CsrMatrix,
+        preconditioner: IncompleteCholesky,
+        /// Row of each fluid cell (by storage index)
+        rows: Vec<Option<usize>>,
+        /// No Dirichlet face: one cell is pinned and the rhs made mean-free
+        pinned: bool,
+    },
+}
+
+/// Neighbour across one face of a cell in the pressure equation
+enum Coupling {
+    Cell(usize),
+    Dirichlet,
+    Neumann,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// SOLVER
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// Incompressible flow on an nx × ny staggered grid
+pub struct NavierStokes2D {
+    nx: usize,
+    ny: usize,
+    dx: f64,
+    dy: f64,
+    viscosity: f64,
+    boundaries: [FlowBoundary; 4],
+    body_force: [f64; 2],
+    /// τ in the time step restriction
+    safety: f64,
+    /// Fixed donor-cell weight γ, or `None` for the smallest stable γ each step
+    donor_cell: Option<f64>,
+    linear_options: IterativeOptions,
+    /// Obstacle cells, ghost layer included (always false there)
+    solid: Vec<bool>,
+    u: Vec<f64>,
+    v: Vec<f64>,
+    p: Vec<f64>,
+    f: Vec<f64>,
+    g: Vec<f64>,
+    poisson: Option<Poisson>,
+    pub time: f64,
+    pub steps: usize,
+}
+
+impl NavierStokes2D {
+    /// nx × ny cells over width × height with kinematic viscosity ν,
+    /// no-slip walls on every side and fluid at rest
+    pub fn new(
+        nx: usize,
+        ny: usize,
+        width: f64,
+        height: f64,
+        viscosity: f64,
+    ) -> Result<Self, NavierStokesError> {
+        if nx < 2 || ny < 2 {
+            return Err(NavierStokesError::InvalidParameter(format!(
+                "grid {} × {} needs at least 2 cells per axis",
+                nx, ny
+            )));
+        }
+        if !(width > 0.0 && height > 0.0 && (width * height).is_finite()) {
+            return Err(NavierStokesError::InvalidParameter(format!(
+                "domain {} × {} must be positive",
+                width, height
+            )));
+        }
+        if !(viscosity > 0.0 && viscosity.is_finite()) {
+            return Err(NavierStokesError::InvalidParameter(format!(
+                "viscosity {} must be positive",
+                viscosity
+            )));
+        }
+        let n = (nx + 2) * (ny + 2);
+        Ok(Self {
+            nx,
+            ny,
+            dx: width / nx as f64,
+            dy: height / ny as f64,
+            viscosity,
+            boundaries: [FlowBoundary::NO_SLIP; 4],
+            body_force: [0.0; 2],
+            safety: 0.5,
+            donor_cell: None,
+            linear_options: IterativeOptions::default()
+                .with_tolerances(1e-10, 1e-12)
+                .with_max_iterations(10 * nx * ny),
+            solid: vec![false; n],
+            u: vec![0.0; n],
+            v: vec![0.0; n],
+            p: vec![0.0; n],
+            f: vec![0.0; n],
+            g: vec![0.0; n],
+            poisson: None,
+            time: 0.0,
+            steps: 0,
+        })
+    }
+
+    pub fn with_boundary(mut self, side: Side, boundary: FlowBoundary) -> Self {
+        self.boundaries[side as usize] = boundary;
+        self.poisson = None;
+        self
+    }
+
+    /// Uniform acceleration (gravity or a driving pressure gradient)
+    pub fn with_body_force(mut self, force: [f64; 2]) -> Self {
+        self.body_force = force;
+        self
+    }
+
+    /// τ ∈ (0, 1] scaling the stable time step
+    pub fn with_safety_factor(mut self, safety: f64) -> Self {
+        self.safety = safety;
+        self
+    }
+
+    /// Fixed donor-cell weight γ ∈ [0, 1] (0 = central differences)
+    pub fn with_donor_cell(mut self, gamma: f64) -> Self {
+        self.donor_cell = Some(gamma);
+        self
+    }
+
+    pub fn with_linear_options(mut self, options: IterativeOptions) -> Self {
+        self.linear_options = options;
+        self
+    }
+
+    /// Unit square at Reynolds number U·1/ν with the top wall moving at U
+    pub fn lid_driven_cavity(
+        n: usize,
+        reynolds: f64,
+        lid_speed: f64,
+    ) -> Result<Self, NavierStokesError> {
+        let solver = Self::new(n, n, 1.0, 1.0, lid_speed / reynolds)?.with_boundary(
+            Side::Top,
+            FlowBoundary::Wall {
+                tangential: lid_speed,
+            },
+        );
+        Ok(solver)
+    }
+
+    /// Channel length × height with a Poiseuille inlet on the left, an outlet on
+    /// the right and a circular cylinder; Re = ū·D/ν
+    #[allow(clippy::too_many_arguments)]
+    pub fn cylinder_channel(
+        nx: usize,
+        ny: usize,
+        length: f64,
+        height: f64,
+        center: [f64; 2],
+        diameter: f64,
+        reynolds: f64,
+        mean_speed: f64,
+    ) -> Result<Self, NavierStokesError> {
+        let mut solver = Self::new(nx, ny, length, height, mean_speed * diameter / reynolds)?
+            .with_boundary(Side::Left, FlowBoundary::ParabolicInflow(mean_speed))
+            .with_boundary(Side::Right, FlowBoundary::Outflow);
+        let r = 0.5 * diameter;
+        solver.add_obstacle(|x, y| {
+            let (dx, dy) = (x - center[0], y - center[1]);
+            dx * dx + dy * dy <= r * r
+        });
+        Ok(solver)
+    }
+
+    pub fn size(&self) -> (usize, usize) {
+        (self.nx, self.ny)
+    }
+
+    pub fn spacing(&self) -> (f64, f64) {
+        (self.dx, self.dy)
+    }
+
+    pub fn viscosity(&self) -> f64 {
+        self.viscosity
+    }
+
+    pub fn boundary(&self, side: Side) -> FlowBoundary {
+        self.boundaries[side as usize]
+    }
+
+    /// Storage index of cell (i, j), ghost layer at i = 0, nx + 1 and j = 0, ny + 1
+    pub fn index(&self, i: usize, j: usize) -> usize {
+        j * (self.nx + 2) + i
+    }
+
+    /// Centre of cell (i, j)
+    pub fn center(&self, i: usize, j: usize) -> [f64; 2] {
+        [(i as f64 - 0.5) * self.dx, (j as f64 - 0.5) * self.dy]
+    }
+
+    /// Mark cells whose centre satisfies `inside(x, y)` as solid
+    pub fn add_obstacle(&mut self, inside: impl Fn(f64, f64) -> bool) {
+        for j in 1..=self.ny {
+            for i in 1..=self.nx {
+                let [x, y] = self.center(i, j);
+                if inside(x, y) {
+                    let k = self.index(i, j);
+                    self.solid[k] = true;
+                    self.p[k] = 0.0;
+                }
+            }
+        }
+        self.poisson = None;
+    }
+
+    pub fn is_solid(&self, i: usize, j: usize) -> bool {
+        self.solid[self.index(i, j)]
+    }
+
+    /// Set the velocity from `field(x, y)` sampled at the face positions
+    pub fn set_velocity(&mut self, field: impl Fn(f64, f64) -> [f64; 2]) {
+        for j in 1..=self.ny {
+            for i in 0..=self.nx {
+                let k = self.index(i, j);
+                self.u[k] = field(i as f64 * self.dx, (j as f64 - 0.5) * self.dy)[0];
+            }
+        }
+        for j in 0..=self.ny {
+            for i in 1..=self.nx {
+                let k = self.index(i, j);
+                self.v[k] = field((i as f64 - 0.5) * self.dx, j as f64 * self.dy)[1];
+            }
+        }
+        self.apply_boundaries();
+    }
+
+    /// u(i, j) on the right face of cell (i, j)
+    pub fn u_face(&self, i: usize, j: usize) -> f64 {
+        self.u[self.index(i, j)]
+    }
+
+    /// v(i, j) on the top face of cell (i, j)
+    pub fn v_face(&self, i: usize, j: usize) -> f64 {
+        self.v[self.index(i, j)]
+    }
+
+    /// Velocity interpolated to the centre of cell (i, j)
+    pub fn velocity(&self, i: usize, j: usize) -> [f64; 2] {
+        let k = self.index(i, j);
+        [
+            0.5 * (self.u[k] + self.u[k - 1]),
+            0.5 * (self.v[k] + self.v[k - self.nx - 2]),
+        ]
+    }
+
+    /// Kinematic pressure p/ρ in cell (i, j)
+    pub fn pressure(&self, i: usize, j: usize) -> f64 {
+        self.p[self.index(i, j)]
+    }
+
+    /// ∂v/∂x - ∂u/∂y at the top-right corner of cell (i, j)
+    pub fn vorticity(&self, i: usize, j: usize) -> f64 {
+        let k = self.index(i, j);
+        let row = self.nx + 2;
+        (self.v[k + 1] - self.v[k]) / self.dx - (self.u[k + row] - self.u[k]) / self.dy
+    }
+
+    /// Largest |∇·u| over fluid cells
+    pub fn max_divergence(&self) -> f64 {
+        let row = self.nx + 2;
+        let mut max: f64 = 0.0;
+        for j in 1..=self.ny {
+            for i in 1..=self.nx {
+                let k = self.index(i, j);
+                if !self.solid[k] {
+                    let div = (self.u[k] - self.u[k - 1]) / self.dx
+                        + (self.v[k] - self.v[k - row]) / self.dy;
+                    max = max.max(div.abs());
+                }
+            }
+        }
+        max
+    }
+
+    /// ½∫|u|² dA over fluid cells (per unit depth and density)
+    pub fn kinetic_energy(&self) -> f64 {
+        let mut sum = 0.0;
+        for j in 1..=self.ny {
+            for i in 1..=self.nx {
+                if !self.is_solid(i, j) {
+                    let [u, v] = self.velocity(i, j);
+                    sum += 0.5 * (u * u + v * v);
+                }
+            }
+        }
+        sum * self.dx * self.dy
+    }
+
+    /// Volume flux through a side, positive out of the domain
+    pub fn outflow_rate(&self, side: Side) -> f64 {
+        let (nx, ny) = (self.nx, self.ny);
+        match side {
+            Side::Left => -(1..=ny).map(|j| self.u_face(0, j)).sum::<f64>() * self.dy,
+            Side::Right => (1..=ny).map(|j| self.u_face(nx, j)).sum::<f64>() * self.dy,
+            Side::Bottom => -(1..=nx).map(|i| self.v_face(i, 0)).sum::<f64>() * self.dx,
+            Side::Top => (1..=nx).map(|i| self.v_face(i, ny)).sum::<f64>() * self.dx,
+        }
+    }
+
+    pub fn poisson_method(&self) -> PoissonMethod {
+        if self.spectral_eligible() {
+            PoissonMethod::Spectral
+        } else {
+            PoissonMethod::ConjugateGradient
+        }
+    }
+
+    /// Largest stable Δt for the current velocity field
+    pub fn stable_time_step(&self) -> f64 {
+        let viscous =
+            0.5 / (self.viscosity * (1.0 / (self.dx * self.dx) + 1.0 / (self.dy * self.dy)));
+        let [u_max, v_max] = self.max_speeds();
+        let mut dt = viscous;
+        if u_max > 0.0 {
+            dt = dt.min(self.dx / u_max);
+        }
+        if v_max > 0.0 {
+            dt = dt.min(self.dy / v_max);
+        }
+        self.safety * dt
+    }
+
+    /// Advance by Δt
+    pub fn step(&mut self, dt: f64) -> Result<(), NavierStokesError> {
+        if !(dt > 0.0 && dt.is_finite()) {
+            return Err(NavierStokesError::InvalidParameter(format!(
+                "time step {} must be positive",
+                dt
+            )));
+        }
+        if self.poisson.is_none() {
+            self.validate()?;
+            self.poisson = Some(self.build_poisson()?);
+        }
+        self.apply_boundaries();
+        self.compute_fg(dt);
+        let rhs = self.poisson_rhs(dt);
+        self.solve_pressure(rhs)?;
+        self.project(dt);
+        self.apply_boundaries();
+        self.time += dt;
+        self.steps += 1;
+        if self.u.iter().chain(&self.v).any(|x| !x.is_finite()) {
+            return Err(NavierStokesError::Diverged { step: self.steps });
+        }
+        Ok(())
+    }
+
+    /// Step with `stable_time_step` capped at `max_dt`, returning Δt
+    pub fn step_adaptive(&mut self, max_dt: f64) -> Result<f64, NavierStokesError> {
+        let dt = self.stable_time_step().min(max_dt);
+        self.step(dt)?;
+        Ok(dt)
+    }
+
+    /// Advance by `duration` with adaptive steps
+    pub fn advance(&mut self, duration: f64) -> Result<(), NavierStokesError> {
+        let end = self.time + duration;
+        while end - self.time > 1e-12 * end.abs().max(1.0) {
+            self.step_adaptive(end - self.time)?;
+        }
+        Ok(())
+    }
+
+    /// Largest |u| and |v| over the faces of the domain (ghosts excluded)
+    fn max_speeds(&self) -> [f64; 2] {
+        let mut max = [0.0_f64; 2];
+        for j in 0..=self.ny {
+            for i in 0..=self.nx {
+                let k = self.index(i, j);
+                if j > 0 {
+                    max[0] = max[0].max(self.u[k].abs());
+                }
+                if i > 0 {
+                    max[1] = max[1].max(self.v[k].abs());
+                }
+            }
+        }
+        max
+    }
+
+    fn validate(&self) -> Result<(), NavierStokesError> {
+        if !(self.safety > 0.0 && self.safety <= 1.0) {
+            return Err(NavierStokesError::InvalidParameter(format!(
+                "safety factor {} must lie in (0, 1]",
+                self.safety
+            )));
+        }
+        if let Some(gamma) = self.donor_cell {
+            if !(0.0..=1.0).contains(&gamma) {
+                return Err(NavierStokesError::InvalidParameter(format!(
+                    "donor-cell weight {} must lie in [0, 1]",
+                    gamma
+                )));
+            }
+        }
+        for side in Side::ALL {
+            let periodic = self.boundary(side) == FlowBoundary::Periodic;
+            if periodic != (self.boundary(side.opposite()) == FlowBoundary::Periodic) {
+                return Err(NavierStokesError::InvalidParameter(format!(
+                    "{:?} is periodic but {:?} is not",
+                    side,
+                    side.opposite()
+                )));
+            }
+        }
+        let fluid = (1..=self.ny).any(|j| (1..=self.nx).any(|i| !self.is_solid(i, j)));
+        if !fluid {
+            return Err(NavierStokesError::InvalidParameter(
+                "no fluid cells".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn spectral_eligible(&self) -> bool {
+        self.boundary(Side::Left) == FlowBoundary::Periodic
+            && self.boundary(Side::Bottom) == FlowBoundary::Periodic
+            && self.nx == self.ny
+            && self.nx.is_power_of_two()
+            && !self.solid.iter().any(|&s| s)
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // BOUNDARY VALUES
+    // ─────────────────────────────────────────────────────────────────────────
+
+    /// Inflow speed into the domain at fraction s ∈ (0, 1) along the side
+    fn inflow_speed(boundary: FlowBoundary, s: f64) -> f64 {
+        match boundary {
+            FlowBoundary::Inflow(speed) => speed,
+            FlowBoundary::ParabolicInflow(mean) => 6.0 * mean * s * (1.0 - s),
+            _ => 0.0,
+        }
+    }
+
+    /// Boundary faces and ghost values from the side conditions, then the
+    /// no-slip values inside obstacles
+    fn apply_boundaries(&mut self) {
+        let (nx, ny) = (self.nx, self.ny);
+        let row = nx + 2;
+        for j in 1..=ny {
+            let s = (j as f64 - 0.5) / ny as f64;
+            let (left, right) = (self.index(0, j), self.index(nx, j));
+            match self.boundary(Side::Left) {
+                FlowBoundary::Wall { tangential } => {
+                    self.u[left] = 0.0;
+                    self.v[left] = 2.0 * tangential - self.v[left + 1];
+                }
+                FlowBoundary::Outflow => self.v[left] = self.v[left + 1],
+                FlowBoundary::Periodic => {
+                    self.u[left] = self.u[right];
+                    self.v[left] = self.v[right];
+                    self.u[right + 1] = self.u[left + 1];
+                    self.v[right + 1] = self.v[left + 1];
+                }
+                inflow => {
+                    self.u[left] = Self::inflow_speed(inflow, s);
+                    self.v[left] = -self.v[left + 1];
+                }
+            }
+            match self.boundary(Side::Right) {
+                FlowBoundary::Wall { tangential } => {
+                    self.u[right] = 0.0;
+                    self.v[right + 1] = 2.0 * tangential - self.v[right];
+                }
+                FlowBoundary::Outflow => self.v[right + 1] = self.v[right],
+                FlowBoundary::Periodic => {}
+                inflow => {
+                    self.u[right] = -Self::inflow_speed(inflow, s);
+                    self.v[right + 1] = -self.v[right];
+                }
+            }
+        }
+        for i in 0..nx + 2 {
+            let s = (i as f64 - 0.5) / nx as f64;
+            let (bottom, top) = (self.index(i, 0), self.index(i, ny));
+            match self.boundary(Side::Bottom) {
+                FlowBoundary::Wall { tangential } => {
+                    self.v[bottom] = 0.0;
+                    self.u[bottom] = 2.0 * tangential - self.u[bottom + row];
+                }
+                FlowBoundary::Outflow => self.u[bottom] = self.u[bottom + row],
+                FlowBoundary::Periodic => {
+                    self.v[bottom] = self.v[top];
+                    self.u[bottom] = self.u[top];
+                    self.v[top + row] = self.v[bottom + row];
+                    self.u[top + row] = self.u[bottom + row];
+                }
+                inflow => {
+                    self.v[bottom] = Self::inflow_speed(inflow, s);
+                    self.u[bottom] = -self.u[bottom + row];
+                }
+            }
+            match self.boundary(Side::Top) {
+                FlowBoundary::Wall { tangential } => {
+                    self.v[top] = 0.0;
+                    self.u[top + row] = 2.0 * tangential - self.u[top];
+                }
+                FlowBoundary::Outflow => self.u[top + row] = self.u[top],
+                FlowBoundary::Periodic => {}
+                inflow => {
+                    self.v[top] = -Self::inflow_speed(inflow, s);
+                    self.u[top + row] = -self.u[top];
+                }
+            }
+        }
+        self.apply_obstacles();
+    }
+
+    /// Faces touching a solid cell carry no flux; faces buried in the obstacle
+    /// mirror the adjacent fluid face so the wall sits on the cell edge
+    fn apply_obstacles(&mut self) {
+        if !self.solid.iter().any(|&s| s) {
+            return;
+        }
+        let row = self.nx + 2;
+        let fluid_face = |solid: &[bool], a: usize, b: usize| !solid[a] && !solid[b];
+        for j in 1..=self.ny {
+            for i in 1..self.nx {
+                let k = self.index(i, j);
+                let (a, b) = (self.solid[k], self.solid[k + 1]);
+                if a && b {
+                    let mut sum = 0.0;
+                    let mut count = 0.0;
+                    for n in [k - row, k + row] {
+                        if fluid_face(&self.solid, n, n + 1) {
+                            sum += self.u[n];
+                            count += 1.0;
+                        }
+                    }
+                    self.u[k] = if count > 0.0 { -sum / count } else { 0.0 };
+                } else if a || b {
+                    self.u[k] = 0.0;
+                }
+            }
+        }
+        for j in 1..self.ny {
+            for i in 1..=self.nx {
+                let k = self.index(i, j);
+                let (a, b) = (self.solid[k], self.solid[k + row]);
+                if a && b {
+                    let mut sum = 0.0;
+                    let mut count = 0.0;
+                    for n in [k - 1, k + 1] {
+                        if fluid_face(&self.solid, n, n + row) {
+                            sum += self.v[n];
+                            count += 1.0;
+                        }
+                    }
+                    self.v[k] = if count > 0.0 { -sum / count } else { 0.0 };
+                } else if a || b {
+                    self.v[k] = 0.0;
+                }
+            }
+        }
+    }
+
+    // ─────────────────────────────────────────────────────────────────────────
+    // PREDICTOR, POISSON, PROJECTION
+    // ─────────────────────────────────────────────────────────────────────────
+
+    /// Faces updated by the momentum equation along x: i range for u, j range for v
+    fn free_u_range(&self) -> std::ops::RangeInclusive<usize> {
+        match self.boundary(Side::Right) {
+            FlowBoundary::Periodic => 1..=self.nx,
+            _ => 1..=self.nx - 1,
+        }
+    }
+
+    fn free_v_range(&self) -> std::ops::RangeInclusive<usize> {
+        match self.boundary(Side::Top) {
+            FlowBoundary::Periodic => 1..=self.ny,
+            _ => 1..=self.ny - 1,
+        }
+    }
+
+    fn compute_fg(&mut self, dt: f64) {
+        let (dx, dy, nu) = (self.dx, self.dy, self.viscosity);
+        let row = self.nx + 2;
+        let gamma = self.donor_cell.unwrap_or_else(|| {
+            // Explicit stability: u²Δt ≤ 2(ν + γ|u|Δx/2) along each axis
+            let [u_max, v_max] = self.max_speeds();
+            let needed = |speed: f64, h: f64| {
+                if speed > 0.0 {
+                    speed * dt / h - 2.0 * nu / (speed * h)
+                } else {
+                    0.0
+                }
+            };
+            needed(u_max, dx).max(needed(v_max, dy)).clamp(0.0, 1.0)
+        });
+        let (u, v) = (&self.u, &self.v);
+        self.f.copy_from_slice(u);
+        self.g.copy_from_slice(v);
+
+        for j in 1..=self.ny {
+            for i in self.free_u_range() {
+                let k = self.index(i, j);
+                if self.solid[k] || self.solid[k + 1] {
+                    continue;
+                }
+                let laplacian = (u[k + 1] - 2.0 * u[k] + u[k - 1]) / (dx * dx)
+                    + (u[k + row] - 2.0 * u[k] + u[k - row]) / (dy * dy);
+                let (east, west) = (0.5 * (u[k] + u[k + 1]), 0.5 * (u[k - 1] + u[k]));
+                let du2dx = (east * east - west * west) / dx
+                    + gamma / dx
+                        * (east.abs() * 0.5 * (u[k] - u[k + 1])
+                            - west.abs() * 0.5 * (u[k - 1] - u[k]));
+                let (north, south) = (0.5 * (v[k] + v[k + 1]), 0.5 * (v[k - row] + v[k - row + 1]));
+                let duvdy = (north * 0.5 * (u[k] + u[k + row]) - south * 0.5 * (u[k - row] + u[k]))
+                    / dy
+                    + gamma / dy
+                        * (north.abs() * 0.5 * (u[k] - u[k + row])
+                            - south.abs() * 0.5 * (u[k - row] - u[k]));
+                self.f[k] = u[k] + dt * (nu * laplacian - du2dx - duvdy + self.body_force[0]);
+            }
+        }
+        for j in self.free_v_range() {
+            for i in 1..=self.nx {
+                let k = self.index(i, j);
+                if self.solid[k] || self.solid[k + row] {
+                    continue;
+                }
+                let laplacian = (v[k + 1] - 2.0 * v[k] + v[k - 1]) / (dx * dx)
+                    + (v[k + row] - 2.0 * v[k] + v[k - row]) / (dy * dy);
+                let (north, south) = (0.5 * (v[k] + v[k + row]), 0.5 * (v[k - row] + v[k]));
+                let dv2dy = (north * north - south * south) / dy
+                    + gamma / dy
+                        * (north.abs() * 0.5 * (v[k] - v[k + row])
+                            - south.abs() * 0.5 * (v[k - row] - v[k]));
+                let (east, west) = (0.5 * (u[k] + u[k + row]), 0.5 * (u[k - 1] + u[k - 1 + row]));
+                let duvdx = (east * 0.5 * (v[k] + v[k + 1]) - west * 0.5 * (v[k - 1] + v[k])) / dx
+                    + gamma / dx
+                        * (east.abs() * 0.5 * (v[k] - v[k + 1])
+                            - west.abs() * 0.5 * (v[k - 1] - v[k]));
+                self.g[k] = v[k] + dt * (nu * laplacian - duvdx - dv2dy + self.body_force[1]);
+            }
+        }
+
+        // Boundary faces: fixed values stay, outflow copies the inner prediction,
+        // periodic faces are the same face seen from both sides
+        let (nx, ny) = (self.nx, self.ny);
+        for j in 1..=ny {
+            let (left, right) = (self.index(0, j), self.index(nx, j));
+            match self.boundary(Side::Left) {
+                FlowBoundary::Outflow => self.f[left] = self.f[left + 1],
+                FlowBoundary::Periodic => self.f[left] = self.f[right],
+                _ => {}
+            }
+            if self.boundary(Side::Right) == FlowBoundary::Outflow {
+                self.f[right] = self.f[right - 1];
+            }
+        }
+        for i in 1..=nx {
+            let (bottom, top) = (self.index(i, 0), self.index(i, ny));
+            match self.boundary(Side::Bottom) {
+                FlowBoundary::Outflow => self.g[bottom] = self.g[bottom + row],
+                FlowBoundary::Periodic => self.g[bottom] = self.g[top],
+                _ => {}
+            }
+            if self.boundary(Side::Top) == FlowBoundary::Outflow {
+                self.g[top] = self.g[top - row];
+            }
+        }
+    }
+
+    /// Neighbour of fluid cell (i, j) across the face in direction `side`
+    fn coupling(&self, i: usize, j: usize, side: Side) -> Coupling {
+        let (nx, ny) = (self.nx, self.ny);
+        let on_edge = match side {
+            Side::Left => i == 1,
+            Side::Right => i == nx,
+            Side::Bottom => j == 1,
+            Side::Top => j == ny,
+        };
+        if on_edge {
+            return match self.boundary(side) {
+                FlowBoundary::Outflow => Coupling::Dirichlet,
+                FlowBoundary::Periodic => Coupling::Cell(match side {
+                    Side::Left => self.index(nx, j),
+                    Side::Right => self.index(1, j),
+                    Side::Bottom => self.index(i, ny),
+                    Side::Top => self.index(i, 1),
+                }),
+                _ => Coupling::Neumann,
+            };
+        }
+        let n = match side {
+            Side::Left => self.index(i - 1, j),
+            Side::Right => self.index(i + 1, j),
+            Side::Bottom => self.index(i, j - 1),
+            Side::Top => self.index(i, j + 1),
+        };
+        if self.solid[n] {
+            Coupling::Neumann
+        } else {
+            Coupling::Cell(n)
+        }
+    }
+
+    fn build_poisson(&self) -> Result<Poisson, NavierStokesError> {
+        if self.spectral_eligible() {
+            let n = self.nx;
+            let mut inverse_eigenvalues = vec![0.0; n * n];
+            for ky in 0..n {
+                for kx in 0..n {
+                    let theta = |k: usize| 2.0 * std::f64::consts::PI * k as f64 / n as f64;
+                    let lambda = (2.0 - 2.0 * theta(kx).cos()) / (self.dx * self.dx)
+                        + (2.0 - 2.0 * theta(ky).cos()) / (self.dy * self.dy);
+                    if kx + ky > 0 {
+                        inverse_eigenvalues[ky * n + kx] = (-1.0 / lambda) as f32;
+                    }
+                }
+            }
+            return Ok(Poisson::Spectral {
+                fft: FFT2D::new(n),
+                inverse_eigenvalues,
+            });
+        }
+
+        let mut rows = vec![None; self.solid.len()];
+        let mut count = 0;
+        for j in 1..=self.ny {
+            for i in 1..=self.nx {
+                let k = self.index(i, j);
+                if !self.solid[k] {
+                    rows[k] = Some(count);
+                    count += 1;
+                }
+            }
+        }
+        let mut triplets = TripletMatrix::with_capacity(count, count, 5 * count);
+        let mut has_dirichlet = false;
+        for j in 1..=self.ny {
+            for i in 1..=self.nx {
+                let Some(r) = rows[self.index(i, j)] else {
+                    continue;
+                };
+                let mut diagonal = 0.0;
+                for side in Side::ALL {
+                    let h2 = match side {
+                        Side::Left | Side::Right => self.dx * self.dx,
+                        Side::Bottom | Side::Top => self.dy * self.dy,
+                    };
+                    match self.coupling(i, j, side) {
+                        Coupling::Cell(n) => {
+                            diagonal += 1.0 / h2;
+                            triplets.push(r, rows[n].expect("fluid neighbour"), -1.0 / h2);
+                        }
+                        // Ghost value -p puts p = 0 on the face
+                        Coupling::Dirichlet => {
+                            diagonal += 2.0 / h2;
+                            has_dirichlet = true;
+                        }
+                        Coupling::Neumann => {}
+                    }
+                }
+                triplets.push(r, r, diagonal);
+            }
+        }
+        let pinned = !has_dirichlet;
+        if pinned {
+            // Σ rows of the pure-Neumann operator vanish, so with a mean-free
+            // rhs this extra term forces p = 0 in the first cell and nothing else
+            let h2 = self.dx * self.dx;
+            triplets.push(0, 0, 1.0 / h2);
+        }
+        let matrix = triplets.to_csr();
+        let preconditioner = IncompleteCholesky::new(&matrix)?;
+        Ok(Poisson::Sparse {
+            matrix,
+            preconditioner,
+            rows,
+            pinned,
+        })
+    }
+
+    /// (∂F/∂x + ∂G/∂y)/Δt per storage index (zero outside the fluid)
+    fn poisson_rhs(&self, dt: f64) -> Vec<f64> {
+        let row = self.nx + 2;
+        let mut rhs = vec![0.0; self.solid.len()];
+        for j in 1..=self.ny {
+            for i in 1..=self.nx {
+                let k = self.index(i, j);
+                if !self.solid[k] {
+                    rhs[k] = ((self.f[k] - self.f[k - 1]) / self.dx
+                        + (self.g[k] - self.g[k - row]) / self.dy)
+                        / dt;
+                }
+            }
+        }
+        rhs
+    }
+
+    fn solve_pressure(&mut self, rhs: Vec<f64>) -> Result<(), NavierStokesError> {
+        let (nx, ny) = (self.nx, self.ny);
+        match self.poisson.as_ref().expect("poisson operator built") {
+            Poisson::Spectral {
+                fft,
+                inverse_eigenvalues,
+            } => {
+                let mut real = vec![0.0_f32; nx * ny];
+                let mut imag = vec![0.0_f32; nx * ny];
+                for j in 1..=ny {
+                    for i in 1..=nx {
+                        real[(j - 1) * nx + i - 1] = rhs[self.index(i, j)] as f32;
+                    }
+                }
+                fft.forward(&mut real, &mut imag);
+                for ((re, im), scale) in real.iter_mut().zip(&mut imag).zip(inverse_eigenvalues) {
+                    *re *= scale;
+                    *im *= scale;
+                }
+                fft.inverse(&mut real, &mut imag);
+                for j in 1..=ny {
+                    for i in 1..=nx {
+                        let k = self.index(i, j);
+                        self.p[k] = real[(j - 1) * nx + i - 1] as f64;
+                    }
+                }
+            }
+            Poisson::Sparse {
+                matrix,
+                preconditioner,
+                rows,
+                pinned,
+            } => {
+                let count = matrix.nrows();
+                let mut b = vec![0.0; count];
+                let mut x0 = vec![0.0; count];
+                for (k, r) in rows.iter().enumerate() {
+                    if let Some(r) = *r {
+                        b[r] = -rhs[k];
+                        x0[r] = self.p[k];
+                    }
+                }
+                if *pinned {
+                    let mean = b.iter().sum::<f64>() / count as f64;
+                    b.iter_mut().for_each(|x| *x -= mean);
+                }
+                let solution = conjugate_gradient(
+                    matrix,
+                    &b,
+                    Some(&x0),
+                    preconditioner,
+                    &self.linear_options,
+                )?
+                .into_result()?;
+                for (k, r) in rows.iter().enumerate() {
+                    if let Some(r) = *r {
+                        self.p[k] = solution.x[r];
+                    }
+                }
+            }
+        }
+        self.apply_pressure_ghosts();
+        Ok(())
+    }
+
+    /// Ghost pressures for the boundary-face gradients used by the projection
+    fn apply_pressure_ghosts(&mut self) {
+        let (nx, ny) = (self.nx, self.ny);
+        let row = nx + 2;
+        for j in 1..=ny {
+            let (left, right) = (self.index(0, j), self.index(nx + 1, j));
+            match self.boundary(Side::Left) {
+                FlowBoundary::Periodic => {
+                    self.p[left] = self.p[right - 1];
+                    self.p[right] = self.p[left + 1];
+                }
+                FlowBoundary::Outflow => self.p[left] = -self.p[left + 1],
+                _ => self.p[left] = self.p[left + 1],
+            }
+            match self.boundary(Side::Right) {
+                FlowBoundary::Periodic => {}
+                FlowBoundary::Outflow => self.p[right] = -self.p[right - 1],
+                _ => self.p[right] = self.p[right - 1],
+            }
+        }
+        for i in 1..=nx {
+            let (bottom, top) = (self.index(i, 0), self.index(i, ny + 1));
+            match self.boundary(Side::Bottom) {
+                FlowBoundary::Periodic => {
+                    self.p[bottom] = self.p[top - row];
+                    self.p[top] = self.p[bottom + row];
+                }
+                FlowBoundary::Outflow => self.p[bottom] = -self.p[bottom + row],
+                _ => self.p[bottom] = self.p[bottom + row],
+            }
+            match self.boundary(Side::Top) {
+                FlowBoundary::Periodic => {}
+                FlowBoundary::Outflow => self.p[top] = -self.p[top - row],
+                _ => self.p[top] = self.p[top - row],
+            }
+        }
+    }
+
+    /// u = F - Δt ∂p/∂x on every face not fixed by a wall, inflow or obstacle
+    fn project(&mut self, dt: f64) {
+        let (nx, ny) = (self.nx, self.ny);
+        let row = nx + 2;
+        let u_open = |b: FlowBoundary| matches!(b, FlowBoundary::Outflow | FlowBoundary::Periodic);
+        let i_start = if u_open(self.boundary(Side::Left)) {
+            0
+        } else {
+            1
+        };
+        let i_end = if u_open(self.boundary(Side::Right)) {
+            nx
+        } else {
+            nx - 1
+        };
+        let j_start = if u_open(self.boundary(Side::Bottom)) {
+            0
+        } else {
+            1
+        };
+        let j_end = if u_open(self.boundary(Side::Top)) {
+            ny
+        } else {
+            ny - 1
+        };
+        for j in 1..=ny {
+            for i in i_start..=i_end {
+                let k = self.index(i, j);
+                if !(self.solid[k] || self.solid[k + 1]) {
+                    self.u[k] = self.f[k] - dt * (self.p[k + 1] - self.p[k]) / self.dx;
+                }
+            }
+        }
+        for j in j_start..=j_end {
+            for i in 1..=nx {
+                let k = self.index(i, j);
+                if !(self.solid[k] || self.solid[k + row]) {
+                    self.v[k] = self.g[k] - dt * (self.p[k + row] - self.p[k]) / self.dy;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physics::fluids::lattice_boltzmann::tests::{interpolate, GHIA_U};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_taylor_green_decay_spectral() {
+        let (n, nu) = (32, 0.05);
+        let mut ns = NavierStokes2D::new(n, n, 2.0 * PI, 2.0 * PI, nu)
+            .unwrap()
+            .with_boundary(Side::Left, FlowBoundary::Periodic)
+            .with_boundary(Side::Right, FlowBoundary::Periodic)
+            .with_boundary(Side::Bottom, FlowBoundary::Periodic)
+            .with_boundary(Side::Top, FlowBoundary::Periodic);
+        assert_eq!(ns.poisson_method(), PoissonMethod::Spectral);
+        ns.set_velocity(|x, y| [x.sin() * y.cos(), -x.cos() * y.sin()]);
+        let energy = ns.kinetic_energy();
+        ns.advance(1.0).unwrap();
+        // E(t) = E(0)·exp(-4νt)
+        let ratio = ns.kinetic_energy() / energy;
+        let expected = (-4.0 * nu * ns.time).exp();
+        assert!(
+            (ratio - expected).abs() / expected < 0.01,
+            "{} vs {}",
+            ratio,
+            expected
+        );
+        assert!(ns.max_divergence() < 1e-4);
+    }
+
+    #[test]
+    fn test_periodic_channel_poiseuille() {
+        // Body-force driven flow between walls: u(y) = g·y(H - y)/(2ν)
+        let (nu, force) = (0.1, 0.8);
+        let mut ns = NavierStokes2D::new(4, 16, 0.25, 1.0, nu)
+            .unwrap()
+            .with_boundary(Side::Left, FlowBoundary::Periodic)
+            .with_boundary(Side::Right, FlowBoundary::Periodic)
+            .with_body_force([force, 0.0]);
+        assert_eq!(ns.poisson_method(), PoissonMethod::ConjugateGradient);
+        ns.advance(6.0).unwrap();
+        for j in 1..=16 {
+            let y = ns.center(1, j)[1];
+            let exact = force * y * (1.0 - y) / (2.0 * nu);
+            assert!((ns.u_face(2, j) - exact).abs() < 0.01, "j = {}", j);
+            assert!(ns.v_face(2, j).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_lid_driven_cavity_matches_ghia() {
+        let n = 24;
+        let mut ns = NavierStokes2D::lid_driven_cavity(n, 100.0, 1.0).unwrap();
+        ns.advance(6.0).unwrap();
+        assert!(ns.max_divergence() < 1e-6);
+        // u faces on the vertical centreline x = ½ are u(n/2, j)
+        let profile: Vec<(f64, f64)> = (0..=n + 1)
+            .map(|j| {
+                let y = (j as f64 - 0.5).clamp(0.0, n as f64) / n as f64;
+                let u = match j {
+                    0 => 0.0,
+                    j if j == n + 1 => 1.0,
+                    j => ns.u_face(n / 2, j),
+                };
+                (y, u)
+            })
+            .collect();
+        for (y, u) in GHIA_U {
+            let error = (interpolate(&profile, y) - u).abs();
+            assert!(error < 0.03, "y = {}: error {}", y, error);
+        }
+    }
+
+    #[test]
+    fn test_cylinder_wake_recirculation() {
+        // Schäfer-Turek channel at Re = 20 (steady, closed recirculation bubble)
+        let (length, height, d) = (2.2, 0.41, 0.1);
+        let mut ns =
+            NavierStokes2D::cylinder_channel(88, 16, length, height, [0.2, 0.2], d, 20.0, 0.2)
+                .unwrap()
+                .with_safety_factor(0.8);
+        ns.advance(6.0).unwrap();
+        assert!(ns.max_divergence() < 1e-6);
+        // Mass in = mass out
+        let inflow = -ns.outflow_rate(Side::Left);
+        assert!((inflow - 0.2 * height).abs() < 1e-2 * inflow);
+        assert!((ns.outflow_rate(Side::Right) - inflow).abs() < 1e-6);
+        // Reversed flow just behind the cylinder, recovered one diameter later
+        let (dx, dy) = ns.spacing();
+        let j = (0.2 / dy).ceil() as usize;
+        let behind = ((0.2 + 0.5 * d) / dx) as usize + 1;
+        assert!(ns.velocity(behind, j)[0] < 0.0);
+        assert!(ns.velocity(behind + (2.0 * d / dx) as usize, j)[0] > 0.0);
+    }
+
+    #[test]
+    fn test_invalid_setup() {
+        assert!(NavierStokes2D::new(1, 8, 1.0, 1.0, 0.1).is_err());
+        assert!(NavierStokes2D::new(8, 8, 1.0, 1.0, 0.0).is_err());
+        let mut ns = NavierStokes2D::new(8, 8, 1.0, 1.0, 0.1)
+            .unwrap()
+            .with_boundary(Side::Left, FlowBoundary::Periodic);
+        assert!(matches!(
+            ns.step(0.01),
+            Err(NavierStokesError::InvalidParameter(_))
+        ));
+        let mut ns = NavierStokes2D::new(8, 8, 1.0, 1.0, 0.1).unwrap();
+        assert!(ns.step(-1.0).is_err());
+        ns.add_obstacle(|_, _| true);
+        assert!(ns.step(0.01).is_err());
+    }
+}
@@ -0,0 +1,1047 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: collision.rs | DNA/src/physics/mechanics/collision.rs
+//! PURPOSE: Broad phase, GJK / EPA, box-box SAT and contact manifolds
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! PURPOSE: Find touching pairs of convex rigid bodies and their contact points
+//!
+//! LAYER: DNA → PHYSICS → MECHANICS
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ ALGORITHM                                                                   │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ Broad phase   world AABBs from the support function, sweep and prune on     │
+//! │               the axis where the box centres spread the most                │
+//! │ GJK           closest point of the Minkowski difference A - B to the        │
+//! │               origin, simplex reduced with Voronoi regions (Ericson)        │
+//! │ EPA           expand the GJK tetrahedron to the face of A - B closest to    │
+//! │               the origin: normal, depth and barycentric witness points      │
+//! │ Core + radius spheres and capsules run GJK on their centre point / segment  │
+//! │               and add the radii; EPA only when the cores overlap            │
+//! │ SAT box-box   15 axes (3 + 3 faces, 9 edge pairs), faces preferred; the     │
+//! │               incident face is clipped against the reference face sides    │
+//! │ Manifold      up to 4 points per pair; single-point results are merged      │
+//! │               with last step's points, impulses carried for warm starting   │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ DATA DEFINED                                                                │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ Aabb              Axis-aligned bounding box                                 │
+//! │ Gjk               Separated (distance, witnesses) or intersecting simplex   │
+//! │ Penetration       EPA result                                                │
+//! │ ContactPoint      Witness points on A and B, penetration depth              │
+//! │ ContactSet        Normal A → B and the points of one narrow-phase query     │
+//! │ ContactManifold   Persistent points with accumulated impulses               │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! Normals point from body A to body B; depth > 0 means overlap.
+//!
+//! REFERENCE: Ericson, "Real-Time Collision Detection" (2005), ch. 4.4, 5.1, 9.5
+//!            van den Bergen, "Collision Detection in Interactive 3D
+//!            Environments" (2004), ch. 4
+//!            Gregorius, "Robust Contact Creation for Physics Simulations"
+//!            (GDC 2015)
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use super::rigid_body::{RigidBody, Shape};
+use glam::DVec3;
+
+const GJK_ITERATIONS: usize = 64;
+const EPA_ITERATIONS: usize = 64;
+const MAX_MANIFOLD_POINTS: usize = 4;
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// BROAD PHASE
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// Axis-aligned bounding box
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Aabb {
+    pub min: DVec3,
+    pub max: DVec3,
+}
+
+impl Aabb {
+    /// Tight world box of a body (supports along ±x, ±y, ±z)
+    pub fn of(body: &RigidBody) -> Self {
+        let axis = |d: DVec3| body.support(d).dot(d);
+        Self {
+            min: DVec3::new(-axis(-DVec3::X), -axis(-DVec3::Y), -axis(-DVec3::Z)),
+            max: DVec3::new(axis(DVec3::X), axis(DVec3::Y), axis(DVec3::Z)),
+        }
+    }
+
+    pub fn expanded(&self, margin: f64) -> Self {
+        Self {
+            min: self.min - DVec3::splat(margin),
+            max: self.max + DVec3::splat(margin),
+        }
+    }
+
+    pub fn center(&self) -> DVec3 {
+        0.5 * (self.min + self.max)
+    }
+
+    pub fn overlaps(&self, other: &Aabb) -> bool {
+        self.min.cmple(other.max).all() && other.min.cmple(self.max).all()
+    }
+}
+
+/// Overlapping pairs (i < j) by sweep and prune
+pub fn broad_phase(boxes: &[Aabb]) -> Vec<(usize, usize)> {
+    if boxes.len() < 2 {
+        return Vec::new();
+    }
+    // Sweep along the axis of largest centre variance
+    let n = boxes.len() as f64;
+    let mean = boxes.iter().map(Aabb::center).sum::<DVec3>() / n;
+    let variance = boxes
+        .iter()
+        .map(|b| (b.center() - mean) * (b.center() - mean))
+        .sum::<DVec3>();
+    let axis = if variance.x >= variance.y && variance.x >= variance.z {
+        0
+    } else if variance.y >= variance.z {
+        1
+    } else {
+        2
+    };
+    let mut order: Vec<usize> = (0..boxes.len()).collect();
+    order.sort_by(|&a, &b| boxes[a].min[axis].total_cmp(&boxes[b].min[axis]));
+
+    let mut pairs = Vec::new();
+    for (k, &i) in order.iter().enumerate() {
+        for &j in &order[k + 1..] {
+            if boxes[j].min[axis] > boxes[i].max[axis] {
+                break;
+            }
+            if boxes[i].overlaps(&boxes[j]) {
+                pairs.push((i.min(j), i.max(j)));
+            }
+        }
+    }
+    pairs.sort_unstable();
+    pairs
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// GJK
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// Vertex of the Minkowski difference with the points that produced it
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SupportPoint {
+    /// a - b
+    pub w: DVec3,
+    pub a: DVec3,
+    pub b: DVec3,
+}
+
+fn support_point(
+    support_a: &impl Fn(DVec3) -> DVec3,
+    support_b: &impl Fn(DVec3) -> DVec3,
+    direction: DVec3,
+) -> SupportPoint {
+    let a = support_a(direction);
+    let b = support_b(-direction);
+    SupportPoint { w: a - b, a, b }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Gjk {
+    Separated {
+        distance: f64,
+        point_a: DVec3,
+        point_b: DVec3,
+    },
+    /// Final simplex (1 to 4 points) containing the origin
+    Intersecting(Vec<SupportPoint>),
+}
+
+/// Closest points of two convex sets given by world support functions
+pub fn gjk(support_a: impl Fn(DVec3) -> DVec3, support_b: impl Fn(DVec3) -> DVec3) -> Gjk {
+    let first = support_point(&support_a, &support_b, DVec3::X);
+    let mut simplex = vec![first];
+    let scale = first.w.length().max(1.0);
+    let mut best = (f64::INFINITY, vec![(first, 1.0)]);
+
+    for _ in 0..GJK_ITERATIONS {
+        let Some(weighted) = closest_on_simplex(&simplex) else {
+            return Gjk::Intersecting(simplex);
+        };
+        let v: DVec3 = weighted.iter().map(|(p, l)| p.w * *l).sum();
+        let distance_sq = v.length_squared();
+        if distance_sq < (1e-10 * scale).powi(2) {
+            simplex = weighted.iter().map(|(p, _)| *p).collect();
+            return Gjk::Intersecting(simplex);
+        }
+        if distance_sq >= best.0 {
+            // No progress (numerical limit): keep the best estimate
+            break;
+        }
+        best = (distance_sq, weighted.clone());
+        simplex = weighted.iter().map(|(p, _)| *p).collect();
+
+        let w = support_point(&support_a, &support_b, -v);
+        let duplicate = simplex
+            .iter()
+            .any(|p| (p.w - w.w).length_squared() <= 1e-24 * scale * scale);
+        if duplicate || distance_sq - v.dot(w.w) <= 1e-12 * distance_sq {
+            break;
+        }
+        simplex.push(w);
+    }
+
+    let (distance_sq, weighted) = best;
+    Gjk::Separated {
+        distance: distance_sq.sqrt(),
+        point_a: weighted.iter().map(|(p, l)| p.a * *l).sum(),
+        point_b: weighted.iter().map(|(p, l)| p.b * *l).sum(),
+    }
+}
+
+/// Sub-simplex closest to the origin with barycentric weights, or `None`
+/// when the origin lies inside a tetrahedron
+fn closest_on_simplex(simplex: &[SupportPoint]) -> Option<Vec<(SupportPoint, f64)>> {
+    match simplex.len() {
+        1 => Some(vec![(simplex[0], 1.0)]),
+        2 => Some(closest_on_segment(simplex[0], simplex[1])),
+        3 => Some(closest_on_triangle(simplex[0], simplex[1], simplex[2])),
+        _ => closest_on_tetrahedron(simplex),
+    }
+}
+
+fn closest_on_segment(a: SupportPoint, b: SupportPoint) -> Vec<(SupportPoint, f64)> {
+    let ab = b.w - a.w;
+    let t = -a.w.dot(ab) / ab.length_squared().max(f64::MIN_POSITIVE);
+    if t <= 0.0 {
+        vec![(a, 1.0)]
+    } else if t >= 1.0 {
+        vec![(b, 1.0)]
+    } else {
+        vec![(a, 1.0 - t), (b, t)]
+    }
+}
+
+/// Ericson 5.1.5 with p = origin
+fn closest_on_triangle(
+    a: SupportPoint,
+    b: SupportPoint,
+    c: SupportPoint,
+) -> Vec<(SupportPoint, f64)> {
+    let (ab, ac) = (b.w - a.w, c.w - a.w);
+    let (d1, d2) = (-ab.dot(a.w), -ac.dot(a.w));
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return vec![(a, 1.0)];
+    }
+    let (d3, d4) = (-ab.dot(b.w), -ac.dot(b.w));
+    if d3 >= 0.0 && d4 <= d3 {
+        return vec![(b, 1.0)];
+    }
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        let v = d1 / (d1 - d3);
+        return vec![(a, 1.0 - v), (b, v)];
+    }
+    let (d5, d6) = (-ab.dot(c.w), -ac.dot(c.w));
+    if d6 >= 0.0 && d5 <= d6 {
+        return vec![(c, 1.0)];
+    }
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        let w = d2 / (d2 - d6);
+        return vec![(a, 1.0 - w), (c, w)];
+    }
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && d4 - d3 >= 0.0 && d5 - d6 >= 0.0 {
+        let w = (d4 - d3) / ((d4 - d3) + (d5 - d6));
+        return vec![(b, 1.0 - w), (c, w)];
+    }
+    let denominator = va + vb + vc;
+    if denominator.abs() < f64::MIN_POSITIVE {
+        // Degenerate (collinear) triangle
+        return closest_on_segment(a, b);
+    }
+    let (v, w) = (vb / denominator, vc / denominator);
+    vec![(a, 1.0 - v - w), (b, v), (c, w)]
+}
+
+fn closest_on_tetrahedron(simplex: &[SupportPoint]) -> Option<Vec<(SupportPoint, f64)>> {
+    let [a, b, c, d] = [simplex[0], simplex[1], simplex[2], simplex[3]];
+    let volume = (b.w - a.w).dot((c.w - a.w).cross(d.w - a.w));
+    let scale = (b.w - a.w).length() * (c.w - a.w).length() * (d.w - a.w).length();
+    if volume.abs() <= 1e-12 * scale {
+        // Flat tetrahedron: the newest point added nothing
+        return Some(closest_on_triangle(a, b, c));
+    }
+    let faces = [(a, b, c, d), (a, c, d, b), (a, d, b, c), (b, d, c, a)];
+    let mut best: Option<(f64, Vec<(SupportPoint, f64)>)> = None;
+    for (p, q, r, opposite) in faces {
+        let normal = (q.w - p.w).cross(r.w - p.w);
+        // Origin outside this face: opposite vertex and origin on different sides
+        if normal.dot(-p.w) * normal.dot(opposite.w - p.w) < 0.0 {
+            let weighted = closest_on_triangle(p, q, r);
+            let v: DVec3 = weighted.iter().map(|(s, l)| s.w * *l).sum();
+            let distance = v.length_squared();
+            if best.as_ref().is_none_or(|(d, _)| distance < *d) {
+                best = Some((distance, weighted));
+            }
+        }
+    }
+    best.map(|(_, weighted)| weighted)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// EPA
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// Minimum translation of two overlapping convex sets
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Penetration {
+    /// Unit normal from A towards B
+    pub normal: DVec3,
+    pub depth: f64,
+    /// Deepest point of A inside B and of B inside A
+    pub point_a: DVec3,
+    pub point_b: DVec3,
+}
+
+struct EpaFace {
+    vertices: [usize; 3],
+    normal: DVec3,
+    distance: f64,
+}
+
+/// Expanding polytope from an intersecting GJK simplex
+pub fn epa(
+    support_a: impl Fn(DVec3) -> DVec3,
+    support_b: impl Fn(DVec3) -> DVec3,
+    simplex: &[SupportPoint],
+) -> Option<Penetration> {
+    let mut points = simplex.to_vec();
+    complete_tetrahedron(&support_a, &support_b, &mut points)?;
+    let center = points.iter().map(|p| p.w).sum::<DVec3>() / 4.0;
+    let scale = points
+        .iter()
+        .fold(0.0_f64, |m, p| m.max(p.w.length()))
+        .max(1e-9);
+
+    let make_face = |points: &[SupportPoint], [i, j, k]: [usize; 3], inside: DVec3| {
+        let normal = (points[j].w - points[i].w)
+            .cross(points[k].w - points[i].w)
+            .normalize_or_zero();
+        let (vertices, normal) = if normal.dot(points[i].w - inside) < 0.0 {
+            ([i, k, j], -normal)
+        } else {
+            ([i, j, k], normal)
+        };
+        EpaFace {
+            vertices,
+            normal,
+            distance: normal.dot(points[vertices[0]].w),
+        }
+    };
+    let mut faces: Vec<EpaFace> = [[0, 1, 2], [0, 3, 1], [0, 2, 3], [1, 3, 2]]
+        .into_iter()
+        .map(|f| make_face(&points, f, center))
+        .collect();
+
+    for _ in 0..EPA_ITERATIONS {
+        let (closest, _) = faces
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| f.normal != DVec3::ZERO)
+            .min_by(|a, b| a.1.distance.total_cmp(&b.1.distance))?;
+        let face = &faces[closest];
+        let w = support_point(&support_a, &support_b, face.normal);
+        if w.w.dot(face.normal) - face.distance <= 1e-9 * scale {
+            break;
+        }
+        // Remove faces seen from w and stitch the horizon to it
+        let mut horizon: Vec<[usize; 2]> = Vec::new();
+        faces.retain(|f| {
+            if f.normal.dot(w.w - points[f.vertices[0]].w) <= 0.0 {
+                return true;
+            }
+            for e in 0..3 {
+                let edge = [f.vertices[e], f.vertices[(e + 1) % 3]];
+                if let Some(shared) = horizon.iter().position(|h| *h == [edge[1], edge[0]]) {
+                    horizon.swap_remove(shared);
+                } else {
+                    horizon.push(edge);
+                }
+            }
+            false
+        });
+        let new = points.len();
+        points.push(w);
+        for [i, j] in horizon {
+            faces.push(make_face(&points, [i, j, new], center));
+        }
+    }
+
+    let face = faces
+        .iter()
+        .filter(|f| f.normal != DVec3::ZERO)
+        .min_by(|a, b| a.distance.total_cmp(&b.distance))?;
+    let [i, j, k] = face.vertices;
+    let weights = barycentric(
+        face.normal * face.distance,
+        points[i].w,
+        points[j].w,
+        points[k].w,
+    );
+    let blend = |f: fn(&SupportPoint) -> DVec3| {
+        f(&points[i]) * weights[0] + f(&points[j]) * weights[1] + f(&points[k]) * weights[2]
+    };
+    Some(Penetration {
+        normal: face.normal,
+        depth: face.distance.max(0.0),
+        point_a: blend(|p| p.a),
+        point_b: blend(|p| p.b),
+    })
+}
+
+/// Grow a touching GJK simplex to a non-degenerate tetrahedron
+fn complete_tetrahedron(
+    support_a: &impl Fn(DVec3) -> DVec3,
+    support_b: &impl Fn(DVec3) -> DVec3,
+    points: &mut Vec<SupportPoint>,
+) -> Option<()> {
+    let axes = [
+        DVec3::X,
+        DVec3::Y,
+        DVec3::Z,
+        -DVec3::X,
+        -DVec3::Y,
+        -DVec3::Z,
+    ];
+    if points.len() == 1 {
+        let p = points[0];
+        let next = axes
+            .iter()
+            .map(|&d| support_point(support_a, support_b, d))
+            .find(|s| (s.w - p.w).length_squared() > 1e-18)?;
+        points.push(next);
+    }
+    if points.len() == 2 {
+        let line = (points[1].w - points[0].w).normalize();
+        let least = if line.x.abs() < 0.57 {
+            DVec3::X
+        } else if line.y.abs() < 0.57 {
+            DVec3::Y
+        } else {
+            DVec3::Z
+        };
+        let start = line.cross(least).normalize();
+        let next = (0..6)
+            .map(|k| {
+                let rotation =
+                    glam::DQuat::from_axis_angle(line, k as f64 * std::f64::consts::PI / 3.0);
+                support_point(support_a, support_b, rotation * start)
+            })
+            .find(|s| (s.w - points[0].w).cross(line).length_squared() > 1e-18)?;
+        points.push(next);
+    }
+    if points.len() == 3 {
+        let normal = (points[1].w - points[0].w).cross(points[2].w - points[0].w);
+        let next = [normal, -normal]
+            .iter()
+            .map(|&d| support_point(support_a, support_b, d))
+            .find(|s| normal.dot(s.w - points[0].w).abs() > 1e-12 * normal.length())?;
+        points.push(next);
+    }
+    Some(())
+}
+
+/// Barycentric coordinates of p (in the plane of abc)
+fn barycentric(p: DVec3, a: DVec3, b: DVec3, c: DVec3) -> [f64; 3] {
+    let (v0, v1, v2) = (b - a, c - a, p - a);
+    let (d00, d01, d11) = (v0.dot(v0), v0.dot(v1), v1.dot(v1));
+    let (d20, d21) = (v2.dot(v0), v2.dot(v1));
+    let denominator = d00 * d11 - d01 * d01;
+    if denominator.abs() < f64::MIN_POSITIVE {
+        return [1.0, 0.0, 0.0];
+    }
+    let v = (d11 * d20 - d01 * d21) / denominator;
+    let w = (d00 * d21 - d01 * d20) / denominator;
+    [1.0 - v - w, v, w]
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// NARROW PHASE
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// One contact: witness points on each surface
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ContactPoint {
+    pub point_a: DVec3,
+    pub point_b: DVec3,
+    /// (point_a - point_b)·normal; negative while still apart (speculative)
+    pub depth: f64,
+}
+
+/// Result of one narrow-phase query
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContactSet {
+    /// Unit normal from A to B
+    pub normal: DVec3,
+    pub points: Vec<ContactPoint>,
+    /// True when `points` is the whole manifold (no need to keep old points)
+    pub complete: bool,
+}
+
+/// Contacts between two bodies closer than `margin`, if any
+pub fn collide(a: &RigidBody, b: &RigidBody, margin: f64) -> Option<ContactSet> {
+    if let (Shape::Cuboid { .. }, Shape::Cuboid { .. }) = (&a.shape, &b.shape) {
+        return box_box(a, b, margin);
+    }
+    let (core_a, radius_a) = core(a);
+    let (core_b, radius_b) = core(b);
+    let complete =
+        matches!(a.shape, Shape::Sphere { .. }) || matches!(b.shape, Shape::Sphere { .. });
+    let radii = radius_a + radius_b;
+    match gjk(core_a, core_b) {
+        Gjk::Separated {
+            distance,
+            point_a,
+            point_b,
+        } if distance > 1e-9 => {
+            if distance - radii > margin {
+                return None;
+            }
+            let normal = (point_b - point_a) / distance;
+            Some(ContactSet {
+                normal,
+                points: vec![ContactPoint {
+                    point_a: point_a + normal * radius_a,
+                    point_b: point_b - normal * radius_b,
+                    depth: radii - distance,
+                }],
+                complete,
+            })
+        }
+        Gjk::Separated { .. } => {
+            // Cores touch: treat as a zero-distance intersection
+            let simplex = vec![support_point(
+                &|d| a.support(d),
+                &|d| b.support(d),
+                DVec3::X,
+            )];
+            penetration_contact(a, b, &simplex, complete)
+        }
+        Gjk::Intersecting(simplex) => {
+            // Re-run on the full shapes: cores overlap, so they do too
+            let full = match gjk(|d| a.support(d), |d| b.support(d)) {
+                Gjk::Intersecting(full) => full,
+                Gjk::Separated { .. } => simplex,
+            };
+            penetration_contact(a, b, &full, complete)
+        }
+    }
+}
+
+fn penetration_contact(
+    a: &RigidBody,
+    b: &RigidBody,
+    simplex: &[SupportPoint],
+    complete: bool,
+) -> Option<ContactSet> {
+    let penetration = epa(|d| a.support(d), |d| b.support(d), simplex)?;
+    Some(ContactSet {
+        normal: penetration.normal,
+        points: vec![ContactPoint {
+            point_a: penetration.point_a,
+            point_b: penetration.point_b,
+            depth: penetration.depth,
+        }],
+        complete,
+    })
+}
+
+/// Support function of the inner core and the radius swept around it
+fn core(body: &RigidBody) -> (impl Fn(DVec3) -> DVec3 + '_, f64) {
+    let radius = match body.shape {
+        Shape::Sphere { radius } | Shape::Capsule { radius, .. } => radius,
+        _ => 0.0,
+    };
+    let support = move |d: DVec3| match body.shape {
+        Shape::Sphere { .. } => body.position,
+        Shape::Capsule { half_height, .. } => {
+            let axis = body.orientation * DVec3::Y;
+            body.position + axis * half_height.copysign(axis.dot(d))
+        }
+        _ => body.support(d),
+    };
+    (support, radius)
+}
+
+/// Separating axis test between two cuboids with face clipping
+fn box_box(a: &RigidBody, b: &RigidBody, margin: f64) -> Option<ContactSet> {
+    let (Shape::Cuboid { half_extents: ha }, Shape::Cuboid { half_extents: hb }) =
+        (&a.shape, &b.shape)
+    else {
+        return None;
+    };
+    let (ra, rb) = (a.rotation(), b.rotation());
+    let axes_a = [ra.x_axis, ra.y_axis, ra.z_axis];
+    let axes_b = [rb.x_axis, rb.y_axis, rb.z_axis];
+    let t = b.position - a.position;
+    let project = |axes: &[DVec3; 3], h: DVec3, l: DVec3| {
+        h.x * axes[0].dot(l).abs() + h.y * axes[1].dot(l).abs() + h.z * axes[2].dot(l).abs()
+    };
+    // Separation along l (negative = overlap)
+    let separation =
+        |l: DVec3| t.dot(l).abs() - project(&axes_a, *ha, l) - project(&axes_b, *hb, l);
+
+    let mut face_a = (f64::NEG_INFINITY, 0);
+    let mut face_b = (f64::NEG_INFINITY, 0);
+    for i in 0..3 {
+        let s = separation(axes_a[i]);
+        if s > margin {
+            return None;
+        }
+        if s > face_a.0 {
+            face_a = (s, i);
+        }
+        let s = separation(axes_b[i]);
+        if s > margin {
+            return None;
+        }
+        if s > face_b.0 {
+            face_b = (s, i);
+        }
+    }
+    let mut edge = (f64::NEG_INFINITY, 0, 0, DVec3::ZERO);
+    for (i, axis_a) in axes_a.iter().enumerate() {
+        for (j, axis_b) in axes_b.iter().enumerate() {
+            let l = axis_a.cross(*axis_b);
+            if l.length_squared() < 1e-12 {
+                continue;
+            }
+            let l = l.normalize();
+            let s = separation(l);
+            if s > margin {
+                return None;
+            }
+            if s > edge.0 {
+                edge = (s, i, j, l);
+            }
+        }
+    }
+
+    // Prefer face contacts unless an edge axis is clearly better
+    const RELATIVE: f64 = 0.95;
+    const ABSOLUTE: f64 = 0.01;
+    let face_sep = face_a.0.max(face_b.0);
+    let tolerance = ABSOLUTE * ha.min_element().min(hb.min_element());
+    if edge.0 > RELATIVE * face_sep + tolerance {
+        let (_, i, j, l) = edge;
+        let normal = if l.dot(t) < 0.0 { -l } else { l };
+        // Supporting edges: A's edge along a_i towards B, B's edge along b_j towards A
+        let mut center_a = a.position;
+        let mut center_b = b.position;
+        for k in 0..3 {
+            if k != i {
+                center_a += axes_a[k] * ha[k].copysign(axes_a[k].dot(normal));
+            }
+            if k != j {
+                center_b -= axes_b[k] * hb[k].copysign(axes_b[k].dot(normal));
+            }
+        }
+        let (point_a, point_b) =
+            closest_between_segments(center_a, axes_a[i] * ha[i], center_b, axes_b[j] * hb[j]);
+        return Some(ContactSet {
+            normal,
+            points: vec![ContactPoint {
+                point_a,
+                point_b,
+                depth: (point_a - point_b).dot(normal),
+            }],
+            complete: true,
+        });
+    }
+
+    let use_a = face_b.0 <= RELATIVE * face_a.0 + tolerance;
+    let (reference, incident, axes_ref, axes_inc, h_ref, h_inc, axis, flip) = if use_a {
+        (a, b, axes_a, axes_b, *ha, *hb, face_a.1, false)
+    } else {
+        (b, a, axes_b, axes_a, *hb, *ha, face_b.1, true)
+    };
+    // Reference normal points from the reference box towards the incident one
+    let d = incident.position - reference.position;
+    let ref_normal = if axes_ref[axis].dot(d) < 0.0 {
+        -axes_ref[axis]
+    } else {
+        axes_ref[axis]
+    };
+    let ref_center = reference.position + ref_normal * h_ref[axis];
+
+    // Incident face: most anti-parallel to the reference normal
+    let (inc_axis, inc_sign) = (0..3)
+        .map(|k| {
+            let c = axes_inc[k].dot(ref_normal);
+            (k, if c > 0.0 { -1.0 } else { 1.0 }, c.abs())
+        })
+        .max_by(|x, y| x.2.total_cmp(&y.2))
+        .map(|(k, s, _)| (k, s))
+        .expect("three axes");
+    let (u, v) = ((inc_axis + 1) % 3, (inc_axis + 2) % 3);
+    let inc_center = incident.position + axes_inc[inc_axis] * (inc_sign * h_inc[inc_axis]);
+    let (eu, ev) = (axes_inc[u] * h_inc[u], axes_inc[v] * h_inc[v]);
+    let mut polygon = vec![
+        inc_center + eu + ev,
+        inc_center - eu + ev,
+        inc_center - eu - ev,
+        inc_center + eu - ev,
+    ];
+    for k in 0..3 {
+        if k == axis {
+            continue;
+        }
+        let offset = axes_ref[k].dot(reference.position);
+        polygon = clip(&polygon, axes_ref[k], offset + h_ref[k]);
+        polygon = clip(&polygon, -axes_ref[k], -offset + h_ref[k]);
+    }
+
+    let mut points: Vec<ContactPoint> = polygon
+        .into_iter()
+        .filter_map(|p| {
+            let s = (p - ref_center).dot(ref_normal);
+            if s > margin {
+                return None;
+            }
+            let on_reference = p - ref_normal * s;
+            let (point_a, point_b) = if flip {
+                (p, on_reference)
+            } else {
+                (on_reference, p)
+            };
+            Some(ContactPoint {
+                point_a,
+                point_b,
+                depth: -s,
+            })
+        })
+        .collect();
+    if points.is_empty() {
+        return None;
+    }
+    let normal = if flip { -ref_normal } else { ref_normal };
+    reduce(&mut points, normal);
+    Some(ContactSet {
+        normal,
+        points,
+        complete: true,
+    })
+}
+
+/// Sutherland-Hodgman: keep the part of the polygon with n·p ≤ offset
+fn clip(polygon: &[DVec3], normal: DVec3, offset: f64) -> Vec<DVec3> {
+    let mut out = Vec::with_capacity(polygon.len() + 2);
+    for (k, &p) in polygon.iter().enumerate() {
+        let q = polygon[(k + 1) % polygon.len()];
+        let (dp, dq) = (normal.dot(p) - offset, normal.dot(q) - offset);
+        if dp <= 0.0 {
+            out.push(p);
+        }
+        if (dp < 0.0) != (dq < 0.0) && dp != dq {
+            out.push(p + (q - p) * (dp / (dp - dq)));
+        }
+    }
+    out
+}
+
+/// Closest points of segments c₁ ± e₁ and c₂ ± e₂
+fn closest_between_segments(c1: DVec3, e1: DVec3, c2: DVec3, e2: DVec3) -> (DVec3, DVec3) {
+    let (p1, p2) = (c1 - e1, c2 - e2);
+    let (d1, d2, r) = (2.0 * e1, 2.0 * e2, c1 - e1 - (c2 - e2));
+    let (a, e, f) = (d1.dot(d1), d2.dot(d2), d2.dot(r));
+    let (b, c) = (d1.dot(d2), d1.dot(r));
+    let denominator = a * e - b * b;
+    let mut s = if denominator > 1e-12 * a * e {
+        ((b * f - c * e) / denominator).clamp(0.0, 1.0)
+    } else {
+        0.5
+    };
+    let mut t = (b * s + f) / e;
+    if t < 0.0 {
+        t = 0.0;
+        s = (-c / a).clamp(0.0, 1.0);
+    } else if t > 1.0 {
+        t = 1.0;
+        s = ((b - c) / a).clamp(0.0, 1.0);
+    }
+    (p1 + d1 * s, p2 + d2 * t)
+}
+
+/// Keep at most four points: deepest, then those spanning the largest area
+fn reduce(points: &mut Vec<ContactPoint>, normal: DVec3) {
+    if points.len() <= MAX_MANIFOLD_POINTS {
+        return;
+    }
+    let position = |p: &ContactPoint| p.point_b;
+    let first = (0..points.len())
+        .max_by(|&i, &j| points[i].depth.total_cmp(&points[j].depth))
+        .expect("non-empty");
+    let p0 = position(&points[first]);
+    let second = (0..points.len())
+        .max_by(|&i, &j| {
+            let di = (position(&points[i]) - p0).length_squared();
+            let dj = (position(&points[j]) - p0).length_squared();
+            di.total_cmp(&dj)
+        })
+        .expect("non-empty");
+    let p1 = position(&points[second]);
+    let area = |k: usize| (p1 - p0).cross(position(&points[k]) - p0).dot(normal);
+    let third = (0..points.len())
+        .max_by(|&i, &j| area(i).abs().total_cmp(&area(j).abs()))
+        .expect("non-empty");
+    // Fourth on the other side of the p0-p1 diagonal
+    let side = area(third).signum();
+    let fourth = (0..points.len())
+        .filter(|&k| k != first && k != second && k != third)
+        .max_by(|&i, &j| (-side * area(i)).total_cmp(&(-side * area(j))));
+    let mut keep = vec![first, second, third];
+    keep.extend(fourth);
+    keep.dedup();
+    *points = keep.into_iter().map(|k| points[k]).collect();
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// PERSISTENT MANIFOLD
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// Contact point tracked across steps (anchors in each body frame)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ManifoldPoint {
+    pub local_a: DVec3,
+    pub local_b: DVec3,
+    pub point_a: DVec3,
+    pub point_b: DVec3,
+    pub depth: f64,
+    /// Accumulated solver impulses (warm start)
+    pub normal_impulse: f64,
+    pub tangent_impulse: [f64; 2],
+}
+
+/// Contact points between one pair of bodies
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContactManifold {
+    pub body_a: usize,
+    pub body_b: usize,
+    pub normal: DVec3,
+    pub points: Vec<ManifoldPoint>,
+}
+
+impl ContactManifold {
+    pub fn new(body_a: usize, body_b: usize) -> Self {
+        Self {
+            body_a,
+            body_b,
+            normal: DVec3::ZERO,
+            points: Vec::new(),
+        }
+    }
+
+    /// Replace or merge with a new narrow-phase result; old points within
+    /// `tolerance` pass their impulses on
+    pub fn update(&mut self, a: &RigidBody, b: &RigidBody, contacts: ContactSet, tolerance: f64) {
+        let normal = contacts.normal;
+        let mut old = std::mem::take(&mut self.points);
+        if self.normal.dot(normal) < 0.95 {
+            old.clear();
+        }
+        self.normal = normal;
+
+        let mut points: Vec<ManifoldPoint> = contacts
+            .points
+            .iter()
+            .map(|c| {
+                let local_a = a.to_local(c.point_a);
+                let mut point = ManifoldPoint {
+                    local_a,
+                    local_b: b.to_local(c.point_b),
+                    point_a: c.point_a,
+                    point_b: c.point_b,
+                    depth: c.depth,
+                    normal_impulse: 0.0,
+                    tangent_impulse: [0.0; 2],
+                };
+                if let Some(k) = old
+                    .iter()
+                    .position(|o| (o.local_a - local_a).length() < tolerance)
+                {
+                    let matched = old.swap_remove(k);
+                    point.normal_impulse = matched.normal_impulse;
+                    point.tangent_impulse = matched.tangent_impulse;
+                }
+                point
+            })
+            .collect();
+
+        if !contacts.complete {
+            // Keep last step's points that still touch and have not slid
+            for mut o in old {
+                o.point_a = a.to_world(o.local_a);
+                o.point_b = b.to_world(o.local_b);
+                let gap = o.point_a - o.point_b;
+                o.depth = gap.dot(normal);
+                let drift = (gap - normal * o.depth).length();
+                if o.depth > -tolerance && drift < tolerance {
+                    points.push(o);
+                }
+            }
+            if points.len() > MAX_MANIFOLD_POINTS {
+                let mut contacts: Vec<ContactPoint> = points
+                    .iter()
+                    .map(|p| ContactPoint {
+                        point_a: p.point_a,
+                        point_b: p.point_b,
+                        depth: p.depth,
+                    })
+                    .collect();
+                reduce(&mut contacts, normal);
+                points.retain(|p| contacts.iter().any(|c| c.point_b == p.point_b));
+                points.truncate(MAX_MANIFOLD_POINTS);
+            }
+        }
+        self.points = points;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::DQuat;
+
+    fn body(shape: Shape, position: DVec3) -> RigidBody {
+        RigidBody::new(shape, 1.0).unwrap().with_position(position)
+    }
+
+    #[test]
+    fn test_gjk_epa_against_analytic() {
+        // Separated spheres treated as generic convex sets
+        let a = body(Shape::sphere(1.0).unwrap(), DVec3::ZERO);
+        let b = body(Shape::sphere(0.5).unwrap(), DVec3::new(2.0, 1.0, 0.0));
+        match gjk(|d| a.support(d), |d| b.support(d)) {
+            Gjk::Separated {
+                distance,
+                point_a,
+                point_b,
+            } => {
+                assert!((distance - (5.0_f64.sqrt() - 1.5)).abs() < 1e-6);
+                assert!((point_a.length() - 1.0).abs() < 1e-6);
+                assert!(((point_b - b.position).length() - 0.5).abs() < 1e-6);
+            }
+            other => panic!("expected separation, got {:?}", other),
+        }
+
+        // Rotated box sunk 0.1 into a wide slab: EPA depth and normal
+        let slab = body(
+            Shape::cuboid(DVec3::new(5.0, 0.5, 5.0)).unwrap(),
+            DVec3::ZERO,
+        );
+        let cube = body(
+            Shape::cuboid(DVec3::splat(0.5)).unwrap(),
+            DVec3::new(0.3, 0.9, -0.2),
+        )
+        .with_orientation(DQuat::from_rotation_y(0.4));
+        let Gjk::Intersecting(simplex) = gjk(|d| slab.support(d), |d| cube.support(d)) else {
+            panic!("expected overlap");
+        };
+        let p = epa(|d| slab.support(d), |d| cube.support(d), &simplex).unwrap();
+        assert!((p.depth - 0.1).abs() < 1e-6, "depth {}", p.depth);
+        assert!((p.normal - DVec3::Y).length() < 1e-6);
+        assert!((p.point_a.y - 0.5).abs() < 1e-6 && (p.point_b.y - 0.4).abs() < 1e-6);
+
+        // Capsule resting across a sphere uses the core path
+        let capsule = body(
+            Shape::capsule(0.2, 1.0).unwrap(),
+            DVec3::new(0.0, 1.15, 0.0),
+        )
+        .with_orientation(DQuat::from_rotation_z(std::f64::consts::FRAC_PI_2));
+        let sphere = body(Shape::sphere(1.0).unwrap(), DVec3::ZERO);
+        let set = collide(&sphere, &capsule, 0.0).unwrap();
+        assert!((set.points[0].depth - 0.05).abs() < 1e-9);
+        assert!((set.normal - DVec3::Y).length() < 1e-9);
+    }
+
+    #[test]
+    fn test_box_box_manifold() {
+        let ground = body(
+            Shape::cuboid(DVec3::new(5.0, 0.5, 5.0)).unwrap(),
+            DVec3::ZERO,
+        );
+        // Face contact: resting cube yields four corners
+        let cube = body(
+            Shape::cuboid(DVec3::splat(0.5)).unwrap(),
+            DVec3::new(1.0, 0.98, 0.0),
+        )
+        .with_orientation(DQuat::from_rotation_y(0.3));
+        let set = collide(&ground, &cube, 0.01).unwrap();
+        assert_eq!(set.points.len(), 4);
+        assert!((set.normal - DVec3::Y).length() < 1e-12);
+        for p in &set.points {
+            assert!((p.depth - 0.02).abs() < 1e-12);
+            assert!((p.point_a.y - 0.5).abs() < 1e-12);
+        }
+        // Reversed order flips the normal
+        let set = collide(&cube, &ground, 0.01).unwrap();
+        assert!((set.normal + DVec3::Y).length() < 1e-12);
+        assert!(set.points.iter().all(|p| (p.point_b.y - 0.5).abs() < 1e-12));
+
+        // Edge-edge: two cubes rotated 45° about crossed axes
+        let a = body(Shape::cuboid(DVec3::splat(0.5)).unwrap(), DVec3::ZERO)
+            .with_orientation(DQuat::from_rotation_x(std::f64::consts::FRAC_PI_4));
+        let gap = 2.0 * 0.5 * 2.0_f64.sqrt() - 0.05;
+        let b = body(
+            Shape::cuboid(DVec3::splat(0.5)).unwrap(),
+            DVec3::new(0.0, gap, 0.0),
+        )
+        .with_orientation(DQuat::from_rotation_z(std::f64::consts::FRAC_PI_4));
+        let set = collide(&a, &b, 0.0).unwrap();
+        assert_eq!(set.points.len(), 1);
+        assert!((set.points[0].depth - 0.05).abs() < 1e-9);
+        assert!((set.normal - DVec3::Y).length() < 1e-9);
+
+        // Separated beyond the margin
+        let far = cube.clone().with_position(DVec3::new(0.0, 1.2, 0.0));
+        assert!(collide(&ground, &far, 0.1).is_none());
+    }
+
+    #[test]
+    fn test_broad_phase_matches_brute_force() {
+        use rand::{Rng, SeedableRng};
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let boxes: Vec<Aabb> = (0..60)
+            .map(|_| {
+                let c = DVec3::new(
+                    rng.gen_range(0.0..10.0),
+                    rng.gen_range(0.0..3.0),
+                    rng.gen_range(0.0..3.0),
+                );
+                let h = DVec3::splat(rng.gen_range(0.1..0.6));
+                Aabb {
+                    min: c - h,
+                    max: c + h,
+                }
+            })
+            .collect();
+        let mut expected = Vec::new();
+        for i in 0..boxes.len() {
+            for j in i + 1..boxes.len() {
+                if boxes[i].overlaps(&boxes[j]) {
+                    expected.push((i, j));
+                }
+            }
+        }
+        assert!(!expected.is_empty());
+        assert_eq!(broad_phase(&boxes), expected);
+    }
+}
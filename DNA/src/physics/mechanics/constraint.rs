@@ -0,0 +1,694 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: constraint.rs | DNA/src/physics/mechanics/constraint.rs
+//! PURPOSE: Sequential-impulse solver for contacts and joints
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! PURPOSE: Velocity constraints between rigid bodies: frictional contacts,
+//!          ball, hinge and distance joints
+//!
+//! LAYER: DNA → PHYSICS → MECHANICS
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ ALGORITHM (projected Gauss-Seidel on velocities, Catto 2005)                │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ Row       Ċ = J·v,  effective mass K = J·M⁻¹·Jᵀ                             │
+//! │ Impulse   Δλ = K⁻¹(-Ċ + bias),  λ accumulated and clamped, v += M⁻¹Jᵀ·Δλ   │
+//! │ Bias      Baumgarte β/Δt·C for joints and penetration beyond the slop;      │
+//! │           speculative contacts allow approach up to the gap/Δt;             │
+//! │           restitution -e·vₙ above a threshold speed                         │
+//! │ Contact   λₙ ≥ 0;  two friction rows |λₜ| ≤ μλₙ                             │
+//! │ Ball      3 rows, x_a + r_a = x_b + r_b (3×3 block)                         │
+//! │ Hinge     ball + 2 angular rows keeping the axes parallel (2×2 block),      │
+//! │           optional motor row on the axis with a torque limit               │
+//! │ Distance  1 row, |p_b - p_a| = length                                       │
+//! │ Warm start: last step's λ applied before iterating                          │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ DATA DEFINED                                                                │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ JointKind         Ball, Hinge, Distance                                     │
+//! │ Joint             Bodies, local anchors, accumulated impulses               │
+//! │ SolverSettings    Iterations, Baumgarte factor, slop, restitution speed     │
+//! │ ConstraintSolver  Velocities and prepared rows for one step                 │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! REFERENCE: Catto, "Iterative Dynamics with Temporal Coherence" (GDC 2005)
+//!            Catto, "Modeling and Solving Constraints" (GDC 2009)
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use super::collision::ContactManifold;
+use super::rigid_body::{skew, RigidBody};
+use glam::{DMat2, DMat3, DVec2, DVec3};
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// SETTINGS
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// Solver parameters shared by all constraints
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SolverSettings {
+    /// Velocity iterations per step
+    pub iterations: usize,
+    /// Fraction of the position error corrected per step
+    pub baumgarte: f64,
+    /// Penetration tolerated without correction (m)
+    pub slop: f64,
+    /// Closing speed below which contacts do not bounce (m/s)
+    pub restitution_threshold: f64,
+    /// Distance at which contacts are created before touching (m)
+    pub contact_margin: f64,
+    pub warm_starting: bool,
+}
+
+impl Default for SolverSettings {
+    fn default() -> Self {
+        Self {
+            iterations: 10,
+            baumgarte: 0.2,
+            slop: 0.005,
+            restitution_threshold: 1.0,
+            contact_margin: 0.02,
+            warm_starting: true,
+        }
+    }
+}
+
+impl SolverSettings {
+    pub fn with_iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    pub fn with_baumgarte(mut self, baumgarte: f64, slop: f64) -> Self {
+        self.baumgarte = baumgarte;
+        self.slop = slop;
+        self
+    }
+
+    pub fn with_warm_starting(mut self, warm_starting: bool) -> Self {
+        self.warm_starting = warm_starting;
+        self
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// JOINTS
+// ─────────────────────────────────────────────────────────────────────────────────
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum JointKind {
+    /// Shared point, free rotation
+    Ball,
+    /// Shared point and axis (body frames); optional motor on the axis
+    Hinge {
+        local_axis_a: DVec3,
+        local_axis_b: DVec3,
+        /// Target relative angular speed and maximum torque
+        motor: Option<(f64, f64)>,
+    },
+    /// Fixed distance between the anchors
+    Distance { length: f64 },
+}
+
+/// Constraint between two bodies given by index
+#[derive(Clone, Debug, PartialEq)]
+pub struct Joint {
+    pub body_a: usize,
+    pub body_b: usize,
+    pub local_anchor_a: DVec3,
+    pub local_anchor_b: DVec3,
+    pub kind: JointKind,
+    /// Whether the two bodies still collide with each other
+    pub collide_connected: bool,
+    linear_impulse: DVec3,
+    angular_impulse: DVec2,
+    motor_impulse: f64,
+}
+
+impl Joint {
+    fn new(
+        (ia, a): (usize, &RigidBody),
+        (ib, b): (usize, &RigidBody),
+        anchor_a: DVec3,
+        anchor_b: DVec3,
+        kind: JointKind,
+    ) -> Self {
+        Self {
+            body_a: ia,
+            body_b: ib,
+            local_anchor_a: a.to_local(anchor_a),
+            local_anchor_b: b.to_local(anchor_b),
+            kind,
+            collide_connected: false,
+            linear_impulse: DVec3::ZERO,
+            angular_impulse: DVec2::ZERO,
+            motor_impulse: 0.0,
+        }
+    }
+
+    /// Ball-and-socket at a world point
+    pub fn ball(a: (usize, &RigidBody), b: (usize, &RigidBody), anchor: DVec3) -> Self {
+        Self::new(a, b, anchor, anchor, JointKind::Ball)
+    }
+
+    /// Hinge through a world point about a world axis
+    pub fn hinge(
+        a: (usize, &RigidBody),
+        b: (usize, &RigidBody),
+        anchor: DVec3,
+        axis: DVec3,
+    ) -> Self {
+        let axis = axis.normalize();
+        let kind = JointKind::Hinge {
+            local_axis_a: a.1.orientation.inverse() * axis,
+            local_axis_b: b.1.orientation.inverse() * axis,
+            motor: None,
+        };
+        Self::new(a, b, anchor, anchor, kind)
+    }
+
+    /// Rod between two world points, keeping their current distance
+    pub fn distance(
+        a: (usize, &RigidBody),
+        b: (usize, &RigidBody),
+        anchor_a: DVec3,
+        anchor_b: DVec3,
+    ) -> Self {
+        let kind = JointKind::Distance {
+            length: (anchor_b - anchor_a).length(),
+        };
+        Self::new(a, b, anchor_a, anchor_b, kind)
+    }
+
+    /// Drive a hinge at `speed` (rad/s, b relative to a) with at most `max_torque`
+    pub fn with_motor(mut self, speed: f64, max_torque: f64) -> Self {
+        if let JointKind::Hinge { motor, .. } = &mut self.kind {
+            *motor = Some((speed, max_torque));
+        }
+        self
+    }
+
+    pub fn with_collide_connected(mut self, collide: bool) -> Self {
+        self.collide_connected = collide;
+        self
+    }
+
+    /// Position error: anchor separation (ball, hinge) or length error (distance)
+    pub fn error(&self, a: &RigidBody, b: &RigidBody) -> f64 {
+        let gap = b.to_world(self.local_anchor_b) - a.to_world(self.local_anchor_a);
+        match self.kind {
+            JointKind::Distance { length } => gap.length() - length,
+            _ => gap.length(),
+        }
+    }
+
+    /// Reaction impulse on body b over the last step
+    pub fn reaction_impulse(&self) -> DVec3 {
+        self.linear_impulse
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// SOLVER
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// Velocity state of one body during the solve
+#[derive(Clone, Copy, Debug)]
+struct SolverBody {
+    linear: DVec3,
+    angular: DVec3,
+    inverse_mass: f64,
+    inverse_inertia: DMat3,
+}
+
+impl SolverBody {
+    fn apply(&mut self, impulse: DVec3, r: DVec3) {
+        self.linear += impulse * self.inverse_mass;
+        self.angular += self.inverse_inertia * r.cross(impulse);
+    }
+
+    fn velocity_at(&self, r: DVec3) -> DVec3 {
+        self.linear + self.angular.cross(r)
+    }
+}
+
+fn pair(bodies: &mut [SolverBody], a: usize, b: usize) -> (&mut SolverBody, &mut SolverBody) {
+    debug_assert_ne!(a, b);
+    if a < b {
+        let (low, high) = bodies.split_at_mut(b);
+        (&mut low[a], &mut high[0])
+    } else {
+        let (low, high) = bodies.split_at_mut(a);
+        (&mut high[0], &mut low[b])
+    }
+}
+
+/// 1 / (J·M⁻¹·Jᵀ) for a linear row along `n` at arms r_a, r_b
+fn row_mass(a: &SolverBody, b: &SolverBody, ra: DVec3, rb: DVec3, n: DVec3) -> f64 {
+    let k = a.inverse_mass
+        + b.inverse_mass
+        + (a.inverse_inertia * ra.cross(n)).cross(ra).dot(n)
+        + (b.inverse_inertia * rb.cross(n)).cross(rb).dot(n);
+    if k > 0.0 {
+        1.0 / k
+    } else {
+        0.0
+    }
+}
+
+/// Unit vectors completing `n` to an orthonormal basis
+fn tangents(n: DVec3) -> [DVec3; 2] {
+    let t = if n.x.abs() >= 0.57 {
+        DVec3::new(n.y, -n.x, 0.0)
+    } else {
+        DVec3::new(0.0, n.z, -n.y)
+    }
+    .normalize();
+    [t, n.cross(t)]
+}
+
+struct ContactRow {
+    manifold: usize,
+    point: usize,
+    body_a: usize,
+    body_b: usize,
+    ra: DVec3,
+    rb: DVec3,
+    normal: DVec3,
+    tangents: [DVec3; 2],
+    normal_mass: f64,
+    tangent_mass: [f64; 2],
+    /// Minimum normal relative velocity
+    target: f64,
+    friction: f64,
+}
+
+struct JointRow {
+    ra: DVec3,
+    rb: DVec3,
+    /// Block effective mass (ball part) or scalar in `.x_axis.x` (distance)
+    linear_mass: DMat3,
+    linear_bias: DVec3,
+    /// Distance: unit direction
+    direction: DVec3,
+    /// Hinge: axis and the two perpendicular directions
+    axis: DVec3,
+    perpendicular: [DVec3; 2],
+    angular_mass: DMat2,
+    angular_bias: DVec2,
+    motor_mass: f64,
+}
+
+/// Contact and joint rows for one step
+pub struct ConstraintSolver {
+    bodies: Vec<SolverBody>,
+    contacts: Vec<ContactRow>,
+    joints: Vec<JointRow>,
+    dt: f64,
+}
+
+impl ConstraintSolver {
+    /// Build rows from the current poses and (already integrated) velocities
+    pub fn new(
+        bodies: &[RigidBody],
+        manifolds: &[ContactManifold],
+        joints: &[Joint],
+        settings: &SolverSettings,
+        dt: f64,
+    ) -> Self {
+        let solver_bodies: Vec<SolverBody> = bodies
+            .iter()
+            .map(|b| SolverBody {
+                linear: b.linear_velocity,
+                angular: b.angular_velocity,
+                inverse_mass: b.inverse_mass(),
+                inverse_inertia: b.inverse_inertia_world(),
+            })
+            .collect();
+
+        let mut contacts = Vec::new();
+        for (m, manifold) in manifolds.iter().enumerate() {
+            let (ia, ib) = (manifold.body_a, manifold.body_b);
+            let (a, b) = (&solver_bodies[ia], &solver_bodies[ib]);
+            let friction = (bodies[ia].friction * bodies[ib].friction).sqrt();
+            let restitution = bodies[ia].restitution.max(bodies[ib].restitution);
+            let normal = manifold.normal;
+            let tangents = tangents(normal);
+            for (k, point) in manifold.points.iter().enumerate() {
+                let p = 0.5 * (point.point_a + point.point_b);
+                let (ra, rb) = (p - bodies[ia].position, p - bodies[ib].position);
+                let approach = (b.velocity_at(rb) - a.velocity_at(ra)).dot(normal);
+                let mut target = if point.depth < 0.0 {
+                    point.depth / dt
+                } else {
+                    settings.baumgarte / dt * (point.depth - settings.slop).max(0.0)
+                };
+                if approach < -settings.restitution_threshold {
+                    target = target.max(-restitution * approach);
+                }
+                contacts.push(ContactRow {
+                    manifold: m,
+                    point: k,
+                    body_a: ia,
+                    body_b: ib,
+                    ra,
+                    rb,
+                    normal,
+                    tangents,
+                    normal_mass: row_mass(a, b, ra, rb, normal),
+                    tangent_mass: tangents.map(|t| row_mass(a, b, ra, rb, t)),
+                    target,
+                    friction,
+                });
+            }
+        }
+
+        let beta = settings.baumgarte / dt;
+        let joint_rows = joints
+            .iter()
+            .map(|joint| {
+                let (ba, bb) = (&bodies[joint.body_a], &bodies[joint.body_b]);
+                let (a, b) = (&solver_bodies[joint.body_a], &solver_bodies[joint.body_b]);
+                let ra = ba.orientation * joint.local_anchor_a;
+                let rb = bb.orientation * joint.local_anchor_b;
+                let gap = (bb.position + rb) - (ba.position + ra);
+                let mut row = JointRow {
+                    ra,
+                    rb,
+                    linear_mass: DMat3::ZERO,
+                    linear_bias: DVec3::ZERO,
+                    direction: DVec3::ZERO,
+                    axis: DVec3::ZERO,
+                    perpendicular: [DVec3::ZERO; 2],
+                    angular_mass: DMat2::ZERO,
+                    angular_bias: DVec2::ZERO,
+                    motor_mass: 0.0,
+                };
+                match joint.kind {
+                    JointKind::Distance { length } => {
+                        let current = gap.length();
+                        let direction = if current > 1e-12 {
+                            gap / current
+                        } else {
+                            DVec3::Y
+                        };
+                        row.direction = direction;
+                        row.linear_mass.x_axis.x = row_mass(a, b, ra, rb, direction);
+                        row.linear_bias.x = -beta * (current - length);
+                    }
+                    JointKind::Ball | JointKind::Hinge { .. } => {
+                        let (sa, sb) = (skew(ra), skew(rb));
+                        let k = DMat3::from_diagonal(DVec3::splat(a.inverse_mass + b.inverse_mass))
+                            - sa * a.inverse_inertia * sa
+                            - sb * b.inverse_inertia * sb;
+                        row.linear_mass = invert_or_zero3(k);
+                        row.linear_bias = -beta * gap;
+                    }
+                }
+                if let JointKind::Hinge {
+                    local_axis_a,
+                    local_axis_b,
+                    motor,
+                } = joint.kind
+                {
+                    let axis_a = ba.orientation * local_axis_a;
+                    let axis_b = bb.orientation * local_axis_b;
+                    let perpendicular = tangents(axis_a);
+                    let inertia = a.inverse_inertia + b.inverse_inertia;
+                    let k = |i: usize, j: usize| perpendicular[i].dot(inertia * perpendicular[j]);
+                    let k = DMat2::from_cols(
+                        DVec2::new(k(0, 0), k(1, 0)),
+                        DVec2::new(k(0, 1), k(1, 1)),
+                    );
+                    row.angular_mass = if k.determinant().abs() > 1e-18 {
+                        k.inverse()
+                    } else {
+                        DMat2::ZERO
+                    };
+                    let error = axis_a.cross(axis_b);
+                    row.angular_bias = -beta
+                        * DVec2::new(perpendicular[0].dot(error), perpendicular[1].dot(error));
+                    row.axis = axis_a;
+                    row.perpendicular = perpendicular;
+                    if motor.is_some() {
+                        let k = axis_a.dot(inertia * axis_a);
+                        row.motor_mass = if k > 0.0 { 1.0 / k } else { 0.0 };
+                    }
+                }
+                row
+            })
+            .collect();
+
+        Self {
+            bodies: solver_bodies,
+            contacts,
+            joints: joint_rows,
+            dt,
+        }
+    }
+
+    /// Apply last step's accumulated impulses (or clear them)
+    pub fn warm_start(
+        &mut self,
+        manifolds: &mut [ContactManifold],
+        joints: &mut [Joint],
+        enabled: bool,
+    ) {
+        for row in &self.contacts {
+            let point = &mut manifolds[row.manifold].points[row.point];
+            if !enabled {
+                point.normal_impulse = 0.0;
+                point.tangent_impulse = [0.0; 2];
+                continue;
+            }
+            let impulse = row.normal * point.normal_impulse
+                + row.tangents[0] * point.tangent_impulse[0]
+                + row.tangents[1] * point.tangent_impulse[1];
+            let (a, b) = pair(&mut self.bodies, row.body_a, row.body_b);
+            a.apply(-impulse, row.ra);
+            b.apply(impulse, row.rb);
+        }
+        for (joint, row) in joints.iter_mut().zip(&self.joints) {
+            if !enabled {
+                joint.linear_impulse = DVec3::ZERO;
+                joint.angular_impulse = DVec2::ZERO;
+                joint.motor_impulse = 0.0;
+                continue;
+            }
+            if let JointKind::Distance { .. } = joint.kind {
+                // Only the component along the current rod carries over
+                joint.linear_impulse = row.direction * joint.linear_impulse.dot(row.direction);
+            }
+            let (a, b) = pair(&mut self.bodies, joint.body_a, joint.body_b);
+            a.apply(-joint.linear_impulse, row.ra);
+            b.apply(joint.linear_impulse, row.rb);
+            let angular = row.perpendicular[0] * joint.angular_impulse.x
+                + row.perpendicular[1] * joint.angular_impulse.y
+                + row.axis * joint.motor_impulse;
+            a.angular -= a.inverse_inertia * angular;
+            b.angular += b.inverse_inertia * angular;
+        }
+    }
+
+    /// One Gauss-Seidel sweep over joints then contacts
+    pub fn iterate(&mut self, manifolds: &mut [ContactManifold], joints: &mut [Joint]) {
+        for (joint, row) in joints.iter_mut().zip(&self.joints) {
+            let (a, b) = pair(&mut self.bodies, joint.body_a, joint.body_b);
+
+            if let JointKind::Hinge { motor, .. } = joint.kind {
+                if let Some((speed, max_torque)) = motor {
+                    let relative = (b.angular - a.angular).dot(row.axis);
+                    let limit = max_torque * self.dt;
+                    let old = joint.motor_impulse;
+                    joint.motor_impulse =
+                        (old + row.motor_mass * (speed - relative)).clamp(-limit, limit);
+                    let angular = row.axis * (joint.motor_impulse - old);
+                    a.angular -= a.inverse_inertia * angular;
+                    b.angular += b.inverse_inertia * angular;
+                }
+                let relative = b.angular - a.angular;
+                let cdot = DVec2::new(
+                    row.perpendicular[0].dot(relative),
+                    row.perpendicular[1].dot(relative),
+                );
+                let lambda = row.angular_mass * (row.angular_bias - cdot);
+                joint.angular_impulse += lambda;
+                let angular = row.perpendicular[0] * lambda.x + row.perpendicular[1] * lambda.y;
+                a.angular -= a.inverse_inertia * angular;
+                b.angular += b.inverse_inertia * angular;
+            }
+
+            let relative = b.velocity_at(row.rb) - a.velocity_at(row.ra);
+            let impulse = match joint.kind {
+                JointKind::Distance { .. } => {
+                    let cdot = relative.dot(row.direction);
+                    row.direction * (row.linear_mass.x_axis.x * (row.linear_bias.x - cdot))
+                }
+                _ => row.linear_mass * (row.linear_bias - relative),
+            };
+            joint.linear_impulse += impulse;
+            a.apply(-impulse, row.ra);
+            b.apply(impulse, row.rb);
+        }
+
+        for row in &self.contacts {
+            let point = &mut manifolds[row.manifold].points[row.point];
+            let (a, b) = pair(&mut self.bodies, row.body_a, row.body_b);
+
+            // Friction bounded by the current normal impulse
+            let limit = row.friction * point.normal_impulse;
+            for k in 0..2 {
+                let relative = b.velocity_at(row.rb) - a.velocity_at(row.ra);
+                let vt = relative.dot(row.tangents[k]);
+                let old = point.tangent_impulse[k];
+                point.tangent_impulse[k] = (old - row.tangent_mass[k] * vt).clamp(-limit, limit);
+                let impulse = row.tangents[k] * (point.tangent_impulse[k] - old);
+                a.apply(-impulse, row.ra);
+                b.apply(impulse, row.rb);
+            }
+
+            let relative = b.velocity_at(row.rb) - a.velocity_at(row.ra);
+            let vn = relative.dot(row.normal);
+            let old = point.normal_impulse;
+            point.normal_impulse = (old + row.normal_mass * (row.target - vn)).max(0.0);
+            let impulse = row.normal * (point.normal_impulse - old);
+            a.apply(-impulse, row.ra);
+            b.apply(impulse, row.rb);
+        }
+    }
+
+    /// Copy the solved velocities back to the bodies
+    pub fn store(&self, bodies: &mut [RigidBody]) {
+        for (body, solved) in bodies.iter_mut().zip(&self.bodies) {
+            if !body.is_static() {
+                body.linear_velocity = solved.linear;
+                body.angular_velocity = solved.angular;
+            }
+        }
+    }
+}
+
+fn invert_or_zero3(m: DMat3) -> DMat3 {
+    if m.determinant().abs() > 1e-18 {
+        m.inverse()
+    } else {
+        DMat3::ZERO
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physics::mechanics::rigid_body::Shape;
+
+    /// Run the solver alone (no contacts) on two bodies
+    fn solve(bodies: &mut [RigidBody], joints: &mut [Joint], dt: f64, steps: usize) {
+        let settings = SolverSettings::default();
+        let gravity = DVec3::new(0.0, -9.81, 0.0);
+        for _ in 0..steps {
+            bodies
+                .iter_mut()
+                .for_each(|b| b.integrate_velocity(dt, gravity));
+            let mut solver = ConstraintSolver::new(bodies, &[], joints, &settings, dt);
+            solver.warm_start(&mut [], joints, true);
+            for _ in 0..settings.iterations {
+                solver.iterate(&mut [], joints);
+            }
+            solver.store(bodies);
+            bodies.iter_mut().for_each(|b| b.integrate_position(dt));
+        }
+    }
+
+    #[test]
+    fn test_pendulum_period_and_length() {
+        // Small bob on a 1 m rod from a fixed pivot: T = 2π√(L/g)
+        let pivot = RigidBody::fixed(Shape::sphere(0.05).unwrap());
+        let start = DVec3::new(0.1_f64.sin(), -0.1_f64.cos(), 0.0);
+        let bob = RigidBody::new(Shape::sphere(0.01).unwrap(), 1000.0)
+            .unwrap()
+            .with_position(start);
+        let mut bodies = vec![pivot, bob];
+        let mut joints = vec![Joint::distance(
+            (0, &bodies[0]),
+            (1, &bodies[1]),
+            DVec3::ZERO,
+            start,
+        )];
+        let dt = 1e-3;
+        let mut crossings = Vec::new();
+        let mut previous = bodies[1].position.x;
+        for step in 0..4200 {
+            solve(&mut bodies, &mut joints, dt, 1);
+            let x = bodies[1].position.x;
+            if previous > 0.0 && x <= 0.0 {
+                crossings.push(step as f64 * dt);
+            }
+            previous = x;
+            assert!(joints[0].error(&bodies[0], &bodies[1]).abs() < 1e-3);
+        }
+        let period = crossings[1] - crossings[0];
+        let expected = 2.0 * std::f64::consts::PI * (1.0 / 9.81_f64).sqrt();
+        // Finite amplitude (0.1 rad) lengthens T by θ₀²/16
+        assert!((period / expected - 1.0).abs() < 0.01, "T = {}", period);
+    }
+
+    #[test]
+    fn test_hinge_keeps_axis_and_motor_drives() {
+        // Plate hanging from a horizontal hinge along z, driven by a motor
+        let frame = RigidBody::fixed(Shape::sphere(0.05).unwrap());
+        let plate = RigidBody::new(Shape::cuboid(DVec3::new(0.2, 0.5, 0.02)).unwrap(), 500.0)
+            .unwrap()
+            .with_position(DVec3::new(0.0, -0.5, 0.0))
+            .with_velocity(DVec3::ZERO, DVec3::new(0.3, 0.0, 0.0));
+        let mut bodies = vec![frame, plate];
+        let hinge = Joint::hinge((0, &bodies[0]), (1, &bodies[1]), DVec3::ZERO, DVec3::Z)
+            .with_motor(2.0, 200.0);
+        let mut joints = vec![hinge];
+        solve(&mut bodies, &mut joints, 1.0 / 240.0, 480);
+        let axis = bodies[1].orientation * DVec3::Z;
+        assert!(axis.dot(DVec3::Z) > 0.999, "axis drifted: {:?}", axis);
+        assert!(joints[0].error(&bodies[0], &bodies[1]) < 1e-3);
+        assert!((bodies[1].angular_velocity.z - 2.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_ball_joint_chain() {
+        // Two-link chain released horizontally stays connected
+        let anchor = RigidBody::fixed(Shape::sphere(0.05).unwrap());
+        let link = |x: f64| {
+            RigidBody::new(Shape::capsule(0.05, 0.2).unwrap(), 1000.0)
+                .unwrap()
+                .with_position(DVec3::new(x, 0.0, 0.0))
+                .with_orientation(glam::DQuat::from_rotation_z(std::f64::consts::FRAC_PI_2))
+        };
+        let mut bodies = vec![anchor, link(0.25), link(0.75)];
+        let mut joints = vec![
+            Joint::ball((0, &bodies[0]), (1, &bodies[1]), DVec3::ZERO),
+            Joint::ball((1, &bodies[1]), (2, &bodies[2]), DVec3::new(0.5, 0.0, 0.0)),
+        ];
+        let energy = |bodies: &[RigidBody]| -> f64 {
+            bodies
+                .iter()
+                .filter(|b| !b.is_static())
+                .map(|b| b.kinetic_energy() + b.mass() * 9.81 * b.position.y)
+                .sum()
+        };
+        let initial = energy(&bodies);
+        let mut lowest: f64 = 0.0;
+        for _ in 0..480 {
+            solve(&mut bodies, &mut joints, 1.0 / 240.0, 1);
+            lowest = lowest.min(bodies[2].position.y);
+        }
+        for (joint, (a, b)) in joints.iter().zip([(0, 1), (1, 2)]) {
+            assert!(joint.error(&bodies[a], &bodies[b]) < 5e-3);
+        }
+        // Swung down and did not gain energy
+        assert!(lowest < -0.5, "lowest {}", lowest);
+        assert!(energy(&bodies) < initial + 1e-6);
+    }
+}
@@ -1,6 +1,6 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: mod.rs | DNA/src/physics/mechanics/mod.rs
-//! PURPOSE: Module exports: particle
+//! PURPOSE: Module exports: particle, rigid_body, collision, constraint, world, soft_body
 //! MODIFIED: 2025-12-09
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
@@ -9,7 +9,22 @@
 pub mod particle;
 pub use particle::Particle;
 
-// pub mod rigid_body;  // TODO: 3D rotation, inertia tensor
-// pub mod soft_body;   // TODO: Mass-spring, FEM deformable
-// pub mod constraint;  // TODO: Joints, springs, distance constraints
-// pub mod collision;   // TODO: GJK, SAT, contact generation
+/// 3D rigid bodies: shapes, inertia tensor, quaternion orientation
+pub mod rigid_body;
+pub use rigid_body::{MassProperties, RigidBody, RigidBodyError, Shape};
+
+/// Broad phase, GJK / EPA, box SAT and persistent contact manifolds
+pub mod collision;
+pub use collision::{Aabb, ContactManifold, ContactPoint, ContactSet};
+
+/// Sequential-impulse contacts and ball / hinge / distance joints
+pub mod constraint;
+pub use constraint::{Joint, JointKind, SolverSettings};
+
+/// Rigid body world stepping collisions and constraints together
+pub mod world;
+pub use world::RigidBodyWorld;
+
+/// Mass-spring networks and position-based cloth, rope and shells
+pub mod soft_body;
+pub use soft_body::{Constraint, SoftBody, SoftBodyError, SoftBodySettings, Spring};
@@ -0,0 +1,619 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: rigid_body.rs | DNA/src/physics/mechanics/rigid_body.rs
+//! PURPOSE: 3D rigid bodies with inertia tensor and quaternion orientation
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! PURPOSE: Convex shapes, mass properties and rigid body state integration
+//!
+//! LAYER: DNA → PHYSICS → MECHANICS
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ DATA DEFINED                                                                │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ Shape             Sphere, Cuboid, Capsule, ConvexMesh (body frame, COM 0)   │
+//! │ MassProperties    Mass and inertia tensor about the centre of mass          │
+//! │ RigidBody         Pose, velocities, inverse mass / inertia, material        │
+//! │ RigidBodyError    Bad shape, density or body index                          │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ ALGORITHM                                                                   │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ World inertia   I⁻¹ = R·I_body⁻¹·Rᵀ,  R from the unit quaternion q          │
+//! │ Velocity        v += Δt(g + F/m)                                            │
+//! │                 ω_b: one Newton step on I(ω - ω₀) + Δt ω×Iω = 0 (body frame,│
+//! │                 implicit gyroscopic term), then ω += Δt I⁻¹τ                │
+//! │ Position        x += Δt v,   q ← normalize(q + ½Δt (0, ω) q)                │
+//! │ Mesh inertia    Σ over tetrahedra (0, a, b, c) of the closed triangle mesh: │
+//! │                 C = Σ det(A)·A·C₀·Aᵀ,  I = tr(C)·1 - C                      │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! REFERENCE: Catto, "Numerical Methods" (GDC 2015), gyroscopic torque
+//!            Blow & Binstock, "How to find the inertia tensor" (2004)
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use glam::{DMat3, DQuat, DVec3};
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// ERRORS
+// ─────────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RigidBodyError {
+    InvalidParameter(String),
+    UnknownBody(usize),
+}
+
+impl std::fmt::Display for RigidBodyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RigidBodyError::InvalidParameter(msg) => write!(f, "Invalid parameter: {}", msg),
+            RigidBodyError::UnknownBody(index) => write!(f, "No rigid body with index {}", index),
+        }
+    }
+}
+
+impl std::error::Error for RigidBodyError {}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// SHAPES
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// Convex collision shape in the body frame, centred on the centre of mass
+#[derive(Clone, Debug, PartialEq)]
+pub enum Shape {
+    Sphere {
+        radius: f64,
+    },
+    /// Box with the given half extents along the body axes
+    Cuboid {
+        half_extents: DVec3,
+    },
+    /// Segment from -half_height to +half_height along body y, swept by radius
+    Capsule {
+        radius: f64,
+        half_height: f64,
+    },
+    /// Closed triangle mesh of a convex solid (counter-clockwise seen from outside)
+    ConvexMesh {
+        vertices: Vec<DVec3>,
+        triangles: Vec<[usize; 3]>,
+    },
+}
+
+/// Mass and inertia tensor about the centre of mass (body frame)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MassProperties {
+    pub mass: f64,
+    pub inertia: DMat3,
+}
+
+impl Shape {
+    pub fn sphere(radius: f64) -> Result<Self, RigidBodyError> {
+        check_positive("radius", radius)?;
+        Ok(Shape::Sphere { radius })
+    }
+
+    pub fn cuboid(half_extents: DVec3) -> Result<Self, RigidBodyError> {
+        check_positive("half extent", half_extents.min_element())?;
+        Ok(Shape::Cuboid { half_extents })
+    }
+
+    pub fn capsule(radius: f64, half_height: f64) -> Result<Self, RigidBodyError> {
+        check_positive("radius", radius)?;
+        check_positive("half height", half_height)?;
+        Ok(Shape::Capsule {
+            radius,
+            half_height,
+        })
+    }
+
+    /// Convex mesh re-centred so its volume centroid is the body origin;
+    /// returns the shape and the centroid in the input coordinates
+    pub fn convex_mesh(
+        vertices: Vec<DVec3>,
+        triangles: Vec<[usize; 3]>,
+    ) -> Result<(Self, DVec3), RigidBodyError> {
+        if triangles
+            .iter()
+            .flatten()
+            .any(|&index| index >= vertices.len())
+        {
+            return Err(RigidBodyError::InvalidParameter(
+                "triangle references a missing vertex".to_string(),
+            ));
+        }
+        let (volume, first_moment) =
+            triangles
+                .iter()
+                .fold((0.0, DVec3::ZERO), |(volume, moment), t| {
+                    let (a, b, c) = (vertices[t[0]], vertices[t[1]], vertices[t[2]]);
+                    let v = a.dot(b.cross(c)) / 6.0;
+                    (volume + v, moment + v * (a + b + c) / 4.0)
+                });
+        if !(volume > 0.0 && volume.is_finite()) {
+            return Err(RigidBodyError::InvalidParameter(format!(
+                "mesh volume {} must be positive (closed, outward-facing triangles)",
+                volume
+            )));
+        }
+        let centroid = first_moment / volume;
+        let vertices = vertices.into_iter().map(|v| v - centroid).collect();
+        Ok((
+            Shape::ConvexMesh {
+                vertices,
+                triangles,
+            },
+            centroid,
+        ))
+    }
+
+    pub fn volume(&self) -> f64 {
+        use std::f64::consts::PI;
+        match self {
+            Shape::Sphere { radius } => 4.0 / 3.0 * PI * radius.powi(3),
+            Shape::Cuboid { half_extents } => {
+                8.0 * half_extents.x * half_extents.y * half_extents.z
+            }
+            Shape::Capsule {
+                radius,
+                half_height,
+            } => PI * radius * radius * (2.0 * half_height + 4.0 / 3.0 * radius),
+            Shape::ConvexMesh {
+                vertices,
+                triangles,
+            } => triangles
+                .iter()
+                .map(|t| vertices[t[0]].dot(vertices[t[1]].cross(vertices[t[2]])) / 6.0)
+                .sum(),
+        }
+    }
+
+    /// Mass and inertia about the origin for uniform `density`
+    pub fn mass_properties(&self, density: f64) -> MassProperties {
+        use std::f64::consts::PI;
+        let mass = density * self.volume();
+        let inertia = match self {
+            Shape::Sphere { radius } => {
+                DMat3::from_diagonal(DVec3::splat(0.4 * mass * radius * radius))
+            }
+            Shape::Cuboid { half_extents } => {
+                let s = *half_extents * *half_extents;
+                DMat3::from_diagonal(mass / 3.0 * DVec3::new(s.y + s.z, s.x + s.z, s.x + s.y))
+            }
+            Shape::Capsule {
+                radius,
+                half_height,
+            } => {
+                let (r, h) = (*radius, 2.0 * half_height);
+                let cylinder = density * PI * r * r * h;
+                let caps = density * 4.0 / 3.0 * PI * r * r * r;
+                let axial = cylinder * r * r / 2.0 + caps * 0.4 * r * r;
+                let transverse = cylinder * (h * h / 12.0 + r * r / 4.0)
+                    + caps * (0.4 * r * r + h * h / 4.0 + 3.0 * h * r / 8.0);
+                DMat3::from_diagonal(DVec3::new(transverse, axial, transverse))
+            }
+            Shape::ConvexMesh {
+                vertices,
+                triangles,
+            } => {
+                // Covariance of the canonical tetrahedron (0, x̂, ŷ, ẑ)
+                let canonical = DMat3::from_cols(
+                    DVec3::new(2.0, 1.0, 1.0),
+                    DVec3::new(1.0, 2.0, 1.0),
+                    DVec3::new(1.0, 1.0, 2.0),
+                ) * (1.0 / 120.0);
+                let covariance = triangles.iter().fold(DMat3::ZERO, |sum, t| {
+                    let a = DMat3::from_cols(vertices[t[0]], vertices[t[1]], vertices[t[2]]);
+                    sum + a * canonical * a.transpose() * a.determinant()
+                }) * density;
+                let trace = covariance.x_axis.x + covariance.y_axis.y + covariance.z_axis.z;
+                DMat3::from_diagonal(DVec3::splat(trace)) - covariance
+            }
+        };
+        MassProperties { mass, inertia }
+    }
+
+    /// Farthest point along `direction` (body frame)
+    pub fn support(&self, direction: DVec3) -> DVec3 {
+        match self {
+            Shape::Sphere { radius } => direction.normalize_or_zero() * *radius,
+            Shape::Cuboid { half_extents } => DVec3::new(
+                half_extents.x.copysign(direction.x),
+                half_extents.y.copysign(direction.y),
+                half_extents.z.copysign(direction.z),
+            ),
+            Shape::Capsule {
+                radius,
+                half_height,
+            } => {
+                DVec3::new(0.0, half_height.copysign(direction.y), 0.0)
+                    + direction.normalize_or_zero() * *radius
+            }
+            Shape::ConvexMesh { vertices, .. } => *vertices
+                .iter()
+                .max_by(|a, b| a.dot(direction).total_cmp(&b.dot(direction)))
+                .expect("mesh has vertices"),
+        }
+    }
+
+    /// Radius of the smallest origin-centred sphere containing the shape
+    pub fn bounding_radius(&self) -> f64 {
+        match self {
+            Shape::Sphere { radius } => *radius,
+            Shape::Cuboid { half_extents } => half_extents.length(),
+            Shape::Capsule {
+                radius,
+                half_height,
+            } => radius + half_height,
+            Shape::ConvexMesh { vertices, .. } => {
+                vertices.iter().fold(0.0, |m: f64, v| m.max(v.length()))
+            }
+        }
+    }
+}
+
+fn check_positive(name: &str, value: f64) -> Result<(), RigidBodyError> {
+    if value > 0.0 && value.is_finite() {
+        Ok(())
+    } else {
+        Err(RigidBodyError::InvalidParameter(format!(
+            "{} {} must be positive",
+            name, value
+        )))
+    }
+}
+
+/// Matrix S(r) with S(r)·x = r × x
+pub(crate) fn skew(r: DVec3) -> DMat3 {
+    DMat3::from_cols(
+        DVec3::new(0.0, r.z, -r.y),
+        DVec3::new(-r.z, 0.0, r.x),
+        DVec3::new(r.y, -r.x, 0.0),
+    )
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// RIGID BODY
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// Rigid body; `position` is the centre of mass in world coordinates
+#[derive(Clone, Debug)]
+pub struct RigidBody {
+    pub shape: Shape,
+    pub position: DVec3,
+    pub orientation: DQuat,
+    pub linear_velocity: DVec3,
+    pub angular_velocity: DVec3,
+    /// 0 for static bodies
+    inverse_mass: f64,
+    inertia_body: DMat3,
+    inverse_inertia_body: DMat3,
+    /// Coefficient of friction μ (combined as √(μ_a·μ_b))
+    pub friction: f64,
+    /// Coefficient of restitution e (combined as max)
+    pub restitution: f64,
+    /// Linear and angular velocity decay per second
+    pub linear_damping: f64,
+    pub angular_damping: f64,
+    force: DVec3,
+    torque: DVec3,
+}
+
+impl RigidBody {
+    /// Dynamic body of uniform `density`
+    pub fn new(shape: Shape, density: f64) -> Result<Self, RigidBodyError> {
+        check_positive("density", density)?;
+        let properties = shape.mass_properties(density);
+        Self::from_mass_properties(shape, properties)
+    }
+
+    /// Dynamic body with externally supplied mass properties (e.g. from CAD)
+    pub fn from_mass_properties(
+        shape: Shape,
+        properties: MassProperties,
+    ) -> Result<Self, RigidBodyError> {
+        check_positive("mass", properties.mass)?;
+        let inverse_inertia = properties.inertia.inverse();
+        if !inverse_inertia.is_finite() || properties.inertia.determinant() <= 0.0 {
+            return Err(RigidBodyError::InvalidParameter(
+                "inertia tensor must be positive definite".to_string(),
+            ));
+        }
+        Ok(Self {
+            inverse_mass: 1.0 / properties.mass,
+            inertia_body: properties.inertia,
+            inverse_inertia_body: inverse_inertia,
+            ..Self::fixed(shape)
+        })
+    }
+
+    /// Immovable body (infinite mass)
+    pub fn fixed(shape: Shape) -> Self {
+        Self {
+            shape,
+            position: DVec3::ZERO,
+            orientation: DQuat::IDENTITY,
+            linear_velocity: DVec3::ZERO,
+            angular_velocity: DVec3::ZERO,
+            inverse_mass: 0.0,
+            inertia_body: DMat3::ZERO,
+            inverse_inertia_body: DMat3::ZERO,
+            friction: 0.5,
+            restitution: 0.0,
+            linear_damping: 0.0,
+            angular_damping: 0.0,
+            force: DVec3::ZERO,
+            torque: DVec3::ZERO,
+        }
+    }
+
+    pub fn with_position(mut self, position: DVec3) -> Self {
+        self.position = position;
+        self
+    }
+
+    pub fn with_orientation(mut self, orientation: DQuat) -> Self {
+        self.orientation = orientation.normalize();
+        self
+    }
+
+    pub fn with_velocity(mut self, linear: DVec3, angular: DVec3) -> Self {
+        self.linear_velocity = linear;
+        self.angular_velocity = angular;
+        self
+    }
+
+    pub fn with_material(mut self, friction: f64, restitution: f64) -> Self {
+        self.friction = friction;
+        self.restitution = restitution;
+        self
+    }
+
+    pub fn with_damping(mut self, linear: f64, angular: f64) -> Self {
+        self.linear_damping = linear;
+        self.angular_damping = angular;
+        self
+    }
+
+    pub fn is_static(&self) -> bool {
+        self.inverse_mass == 0.0
+    }
+
+    pub fn mass(&self) -> f64 {
+        if self.is_static() {
+            f64::INFINITY
+        } else {
+            1.0 / self.inverse_mass
+        }
+    }
+
+    pub fn inverse_mass(&self) -> f64 {
+        self.inverse_mass
+    }
+
+    pub fn rotation(&self) -> DMat3 {
+        DMat3::from_quat(self.orientation)
+    }
+
+    /// I in world coordinates
+    pub fn inertia_world(&self) -> DMat3 {
+        let r = self.rotation();
+        r * self.inertia_body * r.transpose()
+    }
+
+    /// I⁻¹ in world coordinates
+    pub fn inverse_inertia_world(&self) -> DMat3 {
+        let r = self.rotation();
+        r * self.inverse_inertia_body * r.transpose()
+    }
+
+    /// Body-frame point to world
+    pub fn to_world(&self, local: DVec3) -> DVec3 {
+        self.position + self.orientation * local
+    }
+
+    /// World point to body frame
+    pub fn to_local(&self, world: DVec3) -> DVec3 {
+        self.orientation.inverse() * (world - self.position)
+    }
+
+    /// Velocity of the material point at `point` (world)
+    pub fn velocity_at(&self, point: DVec3) -> DVec3 {
+        self.linear_velocity + self.angular_velocity.cross(point - self.position)
+    }
+
+    /// Farthest world point along `direction`
+    pub fn support(&self, direction: DVec3) -> DVec3 {
+        let local = self.orientation.inverse() * direction;
+        self.to_world(self.shape.support(local))
+    }
+
+    pub fn apply_force(&mut self, force: DVec3) {
+        self.force += force;
+    }
+
+    /// Force applied at a world point (adds the torque (p - x) × F)
+    pub fn apply_force_at(&mut self, force: DVec3, point: DVec3) {
+        self.force += force;
+        self.torque += (point - self.position).cross(force);
+    }
+
+    pub fn apply_torque(&mut self, torque: DVec3) {
+        self.torque += torque;
+    }
+
+    /// Instantaneous impulse at a world point
+    pub fn apply_impulse(&mut self, impulse: DVec3, point: DVec3) {
+        self.linear_velocity += impulse * self.inverse_mass;
+        self.angular_velocity +=
+            self.inverse_inertia_world() * (point - self.position).cross(impulse);
+    }
+
+    pub fn kinetic_energy(&self) -> f64 {
+        if self.is_static() {
+            return 0.0;
+        }
+        0.5 * self.mass() * self.linear_velocity.length_squared()
+            + 0.5
+                * self
+                    .angular_velocity
+                    .dot(self.inertia_world() * self.angular_velocity)
+    }
+
+    pub fn linear_momentum(&self) -> DVec3 {
+        if self.is_static() {
+            DVec3::ZERO
+        } else {
+            self.linear_velocity * self.mass()
+        }
+    }
+
+    /// Angular momentum about the centre of mass
+    pub fn angular_momentum(&self) -> DVec3 {
+        self.inertia_world() * self.angular_velocity
+    }
+
+    /// Apply gravity, accumulated forces, gyroscopic torque and damping, then
+    /// clear the accumulators
+    pub fn integrate_velocity(&mut self, dt: f64, gravity: DVec3) {
+        if self.is_static() {
+            self.force = DVec3::ZERO;
+            self.torque = DVec3::ZERO;
+            return;
+        }
+        self.linear_velocity += dt * (gravity + self.force * self.inverse_mass);
+
+        // Implicit gyroscopic step in the body frame (stable for fast spin)
+        let q = self.orientation;
+        let omega = q.inverse() * self.angular_velocity;
+        let i = self.inertia_body;
+        let residual = dt * omega.cross(i * omega);
+        let jacobian = i + dt * (skew(omega) * i - skew(i * omega));
+        let omega = omega - jacobian.inverse() * residual;
+        self.angular_velocity = q * omega + dt * (self.inverse_inertia_world() * self.torque);
+
+        self.linear_velocity *= 1.0 / (1.0 + dt * self.linear_damping);
+        self.angular_velocity *= 1.0 / (1.0 + dt * self.angular_damping);
+        self.force = DVec3::ZERO;
+        self.torque = DVec3::ZERO;
+    }
+
+    /// Advance position and orientation with the current velocities
+    pub fn integrate_position(&mut self, dt: f64) {
+        if self.is_static() {
+            return;
+        }
+        self.position += dt * self.linear_velocity;
+        let spin = DQuat::from_xyzw(
+            self.angular_velocity.x,
+            self.angular_velocity.y,
+            self.angular_velocity.z,
+            0.0,
+        ) * self.orientation;
+        self.orientation = (self.orientation + spin * (0.5 * dt)).normalize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Unit cube [0, 1]³ as 12 outward triangles
+    fn unit_cube() -> (Vec<DVec3>, Vec<[usize; 3]>) {
+        let vertices = (0..8)
+            .map(|i| DVec3::new((i & 1) as f64, ((i >> 1) & 1) as f64, ((i >> 2) & 1) as f64))
+            .collect();
+        let triangles = vec![
+            [0, 2, 1],
+            [1, 2, 3],
+            [4, 5, 6],
+            [5, 7, 6],
+            [0, 1, 4],
+            [1, 5, 4],
+            [2, 6, 3],
+            [3, 6, 7],
+            [0, 4, 2],
+            [2, 4, 6],
+            [1, 3, 5],
+            [3, 7, 5],
+        ];
+        (vertices, triangles)
+    }
+
+    #[test]
+    fn test_mass_properties() {
+        let density = 2.0;
+        // Mesh cube matches the analytic cuboid
+        let (vertices, triangles) = unit_cube();
+        let (mesh, centroid) = Shape::convex_mesh(vertices, triangles).unwrap();
+        assert!((centroid - DVec3::splat(0.5)).length() < 1e-12);
+        let cuboid = Shape::cuboid(DVec3::splat(0.5)).unwrap();
+        let (a, b) = (
+            mesh.mass_properties(density),
+            cuboid.mass_properties(density),
+        );
+        assert!((a.mass - 2.0).abs() < 1e-12);
+        assert!((a.inertia - b.inertia).abs_diff_eq(DMat3::ZERO, 1e-12));
+        assert!((b.inertia.x_axis.x - 2.0 / 6.0).abs() < 1e-12);
+
+        // Capsule tends to a sphere as the segment shrinks
+        let capsule = Shape::capsule(1.0, 1e-9).unwrap().mass_properties(1.0);
+        let sphere = Shape::sphere(1.0).unwrap().mass_properties(1.0);
+        assert!((capsule.mass - sphere.mass).abs() < 1e-6);
+        assert!((capsule.inertia.x_axis.x - sphere.inertia.x_axis.x).abs() < 1e-6);
+
+        assert!(Shape::sphere(-1.0).is_err());
+        let (vertices, mut triangles) = unit_cube();
+        triangles.iter_mut().for_each(|t| t.swap(1, 2));
+        assert!(Shape::convex_mesh(vertices, triangles).is_err());
+    }
+
+    #[test]
+    fn test_torque_free_rotation_conserves_momentum() {
+        // Spin near the unstable intermediate axis (Dzhanibekov effect)
+        let shape = Shape::cuboid(DVec3::new(0.1, 0.3, 0.6)).unwrap();
+        let mut body = RigidBody::new(shape, 1000.0)
+            .unwrap()
+            .with_velocity(DVec3::ZERO, DVec3::new(0.01, 8.0, 0.01));
+        let (momentum, energy) = (body.angular_momentum(), body.kinetic_energy());
+        let dt = 1e-3;
+        let mut flipped = false;
+        for _ in 0..4000 {
+            body.integrate_velocity(dt, DVec3::ZERO);
+            body.integrate_position(dt);
+            flipped |= (body.rotation() * DVec3::Y).dot(DVec3::Y) < 0.0;
+        }
+        assert!(flipped, "intermediate axis should flip");
+        assert!((body.angular_momentum() - momentum).length() < 0.01 * momentum.length());
+        // The implicit gyroscopic step dissipates slightly and never gains energy
+        assert!(body.kinetic_energy() <= energy * (1.0 + 1e-9));
+        assert!(body.kinetic_energy() > 0.9 * energy);
+        assert!((body.orientation.length() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_impulse_and_free_fall() {
+        let mut body = RigidBody::new(Shape::sphere(0.5).unwrap(), 1.0).unwrap();
+        let mass = body.mass();
+        // Off-centre impulse: Δv = J/m, Δω = I⁻¹ (r × J)
+        body.apply_impulse(DVec3::X * mass, DVec3::new(0.0, 0.5, 0.0));
+        assert!((body.linear_velocity - DVec3::X).length() < 1e-12);
+        let expected = -0.5 * mass / (0.4 * mass * 0.25);
+        assert!((body.angular_velocity.z - expected).abs() < 1e-12);
+
+        let mut body = RigidBody::new(Shape::sphere(0.5).unwrap(), 1.0).unwrap();
+        let g = DVec3::new(0.0, -9.81, 0.0);
+        for _ in 0..100 {
+            body.integrate_velocity(0.01, g);
+            body.integrate_position(0.01);
+        }
+        // Semi-implicit Euler: y = -g·Δt²·n(n+1)/2
+        assert!((body.position.y + 9.81 * 1e-4 * 5050.0).abs() < 1e-9);
+        assert!(RigidBody::fixed(Shape::sphere(1.0).unwrap()).is_static());
+    }
+}
@@ -0,0 +1,1149 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: soft_body.rs | DNA/src/physics/mechanics/soft_body.rs
+//! PURPOSE: Deformable bodies: mass-spring networks and position-based dynamics
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! PURPOSE: Cloth, rope and pressurised shells as particles joined by springs
+//!          and position constraints
+//!
+//! LAYER: DNA → PHYSICS → MECHANICS
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ ALGORITHM (one substep h = Δt/n, XPBD)                                      │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ 1. v += h·w·(m·g + F_ext + F_spring),  F = -k(l - l₀)n̂ - c(v_ab·n̂)n̂        │
+//! │ 2. x* = x + h·v                                                             │
+//! │ 3. Project constraints (Gauss-Seidel), α̃ = α/h²:                            │
+//! │      Δλ = (-C - α̃λ) / (Σ wᵢ|∇ᵢC|² + α̃),   xᵢ += wᵢ ∇ᵢC Δλ                  │
+//! │ 4. Self collision: particles closer than the thickness are pushed apart     │
+//! │    (pairs already closer at rest are skipped); ground plane with friction   │
+//! │ 5. v = (x* - x)/h                                                           │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ CONSTRAINTS                                                                 │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ Distance  C = |x_a - x_b| - l₀                                              │
+//! │ Bending   C = θ - θ₀, θ the signed dihedral angle across edge a → b of      │
+//! │           triangles (a, b, c) and (b, a, d):                                │
+//! │             ∇_c θ = -|e| N₁/|N₁|²,  ∇_d θ = -|e| N₂/|N₂|²                   │
+//! │             ∇_a θ = -(1 - s_c)∇_c θ - (1 - s_d)∇_d θ,  ∇_b = -∇_a-∇_c-∇_d   │
+//! │           s = (x - x_a)·e/|e|², the projection onto the edge               │
+//! │ Volume    C = Σ x_a·(x_b × x_c)/6 - p·V₀,  ∇_a C = Σ x_b × x_c / 6          │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ DATA DEFINED                                                                │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ Spring             Hookean spring with dashpot (mass-spring networks)       │
+//! │ Constraint         Distance, Bending, Volume with compliance α = 1/k        │
+//! │ SoftBodySettings   Gravity, substeps, damping, ground, self collision       │
+//! │ SoftBody           Particles, springs, constraints; cloth / rope / balloon  │
+//! │ SoftBodyError      Bad parameter, open mesh, particle index                 │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! Pinned particles have zero inverse mass; move them by writing `positions`.
+//!
+//! DEPENDS ON:
+//!   • data::TriangleMesh  → Cloth and shell topology (edges, hinges)
+//!   • data::UniformGrid3  → Self-collision neighbor search (cell ≥ thickness)
+//!
+//! REFERENCE: Müller et al., "Position Based Dynamics" (2007)
+//!            Macklin et al., "XPBD: Position-Based Simulation of Compliant
+//!            Constrained Dynamics" (2016); "Small Steps in Physics" (2019)
+//!            Bridson et al., "Simulation of Clothing with Folds and Wrinkles" (2003)
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use crate::data::mesh::TriangleMesh;
+use crate::data::spatial_grid::UniformGrid3;
+use glam::Vec3;
+
+/// Particles per self-collision grid cell
+const CELL_CAPACITY: usize = 32;
+
+/// Self-collision grid cells per axis at most (the cell grows instead)
+const MAX_CELLS_PER_AXIS: f32 = 32.0;
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// ERRORS
+// ─────────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SoftBodyError {
+    InvalidParameter(String),
+    UnknownParticle(usize),
+    /// Self collision indexes particles as u16
+    TooManyParticles {
+        count: usize,
+    },
+}
+
+impl std::fmt::Display for SoftBodyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SoftBodyError::InvalidParameter(msg) => write!(f, "Invalid parameter: {}", msg),
+            SoftBodyError::UnknownParticle(index) => {
+                write!(f, "No particle with index {}", index)
+            }
+            SoftBodyError::TooManyParticles { count } => write!(
+                f,
+                "{} particles exceed the self-collision limit of {}",
+                count,
+                u16::MAX as usize + 1
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SoftBodyError {}
+
+fn check_positive(name: &str, value: f32) -> Result<(), SoftBodyError> {
+    if value > 0.0 && value.is_finite() {
+        Ok(())
+    } else {
+        Err(SoftBodyError::InvalidParameter(format!(
+            "{} {} must be positive",
+            name, value
+        )))
+    }
+}
+
+fn check_non_negative(name: &str, value: f32) -> Result<(), SoftBodyError> {
+    if value >= 0.0 && value.is_finite() {
+        Ok(())
+    } else {
+        Err(SoftBodyError::InvalidParameter(format!(
+            "{} {} must be non-negative",
+            name, value
+        )))
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// SPRINGS AND CONSTRAINTS
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// Hookean spring with a dashpot along its axis
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Spring {
+    pub a: usize,
+    pub b: usize,
+    pub rest_length: f32,
+    /// k (N/m)
+    pub stiffness: f32,
+    /// c (N·s/m)
+    pub damping: f32,
+}
+
+impl Spring {
+    /// Force on particle `a` (particle `b` receives the opposite)
+    pub fn force(&self, positions: &[Vec3], velocities: &[Vec3]) -> Vec3 {
+        let delta = positions[self.b] - positions[self.a];
+        let length = delta.length();
+        if length < 1e-9 {
+            return Vec3::ZERO;
+        }
+        let n = delta / length;
+        let closing = (velocities[self.b] - velocities[self.a]).dot(n);
+        n * (self.stiffness * (length - self.rest_length) + self.damping * closing)
+    }
+}
+
+/// Position constraint; compliance α = 1/stiffness, 0 for an inextensible one
+#[derive(Clone, Debug, PartialEq)]
+pub enum Constraint {
+    Distance {
+        a: usize,
+        b: usize,
+        rest_length: f32,
+        compliance: f32,
+    },
+    /// Dihedral angle across edge a → b of triangles (a, b, c) and (b, a, d)
+    Bending {
+        particles: [usize; 4],
+        rest_angle: f32,
+        compliance: f32,
+    },
+    /// Volume enclosed by a closed, outward-facing triangle surface
+    Volume {
+        triangles: Vec<[usize; 3]>,
+        /// Distinct particles of `triangles`
+        particles: Vec<usize>,
+        /// Target volume p·V₀ (m³)
+        target_volume: f32,
+        compliance: f32,
+    },
+}
+
+impl Constraint {
+    /// C(x); zero when satisfied
+    pub fn value(&self, positions: &[Vec3]) -> f32 {
+        match self {
+            Constraint::Distance {
+                a, b, rest_length, ..
+            } => (positions[*a] - positions[*b]).length() - rest_length,
+            Constraint::Bending {
+                particles,
+                rest_angle,
+                ..
+            } => dihedral_angle(particles.map(|i| positions[i])) - rest_angle,
+            Constraint::Volume {
+                triangles,
+                target_volume,
+                ..
+            } => enclosed_volume(triangles, positions) - target_volume,
+        }
+    }
+
+    fn compliance(&self) -> f32 {
+        match self {
+            Constraint::Distance { compliance, .. }
+            | Constraint::Bending { compliance, .. }
+            | Constraint::Volume { compliance, .. } => *compliance,
+        }
+    }
+
+    /// One XPBD projection; returns Δλ
+    fn project(
+        &self,
+        positions: &mut [Vec3],
+        inverse_masses: &[f32],
+        lambda: f32,
+        alpha: f32,
+        gradients: &mut [Vec3],
+    ) -> f32 {
+        let c = self.value(positions);
+        match self {
+            Constraint::Distance { a, b, .. } => {
+                let (a, b) = (*a, *b);
+                let n = (positions[a] - positions[b]).normalize_or_zero();
+                let w = inverse_masses[a] + inverse_masses[b];
+                if w + alpha == 0.0 || n == Vec3::ZERO {
+                    return 0.0;
+                }
+                let dl = (-c - alpha * lambda) / (w + alpha);
+                positions[a] += inverse_masses[a] * dl * n;
+                positions[b] -= inverse_masses[b] * dl * n;
+                dl
+            }
+            Constraint::Bending { particles, .. } => {
+                let Some(grad) = dihedral_gradient(particles.map(|i| positions[i])) else {
+                    return 0.0;
+                };
+                let w: f32 = (0..4)
+                    .map(|k| inverse_masses[particles[k]] * grad[k].length_squared())
+                    .sum();
+                if w + alpha < 1e-12 {
+                    return 0.0;
+                }
+                let dl = (-c - alpha * lambda) / (w + alpha);
+                for k in 0..4 {
+                    let i = particles[k];
+                    positions[i] += inverse_masses[i] * dl * grad[k];
+                }
+                dl
+            }
+            Constraint::Volume {
+                triangles,
+                particles,
+                ..
+            } => {
+                for &i in particles {
+                    gradients[i] = Vec3::ZERO;
+                }
+                for &[a, b, c] in triangles {
+                    let (pa, pb, pc) = (positions[a], positions[b], positions[c]);
+                    gradients[a] += pb.cross(pc) / 6.0;
+                    gradients[b] += pc.cross(pa) / 6.0;
+                    gradients[c] += pa.cross(pb) / 6.0;
+                }
+                let w: f32 = particles
+                    .iter()
+                    .map(|&i| inverse_masses[i] * gradients[i].length_squared())
+                    .sum();
+                if w + alpha < 1e-12 {
+                    return 0.0;
+                }
+                let dl = (-c - alpha * lambda) / (w + alpha);
+                for &i in particles {
+                    positions[i] += inverse_masses[i] * dl * gradients[i];
+                }
+                dl
+            }
+        }
+    }
+}
+
+/// Signed dihedral angle across edge a → b; 0 when the triangles (a, b, c)
+/// and (b, a, d) are coplanar, positive when they fold towards the normals
+pub fn dihedral_angle([a, b, c, d]: [Vec3; 4]) -> f32 {
+    let e = (b - a).normalize_or_zero();
+    let n1 = (b - a).cross(c - a).normalize_or_zero();
+    let n2 = (a - b).cross(d - b).normalize_or_zero();
+    e.dot(n1.cross(n2)).atan2(n1.dot(n2))
+}
+
+/// ∇θ for the four particles, None for degenerate triangles
+fn dihedral_gradient([a, b, c, d]: [Vec3; 4]) -> Option<[Vec3; 4]> {
+    let e = b - a;
+    let length_sq = e.length_squared();
+    let n1 = e.cross(c - a);
+    let n2 = (a - b).cross(d - b);
+    let (n1_sq, n2_sq) = (n1.length_squared(), n2.length_squared());
+    if length_sq < 1e-18 || n1_sq < 1e-18 || n2_sq < 1e-18 {
+        return None;
+    }
+    let length = length_sq.sqrt();
+    let grad_c = -length / n1_sq * n1;
+    let grad_d = -length / n2_sq * n2;
+    let s_c = (c - a).dot(e) / length_sq;
+    let s_d = (d - a).dot(e) / length_sq;
+    let grad_a = -(1.0 - s_c) * grad_c - (1.0 - s_d) * grad_d;
+    let grad_b = -s_c * grad_c - s_d * grad_d;
+    Some([grad_a, grad_b, grad_c, grad_d])
+}
+
+fn enclosed_volume(triangles: &[[usize; 3]], positions: &[Vec3]) -> f32 {
+    triangles
+        .iter()
+        .map(|&[a, b, c]| positions[a].dot(positions[b].cross(positions[c])) / 6.0)
+        .sum()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// SETTINGS
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// Stepping parameters
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SoftBodySettings {
+    pub gravity: Vec3,
+    /// Substeps per step; stiff springs need h < 2√(m/k)
+    pub substeps: usize,
+    /// Constraint sweeps per substep
+    pub iterations: usize,
+    /// Velocity decay per second
+    pub damping: f32,
+    /// Height of a horizontal ground plane
+    pub ground: Option<f32>,
+    /// Coulomb friction coefficient against the ground
+    pub friction: f32,
+    /// Minimum distance kept between particles (m), None disables self collision
+    pub thickness: Option<f32>,
+}
+
+impl Default for SoftBodySettings {
+    fn default() -> Self {
+        Self {
+            gravity: Vec3::new(0.0, -9.81, 0.0),
+            substeps: 10,
+            iterations: 1,
+            damping: 0.0,
+            ground: None,
+            friction: 0.5,
+            thickness: None,
+        }
+    }
+}
+
+impl SoftBodySettings {
+    pub fn with_substeps(mut self, substeps: usize, iterations: usize) -> Self {
+        self.substeps = substeps;
+        self.iterations = iterations;
+        self
+    }
+
+    pub fn with_damping(mut self, damping: f32) -> Self {
+        self.damping = damping;
+        self
+    }
+
+    pub fn with_ground(mut self, height: f32, friction: f32) -> Self {
+        self.ground = Some(height);
+        self.friction = friction;
+        self
+    }
+
+    pub fn with_self_collision(mut self, thickness: f32) -> Self {
+        self.thickness = Some(thickness);
+        self
+    }
+
+    fn check(&self) -> Result<(), SoftBodyError> {
+        if self.substeps == 0 || self.iterations == 0 {
+            return Err(SoftBodyError::InvalidParameter(
+                "substeps and iterations must be at least 1".to_string(),
+            ));
+        }
+        check_non_negative("damping", self.damping)?;
+        check_non_negative("friction", self.friction)?;
+        if let Some(thickness) = self.thickness {
+            check_positive("thickness", thickness)?;
+        }
+        Ok(())
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// SELF-COLLISION GRID
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// UniformGrid3 over a padded box around the particles, rebuilt only when the
+/// particles leave it
+struct CollisionGrid {
+    origin: Vec3,
+    extent: Vec3,
+    cell_size: f32,
+    grid: UniformGrid3<CELL_CAPACITY>,
+}
+
+impl CollisionGrid {
+    fn new(min: Vec3, max: Vec3, thickness: f32) -> Self {
+        let pad = 0.25 * (max - min) + Vec3::splat(2.0 * thickness);
+        let (origin, extent) = (min - pad, max - min + 2.0 * pad);
+        let cell_size = thickness.max(extent.max_element() / MAX_CELLS_PER_AXIS);
+        Self {
+            origin,
+            extent,
+            cell_size,
+            grid: UniformGrid3::new(extent.x, extent.y, extent.z, cell_size),
+        }
+    }
+
+    fn contains(&self, min: Vec3, max: Vec3, thickness: f32) -> bool {
+        self.cell_size >= thickness
+            && min.cmpge(self.origin).all()
+            && max.cmple(self.origin + self.extent).all()
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// SOFT BODY
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// Particles joined by springs (force based) and constraints (position based)
+pub struct SoftBody {
+    pub positions: Vec<Vec3>,
+    pub velocities: Vec<Vec3>,
+    /// 0 for pinned particles
+    inverse_masses: Vec<f32>,
+    /// Positions at construction; closer pairs never self-collide
+    rest_positions: Vec<Vec3>,
+    springs: Vec<Spring>,
+    constraints: Vec<Constraint>,
+    pub settings: SoftBodySettings,
+    forces: Vec<Vec3>,
+    previous: Vec<Vec3>,
+    lambdas: Vec<f32>,
+    gradients: Vec<Vec3>,
+    grid: Option<CollisionGrid>,
+    candidates: Vec<u16>,
+    /// Simulated time (s)
+    pub time: f32,
+}
+
+impl SoftBody {
+    /// Free particles with the given masses (kg); mass 0 pins a particle
+    pub fn new(positions: Vec<Vec3>, masses: &[f32]) -> Result<Self, SoftBodyError> {
+        if positions.len() != masses.len() {
+            return Err(SoftBodyError::InvalidParameter(format!(
+                "{} positions but {} masses",
+                positions.len(),
+                masses.len()
+            )));
+        }
+        for &mass in masses {
+            check_non_negative("mass", mass)?;
+        }
+        let n = positions.len();
+        Ok(Self {
+            velocities: vec![Vec3::ZERO; n],
+            inverse_masses: masses
+                .iter()
+                .map(|&m| if m > 0.0 { 1.0 / m } else { 0.0 })
+                .collect(),
+            rest_positions: positions.clone(),
+            positions,
+            springs: Vec::new(),
+            constraints: Vec::new(),
+            settings: SoftBodySettings::default(),
+            forces: vec![Vec3::ZERO; n],
+            previous: vec![Vec3::ZERO; n],
+            lambdas: Vec::new(),
+            gradients: vec![Vec3::ZERO; n],
+            grid: None,
+            candidates: vec![0; 27 * CELL_CAPACITY],
+            time: 0.0,
+        })
+    }
+
+    /// Particles at the mesh vertices with masses lumped from triangle areas
+    /// (a third of each adjacent triangle); unused vertices are pinned
+    fn from_surface(mesh: &TriangleMesh, areal_density: f32) -> Result<Self, SoftBodyError> {
+        check_positive("areal density", areal_density)?;
+        let mut masses = vec![0.0; mesh.vertex_count()];
+        for (t, triangle) in mesh.triangles.iter().enumerate() {
+            let share = areal_density * mesh.triangle_area(t) / 3.0;
+            for &i in triangle {
+                masses[i] += share;
+            }
+        }
+        Self::new(mesh.vertices.clone(), &masses)
+    }
+
+    /// PBD cloth: distance constraints on the mesh edges and bending
+    /// constraints across its interior edges
+    pub fn cloth(
+        mesh: &TriangleMesh,
+        areal_density: f32,
+        stretch_compliance: f32,
+        bend_compliance: f32,
+    ) -> Result<Self, SoftBodyError> {
+        let mut body = Self::from_surface(mesh, areal_density)?;
+        for [a, b] in mesh.edges() {
+            body.add_distance(a, b, stretch_compliance)?;
+        }
+        for hinge in mesh.hinges() {
+            body.add_bending(hinge, bend_compliance)?;
+        }
+        Ok(body)
+    }
+
+    /// Mass-spring network: structural springs on the mesh edges and flexion
+    /// springs between the opposite vertices of each interior edge
+    pub fn mass_spring(
+        mesh: &TriangleMesh,
+        areal_density: f32,
+        stiffness: f32,
+        bend_stiffness: f32,
+        damping: f32,
+    ) -> Result<Self, SoftBodyError> {
+        let mut body = Self::from_surface(mesh, areal_density)?;
+        for [a, b] in mesh.edges() {
+            body.add_spring(a, b, stiffness, damping)?;
+        }
+        if bend_stiffness > 0.0 {
+            for [_, _, c, d] in mesh.hinges() {
+                body.add_spring(c, d, bend_stiffness, damping)?;
+            }
+        }
+        Ok(body)
+    }
+
+    /// Closed shell of `mass` kg holding `pressure` × its rest volume, with
+    /// cloth constraints on the surface
+    pub fn balloon(
+        mesh: &TriangleMesh,
+        mass: f32,
+        pressure: f32,
+        stretch_compliance: f32,
+        bend_compliance: f32,
+    ) -> Result<Self, SoftBodyError> {
+        if !mesh.is_closed() {
+            return Err(SoftBodyError::InvalidParameter(
+                "balloon mesh must be closed".to_string(),
+            ));
+        }
+        check_positive("mass", mass)?;
+        let mut body = Self::cloth(
+            mesh,
+            mass / mesh.area(),
+            stretch_compliance,
+            bend_compliance,
+        )?;
+        body.add_volume(mesh.triangles.clone(), pressure, 0.0)?;
+        Ok(body)
+    }
+
+    /// Chain of `segments` distance constraints from `start` to `end`
+    pub fn rope(
+        start: Vec3,
+        end: Vec3,
+        segments: usize,
+        mass: f32,
+        compliance: f32,
+    ) -> Result<Self, SoftBodyError> {
+        if segments == 0 {
+            return Err(SoftBodyError::InvalidParameter(
+                "rope needs at least one segment".to_string(),
+            ));
+        }
+        check_positive("mass", mass)?;
+        let positions = (0..=segments)
+            .map(|i| start.lerp(end, i as f32 / segments as f32))
+            .collect();
+        let masses = vec![mass / (segments + 1) as f32; segments + 1];
+        let mut body = Self::new(positions, &masses)?;
+        for i in 0..segments {
+            body.add_distance(i, i + 1, compliance)?;
+        }
+        Ok(body)
+    }
+
+    pub fn with_settings(mut self, settings: SoftBodySettings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    fn check_particle(&self, index: usize) -> Result<(), SoftBodyError> {
+        if index < self.positions.len() {
+            Ok(())
+        } else {
+            Err(SoftBodyError::UnknownParticle(index))
+        }
+    }
+
+    /// Spring with the current distance as rest length; returns its index
+    pub fn add_spring(
+        &mut self,
+        a: usize,
+        b: usize,
+        stiffness: f32,
+        damping: f32,
+    ) -> Result<usize, SoftBodyError> {
+        self.check_particle(a)?;
+        self.check_particle(b)?;
+        check_positive("stiffness", stiffness)?;
+        check_non_negative("damping", damping)?;
+        self.springs.push(Spring {
+            a,
+            b,
+            rest_length: (self.positions[a] - self.positions[b]).length(),
+            stiffness,
+            damping,
+        });
+        Ok(self.springs.len() - 1)
+    }
+
+    /// Keep the current distance between two particles
+    pub fn add_distance(
+        &mut self,
+        a: usize,
+        b: usize,
+        compliance: f32,
+    ) -> Result<usize, SoftBodyError> {
+        self.check_particle(a)?;
+        self.check_particle(b)?;
+        check_non_negative("compliance", compliance)?;
+        let rest_length = (self.positions[a] - self.positions[b]).length();
+        self.add_constraint(Constraint::Distance {
+            a,
+            b,
+            rest_length,
+            compliance,
+        })
+    }
+
+    /// Keep the current dihedral angle across edge a → b of triangles
+    /// (a, b, c) and (b, a, d)
+    pub fn add_bending(
+        &mut self,
+        particles: [usize; 4],
+        compliance: f32,
+    ) -> Result<usize, SoftBodyError> {
+        for i in particles {
+            self.check_particle(i)?;
+        }
+        check_non_negative("compliance", compliance)?;
+        let rest_angle = dihedral_angle(particles.map(|i| self.positions[i]));
+        self.add_constraint(Constraint::Bending {
+            particles,
+            rest_angle,
+            compliance,
+        })
+    }
+
+    /// Hold `pressure` × the current volume enclosed by `triangles`
+    pub fn add_volume(
+        &mut self,
+        triangles: Vec<[usize; 3]>,
+        pressure: f32,
+        compliance: f32,
+    ) -> Result<usize, SoftBodyError> {
+        for &i in triangles.iter().flatten() {
+            self.check_particle(i)?;
+        }
+        check_positive("pressure", pressure)?;
+        check_non_negative("compliance", compliance)?;
+        let volume = enclosed_volume(&triangles, &self.positions);
+        check_positive("enclosed volume", volume)?;
+        let mut particles: Vec<usize> = triangles.iter().flatten().copied().collect();
+        particles.sort_unstable();
+        particles.dedup();
+        self.add_constraint(Constraint::Volume {
+            triangles,
+            particles,
+            target_volume: pressure * volume,
+            compliance,
+        })
+    }
+
+    fn add_constraint(&mut self, constraint: Constraint) -> Result<usize, SoftBodyError> {
+        self.constraints.push(constraint);
+        self.lambdas.push(0.0);
+        Ok(self.constraints.len() - 1)
+    }
+
+    pub fn springs(&self) -> &[Spring] {
+        &self.springs
+    }
+
+    pub fn constraints(&self) -> &[Constraint] {
+        &self.constraints
+    }
+
+    pub fn particle_count(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Fix a particle in place
+    pub fn pin(&mut self, index: usize) -> Result<(), SoftBodyError> {
+        self.check_particle(index)?;
+        self.inverse_masses[index] = 0.0;
+        self.velocities[index] = Vec3::ZERO;
+        Ok(())
+    }
+
+    /// Release a particle with the given mass
+    pub fn unpin(&mut self, index: usize, mass: f32) -> Result<(), SoftBodyError> {
+        self.check_particle(index)?;
+        check_positive("mass", mass)?;
+        self.inverse_masses[index] = 1.0 / mass;
+        Ok(())
+    }
+
+    pub fn is_pinned(&self, index: usize) -> bool {
+        self.inverse_masses[index] == 0.0
+    }
+
+    /// Mass of a particle, infinite when pinned
+    pub fn mass(&self, index: usize) -> f32 {
+        match self.inverse_masses[index] {
+            w if w > 0.0 => 1.0 / w,
+            _ => f32::INFINITY,
+        }
+    }
+
+    /// Mass of the free particles
+    pub fn total_mass(&self) -> f32 {
+        self.inverse_masses
+            .iter()
+            .filter(|&&w| w > 0.0)
+            .map(|w| 1.0 / w)
+            .sum()
+    }
+
+    /// Force on a particle during the next step
+    pub fn apply_force(&mut self, index: usize, force: Vec3) -> Result<(), SoftBodyError> {
+        self.check_particle(index)?;
+        self.forces[index] += force;
+        Ok(())
+    }
+
+    pub fn kinetic_energy(&self) -> f32 {
+        self.velocities
+            .iter()
+            .zip(&self.inverse_masses)
+            .filter(|(_, &w)| w > 0.0)
+            .map(|(v, w)| 0.5 * v.length_squared() / w)
+            .sum()
+    }
+
+    /// Mass-weighted centre of the free particles
+    pub fn centre_of_mass(&self) -> Vec3 {
+        let mut total = 0.0;
+        let mut moment = Vec3::ZERO;
+        for (x, &w) in self.positions.iter().zip(&self.inverse_masses) {
+            if w > 0.0 {
+                total += 1.0 / w;
+                moment += *x / w;
+            }
+        }
+        if total > 0.0 {
+            moment / total
+        } else {
+            Vec3::ZERO
+        }
+    }
+
+    /// Largest |l/l₀ - 1| over springs and distance constraints
+    pub fn max_strain(&self) -> f32 {
+        let strain = |a: usize, b: usize, rest: f32| {
+            if rest > 0.0 {
+                ((self.positions[a] - self.positions[b]).length() / rest - 1.0).abs()
+            } else {
+                0.0
+            }
+        };
+        let springs = self.springs.iter().map(|s| strain(s.a, s.b, s.rest_length));
+        let constraints = self.constraints.iter().filter_map(|c| match c {
+            Constraint::Distance {
+                a, b, rest_length, ..
+            } => Some(strain(*a, *b, *rest_length)),
+            _ => None,
+        });
+        springs.chain(constraints).fold(0.0, f32::max)
+    }
+
+    /// Write the particle positions back into a mesh built from the same vertices
+    pub fn update_mesh(&self, mesh: &mut TriangleMesh) -> Result<(), SoftBodyError> {
+        if mesh.vertex_count() != self.positions.len() {
+            return Err(SoftBodyError::InvalidParameter(format!(
+                "mesh has {} vertices, body has {} particles",
+                mesh.vertex_count(),
+                self.positions.len()
+            )));
+        }
+        mesh.vertices.copy_from_slice(&self.positions);
+        Ok(())
+    }
+
+    /// Advance by `dt` in `settings.substeps` substeps
+    pub fn step(&mut self, dt: f32) -> Result<(), SoftBodyError> {
+        check_positive("time step", dt)?;
+        self.settings.check()?;
+        if self.settings.thickness.is_some() && self.positions.len() > u16::MAX as usize + 1 {
+            return Err(SoftBodyError::TooManyParticles {
+                count: self.positions.len(),
+            });
+        }
+        let h = dt / self.settings.substeps as f32;
+        for _ in 0..self.settings.substeps {
+            self.substep(h);
+        }
+        self.forces.fill(Vec3::ZERO);
+        self.time += dt;
+        Ok(())
+    }
+
+    fn substep(&mut self, h: f32) {
+        let settings = self.settings;
+
+        // Forces: gravity, external, springs
+        let mut accelerations: Vec<Vec3> = self
+            .forces
+            .iter()
+            .zip(&self.inverse_masses)
+            .map(|(f, &w)| {
+                if w > 0.0 {
+                    settings.gravity + *f * w
+                } else {
+                    Vec3::ZERO
+                }
+            })
+            .collect();
+        for spring in &self.springs {
+            let f = spring.force(&self.positions, &self.velocities);
+            accelerations[spring.a] += f * self.inverse_masses[spring.a];
+            accelerations[spring.b] -= f * self.inverse_masses[spring.b];
+        }
+        let decay = 1.0 / (1.0 + h * settings.damping);
+        for ((v, a), &w) in self
+            .velocities
+            .iter_mut()
+            .zip(&accelerations)
+            .zip(&self.inverse_masses)
+        {
+            *v = if w > 0.0 {
+                (*v + h * *a) * decay
+            } else {
+                Vec3::ZERO
+            };
+        }
+
+        // Predict
+        self.previous.copy_from_slice(&self.positions);
+        for (x, v) in self.positions.iter_mut().zip(&self.velocities) {
+            *x += h * *v;
+        }
+
+        // Constraints
+        self.lambdas.fill(0.0);
+        for _ in 0..settings.iterations {
+            for (constraint, lambda) in self.constraints.iter().zip(&mut self.lambdas) {
+                let alpha = constraint.compliance() / (h * h);
+                *lambda += constraint.project(
+                    &mut self.positions,
+                    &self.inverse_masses,
+                    *lambda,
+                    alpha,
+                    &mut self.gradients,
+                );
+            }
+            if let Some(thickness) = settings.thickness {
+                self.solve_self_collisions(thickness);
+            }
+        }
+        if let Some(ground) = settings.ground {
+            self.solve_ground(ground, settings.friction);
+        }
+
+        // Velocities from the position change
+        for ((v, x), p) in self
+            .velocities
+            .iter_mut()
+            .zip(&self.positions)
+            .zip(&self.previous)
+        {
+            *v = (*x - *p) / h;
+        }
+    }
+
+    /// Push particles closer than `thickness` apart along their separation
+    fn solve_self_collisions(&mut self, thickness: f32) {
+        let (min, max) = self.positions.iter().fold(
+            (Vec3::splat(f32::MAX), Vec3::splat(f32::MIN)),
+            |(lo, hi), x| (lo.min(*x), hi.max(*x)),
+        );
+        if !min.is_finite() || !max.is_finite() {
+            return;
+        }
+        let rebuild = match &self.grid {
+            Some(grid) => !grid.contains(min, max, thickness),
+            None => true,
+        };
+        if rebuild {
+            self.grid = Some(CollisionGrid::new(min, max, thickness));
+        }
+        let Some(cache) = self.grid.as_mut() else {
+            return;
+        };
+        cache.grid.clear();
+        for (i, x) in self.positions.iter().enumerate() {
+            let p = *x - cache.origin;
+            cache.grid.insert(p.x, p.y, p.z, i as u16);
+        }
+
+        let thickness_sq = thickness * thickness;
+        for i in 0..self.positions.len() {
+            let p = self.positions[i] - cache.origin;
+            let count = cache
+                .grid
+                .query_radius(p.x, p.y, p.z, thickness, &mut self.candidates);
+            for &j in &self.candidates[..count] {
+                let j = j as usize;
+                if j <= i {
+                    continue;
+                }
+                let (wi, wj) = (self.inverse_masses[i], self.inverse_masses[j]);
+                if wi + wj == 0.0 {
+                    continue;
+                }
+                let delta = self.positions[i] - self.positions[j];
+                let distance_sq = delta.length_squared();
+                if distance_sq >= thickness_sq || distance_sq < 1e-18 {
+                    continue;
+                }
+                // Neighbours in the rest shape may legitimately sit this close
+                if (self.rest_positions[i] - self.rest_positions[j]).length_squared() < thickness_sq
+                {
+                    continue;
+                }
+                let distance = distance_sq.sqrt();
+                let correction = delta * ((thickness - distance) / (distance * (wi + wj)));
+                self.positions[i] += wi * correction;
+                self.positions[j] -= wj * correction;
+            }
+        }
+    }
+
+    /// Keep particles above the ground with position-level Coulomb friction
+    fn solve_ground(&mut self, ground: f32, friction: f32) {
+        for ((x, p), &w) in self
+            .positions
+            .iter_mut()
+            .zip(&self.previous)
+            .zip(&self.inverse_masses)
+        {
+            let depth = ground - x.y;
+            if w == 0.0 || depth <= 0.0 {
+                continue;
+            }
+            x.y = ground;
+            let mut slide = *x - *p;
+            slide.y = 0.0;
+            let length = slide.length();
+            if length <= friction * depth {
+                *x -= slide;
+            } else if length > 0.0 {
+                *x -= slide * (friction * depth / length);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DT: f32 = 1.0 / 60.0;
+
+    #[test]
+    fn test_spring_oscillator_period() {
+        // m = 0.5 kg on k = 200 N/m: T = 2π√(m/k)
+        let mut body = SoftBody::new(vec![Vec3::ZERO, Vec3::new(0.0, -1.0, 0.0)], &[0.0, 0.5])
+            .unwrap()
+            .with_settings(SoftBodySettings::default().with_substeps(20, 1));
+        body.add_spring(0, 1, 200.0, 0.0).unwrap();
+        body.settings.gravity = Vec3::ZERO;
+        body.positions[1].y = -1.1;
+        let mut crossings = Vec::new();
+        let mut last = body.positions[1].y + 1.0;
+        for _ in 0..600 {
+            body.step(DT / 4.0).unwrap();
+            let y = body.positions[1].y + 1.0;
+            if last < 0.0 && y >= 0.0 {
+                crossings.push(body.time);
+            }
+            last = y;
+        }
+        let period = (crossings[crossings.len() - 1] - crossings[0]) / (crossings.len() - 1) as f32;
+        let expected = 2.0 * std::f32::consts::PI * (0.5f32 / 200.0).sqrt();
+        assert!(
+            (period - expected).abs() / expected < 0.02,
+            "period {}",
+            period
+        );
+        assert!(body.max_strain() < 0.11);
+    }
+
+    #[test]
+    fn test_hanging_cloth_and_bending() {
+        // 1 m cloth pinned along one edge: stretch stays small, energy decays
+        let mesh = TriangleMesh::grid(1.0, 1.0, 12, 12);
+        let mut cloth = SoftBody::cloth(&mesh, 0.2, 0.0, 1.0)
+            .unwrap()
+            .with_settings(SoftBodySettings::default().with_damping(2.0));
+        for k in 0..=12 {
+            cloth.pin(k).unwrap();
+        }
+        for _ in 0..300 {
+            cloth.step(DT).unwrap();
+        }
+        assert!(cloth.max_strain() < 0.02, "strain {}", cloth.max_strain());
+        assert!(
+            cloth.kinetic_energy() < 1e-3,
+            "energy {}",
+            cloth.kinetic_energy()
+        );
+        assert!(cloth.centre_of_mass().y < -0.4);
+
+        // A stiff cantilever plate barely sags; a floppy sheet hangs down
+        let sag = |bend_compliance: f32| {
+            let mesh = TriangleMesh::grid(0.5, 0.2, 10, 4);
+            let mut strip = SoftBody::cloth(&mesh, 0.5, 0.0, bend_compliance)
+                .unwrap()
+                .with_settings(
+                    SoftBodySettings::default()
+                        .with_damping(2.0)
+                        .with_substeps(50, 1),
+                );
+            for k in 0..=4 {
+                strip.pin(k).unwrap();
+                strip.pin(5 + k).unwrap();
+            }
+            for _ in 0..240 {
+                strip.step(DT).unwrap();
+            }
+            -strip.positions.iter().fold(0.0, |m: f32, x| m.min(x.y))
+        };
+        let (stiff, floppy) = (sag(1e-6), sag(100.0));
+        assert!(
+            stiff < 0.02 && floppy > 0.3,
+            "stiff {} floppy {}",
+            stiff,
+            floppy
+        );
+    }
+
+    #[test]
+    fn test_balloon_keeps_volume_on_ground() {
+        let mut mesh = TriangleMesh::icosphere(0.3, 2);
+        mesh.translate(Vec3::new(0.0, 0.5, 0.0));
+        let rest = mesh.volume();
+        let mut balloon = SoftBody::balloon(&mesh, 1.0, 1.0, 0.0, 1e-2)
+            .unwrap()
+            .with_settings(
+                SoftBodySettings::default()
+                    .with_damping(1.0)
+                    .with_ground(0.0, 0.8),
+            );
+        for _ in 0..240 {
+            balloon.step(DT).unwrap();
+        }
+        balloon.update_mesh(&mut mesh).unwrap();
+        assert!(
+            (mesh.volume() - rest).abs() / rest < 0.02,
+            "volume {}",
+            mesh.volume()
+        );
+        let lowest = balloon.positions.iter().fold(f32::MAX, |m, x| m.min(x.y));
+        assert!(lowest > -1e-4);
+        assert!((balloon.centre_of_mass().y - 0.3).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_self_collision_separates_layers() {
+        // Two sheets stacked 0.1 m apart fall onto the ground
+        let mut lower = TriangleMesh::grid(0.5, 0.5, 8, 8);
+        lower.translate(Vec3::new(0.0, 0.05, 0.0));
+        let mut upper = lower.clone();
+        upper.translate(Vec3::new(0.002, 0.1, 0.002));
+        let offset = lower.vertex_count();
+        let mesh = TriangleMesh::new(
+            [lower.vertices, upper.vertices].concat(),
+            lower
+                .triangles
+                .iter()
+                .copied()
+                .chain(upper.triangles.iter().map(|t| t.map(|i| i + offset)))
+                .collect(),
+        )
+        .unwrap();
+        let gap = |thickness: Option<f32>| {
+            let mut settings = SoftBodySettings::default()
+                .with_damping(1.0)
+                .with_ground(0.0, 0.5);
+            settings.thickness = thickness;
+            let mut sheets = SoftBody::cloth(&mesh, 0.1, 0.0, 1.0)
+                .unwrap()
+                .with_settings(settings);
+            for _ in 0..120 {
+                sheets.step(DT).unwrap();
+            }
+            let height = |range: std::ops::Range<usize>| {
+                let n = range.len() as f32;
+                range.map(|i| sheets.positions[i].y).sum::<f32>() / n
+            };
+            height(offset..2 * offset) - height(0..offset)
+        };
+        assert!(gap(None).abs() < 1e-3);
+        // Upper particles nest in the lower grid's holes: √(0.05² - 0.044²)
+        let separated = gap(Some(0.05));
+        assert!(separated > 0.02, "gap {}", separated);
+    }
+
+    #[test]
+    fn test_rope_and_validation() {
+        let mut rope = SoftBody::rope(Vec3::ZERO, Vec3::new(1.0, 0.0, 0.0), 10, 0.5, 0.0).unwrap();
+        rope.pin(0).unwrap();
+        let mut lowest: f32 = 0.0;
+        for _ in 0..120 {
+            rope.step(DT).unwrap();
+            lowest = lowest.min(rope.positions[10].y);
+        }
+        assert!(lowest < -0.9, "lowest {}", lowest);
+        assert!(rope.max_strain() < 0.01);
+        assert!((rope.total_mass() - 0.5 * 10.0 / 11.0).abs() < 1e-6);
+
+        assert_eq!(rope.pin(11), Err(SoftBodyError::UnknownParticle(11)));
+        assert!(rope.step(0.0).is_err());
+        assert!(rope.add_distance(0, 1, -1.0).is_err());
+        let open = TriangleMesh::grid(1.0, 1.0, 2, 2);
+        assert!(SoftBody::balloon(&open, 1.0, 1.0, 0.0, 0.0).is_err());
+    }
+}
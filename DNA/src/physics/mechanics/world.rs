@@ -0,0 +1,399 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: world.rs | DNA/src/physics/mechanics/world.rs
+//! PURPOSE: Rigid body world: collision pipeline and constraint solve per step
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! PURPOSE: Steps a set of rigid bodies with gravity, contacts and joints
+//!
+//! LAYER: DNA → PHYSICS → MECHANICS
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ ALGORITHM (one step, semi-implicit Euler)                                   │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ 1. v += Δt·(g + F/m),  ω implicit gyroscopic update                         │
+//! │ 2. Broad phase: sweep and prune on AABBs grown by the contact margin        │
+//! │ 3. Narrow phase: collide() per pair, manifolds matched to last step         │
+//! │ 4. Constraints: warm start, then N sequential-impulse sweeps                │
+//! │ 5. x += Δt·v,  q += ½Δt·ω⊗q (normalized)                                    │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ DATA DEFINED                                                                │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ RigidBodyWorld  Bodies, joints, manifolds (sorted by pair), settings        │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use super::collision::{broad_phase, collide, Aabb, ContactManifold};
+use super::constraint::{ConstraintSolver, Joint, SolverSettings};
+use super::rigid_body::{RigidBody, RigidBodyError};
+use glam::DVec3;
+
+/// Bodies, joints and contacts advanced together
+#[derive(Clone, Debug)]
+pub struct RigidBodyWorld {
+    pub gravity: DVec3,
+    pub settings: SolverSettings,
+    bodies: Vec<RigidBody>,
+    joints: Vec<Joint>,
+    manifolds: Vec<ContactManifold>,
+    time: f64,
+}
+
+impl Default for RigidBodyWorld {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RigidBodyWorld {
+    /// Empty world with Earth gravity along -y
+    pub fn new() -> Self {
+        Self {
+            gravity: DVec3::new(0.0, -9.81, 0.0),
+            settings: SolverSettings::default(),
+            bodies: Vec::new(),
+            joints: Vec::new(),
+            manifolds: Vec::new(),
+            time: 0.0,
+        }
+    }
+
+    pub fn with_gravity(mut self, gravity: DVec3) -> Self {
+        self.gravity = gravity;
+        self
+    }
+
+    pub fn with_settings(mut self, settings: SolverSettings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Add a body, returning its index
+    pub fn add_body(&mut self, body: RigidBody) -> usize {
+        self.bodies.push(body);
+        self.bodies.len() - 1
+    }
+
+    pub fn bodies(&self) -> &[RigidBody] {
+        &self.bodies
+    }
+
+    pub fn body(&self, index: usize) -> Option<&RigidBody> {
+        self.bodies.get(index)
+    }
+
+    pub fn body_mut(&mut self, index: usize) -> Option<&mut RigidBody> {
+        self.bodies.get_mut(index)
+    }
+
+    pub fn joints(&self) -> &[Joint] {
+        &self.joints
+    }
+
+    /// Contact manifolds found in the last step
+    pub fn manifolds(&self) -> &[ContactManifold] {
+        &self.manifolds
+    }
+
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    fn pair(
+        &self,
+        a: usize,
+        b: usize,
+    ) -> Result<(usize, &RigidBody, usize, &RigidBody), RigidBodyError> {
+        let body_a = self.bodies.get(a).ok_or(RigidBodyError::UnknownBody(a))?;
+        let body_b = self.bodies.get(b).ok_or(RigidBodyError::UnknownBody(b))?;
+        if a == b {
+            return Err(RigidBodyError::InvalidParameter(format!(
+                "joint connects body {} to itself",
+                a
+            )));
+        }
+        Ok((a, body_a, b, body_b))
+    }
+
+    /// Add a prebuilt joint after checking its body indices
+    pub fn add_joint(&mut self, joint: Joint) -> Result<usize, RigidBodyError> {
+        self.pair(joint.body_a, joint.body_b)?;
+        self.joints.push(joint);
+        Ok(self.joints.len() - 1)
+    }
+
+    /// Ball-and-socket joint at a world point
+    pub fn add_ball_joint(
+        &mut self,
+        a: usize,
+        b: usize,
+        anchor: DVec3,
+    ) -> Result<usize, RigidBodyError> {
+        let (ia, ba, ib, bb) = self.pair(a, b)?;
+        let joint = Joint::ball((ia, ba), (ib, bb), anchor);
+        self.add_joint(joint)
+    }
+
+    /// Hinge through a world point about a world axis
+    pub fn add_hinge_joint(
+        &mut self,
+        a: usize,
+        b: usize,
+        anchor: DVec3,
+        axis: DVec3,
+    ) -> Result<usize, RigidBodyError> {
+        if axis.length_squared() < 1e-24 {
+            return Err(RigidBodyError::InvalidParameter(
+                "hinge axis is zero".into(),
+            ));
+        }
+        let (ia, ba, ib, bb) = self.pair(a, b)?;
+        let joint = Joint::hinge((ia, ba), (ib, bb), anchor, axis);
+        self.add_joint(joint)
+    }
+
+    /// Rod between two world points
+    pub fn add_distance_joint(
+        &mut self,
+        a: usize,
+        b: usize,
+        anchor_a: DVec3,
+        anchor_b: DVec3,
+    ) -> Result<usize, RigidBodyError> {
+        let (ia, ba, ib, bb) = self.pair(a, b)?;
+        let joint = Joint::distance((ia, ba), (ib, bb), anchor_a, anchor_b);
+        self.add_joint(joint)
+    }
+
+    /// Total kinetic energy
+    pub fn kinetic_energy(&self) -> f64 {
+        self.bodies.iter().map(RigidBody::kinetic_energy).sum()
+    }
+
+    fn ignores(&self, a: usize, b: usize) -> bool {
+        (self.bodies[a].is_static() && self.bodies[b].is_static())
+            || self.joints.iter().any(|j| {
+                !j.collide_connected
+                    && ((j.body_a == a && j.body_b == b) || (j.body_a == b && j.body_b == a))
+            })
+    }
+
+    /// Find contacts, keeping impulses of manifolds that persist
+    fn detect_contacts(&mut self) {
+        let margin = self.settings.contact_margin;
+        let boxes: Vec<Aabb> = self
+            .bodies
+            .iter()
+            .map(|b| Aabb::of(b).expanded(margin))
+            .collect();
+        let mut manifolds = Vec::new();
+        for (a, b) in broad_phase(&boxes) {
+            if self.ignores(a, b) {
+                continue;
+            }
+            let Some(contacts) = collide(&self.bodies[a], &self.bodies[b], margin) else {
+                continue;
+            };
+            let mut manifold = match self
+                .manifolds
+                .binary_search_by_key(&(a, b), |m| (m.body_a, m.body_b))
+            {
+                Ok(k) => std::mem::replace(&mut self.manifolds[k], ContactManifold::new(a, b)),
+                Err(_) => ContactManifold::new(a, b),
+            };
+            manifold.update(&self.bodies[a], &self.bodies[b], contacts, margin);
+            if !manifold.points.is_empty() {
+                manifolds.push(manifold);
+            }
+        }
+        self.manifolds = manifolds;
+    }
+
+    /// Advance by `dt`
+    pub fn step(&mut self, dt: f64) -> Result<(), RigidBodyError> {
+        if !(dt > 0.0 && dt.is_finite()) {
+            return Err(RigidBodyError::InvalidParameter(format!(
+                "time step {}",
+                dt
+            )));
+        }
+        let gravity = self.gravity;
+        for body in &mut self.bodies {
+            body.integrate_velocity(dt, gravity);
+        }
+
+        self.detect_contacts();
+
+        let mut solver = ConstraintSolver::new(
+            &self.bodies,
+            &self.manifolds,
+            &self.joints,
+            &self.settings,
+            dt,
+        );
+        solver.warm_start(
+            &mut self.manifolds,
+            &mut self.joints,
+            self.settings.warm_starting,
+        );
+        for _ in 0..self.settings.iterations {
+            solver.iterate(&mut self.manifolds, &mut self.joints);
+        }
+        solver.store(&mut self.bodies);
+
+        for body in &mut self.bodies {
+            body.integrate_position(dt);
+        }
+        self.time += dt;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physics::mechanics::rigid_body::Shape;
+
+    const DT: f64 = 1.0 / 60.0;
+
+    fn ground(world: &mut RigidBodyWorld, friction: f64) -> usize {
+        let floor = RigidBody::fixed(Shape::cuboid(DVec3::new(5.0, 0.5, 5.0)).unwrap())
+            .with_position(DVec3::new(0.0, -0.5, 0.0))
+            .with_material(friction, 0.0);
+        world.add_body(floor)
+    }
+
+    fn crate_at(y: f64, friction: f64) -> RigidBody {
+        RigidBody::new(Shape::cuboid(DVec3::splat(0.25)).unwrap(), 500.0)
+            .unwrap()
+            .with_position(DVec3::new(0.0, y, 0.0))
+            .with_material(friction, 0.0)
+    }
+
+    #[test]
+    fn test_box_stack_rests() {
+        let mut world = RigidBodyWorld::new();
+        ground(&mut world, 0.6);
+        let boxes: Vec<usize> = (0..3)
+            .map(|k| world.add_body(crate_at(0.26 + 0.51 * k as f64, 0.6)))
+            .collect();
+        for _ in 0..120 {
+            world.step(DT).unwrap();
+        }
+        for (k, &i) in boxes.iter().enumerate() {
+            let body = world.body(i).unwrap();
+            let rest = 0.25 + 0.5 * k as f64;
+            assert!(
+                (body.position.y - rest).abs() < 0.02,
+                "box {} at {}",
+                k,
+                body.position.y
+            );
+            assert!(body.position.x.abs() < 0.01 && body.position.z.abs() < 0.01);
+            assert!(body.linear_velocity.length() < 0.05);
+        }
+        assert!(world.manifolds().iter().all(|m| m.points.len() == 4));
+    }
+
+    #[test]
+    fn test_friction_stopping_distance() {
+        // Sliding box stops after v²/(2μg)
+        let mu = 0.4;
+        let mut world = RigidBodyWorld::new();
+        ground(&mut world, mu);
+        let body = crate_at(0.25, mu).with_velocity(DVec3::new(2.0, 0.0, 0.0), DVec3::ZERO);
+        let i = world.add_body(body);
+        for _ in 0..90 {
+            world.step(DT).unwrap();
+        }
+        let body = world.body(i).unwrap();
+        let expected = 2.0 * 2.0 / (2.0 * mu * 9.81);
+        assert!(body.linear_velocity.length() < 1e-3);
+        assert!(
+            (body.position.x / expected - 1.0).abs() < 0.05,
+            "slid {}",
+            body.position.x
+        );
+    }
+
+    #[test]
+    fn test_restitution_bounce() {
+        // e = 0.8 returns e² of the drop height
+        let mut world = RigidBodyWorld::new();
+        let floor = ground(&mut world, 0.5);
+        world.body_mut(floor).unwrap().restitution = 0.8;
+        let ball = RigidBody::new(Shape::sphere(0.1).unwrap(), 1000.0)
+            .unwrap()
+            .with_position(DVec3::new(0.0, 1.1, 0.0))
+            .with_material(0.5, 0.8);
+        let i = world.add_body(ball);
+        let dt = 1.0 / 240.0;
+        let mut bounced = false;
+        let mut apex: f64 = 0.0;
+        for _ in 0..240 {
+            world.step(dt).unwrap();
+            let body = world.body(i).unwrap();
+            bounced |= body.linear_velocity.y > 0.0;
+            if bounced {
+                apex = apex.max(body.position.y - 0.1);
+            }
+        }
+        assert!((apex / 1.0 - 0.64).abs() < 0.05, "apex {}", apex);
+    }
+
+    #[test]
+    fn test_convex_mesh_and_capsule_rest() {
+        // GJK/EPA path: a tetrahedron and a lying capsule settle on the floor
+        let mut world = RigidBodyWorld::new();
+        ground(&mut world, 0.8);
+        let vertices = vec![
+            DVec3::new(-0.3, 0.0, -0.3),
+            DVec3::new(0.3, 0.0, -0.3),
+            DVec3::new(0.0, 0.0, 0.3),
+            DVec3::new(0.0, 0.4, 0.0),
+        ];
+        let triangles = vec![[0, 1, 2], [0, 3, 1], [1, 3, 2], [2, 3, 0]];
+        let (shape, centroid) = Shape::convex_mesh(vertices, triangles).unwrap();
+        let tetra = RigidBody::new(shape, 800.0)
+            .unwrap()
+            .with_position(centroid + DVec3::new(-1.0, 0.05, 0.0))
+            .with_material(0.8, 0.0);
+        let capsule = RigidBody::new(Shape::capsule(0.1, 0.3).unwrap(), 800.0)
+            .unwrap()
+            .with_position(DVec3::new(1.0, 0.15, 0.0))
+            .with_orientation(glam::DQuat::from_rotation_z(std::f64::consts::FRAC_PI_2))
+            .with_material(0.8, 0.0);
+        let t = world.add_body(tetra);
+        let c = world.add_body(capsule);
+        for _ in 0..120 {
+            world.step(DT).unwrap();
+        }
+        let tetra = world.body(t).unwrap();
+        assert!(
+            (tetra.position.y - centroid.y).abs() < 0.02,
+            "tetra at {}",
+            tetra.position.y
+        );
+        assert!(tetra.linear_velocity.length() < 0.05);
+        let capsule = world.body(c).unwrap();
+        assert!((capsule.position.y - 0.1).abs() < 0.02);
+        assert!(world.kinetic_energy() < 1e-2);
+    }
+
+    #[test]
+    fn test_joint_validation() {
+        let mut world = RigidBodyWorld::new();
+        let a = world.add_body(crate_at(1.0, 0.5));
+        assert_eq!(
+            world.add_ball_joint(a, 7, DVec3::ZERO),
+            Err(RigidBodyError::UnknownBody(7))
+        );
+        assert!(world.add_hinge_joint(a, a, DVec3::ZERO, DVec3::Z).is_err());
+        assert!(world.step(0.0).is_err());
+    }
+}
@@ -1,7 +1,7 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: ekf.rs | DNA/src/physics/solvers/filters/ekf.rs
 //! PURPOSE: Extended Kalman Filter for 2D state estimation
-//! MODIFIED: 2025-12-09
+//! MODIFIED: 2026-10-18
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
 
@@ -41,7 +41,9 @@
 //! └─────────────────────────────────────────────────────────────────────────────┘
 //!
 //! DEPENDS ON:
-//!   • math/mat.rs → Mat2 for covariance matrices
+//!   • math/dmatrix.rs → DMatrix for P, Q, R (f64 internally)
+//!   • physics/solvers/linear/dense.rs → Cholesky solve for the gain
+//!   • math/mat.rs → Mat2 covariance view for callers
 //!   • glam::Vec2 → State representation
 //!
 //! USED BY:
@@ -55,9 +57,15 @@
 // CODE BELOW - Optimized for ML development
 // ─────────────────────────────────────────────────────────────────────────────────
 
+use crate::math::dmatrix::DMatrix;
 use crate::math::mat::Mat2;
 use glam::Vec2;
 
+/// Isotropic 2x2 noise matrix σ·I
+fn isotropic(value: f32) -> DMatrix<f64> {
+    DMatrix::from_diagonal(&[value as f64; 2])
+}
+
 /// Extended Kalman Filter for 2D position estimation
 ///
 /// State vector: [x, y] position
@@ -84,11 +92,11 @@ pub struct EKF {
     /// Estimated state [x, y]
     state: Vec2,
     /// Covariance matrix P (uncertainty)
-    covariance: Mat2,
+    covariance: DMatrix<f64>,
     /// Process noise Q (model uncertainty)
-    process_noise: Mat2,
+    process_noise: DMatrix<f64>,
     /// Measurement noise R (sensor uncertainty)
-    measurement_noise: Mat2,
+    measurement_noise: DMatrix<f64>,
 }
 
 impl EKF {
@@ -96,9 +104,9 @@ impl EKF {
     pub fn new(initial_pos: Vec2) -> Self {
         Self {
             state: initial_pos,
-            covariance: Mat2::IDENTITY,
-            process_noise: Mat2::diagonal(0.1, 0.1),
-            measurement_noise: Mat2::diagonal(1.0, 1.0),
+            covariance: DMatrix::identity(2),
+            process_noise: isotropic(0.1),
+            measurement_noise: isotropic(1.0),
         }
     }
 
@@ -106,9 +114,9 @@ impl EKF {
     pub fn with_noise(initial_pos: Vec2, process_noise: f32, measurement_noise: f32) -> Self {
         Self {
             state: initial_pos,
-            covariance: Mat2::IDENTITY,
-            process_noise: Mat2::diagonal(process_noise, process_noise),
-            measurement_noise: Mat2::diagonal(measurement_noise, measurement_noise),
+            covariance: DMatrix::identity(2),
+            process_noise: isotropic(process_noise),
+            measurement_noise: isotropic(measurement_noise),
         }
     }
 
@@ -121,23 +129,29 @@ impl EKF {
     /// Get current covariance (uncertainty)
     #[inline]
     pub fn covariance(&self) -> Mat2 {
-        self.covariance
+        let p = &self.covariance;
+        Mat2::new(
+            p[(0, 0)] as f32,
+            p[(0, 1)] as f32,
+            p[(1, 0)] as f32,
+            p[(1, 1)] as f32,
+        )
     }
 
     /// Get uncertainty magnitude (trace of covariance)
     #[inline]
     pub fn uncertainty(&self) -> f32 {
-        self.covariance.trace()
+        self.covariance.trace() as f32
     }
 
     /// Set process noise (higher = less trust in motion model)
     pub fn set_process_noise(&mut self, noise: f32) {
-        self.process_noise = Mat2::diagonal(noise, noise);
+        self.process_noise = isotropic(noise);
     }
 
     /// Set measurement noise (higher = less trust in sensors)
     pub fn set_measurement_noise(&mut self, noise: f32) {
-        self.measurement_noise = Mat2::diagonal(noise, noise);
+        self.measurement_noise = isotropic(noise);
     }
 
     /// Predict step: propagate state forward using velocity
@@ -151,8 +165,8 @@ impl EKF {
         // Covariance prediction: P = F * P * F' + Q
         // For linear model, F = I, so P = P + Q
         // But we scale Q by dt^2 for proper integration
-        let q_scaled = self.process_noise.scale(dt * dt);
-        self.covariance = self.covariance.add(q_scaled);
+        let q_scaled = self.process_noise.scale((dt * dt) as f64);
+        self.covariance = &self.covariance + &q_scaled;
     }
 
     /// Update step: correct state using measurement
@@ -163,20 +177,25 @@ impl EKF {
         let innovation = measurement - self.state;
 
         // Innovation covariance: S = H * P * H' + R (H = I)
-        let s = self.covariance.add(self.measurement_noise);
+        let s = &self.covariance + &self.measurement_noise;
 
         // Kalman gain: K = P * H' * S^-1 (H = I)
-        if let Some(s_inv) = s.inverse() {
-            let k = self.covariance.mul(s_inv);
+        // S and P are symmetric, so K' = S^-1 * P (Cholesky solve, no inverse)
+        if let Ok(chol) = s.cholesky() {
+            let k = chol
+                .solve_matrix(&self.covariance)
+                .expect("S and P are both 2x2")
+                .transpose();
 
             // State update: x = x + K * y
-            self.state += k.mul_vec(innovation);
+            let dx = k.mul_vec(&[innovation.x as f64, innovation.y as f64]);
+            self.state += Vec2::new(dx[0] as f32, dx[1] as f32);
 
             // Covariance update: P = (I - K * H) * P (H = I)
-            let i_minus_k = Mat2::IDENTITY.sub(k);
-            self.covariance = i_minus_k.mul(self.covariance);
+            let i_minus_k = &DMatrix::identity(2) - &k;
+            self.covariance = &i_minus_k * &self.covariance;
         }
-        // If S is singular, skip update (shouldn't happen with proper noise)
+        // If S is not positive definite, skip update (shouldn't happen with proper noise)
     }
 
     /// Combined predict and update in one step
@@ -188,7 +207,7 @@ impl EKF {
     /// Reset filter to new position with high uncertainty
     pub fn reset(&mut self, position: Vec2) {
         self.state = position;
-        self.covariance = Mat2::diagonal(10.0, 10.0);
+        self.covariance = isotropic(10.0);
     }
 }
 
@@ -1,33 +1,707 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: dense.rs | DNA/src/physics/solvers/linear/dense.rs
-//! PURPOSE: Dense matrix linear algebra (LU, QR decomposition)
-//! MODIFIED: 2025-12-09
+//! PURPOSE: Dense matrix linear algebra (LU, Cholesky, QR, least squares)
+//! MODIFIED: 2026-10-18
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
 
 //!
-//! PURPOSE: Dense matrix linear algebra (LU, QR decomposition)
+//! PURPOSE: Dense matrix linear algebra (LU, Cholesky, QR, least squares)
 //!
 //! LAYER: DNA → PHYSICS → SOLVERS → LINEAR
 //!
 //! ┌─────────────────────────────────────────────────────────────────────────────┐
 //! │ ALGORITHM                                                                   │
 //! ├─────────────────────────────────────────────────────────────────────────────┤
-//! │ LU Decomposition: A = L·U                                                   │
-//! │   L = lower triangular                                                      │
-//! │   U = upper triangular                                                      │
-//! │   Solve Ax = b via L(Ux) = b                                                │
-//! │   Complexity: O(n³)                                                         │
+//! │ LU Decomposition: P·A = L·U  (any Scalar, incl. Complex for AC MNA)         │
+//! │   L = unit lower triangular, U = upper triangular                           │
+//! │   Partial pivoting (largest |a| in column) for numerical stability          │
+//! │   Solve Ax = b via L(Ux) = Pb; Aᵀx = b via Uᵀ(Lᵀ(Px)) = b                   │
+//! │   Complexity: O(n³) factor, O(n²) per solve                                 │
 //! │                                                                             │
-//! │ Partial pivoting for numerical stability                                    │
+//! │ Cholesky: A = L·Lᵀ for symmetric positive definite A (half the work of LU)  │
+//! │                                                                             │
+//! │ Householder QR: A = Q·R, Hₖ = I - βvvᵀ; least squares min‖Ax - b‖₂ via      │
+//! │   R x = (Qᵀb)[..n]                                                          │
+//! │                                                                             │
+//! │ Condition estimate: ‖A‖₁·est(‖A⁻¹‖₁), Hager/Higham (LAPACK xLACON), O(n²)   │
+//! │   after the LU; exact κ₂ = σmax/σmin from the SVD in eigensolver.rs         │
 //! └─────────────────────────────────────────────────────────────────────────────┘
 //!
-//! TODO: Migrate from DNA/src/spice/matrix.rs
-//!
-//! REFERENCE: https://en.wikipedia.org/wiki/LU_decomposition
+//! REFERENCE: Golub & Van Loan, "Matrix Computations" (4th ed.), ch. 3-5
 //!
 //! ═══════════════════════════════════════════════════════════════════════════════
 
-// TODO: Implement LU decomposition with partial pivoting
-// TODO: Forward/backward substitution
-// TODO: QR decomposition (Gram-Schmidt or Householder)
+use crate::math::dmatrix::{DMatrix, Scalar};
+
+/// Linear algebra error
+#[derive(Debug, Clone, PartialEq)]
+pub enum LinalgError {
+    /// Operand shapes do not fit together
+    DimensionMismatch {
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+    /// Operation needs a square matrix
+    NotSquare { rows: usize, cols: usize },
+    /// Zero (or below-tolerance) pivot in the given column
+    Singular { column: usize },
+    /// Cholesky found a non-positive pivot in the given column
+    NotPositiveDefinite { column: usize },
+    /// Symmetric-only routine given a non-symmetric matrix
+    NotSymmetric,
+    /// Least squares with numerically dependent columns
+    RankDeficient { rank: usize },
+    /// Iterative routine did not converge
+    NoConvergence { iterations: usize },
+}
+
+impl std::fmt::Display for LinalgError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LinalgError::DimensionMismatch { expected, found } => write!(
+                f,
+                "Dimension mismatch: expected {}x{}, found {}x{}",
+                expected.0, expected.1, found.0, found.1
+            ),
+            LinalgError::NotSquare { rows, cols } => {
+                write!(f, "Matrix is {}x{}, expected square", rows, cols)
+            }
+            LinalgError::Singular { column } => {
+                write!(f, "Matrix is singular at column {}", column)
+            }
+            LinalgError::NotPositiveDefinite { column } => {
+                write!(f, "Matrix is not positive definite (column {})", column)
+            }
+            LinalgError::NotSymmetric => write!(f, "Matrix is not symmetric"),
+            LinalgError::RankDeficient { rank } => {
+                write!(f, "Matrix is rank deficient (rank {})", rank)
+            }
+            LinalgError::NoConvergence { iterations } => {
+                write!(f, "No convergence after {} iterations", iterations)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LinalgError {}
+
+fn require_square<T: Scalar>(a: &DMatrix<T>) -> Result<usize, LinalgError> {
+    if a.is_square() {
+        Ok(a.nrows())
+    } else {
+        Err(LinalgError::NotSquare {
+            rows: a.nrows(),
+            cols: a.ncols(),
+        })
+    }
+}
+
+fn require_len(expected: usize, found: usize) -> Result<(), LinalgError> {
+    if expected == found {
+        Ok(())
+    } else {
+        Err(LinalgError::DimensionMismatch {
+            expected: (expected, 1),
+            found: (found, 1),
+        })
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// LU
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// LU factorization with partial pivoting, P·A = L·U
+#[derive(Clone, Debug)]
+pub struct Lu<T = f64> {
+    /// L below the diagonal (unit diagonal implied), U on and above
+    lu: DMatrix<T>,
+    /// Row swapped with row k at step k
+    pivots: Vec<usize>,
+    /// +1 / -1: parity of the row permutation
+    sign: f64,
+}
+
+impl<T: Scalar> DMatrix<T> {
+    /// LU with a pivot tolerance relative to the largest element
+    pub fn lu(&self) -> Result<Lu<T>, LinalgError> {
+        let n = require_square(self)?;
+        let tol = n as f64 * f64::EPSILON * self.max_modulus();
+        self.lu_with_tolerance(tol)
+    }
+
+    /// LU failing on any pivot with |pivot| ≤ `pivot_tol` (absolute)
+    pub fn lu_with_tolerance(&self, pivot_tol: f64) -> Result<Lu<T>, LinalgError> {
+        let n = require_square(self)?;
+        let mut lu = self.clone();
+        let mut pivots = Vec::with_capacity(n);
+        let mut sign = 1.0;
+
+        for k in 0..n {
+            // Find pivot
+            let mut max_val = lu[(k, k)].modulus();
+            let mut max_row = k;
+            for i in k + 1..n {
+                let val = lu[(i, k)].modulus();
+                if val > max_val {
+                    max_val = val;
+                    max_row = i;
+                }
+            }
+            if max_val <= pivot_tol || !max_val.is_finite() {
+                return Err(LinalgError::Singular { column: k });
+            }
+            if max_row != k {
+                lu.swap_rows(k, max_row);
+                sign = -sign;
+            }
+            pivots.push(max_row);
+
+            // Elimination
+            let pivot = lu[(k, k)];
+            for i in k + 1..n {
+                let factor = lu[(i, k)] / pivot;
+                lu[(i, k)] = factor;
+                if factor == T::zero() {
+                    continue;
+                }
+                for j in k + 1..n {
+                    lu[(i, j)] = lu[(i, j)] - factor * lu[(k, j)];
+                }
+            }
+        }
+        Ok(Lu { lu, pivots, sign })
+    }
+
+    /// Solve A·x = b by LU
+    pub fn solve(&self, b: &[T]) -> Result<Vec<T>, LinalgError> {
+        self.lu()?.solve(b)
+    }
+
+    /// Determinant via LU (zero for singular matrices)
+    pub fn determinant(&self) -> Result<T, LinalgError> {
+        require_square(self)?;
+        match self.lu_with_tolerance(0.0) {
+            Ok(lu) => Ok(lu.determinant()),
+            Err(LinalgError::Singular { .. }) => Ok(T::zero()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Inverse via LU
+    pub fn inverse(&self) -> Result<DMatrix<T>, LinalgError> {
+        Ok(self.lu()?.inverse())
+    }
+}
+
+impl<T: Scalar> Lu<T> {
+    pub fn dimension(&self) -> usize {
+        self.lu.nrows()
+    }
+
+    /// Solve A·x = b
+    pub fn solve(&self, b: &[T]) -> Result<Vec<T>, LinalgError> {
+        require_len(self.dimension(), b.len())?;
+        let mut x = b.to_vec();
+        self.solve_in_place(&mut x);
+        Ok(x)
+    }
+
+    /// Solve A·x = b, overwriting `b` with x; panics on length mismatch
+    pub fn solve_in_place(&self, b: &mut [T]) {
+        let n = self.dimension();
+        assert_eq!(b.len(), n, "Lu::solve_in_place dimension");
+        for (k, &p) in self.pivots.iter().enumerate() {
+            b.swap(k, p);
+        }
+        // Forward substitution (Ly = Pb)
+        for i in 0..n {
+            let mut sum = b[i];
+            #[allow(clippy::needless_range_loop)]
+            for j in 0..i {
+                sum = sum - self.lu[(i, j)] * b[j];
+            }
+            b[i] = sum;
+        }
+        // Back substitution (Ux = y)
+        for i in (0..n).rev() {
+            let mut sum = b[i];
+            #[allow(clippy::needless_range_loop)]
+            for j in i + 1..n {
+                sum = sum - self.lu[(i, j)] * b[j];
+            }
+            b[i] = sum / self.lu[(i, i)];
+        }
+    }
+
+    /// Solve Aᵀ·x = b (plain transpose, no conjugation)
+    pub fn solve_transpose(&self, b: &[T]) -> Result<Vec<T>, LinalgError> {
+        let n = self.dimension();
+        require_len(n, b.len())?;
+        let mut x = b.to_vec();
+        // Uᵀw = b
+        for i in 0..n {
+            let mut sum = x[i];
+            #[allow(clippy::needless_range_loop)]
+            for j in 0..i {
+                sum = sum - self.lu[(j, i)] * x[j];
+            }
+            x[i] = sum / self.lu[(i, i)];
+        }
+        // Lᵀv = w
+        for i in (0..n).rev() {
+            let mut sum = x[i];
+            #[allow(clippy::needless_range_loop)]
+            for j in i + 1..n {
+                sum = sum - self.lu[(j, i)] * x[j];
+            }
+            x[i] = sum;
+        }
+        // x = Pᵀv
+        for (k, &p) in self.pivots.iter().enumerate().rev() {
+            x.swap(k, p);
+        }
+        Ok(x)
+    }
+
+    /// Solve A·X = B column by column
+    pub fn solve_matrix(&self, b: &DMatrix<T>) -> Result<DMatrix<T>, LinalgError> {
+        let n = self.dimension();
+        if b.nrows() != n {
+            return Err(LinalgError::DimensionMismatch {
+                expected: (n, b.ncols()),
+                found: b.shape(),
+            });
+        }
+        let mut x = DMatrix::zeros(n, b.ncols());
+        for j in 0..b.ncols() {
+            let mut col = b.column(j);
+            self.solve_in_place(&mut col);
+            for (i, v) in col.into_iter().enumerate() {
+                x[(i, j)] = v;
+            }
+        }
+        Ok(x)
+    }
+
+    pub fn determinant(&self) -> T {
+        self.lu
+            .diagonal()
+            .into_iter()
+            .fold(T::from_f64(self.sign), |acc, d| acc * d)
+    }
+
+    pub fn inverse(&self) -> DMatrix<T> {
+        let n = self.dimension();
+        self.solve_matrix(&DMatrix::identity(n))
+            .expect("identity has matching dimension")
+    }
+
+    /// Unit lower triangular factor L
+    pub fn l(&self) -> DMatrix<T> {
+        let n = self.dimension();
+        DMatrix::from_fn(n, n, |i, j| match i.cmp(&j) {
+            std::cmp::Ordering::Greater => self.lu[(i, j)],
+            std::cmp::Ordering::Equal => T::one(),
+            std::cmp::Ordering::Less => T::zero(),
+        })
+    }
+
+    /// Upper triangular factor U
+    pub fn u(&self) -> DMatrix<T> {
+        let n = self.dimension();
+        DMatrix::from_fn(
+            n,
+            n,
+            |i, j| if i <= j { self.lu[(i, j)] } else { T::zero() },
+        )
+    }
+
+    /// Permutation P (P·A = L·U)
+    pub fn p(&self) -> DMatrix<T> {
+        let n = self.dimension();
+        let mut p = DMatrix::identity(n);
+        for (k, &r) in self.pivots.iter().enumerate() {
+            p.swap_rows(k, r);
+        }
+        p
+    }
+}
+
+impl Lu<f64> {
+    /// Estimate ‖A⁻¹‖₁ (Hager's method, as in LAPACK xLACON)
+    pub fn inverse_norm_1_estimate(&self) -> f64 {
+        let n = self.dimension();
+        if n == 0 {
+            return 0.0;
+        }
+        let mut x = vec![1.0 / n as f64; n];
+        let mut estimate = 0.0;
+        for iteration in 0..5 {
+            let y = self.solve(&x).expect("dimension matches");
+            estimate = y.iter().map(|v| v.abs()).sum();
+            let xi: Vec<f64> = y
+                .iter()
+                .map(|v| if *v >= 0.0 { 1.0 } else { -1.0 })
+                .collect();
+            let z = self.solve_transpose(&xi).expect("dimension matches");
+            let (j, zmax) = z
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (i, v.abs()))
+                .fold((0, 0.0), |best, c| if c.1 > best.1 { c } else { best });
+            let ztx: f64 = z.iter().zip(&x).map(|(a, b)| a * b).sum();
+            if iteration > 0 && zmax <= ztx {
+                break;
+            }
+            x = vec![0.0; n];
+            x[j] = 1.0;
+        }
+        estimate
+    }
+}
+
+impl DMatrix<f64> {
+    /// 1-norm condition number estimate κ₁ ≈ ‖A‖₁·‖A⁻¹‖₁ (∞ if singular)
+    pub fn condition_estimate(&self) -> Result<f64, LinalgError> {
+        match self.lu() {
+            Ok(lu) => Ok(self.norm_1() * lu.inverse_norm_1_estimate()),
+            Err(LinalgError::Singular { .. }) => Ok(f64::INFINITY),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Cholesky factorization A = L·Lᵀ (A symmetric positive definite)
+    ///
+    /// Only the lower triangle of A is read.
+    pub fn cholesky(&self) -> Result<Cholesky, LinalgError> {
+        let n = require_square(self)?;
+        let mut l = DMatrix::zeros(n, n);
+        for j in 0..n {
+            let mut d = self[(j, j)];
+            for k in 0..j {
+                d -= l[(j, k)] * l[(j, k)];
+            }
+            if d <= 0.0 || !d.is_finite() {
+                return Err(LinalgError::NotPositiveDefinite { column: j });
+            }
+            let ljj = d.sqrt();
+            l[(j, j)] = ljj;
+            for i in j + 1..n {
+                let mut s = self[(i, j)];
+                for k in 0..j {
+                    s -= l[(i, k)] * l[(j, k)];
+                }
+                l[(i, j)] = s / ljj;
+            }
+        }
+        Ok(Cholesky { l })
+    }
+
+    /// Householder QR factorization (any shape)
+    pub fn qr(&self) -> Qr {
+        let (m, n) = self.shape();
+        let mut r = self.clone();
+        let mut reflectors = Vec::with_capacity(n.min(m));
+        for k in 0..n.min(m.saturating_sub(1)) {
+            let norm: f64 = (k..m).map(|i| r[(i, k)].powi(2)).sum::<f64>().sqrt();
+            if norm == 0.0 {
+                reflectors.push((Vec::new(), 0.0));
+                continue;
+            }
+            let alpha = if r[(k, k)] > 0.0 { -norm } else { norm };
+            let mut v: Vec<f64> = (k..m).map(|i| r[(i, k)]).collect();
+            v[0] -= alpha;
+            let vtv: f64 = v.iter().map(|x| x * x).sum();
+            let beta = if vtv == 0.0 { 0.0 } else { 2.0 / vtv };
+            for j in k..n {
+                let s: f64 = (k..m).map(|i| v[i - k] * r[(i, j)]).sum::<f64>() * beta;
+                for i in k..m {
+                    r[(i, j)] -= s * v[i - k];
+                }
+            }
+            // Clean the annihilated column
+            for i in k + 1..m {
+                r[(i, k)] = 0.0;
+            }
+            reflectors.push((v, beta));
+        }
+        Qr { r, reflectors }
+    }
+
+    /// Least squares min‖A·x - b‖₂ (A is m × n with m ≥ n, full column rank)
+    pub fn least_squares(&self, b: &[f64]) -> Result<Vec<f64>, LinalgError> {
+        self.qr().solve_least_squares(b)
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// Cholesky
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// Cholesky factor of a symmetric positive definite matrix
+#[derive(Clone, Debug)]
+pub struct Cholesky {
+    l: DMatrix<f64>,
+}
+
+impl Cholesky {
+    /// Lower triangular factor L
+    pub fn l(&self) -> &DMatrix<f64> {
+        &self.l
+    }
+
+    pub fn solve(&self, b: &[f64]) -> Result<Vec<f64>, LinalgError> {
+        let n = self.l.nrows();
+        require_len(n, b.len())?;
+        let mut x = b.to_vec();
+        for i in 0..n {
+            let mut s = x[i];
+            #[allow(clippy::needless_range_loop)]
+            for k in 0..i {
+                s -= self.l[(i, k)] * x[k];
+            }
+            x[i] = s / self.l[(i, i)];
+        }
+        for i in (0..n).rev() {
+            let mut s = x[i];
+            #[allow(clippy::needless_range_loop)]
+            for k in i + 1..n {
+                s -= self.l[(k, i)] * x[k];
+            }
+            x[i] = s / self.l[(i, i)];
+        }
+        Ok(x)
+    }
+
+    /// Solve A·X = B column by column
+    pub fn solve_matrix(&self, b: &DMatrix<f64>) -> Result<DMatrix<f64>, LinalgError> {
+        let n = self.l.nrows();
+        if b.nrows() != n {
+            return Err(LinalgError::DimensionMismatch {
+                expected: (n, b.ncols()),
+                found: b.shape(),
+            });
+        }
+        let mut x = DMatrix::zeros(n, b.ncols());
+        for j in 0..b.ncols() {
+            for (i, v) in self.solve(&b.column(j))?.into_iter().enumerate() {
+                x[(i, j)] = v;
+            }
+        }
+        Ok(x)
+    }
+
+    pub fn determinant(&self) -> f64 {
+        self.l.diagonal().iter().map(|d| d * d).product()
+    }
+
+    pub fn inverse(&self) -> DMatrix<f64> {
+        let n = self.l.nrows();
+        self.solve_matrix(&DMatrix::identity(n))
+            .expect("identity has matching dimension")
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// QR
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// Householder QR: A = Q·R
+#[derive(Clone, Debug)]
+pub struct Qr {
+    /// R (m × n, zero below the diagonal)
+    r: DMatrix<f64>,
+    /// (v, β) per column; Hₖ = I - β·v·vᵀ acting on rows k..m
+    reflectors: Vec<(Vec<f64>, f64)>,
+}
+
+impl Qr {
+    /// Upper triangular (trapezoidal) factor R, m × n
+    pub fn r(&self) -> &DMatrix<f64> {
+        &self.r
+    }
+
+    /// Full orthogonal factor Q, m × m
+    pub fn q(&self) -> DMatrix<f64> {
+        let m = self.r.nrows();
+        let mut q = DMatrix::identity(m);
+        // Q = H₀·H₁·…; apply to I from the last reflector back
+        for (k, (v, beta)) in self.reflectors.iter().enumerate().rev() {
+            if v.is_empty() {
+                continue;
+            }
+            for j in 0..m {
+                let s: f64 = (k..m).map(|i| v[i - k] * q[(i, j)]).sum::<f64>() * beta;
+                for i in k..m {
+                    q[(i, j)] -= s * v[i - k];
+                }
+            }
+        }
+        q
+    }
+
+    /// Qᵀ·b
+    pub fn q_transpose_mul(&self, b: &[f64]) -> Result<Vec<f64>, LinalgError> {
+        let m = self.r.nrows();
+        require_len(m, b.len())?;
+        let mut y = b.to_vec();
+        for (k, (v, beta)) in self.reflectors.iter().enumerate() {
+            if v.is_empty() {
+                continue;
+            }
+            let s: f64 = (k..m).map(|i| v[i - k] * y[i]).sum::<f64>() * beta;
+            for i in k..m {
+                y[i] -= s * v[i - k];
+            }
+        }
+        Ok(y)
+    }
+
+    /// Numerical rank from |Rᵢᵢ| relative to the largest diagonal entry
+    pub fn rank(&self) -> usize {
+        let diag = self.r.diagonal();
+        let max = diag.iter().map(|d| d.abs()).fold(0.0, f64::max);
+        let tol = max * f64::EPSILON * self.r.nrows().max(self.r.ncols()) as f64;
+        diag.iter().filter(|d| d.abs() > tol).count()
+    }
+
+    /// Least squares solution of min‖A·x - b‖₂ (exact solve when A is square)
+    pub fn solve_least_squares(&self, b: &[f64]) -> Result<Vec<f64>, LinalgError> {
+        let (m, n) = self.r.shape();
+        if m < n {
+            return Err(LinalgError::DimensionMismatch {
+                expected: (n, n),
+                found: (m, n),
+            });
+        }
+        let rank = self.rank();
+        if rank < n {
+            return Err(LinalgError::RankDeficient { rank });
+        }
+        let y = self.q_transpose_mul(b)?;
+        let mut x = y[..n].to_vec();
+        for i in (0..n).rev() {
+            let mut s = x[i];
+            #[allow(clippy::needless_range_loop)]
+            for j in i + 1..n {
+                s -= self.r[(i, j)] * x[j];
+            }
+            x[i] = s / self.r[(i, i)];
+        }
+        Ok(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: &[f64], b: &[f64], tol: f64) {
+        assert_eq!(a.len(), b.len());
+        for (x, y) in a.iter().zip(b) {
+            assert!((x - y).abs() < tol, "{:?} vs {:?}", a, b);
+        }
+    }
+
+    fn sample() -> DMatrix<f64> {
+        DMatrix::from_rows(&[
+            vec![2.0, 1.0, 1.0, 0.0],
+            vec![4.0, 3.0, 3.0, 1.0],
+            vec![8.0, 7.0, 9.0, 5.0],
+            vec![6.0, 7.0, 9.0, 8.0],
+        ])
+    }
+
+    #[test]
+    fn test_lu_solve_determinant_inverse() {
+        let a = sample();
+        let lu = a.lu().unwrap();
+        // P·A = L·U
+        let pa = &lu.p() * &a;
+        let l_u = &lu.l() * &lu.u();
+        assert_close(pa.as_slice(), l_u.as_slice(), 1e-12);
+
+        let x_true = [1.0, -2.0, 3.0, 0.5];
+        let b = a.mul_vec(&x_true);
+        assert_close(&lu.solve(&b).unwrap(), &x_true, 1e-12);
+
+        let bt = a.transpose().mul_vec(&x_true);
+        assert_close(&lu.solve_transpose(&bt).unwrap(), &x_true, 1e-12);
+
+        assert!((a.determinant().unwrap() - 8.0).abs() < 1e-10);
+        let inv = a.inverse().unwrap();
+        assert_close(
+            (&a * &inv).as_slice(),
+            DMatrix::identity(4).as_slice(),
+            1e-12,
+        );
+
+        let singular = DMatrix::from_rows(&[vec![1.0, 2.0], vec![2.0, 4.0]]);
+        assert_eq!(
+            singular.lu().unwrap_err(),
+            LinalgError::Singular { column: 1 }
+        );
+        assert_eq!(singular.determinant().unwrap(), 0.0);
+    }
+
+    #[test]
+    fn test_cholesky() {
+        let a = DMatrix::from_rows(&[
+            vec![4.0, 12.0, -16.0],
+            vec![12.0, 37.0, -43.0],
+            vec![-16.0, -43.0, 98.0],
+        ]);
+        let chol = a.cholesky().unwrap();
+        assert_close(
+            chol.l().as_slice(),
+            &[2.0, 0.0, 0.0, 6.0, 1.0, 0.0, -8.0, 5.0, 3.0],
+            1e-12,
+        );
+        assert!((chol.determinant() - 36.0).abs() < 1e-9);
+        let x = chol.solve(&[1.0, 2.0, 3.0]).unwrap();
+        assert_close(&a.mul_vec(&x), &[1.0, 2.0, 3.0], 1e-9);
+
+        let indefinite = DMatrix::from_rows(&[vec![1.0, 2.0], vec![2.0, 1.0]]);
+        assert_eq!(
+            indefinite.cholesky().unwrap_err(),
+            LinalgError::NotPositiveDefinite { column: 1 }
+        );
+    }
+
+    #[test]
+    fn test_qr_and_least_squares() {
+        let a = sample();
+        let qr = a.qr();
+        let q = qr.q();
+        assert_close((&q * qr.r()).as_slice(), a.as_slice(), 1e-12);
+        assert_close(
+            (&q.transpose() * &q).as_slice(),
+            DMatrix::identity(4).as_slice(),
+            1e-12,
+        );
+
+        // Fit y = c0 + c1·t to noiseless points: exact recovery
+        let t = [0.0, 1.0, 2.0, 3.0, 4.0];
+        let design = DMatrix::from_fn(5, 2, |i, j| if j == 0 { 1.0 } else { t[i] });
+        let y: Vec<f64> = t.iter().map(|t| 1.5 - 0.25 * t).collect();
+        assert_close(&design.least_squares(&y).unwrap(), &[1.5, -0.25], 1e-12);
+
+        let dependent = DMatrix::from_fn(4, 2, |i, _| i as f64);
+        assert!(matches!(
+            dependent.least_squares(&[0.0; 4]),
+            Err(LinalgError::RankDeficient { rank: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_condition_estimate() {
+        // Hilbert matrix: κ₁(H₅) ≈ 9.4e5
+        let h = DMatrix::from_fn(5, 5, |i, j| 1.0 / (i + j + 1) as f64);
+        let exact = h.norm_1() * h.inverse().unwrap().norm_1();
+        let estimate = h.condition_estimate().unwrap();
+        assert!(estimate <= exact * (1.0 + 1e-9) && estimate > exact / 3.0);
+        assert_eq!(DMatrix::identity(3).condition_estimate().unwrap(), 1.0);
+    }
+}
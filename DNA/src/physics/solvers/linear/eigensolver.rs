@@ -0,0 +1,344 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: eigensolver.rs | DNA/src/physics/solvers/linear/eigensolver.rs
+//! PURPOSE: Symmetric eigendecomposition and SVD by Jacobi rotations
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! PURPOSE: Eigenvalues of symmetric matrices, singular value decomposition
+//!
+//! LAYER: DNA → PHYSICS → SOLVERS → LINEAR
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ ALGORITHM                                                                   │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ Cyclic Jacobi (symmetric A = V·Λ·Vᵀ):                                       │
+//! │   sweep all (p, q), rotate by θ with tan 2θ = 2a_pq / (a_qq - a_pp)         │
+//! │   until off(A) ≤ ε·‖A‖_F; quadratic convergence, high relative accuracy     │
+//! │                                                                             │
+//! │ One-sided Jacobi SVD (A = U·Σ·Vᵀ):                                          │
+//! │   orthogonalize column pairs of A·V; σᵢ = ‖column i‖, uᵢ = column / σᵢ      │
+//! │                                                                             │
+//! │ O(n³) per sweep, typically 6-10 sweeps. Fine for the small dense systems    │
+//! │ here (modal analysis, inertia tensors, fitting); not for n in the 1000s.    │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! REFERENCE: Golub & Van Loan, "Matrix Computations" (4th ed.), §8.5 and §8.6.3
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use super::dense::LinalgError;
+use crate::math::dmatrix::DMatrix;
+
+const MAX_SWEEPS: usize = 60;
+
+/// Eigendecomposition of a symmetric matrix
+#[derive(Clone, Debug)]
+pub struct SymmetricEigen {
+    /// Eigenvalues, ascending
+    pub values: Vec<f64>,
+    /// Orthonormal eigenvectors as columns, in the order of `values`
+    pub vectors: DMatrix<f64>,
+}
+
+impl SymmetricEigen {
+    /// Decompose a symmetric matrix (symmetry checked to a relative 1e-10)
+    pub fn new(a: &DMatrix<f64>) -> Result<Self, LinalgError> {
+        if !a.is_square() {
+            return Err(LinalgError::NotSquare {
+                rows: a.nrows(),
+                cols: a.ncols(),
+            });
+        }
+        if !a.is_symmetric(1e-10 * a.max_modulus().max(f64::MIN_POSITIVE)) {
+            return Err(LinalgError::NotSymmetric);
+        }
+        let n = a.nrows();
+        let mut m = a.clone();
+        let mut v = DMatrix::identity(n);
+        let tol = f64::EPSILON * a.norm_frobenius();
+
+        let off = |m: &DMatrix<f64>| {
+            let mut s = 0.0;
+            for i in 0..n {
+                for j in i + 1..n {
+                    s += 2.0 * m[(i, j)] * m[(i, j)];
+                }
+            }
+            s.sqrt()
+        };
+
+        let mut sweeps = 0;
+        while off(&m) > tol {
+            if sweeps == MAX_SWEEPS {
+                return Err(LinalgError::NoConvergence { iterations: sweeps });
+            }
+            sweeps += 1;
+            for p in 0..n {
+                for q in p + 1..n {
+                    let apq = m[(p, q)];
+                    if apq == 0.0 {
+                        continue;
+                    }
+                    let (c, s) = jacobi_rotation(m[(p, p)], m[(q, q)], apq);
+                    // A ← Jᵀ·A·J
+                    for k in 0..n {
+                        let (akp, akq) = (m[(k, p)], m[(k, q)]);
+                        m[(k, p)] = c * akp - s * akq;
+                        m[(k, q)] = s * akp + c * akq;
+                    }
+                    for k in 0..n {
+                        let (apk, aqk) = (m[(p, k)], m[(q, k)]);
+                        m[(p, k)] = c * apk - s * aqk;
+                        m[(q, k)] = s * apk + c * aqk;
+                    }
+                    for k in 0..n {
+                        let (vkp, vkq) = (v[(k, p)], v[(k, q)]);
+                        v[(k, p)] = c * vkp - s * vkq;
+                        v[(k, q)] = s * vkp + c * vkq;
+                    }
+                }
+            }
+        }
+
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&i, &j| m[(i, i)].total_cmp(&m[(j, j)]));
+        Ok(SymmetricEigen {
+            values: order.iter().map(|&i| m[(i, i)]).collect(),
+            vectors: DMatrix::from_fn(n, n, |i, j| v[(i, order[j])]),
+        })
+    }
+
+    /// Eigenvector for `values[k]`
+    pub fn vector(&self, k: usize) -> Vec<f64> {
+        self.vectors.column(k)
+    }
+
+    /// V·Λ·Vᵀ
+    pub fn reconstruct(&self) -> DMatrix<f64> {
+        let lambda = DMatrix::from_diagonal(&self.values);
+        &(&self.vectors * &lambda) * &self.vectors.transpose()
+    }
+}
+
+/// (c, s) zeroing the off-diagonal of [[app, apq], [apq, aqq]]
+fn jacobi_rotation(app: f64, aqq: f64, apq: f64) -> (f64, f64) {
+    let tau = (aqq - app) / (2.0 * apq);
+    let t = tau.signum() / (tau.abs() + (1.0 + tau * tau).sqrt());
+    let t = if tau == 0.0 { 1.0 } else { t };
+    let c = 1.0 / (1.0 + t * t).sqrt();
+    (c, t * c)
+}
+
+/// Thin singular value decomposition A = U·Σ·Vᵀ
+#[derive(Clone, Debug)]
+pub struct Svd {
+    /// Left singular vectors, m × k (k = min(m, n))
+    pub u: DMatrix<f64>,
+    /// Singular values, descending
+    pub singular_values: Vec<f64>,
+    /// Right singular vectors, n × k
+    pub v: DMatrix<f64>,
+}
+
+impl Svd {
+    pub fn new(a: &DMatrix<f64>) -> Result<Self, LinalgError> {
+        let (m, n) = a.shape();
+        if m < n {
+            // Decompose Aᵀ = V·Σ·Uᵀ and swap
+            let t = Svd::new(&a.transpose())?;
+            return Ok(Svd {
+                u: t.v,
+                singular_values: t.singular_values,
+                v: t.u,
+            });
+        }
+
+        let mut w = a.clone();
+        let mut v = DMatrix::identity(n);
+        let mut sweeps = 0;
+        loop {
+            let mut rotated = false;
+            for p in 0..n {
+                for q in p + 1..n {
+                    let (mut alpha, mut beta, mut gamma) = (0.0, 0.0, 0.0);
+                    for i in 0..m {
+                        alpha += w[(i, p)] * w[(i, p)];
+                        beta += w[(i, q)] * w[(i, q)];
+                        gamma += w[(i, p)] * w[(i, q)];
+                    }
+                    if gamma.abs() <= f64::EPSILON * (alpha * beta).sqrt() || gamma == 0.0 {
+                        continue;
+                    }
+                    rotated = true;
+                    let (c, s) = jacobi_rotation(alpha, beta, gamma);
+                    for i in 0..m {
+                        let (wp, wq) = (w[(i, p)], w[(i, q)]);
+                        w[(i, p)] = c * wp - s * wq;
+                        w[(i, q)] = s * wp + c * wq;
+                    }
+                    for i in 0..n {
+                        let (vp, vq) = (v[(i, p)], v[(i, q)]);
+                        v[(i, p)] = c * vp - s * vq;
+                        v[(i, q)] = s * vp + c * vq;
+                    }
+                }
+            }
+            if !rotated {
+                break;
+            }
+            sweeps += 1;
+            if sweeps == MAX_SWEEPS {
+                return Err(LinalgError::NoConvergence { iterations: sweeps });
+            }
+        }
+
+        let sigma: Vec<f64> = (0..n)
+            .map(|j| (0..m).map(|i| w[(i, j)] * w[(i, j)]).sum::<f64>().sqrt())
+            .collect();
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&i, &j| sigma[j].total_cmp(&sigma[i]));
+
+        let mut u = DMatrix::zeros(m, n);
+        for (k, &j) in order.iter().enumerate() {
+            if sigma[j] > 0.0 {
+                for i in 0..m {
+                    u[(i, k)] = w[(i, j)] / sigma[j];
+                }
+            }
+        }
+        Ok(Svd {
+            u,
+            singular_values: order.iter().map(|&j| sigma[j]).collect(),
+            v: DMatrix::from_fn(n, n, |i, k| v[(i, order[k])]),
+        })
+    }
+
+    /// Default rank tolerance: max(m, n)·ε·σmax
+    pub fn default_tolerance(&self) -> f64 {
+        let dim = self.u.nrows().max(self.v.nrows()) as f64;
+        dim * f64::EPSILON * self.singular_values.first().copied().unwrap_or(0.0)
+    }
+
+    /// Number of singular values above `tol`
+    pub fn rank(&self, tol: f64) -> usize {
+        self.singular_values.iter().filter(|&&s| s > tol).count()
+    }
+
+    /// 2-norm condition number σmax / σmin (∞ if singular)
+    pub fn condition_number(&self) -> f64 {
+        match (self.singular_values.first(), self.singular_values.last()) {
+            (Some(&max), Some(&min)) if min > 0.0 => max / min,
+            (Some(_), Some(_)) => f64::INFINITY,
+            _ => 0.0,
+        }
+    }
+
+    /// Moore-Penrose pseudo-inverse, dropping σ ≤ `tol`
+    pub fn pseudo_inverse(&self, tol: f64) -> DMatrix<f64> {
+        let (m, n) = (self.u.nrows(), self.v.nrows());
+        let mut pinv = DMatrix::zeros(n, m);
+        for (k, &s) in self.singular_values.iter().enumerate() {
+            if s <= tol {
+                continue;
+            }
+            for i in 0..n {
+                let vik = self.v[(i, k)] / s;
+                for j in 0..m {
+                    pinv[(i, j)] += vik * self.u[(j, k)];
+                }
+            }
+        }
+        pinv
+    }
+
+    /// Minimum-norm least squares solution of A·x ≈ b
+    pub fn solve(&self, b: &[f64], tol: f64) -> Result<Vec<f64>, LinalgError> {
+        if b.len() != self.u.nrows() {
+            return Err(LinalgError::DimensionMismatch {
+                expected: (self.u.nrows(), 1),
+                found: (b.len(), 1),
+            });
+        }
+        Ok(self.pseudo_inverse(tol).mul_vec(b))
+    }
+
+    /// U·Σ·Vᵀ
+    pub fn reconstruct(&self) -> DMatrix<f64> {
+        let sigma = DMatrix::from_diagonal(&self.singular_values);
+        &(&self.u * &sigma) * &self.v.transpose()
+    }
+}
+
+impl DMatrix<f64> {
+    pub fn symmetric_eigen(&self) -> Result<SymmetricEigen, LinalgError> {
+        SymmetricEigen::new(self)
+    }
+
+    pub fn svd(&self) -> Result<Svd, LinalgError> {
+        Svd::new(self)
+    }
+
+    /// Exact 2-norm condition number from the SVD
+    pub fn condition_number(&self) -> Result<f64, LinalgError> {
+        Ok(self.svd()?.condition_number())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_matrix_close(a: &DMatrix<f64>, b: &DMatrix<f64>, tol: f64) {
+        assert_eq!(a.shape(), b.shape());
+        for (x, y) in a.as_slice().iter().zip(b.as_slice()) {
+            assert!((x - y).abs() < tol, "{:?}\nvs\n{:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_symmetric_eigen() {
+        // Fixed-fixed 3-mass spring chain: λ = 2 - 2cos(kπ/4)
+        let a = DMatrix::from_rows(&[
+            vec![2.0, -1.0, 0.0],
+            vec![-1.0, 2.0, -1.0],
+            vec![0.0, -1.0, 2.0],
+        ]);
+        let eig = a.symmetric_eigen().unwrap();
+        let expected: Vec<f64> = (1..=3)
+            .map(|k| 2.0 - 2.0 * (k as f64 * std::f64::consts::PI / 4.0).cos())
+            .collect();
+        for (l, e) in eig.values.iter().zip(&expected) {
+            assert!((l - e).abs() < 1e-12);
+        }
+        assert_matrix_close(&eig.reconstruct(), &a, 1e-12);
+        let vtv = &eig.vectors.transpose() * &eig.vectors;
+        assert_matrix_close(&vtv, &DMatrix::identity(3), 1e-12);
+
+        let skew = DMatrix::from_rows(&[vec![0.0, 1.0], vec![-1.0, 0.0]]);
+        assert_eq!(
+            skew.symmetric_eigen().unwrap_err(),
+            LinalgError::NotSymmetric
+        );
+    }
+
+    #[test]
+    fn test_svd() {
+        let a = DMatrix::from_rows(&[vec![3.0, 2.0, 2.0], vec![2.0, 3.0, -2.0]]);
+        let svd = a.svd().unwrap();
+        assert!((svd.singular_values[0] - 5.0).abs() < 1e-12);
+        assert!((svd.singular_values[1] - 3.0).abs() < 1e-12);
+        assert_matrix_close(&svd.reconstruct(), &a, 1e-12);
+        assert!((svd.condition_number() - 5.0 / 3.0).abs() < 1e-12);
+
+        // Rank-1 matrix: pseudo-inverse satisfies A·A⁺·A = A
+        let r1 = DMatrix::from_fn(3, 2, |i, j| (i + 1) as f64 * (j + 1) as f64);
+        let svd = r1.svd().unwrap();
+        let tol = svd.default_tolerance();
+        assert_eq!(svd.rank(tol), 1);
+        let pinv = svd.pseudo_inverse(tol);
+        assert_matrix_close(&(&(&r1 * &pinv) * &r1), &r1, 1e-12);
+        assert!(r1.condition_number().unwrap() > 1e12);
+    }
+}
@@ -0,0 +1,836 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: iterative.rs | DNA/src/physics/solvers/linear/iterative.rs
+//! PURPOSE: Preconditioned Krylov solvers (CG, BiCGSTAB, restarted GMRES)
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! PURPOSE: Iterative solution of large sparse A·x = b
+//!
+//! LAYER: DNA → PHYSICS → SOLVERS → LINEAR
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ ALGORITHM                                                                   │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ CG          A symmetric positive definite. 1 A·x + 1 M⁻¹ per iteration.     │
+//! │             Minimizes the A-norm error over the Krylov space.               │
+//! │ BiCGSTAB    General A. 2 A·x + 2 M⁻¹ per iteration, short recurrences,      │
+//! │             residual not monotone; may break down (reported, not hidden).  │
+//! │ GMRES(m)    General A. Minimizes ‖r‖₂ over the space; memory O(m·n),        │
+//! │             restarts every m iterations. Residual never increases.          │
+//! │                                                                             │
+//! │ Preconditioning (BiCGSTAB and GMRES right-preconditioned, so the reported   │
+//! │ residual is the true ‖b - A·x‖₂ rather than a preconditioned one):          │
+//! │   Jacobi               M = diag(A)                          cheapest       │
+//! │   Ilu0                 L·U with the sparsity of A           general A      │
+//! │   IncompleteCholesky   L·Lᵀ with the sparsity of tril(A)    SPD A, for CG  │
+//! │                                                                             │
+//! │ Stop when ‖r‖₂ ≤ max(rtol·‖b‖₂, atol) or after max_iterations.              │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! Non-convergence is not an error by itself: the solution carries
+//! `converged`, the residual history and the best iterate, and
+//! `into_result()` turns a miss into `LinalgError::NoConvergence`.
+//!
+//! REFERENCE: Saad, "Iterative Methods for Sparse Linear Systems" (2nd ed.),
+//!            ch. 6-7 (GMRES, CG, BiCGSTAB) and ch. 10 (ILU/IC preconditioners)
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use super::dense::LinalgError;
+use super::sparse::{CsrMatrix, LinearOperator, TripletMatrix};
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// PRECONDITIONERS
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// Approximate inverse z = M⁻¹·r
+pub trait Preconditioner {
+    fn apply(&self, r: &[f64], z: &mut [f64]);
+}
+
+/// No preconditioning (M = I)
+#[derive(Clone, Copy, Debug, Default)]
+pub struct IdentityPreconditioner;
+
+impl Preconditioner for IdentityPreconditioner {
+    fn apply(&self, r: &[f64], z: &mut [f64]) {
+        z.copy_from_slice(r);
+    }
+}
+
+/// Diagonal scaling M = diag(A)
+#[derive(Clone, Debug)]
+pub struct Jacobi {
+    inv_diagonal: Vec<f64>,
+}
+
+impl Jacobi {
+    pub fn new(a: &CsrMatrix) -> Result<Self, LinalgError> {
+        require_square(a)?;
+        let inv_diagonal = a
+            .diagonal()
+            .into_iter()
+            .enumerate()
+            .map(|(i, d)| {
+                if d == 0.0 || !d.is_finite() {
+                    Err(LinalgError::Singular { column: i })
+                } else {
+                    Ok(1.0 / d)
+                }
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { inv_diagonal })
+    }
+}
+
+impl Preconditioner for Jacobi {
+    fn apply(&self, r: &[f64], z: &mut [f64]) {
+        for ((zi, ri), d) in z.iter_mut().zip(r).zip(&self.inv_diagonal) {
+            *zi = ri * d;
+        }
+    }
+}
+
+/// Incomplete LU with zero fill-in: L (unit, strictly lower) and U share A's pattern
+#[derive(Clone, Debug)]
+pub struct Ilu0 {
+    factors: CsrMatrix,
+    diagonal: Vec<usize>,
+}
+
+impl Ilu0 {
+    pub fn new(a: &CsrMatrix) -> Result<Self, LinalgError> {
+        let n = require_square(a)?;
+        let (offsets, cols) = (a.row_offsets(), a.col_indices());
+        let mut values = a.values().to_vec();
+        let diagonal = (0..n)
+            .map(|i| a.position(i, i).ok_or(LinalgError::Singular { column: i }))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // IKJ variant: row i is eliminated against the finished rows k < i
+        let mut marker = vec![usize::MAX; n];
+        for i in 0..n {
+            let row = offsets[i]..offsets[i + 1];
+            for p in row.clone() {
+                marker[cols[p]] = p;
+            }
+            for p in row.clone() {
+                let k = cols[p];
+                if k >= i {
+                    break;
+                }
+                let factor = values[p] / values[diagonal[k]];
+                values[p] = factor;
+                for q in diagonal[k] + 1..offsets[k + 1] {
+                    let m = marker[cols[q]];
+                    if m != usize::MAX {
+                        values[m] -= factor * values[q];
+                    }
+                }
+            }
+            for p in row {
+                marker[cols[p]] = usize::MAX;
+            }
+            let pivot = values[diagonal[i]];
+            if pivot == 0.0 || !pivot.is_finite() {
+                return Err(LinalgError::Singular { column: i });
+            }
+        }
+
+        let factors = CsrMatrix::from_csr(n, n, offsets.to_vec(), cols.to_vec(), values)?;
+        Ok(Self { factors, diagonal })
+    }
+}
+
+impl Preconditioner for Ilu0 {
+    fn apply(&self, r: &[f64], z: &mut [f64]) {
+        let (offsets, cols, values) = (
+            self.factors.row_offsets(),
+            self.factors.col_indices(),
+            self.factors.values(),
+        );
+        // L·y = r
+        for i in 0..z.len() {
+            let mut s = r[i];
+            for p in offsets[i]..self.diagonal[i] {
+                s -= values[p] * z[cols[p]];
+            }
+            z[i] = s;
+        }
+        // U·z = y
+        for i in (0..z.len()).rev() {
+            let mut s = z[i];
+            for p in self.diagonal[i] + 1..offsets[i + 1] {
+                s -= values[p] * z[cols[p]];
+            }
+            z[i] = s / values[self.diagonal[i]];
+        }
+    }
+}
+
+/// Incomplete Cholesky IC(0): L·Lᵀ ≈ A with L restricted to the pattern of tril(A)
+#[derive(Clone, Debug)]
+pub struct IncompleteCholesky {
+    /// Lower triangle, diagonal stored last in each row
+    l: CsrMatrix,
+}
+
+impl IncompleteCholesky {
+    /// Reads the lower triangle of `a` (assumed symmetric)
+    pub fn new(a: &CsrMatrix) -> Result<Self, LinalgError> {
+        let n = require_square(a)?;
+        let mut lower = TripletMatrix::with_capacity(n, n, a.nnz() / 2 + n);
+        for i in 0..n {
+            let (cols, vals) = a.row(i);
+            for (&j, &v) in cols.iter().zip(vals) {
+                if j <= i {
+                    lower.push(i, j, v);
+                }
+            }
+        }
+        let pattern = lower.to_csr();
+        let (offsets, cols) = (pattern.row_offsets(), pattern.col_indices());
+        let mut values = pattern.values().to_vec();
+
+        for i in 0..n {
+            let (start, end) = (offsets[i], offsets[i + 1]);
+            if start == end || cols[end - 1] != i {
+                return Err(LinalgError::NotPositiveDefinite { column: i });
+            }
+            for p in start..end {
+                let k = cols[p];
+                // Σ_{j<k} L[i,j]·L[k,j] over the shared pattern (sorted merge)
+                let (mut a_pos, mut b_pos, b_end) = (start, offsets[k], offsets[k + 1] - 1);
+                let mut dot = 0.0;
+                while a_pos < p && b_pos < b_end {
+                    match cols[a_pos].cmp(&cols[b_pos]) {
+                        std::cmp::Ordering::Less => a_pos += 1,
+                        std::cmp::Ordering::Greater => b_pos += 1,
+                        std::cmp::Ordering::Equal => {
+                            dot += values[a_pos] * values[b_pos];
+                            a_pos += 1;
+                            b_pos += 1;
+                        }
+                    }
+                }
+                let s = values[p] - dot;
+                if k < i {
+                    values[p] = s / values[offsets[k + 1] - 1];
+                } else if s > 0.0 && s.is_finite() {
+                    values[p] = s.sqrt();
+                } else {
+                    return Err(LinalgError::NotPositiveDefinite { column: i });
+                }
+            }
+        }
+
+        let l = CsrMatrix::from_csr(n, n, offsets.to_vec(), cols.to_vec(), values)?;
+        Ok(Self { l })
+    }
+
+    pub fn l(&self) -> &CsrMatrix {
+        &self.l
+    }
+}
+
+impl Preconditioner for IncompleteCholesky {
+    fn apply(&self, r: &[f64], z: &mut [f64]) {
+        let (offsets, cols, values) = (self.l.row_offsets(), self.l.col_indices(), self.l.values());
+        // L·y = r
+        for i in 0..z.len() {
+            let diag = offsets[i + 1] - 1;
+            let mut s = r[i];
+            for p in offsets[i]..diag {
+                s -= values[p] * z[cols[p]];
+            }
+            z[i] = s / values[diag];
+        }
+        // Lᵀ·z = y (column sweep over the rows of L)
+        for i in (0..z.len()).rev() {
+            let diag = offsets[i + 1] - 1;
+            z[i] /= values[diag];
+            for p in offsets[i]..diag {
+                z[cols[p]] -= values[p] * z[i];
+            }
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// OPTIONS AND RESULTS
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// Stopping criteria and GMRES restart length
+#[derive(Clone, Debug, PartialEq)]
+pub struct IterativeOptions {
+    /// Relative tolerance on ‖r‖₂ / ‖b‖₂
+    pub rtol: f64,
+    /// Absolute tolerance on ‖r‖₂
+    pub atol: f64,
+    pub max_iterations: usize,
+    /// GMRES Krylov subspace size before restart
+    pub restart: usize,
+}
+
+impl Default for IterativeOptions {
+    fn default() -> Self {
+        Self {
+            rtol: 1e-8,
+            atol: 0.0,
+            max_iterations: 1000,
+            restart: 30,
+        }
+    }
+}
+
+impl IterativeOptions {
+    pub fn with_tolerances(mut self, rtol: f64, atol: f64) -> Self {
+        self.rtol = rtol;
+        self.atol = atol;
+        self
+    }
+
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    pub fn with_restart(mut self, restart: usize) -> Self {
+        self.restart = restart;
+        self
+    }
+
+    fn threshold(&self, b_norm: f64) -> f64 {
+        (self.rtol * b_norm).max(self.atol)
+    }
+}
+
+/// Iterate, convergence flag and residual history
+#[derive(Clone, Debug)]
+pub struct IterativeSolution {
+    pub x: Vec<f64>,
+    pub iterations: usize,
+    /// Final ‖b - A·x‖₂
+    pub residual_norm: f64,
+    pub converged: bool,
+    /// ‖r‖₂ at the start and after every iteration
+    /// (GMRES: the Arnoldi estimate within a cycle)
+    pub history: Vec<f64>,
+}
+
+impl IterativeSolution {
+    /// `Err(NoConvergence)` unless the tolerance was met
+    pub fn into_result(self) -> Result<Self, LinalgError> {
+        if self.converged {
+            Ok(self)
+        } else {
+            Err(LinalgError::NoConvergence {
+                iterations: self.iterations,
+            })
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// SOLVERS
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// Preconditioned conjugate gradient (A and M symmetric positive definite)
+pub fn conjugate_gradient<A, P>(
+    a: &A,
+    b: &[f64],
+    x0: Option<&[f64]>,
+    preconditioner: &P,
+    options: &IterativeOptions,
+) -> Result<IterativeSolution, LinalgError>
+where
+    A: LinearOperator + ?Sized,
+    P: Preconditioner + ?Sized,
+{
+    let (mut x, mut r) = initial_residual(a, b, x0)?;
+    let n = b.len();
+    let tol = options.threshold(norm(b));
+    let mut history = vec![norm(&r)];
+
+    let mut z = vec![0.0; n];
+    preconditioner.apply(&r, &mut z);
+    let mut p = z.clone();
+    let mut rz = dot(&r, &z);
+    let mut q = vec![0.0; n];
+
+    let mut converged = history[0] <= tol;
+    while !converged && history.len() <= options.max_iterations {
+        a.apply(&p, &mut q);
+        let pq = dot(&p, &q);
+        if !(pq > 0.0 && pq.is_finite()) {
+            // A (or M) is not positive definite along p
+            break;
+        }
+        let alpha = rz / pq;
+        axpy(alpha, &p, &mut x);
+        axpy(-alpha, &q, &mut r);
+        history.push(norm(&r));
+        converged = *history.last().expect("nonempty") <= tol;
+        if converged {
+            break;
+        }
+        preconditioner.apply(&r, &mut z);
+        let rz_next = dot(&r, &z);
+        let beta = rz_next / rz;
+        rz = rz_next;
+        for (pi, zi) in p.iter_mut().zip(&z) {
+            *pi = zi + beta * *pi;
+        }
+    }
+    Ok(finish(a, b, x, converged, history))
+}
+
+/// Right-preconditioned BiCGSTAB (general nonsymmetric A)
+pub fn bicgstab<A, P>(
+    a: &A,
+    b: &[f64],
+    x0: Option<&[f64]>,
+    preconditioner: &P,
+    options: &IterativeOptions,
+) -> Result<IterativeSolution, LinalgError>
+where
+    A: LinearOperator + ?Sized,
+    P: Preconditioner + ?Sized,
+{
+    let (mut x, mut r) = initial_residual(a, b, x0)?;
+    let n = b.len();
+    let tol = options.threshold(norm(b));
+    let mut history = vec![norm(&r)];
+
+    let r_hat = r.clone();
+    let (mut rho, mut alpha, mut omega) = (1.0, 1.0, 1.0);
+    let (mut p, mut v) = (vec![0.0; n], vec![0.0; n]);
+    let (mut p_hat, mut s_hat, mut t) = (vec![0.0; n], vec![0.0; n], vec![0.0; n]);
+
+    let mut converged = history[0] <= tol;
+    while !converged && history.len() <= options.max_iterations {
+        let rho_next = dot(&r_hat, &r);
+        if rho_next == 0.0 || !rho_next.is_finite() {
+            break;
+        }
+        if history.len() == 1 {
+            p.copy_from_slice(&r);
+        } else {
+            let beta = (rho_next / rho) * (alpha / omega);
+            for i in 0..n {
+                p[i] = r[i] + beta * (p[i] - omega * v[i]);
+            }
+        }
+        rho = rho_next;
+
+        preconditioner.apply(&p, &mut p_hat);
+        a.apply(&p_hat, &mut v);
+        let rv = dot(&r_hat, &v);
+        if rv == 0.0 || !rv.is_finite() {
+            break;
+        }
+        alpha = rho / rv;
+        // s overwrites r
+        axpy(-alpha, &v, &mut r);
+        axpy(alpha, &p_hat, &mut x);
+        let s_norm = norm(&r);
+        if s_norm <= tol {
+            history.push(s_norm);
+            converged = true;
+            break;
+        }
+
+        preconditioner.apply(&r, &mut s_hat);
+        a.apply(&s_hat, &mut t);
+        let tt = dot(&t, &t);
+        omega = if tt > 0.0 { dot(&t, &r) / tt } else { 0.0 };
+        axpy(omega, &s_hat, &mut x);
+        axpy(-omega, &t, &mut r);
+        history.push(norm(&r));
+        converged = *history.last().expect("nonempty") <= tol;
+        if omega == 0.0 {
+            break;
+        }
+    }
+    Ok(finish(a, b, x, converged, history))
+}
+
+/// Right-preconditioned restarted GMRES(m) (general nonsymmetric A)
+pub fn gmres<A, P>(
+    a: &A,
+    b: &[f64],
+    x0: Option<&[f64]>,
+    preconditioner: &P,
+    options: &IterativeOptions,
+) -> Result<IterativeSolution, LinalgError>
+where
+    A: LinearOperator + ?Sized,
+    P: Preconditioner + ?Sized,
+{
+    let (mut x, mut r) = initial_residual(a, b, x0)?;
+    let n = b.len();
+    let m = options.restart.clamp(1, n.max(1));
+    let tol = options.threshold(norm(b));
+    let mut history = vec![norm(&r)];
+    let mut converged = history[0] <= tol;
+    let mut iterations = 0;
+
+    let mut basis: Vec<Vec<f64>> = Vec::with_capacity(m + 1);
+    let mut h = vec![vec![0.0; m]; m + 1];
+    let (mut cs, mut sn, mut g) = (vec![0.0; m], vec![0.0; m], vec![0.0; m + 1]);
+    let (mut z, mut w) = (vec![0.0; n], vec![0.0; n]);
+
+    while !converged && iterations < options.max_iterations {
+        // r = b - A·x at the start of each cycle
+        if iterations > 0 {
+            a.apply(&x, &mut w);
+            for i in 0..n {
+                r[i] = b[i] - w[i];
+            }
+        }
+        let beta = norm(&r);
+        if beta <= tol {
+            converged = true;
+            break;
+        }
+        basis.clear();
+        basis.push(r.iter().map(|v| v / beta).collect());
+        g.iter_mut().for_each(|v| *v = 0.0);
+        g[0] = beta;
+
+        let mut k = 0;
+        while k < m && iterations < options.max_iterations {
+            preconditioner.apply(&basis[k], &mut z);
+            a.apply(&z, &mut w);
+            // Modified Gram-Schmidt
+            for (i, v) in basis.iter().enumerate() {
+                h[i][k] = dot(&w, v);
+                axpy(-h[i][k], v, &mut w);
+            }
+            h[k + 1][k] = norm(&w);
+
+            // Apply the earlier Givens rotations, then zero h[k+1][k]
+            for i in 0..k {
+                let t = cs[i] * h[i][k] + sn[i] * h[i + 1][k];
+                h[i + 1][k] = -sn[i] * h[i][k] + cs[i] * h[i + 1][k];
+                h[i][k] = t;
+            }
+            let denom = h[k][k].hypot(h[k + 1][k]);
+            let happy = h[k + 1][k] <= f64::EPSILON * denom;
+            if h[k + 1][k] > 0.0 {
+                basis.push(w.iter().map(|v| v / h[k + 1][k]).collect());
+            }
+            (cs[k], sn[k]) = if denom == 0.0 {
+                (1.0, 0.0)
+            } else {
+                (h[k][k] / denom, h[k + 1][k] / denom)
+            };
+            h[k][k] = denom;
+            h[k + 1][k] = 0.0;
+            g[k + 1] = -sn[k] * g[k];
+            g[k] *= cs[k];
+
+            iterations += 1;
+            k += 1;
+            history.push(g[k].abs());
+            if g[k].abs() <= tol {
+                converged = true;
+                break;
+            }
+            if happy {
+                break;
+            }
+        }
+
+        // y = H⁻¹·g (upper triangular), x += M⁻¹·(V·y)
+        let mut y = g[..k].to_vec();
+        for i in (0..k).rev() {
+            for j in i + 1..k {
+                y[i] -= h[i][j] * y[j];
+            }
+            if h[i][i] == 0.0 {
+                return Err(LinalgError::Singular { column: i });
+            }
+            y[i] /= h[i][i];
+        }
+        w.iter_mut().for_each(|v| *v = 0.0);
+        for (yi, v) in y.iter().zip(&basis) {
+            axpy(*yi, v, &mut w);
+        }
+        preconditioner.apply(&w, &mut z);
+        axpy(1.0, &z, &mut x);
+    }
+    let mut solution = finish(a, b, x, converged, history);
+    solution.iterations = iterations;
+    Ok(solution)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// HELPERS
+// ─────────────────────────────────────────────────────────────────────────────────
+
+fn require_square(a: &CsrMatrix) -> Result<usize, LinalgError> {
+    if a.nrows() == a.ncols() {
+        Ok(a.nrows())
+    } else {
+        Err(LinalgError::NotSquare {
+            rows: a.nrows(),
+            cols: a.ncols(),
+        })
+    }
+}
+
+/// (x0 or zeros, b - A·x0) after checking dimensions
+fn initial_residual<A: LinearOperator + ?Sized>(
+    a: &A,
+    b: &[f64],
+    x0: Option<&[f64]>,
+) -> Result<(Vec<f64>, Vec<f64>), LinalgError> {
+    let n = a.dimension();
+    for len in std::iter::once(b.len()).chain(x0.map(|x| x.len())) {
+        if len != n {
+            return Err(LinalgError::DimensionMismatch {
+                expected: (n, 1),
+                found: (len, 1),
+            });
+        }
+    }
+    let x = x0.map_or_else(|| vec![0.0; n], |x| x.to_vec());
+    let mut ax = vec![0.0; n];
+    a.apply(&x, &mut ax);
+    let r = b.iter().zip(&ax).map(|(bi, ai)| bi - ai).collect();
+    Ok((x, r))
+}
+
+/// Package the iterate with its true residual
+fn finish<A: LinearOperator + ?Sized>(
+    a: &A,
+    b: &[f64],
+    x: Vec<f64>,
+    converged: bool,
+    history: Vec<f64>,
+) -> IterativeSolution {
+    let mut ax = vec![0.0; b.len()];
+    a.apply(&x, &mut ax);
+    let residual_norm = b
+        .iter()
+        .zip(&ax)
+        .map(|(bi, ai)| (bi - ai).powi(2))
+        .sum::<f64>()
+        .sqrt();
+    IterativeSolution {
+        x,
+        iterations: history.len() - 1,
+        residual_norm,
+        converged,
+        history,
+    }
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn norm(a: &[f64]) -> f64 {
+    dot(a, a).sqrt()
+}
+
+/// y += alpha·x
+fn axpy(alpha: f64, x: &[f64], y: &mut [f64]) {
+    for (yi, xi) in y.iter_mut().zip(x) {
+        *yi += alpha * xi;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 5-point Laplacian on an n × n interior grid (SPD)
+    fn poisson_2d(n: usize) -> CsrMatrix {
+        let mut t = TripletMatrix::new(n * n, n * n);
+        for i in 0..n {
+            for j in 0..n {
+                let row = i * n + j;
+                t.push(row, row, 4.0);
+                if i > 0 {
+                    t.push(row, row - n, -1.0);
+                }
+                if i + 1 < n {
+                    t.push(row, row + n, -1.0);
+                }
+                if j > 0 {
+                    t.push(row, row - 1, -1.0);
+                }
+                if j + 1 < n {
+                    t.push(row, row + 1, -1.0);
+                }
+            }
+        }
+        t.to_csr()
+    }
+
+    /// Upwinded convection-diffusion on an n × n grid (nonsymmetric)
+    fn convection_diffusion(n: usize, peclet: f64) -> CsrMatrix {
+        let mut t = TripletMatrix::new(n * n, n * n);
+        for i in 0..n {
+            for j in 0..n {
+                let row = i * n + j;
+                t.push(row, row, 4.0 + peclet);
+                if i > 0 {
+                    t.push(row, row - n, -1.0);
+                }
+                if i + 1 < n {
+                    t.push(row, row + n, -1.0);
+                }
+                if j > 0 {
+                    t.push(row, row - 1, -1.0 - peclet);
+                }
+                if j + 1 < n {
+                    t.push(row, row + 1, -1.0);
+                }
+            }
+        }
+        t.to_csr()
+    }
+
+    fn manufactured(a: &CsrMatrix) -> (Vec<f64>, Vec<f64>) {
+        let x: Vec<f64> = (0..a.nrows()).map(|i| (i as f64 * 0.37).sin()).collect();
+        (a.mul_vec(&x), x)
+    }
+
+    fn max_error(a: &[f64], b: &[f64]) -> f64 {
+        a.iter()
+            .zip(b)
+            .map(|(x, y)| (x - y).abs())
+            .fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn test_cg_preconditioners_on_poisson() {
+        let a = poisson_2d(20);
+        let (b, x_true) = manufactured(&a);
+        let options = IterativeOptions::default().with_tolerances(1e-10, 0.0);
+
+        let plain = conjugate_gradient(&a, &b, None, &IdentityPreconditioner, &options).unwrap();
+        let jacobi = conjugate_gradient(&a, &b, None, &Jacobi::new(&a).unwrap(), &options).unwrap();
+        let ic = IncompleteCholesky::new(&a).unwrap();
+        let ichol = conjugate_gradient(&a, &b, None, &ic, &options).unwrap();
+
+        for sol in [&plain, &jacobi, &ichol] {
+            assert!(sol.converged);
+            assert!(sol.residual_norm <= 1e-9 * norm(&b));
+            assert!(max_error(&sol.x, &x_true) < 1e-8);
+            assert_eq!(sol.history.len(), sol.iterations + 1);
+        }
+        assert!(
+            ichol.iterations * 3 < plain.iterations * 2,
+            "IC(0) {} vs none {}",
+            ichol.iterations,
+            plain.iterations
+        );
+    }
+
+    #[test]
+    fn test_incomplete_factorizations_exact_for_tridiagonal() {
+        // No fill-in for a tridiagonal matrix: IC(0) and ILU(0) are exact
+        let mut t = TripletMatrix::new(6, 6);
+        for i in 0..6 {
+            t.push(i, i, 2.5);
+            if i > 0 {
+                t.push(i, i - 1, -1.0);
+                t.push(i - 1, i, -1.2);
+            }
+        }
+        let nonsym = t.to_csr();
+        let spd =
+            CsrMatrix::from_dense(&(&nonsym.to_dense() + &nonsym.to_dense().transpose()), 0.0);
+        let b = [1.0, 0.0, -2.0, 0.5, 3.0, 1.0];
+        let options = IterativeOptions::default();
+
+        let cg = conjugate_gradient(
+            &spd,
+            &b,
+            None,
+            &IncompleteCholesky::new(&spd).unwrap(),
+            &options,
+        )
+        .unwrap();
+        assert!(cg.converged && cg.iterations == 1);
+
+        let ilu = Ilu0::new(&nonsym).unwrap();
+        let gm = gmres(&nonsym, &b, None, &ilu, &options).unwrap();
+        assert!(gm.converged && gm.iterations == 1);
+        let bi = bicgstab(&nonsym, &b, None, &ilu, &options).unwrap();
+        assert!(bi.converged && bi.iterations == 1);
+
+        // Indefinite: IC(0) must refuse
+        let indefinite = CsrMatrix::from_dense(
+            &crate::math::dmatrix::DMatrix::from_rows(&[vec![1.0, 2.0], vec![2.0, 1.0]]),
+            0.0,
+        );
+        assert_eq!(
+            IncompleteCholesky::new(&indefinite).unwrap_err(),
+            LinalgError::NotPositiveDefinite { column: 1 }
+        );
+    }
+
+    #[test]
+    fn test_nonsymmetric_solvers() {
+        let a = convection_diffusion(16, 2.0);
+        let (b, x_true) = manufactured(&a);
+        let options = IterativeOptions::default()
+            .with_tolerances(1e-10, 0.0)
+            .with_restart(20);
+        let ilu = Ilu0::new(&a).unwrap();
+
+        let gm_plain = gmres(&a, &b, None, &IdentityPreconditioner, &options).unwrap();
+        let gm_ilu = gmres(&a, &b, None, &ilu, &options).unwrap();
+        let bi_plain = bicgstab(&a, &b, None, &IdentityPreconditioner, &options).unwrap();
+        let bi_ilu = bicgstab(&a, &b, None, &ilu, &options).unwrap();
+
+        for sol in [&gm_plain, &gm_ilu, &bi_plain, &bi_ilu] {
+            assert!(sol.converged);
+            assert!(max_error(&sol.x, &x_true) < 1e-7);
+        }
+        assert!(gm_ilu.iterations < gm_plain.iterations);
+        assert!(bi_ilu.iterations < bi_plain.iterations);
+        // GMRES residual is monotone within and across restarts
+        assert!(gm_plain
+            .history
+            .windows(2)
+            .all(|w| w[1] <= w[0] * (1.0 + 1e-12)));
+    }
+
+    #[test]
+    fn test_iteration_limit_and_initial_guess() {
+        let a = poisson_2d(10);
+        let (b, x_true) = manufactured(&a);
+        let limited = IterativeOptions::default().with_max_iterations(3);
+        let sol = conjugate_gradient(&a, &b, None, &IdentityPreconditioner, &limited).unwrap();
+        assert!(!sol.converged);
+        assert_eq!(sol.iterations, 3);
+        assert_eq!(
+            sol.into_result().unwrap_err(),
+            LinalgError::NoConvergence { iterations: 3 }
+        );
+
+        // Starting from the answer converges immediately
+        let sol = gmres(
+            &a,
+            &b,
+            Some(&x_true),
+            &IdentityPreconditioner,
+            &IterativeOptions::default().with_tolerances(1e-8, 1e-10),
+        )
+        .unwrap();
+        assert!(sol.converged && sol.iterations == 0);
+
+        assert!(matches!(
+            bicgstab(&a, &b[..5], None, &IdentityPreconditioner, &limited),
+            Err(LinalgError::DimensionMismatch { .. })
+        ));
+    }
+}
@@ -1,13 +1,25 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: mod.rs | DNA/src/physics/solvers/linear/mod.rs
-//! PURPOSE: Module exports: dense
-//! MODIFIED: 2025-12-09
+//! PURPOSE: Module exports: dense, eigensolver, sparse, iterative
+//! MODIFIED: 2026-10-18
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
 
-/// Dense matrix operations (LU, QR)
+/// Dense matrix decompositions (LU, Cholesky, QR, least squares)
 pub mod dense;
+pub use dense::{Cholesky, LinalgError, Lu, Qr};
 
-// pub mod sparse;      // TODO: CSR format, sparse operations
-// pub mod iterative;   // TODO: CG, GMRES, BiCGSTAB
-// pub mod eigensolver; // TODO: Power iteration, QR algorithm
+/// Symmetric eigendecomposition and SVD (Jacobi)
+pub mod eigensolver;
+pub use eigensolver::{Svd, SymmetricEigen};
+
+/// CSR sparse matrices, triplet assembly, LinearOperator trait
+pub mod sparse;
+pub use sparse::{CsrMatrix, FnOperator, LinearOperator, TripletMatrix};
+
+/// Krylov solvers (CG, BiCGSTAB, GMRES) with Jacobi / ILU(0) / IC(0) preconditioning
+pub mod iterative;
+pub use iterative::{
+    bicgstab, conjugate_gradient, gmres, IdentityPreconditioner, Ilu0, IncompleteCholesky,
+    IterativeOptions, IterativeSolution, Jacobi, Preconditioner,
+};
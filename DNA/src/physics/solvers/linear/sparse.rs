@@ -0,0 +1,359 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: sparse.rs | DNA/src/physics/solvers/linear/sparse.rs
+//! PURPOSE: CSR sparse matrix, triplet assembly, linear operator trait
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! PURPOSE: Sparse storage for large grids and meshes (heat conduction, FEM)
+//!
+//! LAYER: DNA → PHYSICS → SOLVERS → LINEAR
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ DATA DEFINED                                                                │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ LinearOperator      y = A·x without storing A (what Krylov solvers need)    │
+//! │ FnOperator          Closure adapter (matrix-free operators)                 │
+//! │ TripletMatrix       (row, col, value) assembly; duplicates are summed       │
+//! │ CsrMatrix           Compressed sparse row, columns sorted within each row   │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! CSR layout for row i: col_indices[row_offsets[i]..row_offsets[i + 1]]
+//! with matching values. Assemble element by element into a TripletMatrix
+//! (FEM stiffness, finite-difference stencils), then convert once.
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use super::dense::LinalgError;
+use crate::math::dmatrix::DMatrix;
+
+/// Square linear map y = A·x
+pub trait LinearOperator {
+    /// Number of rows (= columns)
+    fn dimension(&self) -> usize;
+
+    /// y = A·x (y is fully overwritten)
+    fn apply(&self, x: &[f64], y: &mut [f64]);
+}
+
+/// `LinearOperator` from a closure
+pub struct FnOperator<F> {
+    dimension: usize,
+    f: F,
+}
+
+impl<F: Fn(&[f64], &mut [f64])> FnOperator<F> {
+    pub fn new(dimension: usize, f: F) -> Self {
+        Self { dimension, f }
+    }
+}
+
+impl<F: Fn(&[f64], &mut [f64])> LinearOperator for FnOperator<F> {
+    fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    fn apply(&self, x: &[f64], y: &mut [f64]) {
+        (self.f)(x, y)
+    }
+}
+
+impl LinearOperator for DMatrix<f64> {
+    fn dimension(&self) -> usize {
+        self.nrows()
+    }
+
+    fn apply(&self, x: &[f64], y: &mut [f64]) {
+        y.copy_from_slice(&self.mul_vec(x));
+    }
+}
+
+/// Coordinate-format builder for `CsrMatrix`
+#[derive(Clone, Debug, Default)]
+pub struct TripletMatrix {
+    nrows: usize,
+    ncols: usize,
+    entries: Vec<(usize, usize, f64)>,
+}
+
+impl TripletMatrix {
+    pub fn new(nrows: usize, ncols: usize) -> Self {
+        Self {
+            nrows,
+            ncols,
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn with_capacity(nrows: usize, ncols: usize, capacity: usize) -> Self {
+        Self {
+            nrows,
+            ncols,
+            entries: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Add `value` at (row, col); repeated positions accumulate
+    pub fn push(&mut self, row: usize, col: usize, value: f64) {
+        assert!(
+            row < self.nrows && col < self.ncols,
+            "triplet ({}, {}) outside {}x{}",
+            row,
+            col,
+            self.nrows,
+            self.ncols
+        );
+        self.entries.push((row, col, value));
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn to_csr(&self) -> CsrMatrix {
+        let mut entries = self.entries.clone();
+        entries.sort_by_key(|&(i, j, _)| (i, j));
+
+        let mut row_offsets = vec![0; self.nrows + 1];
+        let mut col_indices = Vec::with_capacity(entries.len());
+        let mut values: Vec<f64> = Vec::with_capacity(entries.len());
+        let mut last = None;
+        for (i, j, v) in entries {
+            if last == Some((i, j)) {
+                *values.last_mut().expect("previous entry") += v;
+                continue;
+            }
+            last = Some((i, j));
+            row_offsets[i + 1] += 1;
+            col_indices.push(j);
+            values.push(v);
+        }
+        for i in 0..self.nrows {
+            row_offsets[i + 1] += row_offsets[i];
+        }
+        CsrMatrix {
+            nrows: self.nrows,
+            ncols: self.ncols,
+            row_offsets,
+            col_indices,
+            values,
+        }
+    }
+}
+
+/// Compressed sparse row matrix
+#[derive(Clone, Debug, PartialEq)]
+pub struct CsrMatrix {
+    nrows: usize,
+    ncols: usize,
+    row_offsets: Vec<usize>,
+    col_indices: Vec<usize>,
+    values: Vec<f64>,
+}
+
+impl CsrMatrix {
+    /// Build from raw CSR arrays (columns must be sorted and unique per row)
+    pub fn from_csr(
+        nrows: usize,
+        ncols: usize,
+        row_offsets: Vec<usize>,
+        col_indices: Vec<usize>,
+        values: Vec<f64>,
+    ) -> Result<Self, LinalgError> {
+        let nnz = values.len();
+        let valid = row_offsets.len() == nrows + 1
+            && row_offsets.first() == Some(&0)
+            && row_offsets.last() == Some(&nnz)
+            && col_indices.len() == nnz
+            && row_offsets.windows(2).all(|w| w[0] <= w[1])
+            && (0..nrows).all(|i| {
+                let cols = &col_indices[row_offsets[i]..row_offsets[i + 1]];
+                cols.windows(2).all(|w| w[0] < w[1]) && cols.iter().all(|&j| j < ncols)
+            });
+        if !valid {
+            return Err(LinalgError::DimensionMismatch {
+                expected: (nrows, ncols),
+                found: (row_offsets.len().saturating_sub(1), nnz),
+            });
+        }
+        Ok(Self {
+            nrows,
+            ncols,
+            row_offsets,
+            col_indices,
+            values,
+        })
+    }
+
+    pub fn identity(n: usize) -> Self {
+        Self {
+            nrows: n,
+            ncols: n,
+            row_offsets: (0..=n).collect(),
+            col_indices: (0..n).collect(),
+            values: vec![1.0; n],
+        }
+    }
+
+    /// Keep the entries of a dense matrix with |a| > `drop_tol`
+    pub fn from_dense(a: &DMatrix<f64>, drop_tol: f64) -> Self {
+        let mut t = TripletMatrix::new(a.nrows(), a.ncols());
+        for i in 0..a.nrows() {
+            for (j, &v) in a.row(i).iter().enumerate() {
+                if v.abs() > drop_tol {
+                    t.push(i, j, v);
+                }
+            }
+        }
+        t.to_csr()
+    }
+
+    pub fn nrows(&self) -> usize {
+        self.nrows
+    }
+
+    pub fn ncols(&self) -> usize {
+        self.ncols
+    }
+
+    /// Number of stored entries
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn row_offsets(&self) -> &[usize] {
+        &self.row_offsets
+    }
+
+    pub fn col_indices(&self) -> &[usize] {
+        &self.col_indices
+    }
+
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    pub fn values_mut(&mut self) -> &mut [f64] {
+        &mut self.values
+    }
+
+    /// (column indices, values) of row i
+    pub fn row(&self, i: usize) -> (&[usize], &[f64]) {
+        let range = self.row_offsets[i]..self.row_offsets[i + 1];
+        (&self.col_indices[range.clone()], &self.values[range])
+    }
+
+    /// Position of (i, j) in `values`, if stored
+    pub fn position(&self, i: usize, j: usize) -> Option<usize> {
+        let start = self.row_offsets[i];
+        self.row(i).0.binary_search(&j).ok().map(|k| start + k)
+    }
+
+    /// Entry (i, j), zero if not stored
+    pub fn get(&self, i: usize, j: usize) -> f64 {
+        self.position(i, j).map_or(0.0, |k| self.values[k])
+    }
+
+    /// Diagonal entries (zero where not stored)
+    pub fn diagonal(&self) -> Vec<f64> {
+        (0..self.nrows.min(self.ncols))
+            .map(|i| self.get(i, i))
+            .collect()
+    }
+
+    pub fn mul_vec(&self, x: &[f64]) -> Vec<f64> {
+        let mut y = vec![0.0; self.nrows];
+        self.mul_vec_into(x, &mut y);
+        y
+    }
+
+    /// y = A·x
+    pub fn mul_vec_into(&self, x: &[f64], y: &mut [f64]) {
+        assert_eq!(x.len(), self.ncols, "CsrMatrix::mul_vec dimension");
+        for (i, yi) in y.iter_mut().enumerate().take(self.nrows) {
+            let (cols, vals) = self.row(i);
+            *yi = cols.iter().zip(vals).map(|(&j, &v)| v * x[j]).sum();
+        }
+    }
+
+    pub fn transpose(&self) -> CsrMatrix {
+        let mut t = TripletMatrix::with_capacity(self.ncols, self.nrows, self.nnz());
+        for i in 0..self.nrows {
+            let (cols, vals) = self.row(i);
+            for (&j, &v) in cols.iter().zip(vals) {
+                t.push(j, i, v);
+            }
+        }
+        t.to_csr()
+    }
+
+    /// Structurally and numerically symmetric to within `tol`
+    pub fn is_symmetric(&self, tol: f64) -> bool {
+        self.nrows == self.ncols
+            && (0..self.nrows).all(|i| {
+                let (cols, vals) = self.row(i);
+                cols.iter()
+                    .zip(vals)
+                    .all(|(&j, &v)| (v - self.get(j, i)).abs() <= tol)
+            })
+    }
+
+    pub fn to_dense(&self) -> DMatrix<f64> {
+        let mut a = DMatrix::zeros(self.nrows, self.ncols);
+        for i in 0..self.nrows {
+            let (cols, vals) = self.row(i);
+            for (&j, &v) in cols.iter().zip(vals) {
+                a[(i, j)] = v;
+            }
+        }
+        a
+    }
+}
+
+impl LinearOperator for CsrMatrix {
+    fn dimension(&self) -> usize {
+        self.nrows
+    }
+
+    fn apply(&self, x: &[f64], y: &mut [f64]) {
+        self.mul_vec_into(x, y);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_triplet_assembly_sums_duplicates() {
+        let mut t = TripletMatrix::new(3, 3);
+        t.push(2, 0, 1.0);
+        t.push(0, 0, 2.0);
+        t.push(1, 1, 3.0);
+        t.push(0, 0, 0.5);
+        t.push(0, 2, -1.0);
+        let a = t.to_csr();
+        assert_eq!(a.nnz(), 4);
+        assert_eq!(a.row_offsets(), &[0, 2, 3, 4]);
+        assert_eq!(a.col_indices(), &[0, 2, 1, 0]);
+        assert_eq!(a.get(0, 0), 2.5);
+        assert_eq!(a.get(1, 2), 0.0);
+        assert_eq!(a.mul_vec(&[1.0, 2.0, 3.0]), vec![-0.5, 6.0, 1.0]);
+        assert_eq!(a.transpose().get(0, 2), 1.0);
+        assert_eq!(CsrMatrix::from_dense(&a.to_dense(), 0.0), a);
+    }
+
+    #[test]
+    fn test_from_csr_validates() {
+        assert!(CsrMatrix::from_csr(2, 2, vec![0, 1, 2], vec![0, 1], vec![1.0, 1.0]).is_ok());
+        // Unsorted columns in row 0
+        assert!(CsrMatrix::from_csr(2, 2, vec![0, 2, 2], vec![1, 0], vec![1.0, 1.0]).is_err());
+        // Column out of range
+        assert!(CsrMatrix::from_csr(2, 2, vec![0, 1, 1], vec![2], vec![1.0]).is_err());
+    }
+}
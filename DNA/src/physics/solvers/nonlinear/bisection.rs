@@ -0,0 +1,240 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: bisection.rs | DNA/src/physics/solvers/nonlinear/bisection.rs
+//! PURPOSE: Bracketing scalar root finders (bisection, Brent)
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! PURPOSE: Guaranteed root finding for f(x) = 0 given a sign change on [a, b]
+//!
+//! LAYER: DNA → PHYSICS → SOLVERS → NONLINEAR
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ ALGORITHM                                                                   │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ Bisection   halve the bracket each step: linear, |b - a|/2ᵏ, never fails    │
+//! │ Brent       inverse quadratic / secant steps, falling back to bisection     │
+//! │             whenever they leave the bracket or stall: superlinear on       │
+//! │             smooth f, never slower than ~2× bisection                       │
+//! │                                                                             │
+//! │ Both stop when |b - a| ≤ 2·(xtol + 2ε|x|) or |f(x)| ≤ ftol.                 │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! REFERENCE: Brent, "Algorithms for Minimization without Derivatives" (1973), ch. 4
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use super::problem::{NonlinearError, SolverOptions};
+
+/// Root of a scalar function
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScalarRoot {
+    pub x: f64,
+    /// f(x)
+    pub value: f64,
+    pub iterations: usize,
+    pub evaluations: usize,
+}
+
+/// Evaluate the end points; returns an exact root if one is hit
+fn check_bracket(
+    f: &mut impl FnMut(f64) -> f64,
+    a: f64,
+    b: f64,
+) -> Result<Result<(f64, f64), ScalarRoot>, NonlinearError> {
+    if !(a.is_finite() && b.is_finite()) || a == b {
+        return Err(NonlinearError::InvalidParameter(format!(
+            "bracket [{}, {}] must be finite and non-empty",
+            a, b
+        )));
+    }
+    let (fa, fb) = (f(a), f(b));
+    if !(fa.is_finite() && fb.is_finite()) {
+        return Err(NonlinearError::NonFinite { iteration: 0 });
+    }
+    for (x, fx) in [(a, fa), (b, fb)] {
+        if fx == 0.0 {
+            return Ok(Err(ScalarRoot {
+                x,
+                value: 0.0,
+                iterations: 0,
+                evaluations: 2,
+            }));
+        }
+    }
+    if fa.signum() == fb.signum() {
+        return Err(NonlinearError::InvalidBracket { a, b });
+    }
+    Ok(Ok((fa, fb)))
+}
+
+/// Bisection on [a, b] (f(a), f(b) of opposite sign)
+pub fn bisection(
+    mut f: impl FnMut(f64) -> f64,
+    a: f64,
+    b: f64,
+    options: &SolverOptions,
+) -> Result<ScalarRoot, NonlinearError> {
+    options.check()?;
+    let (mut fa, _) = match check_bracket(&mut f, a, b)? {
+        Ok(values) => values,
+        Err(root) => return Ok(root),
+    };
+    let (mut a, mut b) = (a, b);
+    for iteration in 1..=options.max_iterations {
+        let m = 0.5 * (a + b);
+        let fm = f(m);
+        if !fm.is_finite() {
+            return Err(NonlinearError::NonFinite { iteration });
+        }
+        if fm.signum() == fa.signum() {
+            a = m;
+            fa = fm;
+        } else {
+            b = m;
+        }
+        let tol = options.xtol + 2.0 * f64::EPSILON * m.abs();
+        if fm.abs() <= options.ftol || (b - a).abs() <= 2.0 * tol {
+            return Ok(ScalarRoot {
+                x: m,
+                value: fm,
+                iterations: iteration,
+                evaluations: iteration + 2,
+            });
+        }
+    }
+    Err(NonlinearError::NoConvergence {
+        iterations: options.max_iterations,
+        residual: (b - a).abs(),
+    })
+}
+
+/// Brent's method on [a, b] (f(a), f(b) of opposite sign)
+pub fn brent(
+    mut f: impl FnMut(f64) -> f64,
+    a: f64,
+    b: f64,
+    options: &SolverOptions,
+) -> Result<ScalarRoot, NonlinearError> {
+    options.check()?;
+    let (mut fa, mut fb) = match check_bracket(&mut f, a, b)? {
+        Ok(values) => values,
+        Err(root) => return Ok(root),
+    };
+    let (mut a, mut b) = (a, b);
+    // c is the previous b (or the far end of the bracket); b is the best estimate
+    let (mut c, mut fc) = (a, fa);
+    let mut d = b - a;
+    let mut e = d;
+
+    for iteration in 1..=options.max_iterations {
+        if fb.signum() == fc.signum() {
+            c = a;
+            fc = fa;
+            d = b - a;
+            e = d;
+        }
+        if fc.abs() < fb.abs() {
+            a = b;
+            b = c;
+            c = a;
+            fa = fb;
+            fb = fc;
+            fc = fa;
+        }
+        let tol = options.xtol + 2.0 * f64::EPSILON * b.abs();
+        let m = 0.5 * (c - b);
+        if m.abs() <= tol || fb.abs() <= options.ftol {
+            return Ok(ScalarRoot {
+                x: b,
+                value: fb,
+                iterations: iteration - 1,
+                evaluations: iteration + 1,
+            });
+        }
+
+        if e.abs() >= tol && fa.abs() > fb.abs() {
+            // Interpolate: secant if a == c, otherwise inverse quadratic
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                (2.0 * m * s, 1.0 - s)
+            } else {
+                let q = fa / fc;
+                let r = fb / fc;
+                (
+                    s * (2.0 * m * q * (q - r) - (b - a) * (r - 1.0)),
+                    (q - 1.0) * (r - 1.0) * (s - 1.0),
+                )
+            };
+            if p > 0.0 {
+                q = -q;
+            } else {
+                p = -p;
+            }
+            if 2.0 * p < (3.0 * m * q - (tol * q).abs()).min((e * q).abs()) {
+                e = d;
+                d = p / q;
+            } else {
+                d = m;
+                e = m;
+            }
+        } else {
+            d = m;
+            e = m;
+        }
+
+        a = b;
+        fa = fb;
+        b += if d.abs() > tol { d } else { tol.copysign(m) };
+        fb = f(b);
+        if !fb.is_finite() {
+            return Err(NonlinearError::NonFinite { iteration });
+        }
+    }
+    Err(NonlinearError::NoConvergence {
+        iterations: options.max_iterations,
+        residual: fb.abs(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bisection_and_brent_agree() {
+        let f = |x: f64| x * x * x - 2.0 * x - 5.0;
+        let root = 2.094_551_481_542_326_5;
+        let options = SolverOptions::default().with_tolerances(1e-14, 0.0);
+        let bis = bisection(f, 2.0, 3.0, &options).unwrap();
+        let br = brent(f, 2.0, 3.0, &options).unwrap();
+        assert!((bis.x - root).abs() < 1e-12);
+        assert!((br.x - root).abs() < 1e-12);
+        assert!(br.evaluations * 3 < bis.evaluations);
+    }
+
+    #[test]
+    fn test_brent_hard_cases() {
+        let options = SolverOptions::default().with_tolerances(1e-12, 0.0);
+        // Flat near the root: interpolation stalls, bisection safeguard kicks in
+        let r = brent(|x: f64| (x - 1.0).powi(9), 0.0, 1.7, &options).unwrap();
+        assert!((r.x - 1.0).abs() < 1e-2);
+        // Discontinuous sign change
+        let r = brent(
+            |x: f64| if x < 0.3 { -1.0 } else { 1.0 },
+            0.0,
+            1.0,
+            &options,
+        )
+        .unwrap();
+        assert!((r.x - 0.3).abs() < 1e-10);
+        // Exact root at an end point
+        assert_eq!(brent(|x| x, 0.0, 1.0, &options).unwrap().x, 0.0);
+
+        assert_eq!(
+            brent(|x: f64| x * x + 1.0, -1.0, 1.0, &options).unwrap_err(),
+            NonlinearError::InvalidBracket { a: -1.0, b: 1.0 }
+        );
+    }
+}
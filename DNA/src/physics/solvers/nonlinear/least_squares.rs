@@ -0,0 +1,348 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: least_squares.rs | DNA/src/physics/solvers/nonlinear/least_squares.rs
+//! PURPOSE: Bound-constrained Levenberg-Marquardt nonlinear least squares
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! PURPOSE: min ½‖r(p)‖² subject to lower ≤ p ≤ upper
+//!
+//! LAYER: DNA → PHYSICS → SOLVERS → NONLINEAR
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ ALGORITHM                                                                   │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ Step:   (JᵀJ + λ·D)·δ = -Jᵀr,   D = diag(JᵀJ) (Marquardt scaling)           │
+//! │ Bounds: parameters at a bound whose gradient points outward are held        │
+//! │         fixed for the step; the trial point is projected into the box       │
+//! │ Gain:   ρ = (actual decrease) / (decrease predicted by the linear model)    │
+//! │         ρ > 0: accept, λ ← λ·max(1/3, 1 - (2ρ - 1)³), ν ← 2                 │
+//! │         ρ ≤ 0: reject, λ ← λ·ν, ν ← 2ν                                      │
+//! │                                                                             │
+//! │ Converged when ‖projected Jᵀr‖∞ ≤ gtol, ‖δ‖∞ ≤ xtol·(xtol + ‖p‖∞), or the   │
+//! │ relative cost decrease ≤ ftol.                                              │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! Curve fitting, calibration, and pose-graph style problems (residual per
+//! constraint) all fit this shape; the Jacobian defaults to forward differences.
+//!
+//! REFERENCE: Madsen, Nielsen & Tingleff, "Methods for Non-Linear Least Squares
+//!            Problems" (2004), §3.2
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use super::problem::{
+    check_dimension, dot, forward_difference, norm_inf, NonlinearError, SolverOptions,
+};
+use crate::math::dmatrix::DMatrix;
+
+/// Residual vector r(p): ℝⁿ → ℝᵐ, m ≥ n for a well-posed fit
+pub trait LeastSquaresProblem {
+    /// Number of parameters n
+    fn parameters(&self) -> usize;
+
+    /// Number of residuals m
+    fn residuals(&self) -> usize;
+
+    /// r = r(p)
+    fn residual(&self, p: &[f64], r: &mut [f64]);
+
+    /// J = ∂r/∂p, m × n (forward differences unless overridden)
+    fn jacobian(&self, p: &[f64], jacobian: &mut DMatrix<f64>) {
+        let mut r0 = vec![0.0; self.residuals()];
+        self.residual(p, &mut r0);
+        forward_difference(p, &r0, jacobian, |pp, rp| self.residual(pp, rp));
+    }
+}
+
+/// `LeastSquaresProblem` from a closure (finite-difference Jacobian)
+pub struct FnLeastSquares<F> {
+    parameters: usize,
+    residuals: usize,
+    f: F,
+}
+
+impl<F: Fn(&[f64], &mut [f64])> FnLeastSquares<F> {
+    pub fn new(parameters: usize, residuals: usize, f: F) -> Self {
+        Self {
+            parameters,
+            residuals,
+            f,
+        }
+    }
+}
+
+impl<F: Fn(&[f64], &mut [f64])> LeastSquaresProblem for FnLeastSquares<F> {
+    fn parameters(&self) -> usize {
+        self.parameters
+    }
+
+    fn residuals(&self) -> usize {
+        self.residuals
+    }
+
+    fn residual(&self, p: &[f64], r: &mut [f64]) {
+        (self.f)(p, r)
+    }
+}
+
+/// Box constraints lower ≤ p ≤ upper (use ±∞ for free parameters)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bounds {
+    pub lower: Vec<f64>,
+    pub upper: Vec<f64>,
+}
+
+impl Bounds {
+    pub fn new(lower: Vec<f64>, upper: Vec<f64>) -> Result<Self, NonlinearError> {
+        check_dimension(lower.len(), upper.len())?;
+        if let Some(i) = (0..lower.len())
+            .find(|&i| lower[i].is_nan() || upper[i].is_nan() || lower[i] > upper[i])
+        {
+            return Err(NonlinearError::InvalidParameter(format!(
+                "bound {}: lower {} > upper {}",
+                i, lower[i], upper[i]
+            )));
+        }
+        Ok(Self { lower, upper })
+    }
+
+    /// No constraints on n parameters
+    pub fn unbounded(n: usize) -> Self {
+        Self {
+            lower: vec![f64::NEG_INFINITY; n],
+            upper: vec![f64::INFINITY; n],
+        }
+    }
+
+    pub fn project(&self, p: &mut [f64]) {
+        for ((pi, lo), hi) in p.iter_mut().zip(&self.lower).zip(&self.upper) {
+            *pi = pi.clamp(*lo, *hi);
+        }
+    }
+
+    /// Held at a bound with the descent direction (-gradient) pointing out
+    fn is_active(&self, i: usize, p: f64, gradient: f64) -> bool {
+        (p <= self.lower[i] && gradient > 0.0) || (p >= self.upper[i] && gradient < 0.0)
+    }
+}
+
+/// Result of a least-squares fit
+#[derive(Clone, Debug)]
+pub struct LeastSquaresFit {
+    pub parameters: Vec<f64>,
+    pub residuals: Vec<f64>,
+    /// ½‖r‖²
+    pub cost: f64,
+    /// ‖projected Jᵀr‖∞
+    pub gradient_norm: f64,
+    pub iterations: usize,
+    pub evaluations: usize,
+    pub converged: bool,
+}
+
+impl LeastSquaresFit {
+    /// `Err(NoConvergence)` unless a stopping tolerance was met
+    pub fn into_result(self) -> Result<Self, NonlinearError> {
+        if self.converged {
+            Ok(self)
+        } else {
+            Err(NonlinearError::NoConvergence {
+                iterations: self.iterations,
+                residual: self.gradient_norm,
+            })
+        }
+    }
+}
+
+/// Levenberg-Marquardt from `p0`, optionally within `bounds`
+pub fn levenberg_marquardt<P: LeastSquaresProblem + ?Sized>(
+    problem: &P,
+    p0: &[f64],
+    bounds: Option<&Bounds>,
+    options: &SolverOptions,
+) -> Result<LeastSquaresFit, NonlinearError> {
+    options.check()?;
+    let (n, m) = (problem.parameters(), problem.residuals());
+    check_dimension(n, p0.len())?;
+    let bounds = match bounds {
+        Some(b) => {
+            check_dimension(n, b.lower.len())?;
+            b.clone()
+        }
+        None => Bounds::unbounded(n),
+    };
+
+    let mut p = p0.to_vec();
+    bounds.project(&mut p);
+    let mut r = vec![0.0; m];
+    problem.residual(&p, &mut r);
+    let mut evaluations = 1;
+    let mut cost = 0.5 * dot(&r, &r);
+    if !cost.is_finite() {
+        return Err(NonlinearError::NonFinite { iteration: 0 });
+    }
+
+    let mut jacobian = DMatrix::zeros(m, n);
+    let mut trial = vec![0.0; n];
+    let mut r_trial = vec![0.0; m];
+    let mut lambda = 0.0;
+    let mut nu = 2.0;
+    let mut gradient_norm = f64::INFINITY;
+    let mut converged = false;
+    let mut iterations = 0;
+
+    'outer: while iterations < options.max_iterations {
+        problem.jacobian(&p, &mut jacobian);
+        let jt = jacobian.transpose();
+        let mut normal = &jt * &jacobian;
+        let mut gradient = jt.mul_vec(&r);
+
+        // Freeze parameters pinned at a bound
+        for i in 0..n {
+            if bounds.is_active(i, p[i], gradient[i]) {
+                gradient[i] = 0.0;
+                for k in 0..n {
+                    normal[(i, k)] = 0.0;
+                    normal[(k, i)] = 0.0;
+                }
+                normal[(i, i)] = 1.0;
+            }
+        }
+        gradient_norm = norm_inf(&gradient);
+        if gradient_norm <= options.gtol {
+            converged = true;
+            break;
+        }
+        iterations += 1;
+        if lambda == 0.0 {
+            lambda = 1e-3 * normal.diagonal().into_iter().fold(0.0, f64::max);
+        }
+
+        loop {
+            let mut damped = normal.clone();
+            for i in 0..n {
+                damped[(i, i)] += lambda * normal[(i, i)].max(1e-12);
+            }
+            let rhs: Vec<f64> = gradient.iter().map(|g| -g).collect();
+            let step = match damped.cholesky().and_then(|c| c.solve(&rhs)) {
+                Ok(step) => step,
+                Err(_) => {
+                    lambda *= nu;
+                    nu *= 2.0;
+                    if !lambda.is_finite() {
+                        break 'outer;
+                    }
+                    continue;
+                }
+            };
+            for i in 0..n {
+                trial[i] = p[i] + step[i];
+            }
+            bounds.project(&mut trial);
+            let taken: Vec<f64> = trial.iter().zip(&p).map(|(t, pi)| t - pi).collect();
+
+            problem.residual(&trial, &mut r_trial);
+            evaluations += 1;
+            let cost_trial = 0.5 * dot(&r_trial, &r_trial);
+            // Linear-model decrease: -(gᵀs + ½ sᵀ(JᵀJ)s)
+            let js = jacobian.mul_vec(&taken);
+            let predicted = -(dot(&gradient, &taken) + 0.5 * dot(&js, &js));
+            let actual = cost - cost_trial;
+
+            let step_norm = norm_inf(&taken);
+            let small_step = step_norm <= options.xtol * (options.xtol + norm_inf(&p));
+            if cost_trial.is_finite() && actual > 0.0 && predicted > 0.0 {
+                let rho = actual / predicted;
+                lambda *= (1.0 - (2.0 * rho - 1.0).powi(3)).max(1.0 / 3.0);
+                nu = 2.0;
+                std::mem::swap(&mut p, &mut trial);
+                std::mem::swap(&mut r, &mut r_trial);
+                let previous = cost;
+                cost = cost_trial;
+                if small_step || actual <= options.ftol * previous {
+                    converged = true;
+                    break 'outer;
+                }
+                break;
+            }
+            if small_step {
+                // The model cannot find a smaller step that helps: at a minimum
+                converged = true;
+                break 'outer;
+            }
+            lambda *= nu;
+            nu *= 2.0;
+            if !lambda.is_finite() {
+                break 'outer;
+            }
+        }
+    }
+
+    Ok(LeastSquaresFit {
+        parameters: p,
+        residuals: r,
+        cost,
+        gradient_norm,
+        iterations,
+        evaluations,
+        converged,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decay_data() -> (Vec<f64>, Vec<f64>) {
+        // y = 3·exp(-0.7 t) + 0.5 with a small deterministic perturbation
+        let t: Vec<f64> = (0..25).map(|i| i as f64 * 0.25).collect();
+        let y = t
+            .iter()
+            .enumerate()
+            .map(|(i, t)| 3.0 * (-0.7 * t).exp() + 0.5 + 1e-3 * ((i * 7 % 5) as f64 - 2.0))
+            .collect();
+        (t, y)
+    }
+
+    #[test]
+    fn test_exponential_fit() {
+        let (t, y) = decay_data();
+        let problem = FnLeastSquares::new(3, t.len(), |p: &[f64], r: &mut [f64]| {
+            for i in 0..t.len() {
+                r[i] = p[0] * (-p[1] * t[i]).exp() + p[2] - y[i];
+            }
+        });
+        let fit = levenberg_marquardt(&problem, &[1.0, 0.1, 0.0], None, &SolverOptions::default())
+            .unwrap()
+            .into_result()
+            .unwrap();
+        assert!((fit.parameters[0] - 3.0).abs() < 1e-2);
+        assert!((fit.parameters[1] - 0.7).abs() < 1e-2);
+        assert!((fit.parameters[2] - 0.5).abs() < 1e-2);
+        assert!(fit.cost < 1e-4);
+    }
+
+    #[test]
+    fn test_rosenbrock_residuals_and_bounds() {
+        let rosenbrock = FnLeastSquares::new(2, 2, |p: &[f64], r: &mut [f64]| {
+            r[0] = 10.0 * (p[1] - p[0] * p[0]);
+            r[1] = 1.0 - p[0];
+        });
+        let options = SolverOptions::default();
+        let fit = levenberg_marquardt(&rosenbrock, &[-1.2, 1.0], None, &options).unwrap();
+        assert!(fit.converged);
+        assert!((fit.parameters[0] - 1.0).abs() < 1e-8);
+
+        // Constrain x ≤ 0.5: optimum on the bound at (0.5, 0.25)
+        let bounds = Bounds::new(vec![-2.0, -2.0], vec![0.5, 2.0]).unwrap();
+        let fit = levenberg_marquardt(&rosenbrock, &[-1.2, 1.0], Some(&bounds), &options).unwrap();
+        assert!(fit.converged);
+        assert_eq!(fit.parameters[0], 0.5);
+        assert!((fit.parameters[1] - 0.25).abs() < 1e-8);
+        assert!((fit.cost - 0.125).abs() < 1e-10);
+
+        assert!(Bounds::new(vec![1.0], vec![0.0]).is_err());
+    }
+}
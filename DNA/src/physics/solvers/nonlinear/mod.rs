@@ -1,10 +1,45 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: mod.rs | DNA/src/physics/solvers/nonlinear/mod.rs
-//! PURPOSE: Nonlinear module implementation
-//! MODIFIED: 2025-12-09
+//! PURPOSE: Module exports: problem, bisection, newton, optimization, least_squares
+//! MODIFIED: 2026-10-18
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
 
-// pub mod newton;       // TODO: Newton-Raphson iteration
-// pub mod bisection;    // TODO: Bracketing root finding
-// pub mod optimization; // TODO: Gradient descent, L-BFGS
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ CHOOSING A SOLVER                                                           │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ f(x) = 0, sign change known     brent (bisection if f is erratic)           │
+//! │ f(x) = 0, f' available          newton_scalar                               │
+//! │ F(x) = 0, square system         newton_system (DC operating points)         │
+//! │ min f(x), smooth, ∇f cheap      lbfgs (gradient_descent as a baseline)      │
+//! │ min f(x), noisy / no gradient   nelder_mead                                 │
+//! │ min ½‖r(p)‖², box bounds        levenberg_marquardt (fitting, pose graphs,  │
+//! │                                 loop-filter tuning)                         │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+/// Problem traits, options, errors
+pub mod problem;
+pub use problem::{
+    FnNonlinearSystem, FnObjective, NonlinearError, NonlinearSystem, Objective, SolverOptions,
+};
+
+/// Bracketing root finding (bisection, Brent)
+pub mod bisection;
+pub use bisection::{bisection, brent, ScalarRoot};
+
+/// Newton-Raphson with line search (scalar and systems)
+pub mod newton;
+pub use newton::{newton_scalar, newton_system, NewtonSolution};
+
+/// Gradient descent, L-BFGS, Nelder-Mead
+pub mod optimization;
+pub use optimization::{gradient_descent, lbfgs, nelder_mead, Minimum};
+
+/// Levenberg-Marquardt with bounds
+pub mod least_squares;
+pub use least_squares::{
+    levenberg_marquardt, Bounds, FnLeastSquares, LeastSquaresFit, LeastSquaresProblem,
+};
@@ -0,0 +1,284 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: newton.rs | DNA/src/physics/solvers/nonlinear/newton.rs
+//! PURPOSE: Damped Newton-Raphson for scalar equations and square systems
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! PURPOSE: Newton iteration with backtracking line search
+//!
+//! LAYER: DNA → PHYSICS → SOLVERS → NONLINEAR
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ ALGORITHM                                                                   │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ Direction:  J(x)·Δx = -F(x)    (LU; if J is singular, the regularized      │
+//! │             normal equations (JᵀJ + μI)·Δx = -JᵀF instead)                  │
+//! │ Merit:      φ(x) = ½‖F(x)‖²,   φ'(0) along Δx = (JᵀF)·Δx                    │
+//! │ Armijo:     accept λ when φ(x + λΔx) ≤ φ(x) + 10⁻⁴·λ·φ'(0),                 │
+//! │             else λ ← quadratic-model minimizer, clamped to [λ/10, λ/2]      │
+//! │                                                                             │
+//! │ Quadratic convergence near a simple root; the line search keeps far-away   │
+//! │ starts from diverging (e.g. diode exponentials in a DC operating point).   │
+//! │ Stops when ‖F‖∞ ≤ ftol or ‖λΔx‖∞ ≤ xtol·(1 + ‖x‖∞).                        │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! REFERENCE: Dennis & Schnabel, "Numerical Methods for Unconstrained
+//!            Optimization and Nonlinear Equations" (1996), §6.3
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use super::bisection::ScalarRoot;
+use super::problem::{
+    check_dimension, dot, norm_inf, NonlinearError, NonlinearSystem, SolverOptions,
+};
+use crate::math::dmatrix::DMatrix;
+
+const ARMIJO: f64 = 1e-4;
+const MIN_LAMBDA: f64 = 1e-10;
+
+/// Scalar Newton with backtracking on |f|
+pub fn newton_scalar(
+    mut f: impl FnMut(f64) -> f64,
+    mut df: impl FnMut(f64) -> f64,
+    x0: f64,
+    options: &SolverOptions,
+) -> Result<ScalarRoot, NonlinearError> {
+    options.check()?;
+    let mut x = x0;
+    let mut fx = f(x);
+    let mut evaluations = 1;
+    if !fx.is_finite() {
+        return Err(NonlinearError::NonFinite { iteration: 0 });
+    }
+    for iteration in 1..=options.max_iterations {
+        if fx.abs() <= options.ftol {
+            return Ok(ScalarRoot {
+                x,
+                value: fx,
+                iterations: iteration - 1,
+                evaluations,
+            });
+        }
+        let slope = df(x);
+        if slope == 0.0 || !slope.is_finite() {
+            return Err(NonlinearError::NoConvergence {
+                iterations: iteration,
+                residual: fx.abs(),
+            });
+        }
+        let step = -fx / slope;
+        let mut lambda = 1.0;
+        let (x_new, f_new) = loop {
+            let trial = x + lambda * step;
+            let f_trial = f(trial);
+            evaluations += 1;
+            if f_trial.is_finite() && f_trial.abs() <= (1.0 - ARMIJO * lambda) * fx.abs() {
+                break (trial, f_trial);
+            }
+            lambda *= 0.5;
+            if lambda < MIN_LAMBDA {
+                return Err(NonlinearError::NoConvergence {
+                    iterations: iteration,
+                    residual: fx.abs(),
+                });
+            }
+        };
+        let dx = (x_new - x).abs();
+        x = x_new;
+        fx = f_new;
+        if dx <= options.xtol * (1.0 + x.abs()) {
+            return Ok(ScalarRoot {
+                x,
+                value: fx,
+                iterations: iteration,
+                evaluations,
+            });
+        }
+    }
+    if fx.abs() <= options.ftol {
+        return Ok(ScalarRoot {
+            x,
+            value: fx,
+            iterations: options.max_iterations,
+            evaluations,
+        });
+    }
+    Err(NonlinearError::NoConvergence {
+        iterations: options.max_iterations,
+        residual: fx.abs(),
+    })
+}
+
+/// Root of a square system
+#[derive(Clone, Debug)]
+pub struct NewtonSolution {
+    pub x: Vec<f64>,
+    /// F(x)
+    pub residual: Vec<f64>,
+    /// ‖F(x)‖∞
+    pub residual_norm: f64,
+    pub iterations: usize,
+    pub evaluations: usize,
+    pub jacobian_evaluations: usize,
+}
+
+/// Damped Newton for F(x) = 0
+pub fn newton_system<S: NonlinearSystem + ?Sized>(
+    system: &S,
+    x0: &[f64],
+    options: &SolverOptions,
+) -> Result<NewtonSolution, NonlinearError> {
+    options.check()?;
+    let n = system.dimension();
+    check_dimension(n, x0.len())?;
+
+    let mut x = x0.to_vec();
+    let mut f = vec![0.0; n];
+    system.residual(&x, &mut f);
+    let mut evaluations = 1;
+    let mut jacobian_evaluations = 0;
+    if f.iter().any(|v| !v.is_finite()) {
+        return Err(NonlinearError::NonFinite { iteration: 0 });
+    }
+    let mut jacobian = DMatrix::zeros(n, n);
+    let mut trial = vec![0.0; n];
+    let mut f_trial = vec![0.0; n];
+
+    let done = |x: Vec<f64>, f: Vec<f64>, iterations, evaluations, jacobian_evaluations| {
+        Ok(NewtonSolution {
+            residual_norm: norm_inf(&f),
+            x,
+            residual: f,
+            iterations,
+            evaluations,
+            jacobian_evaluations,
+        })
+    };
+
+    for iteration in 1..=options.max_iterations {
+        if norm_inf(&f) <= options.ftol {
+            return done(x, f, iteration - 1, evaluations, jacobian_evaluations);
+        }
+        system.jacobian(&x, &mut jacobian);
+        jacobian_evaluations += 1;
+
+        let gradient = jacobian.transpose().mul_vec(&f);
+        let dx = newton_direction(&jacobian, &f, &gradient)
+            .ok_or(NonlinearError::NonFinite { iteration })?;
+        let phi = 0.5 * dot(&f, &f);
+        let slope = dot(&gradient, &dx);
+
+        // Backtracking with a quadratic model of φ(λ)
+        let mut lambda = 1.0;
+        loop {
+            for i in 0..n {
+                trial[i] = x[i] + lambda * dx[i];
+            }
+            system.residual(&trial, &mut f_trial);
+            evaluations += 1;
+            let phi_trial = 0.5 * dot(&f_trial, &f_trial);
+            if phi_trial.is_finite() && phi_trial <= phi + ARMIJO * lambda * slope {
+                break;
+            }
+            let model = if phi_trial.is_finite() {
+                -slope * lambda * lambda / (2.0 * (phi_trial - phi - slope * lambda))
+            } else {
+                0.0
+            };
+            lambda = model.clamp(0.1 * lambda, 0.5 * lambda);
+            if lambda < MIN_LAMBDA {
+                return Err(NonlinearError::NoConvergence {
+                    iterations: iteration,
+                    residual: norm_inf(&f),
+                });
+            }
+        }
+
+        let step = lambda * norm_inf(&dx);
+        std::mem::swap(&mut x, &mut trial);
+        std::mem::swap(&mut f, &mut f_trial);
+        if step <= options.xtol * (1.0 + norm_inf(&x)) {
+            return done(x, f, iteration, evaluations, jacobian_evaluations);
+        }
+    }
+    if norm_inf(&f) <= options.ftol {
+        return done(
+            x,
+            f,
+            options.max_iterations,
+            evaluations,
+            jacobian_evaluations,
+        );
+    }
+    Err(NonlinearError::NoConvergence {
+        iterations: options.max_iterations,
+        residual: norm_inf(&f),
+    })
+}
+
+/// Newton step, or a regularized Gauss-Newton step when J is singular
+fn newton_direction(jacobian: &DMatrix<f64>, f: &[f64], gradient: &[f64]) -> Option<Vec<f64>> {
+    let rhs: Vec<f64> = f.iter().map(|v| -v).collect();
+    if let Ok(dx) = jacobian.solve(&rhs) {
+        if dx.iter().all(|v| v.is_finite()) {
+            return Some(dx);
+        }
+    }
+    let mut normal = &jacobian.transpose() * jacobian;
+    let scale = normal.diagonal().into_iter().fold(1.0, f64::max);
+    let mu = 1e-8 * scale;
+    for i in 0..normal.nrows() {
+        normal[(i, i)] += mu;
+    }
+    let rhs: Vec<f64> = gradient.iter().map(|v| -v).collect();
+    normal.cholesky().ok()?.solve(&rhs).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physics::solvers::nonlinear::problem::FnNonlinearSystem;
+
+    #[test]
+    fn test_newton_scalar_with_line_search() {
+        let options = SolverOptions::default();
+        let r = newton_scalar(|x: f64| x * x - 2.0, |x| 2.0 * x, 1.0, &options).unwrap();
+        assert!((r.x - 2f64.sqrt()).abs() < 1e-12);
+        assert!(r.iterations <= 6);
+
+        // Plain Newton overshoots and diverges on atan from x0 = 3
+        let r = newton_scalar(|x: f64| x.atan(), |x| 1.0 / (1.0 + x * x), 3.0, &options).unwrap();
+        assert!(r.x.abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_newton_system() {
+        // Circle ∩ parabola: x² + y² = 4, y = x² - 1
+        let system = FnNonlinearSystem::new(2, |x: &[f64], f: &mut [f64]| {
+            f[0] = x[0] * x[0] + x[1] * x[1] - 4.0;
+            f[1] = x[1] - x[0] * x[0] + 1.0;
+        });
+        let sol = newton_system(&system, &[1.0, 1.0], &SolverOptions::default()).unwrap();
+        let y = (-1.0 + 13f64.sqrt()) / 2.0;
+        assert!((sol.x[1] - y).abs() < 1e-10);
+        assert!((sol.x[0] - (y + 1.0).sqrt()).abs() < 1e-10);
+        assert!(sol.residual_norm < 1e-10);
+
+        // Diode + resistor: (V - Vd)/R = Is·(exp(Vd/Vt) - 1), from a far start
+        let (vs, r, is, vt) = (5.0, 1000.0, 1e-14, 0.025852);
+        let diode = FnNonlinearSystem::new(1, move |x: &[f64], f: &mut [f64]| {
+            f[0] = (vs - x[0]) / r - is * ((x[0] / vt).exp() - 1.0);
+        });
+        let sol = newton_system(&diode, &[2.0], &SolverOptions::default()).unwrap();
+        let current = (vs - sol.x[0]) / r;
+        assert!(sol.x[0] > 0.6 && sol.x[0] < 0.75);
+        assert!((current - is * ((sol.x[0] / vt).exp() - 1.0)).abs() < 1e-12);
+
+        assert!(matches!(
+            newton_system(&system, &[1.0], &SolverOptions::default()),
+            Err(NonlinearError::DimensionMismatch { .. })
+        ));
+    }
+}
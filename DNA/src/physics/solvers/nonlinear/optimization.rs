@@ -0,0 +1,472 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: optimization.rs | DNA/src/physics/solvers/nonlinear/optimization.rs
+//! PURPOSE: Unconstrained minimization (gradient descent, L-BFGS, Nelder-Mead)
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! PURPOSE: Minimize a smooth (or merely continuous) objective f: ℝⁿ → ℝ
+//!
+//! LAYER: DNA → PHYSICS → SOLVERS → NONLINEAR
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ ALGORITHM                                                                   │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ Gradient descent  d = -∇f, Armijo backtracking; step grows 2× after each    │
+//! │                   accepted step. Linear rate, robust, needs only ∇f.        │
+//! │ L-BFGS(m)         d = -H·∇f from the last m (s, y) pairs (two-loop          │
+//! │                   recursion), Armijo backtracking from λ = 1; pairs with    │
+//! │                   sᵀy ≤ 0 are skipped. Superlinear, O(m·n) memory.          │
+//! │ Nelder-Mead       Derivative-free simplex (reflect / expand / contract /    │
+//! │                   shrink) with dimension-adaptive coefficients.             │
+//! │                                                                             │
+//! │ Converged when ‖∇f‖∞ ≤ gtol, |Δf| ≤ ftol·max(|f|, 1), or (Nelder-Mead)      │
+//! │ the simplex spread in x and f is within xtol / ftol.                        │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! Running out of iterations is not an error: `Minimum` carries the best
+//! point and `converged`; `into_result()` turns a miss into an error.
+//!
+//! REFERENCE: Nocedal & Wright, "Numerical Optimization" (2nd ed.), §3.1, §7.2
+//!            Gao & Han, "Implementing the Nelder-Mead simplex algorithm with
+//!            adaptive parameters", Comput. Optim. Appl. 51 (2012)
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use super::problem::{check_dimension, dot, norm_inf, NonlinearError, Objective, SolverOptions};
+use std::collections::VecDeque;
+
+const ARMIJO: f64 = 1e-4;
+
+/// Result of a minimization
+#[derive(Clone, Debug)]
+pub struct Minimum {
+    pub x: Vec<f64>,
+    /// f(x)
+    pub value: f64,
+    /// ‖∇f(x)‖∞ (None for derivative-free methods)
+    pub gradient_norm: Option<f64>,
+    pub iterations: usize,
+    /// Objective value calls (excluding those inside a default FD gradient)
+    pub evaluations: usize,
+    pub gradient_evaluations: usize,
+    pub converged: bool,
+}
+
+impl Minimum {
+    /// `Err(NoConvergence)` unless a stopping tolerance was met
+    pub fn into_result(self) -> Result<Self, NonlinearError> {
+        if self.converged {
+            Ok(self)
+        } else {
+            Err(NonlinearError::NoConvergence {
+                iterations: self.iterations,
+                residual: self.gradient_norm.unwrap_or(f64::NAN),
+            })
+        }
+    }
+}
+
+/// Counts value and gradient calls on behalf of the solvers
+struct Counted<'a, O: ?Sized> {
+    objective: &'a O,
+    evaluations: usize,
+    gradients: usize,
+}
+
+impl<O: Objective + ?Sized> Counted<'_, O> {
+    fn value(&mut self, x: &[f64]) -> f64 {
+        self.evaluations += 1;
+        self.objective.value(x)
+    }
+
+    fn gradient(&mut self, x: &[f64], g: &mut [f64]) {
+        self.gradients += 1;
+        self.objective.gradient(x, g);
+    }
+}
+
+/// Backtracking Armijo search along `direction`; returns (λ, x + λd, f)
+fn backtrack<O: Objective + ?Sized>(
+    counted: &mut Counted<'_, O>,
+    x: &[f64],
+    fx: f64,
+    slope: f64,
+    direction: &[f64],
+    initial: f64,
+) -> Option<(f64, Vec<f64>, f64)> {
+    let mut lambda = initial;
+    let mut trial = vec![0.0; x.len()];
+    for _ in 0..60 {
+        for i in 0..x.len() {
+            trial[i] = x[i] + lambda * direction[i];
+        }
+        let f_trial = counted.value(&trial);
+        if f_trial.is_finite() && f_trial <= fx + ARMIJO * lambda * slope {
+            return Some((lambda, trial, f_trial));
+        }
+        lambda *= 0.5;
+    }
+    None
+}
+
+fn start<O: Objective + ?Sized>(
+    objective: &O,
+    x0: &[f64],
+    options: &SolverOptions,
+) -> Result<(), NonlinearError> {
+    options.check()?;
+    check_dimension(objective.dimension(), x0.len())
+}
+
+fn f_converged(f_old: f64, f_new: f64, ftol: f64) -> bool {
+    (f_old - f_new).abs() <= ftol * f_old.abs().max(f_new.abs()).max(1.0)
+}
+
+/// Steepest descent with backtracking line search
+pub fn gradient_descent<O: Objective + ?Sized>(
+    objective: &O,
+    x0: &[f64],
+    options: &SolverOptions,
+) -> Result<Minimum, NonlinearError> {
+    start(objective, x0, options)?;
+    let n = x0.len();
+    let mut counted = Counted {
+        objective,
+        evaluations: 0,
+        gradients: 0,
+    };
+    let mut x = x0.to_vec();
+    let mut fx = counted.value(&x);
+    let mut g = vec![0.0; n];
+    counted.gradient(&x, &mut g);
+    if !fx.is_finite() {
+        return Err(NonlinearError::NonFinite { iteration: 0 });
+    }
+    let mut step = 1.0 / norm_inf(&g).max(1.0);
+    let mut converged = norm_inf(&g) <= options.gtol;
+    let mut iterations = 0;
+
+    while !converged && iterations < options.max_iterations {
+        iterations += 1;
+        let direction: Vec<f64> = g.iter().map(|v| -v).collect();
+        let slope = -dot(&g, &g);
+        let Some((lambda, x_new, f_new)) = backtrack(&mut counted, &x, fx, slope, &direction, step)
+        else {
+            break;
+        };
+        step = 2.0 * lambda;
+        let f_old = fx;
+        x = x_new;
+        fx = f_new;
+        counted.gradient(&x, &mut g);
+        converged = norm_inf(&g) <= options.gtol || f_converged(f_old, fx, options.ftol);
+    }
+    Ok(Minimum {
+        gradient_norm: Some(norm_inf(&g)),
+        x,
+        value: fx,
+        iterations,
+        evaluations: counted.evaluations,
+        gradient_evaluations: counted.gradients,
+        converged,
+    })
+}
+
+/// Limited-memory BFGS keeping the last `memory` correction pairs
+pub fn lbfgs<O: Objective + ?Sized>(
+    objective: &O,
+    x0: &[f64],
+    memory: usize,
+    options: &SolverOptions,
+) -> Result<Minimum, NonlinearError> {
+    start(objective, x0, options)?;
+    if memory == 0 {
+        return Err(NonlinearError::InvalidParameter(
+            "L-BFGS memory must be at least 1".to_string(),
+        ));
+    }
+    let n = x0.len();
+    let mut counted = Counted {
+        objective,
+        evaluations: 0,
+        gradients: 0,
+    };
+    let mut x = x0.to_vec();
+    let mut fx = counted.value(&x);
+    let mut g = vec![0.0; n];
+    counted.gradient(&x, &mut g);
+    if !fx.is_finite() {
+        return Err(NonlinearError::NonFinite { iteration: 0 });
+    }
+    // (s, y, 1 / sᵀy)
+    let mut pairs: VecDeque<(Vec<f64>, Vec<f64>, f64)> = VecDeque::with_capacity(memory);
+    let mut converged = norm_inf(&g) <= options.gtol;
+    let mut iterations = 0;
+    let mut g_new = vec![0.0; n];
+
+    while !converged && iterations < options.max_iterations {
+        iterations += 1;
+
+        // Two-loop recursion: d = -H·g
+        let mut q = g.clone();
+        let mut alphas = Vec::with_capacity(pairs.len());
+        for (s, y, rho) in pairs.iter().rev() {
+            let a = rho * dot(s, &q);
+            for (qi, yi) in q.iter_mut().zip(y) {
+                *qi -= a * yi;
+            }
+            alphas.push(a);
+        }
+        let gamma = pairs
+            .back()
+            .map_or(1.0 / norm_inf(&g).max(1.0), |(s, y, _)| {
+                dot(s, y) / dot(y, y)
+            });
+        for qi in q.iter_mut() {
+            *qi *= gamma;
+        }
+        for ((s, y, rho), a) in pairs.iter().zip(alphas.iter().rev()) {
+            let b = rho * dot(y, &q);
+            for (qi, si) in q.iter_mut().zip(s) {
+                *qi += (a - b) * si;
+            }
+        }
+        let mut direction: Vec<f64> = q.iter().map(|v| -v).collect();
+        let mut slope = dot(&g, &direction);
+        if slope >= 0.0 {
+            // Not a descent direction (round-off): restart from steepest descent
+            pairs.clear();
+            direction = g.iter().map(|v| -v / norm_inf(&g).max(1.0)).collect();
+            slope = dot(&g, &direction);
+        }
+
+        let Some((_, x_new, f_new)) = backtrack(&mut counted, &x, fx, slope, &direction, 1.0)
+        else {
+            break;
+        };
+        counted.gradient(&x_new, &mut g_new);
+        let s: Vec<f64> = x_new.iter().zip(&x).map(|(a, b)| a - b).collect();
+        let y: Vec<f64> = g_new.iter().zip(&g).map(|(a, b)| a - b).collect();
+        let sy = dot(&s, &y);
+        if sy > f64::EPSILON * dot(&y, &y) {
+            if pairs.len() == memory {
+                pairs.pop_front();
+            }
+            pairs.push_back((s, y, 1.0 / sy));
+        }
+
+        let f_old = fx;
+        x = x_new;
+        fx = f_new;
+        std::mem::swap(&mut g, &mut g_new);
+        converged = norm_inf(&g) <= options.gtol || f_converged(f_old, fx, options.ftol);
+    }
+    Ok(Minimum {
+        gradient_norm: Some(norm_inf(&g)),
+        x,
+        value: fx,
+        iterations,
+        evaluations: counted.evaluations,
+        gradient_evaluations: counted.gradients,
+        converged,
+    })
+}
+
+/// Nelder-Mead simplex search starting from x0 and x0 + initial_step·eᵢ
+pub fn nelder_mead<O: Objective + ?Sized>(
+    objective: &O,
+    x0: &[f64],
+    initial_step: f64,
+    options: &SolverOptions,
+) -> Result<Minimum, NonlinearError> {
+    start(objective, x0, options)?;
+    if !(initial_step != 0.0 && initial_step.is_finite()) {
+        return Err(NonlinearError::InvalidParameter(format!(
+            "initial simplex step {} must be finite and non-zero",
+            initial_step
+        )));
+    }
+    let n = x0.len();
+    let mut counted = Counted {
+        objective,
+        evaluations: 0,
+        gradients: 0,
+    };
+    if n == 0 {
+        return Ok(Minimum {
+            x: Vec::new(),
+            value: counted.value(x0),
+            gradient_norm: None,
+            iterations: 0,
+            evaluations: 1,
+            gradient_evaluations: 0,
+            converged: true,
+        });
+    }
+    let nf = n as f64;
+    let (reflect, expand, contract, shrink) =
+        (1.0, 1.0 + 2.0 / nf, 0.75 - 0.5 / nf, 1.0 - 1.0 / nf);
+
+    let mut simplex: Vec<(Vec<f64>, f64)> = (0..=n)
+        .map(|k| {
+            let mut x = x0.to_vec();
+            if k > 0 {
+                x[k - 1] += initial_step;
+            }
+            let f = counted.value(&x);
+            (x, f)
+        })
+        .collect();
+    if simplex.iter().any(|(_, f)| f.is_nan()) {
+        return Err(NonlinearError::NonFinite { iteration: 0 });
+    }
+
+    let along = |c: &[f64], w: &[f64], t: f64| -> Vec<f64> {
+        c.iter().zip(w).map(|(ci, wi)| ci + t * (ci - wi)).collect()
+    };
+
+    let mut converged = false;
+    let mut iterations = 0;
+    while iterations < options.max_iterations {
+        simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+        let (best, worst) = (&simplex[0], &simplex[n]);
+        let x_spread = simplex[1..]
+            .iter()
+            .flat_map(|(x, _)| x.iter().zip(&best.0).map(|(a, b)| (a - b).abs()))
+            .fold(0.0, f64::max);
+        if x_spread <= options.xtol * (1.0 + norm_inf(&best.0))
+            && (worst.1 - best.1).abs() <= options.ftol * best.1.abs().max(1.0)
+        {
+            converged = true;
+            break;
+        }
+        iterations += 1;
+
+        let mut centroid = vec![0.0; n];
+        for (x, _) in &simplex[..n] {
+            for (c, xi) in centroid.iter_mut().zip(x) {
+                *c += xi / nf;
+            }
+        }
+        let worst = simplex[n].clone();
+        let xr = along(&centroid, &worst.0, reflect);
+        let fr = counted.value(&xr);
+
+        if fr < simplex[0].1 {
+            let xe = along(&centroid, &worst.0, expand);
+            let fe = counted.value(&xe);
+            simplex[n] = if fe < fr { (xe, fe) } else { (xr, fr) };
+            continue;
+        }
+        if fr < simplex[n - 1].1 {
+            simplex[n] = (xr, fr);
+            continue;
+        }
+        // Outside (fr < f_worst) or inside contraction
+        let (xc, fc) = if fr < worst.1 {
+            let xc = along(&centroid, &worst.0, contract);
+            let fc = counted.value(&xc);
+            (xc, fc)
+        } else {
+            let xc = along(&centroid, &worst.0, -contract);
+            let fc = counted.value(&xc);
+            (xc, fc)
+        };
+        if fc < fr.min(worst.1) {
+            simplex[n] = (xc, fc);
+            continue;
+        }
+        // Shrink toward the best vertex
+        let best = simplex[0].0.clone();
+        for vertex in simplex.iter_mut().skip(1) {
+            for (xi, bi) in vertex.0.iter_mut().zip(&best) {
+                *xi = bi + shrink * (*xi - bi);
+            }
+            vertex.1 = counted.value(&vertex.0);
+        }
+    }
+    simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+    let (x, value) = simplex.swap_remove(0);
+    Ok(Minimum {
+        x,
+        value,
+        gradient_norm: None,
+        iterations,
+        evaluations: counted.evaluations,
+        gradient_evaluations: counted.gradients,
+        converged,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Rosenbrock with an analytic gradient
+    struct Rosenbrock;
+
+    impl Objective for Rosenbrock {
+        fn dimension(&self) -> usize {
+            2
+        }
+
+        fn value(&self, x: &[f64]) -> f64 {
+            (1.0 - x[0]).powi(2) + 100.0 * (x[1] - x[0] * x[0]).powi(2)
+        }
+
+        fn gradient(&self, x: &[f64], g: &mut [f64]) {
+            g[0] = -2.0 * (1.0 - x[0]) - 400.0 * x[0] * (x[1] - x[0] * x[0]);
+            g[1] = 200.0 * (x[1] - x[0] * x[0]);
+        }
+    }
+
+    #[test]
+    fn test_lbfgs_rosenbrock() {
+        let options = SolverOptions::default()
+            .with_tolerances(1e-12, 0.0)
+            .with_gtol(1e-9);
+        let min = lbfgs(&Rosenbrock, &[-1.2, 1.0], 6, &options).unwrap();
+        assert!(min.converged, "{:?}", min);
+        assert!((min.x[0] - 1.0).abs() < 1e-7 && (min.x[1] - 1.0).abs() < 1e-7);
+        assert!(min.iterations < 100);
+    }
+
+    #[test]
+    fn test_gradient_descent_quadratic_with_fd_gradient() {
+        // Mildly ill-conditioned bowl; FD gradient via FnObjective
+        let bowl = super::super::problem::FnObjective::new(3, |x: &[f64]| {
+            (x[0] - 1.0).powi(2) + 4.0 * (x[1] + 2.0).powi(2) + 0.5 * (x[2] - 0.5).powi(2)
+        });
+        let options = SolverOptions::default()
+            .with_gtol(1e-6)
+            .with_max_iterations(2000);
+        let min = gradient_descent(&bowl, &[0.0; 3], &options)
+            .unwrap()
+            .into_result()
+            .unwrap();
+        for (x, e) in min.x.iter().zip([1.0, -2.0, 0.5]) {
+            assert!((x - e).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_nelder_mead() {
+        let options = SolverOptions::default()
+            .with_tolerances(1e-9, 1e-14)
+            .with_max_iterations(2000);
+        let min = nelder_mead(&Rosenbrock, &[-1.2, 1.0], 0.5, &options).unwrap();
+        assert!(min.converged);
+        assert!((min.x[0] - 1.0).abs() < 1e-6 && (min.x[1] - 1.0).abs() < 1e-6);
+        assert!(min.gradient_norm.is_none());
+
+        let capped = SolverOptions::default().with_max_iterations(5);
+        let min = nelder_mead(&Rosenbrock, &[-1.2, 1.0], 0.5, &capped).unwrap();
+        assert!(!min.converged && min.iterations == 5);
+        assert!(matches!(
+            min.into_result(),
+            Err(NonlinearError::NoConvergence { iterations: 5, .. })
+        ));
+    }
+}
@@ -0,0 +1,272 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: problem.rs | DNA/src/physics/solvers/nonlinear/problem.rs
+//! PURPOSE: Problem traits, options and errors shared by the nonlinear solvers
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! PURPOSE: What the root finders and optimizers are asked to solve
+//!
+//! LAYER: DNA → PHYSICS → SOLVERS → NONLINEAR
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ DATA DEFINED                                                                │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ NonlinearSystem     F(x) = 0, F: ℝⁿ → ℝⁿ, Jacobian defaults to FD          │
+//! │ Objective           min f(x), f: ℝⁿ → ℝ, gradient defaults to central FD   │
+//! │ FnNonlinearSystem   Closure adapters (override the trait for analytic      │
+//! │ FnObjective           Jacobians / gradients)                               │
+//! │ SolverOptions       xtol / ftol / gtol / max_iterations                     │
+//! │ NonlinearError      Bad input, non-finite values, no convergence            │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use crate::math::dmatrix::DMatrix;
+
+/// Square nonlinear system F(x) = 0
+pub trait NonlinearSystem {
+    fn dimension(&self) -> usize;
+
+    /// f = F(x)
+    fn residual(&self, x: &[f64], f: &mut [f64]);
+
+    /// J = ∂F/∂x (forward differences unless overridden)
+    fn jacobian(&self, x: &[f64], jacobian: &mut DMatrix<f64>) {
+        let n = self.dimension();
+        let mut f0 = vec![0.0; n];
+        self.residual(x, &mut f0);
+        forward_difference(x, &f0, jacobian, |xp, fp| self.residual(xp, fp));
+    }
+}
+
+/// `NonlinearSystem` from a closure (finite-difference Jacobian)
+pub struct FnNonlinearSystem<F> {
+    dimension: usize,
+    f: F,
+}
+
+impl<F: Fn(&[f64], &mut [f64])> FnNonlinearSystem<F> {
+    pub fn new(dimension: usize, f: F) -> Self {
+        Self { dimension, f }
+    }
+}
+
+impl<F: Fn(&[f64], &mut [f64])> NonlinearSystem for FnNonlinearSystem<F> {
+    fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    fn residual(&self, x: &[f64], f: &mut [f64]) {
+        (self.f)(x, f)
+    }
+}
+
+/// Scalar objective for minimization
+pub trait Objective {
+    fn dimension(&self) -> usize;
+
+    fn value(&self, x: &[f64]) -> f64;
+
+    /// g = ∇f(x) (central differences unless overridden)
+    fn gradient(&self, x: &[f64], g: &mut [f64]) {
+        let mut xp = x.to_vec();
+        for i in 0..x.len() {
+            let h = f64::EPSILON.cbrt() * x[i].abs().max(1.0);
+            xp[i] = x[i] + h;
+            let fp = self.value(&xp);
+            xp[i] = x[i] - h;
+            let fm = self.value(&xp);
+            xp[i] = x[i];
+            g[i] = (fp - fm) / (2.0 * h);
+        }
+    }
+}
+
+/// `Objective` from a closure (finite-difference gradient)
+pub struct FnObjective<F> {
+    dimension: usize,
+    f: F,
+}
+
+impl<F: Fn(&[f64]) -> f64> FnObjective<F> {
+    pub fn new(dimension: usize, f: F) -> Self {
+        Self { dimension, f }
+    }
+}
+
+impl<F: Fn(&[f64]) -> f64> Objective for FnObjective<F> {
+    fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    fn value(&self, x: &[f64]) -> f64 {
+        (self.f)(x)
+    }
+}
+
+/// Forward-difference Jacobian of `eval` around (x, f0), m × n
+pub(crate) fn forward_difference(
+    x: &[f64],
+    f0: &[f64],
+    jacobian: &mut DMatrix<f64>,
+    mut eval: impl FnMut(&[f64], &mut [f64]),
+) {
+    let mut xp = x.to_vec();
+    let mut fp = vec![0.0; f0.len()];
+    for j in 0..x.len() {
+        let h = f64::EPSILON.sqrt() * x[j].abs().max(1.0);
+        xp[j] = x[j] + h;
+        eval(&xp, &mut fp);
+        xp[j] = x[j];
+        for i in 0..f0.len() {
+            jacobian[(i, j)] = (fp[i] - f0[i]) / h;
+        }
+    }
+}
+
+/// Convergence tolerances and iteration budget
+#[derive(Clone, Debug, PartialEq)]
+pub struct SolverOptions {
+    /// Step tolerance (relative to |x|, absolute near zero)
+    pub xtol: f64,
+    /// Function tolerance: |f| for roots, relative decrease for minimization
+    pub ftol: f64,
+    /// Gradient tolerance (∞-norm)
+    pub gtol: f64,
+    pub max_iterations: usize,
+}
+
+impl Default for SolverOptions {
+    fn default() -> Self {
+        Self {
+            xtol: 1e-10,
+            ftol: 1e-12,
+            gtol: 1e-8,
+            max_iterations: 200,
+        }
+    }
+}
+
+impl SolverOptions {
+    pub fn with_tolerances(mut self, xtol: f64, ftol: f64) -> Self {
+        self.xtol = xtol;
+        self.ftol = ftol;
+        self
+    }
+
+    pub fn with_gtol(mut self, gtol: f64) -> Self {
+        self.gtol = gtol;
+        self
+    }
+
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    pub(crate) fn check(&self) -> Result<(), NonlinearError> {
+        if !(self.xtol >= 0.0 && self.ftol >= 0.0 && self.gtol >= 0.0) {
+            return Err(NonlinearError::InvalidParameter(
+                "tolerances must be non-negative".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Nonlinear solver error
+#[derive(Debug, Clone, PartialEq)]
+pub enum NonlinearError {
+    DimensionMismatch {
+        expected: usize,
+        found: usize,
+    },
+    InvalidParameter(String),
+    /// f(a) and f(b) have the same sign
+    InvalidBracket {
+        a: f64,
+        b: f64,
+    },
+    /// NaN or infinity in a function value or iterate
+    NonFinite {
+        iteration: usize,
+    },
+    /// Iteration budget exhausted; `residual` is the best |F| or ‖∇f‖ reached
+    NoConvergence {
+        iterations: usize,
+        residual: f64,
+    },
+}
+
+impl std::fmt::Display for NonlinearError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NonlinearError::DimensionMismatch { expected, found } => {
+                write!(
+                    f,
+                    "Dimension mismatch: expected {}, found {}",
+                    expected, found
+                )
+            }
+            NonlinearError::InvalidParameter(msg) => write!(f, "Invalid parameter: {}", msg),
+            NonlinearError::InvalidBracket { a, b } => {
+                write!(f, "[{}, {}] does not bracket a root", a, b)
+            }
+            NonlinearError::NonFinite { iteration } => {
+                write!(f, "Non-finite value at iteration {}", iteration)
+            }
+            NonlinearError::NoConvergence {
+                iterations,
+                residual,
+            } => write!(
+                f,
+                "No convergence after {} iterations (residual {:.3e})",
+                iterations, residual
+            ),
+        }
+    }
+}
+
+impl std::error::Error for NonlinearError {}
+
+pub(crate) fn check_dimension(expected: usize, found: usize) -> Result<(), NonlinearError> {
+    if expected == found {
+        Ok(())
+    } else {
+        Err(NonlinearError::DimensionMismatch { expected, found })
+    }
+}
+
+pub(crate) fn norm_inf(v: &[f64]) -> f64 {
+    v.iter().fold(0.0, |m, x| m.max(x.abs()))
+}
+
+pub(crate) fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finite_difference_derivatives() {
+        let system = FnNonlinearSystem::new(2, |x: &[f64], f: &mut [f64]| {
+            f[0] = x[0] * x[0] + x[1];
+            f[1] = x[0] * x[1].sin();
+        });
+        let mut j = DMatrix::zeros(2, 2);
+        system.jacobian(&[1.5, 0.3], &mut j);
+        let exact = [3.0, 1.0, 0.3f64.sin(), 1.5 * 0.3f64.cos()];
+        for (a, b) in j.as_slice().iter().zip(&exact) {
+            assert!((a - b).abs() < 1e-6);
+        }
+
+        let objective = FnObjective::new(2, |x: &[f64]| x[0].powi(3) + x[0] * x[1]);
+        let mut g = [0.0; 2];
+        objective.gradient(&[2.0, -1.0], &mut g);
+        assert!((g[0] - 11.0).abs() < 1e-8 && (g[1] - 2.0).abs() < 1e-8);
+    }
+}
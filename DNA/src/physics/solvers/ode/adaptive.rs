@@ -0,0 +1,388 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: adaptive.rs | DNA/src/physics/solvers/ode/adaptive.rs
+//! PURPOSE: Dormand-Prince 5(4) adaptive integrator with dense output
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! PURPOSE: Adaptive RK45 (Dormand-Prince) with error control and dense output
+//!
+//! LAYER: DNA → PHYSICS → SOLVERS → ODE
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ ALGORITHM                                                                   │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ 7 stages, FSAL (k7 of one step is k1 of the next), 6 evals per step.        │
+//! │                                                                             │
+//! │   y_{n+1} = 5th-order solution, err = h·Σ eᵢkᵢ (5th - 4th order)            │
+//! │   ‖err‖ = RMS( errᵢ / (atol + rtol·max(|y_n|, |y_{n+1}|)) )                 │
+//! │                                                                             │
+//! │   accept if ‖err‖ ≤ 1;  h ← h·clamp(0.9·‖err‖^(-1/5), 0.2, 5)               │
+//! │                                                                             │
+//! │ Dense output: Hairer's 4th-order continuous extension, free per step.       │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! REFERENCE: Hairer, Nørsett & Wanner, "Solving ODEs I", §II.4-II.6 (DOPRI5)
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use super::system::{check_problem, DenseSegment, OdeError, OdeSystem, Solution, ZeroCrossing};
+
+/// Step-size control settings
+#[derive(Clone, Debug, PartialEq)]
+pub struct AdaptiveOptions {
+    /// Relative tolerance
+    pub rtol: f64,
+    /// Absolute tolerance
+    pub atol: f64,
+    /// First step (estimated from the problem when None)
+    pub initial_step: Option<f64>,
+    pub min_step: f64,
+    pub max_step: f64,
+    pub max_steps: usize,
+    /// Keep the continuous extension for `Solution::interpolate`
+    pub dense_output: bool,
+}
+
+impl Default for AdaptiveOptions {
+    fn default() -> Self {
+        Self {
+            rtol: 1e-6,
+            atol: 1e-9,
+            initial_step: None,
+            min_step: 1e-12,
+            max_step: f64::INFINITY,
+            max_steps: 100_000,
+            dense_output: true,
+        }
+    }
+}
+
+impl AdaptiveOptions {
+    pub fn with_tolerances(mut self, rtol: f64, atol: f64) -> Self {
+        self.rtol = rtol;
+        self.atol = atol;
+        self
+    }
+
+    pub fn with_max_step(mut self, max_step: f64) -> Self {
+        self.max_step = max_step;
+        self
+    }
+
+    pub fn with_initial_step(mut self, h: f64) -> Self {
+        self.initial_step = Some(h);
+        self
+    }
+
+    pub(crate) fn check(&self) -> Result<(), OdeError> {
+        if !(self.rtol > 0.0 && self.atol >= 0.0) {
+            return Err(OdeError::InvalidParameter(format!(
+                "tolerances rtol {} / atol {} must be positive",
+                self.rtol, self.atol
+            )));
+        }
+        if !(self.min_step > 0.0 && self.max_step >= self.min_step) {
+            return Err(OdeError::InvalidParameter(format!(
+                "step bounds [{}, {}] are invalid",
+                self.min_step, self.max_step
+            )));
+        }
+        Ok(())
+    }
+
+    /// Weighted RMS norm of `err` against the larger of `y0`, `y1`
+    pub(crate) fn error_norm(&self, err: &[f64], y0: &[f64], y1: &[f64]) -> f64 {
+        let sum: f64 = err
+            .iter()
+            .zip(y0.iter().zip(y1))
+            .map(|(e, (a, b))| {
+                let scale = self.atol + self.rtol * a.abs().max(b.abs());
+                (e / scale).powi(2)
+            })
+            .sum();
+        (sum / err.len().max(1) as f64).sqrt()
+    }
+
+    /// Starting step from the derivative scale (Hairer's heuristic)
+    pub(crate) fn initial_step<S: OdeSystem + ?Sized>(
+        &self,
+        system: &S,
+        t0: f64,
+        y0: &[f64],
+        f0: &[f64],
+        order: i32,
+    ) -> f64 {
+        if let Some(h) = self.initial_step {
+            return h;
+        }
+        let zeros = vec![0.0; y0.len()];
+        let d0 = self.error_norm(y0, y0, &zeros);
+        let d1 = self.error_norm(f0, y0, &zeros);
+        let h0 = if d0 < 1e-5 || d1 < 1e-5 {
+            1e-6
+        } else {
+            0.01 * d0 / d1
+        };
+        let y1: Vec<f64> = y0.iter().zip(f0).map(|(y, f)| y + h0 * f).collect();
+        let mut f1 = vec![0.0; y0.len()];
+        system.derivative(t0 + h0, &y1, &mut f1);
+        let diff: Vec<f64> = f1.iter().zip(f0).map(|(a, b)| a - b).collect();
+        let d2 = self.error_norm(&diff, y0, &zeros) / h0;
+        let h1 = if d1.max(d2) <= 1e-15 {
+            (h0 * 1e-3).max(1e-6)
+        } else {
+            (0.01 / d1.max(d2)).powf(1.0 / (order as f64 + 1.0))
+        };
+        (100.0 * h0).min(h1).clamp(self.min_step, self.max_step)
+    }
+}
+
+// Butcher tableau (Dormand & Prince 1980)
+const C2: f64 = 1.0 / 5.0;
+const C3: f64 = 3.0 / 10.0;
+const C4: f64 = 4.0 / 5.0;
+const C5: f64 = 8.0 / 9.0;
+const A21: f64 = 1.0 / 5.0;
+const A31: f64 = 3.0 / 40.0;
+const A32: f64 = 9.0 / 40.0;
+const A41: f64 = 44.0 / 45.0;
+const A42: f64 = -56.0 / 15.0;
+const A43: f64 = 32.0 / 9.0;
+const A51: f64 = 19372.0 / 6561.0;
+const A52: f64 = -25360.0 / 2187.0;
+const A53: f64 = 64448.0 / 6561.0;
+const A54: f64 = -212.0 / 729.0;
+const A61: f64 = 9017.0 / 3168.0;
+const A62: f64 = -355.0 / 33.0;
+const A63: f64 = 46732.0 / 5247.0;
+const A64: f64 = 49.0 / 176.0;
+const A65: f64 = -5103.0 / 18656.0;
+const A71: f64 = 35.0 / 384.0;
+const A73: f64 = 500.0 / 1113.0;
+const A74: f64 = 125.0 / 192.0;
+const A75: f64 = -2187.0 / 6784.0;
+const A76: f64 = 11.0 / 84.0;
+// 5th minus 4th order weights
+const E1: f64 = 71.0 / 57600.0;
+const E3: f64 = -71.0 / 16695.0;
+const E4: f64 = 71.0 / 1920.0;
+const E5: f64 = -17253.0 / 339200.0;
+const E6: f64 = 22.0 / 525.0;
+const E7: f64 = -1.0 / 40.0;
+// Continuous extension
+const D1: f64 = -12715105075.0 / 11282082432.0;
+const D3: f64 = 87487479700.0 / 32700410799.0;
+const D4: f64 = -10690763975.0 / 1880347072.0;
+const D5: f64 = 701980252875.0 / 199316789632.0;
+const D6: f64 = -1453857185.0 / 822651844.0;
+const D7: f64 = 69997945.0 / 29380423.0;
+
+/// Integrate with Dormand-Prince 5(4) under `options` tolerances
+pub fn solve_dopri5<S: OdeSystem + ?Sized>(
+    system: &S,
+    t_span: (f64, f64),
+    y0: &[f64],
+    options: &AdaptiveOptions,
+    events: &[ZeroCrossing],
+) -> Result<Solution, OdeError> {
+    check_problem(system.dimension(), t_span, y0)?;
+    options.check()?;
+
+    let (mut t, t_end) = t_span;
+    let n = y0.len();
+    let mut k1 = vec![0.0; n];
+    system.derivative(t, y0, &mut k1);
+    let mut solution = Solution::new(t, y0.to_vec(), k1.clone());
+    solution.stats.evaluations = 1;
+    if t == t_end {
+        return Ok(solution);
+    }
+
+    let mut h = options.initial_step(system, t, y0, &k1, 5);
+    solution.stats.evaluations += 1;
+    let (mut k2, mut k3, mut k4) = (vec![0.0; n], vec![0.0; n], vec![0.0; n]);
+    let (mut k5, mut k6, mut k7) = (vec![0.0; n], vec![0.0; n], vec![0.0; n]);
+    let mut y = y0.to_vec();
+    let mut tmp = vec![0.0; n];
+    let mut y1 = vec![0.0; n];
+    let mut err = vec![0.0; n];
+    let mut rejected_last = false;
+
+    loop {
+        if solution.stats.accepted_steps + solution.stats.rejected_steps >= options.max_steps {
+            return Err(OdeError::MaxStepsExceeded { t });
+        }
+        let last = t + h >= t_end;
+        if last {
+            h = t_end - t;
+        }
+
+        for i in 0..n {
+            tmp[i] = y[i] + h * A21 * k1[i];
+        }
+        system.derivative(t + C2 * h, &tmp, &mut k2);
+        for i in 0..n {
+            tmp[i] = y[i] + h * (A31 * k1[i] + A32 * k2[i]);
+        }
+        system.derivative(t + C3 * h, &tmp, &mut k3);
+        for i in 0..n {
+            tmp[i] = y[i] + h * (A41 * k1[i] + A42 * k2[i] + A43 * k3[i]);
+        }
+        system.derivative(t + C4 * h, &tmp, &mut k4);
+        for i in 0..n {
+            tmp[i] = y[i] + h * (A51 * k1[i] + A52 * k2[i] + A53 * k3[i] + A54 * k4[i]);
+        }
+        system.derivative(t + C5 * h, &tmp, &mut k5);
+        for i in 0..n {
+            tmp[i] =
+                y[i] + h * (A61 * k1[i] + A62 * k2[i] + A63 * k3[i] + A64 * k4[i] + A65 * k5[i]);
+        }
+        system.derivative(t + h, &tmp, &mut k6);
+        for i in 0..n {
+            y1[i] =
+                y[i] + h * (A71 * k1[i] + A73 * k3[i] + A74 * k4[i] + A75 * k5[i] + A76 * k6[i]);
+        }
+        system.derivative(t + h, &y1, &mut k7);
+        solution.stats.evaluations += 6;
+
+        for i in 0..n {
+            err[i] =
+                h * (E1 * k1[i] + E3 * k3[i] + E4 * k4[i] + E5 * k5[i] + E6 * k6[i] + E7 * k7[i]);
+        }
+        let norm = options.error_norm(&err, &y, &y1);
+        if !norm.is_finite() {
+            // Blow-up inside the step: retry much smaller before giving up
+            solution.stats.rejected_steps += 1;
+            h *= 0.1;
+            if h < options.min_step {
+                return Err(OdeError::NonFinite { t });
+            }
+            rejected_last = true;
+            continue;
+        }
+
+        let factor = if norm == 0.0 {
+            5.0
+        } else {
+            (0.9 * norm.powf(-0.2)).clamp(0.2, 5.0)
+        };
+        if norm <= 1.0 {
+            let dense = DenseSegment {
+                t0: t,
+                h,
+                rcont: dense_coefficients(h, &y, &y1, &k1, &k3, &k4, &k5, &k6, &k7),
+            };
+            let t1 = if last { t_end } else { t + h };
+            if solution.push_step(system, events, t1, y1.clone(), k7.clone(), Some(dense)) || last {
+                break;
+            }
+            t = t1;
+            y.copy_from_slice(&y1);
+            std::mem::swap(&mut k1, &mut k7);
+            let factor = if rejected_last {
+                factor.min(1.0)
+            } else {
+                factor
+            };
+            h = (h * factor).min(options.max_step);
+            rejected_last = false;
+        } else {
+            solution.stats.rejected_steps += 1;
+            h *= factor;
+            rejected_last = true;
+        }
+        if h < options.min_step {
+            return Err(OdeError::StepSizeTooSmall { t, h });
+        }
+    }
+
+    if !options.dense_output {
+        solution.discard_dense();
+    }
+    Ok(solution)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn dense_coefficients(
+    h: f64,
+    y0: &[f64],
+    y1: &[f64],
+    k1: &[f64],
+    k3: &[f64],
+    k4: &[f64],
+    k5: &[f64],
+    k6: &[f64],
+    k7: &[f64],
+) -> [Vec<f64>; 5] {
+    let n = y0.len();
+    let mut r = [
+        y0.to_vec(),
+        vec![0.0; n],
+        vec![0.0; n],
+        vec![0.0; n],
+        vec![0.0; n],
+    ];
+    for i in 0..n {
+        let ydiff = y1[i] - y0[i];
+        let bspl = h * k1[i] - ydiff;
+        r[1][i] = ydiff;
+        r[2][i] = bspl;
+        r[3][i] = ydiff - h * k7[i] - bspl;
+        r[4][i] = h * (D1 * k1[i] + D3 * k3[i] + D4 * k4[i] + D5 * k5[i] + D6 * k6[i] + D7 * k7[i]);
+    }
+    r
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physics::solvers::ode::system::FnSystem;
+
+    #[test]
+    fn test_tolerance_and_dense_output() {
+        // Harmonic oscillator: y = (cos t, -sin t)
+        let system = FnSystem::new(2, |_, y: &[f64], dy: &mut [f64]| {
+            dy[0] = y[1];
+            dy[1] = -y[0];
+        });
+        let options = AdaptiveOptions::default().with_tolerances(1e-9, 1e-12);
+        let sol = solve_dopri5(&system, (0.0, 10.0), &[1.0, 0.0], &options, &[]).unwrap();
+        assert_eq!(sol.final_time(), 10.0);
+        assert!((sol.final_state()[0] - 10f64.cos()).abs() < 1e-7);
+        assert!(sol.stats.accepted_steps < 400, "{:?}", sol.stats);
+
+        // Between step points the continuous extension keeps ~tolerance accuracy
+        for k in 0..50 {
+            let t = 0.193 * k as f64;
+            let y = sol.interpolate(t).unwrap();
+            assert!((y[0] - t.cos()).abs() < 1e-7, "t = {}", t);
+        }
+    }
+
+    #[test]
+    fn test_terminal_event() {
+        // Ball dropped from 10 m: hits the ground at t = √(2h/g)
+        let g = 9.81;
+        let system = FnSystem::new(2, move |_, y: &[f64], dy: &mut [f64]| {
+            dy[0] = y[1];
+            dy[1] = -g;
+        });
+        let ground = ZeroCrossing::new(|_, y: &[f64]| y[0]).falling().terminal();
+        let sol = solve_dopri5(
+            &system,
+            (0.0, 10.0),
+            &[10.0, 0.0],
+            &AdaptiveOptions::default(),
+            &[ground],
+        )
+        .unwrap();
+        assert!(sol.terminated);
+        let expected = (2.0 * 10.0 / g).sqrt();
+        assert_eq!(sol.events.len(), 1);
+        assert!((sol.events[0].t - expected).abs() < 1e-9);
+        assert!((sol.final_time() - expected).abs() < 1e-9);
+        assert!(sol.final_state()[0].abs() < 1e-8);
+    }
+}
@@ -0,0 +1,200 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: explicit.rs | DNA/src/physics/solvers/ode/explicit.rs
+//! PURPOSE: Fixed-step explicit Euler and RK4 over vector states
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! PURPOSE: Fixed-step explicit integration of any `OdeSystem`
+//!
+//! LAYER: DNA → PHYSICS → SOLVERS → ODE
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ METHODS                                                                     │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ Euler   y_{n+1} = y_n + h·k1                          1 eval/step, O(h)     │
+//! │ Rk4     y_{n+1} = y_n + h·(k1 + 2k2 + 2k3 + k4)/6     4 evals/step, O(h⁴)   │
+//! │                                                                             │
+//! │ The last step is shortened to land exactly on t_end.                        │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! The scalar `euler_step` / `rk4_step` remain for one-line use; these are the
+//! same schemes for state vectors, with events and interpolation.
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use super::system::{check_problem, OdeError, OdeSystem, Solution, ZeroCrossing};
+
+/// Fixed-step explicit scheme
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExplicitMethod {
+    Euler,
+    Rk4,
+}
+
+impl ExplicitMethod {
+    /// Global order of accuracy
+    pub fn order(&self) -> usize {
+        match self {
+            ExplicitMethod::Euler => 1,
+            ExplicitMethod::Rk4 => 4,
+        }
+    }
+
+    /// Derivative evaluations per step (beyond the FSAL k1)
+    fn stages(&self) -> usize {
+        match self {
+            ExplicitMethod::Euler => 0,
+            ExplicitMethod::Rk4 => 3,
+        }
+    }
+
+    /// Advance one step: `out = y(t + h)` given `dydt = f(t, y)`
+    pub fn step<S: OdeSystem + ?Sized>(
+        &self,
+        system: &S,
+        t: f64,
+        y: &[f64],
+        dydt: &[f64],
+        h: f64,
+        out: &mut [f64],
+    ) {
+        let n = y.len();
+        match self {
+            ExplicitMethod::Euler => {
+                for i in 0..n {
+                    out[i] = y[i] + h * dydt[i];
+                }
+            }
+            ExplicitMethod::Rk4 => {
+                let mut tmp = vec![0.0; n];
+                let mut k2 = vec![0.0; n];
+                let mut k3 = vec![0.0; n];
+                let mut k4 = vec![0.0; n];
+                for i in 0..n {
+                    tmp[i] = y[i] + 0.5 * h * dydt[i];
+                }
+                system.derivative(t + 0.5 * h, &tmp, &mut k2);
+                for i in 0..n {
+                    tmp[i] = y[i] + 0.5 * h * k2[i];
+                }
+                system.derivative(t + 0.5 * h, &tmp, &mut k3);
+                for i in 0..n {
+                    tmp[i] = y[i] + h * k3[i];
+                }
+                system.derivative(t + h, &tmp, &mut k4);
+                for i in 0..n {
+                    out[i] = y[i] + h * (dydt[i] + 2.0 * k2[i] + 2.0 * k3[i] + k4[i]) / 6.0;
+                }
+            }
+        }
+    }
+}
+
+/// Integrate from `t_span.0` to `t_span.1` with constant step `h`
+pub fn solve_fixed<S: OdeSystem + ?Sized>(
+    system: &S,
+    method: ExplicitMethod,
+    t_span: (f64, f64),
+    y0: &[f64],
+    h: f64,
+    events: &[ZeroCrossing],
+) -> Result<Solution, OdeError> {
+    check_problem(system.dimension(), t_span, y0)?;
+    if !(h > 0.0 && h.is_finite()) {
+        return Err(OdeError::InvalidParameter(format!(
+            "step size {} must be positive",
+            h
+        )));
+    }
+
+    let (mut t, t_end) = t_span;
+    let n = y0.len();
+    let mut dydt = vec![0.0; n];
+    system.derivative(t, y0, &mut dydt);
+    let mut solution = Solution::new(t, y0.to_vec(), dydt);
+    solution.stats.evaluations = 1;
+
+    let mut next = vec![0.0; n];
+    while t < t_end {
+        let step = h.min(t_end - t);
+        // Avoid a sliver step from round-off in the accumulated time
+        let step = if t_end - (t + step) < 1e-12 * h {
+            t_end - t
+        } else {
+            step
+        };
+        let i = solution.t.len() - 1;
+        method.step(
+            system,
+            t,
+            &solution.y[i],
+            &solution.dydt[i],
+            step,
+            &mut next,
+        );
+        let t1 = t + step;
+        if next.iter().any(|v| !v.is_finite()) {
+            return Err(OdeError::NonFinite { t: t1 });
+        }
+        let mut d1 = vec![0.0; n];
+        system.derivative(t1, &next, &mut d1);
+        solution.stats.evaluations += method.stages() + 1;
+        if solution.push_step(system, events, t1, next.clone(), d1, None) {
+            break;
+        }
+        t = t1;
+    }
+    Ok(solution)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physics::solvers::ode::system::FnSystem;
+
+    fn oscillator() -> FnSystem<impl Fn(f64, &[f64], &mut [f64])> {
+        FnSystem::new(2, |_, y: &[f64], dy: &mut [f64]| {
+            dy[0] = y[1];
+            dy[1] = -y[0];
+        })
+    }
+
+    #[test]
+    fn test_convergence_order() {
+        let system = oscillator();
+        for method in [ExplicitMethod::Euler, ExplicitMethod::Rk4] {
+            let error = |h: f64| {
+                let sol = solve_fixed(&system, method, (0.0, 1.0), &[1.0, 0.0], h, &[]).unwrap();
+                (sol.final_state()[0] - 1f64.cos()).abs()
+            };
+            let ratio = error(0.02) / error(0.01);
+            let observed = ratio.log2();
+            assert!(
+                (observed - method.order() as f64).abs() < 0.2,
+                "{:?}: observed order {}",
+                method,
+                observed
+            );
+        }
+    }
+
+    #[test]
+    fn test_lands_on_end_and_interpolates() {
+        let system = oscillator();
+        let sol = solve_fixed(
+            &system,
+            ExplicitMethod::Rk4,
+            (0.0, 1.05),
+            &[1.0, 0.0],
+            0.1,
+            &[],
+        )
+        .unwrap();
+        assert_eq!(sol.final_time(), 1.05);
+        assert_eq!(sol.t.len(), 12);
+        let mid = sol.interpolate(0.55).unwrap();
+        assert!((mid[0] - 0.55f64.cos()).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,227 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: implicit.rs | DNA/src/physics/solvers/ode/implicit.rs
+//! PURPOSE: Linearly implicit Rosenbrock 2(3) integrator for stiff systems
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! PURPOSE: Adaptive L-stable Rosenbrock method (the ode23s pair)
+//!
+//! LAYER: DNA → PHYSICS → SOLVERS → ODE
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ ALGORITHM                                                                   │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ d = 1/(2 + √2),  W = I - h·d·J,  T = ∂f/∂t                                  │
+//! │                                                                             │
+//! │   k1 = W⁻¹ (f(t, y) + h·d·T)                                                │
+//! │   k2 = W⁻¹ (f(t + h/2, y + h·k1/2) - k1) + k1                               │
+//! │   y_{n+1} = y + h·k2                                            (2nd order) │
+//! │   k3 = W⁻¹ (f(t+h, y_{n+1}) - e32·(k2 - f₁) - 2(k1 - f₀) + h·d·T)           │
+//! │   err = h·(k1 - 2k2 + k3)/6                         e32 = 6 + √2            │
+//! │                                                                             │
+//! │ One Jacobian + one LU per step, no Newton iteration. L-stable, so step      │
+//! │ size follows accuracy, not the fastest decaying mode.                       │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! REFERENCE: Shampine & Reichelt, "The MATLAB ODE Suite", SIAM J. Sci.
+//!            Comput. 18(1), 1997
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use super::adaptive::AdaptiveOptions;
+use super::system::{check_problem, OdeError, OdeSystem, Solution, ZeroCrossing};
+use crate::math::dmatrix::DMatrix;
+
+/// Integrate a (possibly stiff) system with the Rosenbrock 2(3) pair
+///
+/// Uses `OdeSystem::jacobian` every step; override it for large systems.
+pub fn solve_rosenbrock<S: OdeSystem + ?Sized>(
+    system: &S,
+    t_span: (f64, f64),
+    y0: &[f64],
+    options: &AdaptiveOptions,
+    events: &[ZeroCrossing],
+) -> Result<Solution, OdeError> {
+    check_problem(system.dimension(), t_span, y0)?;
+    options.check()?;
+
+    let d = 1.0 / (2.0 + std::f64::consts::SQRT_2);
+    let e32 = 6.0 + std::f64::consts::SQRT_2;
+
+    let (mut t, t_end) = t_span;
+    let n = y0.len();
+    let mut f0 = vec![0.0; n];
+    system.derivative(t, y0, &mut f0);
+    let mut solution = Solution::new(t, y0.to_vec(), f0.clone());
+    solution.stats.evaluations = 1;
+    if t == t_end {
+        return Ok(solution);
+    }
+
+    let mut h = options.initial_step(system, t, y0, &f0, 2);
+    solution.stats.evaluations += 1;
+    let mut y = y0.to_vec();
+    let mut jac = vec![0.0; n * n];
+    let mut w = DMatrix::zeros(n, n);
+    let mut dfdt = vec![0.0; n];
+    let (mut k1, mut k2, mut k3) = (vec![0.0; n], vec![0.0; n], vec![0.0; n]);
+    let (mut f1, mut f2) = (vec![0.0; n], vec![0.0; n]);
+    let mut tmp = vec![0.0; n];
+    let mut y1 = vec![0.0; n];
+    let mut err = vec![0.0; n];
+    let mut jacobian_current = false;
+    let mut rejected_last = false;
+
+    loop {
+        if solution.stats.accepted_steps + solution.stats.rejected_steps >= options.max_steps {
+            return Err(OdeError::MaxStepsExceeded { t });
+        }
+        let last = t + h >= t_end;
+        if last {
+            h = t_end - t;
+        }
+
+        if !jacobian_current {
+            system.jacobian(t, &y, &mut jac);
+            let delta = f64::EPSILON.sqrt() * t.abs().max(1.0);
+            system.derivative(t + delta, &y, &mut tmp);
+            for i in 0..n {
+                dfdt[i] = (tmp[i] - f0[i]) / delta;
+            }
+            solution.stats.jacobians += 1;
+            solution.stats.evaluations += 1;
+            jacobian_current = true;
+        }
+
+        for i in 0..n {
+            for j in 0..n {
+                let identity = if i == j { 1.0 } else { 0.0 };
+                w[(i, j)] = identity - h * d * jac[i * n + j];
+            }
+        }
+        solution.stats.factorizations += 1;
+        let Ok(lu) = w.lu_with_tolerance(0.0) else {
+            solution.stats.rejected_steps += 1;
+            h *= 0.5;
+            if h < options.min_step {
+                return Err(OdeError::SingularMatrix { t });
+            }
+            continue;
+        };
+
+        for i in 0..n {
+            k1[i] = f0[i] + h * d * dfdt[i];
+        }
+        lu.solve_in_place(&mut k1);
+        for i in 0..n {
+            tmp[i] = y[i] + 0.5 * h * k1[i];
+        }
+        system.derivative(t + 0.5 * h, &tmp, &mut f1);
+        for i in 0..n {
+            k2[i] = f1[i] - k1[i];
+        }
+        lu.solve_in_place(&mut k2);
+        for i in 0..n {
+            k2[i] += k1[i];
+            y1[i] = y[i] + h * k2[i];
+        }
+        system.derivative(t + h, &y1, &mut f2);
+        for i in 0..n {
+            k3[i] = f2[i] - e32 * (k2[i] - f1[i]) - 2.0 * (k1[i] - f0[i]) + h * d * dfdt[i];
+        }
+        lu.solve_in_place(&mut k3);
+        solution.stats.evaluations += 2;
+
+        for i in 0..n {
+            err[i] = h / 6.0 * (k1[i] - 2.0 * k2[i] + k3[i]);
+        }
+        let norm = options.error_norm(&err, &y, &y1);
+        if !norm.is_finite() {
+            solution.stats.rejected_steps += 1;
+            h *= 0.1;
+            if h < options.min_step {
+                return Err(OdeError::NonFinite { t });
+            }
+            rejected_last = true;
+            continue;
+        }
+
+        let factor = if norm == 0.0 {
+            5.0
+        } else {
+            (0.9 * norm.powf(-1.0 / 3.0)).clamp(0.2, 5.0)
+        };
+        if norm <= 1.0 {
+            let t1 = if last { t_end } else { t + h };
+            if solution.push_step(system, events, t1, y1.clone(), f2.clone(), None) || last {
+                break;
+            }
+            t = t1;
+            y.copy_from_slice(&y1);
+            f0.copy_from_slice(&f2);
+            jacobian_current = false;
+            let factor = if rejected_last {
+                factor.min(1.0)
+            } else {
+                factor
+            };
+            h = (h * factor).min(options.max_step);
+            rejected_last = false;
+        } else {
+            solution.stats.rejected_steps += 1;
+            h *= factor;
+            rejected_last = true;
+        }
+        if h < options.min_step {
+            return Err(OdeError::StepSizeTooSmall { t, h });
+        }
+    }
+    Ok(solution)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physics::solvers::ode::adaptive::solve_dopri5;
+    use crate::physics::solvers::ode::system::FnSystem;
+
+    #[test]
+    fn test_stiff_relaxation() {
+        // y' = -λ(y - cos t): after the transient y ≈ cos t, λ = 10⁴
+        let lambda = 1e4;
+        let system = FnSystem::new(1, move |t, y: &[f64], dy: &mut [f64]| {
+            dy[0] = -lambda * (y[0] - t.cos());
+        });
+        let options = AdaptiveOptions::default().with_tolerances(1e-5, 1e-8);
+        let stiff = solve_rosenbrock(&system, (0.0, 2.0), &[0.0], &options, &[]).unwrap();
+        assert!((stiff.final_state()[0] - 2f64.cos()).abs() < 1e-3);
+
+        // The explicit solver is held to h ~ 3/λ by stability
+        let explicit = solve_dopri5(&system, (0.0, 2.0), &[0.0], &options, &[]).unwrap();
+        assert!(
+            stiff.stats.accepted_steps * 10 < explicit.stats.accepted_steps,
+            "rosenbrock {:?} vs dopri5 {:?}",
+            stiff.stats,
+            explicit.stats
+        );
+    }
+
+    #[test]
+    fn test_robertson_conserves_mass() {
+        // Robertson kinetics: classic stiff test, y₁ + y₂ + y₃ = 1
+        let system = FnSystem::new(3, |_, y: &[f64], dy: &mut [f64]| {
+            dy[0] = -0.04 * y[0] + 1e4 * y[1] * y[2];
+            dy[1] = 0.04 * y[0] - 1e4 * y[1] * y[2] - 3e7 * y[1] * y[1];
+            dy[2] = 3e7 * y[1] * y[1];
+        });
+        let options = AdaptiveOptions::default().with_tolerances(1e-4, 1e-8);
+        let sol = solve_rosenbrock(&system, (0.0, 40.0), &[1.0, 0.0, 0.0], &options, &[]).unwrap();
+        let y = sol.final_state();
+        assert!((y.iter().sum::<f64>() - 1.0).abs() < 1e-6);
+        // Reference y₁(40) = 0.7158
+        assert!((y[0] - 0.7158).abs() < 2e-3, "{:?}", y);
+        assert!(sol.stats.accepted_steps < 500);
+    }
+}
@@ -1,10 +1,23 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: mod.rs | DNA/src/physics/solvers/ode/mod.rs
-//! PURPOSE: Module exports: euler, rk4, verlet
-//! MODIFIED: 2025-12-09
+//! PURPOSE: Module exports: euler, rk4, verlet, system, explicit, adaptive, implicit, symplectic
+//! MODIFIED: 2026-10-18
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
 
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ CHOOSING AN INTEGRATOR                                                      │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ solve_dopri5      general non-stiff problems (adaptive, dense output)       │
+//! │ solve_rosenbrock  stiff problems (chemistry, circuits, fine grids)          │
+//! │ solve_symplectic  conservative q'' = a(q) over long times (orbits, MD)      │
+//! │ solve_fixed       fixed-step Euler / RK4 (real-time loops, teaching)        │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! All take an `OdeSystem` (or `SeparableSystem`) and a slice of
+//! `ZeroCrossing` events, and return a `Solution`.
+
 /// Forward Euler (1st order)
 pub mod euler;
 pub use euler::euler_step;
@@ -17,5 +30,25 @@ pub use rk4::rk4_step;
 pub mod verlet;
 pub use verlet::verlet_step;
 
-// pub mod leapfrog;  // TODO: Leapfrog (symplectic)
-// pub mod adaptive;  // TODO: RK45, Dormand-Prince
+/// System traits, solutions, events
+pub mod system;
+pub use system::{
+    Crossing, EventHit, FirstOrderForm, FnSeparable, FnSystem, OdeError, OdeSystem,
+    SeparableSystem, Solution, SolverStats, ZeroCrossing,
+};
+
+/// Fixed-step Euler / RK4 over vector states
+pub mod explicit;
+pub use explicit::{solve_fixed, ExplicitMethod};
+
+/// RK45, Dormand-Prince
+pub mod adaptive;
+pub use adaptive::{solve_dopri5, AdaptiveOptions};
+
+/// Rosenbrock 2(3) for stiff systems
+pub mod implicit;
+pub use implicit::solve_rosenbrock;
+
+/// Leapfrog, Yoshida (symplectic)
+pub mod symplectic;
+pub use symplectic::{solve_symplectic, SymplecticMethod};
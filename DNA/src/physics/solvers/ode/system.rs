@@ -0,0 +1,498 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: system.rs | DNA/src/physics/solvers/ode/system.rs
+//! PURPOSE: ODE system traits, solutions, dense interpolation and zero-crossing events
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! PURPOSE: Shared vocabulary for the vector-state integrators
+//!
+//! LAYER: DNA → PHYSICS → SOLVERS → ODE
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ TYPES                                                                       │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ OdeSystem        dy/dt = f(t, y), y ∈ ℝⁿ (Jacobian by finite differences    │
+//! │                  unless overridden)                                         │
+//! │ SeparableSystem  q'' = a(t, q) for the symplectic integrators               │
+//! │ ZeroCrossing     event function g(t, y); located where g changes sign       │
+//! │ Solution         accepted steps + events + interpolation between steps      │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! Events are located on the step interpolant (Dormand-Prince dense output,
+//! cubic Hermite otherwise) with the Illinois variant of regula falsi.
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+/// First-order system dy/dt = f(t, y)
+pub trait OdeSystem {
+    /// Length of the state vector
+    fn dimension(&self) -> usize;
+
+    /// Write f(t, y) into `dydt`
+    fn derivative(&self, t: f64, y: &[f64], dydt: &mut [f64]);
+
+    /// Write ∂f/∂y (row-major n×n, `jac[i * n + j] = ∂fᵢ/∂yⱼ`) into `jac`
+    ///
+    /// The default uses forward differences; override it when the Jacobian
+    /// is known analytically (stiff solvers call this every step).
+    fn jacobian(&self, t: f64, y: &[f64], jac: &mut [f64]) {
+        let n = self.dimension();
+        let mut f0 = vec![0.0; n];
+        let mut f1 = vec![0.0; n];
+        let mut yp = y.to_vec();
+        self.derivative(t, y, &mut f0);
+        for j in 0..n {
+            let delta = f64::EPSILON.sqrt() * y[j].abs().max(1.0);
+            yp[j] = y[j] + delta;
+            self.derivative(t, &yp, &mut f1);
+            yp[j] = y[j];
+            for i in 0..n {
+                jac[i * n + j] = (f1[i] - f0[i]) / delta;
+            }
+        }
+    }
+}
+
+/// `OdeSystem` from a closure `|t, y, dydt| ...`
+pub struct FnSystem<F> {
+    dimension: usize,
+    f: F,
+}
+
+impl<F: Fn(f64, &[f64], &mut [f64])> FnSystem<F> {
+    pub fn new(dimension: usize, f: F) -> Self {
+        Self { dimension, f }
+    }
+}
+
+impl<F: Fn(f64, &[f64], &mut [f64])> OdeSystem for FnSystem<F> {
+    fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    fn derivative(&self, t: f64, y: &[f64], dydt: &mut [f64]) {
+        (self.f)(t, y, dydt)
+    }
+}
+
+/// Second-order system q'' = a(t, q) (velocity-independent forces)
+pub trait SeparableSystem {
+    /// Number of coordinates in q
+    fn dimension(&self) -> usize;
+
+    /// Write a(t, q) into `accel`
+    fn acceleration(&self, t: f64, q: &[f64], accel: &mut [f64]);
+}
+
+/// `SeparableSystem` from a closure `|t, q, accel| ...`
+pub struct FnSeparable<F> {
+    dimension: usize,
+    f: F,
+}
+
+impl<F: Fn(f64, &[f64], &mut [f64])> FnSeparable<F> {
+    pub fn new(dimension: usize, f: F) -> Self {
+        Self { dimension, f }
+    }
+}
+
+impl<F: Fn(f64, &[f64], &mut [f64])> SeparableSystem for FnSeparable<F> {
+    fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    fn acceleration(&self, t: f64, q: &[f64], accel: &mut [f64]) {
+        (self.f)(t, q, accel)
+    }
+}
+
+/// A separable system viewed as the first-order system y = [q, v], y' = [v, a(q)]
+pub struct FirstOrderForm<'a, S: ?Sized>(pub &'a S);
+
+impl<S: SeparableSystem + ?Sized> OdeSystem for FirstOrderForm<'_, S> {
+    fn dimension(&self) -> usize {
+        2 * self.0.dimension()
+    }
+
+    fn derivative(&self, t: f64, y: &[f64], dydt: &mut [f64]) {
+        let n = self.0.dimension();
+        dydt[..n].copy_from_slice(&y[n..]);
+        self.0.acceleration(t, &y[..n], &mut dydt[n..]);
+    }
+}
+
+/// ODE solver error
+#[derive(Debug, Clone, PartialEq)]
+pub enum OdeError {
+    /// State length does not match `dimension()`
+    DimensionMismatch { expected: usize, found: usize },
+    /// Step size, span or tolerance out of range
+    InvalidParameter(String),
+    /// Adaptive step fell below the minimum step size
+    StepSizeTooSmall { t: f64, h: f64 },
+    /// Step budget exhausted before reaching the end of the span
+    MaxStepsExceeded { t: f64 },
+    /// Iteration matrix I - hγJ could not be factorized
+    SingularMatrix { t: f64 },
+    /// State became NaN or infinite
+    NonFinite { t: f64 },
+}
+
+impl std::fmt::Display for OdeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OdeError::DimensionMismatch { expected, found } => {
+                write!(
+                    f,
+                    "State has {} components, system expects {}",
+                    found, expected
+                )
+            }
+            OdeError::InvalidParameter(msg) => write!(f, "Invalid parameter: {}", msg),
+            OdeError::StepSizeTooSmall { t, h } => {
+                write!(f, "Step size {:e} below minimum at t = {}", h, t)
+            }
+            OdeError::MaxStepsExceeded { t } => {
+                write!(f, "Maximum step count reached at t = {}", t)
+            }
+            OdeError::SingularMatrix { t } => write!(f, "Singular iteration matrix at t = {}", t),
+            OdeError::NonFinite { t } => write!(f, "Non-finite state at t = {}", t),
+        }
+    }
+}
+
+impl std::error::Error for OdeError {}
+
+/// Which sign changes of an event function count
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Crossing {
+    #[default]
+    Either,
+    /// g goes from negative to non-negative
+    Rising,
+    /// g goes from positive to non-positive
+    Falling,
+}
+
+/// Boxed event function g(t, y)
+type EventFn<'a> = Box<dyn Fn(f64, &[f64]) -> f64 + 'a>;
+
+/// Event function g(t, y); an event fires where g crosses zero
+pub struct ZeroCrossing<'a> {
+    function: EventFn<'a>,
+    pub direction: Crossing,
+    /// Stop integration at the first occurrence
+    pub terminal: bool,
+}
+
+impl<'a> ZeroCrossing<'a> {
+    pub fn new(function: impl Fn(f64, &[f64]) -> f64 + 'a) -> Self {
+        Self {
+            function: Box::new(function),
+            direction: Crossing::Either,
+            terminal: false,
+        }
+    }
+
+    pub fn rising(mut self) -> Self {
+        self.direction = Crossing::Rising;
+        self
+    }
+
+    pub fn falling(mut self) -> Self {
+        self.direction = Crossing::Falling;
+        self
+    }
+
+    pub fn terminal(mut self) -> Self {
+        self.terminal = true;
+        self
+    }
+
+    pub fn evaluate(&self, t: f64, y: &[f64]) -> f64 {
+        (self.function)(t, y)
+    }
+
+    fn fires(&self, g0: f64, g1: f64) -> bool {
+        let rising = g0 < 0.0 && g1 >= 0.0;
+        let falling = g0 > 0.0 && g1 <= 0.0;
+        match self.direction {
+            Crossing::Either => rising || falling,
+            Crossing::Rising => rising,
+            Crossing::Falling => falling,
+        }
+    }
+}
+
+/// A located event
+#[derive(Clone, Debug, PartialEq)]
+pub struct EventHit {
+    /// Index into the event slice passed to the solver
+    pub event: usize,
+    pub t: f64,
+    pub y: Vec<f64>,
+}
+
+/// Work counters
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SolverStats {
+    pub accepted_steps: usize,
+    pub rejected_steps: usize,
+    /// Calls to `derivative` / `acceleration`
+    pub evaluations: usize,
+    pub jacobians: usize,
+    pub factorizations: usize,
+}
+
+/// Dormand-Prince continuous extension on one step (Hairer's coefficients)
+#[derive(Clone, Debug)]
+pub(crate) struct DenseSegment {
+    pub t0: f64,
+    pub h: f64,
+    pub rcont: [Vec<f64>; 5],
+}
+
+impl DenseSegment {
+    fn evaluate(&self, t: f64) -> Vec<f64> {
+        let s = (t - self.t0) / self.h;
+        let s1 = 1.0 - s;
+        let [r1, r2, r3, r4, r5] = &self.rcont;
+        (0..r1.len())
+            .map(|i| r1[i] + s * (r2[i] + s1 * (r3[i] + s * (r4[i] + s1 * r5[i]))))
+            .collect()
+    }
+}
+
+/// Integration result: accepted step points, events and an interpolant
+#[derive(Clone, Debug, Default)]
+pub struct Solution {
+    pub t: Vec<f64>,
+    pub y: Vec<Vec<f64>>,
+    /// dy/dt at each point (used for Hermite interpolation)
+    pub dydt: Vec<Vec<f64>>,
+    pub events: Vec<EventHit>,
+    /// True when a terminal event ended the integration early
+    pub terminated: bool,
+    pub stats: SolverStats,
+    dense: Vec<DenseSegment>,
+}
+
+impl Solution {
+    pub(crate) fn new(t0: f64, y0: Vec<f64>, dydt0: Vec<f64>) -> Self {
+        Self {
+            t: vec![t0],
+            y: vec![y0],
+            dydt: vec![dydt0],
+            ..Default::default()
+        }
+    }
+
+    pub fn final_time(&self) -> f64 {
+        *self.t.last().expect("solution has an initial point")
+    }
+
+    pub fn final_state(&self) -> &[f64] {
+        self.y.last().expect("solution has an initial point")
+    }
+
+    /// State at `t` inside the integrated span
+    ///
+    /// Uses the Dormand-Prince continuous extension when the solver produced
+    /// one, cubic Hermite interpolation between step points otherwise.
+    pub fn interpolate(&self, t: f64) -> Option<Vec<f64>> {
+        let (first, last) = (self.t[0], self.final_time());
+        if !(first..=last).contains(&t) {
+            return None;
+        }
+        if self.t.len() == 1 {
+            return Some(self.y[0].clone());
+        }
+        let i = self
+            .t
+            .partition_point(|&ti| ti <= t)
+            .clamp(1, self.t.len() - 1)
+            - 1;
+        Some(match self.dense.get(i) {
+            Some(segment) => segment.evaluate(t),
+            None => hermite(
+                (self.t[i], &self.y[i], &self.dydt[i]),
+                (self.t[i + 1], &self.y[i + 1], &self.dydt[i + 1]),
+                t,
+            ),
+        })
+    }
+
+    /// Record an accepted step from the last point to `t1`, locating events
+    /// on the step interpolant. Returns true if a terminal event fired (the
+    /// step is then truncated at the event).
+    pub(crate) fn push_step<S: OdeSystem + ?Sized>(
+        &mut self,
+        system: &S,
+        events: &[ZeroCrossing],
+        t1: f64,
+        y1: Vec<f64>,
+        dydt1: Vec<f64>,
+        dense: Option<DenseSegment>,
+    ) -> bool {
+        let i = self.t.len() - 1;
+        let t0 = self.t[i];
+        self.stats.accepted_steps += 1;
+
+        let interp = |t: f64| -> Vec<f64> {
+            match &dense {
+                Some(segment) => segment.evaluate(t),
+                None => hermite((t0, &self.y[i], &self.dydt[i]), (t1, &y1, &dydt1), t),
+            }
+        };
+
+        let mut hits: Vec<EventHit> = Vec::new();
+        for (k, event) in events.iter().enumerate() {
+            let g0 = event.evaluate(t0, &self.y[i]);
+            let g1 = event.evaluate(t1, &y1);
+            if event.fires(g0, g1) {
+                let te = illinois(|t| event.evaluate(t, &interp(t)), t0, g0, t1, g1);
+                hits.push(EventHit {
+                    event: k,
+                    t: te,
+                    y: interp(te),
+                });
+            }
+        }
+        hits.sort_by(|a, b| a.t.total_cmp(&b.t));
+
+        let stop = hits.iter().position(|h| events[h.event].terminal);
+        if let Some(stop) = stop {
+            let te = hits[stop].t;
+            let ye = hits[stop].y.clone();
+            hits.truncate(stop + 1);
+            let mut de = vec![0.0; ye.len()];
+            system.derivative(te, &ye, &mut de);
+            self.stats.evaluations += 1;
+            self.events.extend(hits);
+            self.dense.extend(dense);
+            self.t.push(te);
+            self.y.push(ye);
+            self.dydt.push(de);
+            self.terminated = true;
+            return true;
+        }
+
+        self.events.extend(hits);
+        self.dense.extend(dense);
+        self.t.push(t1);
+        self.y.push(y1);
+        self.dydt.push(dydt1);
+        false
+    }
+
+    /// Drop dense segments when the caller did not ask for them
+    pub(crate) fn discard_dense(&mut self) {
+        self.dense.clear();
+    }
+}
+
+/// Cubic Hermite interpolation between (t0, y0, y0') and (t1, y1, y1')
+fn hermite(a: (f64, &[f64], &[f64]), b: (f64, &[f64], &[f64]), t: f64) -> Vec<f64> {
+    let (t0, y0, d0) = a;
+    let (t1, y1, d1) = b;
+    let h = t1 - t0;
+    if h == 0.0 {
+        return y1.to_vec();
+    }
+    let s = (t - t0) / h;
+    let h00 = (1.0 + 2.0 * s) * (1.0 - s) * (1.0 - s);
+    let h10 = s * (1.0 - s) * (1.0 - s);
+    let h01 = s * s * (3.0 - 2.0 * s);
+    let h11 = s * s * (s - 1.0);
+    (0..y0.len())
+        .map(|i| h00 * y0[i] + h10 * h * d0[i] + h01 * y1[i] + h11 * h * d1[i])
+        .collect()
+}
+
+/// Root of g on [a, b] given a sign change (Illinois regula falsi)
+fn illinois(g: impl Fn(f64) -> f64, mut a: f64, mut ga: f64, mut b: f64, mut gb: f64) -> f64 {
+    if gb == 0.0 {
+        return b;
+    }
+    let tol = 4.0 * f64::EPSILON * a.abs().max(b.abs()).max(1.0);
+    let mut side = 0;
+    for _ in 0..100 {
+        if (b - a).abs() <= tol {
+            break;
+        }
+        let c = (a * gb - b * ga) / (gb - ga);
+        let gc = g(c);
+        if gc == 0.0 {
+            return c;
+        }
+        if gc.signum() == gb.signum() {
+            b = c;
+            gb = gc;
+            if side == -1 {
+                ga /= 2.0;
+            }
+            side = -1;
+        } else {
+            a = c;
+            ga = gc;
+            if side == 1 {
+                gb /= 2.0;
+            }
+            side = 1;
+        }
+    }
+    b
+}
+
+/// Check span, step and initial state against the system
+pub(crate) fn check_problem(
+    dimension: usize,
+    t_span: (f64, f64),
+    y0: &[f64],
+) -> Result<(), OdeError> {
+    if y0.len() != dimension {
+        return Err(OdeError::DimensionMismatch {
+            expected: dimension,
+            found: y0.len(),
+        });
+    }
+    let (t0, t1) = t_span;
+    if !t0.is_finite() || !t1.is_finite() || t1 < t0 {
+        return Err(OdeError::InvalidParameter(format!(
+            "time span ({}, {}) must be finite and increasing",
+            t0, t1
+        )));
+    }
+    if y0.iter().any(|v| !v.is_finite()) {
+        return Err(OdeError::NonFinite { t: t0 });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finite_difference_jacobian() {
+        // f = (y0·y1, sin y0)
+        let system = FnSystem::new(2, |_, y: &[f64], dy: &mut [f64]| {
+            dy[0] = y[0] * y[1];
+            dy[1] = y[0].sin();
+        });
+        let mut jac = [0.0; 4];
+        system.jacobian(0.0, &[0.5, 2.0], &mut jac);
+        let expected = [2.0, 0.5, 0.5f64.cos(), 0.0];
+        for (a, b) in jac.iter().zip(expected) {
+            assert!((a - b).abs() < 1e-6, "{:?}", jac);
+        }
+    }
+
+    #[test]
+    fn test_illinois_root() {
+        let root = illinois(|t| t * t - 2.0, 0.0, -2.0, 2.0, 2.0);
+        assert!((root - 2f64.sqrt()).abs() < 1e-12);
+    }
+}
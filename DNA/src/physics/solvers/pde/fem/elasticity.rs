@@ -0,0 +1,765 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: elasticity.rs | DNA/src/physics/solvers/pde/fem/elasticity.rs
+//! PURPOSE: Small-strain linear elasticity (plane stress / strain, 3D solids)
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! PURPOSE: Displacement-based linear elastic FEM with stress recovery
+//!
+//! LAYER: DNA → PHYSICS → SOLVERS → PDE → FEM
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ PHYSICS                                                                     │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ Equilibrium   ∇·σ + b = 0,   σ = D·ε,   ε = ½(∇u + ∇uᵀ)                     │
+//! │ Voigt order   2D [xx, yy, xy],  3D [xx, yy, zz, xy, yz, zx]  (γ = 2ε)       │
+//! │                                                                             │
+//! │ Solid         λ = Eν/((1+ν)(1-2ν)),  μ = E/(2(1+ν))                         │
+//! │               D = λ·1⊗1 + 2μ on normals, μ on shears                        │
+//! │ Plane strain  3D D restricted to x, y;  σzz = ν(σxx + σyy)                  │
+//! │ Plane stress  D = E/(1-ν²)·[1 ν 0; ν 1 0; 0 0 (1-ν)/2],  σzz = 0            │
+//! │                                                                             │
+//! │ von Mises     √(½[(σxx-σyy)² + (σyy-σzz)² + (σzz-σxx)²]                     │
+//! │                  + 3(σxy² + σyz² + σzx²))                                   │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ ALGORITHM                                                                   │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ Node a, component i → dof a·d + i  (d = mesh dimension)                     │
+//! │ Kₑ = ∫ Bᵀ D B t dΩ         (t = thickness in 2D, 1 in 3D)                   │
+//! │ F  = ∫ b N t dΩ + ∫ t̄ N t dΓ + point loads                                  │
+//! │ Solve K·u = F (Dirichlet elimination, CG), then                             │
+//! │   element stress   σ = D·B·uₑ at the centroid                               │
+//! │   nodal stress     average of σ evaluated at the node in every element      │
+//! │   reactions        K·u - F on the fixed dofs                                │
+//! │   strain energy    ½uᵀK·u                                                   │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ DATA DEFINED                                                                │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ ElasticMaterial    E, ν, ρ (+ steel, aluminium, southern pine)              │
+//! │ Formulation        PlaneStress, PlaneStrain (with thickness), Solid         │
+//! │ Stress             Symmetric tensor components, von Mises                   │
+//! │ ElasticityProblem  Per-element material, load vector, fixed dofs           │
+//! │ ElasticSolution    Displacements, stresses, reactions, strain energy        │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use super::element::reference_nodes;
+use super::{
+    apply_dirichlet, check_facets, check_nodes, check_positive, facet_integration,
+    integration_points, map_point, preconditioner, solve_spd, FemError,
+};
+use crate::physics::solvers::linear::{CsrMatrix, IterativeOptions, TripletMatrix};
+use crate::world::grid::unstructured::{Facet, UnstructuredMesh};
+use glam::DVec3;
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// MATERIAL
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// Isotropic linear elastic material
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ElasticMaterial {
+    /// Young's modulus E (Pa)
+    pub youngs_modulus: f64,
+    /// Poisson's ratio ν
+    pub poissons_ratio: f64,
+    /// Density ρ (kg/m³), used by gravity loads
+    pub density: f64,
+}
+
+impl ElasticMaterial {
+    pub fn new(youngs_modulus: f64, poissons_ratio: f64, density: f64) -> Result<Self, FemError> {
+        check_positive("Young's modulus", youngs_modulus)?;
+        check_positive("density", density)?;
+        if !(poissons_ratio > -1.0 && poissons_ratio < 0.5) {
+            return Err(FemError::InvalidParameter(format!(
+                "Poisson's ratio {} must be in (-1, 0.5)",
+                poissons_ratio
+            )));
+        }
+        Ok(Self {
+            youngs_modulus,
+            poissons_ratio,
+            density,
+        })
+    }
+
+    /// Structural steel
+    pub fn steel() -> Self {
+        Self {
+            youngs_modulus: 200e9,
+            poissons_ratio: 0.3,
+            density: 7850.0,
+        }
+    }
+
+    /// 6061-T6 aluminium
+    pub fn aluminium() -> Self {
+        Self {
+            youngs_modulus: 69e9,
+            poissons_ratio: 0.33,
+            density: 2700.0,
+        }
+    }
+
+    /// Southern pine along the grain (E = 1.4e6 psi), treated as isotropic
+    pub fn southern_pine() -> Self {
+        Self {
+            youngs_modulus: 9.65e9,
+            poissons_ratio: 0.3,
+            density: 550.0,
+        }
+    }
+
+    /// Shear modulus G = E / (2(1 + ν))
+    pub fn shear_modulus(&self) -> f64 {
+        self.youngs_modulus / (2.0 * (1.0 + self.poissons_ratio))
+    }
+
+    /// Lamé's first parameter λ
+    pub fn lame_lambda(&self) -> f64 {
+        let nu = self.poissons_ratio;
+        self.youngs_modulus * nu / ((1.0 + nu) * (1.0 - 2.0 * nu))
+    }
+}
+
+/// Kinematic assumption tying the mesh dimension to a stress state
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Formulation {
+    /// Thin plate loaded in plane: σzz = 0
+    PlaneStress { thickness: f64 },
+    /// Long body: εzz = 0
+    PlaneStrain { thickness: f64 },
+    /// Full 3D
+    Solid,
+}
+
+impl Formulation {
+    pub fn dimension(self) -> usize {
+        match self {
+            Formulation::Solid => 3,
+            _ => 2,
+        }
+    }
+
+    /// Out-of-plane thickness (1 for solids)
+    pub fn thickness(self) -> f64 {
+        match self {
+            Formulation::PlaneStress { thickness } | Formulation::PlaneStrain { thickness } => {
+                thickness
+            }
+            Formulation::Solid => 1.0,
+        }
+    }
+
+    /// Elasticity matrix D in Voigt order (upper-left 3 × 3 in 2D)
+    fn elasticity_matrix(self, material: &ElasticMaterial) -> [[f64; 6]; 6] {
+        let mut d = [[0.0; 6]; 6];
+        let (e, nu) = (material.youngs_modulus, material.poissons_ratio);
+        let (lambda, mu) = (material.lame_lambda(), material.shear_modulus());
+        match self {
+            Formulation::PlaneStress { .. } => {
+                let c = e / (1.0 - nu * nu);
+                d[0][0] = c;
+                d[1][1] = c;
+                d[0][1] = c * nu;
+                d[1][0] = c * nu;
+                d[2][2] = mu;
+            }
+            Formulation::PlaneStrain { .. } => {
+                d[0][0] = lambda + 2.0 * mu;
+                d[1][1] = lambda + 2.0 * mu;
+                d[0][1] = lambda;
+                d[1][0] = lambda;
+                d[2][2] = mu;
+            }
+            Formulation::Solid => {
+                for (i, row) in d.iter_mut().enumerate().take(3) {
+                    for (j, value) in row.iter_mut().enumerate().take(3) {
+                        *value = if i == j { lambda + 2.0 * mu } else { lambda };
+                    }
+                }
+                for (i, row) in d.iter_mut().enumerate().skip(3) {
+                    row[i] = mu;
+                }
+            }
+        }
+        d
+    }
+}
+
+/// Strain-displacement column for component `i` of a node with gradient `g`
+fn strain_column(dimension: usize, g: DVec3, i: usize) -> [f64; 6] {
+    match (dimension, i) {
+        (2, 0) => [g.x, 0.0, g.y, 0.0, 0.0, 0.0],
+        (2, _) => [0.0, g.y, g.x, 0.0, 0.0, 0.0],
+        (_, 0) => [g.x, 0.0, 0.0, g.y, 0.0, g.z],
+        (_, 1) => [0.0, g.y, 0.0, g.x, g.z, 0.0],
+        _ => [0.0, 0.0, g.z, 0.0, g.y, g.x],
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// STRESS
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// Symmetric Cauchy stress (Pa)
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Stress {
+    pub xx: f64,
+    pub yy: f64,
+    pub zz: f64,
+    pub xy: f64,
+    pub yz: f64,
+    pub zx: f64,
+}
+
+impl Stress {
+    pub fn von_mises(&self) -> f64 {
+        let normal =
+            (self.xx - self.yy).powi(2) + (self.yy - self.zz).powi(2) + (self.zz - self.xx).powi(2);
+        let shear = self.xy * self.xy + self.yz * self.yz + self.zx * self.zx;
+        (0.5 * normal + 3.0 * shear).sqrt()
+    }
+
+    /// Mean normal stress (σxx + σyy + σzz) / 3
+    pub fn hydrostatic(&self) -> f64 {
+        (self.xx + self.yy + self.zz) / 3.0
+    }
+
+    fn scaled(self, s: f64) -> Self {
+        Self {
+            xx: self.xx * s,
+            yy: self.yy * s,
+            zz: self.zz * s,
+            xy: self.xy * s,
+            yz: self.yz * s,
+            zx: self.zx * s,
+        }
+    }
+
+    fn added(self, o: Self) -> Self {
+        Self {
+            xx: self.xx + o.xx,
+            yy: self.yy + o.yy,
+            zz: self.zz + o.zz,
+            xy: self.xy + o.xy,
+            yz: self.yz + o.yz,
+            zx: self.zx + o.zx,
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// PROBLEM
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// Linear elastic boundary value problem on a mesh
+#[derive(Clone, Debug)]
+pub struct ElasticityProblem<'a> {
+    mesh: &'a UnstructuredMesh,
+    formulation: Formulation,
+    materials: Vec<ElasticMaterial>,
+    load: Vec<f64>,
+    fixed: Vec<Option<f64>>,
+    linear_options: IterativeOptions,
+}
+
+impl<'a> ElasticityProblem<'a> {
+    /// Uniform material; the formulation must match the mesh dimension
+    pub fn new(
+        mesh: &'a UnstructuredMesh,
+        formulation: Formulation,
+        material: ElasticMaterial,
+    ) -> Result<Self, FemError> {
+        if formulation.dimension() != mesh.dimension() {
+            return Err(FemError::InvalidParameter(format!(
+                "{:?} needs a {}D mesh, got {}D",
+                formulation,
+                formulation.dimension(),
+                mesh.dimension()
+            )));
+        }
+        check_positive("thickness", formulation.thickness())?;
+        let dofs = mesh.dimension() * mesh.node_count();
+        Ok(Self {
+            mesh,
+            formulation,
+            materials: vec![material; mesh.element_count()],
+            load: vec![0.0; dofs],
+            fixed: vec![None; dofs],
+            linear_options: IterativeOptions::default()
+                .with_tolerances(1e-10, 0.0)
+                .with_max_iterations((10 * dofs).max(1000)),
+        })
+    }
+
+    pub fn with_linear_options(mut self, options: IterativeOptions) -> Self {
+        self.linear_options = options;
+        self
+    }
+
+    pub fn mesh(&self) -> &UnstructuredMesh {
+        self.mesh
+    }
+
+    pub fn formulation(&self) -> Formulation {
+        self.formulation
+    }
+
+    fn dimension(&self) -> usize {
+        self.mesh.dimension()
+    }
+
+    /// Material of the given elements
+    pub fn set_material(
+        &mut self,
+        elements: &[usize],
+        material: ElasticMaterial,
+    ) -> Result<(), FemError> {
+        if let Some(&e) = elements.iter().find(|&&e| e >= self.materials.len()) {
+            return Err(FemError::UnknownElement(e));
+        }
+        for &e in elements {
+            self.materials[e] = material;
+        }
+        Ok(())
+    }
+
+    /// Prescribe displacement component `component` (0 = x, 1 = y, 2 = z)
+    pub fn fix(&mut self, nodes: &[usize], component: usize, value: f64) -> Result<(), FemError> {
+        check_nodes(self.mesh, nodes)?;
+        if component >= self.dimension() {
+            return Err(FemError::InvalidParameter(format!(
+                "component {} of a {}D problem",
+                component,
+                self.dimension()
+            )));
+        }
+        let d = self.dimension();
+        for &n in nodes {
+            self.fixed[n * d + component] = Some(value);
+        }
+        Ok(())
+    }
+
+    /// Prescribe every displacement component (z ignored in 2D)
+    pub fn fix_displacement(&mut self, nodes: &[usize], u: DVec3) -> Result<(), FemError> {
+        for i in 0..self.dimension() {
+            self.fix(nodes, i, u[i])?;
+        }
+        Ok(())
+    }
+
+    /// Fully fixed support
+    pub fn clamp(&mut self, nodes: &[usize]) -> Result<(), FemError> {
+        self.fix_displacement(nodes, DVec3::ZERO)
+    }
+
+    /// Body force density b(x) (N/m³) over the whole mesh
+    pub fn add_body_force(&mut self, b: impl Fn(DVec3) -> DVec3) {
+        self.add_element_force(|_, x| b(x));
+    }
+
+    /// Self weight ρ·g with each element's density
+    pub fn add_gravity(&mut self, g: DVec3) {
+        let materials = self.materials.clone();
+        self.add_element_force(|e, _| g * materials[e].density);
+    }
+
+    fn add_element_force(&mut self, b: impl Fn(usize, DVec3) -> DVec3) {
+        let d = self.dimension();
+        let thickness = self.formulation.thickness();
+        for e in 0..self.mesh.element_count() {
+            let element = self.mesh.element(e);
+            for (point, weight) in integration_points(self.mesh, e, thickness) {
+                let force = b(e, point.position) * weight;
+                for (&node, n) in element.iter().zip(&point.values) {
+                    for i in 0..d {
+                        self.load[node * d + i] += force[i] * n;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Surface traction t̄(x) (Pa) on facets
+    pub fn add_traction(
+        &mut self,
+        facets: &[Facet],
+        traction: impl Fn(DVec3) -> DVec3,
+    ) -> Result<(), FemError> {
+        self.add_facet_force(facets, |point| traction(point.position))
+    }
+
+    /// Pressure p (Pa) pushing against the outward normal of facets
+    pub fn add_pressure(&mut self, facets: &[Facet], pressure: f64) -> Result<(), FemError> {
+        self.add_facet_force(facets, |point| -point.normal * pressure)
+    }
+
+    fn add_facet_force(
+        &mut self,
+        facets: &[Facet],
+        traction: impl Fn(&super::element::FacetPoint) -> DVec3,
+    ) -> Result<(), FemError> {
+        check_facets(self.mesh, facets)?;
+        let d = self.dimension();
+        for facet in facets {
+            for point in facet_integration(self.mesh, facet, self.formulation.thickness()) {
+                let force = traction(&point) * point.weight;
+                for (&node, n) in facet.nodes.iter().zip(&point.values) {
+                    for i in 0..d {
+                        self.load[node * d + i] += force[i] * n;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Concentrated force at a node (N; z ignored in 2D)
+    pub fn add_point_load(&mut self, node: usize, force: DVec3) -> Result<(), FemError> {
+        check_nodes(self.mesh, &[node])?;
+        let d = self.dimension();
+        for i in 0..d {
+            self.load[node * d + i] += force[i];
+        }
+        Ok(())
+    }
+
+    pub fn load(&self) -> &[f64] {
+        &self.load
+    }
+
+    /// Global stiffness matrix K, no boundary conditions
+    pub fn stiffness(&self) -> CsrMatrix {
+        let d = self.dimension();
+        let voigt = if d == 2 { 3 } else { 6 };
+        let per = self.mesh.kind().node_count() * d;
+        let dofs = d * self.mesh.node_count();
+        let mut triplets =
+            TripletMatrix::with_capacity(dofs, dofs, self.mesh.element_count() * per * per);
+        let mut local = vec![0.0; per * per];
+        let mut columns = vec![[0.0; 6]; per];
+        for e in 0..self.mesh.element_count() {
+            let dm = self.formulation.elasticity_matrix(&self.materials[e]);
+            local.iter_mut().for_each(|v| *v = 0.0);
+            for (point, weight) in integration_points(self.mesh, e, self.formulation.thickness()) {
+                for (k, column) in columns.iter_mut().enumerate() {
+                    *column = strain_column(d, point.gradients[k / d], k % d);
+                }
+                for (q, bq) in columns.iter().enumerate() {
+                    // D·B column q
+                    let mut db = [0.0; 6];
+                    for (r, value) in db.iter_mut().enumerate().take(voigt) {
+                        *value = (0..voigt).map(|s| dm[r][s] * bq[s]).sum();
+                    }
+                    for (p, bp) in columns.iter().enumerate() {
+                        let v: f64 = (0..voigt).map(|r| bp[r] * db[r]).sum();
+                        local[p * per + q] += weight * v;
+                    }
+                }
+            }
+            let element = self.mesh.element(e);
+            for p in 0..per {
+                for q in 0..per {
+                    triplets.push(
+                        element[p / d] * d + p % d,
+                        element[q / d] * d + q % d,
+                        local[p * per + q],
+                    );
+                }
+            }
+        }
+        triplets.to_csr()
+    }
+
+    /// Solve K·u = F and recover stresses
+    pub fn solve(&self) -> Result<ElasticSolution, FemError> {
+        if self.fixed.iter().all(|f| f.is_none()) {
+            return Err(FemError::Unconstrained);
+        }
+        let k = self.stiffness();
+        let mut a = k.clone();
+        let mut b = self.load.clone();
+        apply_dirichlet(&mut a, &mut b, &self.fixed);
+        let x0: Vec<f64> = self.fixed.iter().map(|f| f.unwrap_or(0.0)).collect();
+        let precond = preconditioner(&a)?;
+        let u = solve_spd(&a, &b, &x0, precond.as_ref(), &self.linear_options)?;
+
+        let d = self.dimension();
+        let to_vec = |v: &[f64], n: usize| {
+            let mut out = DVec3::ZERO;
+            for i in 0..d {
+                out[i] = v[n * d + i];
+            }
+            out
+        };
+        let ku = k.mul_vec(&u);
+        let mut reactions = vec![DVec3::ZERO; self.mesh.node_count()];
+        for (dof, f) in self.fixed.iter().enumerate() {
+            if f.is_some() {
+                reactions[dof / d][dof % d] = ku[dof] - self.load[dof];
+            }
+        }
+        let strain_energy = 0.5 * u.iter().zip(&ku).map(|(a, b)| a * b).sum::<f64>();
+
+        let kind = self.mesh.kind();
+        let centroid = if d == 2 {
+            DVec3::new(1.0, 1.0, 0.0) / 3.0
+        } else {
+            DVec3::splat(0.25)
+        };
+        let corners = reference_nodes(kind);
+        let mut element_stress = Vec::with_capacity(self.mesh.element_count());
+        let mut nodal_sum = vec![Stress::default(); self.mesh.node_count()];
+        let mut nodal_count = vec![0usize; self.mesh.node_count()];
+        for e in 0..self.mesh.element_count() {
+            element_stress.push(self.stress_at(e, centroid, &u));
+            for (&node, xi) in self.mesh.element(e).iter().zip(&corners) {
+                nodal_sum[node] = nodal_sum[node].added(self.stress_at(e, *xi, &u));
+                nodal_count[node] += 1;
+            }
+        }
+        let nodal_stress = nodal_sum
+            .into_iter()
+            .zip(nodal_count)
+            .map(|(s, c)| s.scaled(1.0 / c.max(1) as f64))
+            .collect();
+
+        Ok(ElasticSolution {
+            displacements: (0..self.mesh.node_count()).map(|n| to_vec(&u, n)).collect(),
+            element_stress,
+            nodal_stress,
+            reactions,
+            strain_energy,
+        })
+    }
+
+    /// σ = D·B·uₑ at reference point `xi` of an element
+    fn stress_at(&self, element: usize, xi: DVec3, u: &[f64]) -> Stress {
+        let d = self.dimension();
+        let point = map_point(self.mesh.kind(), &self.mesh.element_nodes(element), xi);
+        let mut strain = [0.0; 6];
+        for (&node, g) in self.mesh.element(element).iter().zip(&point.gradients) {
+            for i in 0..d {
+                let column = strain_column(d, *g, i);
+                for (s, c) in strain.iter_mut().zip(column) {
+                    *s += c * u[node * d + i];
+                }
+            }
+        }
+        let material = &self.materials[element];
+        let dm = self.formulation.elasticity_matrix(material);
+        let mut sigma = [0.0; 6];
+        for (r, value) in sigma.iter_mut().enumerate() {
+            *value = (0..6).map(|s| dm[r][s] * strain[s]).sum();
+        }
+        match self.formulation {
+            Formulation::Solid => Stress {
+                xx: sigma[0],
+                yy: sigma[1],
+                zz: sigma[2],
+                xy: sigma[3],
+                yz: sigma[4],
+                zx: sigma[5],
+            },
+            Formulation::PlaneStress { .. } | Formulation::PlaneStrain { .. } => Stress {
+                xx: sigma[0],
+                yy: sigma[1],
+                zz: match self.formulation {
+                    Formulation::PlaneStrain { .. } => {
+                        material.poissons_ratio * (sigma[0] + sigma[1])
+                    }
+                    _ => 0.0,
+                },
+                xy: sigma[2],
+                yz: 0.0,
+                zx: 0.0,
+            },
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// SOLUTION
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// Displacements and recovered stresses of a solved problem
+#[derive(Clone, Debug, PartialEq)]
+pub struct ElasticSolution {
+    /// Nodal displacement (z = 0 in 2D)
+    pub displacements: Vec<DVec3>,
+    /// Stress at each element centroid
+    pub element_stress: Vec<Stress>,
+    /// Element stresses averaged at the nodes
+    pub nodal_stress: Vec<Stress>,
+    /// Support reactions (zero on free components)
+    pub reactions: Vec<DVec3>,
+    /// ½uᵀK·u (J, thickness included in 2D)
+    pub strain_energy: f64,
+}
+
+impl ElasticSolution {
+    pub fn max_displacement(&self) -> f64 {
+        self.displacements
+            .iter()
+            .map(|u| u.length())
+            .fold(0.0, f64::max)
+    }
+
+    /// Nodal von Mises stress
+    pub fn von_mises(&self) -> Vec<f64> {
+        self.nodal_stress.iter().map(Stress::von_mises).collect()
+    }
+
+    pub fn max_von_mises(&self) -> f64 {
+        self.nodal_stress
+            .iter()
+            .map(Stress::von_mises)
+            .fold(0.0, f64::max)
+    }
+
+    /// Sum of support reactions
+    pub fn total_reaction(&self) -> DVec3 {
+        self.reactions.iter().sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uniaxial_bar() {
+        let mesh = UnstructuredMesh::cuboid(1.0, 0.2, 0.2, 5, 1, 1).unwrap();
+        let steel = ElasticMaterial::steel();
+        let mut problem = ElasticityProblem::new(&mesh, Formulation::Solid, steel).unwrap();
+        // Rollers on x = 0, rigid-body modes removed at two corners
+        problem
+            .fix(&mesh.nodes_where(|p| p.x < 1e-9), 0, 0.0)
+            .unwrap();
+        let origin = mesh.nodes_where(|p| p.length() < 1e-9);
+        problem.fix(&origin, 1, 0.0).unwrap();
+        problem.fix(&origin, 2, 0.0).unwrap();
+        let corner = mesh.nodes_where(|p| (p - DVec3::new(0.0, 0.2, 0.0)).length() < 1e-9);
+        problem.fix(&corner, 2, 0.0).unwrap();
+        let sigma = 1e8;
+        let end = mesh.boundary_facets_where(|p| p.x > 1.0 - 1e-9);
+        problem
+            .add_traction(&end, |_| DVec3::new(sigma, 0.0, 0.0))
+            .unwrap();
+
+        let solution = problem.solve().unwrap();
+        for (p, u) in mesh.nodes().iter().zip(&solution.displacements) {
+            let exact = DVec3::new(p.x, -0.3 * p.y, -0.3 * p.z) * sigma / steel.youngs_modulus;
+            assert!((*u - exact).length() < 1e-9, "{:?} vs {:?}", u, exact);
+        }
+        for vm in solution.von_mises() {
+            assert!((vm - sigma).abs() < 1e-4 * sigma);
+        }
+        let reaction = solution.total_reaction();
+        assert!((reaction.x + sigma * 0.04).abs() < 1e-6 * sigma);
+        // Strain energy σ²V / 2E
+        let energy = sigma * sigma * 0.04 / (2.0 * steel.youngs_modulus);
+        assert!((solution.strain_energy - energy).abs() < 1e-6 * energy);
+    }
+
+    #[test]
+    fn test_confined_plane_strain_block() {
+        let mesh = UnstructuredMesh::rectangle(1.0, 1.0, 4, 4).unwrap();
+        let material = ElasticMaterial::new(10e9, 0.25, 1000.0).unwrap();
+        let formulation = Formulation::PlaneStrain { thickness: 2.0 };
+        let mut problem = ElasticityProblem::new(&mesh, formulation, material).unwrap();
+        problem
+            .fix(
+                &mesh.nodes_where(|p| p.x < 1e-9 || p.x > 1.0 - 1e-9),
+                0,
+                0.0,
+            )
+            .unwrap();
+        problem
+            .fix(&mesh.nodes_where(|p| p.y < 1e-9), 1, 0.0)
+            .unwrap();
+        let p = 1e6;
+        problem
+            .add_pressure(&mesh.boundary_facets_where(|x| x.y > 1.0 - 1e-9), p)
+            .unwrap();
+
+        let solution = problem.solve().unwrap();
+        let sxx = 0.25 / 0.75 * -p;
+        for s in &solution.element_stress {
+            assert!((s.yy + p).abs() < 1e-6 * p);
+            assert!((s.xx - sxx).abs() < 1e-6 * p);
+            assert!((s.zz - 0.25 * (sxx - p)).abs() < 1e-6 * p);
+            assert!(s.xy.abs() < 1e-6 * p);
+        }
+        // Bottom supports carry p × width × thickness
+        assert!((solution.total_reaction().y - 2.0 * p).abs() < 1e-6 * p);
+    }
+
+    #[test]
+    fn test_cantilever_tip_deflection() {
+        let (length, height, thickness) = (10.0, 1.0, 0.1);
+        let mesh = UnstructuredMesh::rectangle(length, height, 40, 4)
+            .unwrap()
+            .to_quadratic();
+        let material = ElasticMaterial::new(1e9, 0.3, 1.0).unwrap();
+        let formulation = Formulation::PlaneStress { thickness };
+        let mut problem = ElasticityProblem::new(&mesh, formulation, material).unwrap();
+        problem.clamp(&mesh.nodes_where(|p| p.x < 1e-9)).unwrap();
+        let force = 1000.0;
+        let tip = mesh.boundary_facets_where(|p| p.x > length - 1e-9);
+        problem
+            .add_traction(&tip, |_| {
+                DVec3::new(0.0, -force / (height * thickness), 0.0)
+            })
+            .unwrap();
+
+        let solution = problem.solve().unwrap();
+        // Timoshenko: PL³/3EI + PL/κGA with κ = 5/6
+        let inertia = thickness * height.powi(3) / 12.0;
+        let area = thickness * height;
+        let exact = force * length.powi(3) / (3.0 * material.youngs_modulus * inertia)
+            + force * length / (5.0 / 6.0 * material.shear_modulus() * area);
+        let centre = mesh.nodes_where(|p| (p - DVec3::new(length, 0.5, 0.0)).length() < 1e-9)[0];
+        let deflection = -solution.displacements[centre].y;
+        assert!(
+            (deflection - exact).abs() < 0.03 * exact,
+            "tip {} vs {}",
+            deflection,
+            exact
+        );
+        // Root bending stress M·c/I away from the clamp singularity
+        let root = force * (length - 1.0) * 0.5 / inertia;
+        let nodes = mesh.nodes_where(|p| (p - DVec3::new(1.0, 1.0, 0.0)).length() < 1e-9);
+        let vm = solution.nodal_stress[nodes[0]].von_mises();
+        assert!((vm - root).abs() < 0.05 * root, "root {} vs {}", vm, root);
+    }
+
+    #[test]
+    fn test_validation() {
+        let mesh = UnstructuredMesh::rectangle(1.0, 1.0, 2, 2).unwrap();
+        assert!(ElasticMaterial::new(1e9, 0.5, 1.0).is_err());
+        assert!(
+            ElasticityProblem::new(&mesh, Formulation::Solid, ElasticMaterial::steel()).is_err()
+        );
+        let stress = Formulation::PlaneStress { thickness: 0.0 };
+        assert!(ElasticityProblem::new(&mesh, stress, ElasticMaterial::steel()).is_err());
+        let stress = Formulation::PlaneStress { thickness: 1.0 };
+        let mut problem = ElasticityProblem::new(&mesh, stress, ElasticMaterial::steel()).unwrap();
+        assert_eq!(problem.solve(), Err(FemError::Unconstrained));
+        assert!(problem.fix(&[0], 2, 0.0).is_err());
+        assert_eq!(
+            problem.add_point_load(42, DVec3::X),
+            Err(FemError::UnknownNode(42))
+        );
+    }
+}
@@ -0,0 +1,387 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: element.rs | DNA/src/physics/solvers/pde/fem/element.rs
+//! PURPOSE: Lagrange shape functions, quadrature and isoparametric mapping
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! PURPOSE: Per-element building blocks shared by the FEM problems
+//!
+//! LAYER: DNA → PHYSICS → SOLVERS → PDE → FEM
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ REFERENCE ELEMENTS                                                          │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ Triangle (0,0) (1,0) (0,1);  tetrahedron adds (0,0,1)                       │
+//! │ Barycentric L₀ = 1 - ξ - η (- ζ),  L₁ = ξ,  L₂ = η,  L₃ = ζ                 │
+//! │ Linear:     Nᵢ = Lᵢ                                                         │
+//! │ Quadratic:  corner Nᵢ = Lᵢ(2Lᵢ - 1),  mid-edge N_ij = 4LᵢLⱼ                 │
+//! │                                                                             │
+//! │ Mapping x(ξ) = Σ xₐNₐ(ξ),  J = ∂x/∂ξ (2D: third column ẑ),                  │
+//! │ ∇Nₐ = J⁻ᵀ∇_ξNₐ,  ∫ f dΩ ≈ Σ_q w_q f(ξ_q) det J(ξ_q)                         │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ QUADRATURE (exact for polynomials up to the degree)                         │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ Line      Gauss-Legendre, 3 points                     degree 5             │
+//! │ Triangle  centroid / 3 points / Dunavant 6 points      degree 1 / 2 / 4     │
+//! │ Tetra     centroid / 4 points / Keast 11 points        degree 1 / 2 / 4     │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! REFERENCE: Zienkiewicz, Taylor & Zhu, "The Finite Element Method: Its Basis
+//!            and Fundamentals" (7th ed.), ch. 6
+//!            Dunavant, Int. J. Numer. Meth. Eng. 21 (1985) 1129-1148
+//!            Keast, Comput. Methods Appl. Mech. Eng. 55 (1986) 339-348
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use crate::world::grid::unstructured::ElementKind;
+use glam::{DMat3, DVec3};
+
+/// Reference point and weight (weights sum to the reference measure)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct QuadraturePoint {
+    pub xi: DVec3,
+    pub weight: f64,
+}
+
+fn point(x: f64, y: f64, z: f64, weight: f64) -> QuadraturePoint {
+    QuadraturePoint {
+        xi: DVec3::new(x, y, z),
+        weight,
+    }
+}
+
+/// Rule on the reference triangle (dimension 2) or tetrahedron (dimension 3)
+/// exact to at least `degree` (≤ 4)
+pub fn quadrature(dimension: usize, degree: usize) -> Vec<QuadraturePoint> {
+    match (dimension, degree) {
+        (2, 0..=1) => vec![point(1.0 / 3.0, 1.0 / 3.0, 0.0, 0.5)],
+        (2, 2) => vec![
+            point(1.0 / 6.0, 1.0 / 6.0, 0.0, 1.0 / 6.0),
+            point(2.0 / 3.0, 1.0 / 6.0, 0.0, 1.0 / 6.0),
+            point(1.0 / 6.0, 2.0 / 3.0, 0.0, 1.0 / 6.0),
+        ],
+        (2, _) => {
+            let (a, wa) = (0.445_948_490_915_965, 0.223_381_589_678_011 / 2.0);
+            let (b, wb) = (0.091_576_213_509_771, 0.109_951_743_655_322 / 2.0);
+            vec![
+                point(a, a, 0.0, wa),
+                point(1.0 - 2.0 * a, a, 0.0, wa),
+                point(a, 1.0 - 2.0 * a, 0.0, wa),
+                point(b, b, 0.0, wb),
+                point(1.0 - 2.0 * b, b, 0.0, wb),
+                point(b, 1.0 - 2.0 * b, 0.0, wb),
+            ]
+        }
+        (_, 0..=1) => vec![point(0.25, 0.25, 0.25, 1.0 / 6.0)],
+        (_, 2) => {
+            let (a, b) = (0.138_196_601_125_010_5, 0.585_410_196_624_968_5);
+            vec![
+                point(a, a, a, 1.0 / 24.0),
+                point(b, a, a, 1.0 / 24.0),
+                point(a, b, a, 1.0 / 24.0),
+                point(a, a, b, 1.0 / 24.0),
+            ]
+        }
+        _ => {
+            let (a, b) = (1.0 / 14.0, 11.0 / 14.0);
+            let s = (5.0f64 / 14.0).sqrt();
+            let (c, d) = ((1.0 + s) / 4.0, (1.0 - s) / 4.0);
+            let (w1, w2, w3) = (-74.0 / 5625.0, 343.0 / 45000.0, 56.0 / 2250.0);
+            vec![
+                point(0.25, 0.25, 0.25, w1),
+                point(a, a, a, w2),
+                point(b, a, a, w2),
+                point(a, b, a, w2),
+                point(a, a, b, w2),
+                point(c, c, d, w3),
+                point(c, d, c, w3),
+                point(d, c, c, w3),
+                point(c, d, d, w3),
+                point(d, c, d, w3),
+                point(d, d, c, w3),
+            ]
+        }
+    }
+}
+
+/// 3-point Gauss-Legendre rule on [0, 1]
+pub fn line_quadrature() -> [(f64, f64); 3] {
+    let h = 0.5 * (0.6f64).sqrt();
+    [
+        (0.5 - h, 5.0 / 18.0),
+        (0.5, 8.0 / 18.0),
+        (0.5 + h, 5.0 / 18.0),
+    ]
+}
+
+/// Quadrature degree that integrates mass and stiffness terms exactly on
+/// straight-sided elements of the given order
+pub fn degree_for(kind: ElementKind) -> usize {
+    2 * kind.order()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// SHAPE FUNCTIONS
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// Nₐ(ξ) and ∇_ξNₐ(ξ) for every node of the element
+pub fn shape_functions(kind: ElementKind, xi: DVec3) -> (Vec<f64>, Vec<DVec3>) {
+    let dim = kind.dimension();
+    // Barycentric coordinates and their reference gradients
+    let mut l = [1.0 - xi.x - xi.y, xi.x, xi.y, 0.0];
+    let mut dl = [DVec3::new(-1.0, -1.0, 0.0), DVec3::X, DVec3::Y, DVec3::ZERO];
+    if dim == 3 {
+        l[0] -= xi.z;
+        l[3] = xi.z;
+        dl[0].z = -1.0;
+        dl[3] = DVec3::Z;
+    }
+    let corners = dim + 1;
+    let n = kind.node_count();
+    let mut values = Vec::with_capacity(n);
+    let mut gradients = Vec::with_capacity(n);
+    if kind.order() == 1 {
+        values.extend_from_slice(&l[..corners]);
+        gradients.extend_from_slice(&dl[..corners]);
+    } else {
+        for i in 0..corners {
+            values.push(l[i] * (2.0 * l[i] - 1.0));
+            gradients.push((4.0 * l[i] - 1.0) * dl[i]);
+        }
+        for &[i, j] in kind.edges() {
+            values.push(4.0 * l[i] * l[j]);
+            gradients.push(4.0 * (l[i] * dl[j] + l[j] * dl[i]));
+        }
+    }
+    (values, gradients)
+}
+
+/// Reference coordinates of the element nodes
+pub fn reference_nodes(kind: ElementKind) -> Vec<DVec3> {
+    let mut corners = vec![DVec3::ZERO, DVec3::X, DVec3::Y];
+    if kind.dimension() == 3 {
+        corners.push(DVec3::Z);
+    }
+    let mut nodes = corners.clone();
+    if kind.order() == 2 {
+        for &[i, j] in kind.edges() {
+            nodes.push(0.5 * (corners[i] + corners[j]));
+        }
+    }
+    nodes
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// ISOPARAMETRIC MAPPING
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// Shape functions mapped to a physical point of an element
+#[derive(Clone, Debug)]
+pub struct MappedPoint {
+    pub position: DVec3,
+    pub values: Vec<f64>,
+    /// Physical gradients ∇Nₐ
+    pub gradients: Vec<DVec3>,
+    /// det J (area / volume scale, positive for valid elements)
+    pub det_j: f64,
+}
+
+/// Map reference point `xi` through an element with node positions `nodes`
+pub fn map_point(kind: ElementKind, nodes: &[DVec3], xi: DVec3) -> MappedPoint {
+    let (values, reference) = shape_functions(kind, xi);
+    let mut columns = [DVec3::ZERO; 3];
+    let mut position = DVec3::ZERO;
+    for ((x, &n), g) in nodes.iter().zip(&values).zip(&reference) {
+        position += n * *x;
+        columns[0] += g.x * *x;
+        columns[1] += g.y * *x;
+        columns[2] += g.z * *x;
+    }
+    if kind.dimension() == 2 {
+        columns[2] = DVec3::Z;
+    }
+    let j = DMat3::from_cols(columns[0], columns[1], columns[2]);
+    let det_j = j.determinant();
+    let inverse_t = j.inverse().transpose();
+    let gradients = reference.iter().map(|g| inverse_t * *g).collect();
+    MappedPoint {
+        position,
+        values,
+        gradients,
+        det_j,
+    }
+}
+
+/// Quadrature point on a boundary facet
+#[derive(Clone, Debug)]
+pub struct FacetPoint {
+    pub position: DVec3,
+    /// Facet shape functions in the facet node order
+    pub values: Vec<f64>,
+    /// Unit outward normal
+    pub normal: DVec3,
+    /// Quadrature weight × length / area scale
+    pub weight: f64,
+}
+
+/// Quadrature points on a facet of an element of `kind` (`nodes` in facet
+/// order: corners, then mid-edge nodes)
+pub fn facet_points(kind: ElementKind, nodes: &[DVec3]) -> Vec<FacetPoint> {
+    if kind.dimension() == 2 {
+        line_quadrature()
+            .iter()
+            .map(|&(s, w)| {
+                let (values, ds): (Vec<f64>, Vec<f64>) = if nodes.len() == 2 {
+                    (vec![1.0 - s, s], vec![-1.0, 1.0])
+                } else {
+                    (
+                        vec![
+                            (1.0 - s) * (1.0 - 2.0 * s),
+                            s * (2.0 * s - 1.0),
+                            4.0 * s * (1.0 - s),
+                        ],
+                        vec![4.0 * s - 3.0, 4.0 * s - 1.0, 4.0 - 8.0 * s],
+                    )
+                };
+                let position = nodes.iter().zip(&values).map(|(x, n)| *x * *n).sum();
+                let tangent: DVec3 = nodes.iter().zip(&ds).map(|(x, d)| *x * *d).sum();
+                // Element edges run counter-clockwise: outward is the tangent turned right
+                let normal = DVec3::new(tangent.y, -tangent.x, 0.0);
+                FacetPoint {
+                    position,
+                    values,
+                    normal: normal.normalize_or_zero(),
+                    weight: w * tangent.length(),
+                }
+            })
+            .collect()
+    } else {
+        let face = if nodes.len() == 3 {
+            ElementKind::Triangle3
+        } else {
+            ElementKind::Triangle6
+        };
+        quadrature(2, 4)
+            .iter()
+            .map(|q| {
+                let (values, reference) = shape_functions(face, q.xi);
+                let mut position = DVec3::ZERO;
+                let (mut dx_dxi, mut dx_deta) = (DVec3::ZERO, DVec3::ZERO);
+                for ((x, &n), g) in nodes.iter().zip(&values).zip(&reference) {
+                    position += n * *x;
+                    dx_dxi += g.x * *x;
+                    dx_deta += g.y * *x;
+                }
+                let area_normal = dx_dxi.cross(dx_deta);
+                FacetPoint {
+                    position,
+                    values,
+                    normal: area_normal.normalize_or_zero(),
+                    weight: q.weight * area_normal.length(),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quadrature_exactness() {
+        // ∫ ξ^a η^b ζ^c over the reference simplex = a! b! c! / (a + b + c + d)!
+        let factorial = |n: u32| (1..=n).product::<u32>() as f64;
+        for dimension in [2, 3] {
+            for degree in [1, 2, 4] {
+                let rule = quadrature(dimension, degree);
+                for a in 0..=degree as u32 {
+                    for b in 0..=(degree as u32 - a) {
+                        let cs = if dimension == 3 {
+                            degree as u32 - a - b
+                        } else {
+                            0
+                        };
+                        for c in 0..=cs {
+                            let exact = factorial(a) * factorial(b) * factorial(c)
+                                / factorial(a + b + c + dimension as u32);
+                            let sum: f64 = rule
+                                .iter()
+                                .map(|q| {
+                                    q.weight
+                                        * q.xi.x.powi(a as i32)
+                                        * q.xi.y.powi(b as i32)
+                                        * q.xi.z.powi(c as i32)
+                                })
+                                .sum();
+                            assert!(
+                                (sum - exact).abs() < 1e-12,
+                                "dim {} degree {} monomial ({}, {}, {})",
+                                dimension,
+                                degree,
+                                a,
+                                b,
+                                c
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_shape_functions_interpolate() {
+        let xi = DVec3::new(0.2, 0.3, 0.1);
+        for kind in [
+            ElementKind::Triangle3,
+            ElementKind::Triangle6,
+            ElementKind::Tetra4,
+            ElementKind::Tetra10,
+        ] {
+            let nodes = reference_nodes(kind);
+            assert_eq!(nodes.len(), kind.node_count());
+            // Kronecker delta at the nodes
+            for (a, x) in nodes.iter().enumerate() {
+                let (values, _) = shape_functions(kind, *x);
+                for (b, v) in values.iter().enumerate() {
+                    assert!((v - if a == b { 1.0 } else { 0.0 }).abs() < 1e-12);
+                }
+            }
+            // Gradients match finite differences
+            let (_, gradients) = shape_functions(kind, xi);
+            for axis in 0..kind.dimension() {
+                let mut step = DVec3::ZERO;
+                step[axis] = 1e-6;
+                let (plus, _) = shape_functions(kind, xi + step);
+                let (minus, _) = shape_functions(kind, xi - step);
+                for a in 0..kind.node_count() {
+                    let fd = (plus[a] - minus[a]) / 2e-6;
+                    assert!((fd - gradients[a][axis]).abs() < 1e-8);
+                }
+            }
+            // Mapping reproduces linear fields exactly
+            let physical: Vec<DVec3> = nodes
+                .iter()
+                .map(|x| DVec3::new(2.0 * x.x + 0.5 * x.y, 1.5 * x.y, 3.0 * x.z))
+                .collect();
+            let mapped = map_point(kind, &physical, xi);
+            let field = |p: DVec3| 1.0 + 2.0 * p.x - p.y + 0.5 * p.z;
+            let gradient: DVec3 = physical
+                .iter()
+                .zip(&mapped.gradients)
+                .map(|(p, g)| field(*p) * *g)
+                .sum();
+            let expected = if kind.dimension() == 2 {
+                DVec3::new(2.0, -1.0, 0.0)
+            } else {
+                DVec3::new(2.0, -1.0, 0.5)
+            };
+            assert!((gradient - expected).length() < 1e-12);
+        }
+    }
+}
@@ -0,0 +1,208 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: mod.rs | DNA/src/physics/solvers/pde/fem/mod.rs
+//! PURPOSE: Finite element method: elements, scalar fields, linear elasticity
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! PURPOSE: Galerkin FEM on unstructured triangle / tetrahedron meshes
+//!
+//! LAYER: DNA → PHYSICS → SOLVERS → PDE → FEM
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ PIPELINE                                                                    │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ world::grid::UnstructuredMesh   nodes + P1 / P2 connectivity                │
+//! │   → element::map_point          shape functions, J, quadrature              │
+//! │   → element matrices            Kₑ = ∫ Bᵀ D B dΩ,  Mₑ = ∫ ρ NᵀN dΩ          │
+//! │   → TripletMatrix → CsrMatrix   global assembly (duplicates summed)         │
+//! │   → Dirichlet elimination       fixed rows keep their diagonal, columns     │
+//! │                                 move to the right-hand side (stays SPD)     │
+//! │   → CG + IC(0) (Jacobi fallback)                                            │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! Neumann data (flux, traction, pressure) and sources are integrated into
+//! the load vector when they are added, so problems hold no closures.
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+/// Shape functions, quadrature rules and the isoparametric map
+pub mod element;
+pub use element::{map_point, quadrature, shape_functions, MappedPoint, QuadraturePoint};
+
+/// Poisson and heat conduction (steady, θ-method transient)
+pub mod scalar;
+pub use scalar::{HeatTransient, ScalarProblem};
+
+/// Linear elasticity: plane stress / strain and 3D, von Mises output
+pub mod elasticity;
+pub use elasticity::{ElasticMaterial, ElasticSolution, ElasticityProblem, Formulation, Stress};
+
+use crate::physics::solvers::linear::{
+    conjugate_gradient, CsrMatrix, IncompleteCholesky, IterativeOptions, Jacobi, LinalgError,
+    Preconditioner,
+};
+use crate::world::grid::unstructured::{Facet, UnstructuredMesh, UnstructuredMeshError};
+use element::{facet_points, FacetPoint};
+use glam::DVec3;
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// ERRORS
+// ─────────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FemError {
+    InvalidParameter(String),
+    UnknownNode(usize),
+    UnknownElement(usize),
+    /// No Dirichlet condition: the stiffness matrix is singular
+    Unconstrained,
+    Mesh(UnstructuredMeshError),
+    Linear(LinalgError),
+}
+
+impl std::fmt::Display for FemError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FemError::InvalidParameter(msg) => write!(f, "Invalid parameter: {}", msg),
+            FemError::UnknownNode(node) => write!(f, "No node with index {}", node),
+            FemError::UnknownElement(element) => {
+                write!(f, "No element with index {}", element)
+            }
+            FemError::Unconstrained => write!(
+                f,
+                "Problem needs at least one Dirichlet condition (stiffness is singular)"
+            ),
+            FemError::Mesh(e) => write!(f, "Mesh error: {}", e),
+            FemError::Linear(e) => write!(f, "Linear solve failed: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for FemError {}
+
+impl From<LinalgError> for FemError {
+    fn from(e: LinalgError) -> Self {
+        FemError::Linear(e)
+    }
+}
+
+impl From<UnstructuredMeshError> for FemError {
+    fn from(e: UnstructuredMeshError) -> Self {
+        FemError::Mesh(e)
+    }
+}
+
+fn check_positive(name: &str, value: f64) -> Result<(), FemError> {
+    if value > 0.0 && value.is_finite() {
+        Ok(())
+    } else {
+        Err(FemError::InvalidParameter(format!(
+            "{} {} must be positive",
+            name, value
+        )))
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// SHARED LINEAR ALGEBRA
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// Eliminate fixed degrees of freedom symmetrically: A_ii·x_i = A_ii·g_i on
+/// fixed rows, A_ij·g_j moved to the right-hand side elsewhere
+fn apply_dirichlet(a: &mut CsrMatrix, b: &mut [f64], fixed: &[Option<f64>]) {
+    let offsets = a.row_offsets().to_vec();
+    let columns = a.col_indices().to_vec();
+    let values = a.values_mut();
+    for i in 0..b.len() {
+        let mut diagonal = 1.0;
+        for p in offsets[i]..offsets[i + 1] {
+            let j = columns[p];
+            if fixed[i].is_some() {
+                if i == j && values[p] != 0.0 {
+                    diagonal = values[p];
+                } else {
+                    values[p] = 0.0;
+                }
+            } else if let Some(g) = fixed[j] {
+                b[i] -= values[p] * g;
+                values[p] = 0.0;
+            }
+        }
+        if let Some(g) = fixed[i] {
+            b[i] = diagonal * g;
+        }
+    }
+}
+
+/// IC(0) for CG; it can break down on elasticity matrices, Jacobi always exists
+fn preconditioner(a: &CsrMatrix) -> Result<Box<dyn Preconditioner>, FemError> {
+    match IncompleteCholesky::new(a) {
+        Ok(ic) => Ok(Box::new(ic)),
+        Err(_) => Ok(Box::new(Jacobi::new(a)?)),
+    }
+}
+
+/// Solve a symmetric positive definite system with preconditioned CG
+fn solve_spd(
+    a: &CsrMatrix,
+    b: &[f64],
+    x0: &[f64],
+    preconditioner: &dyn Preconditioner,
+    options: &IterativeOptions,
+) -> Result<Vec<f64>, FemError> {
+    let solution = conjugate_gradient(a, b, Some(x0), preconditioner, options)?;
+    Ok(solution.into_result()?.x)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// SHARED INTEGRATION
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// Mapped quadrature points of an element with weights w·det J·thickness
+fn integration_points(
+    mesh: &UnstructuredMesh,
+    element: usize,
+    thickness: f64,
+) -> Vec<(MappedPoint, f64)> {
+    let kind = mesh.kind();
+    let nodes = mesh.element_nodes(element);
+    quadrature(kind.dimension(), element::degree_for(kind))
+        .iter()
+        .map(|q| {
+            let point = map_point(kind, &nodes, q.xi);
+            let weight = q.weight * point.det_j * thickness;
+            (point, weight)
+        })
+        .collect()
+}
+
+/// Facet quadrature points; 2D line weights are scaled by the thickness
+fn facet_integration(mesh: &UnstructuredMesh, facet: &Facet, thickness: f64) -> Vec<FacetPoint> {
+    let nodes: Vec<DVec3> = facet.nodes.iter().map(|&n| mesh.nodes()[n]).collect();
+    let mut points = facet_points(mesh.kind(), &nodes);
+    if mesh.dimension() == 2 {
+        for p in &mut points {
+            p.weight *= thickness;
+        }
+    }
+    points
+}
+
+fn check_nodes(mesh: &UnstructuredMesh, nodes: &[usize]) -> Result<(), FemError> {
+    match nodes.iter().find(|&&n| n >= mesh.node_count()) {
+        Some(&n) => Err(FemError::UnknownNode(n)),
+        None => Ok(()),
+    }
+}
+
+fn check_facets(mesh: &UnstructuredMesh, facets: &[Facet]) -> Result<(), FemError> {
+    for facet in facets {
+        if facet.element >= mesh.element_count() {
+            return Err(FemError::UnknownElement(facet.element));
+        }
+        check_nodes(mesh, &facet.nodes)?;
+    }
+    Ok(())
+}
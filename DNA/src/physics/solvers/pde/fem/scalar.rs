@@ -0,0 +1,479 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: scalar.rs | DNA/src/physics/solvers/pde/fem/scalar.rs
+//! PURPOSE: Poisson / steady and transient heat conduction with finite elements
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! PURPOSE: Scalar diffusion on unstructured P1 / P2 meshes
+//!
+//! LAYER: DNA → PHYSICS → SOLVERS → PDE → FEM
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ PHYSICS                                                                     │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ Steady:     -∇·(k∇u) = f             in Ω                                   │
+//! │ Transient:  ρc_p·∂u/∂t = ∇·(k∇u) + f                                        │
+//! │ Dirichlet   u = g                    on Γ_D                                 │
+//! │ Neumann     k∇u·n = q                on Γ_N  (q > 0 flows into the body)    │
+//! │                                                                             │
+//! │ 2D problems are per unit thickness                                          │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ ALGORITHM                                                                   │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ Kₑ = ∫ kₑ ∇Nₐ·∇N_b dΩ      Mₑ = ∫ (ρc_p)ₑ NₐN_b dΩ   (consistent mass)      │
+//! │ F  = ∫ f Nₐ dΩ + ∫ q Nₐ dΓ + point sources                                  │
+//! │ Steady      K·u = F                                                         │
+//! │ θ-method    (M/Δt + θK)·uⁿ⁺¹ = (M/Δt - (1-θ)K)·uⁿ + F                       │
+//! │             θ = 1/2 Crank-Nicolson, θ = 1 backward Euler                    │
+//! │             system, Dirichlet lift and preconditioner built once            │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ DATA DEFINED                                                                │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ ScalarProblem   Per-element k and ρc_p, load vector, fixed nodes            │
+//! │ HeatTransient   Prepared θ-method stepper with the current field            │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use super::{
+    apply_dirichlet, check_facets, check_nodes, check_positive, facet_integration,
+    integration_points, map_point, preconditioner, solve_spd, FemError,
+};
+use crate::physics::solvers::linear::{CsrMatrix, IterativeOptions, Preconditioner, TripletMatrix};
+use crate::world::grid::unstructured::{Facet, UnstructuredMesh};
+use glam::DVec3;
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// PROBLEM
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// Scalar diffusion problem on a mesh (u is a temperature or potential)
+#[derive(Clone, Debug)]
+pub struct ScalarProblem<'a> {
+    mesh: &'a UnstructuredMesh,
+    conductivity: Vec<f64>,
+    capacity: Vec<f64>,
+    load: Vec<f64>,
+    fixed: Vec<Option<f64>>,
+    linear_options: IterativeOptions,
+}
+
+impl<'a> ScalarProblem<'a> {
+    /// Uniform conductivity k, volumetric heat capacity ρc_p = 1
+    pub fn new(mesh: &'a UnstructuredMesh, conductivity: f64) -> Result<Self, FemError> {
+        check_positive("conductivity", conductivity)?;
+        let n = mesh.node_count();
+        Ok(Self {
+            mesh,
+            conductivity: vec![conductivity; mesh.element_count()],
+            capacity: vec![1.0; mesh.element_count()],
+            load: vec![0.0; n],
+            fixed: vec![None; n],
+            linear_options: IterativeOptions::default()
+                .with_tolerances(1e-12, 0.0)
+                .with_max_iterations(n.max(1000)),
+        })
+    }
+
+    pub fn with_linear_options(mut self, options: IterativeOptions) -> Self {
+        self.linear_options = options;
+        self
+    }
+
+    pub fn mesh(&self) -> &UnstructuredMesh {
+        self.mesh
+    }
+
+    /// Conductivity of the given elements
+    pub fn set_conductivity(&mut self, elements: &[usize], k: f64) -> Result<(), FemError> {
+        check_positive("conductivity", k)?;
+        set_elements(&mut self.conductivity, elements, k)
+    }
+
+    /// Volumetric heat capacity ρc_p of the given elements (transient only)
+    pub fn set_capacity(&mut self, elements: &[usize], rho_cp: f64) -> Result<(), FemError> {
+        check_positive("heat capacity", rho_cp)?;
+        set_elements(&mut self.capacity, elements, rho_cp)
+    }
+
+    /// Fix u = value on nodes
+    pub fn fix(&mut self, nodes: &[usize], value: f64) -> Result<(), FemError> {
+        self.fix_with(nodes, |_| value)
+    }
+
+    /// Fix u = g(x) on nodes
+    pub fn fix_with(&mut self, nodes: &[usize], g: impl Fn(DVec3) -> f64) -> Result<(), FemError> {
+        check_nodes(self.mesh, nodes)?;
+        for &n in nodes {
+            self.fixed[n] = Some(g(self.mesh.nodes()[n]));
+        }
+        Ok(())
+    }
+
+    pub fn is_fixed(&self, node: usize) -> bool {
+        self.fixed.get(node).is_some_and(|f| f.is_some())
+    }
+
+    /// Volumetric source f(x) over the whole mesh
+    pub fn add_source(&mut self, f: impl Fn(DVec3) -> f64) {
+        for e in 0..self.mesh.element_count() {
+            let element = self.mesh.element(e);
+            for (point, weight) in integration_points(self.mesh, e, 1.0) {
+                let value = f(point.position) * weight;
+                for (&node, n) in element.iter().zip(&point.values) {
+                    self.load[node] += value * n;
+                }
+            }
+        }
+    }
+
+    /// Boundary flux q(x) into the body through facets
+    pub fn add_flux(&mut self, facets: &[Facet], q: impl Fn(DVec3) -> f64) -> Result<(), FemError> {
+        check_facets(self.mesh, facets)?;
+        for facet in facets {
+            for point in facet_integration(self.mesh, facet, 1.0) {
+                let value = q(point.position) * point.weight;
+                for (&node, n) in facet.nodes.iter().zip(&point.values) {
+                    self.load[node] += value * n;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Concentrated source at a node (W in 3D, W/m in 2D)
+    pub fn add_point_source(&mut self, node: usize, value: f64) -> Result<(), FemError> {
+        check_nodes(self.mesh, &[node])?;
+        self.load[node] += value;
+        Ok(())
+    }
+
+    pub fn load(&self) -> &[f64] {
+        &self.load
+    }
+
+    /// Global stiffness (conductance) matrix K, no boundary conditions
+    pub fn stiffness(&self) -> CsrMatrix {
+        self.assemble(&self.conductivity, |point, a, b| {
+            point.gradients[a].dot(point.gradients[b])
+        })
+    }
+
+    /// Consistent capacity matrix M weighted by ρc_p
+    pub fn mass(&self) -> CsrMatrix {
+        self.assemble(&self.capacity, |point, a, b| {
+            point.values[a] * point.values[b]
+        })
+    }
+
+    fn assemble(
+        &self,
+        coefficient: &[f64],
+        integrand: impl Fn(&super::MappedPoint, usize, usize) -> f64,
+    ) -> CsrMatrix {
+        let n = self.mesh.node_count();
+        let per = self.mesh.kind().node_count();
+        let mut triplets =
+            TripletMatrix::with_capacity(n, n, self.mesh.element_count() * per * per);
+        let mut local = vec![0.0; per * per];
+        for (e, &c) in coefficient.iter().enumerate() {
+            local.iter_mut().for_each(|v| *v = 0.0);
+            for (point, weight) in integration_points(self.mesh, e, c) {
+                for a in 0..per {
+                    for b in 0..per {
+                        local[a * per + b] += weight * integrand(&point, a, b);
+                    }
+                }
+            }
+            let element = self.mesh.element(e);
+            for a in 0..per {
+                for b in 0..per {
+                    triplets.push(element[a], element[b], local[a * per + b]);
+                }
+            }
+        }
+        triplets.to_csr()
+    }
+
+    fn check_constrained(&self) -> Result<(), FemError> {
+        if self.fixed.iter().any(|f| f.is_some()) {
+            Ok(())
+        } else {
+            Err(FemError::Unconstrained)
+        }
+    }
+
+    /// Steady solution K·u = F
+    pub fn solve(&self) -> Result<Vec<f64>, FemError> {
+        self.check_constrained()?;
+        let mut a = self.stiffness();
+        let mut b = self.load.clone();
+        apply_dirichlet(&mut a, &mut b, &self.fixed);
+        let x0: Vec<f64> = self.fixed.iter().map(|f| f.unwrap_or(0.0)).collect();
+        let precond = preconditioner(&a)?;
+        solve_spd(&a, &b, &x0, precond.as_ref(), &self.linear_options)
+    }
+
+    /// ∇u at the centroid of an element (exact for P1)
+    pub fn gradient(&self, u: &[f64], element: usize) -> Result<DVec3, FemError> {
+        if element >= self.mesh.element_count() {
+            return Err(FemError::UnknownElement(element));
+        }
+        let kind = self.mesh.kind();
+        let xi = if kind.dimension() == 2 {
+            DVec3::new(1.0, 1.0, 0.0) / 3.0
+        } else {
+            DVec3::splat(0.25)
+        };
+        let point = map_point(kind, &self.mesh.element_nodes(element), xi);
+        Ok(self
+            .mesh
+            .element(element)
+            .iter()
+            .zip(&point.gradients)
+            .map(|(&n, g)| *g * u[n])
+            .sum())
+    }
+
+    /// Heat flux -k∇u at the centroid of an element
+    pub fn flux(&self, u: &[f64], element: usize) -> Result<DVec3, FemError> {
+        Ok(-self.gradient(u, element)? * self.conductivity[element])
+    }
+
+    /// Prepare θ-method time stepping from `initial` (fixed nodes are reset
+    /// to their prescribed values)
+    pub fn transient(
+        &self,
+        dt: f64,
+        theta: f64,
+        initial: &[f64],
+    ) -> Result<HeatTransient, FemError> {
+        check_positive("time step", dt)?;
+        if !(0.0..=1.0).contains(&theta) {
+            return Err(FemError::InvalidParameter(format!(
+                "theta {} must be in [0, 1]",
+                theta
+            )));
+        }
+        if initial.len() != self.mesh.node_count() {
+            return Err(FemError::InvalidParameter(format!(
+                "initial field has {} values for {} nodes",
+                initial.len(),
+                self.mesh.node_count()
+            )));
+        }
+        let k = self.stiffness();
+        let m = self.mass();
+        let n = self.mesh.node_count();
+        // M/Δt ± K share the sparsity pattern of the element connectivity
+        let mut system = m.clone();
+        let mut explicit = m;
+        for (s, (e, kv)) in system
+            .values_mut()
+            .iter_mut()
+            .zip(explicit.values_mut().iter_mut().zip(k.values()))
+        {
+            let mass = *s / dt;
+            *s = mass + theta * kv;
+            *e = mass - (1.0 - theta) * kv;
+        }
+        let mut lift = vec![0.0; n];
+        apply_dirichlet(&mut system, &mut lift, &self.fixed);
+        let precond = preconditioner(&system)?;
+        let mut temperature = initial.to_vec();
+        for (t, f) in temperature.iter_mut().zip(&self.fixed) {
+            if let Some(g) = f {
+                *t = *g;
+            }
+        }
+        Ok(HeatTransient {
+            system,
+            explicit,
+            load: self.load.clone(),
+            lift,
+            fixed: self.fixed.iter().map(|f| f.is_some()).collect(),
+            preconditioner: precond,
+            options: self.linear_options.clone(),
+            temperature,
+            dt,
+            time: 0.0,
+        })
+    }
+}
+
+fn set_elements(values: &mut [f64], elements: &[usize], value: f64) -> Result<(), FemError> {
+    if let Some(&e) = elements.iter().find(|&&e| e >= values.len()) {
+        return Err(FemError::UnknownElement(e));
+    }
+    for &e in elements {
+        values[e] = value;
+    }
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// TRANSIENT
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// θ-method heat stepper built by [`ScalarProblem::transient`]
+pub struct HeatTransient {
+    system: CsrMatrix,
+    explicit: CsrMatrix,
+    load: Vec<f64>,
+    /// Right-hand side of the Dirichlet elimination with zero load
+    lift: Vec<f64>,
+    fixed: Vec<bool>,
+    preconditioner: Box<dyn Preconditioner>,
+    options: IterativeOptions,
+    temperature: Vec<f64>,
+    dt: f64,
+    time: f64,
+}
+
+impl HeatTransient {
+    pub fn temperature(&self) -> &[f64] {
+        &self.temperature
+    }
+
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    pub fn dt(&self) -> f64 {
+        self.dt
+    }
+
+    /// Advance one time step
+    pub fn step(&mut self) -> Result<(), FemError> {
+        let mut b = self.explicit.mul_vec(&self.temperature);
+        for (i, value) in b.iter_mut().enumerate() {
+            *value = if self.fixed[i] {
+                self.lift[i]
+            } else {
+                *value + self.load[i] + self.lift[i]
+            };
+        }
+        self.temperature = solve_spd(
+            &self.system,
+            &b,
+            &self.temperature,
+            self.preconditioner.as_ref(),
+            &self.options,
+        )?;
+        self.time += self.dt;
+        Ok(())
+    }
+
+    /// Step until `duration` has elapsed (rounded to whole steps); returns
+    /// the number of steps taken
+    pub fn advance(&mut self, duration: f64) -> Result<usize, FemError> {
+        let steps = (duration / self.dt - 1e-9).ceil().max(0.0) as usize;
+        for _ in 0..steps {
+            self.step()?;
+        }
+        Ok(steps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    fn boundary_nodes(mesh: &UnstructuredMesh) -> Vec<usize> {
+        mesh.nodes_where(|p| p.x < 1e-9 || p.y < 1e-9 || p.x > 1.0 - 1e-9 || p.y > 1.0 - 1e-9)
+    }
+
+    fn poisson_error(mesh: &UnstructuredMesh) -> f64 {
+        let mut problem = ScalarProblem::new(mesh, 1.0).unwrap();
+        problem.fix(&boundary_nodes(mesh), 0.0).unwrap();
+        problem.add_source(|p| 2.0 * PI * PI * (PI * p.x).sin() * (PI * p.y).sin());
+        let u = problem.solve().unwrap();
+        mesh.nodes()
+            .iter()
+            .zip(&u)
+            .map(|(p, u)| (u - (PI * p.x).sin() * (PI * p.y).sin()).abs())
+            .fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn test_poisson_manufactured_solution() {
+        let coarse = UnstructuredMesh::rectangle(1.0, 1.0, 8, 8).unwrap();
+        let fine = UnstructuredMesh::rectangle(1.0, 1.0, 16, 16).unwrap();
+        let (e8, e16) = (poisson_error(&coarse), poisson_error(&fine));
+        // P1: second order at the nodes
+        assert!(e16 < 0.02, "P1 error {}", e16);
+        assert!(e8 / e16 > 3.0, "P1 rate {} / {}", e8, e16);
+        // P2 on the coarse mesh beats P1 on the fine one
+        let p2 = poisson_error(&coarse.to_quadratic());
+        assert!(p2 < e16 / 4.0, "P2 error {} vs P1 {}", p2, e16);
+    }
+
+    #[test]
+    fn test_conduction_through_bar_is_linear() {
+        let mesh = UnstructuredMesh::cuboid(2.0, 0.5, 0.5, 8, 2, 2).unwrap();
+        let k = 4.0;
+        let mut problem = ScalarProblem::new(&mesh, k).unwrap();
+        problem
+            .fix(&mesh.nodes_where(|p| p.x < 1e-9), 10.0)
+            .unwrap();
+        // 3 W/m² into the x = 2 face
+        problem
+            .add_flux(&mesh.boundary_facets_where(|p| p.x > 2.0 - 1e-9), |_| 3.0)
+            .unwrap();
+        let u = problem.solve().unwrap();
+        for (p, t) in mesh.nodes().iter().zip(&u) {
+            assert!((t - (10.0 + 3.0 * p.x / k)).abs() < 1e-8);
+        }
+        let flux = problem.flux(&u, 0).unwrap();
+        assert!((flux - DVec3::new(-3.0, 0.0, 0.0)).length() < 1e-8);
+    }
+
+    #[test]
+    fn test_transient_mode_decay() {
+        let mesh = UnstructuredMesh::rectangle(1.0, 1.0, 12, 12)
+            .unwrap()
+            .to_quadratic();
+        let mut problem = ScalarProblem::new(&mesh, 1.0).unwrap();
+        problem.fix(&boundary_nodes(&mesh), 0.0).unwrap();
+        let initial: Vec<f64> = mesh
+            .nodes()
+            .iter()
+            .map(|p| (PI * p.x).sin() * (PI * p.y).sin())
+            .collect();
+        let mut heat = problem.transient(1e-3, 0.5, &initial).unwrap();
+        assert_eq!(heat.advance(0.05).unwrap(), 50);
+        let centre = mesh.nodes_where(|p| (p - DVec3::new(0.5, 0.5, 0.0)).length() < 1e-9)[0];
+        let exact = (-2.0 * PI * PI * heat.time()).exp();
+        let relative = (heat.temperature()[centre] - exact).abs() / exact;
+        assert!(
+            relative < 0.01,
+            "centre {} vs {}",
+            heat.temperature()[centre],
+            exact
+        );
+    }
+
+    #[test]
+    fn test_validation() {
+        let mesh = UnstructuredMesh::rectangle(1.0, 1.0, 2, 2).unwrap();
+        assert!(matches!(
+            ScalarProblem::new(&mesh, -1.0),
+            Err(FemError::InvalidParameter(_))
+        ));
+        let mut problem = ScalarProblem::new(&mesh, 1.0).unwrap();
+        assert_eq!(problem.solve(), Err(FemError::Unconstrained));
+        assert_eq!(problem.fix(&[99], 0.0), Err(FemError::UnknownNode(99)));
+        assert_eq!(
+            problem.set_conductivity(&[8], 2.0),
+            Err(FemError::UnknownElement(8))
+        );
+        assert!(problem.transient(0.1, 1.5, &[0.0; 9]).is_err());
+    }
+}
@@ -1,6 +1,6 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: mod.rs | DNA/src/physics/solvers/pde/mod.rs
-//! PURPOSE: Module exports: spectral
+//! PURPOSE: Module exports: spectral, fem
 //! MODIFIED: 2025-12-09
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
@@ -9,5 +9,11 @@
 pub mod spectral;
 pub use spectral::FFT2D;
 
+/// Finite element method on unstructured P1 / P2 meshes
+pub mod fem;
+pub use fem::{
+    ElasticMaterial, ElasticSolution, ElasticityProblem, FemError, Formulation, HeatTransient,
+    ScalarProblem, Stress,
+};
+
 // pub mod fdm;       // TODO: Finite Difference Method
-// pub mod fem;       // TODO: Finite Element Method
@@ -1,7 +1,7 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: conduction.rs | DNA/src/physics/thermal/conduction.rs
 //! PURPOSE: Heat equation solver (thermal conduction)
-//! MODIFIED: 2025-12-09
+//! MODIFIED: 2026-10-18
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
 
@@ -22,12 +22,923 @@
 //! │     k = thermal conductivity                                                │
 //! │     ρ = density                                                             │
 //! │     c_p = specific heat capacity                                            │
+//! │                                                                             │
+//! │ With heterogeneous k and a volumetric source q̇ (W/m³):                      │
+//! │   ρ·c_p·∂T/∂t = ∇·(k∇T) + q̇                                                 │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ DISCRETIZATION (cell-centred finite volume)                                 │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ Cell i:   Cᵢ·dTᵢ/dt = Σⱼ Gᵢⱼ(Tⱼ - Tᵢ) + Σ G_b(T_b - Tᵢ) + q̇ᵢVᵢ + q″A       │
+//! │           Cᵢ = ρc_p·V,  Gᵢⱼ = A / (Δ/2kᵢ + Δ/2kⱼ)  (series resistance)      │
+//! │ Faces:    Dirichlet  G_b = 2kA/Δ to the wall temperature                    │
+//! │           Robin      G_b = A / (1/h + Δ/2k) to the ambient                  │
+//! │           Neumann    prescribed flux q″ (W/m², positive = into the body)    │
+//! │ Matrix:   C·dT/dt = -K·T + b,  K symmetric positive (semi)definite          │
+//! │                                                                             │
+//! │ Explicit (FTCS)    T += Δt·C⁻¹(b - K·T),  stable for Δt ≤ minᵢ Cᵢ/Kᵢᵢ       │
+//! │                    (uniform interior: Δt ≤ Δx²/(2·d·α); a Dirichlet face    │
+//! │                    tightens it to Δx²/(3α) along that axis in 1D)           │
+//! │ Crank-Nicolson     (C/Δt + K/2)·Tⁿ⁺¹ = (C/Δt - K/2)·Tⁿ + b  (CG + IC(0))    │
+//! │ Backward Euler     (C/Δt + K)·Tⁿ⁺¹ = C/Δt·Tⁿ + b     (no CN ringing)        │
+//! │ ADI (Douglas)      (C + Δt/2·K_x)·δ₁ = Δt(b - K·Tⁿ)                         │
+//! │                    (C + Δt/2·K_y)·δ₂ = C·δ₁                                 │
+//! │                    (C + Δt/2·K_z)·δ₃ = C·δ₂,   Tⁿ⁺¹ = Tⁿ + δ₃               │
+//! │                    tridiagonal line solves, 2nd order, unconditionally      │
+//! │                    stable                                                   │
+//! │ Steady state       K·T = b  (needs at least one Dirichlet or Robin face)    │
 //! └─────────────────────────────────────────────────────────────────────────────┘
 //!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ DATA DEFINED                                                                │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ Grid             Uniform nx × ny × nz cells (unused axes have n = 1)        │
+//! │ Material         k, ρ, c_p (+ common board materials)                       │
+//! │ Face / Boundary  Per-face Dirichlet, Neumann or Robin condition             │
+//! │ Scheme           Explicit, CrankNicolson, BackwardEuler, Adi                │
+//! │ HeatSolver       Temperature field, per-cell material and source            │
+//! │ ThermalError     Bad input, unstable step, ill-posed steady state           │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! A 2D board is a grid with nz = 1 and dz = board thickness; Robin conditions
+//! on the Z faces then model convection from the top and bottom copper.
+//!
 //! REFERENCE: https://en.wikipedia.org/wiki/Heat_equation
+//!            Patankar, "Numerical Heat Transfer and Fluid Flow" (1980), ch. 4
+//!            Douglas & Gunn, Numer. Math. 6 (1964) 428-453
 //!
 //! ═══════════════════════════════════════════════════════════════════════════════
 
-// TODO: Implement finite difference heat equation solver
-// TODO: Support different boundary conditions (Dirichlet, Neumann, Robin)
-// TODO: 1D, 2D, 3D versions
+use crate::physics::solvers::linear::{
+    conjugate_gradient, IncompleteCholesky, IterativeOptions, LinalgError, TripletMatrix,
+};
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// GRID AND MATERIALS
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// Uniform Cartesian grid of nx × ny × nz cells, x fastest
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Grid {
+    pub nx: usize,
+    pub ny: usize,
+    pub nz: usize,
+    /// Cell size along x, y, z (m)
+    pub dx: f64,
+    pub dy: f64,
+    pub dz: f64,
+}
+
+impl Grid {
+    pub fn new(
+        nx: usize,
+        ny: usize,
+        nz: usize,
+        dx: f64,
+        dy: f64,
+        dz: f64,
+    ) -> Result<Self, ThermalError> {
+        if nx == 0 || ny == 0 || nz == 0 {
+            return Err(ThermalError::InvalidParameter(
+                "grid must have at least one cell per axis".to_string(),
+            ));
+        }
+        if !(dx > 0.0 && dy > 0.0 && dz > 0.0 && (dx * dy * dz).is_finite()) {
+            return Err(ThermalError::InvalidParameter(format!(
+                "cell size ({}, {}, {}) must be positive",
+                dx, dy, dz
+            )));
+        }
+        Ok(Self {
+            nx,
+            ny,
+            nz,
+            dx,
+            dy,
+            dz,
+        })
+    }
+
+    /// 1D rod of n cells with cross-section `area`
+    pub fn line(n: usize, dx: f64, area: f64) -> Result<Self, ThermalError> {
+        let side = area.sqrt();
+        Self::new(n, 1, 1, dx, side, side)
+    }
+
+    /// 2D plate of nx × ny cells and the given thickness
+    pub fn plane(
+        nx: usize,
+        ny: usize,
+        dx: f64,
+        dy: f64,
+        thickness: f64,
+    ) -> Result<Self, ThermalError> {
+        Self::new(nx, ny, 1, dx, dy, thickness)
+    }
+
+    pub fn len(&self) -> usize {
+        self.nx * self.ny * self.nz
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn index(&self, i: usize, j: usize, k: usize) -> usize {
+        i + self.nx * (j + self.ny * k)
+    }
+
+    pub fn coordinates(&self, index: usize) -> (usize, usize, usize) {
+        (
+            index % self.nx,
+            (index / self.nx) % self.ny,
+            index / (self.nx * self.ny),
+        )
+    }
+
+    /// Cell centre (m), origin at the grid corner
+    pub fn center(&self, i: usize, j: usize, k: usize) -> [f64; 3] {
+        [
+            (i as f64 + 0.5) * self.dx,
+            (j as f64 + 0.5) * self.dy,
+            (k as f64 + 0.5) * self.dz,
+        ]
+    }
+
+    pub fn cell_volume(&self) -> f64 {
+        self.dx * self.dy * self.dz
+    }
+
+    fn cells(&self, axis: usize) -> usize {
+        [self.nx, self.ny, self.nz][axis]
+    }
+
+    fn spacing(&self, axis: usize) -> f64 {
+        [self.dx, self.dy, self.dz][axis]
+    }
+
+    /// Index offset between neighbours along `axis`
+    fn stride(&self, axis: usize) -> usize {
+        [1, self.nx, self.nx * self.ny][axis]
+    }
+
+    /// Area of a face normal to `axis`
+    fn face_area(&self, axis: usize) -> f64 {
+        self.cell_volume() / self.spacing(axis)
+    }
+}
+
+/// Thermal properties of a cell
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Material {
+    /// k (W/(m·K))
+    pub conductivity: f64,
+    /// ρ (kg/m³)
+    pub density: f64,
+    /// c_p (J/(kg·K))
+    pub specific_heat: f64,
+}
+
+impl Material {
+    pub const COPPER: Material = Material::new(401.0, 8960.0, 385.0);
+    pub const ALUMINUM: Material = Material::new(237.0, 2700.0, 897.0);
+    pub const SILICON: Material = Material::new(149.0, 2329.0, 712.0);
+    /// In-plane glass-epoxy laminate
+    pub const FR4: Material = Material::new(0.3, 1850.0, 1100.0);
+
+    pub const fn new(conductivity: f64, density: f64, specific_heat: f64) -> Self {
+        Self {
+            conductivity,
+            density,
+            specific_heat,
+        }
+    }
+
+    /// α = k/(ρ·c_p) (m²/s)
+    pub fn diffusivity(&self) -> f64 {
+        self.conductivity / self.volumetric_heat_capacity()
+    }
+
+    /// ρ·c_p (J/(m³·K))
+    pub fn volumetric_heat_capacity(&self) -> f64 {
+        self.density * self.specific_heat
+    }
+
+    fn check(&self) -> Result<(), ThermalError> {
+        let valid = self.conductivity > 0.0
+            && self.density > 0.0
+            && self.specific_heat > 0.0
+            && (self.conductivity * self.volumetric_heat_capacity()).is_finite();
+        if valid {
+            Ok(())
+        } else {
+            Err(ThermalError::InvalidParameter(format!(
+                "material properties must be positive: {:?}",
+                self
+            )))
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// BOUNDARIES AND SCHEMES
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// Outer face of the grid
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Face {
+    XMin,
+    XMax,
+    YMin,
+    YMax,
+    ZMin,
+    ZMax,
+}
+
+impl Face {
+    pub const ALL: [Face; 6] = [
+        Face::XMin,
+        Face::XMax,
+        Face::YMin,
+        Face::YMax,
+        Face::ZMin,
+        Face::ZMax,
+    ];
+
+    fn is_max(self) -> bool {
+        self as usize % 2 == 1
+    }
+}
+
+/// Condition applied on a whole face
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Boundary {
+    /// Fixed wall temperature (K)
+    Dirichlet(f64),
+    /// Prescribed heat flux (W/m², positive into the body); 0 = insulated
+    Neumann(f64),
+    /// Convection q″ = h·(T_ambient - T_surface)
+    Robin {
+        /// Heat transfer coefficient (W/(m²·K))
+        h: f64,
+        ambient: f64,
+    },
+}
+
+impl Boundary {
+    pub const INSULATED: Boundary = Boundary::Neumann(0.0);
+
+    fn check(&self) -> Result<(), ThermalError> {
+        let valid = match *self {
+            Boundary::Dirichlet(t) => t.is_finite(),
+            Boundary::Neumann(q) => q.is_finite(),
+            Boundary::Robin { h, ambient } => h > 0.0 && h.is_finite() && ambient.is_finite(),
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(ThermalError::InvalidParameter(format!(
+                "invalid boundary condition {:?}",
+                self
+            )))
+        }
+    }
+}
+
+/// Time integration scheme
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Scheme {
+    /// Forward Euler (FTCS), conditionally stable
+    Explicit,
+    /// Trapezoidal, 2nd order; may ring on sharp initial data with large Δt
+    CrankNicolson,
+    /// 1st order, L-stable
+    BackwardEuler,
+    /// Douglas alternating direction implicit, 2nd order, line solves only
+    Adi,
+}
+
+/// Heat solver error
+#[derive(Debug, Clone, PartialEq)]
+pub enum ThermalError {
+    InvalidParameter(String),
+    /// Explicit step above the stability limit
+    Unstable {
+        dt: f64,
+        limit: f64,
+    },
+    /// Steady state requested with only Neumann faces (K is singular)
+    IllPosed,
+    Linear(LinalgError),
+}
+
+impl std::fmt::Display for ThermalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ThermalError::InvalidParameter(msg) => write!(f, "Invalid parameter: {}", msg),
+            ThermalError::Unstable { dt, limit } => write!(
+                f,
+                "Explicit time step {:.3e} s exceeds stability limit {:.3e} s",
+                dt, limit
+            ),
+            ThermalError::IllPosed => write!(
+                f,
+                "Steady state needs at least one Dirichlet or Robin boundary"
+            ),
+            ThermalError::Linear(e) => write!(f, "Linear solve failed: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ThermalError {}
+
+impl From<LinalgError> for ThermalError {
+    fn from(e: LinalgError) -> Self {
+        ThermalError::Linear(e)
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// DISCRETE OPERATOR
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// C·dT/dt = -K·T + b, with K split by axis for ADI
+struct Operator {
+    /// Cᵢ = ρc_p·V (J/K)
+    capacity: Vec<f64>,
+    /// Conductance from cell i to its + neighbour along each axis (0 at the end)
+    coupling: [Vec<f64>; 3],
+    /// Conductance from cell i to a fixed boundary temperature, per axis
+    boundary: [Vec<f64>; 3],
+    /// Sources, boundary fluxes and fixed-temperature terms (W)
+    rhs: Vec<f64>,
+}
+
+impl Operator {
+    fn diagonal(&self, grid: &Grid, axis: usize, i: usize) -> f64 {
+        let stride = grid.stride(axis);
+        let below = if !(i / stride).is_multiple_of(grid.cells(axis)) {
+            self.coupling[axis][i - stride]
+        } else {
+            0.0
+        };
+        self.coupling[axis][i] + below + self.boundary[axis][i]
+    }
+
+    /// y (+)= K_axis · t
+    fn apply_axis(&self, grid: &Grid, axis: usize, t: &[f64], y: &mut [f64]) {
+        let stride = grid.stride(axis);
+        for i in 0..t.len() {
+            y[i] += self.boundary[axis][i] * t[i];
+            let g = self.coupling[axis][i];
+            if g != 0.0 {
+                let flow = g * (t[i] - t[i + stride]);
+                y[i] += flow;
+                y[i + stride] -= flow;
+            }
+        }
+    }
+
+    /// K · t
+    fn apply(&self, grid: &Grid, t: &[f64]) -> Vec<f64> {
+        let mut y = vec![0.0; t.len()];
+        for axis in 0..3 {
+            self.apply_axis(grid, axis, t, &mut y);
+        }
+        y
+    }
+
+    /// shift·C + scale·K as a sparse matrix
+    fn assemble(&self, grid: &Grid, shift: f64, scale: f64) -> TripletMatrix {
+        let n = self.capacity.len();
+        let mut a = TripletMatrix::with_capacity(n, n, 7 * n);
+        for i in 0..n {
+            a.push(i, i, shift * self.capacity[i]);
+            for axis in 0..3 {
+                a.push(i, i, scale * self.boundary[axis][i]);
+                let g = self.coupling[axis][i];
+                if g != 0.0 {
+                    let j = i + grid.stride(axis);
+                    a.push(i, i, scale * g);
+                    a.push(j, j, scale * g);
+                    a.push(i, j, -scale * g);
+                    a.push(j, i, -scale * g);
+                }
+            }
+        }
+        a
+    }
+
+    /// Solve (C + scale·K_axis)·x = rhs line by line (Thomas algorithm)
+    fn solve_lines(&self, grid: &Grid, axis: usize, scale: f64, rhs: &[f64], x: &mut [f64]) {
+        let stride = grid.stride(axis);
+        let len = grid.cells(axis);
+        let mut upper = vec![0.0; len];
+        let mut work = vec![0.0; len];
+        for start in (0..grid.len()).filter(|&i| (i / stride).is_multiple_of(len)) {
+            // Forward sweep: row m has sub = -g(m-1), diag, super = -g(m)
+            let mut previous_upper = 0.0;
+            let mut previous_work = 0.0;
+            for m in 0..len {
+                let i = start + m * stride;
+                let sub = if m > 0 {
+                    -scale * self.coupling[axis][i - stride]
+                } else {
+                    0.0
+                };
+                let diag = self.capacity[i] + scale * self.diagonal(grid, axis, i);
+                let denom = diag - sub * previous_upper;
+                upper[m] = -scale * self.coupling[axis][i] / denom;
+                work[m] = (rhs[i] - sub * previous_work) / denom;
+                previous_upper = upper[m];
+                previous_work = work[m];
+            }
+            // Back substitution
+            let mut next = 0.0;
+            for m in (0..len).rev() {
+                next = work[m] - upper[m] * next;
+                x[start + m * stride] = next;
+            }
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// SOLVER
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// Transient and steady conduction on a uniform grid
+#[derive(Clone, Debug)]
+pub struct HeatSolver {
+    grid: Grid,
+    /// Cell temperatures (K), indexed by `Grid::index`
+    pub temperature: Vec<f64>,
+    materials: Vec<Material>,
+    /// Volumetric heat source per cell (W/m³)
+    source: Vec<f64>,
+    boundaries: [Boundary; 6],
+    /// Simulated time (s)
+    pub time: f64,
+    /// Settings for the CG solves of the implicit schemes and steady state
+    pub linear_options: IterativeOptions,
+}
+
+impl HeatSolver {
+    /// Uniform material and temperature, all faces insulated
+    pub fn new(grid: Grid, material: Material, temperature: f64) -> Result<Self, ThermalError> {
+        material.check()?;
+        let n = grid.len();
+        Ok(Self {
+            grid,
+            temperature: vec![temperature; n],
+            materials: vec![material; n],
+            source: vec![0.0; n],
+            boundaries: [Boundary::INSULATED; 6],
+            time: 0.0,
+            linear_options: IterativeOptions::default().with_tolerances(1e-12, 0.0),
+        })
+    }
+
+    pub fn with_boundary(mut self, face: Face, boundary: Boundary) -> Result<Self, ThermalError> {
+        self.set_boundary(face, boundary)?;
+        Ok(self)
+    }
+
+    pub fn grid(&self) -> &Grid {
+        &self.grid
+    }
+
+    pub fn boundary(&self, face: Face) -> Boundary {
+        self.boundaries[face as usize]
+    }
+
+    pub fn set_boundary(&mut self, face: Face, boundary: Boundary) -> Result<(), ThermalError> {
+        boundary.check()?;
+        self.boundaries[face as usize] = boundary;
+        Ok(())
+    }
+
+    pub fn material(&self, i: usize, j: usize, k: usize) -> Material {
+        self.materials[self.grid.index(i, j, k)]
+    }
+
+    pub fn set_material(
+        &mut self,
+        i: usize,
+        j: usize,
+        k: usize,
+        material: Material,
+    ) -> Result<(), ThermalError> {
+        material.check()?;
+        let index = self.grid.index(i, j, k);
+        self.materials[index] = material;
+        Ok(())
+    }
+
+    /// Assign `material` to every cell whose centre satisfies `inside`
+    pub fn fill_material(
+        &mut self,
+        material: Material,
+        inside: impl Fn([f64; 3]) -> bool,
+    ) -> Result<(), ThermalError> {
+        material.check()?;
+        for index in 0..self.grid.len() {
+            let (i, j, k) = self.grid.coordinates(index);
+            if inside(self.grid.center(i, j, k)) {
+                self.materials[index] = material;
+            }
+        }
+        Ok(())
+    }
+
+    /// Volumetric sources (W/m³)
+    pub fn source(&self) -> &[f64] {
+        &self.source
+    }
+
+    pub fn source_mut(&mut self) -> &mut [f64] {
+        &mut self.source
+    }
+
+    /// Dissipate `watts` in one cell (replaces any previous source there)
+    pub fn set_power(&mut self, i: usize, j: usize, k: usize, watts: f64) {
+        let index = self.grid.index(i, j, k);
+        self.source[index] = watts / self.grid.cell_volume();
+    }
+
+    /// Total dissipated power (W)
+    pub fn total_power(&self) -> f64 {
+        self.source.iter().sum::<f64>() * self.grid.cell_volume()
+    }
+
+    /// Hottest cell and its temperature
+    pub fn hot_spot(&self) -> ((usize, usize, usize), f64) {
+        let (index, t) =
+            self.temperature
+                .iter()
+                .enumerate()
+                .fold((0, f64::NEG_INFINITY), |best, (i, &t)| {
+                    if t > best.1 {
+                        (i, t)
+                    } else {
+                        best
+                    }
+                });
+        (self.grid.coordinates(index), t)
+    }
+
+    /// Stored heat Σ ρc_p·V·T (J, relative to 0 K)
+    pub fn thermal_energy(&self) -> f64 {
+        let volume = self.grid.cell_volume();
+        self.materials
+            .iter()
+            .zip(&self.temperature)
+            .map(|(m, t)| m.volumetric_heat_capacity() * volume * t)
+            .sum()
+    }
+
+    /// Largest stable explicit step, minᵢ Cᵢ/Kᵢᵢ
+    pub fn stable_time_step(&self) -> f64 {
+        let op = self.operator();
+        (0..self.grid.len())
+            .map(|i| {
+                let k: f64 = (0..3).map(|axis| op.diagonal(&self.grid, axis, i)).sum();
+                op.capacity[i] / k
+            })
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    /// Advance by `dt`
+    pub fn step(&mut self, dt: f64, scheme: Scheme) -> Result<(), ThermalError> {
+        if !(dt > 0.0 && dt.is_finite()) {
+            return Err(ThermalError::InvalidParameter(format!(
+                "time step {} must be positive",
+                dt
+            )));
+        }
+        match scheme {
+            Scheme::Explicit => self.step_explicit(dt)?,
+            Scheme::CrankNicolson => self.step_theta(dt, 0.5)?,
+            Scheme::BackwardEuler => self.step_theta(dt, 1.0)?,
+            Scheme::Adi => self.step_adi(dt),
+        }
+        self.time += dt;
+        Ok(())
+    }
+
+    /// Advance by `duration` in steps of at most `dt`
+    pub fn advance(&mut self, duration: f64, dt: f64, scheme: Scheme) -> Result<(), ThermalError> {
+        let steps = (duration / dt).ceil().max(1.0) as usize;
+        let dt = duration / steps as f64;
+        for _ in 0..steps {
+            self.step(dt, scheme)?;
+        }
+        Ok(())
+    }
+
+    /// Solve K·T = b; the current temperature is the initial guess
+    pub fn steady_state(&self) -> Result<Vec<f64>, ThermalError> {
+        let fixed = self
+            .boundaries
+            .iter()
+            .any(|b| !matches!(b, Boundary::Neumann(_)));
+        if !fixed {
+            return Err(ThermalError::IllPosed);
+        }
+        let op = self.operator();
+        self.solve_spd(&op, 0.0, 1.0, &op.rhs)
+    }
+
+    fn step_explicit(&mut self, dt: f64) -> Result<(), ThermalError> {
+        let limit = self.stable_time_step();
+        if dt > limit * (1.0 + 1e-12) {
+            return Err(ThermalError::Unstable { dt, limit });
+        }
+        let op = self.operator();
+        let kt = op.apply(&self.grid, &self.temperature);
+        for (i, t) in self.temperature.iter_mut().enumerate() {
+            *t += dt * (op.rhs[i] - kt[i]) / op.capacity[i];
+        }
+        Ok(())
+    }
+
+    /// (C/Δt + θK)·Tⁿ⁺¹ = (C/Δt - (1-θ)K)·Tⁿ + b
+    fn step_theta(&mut self, dt: f64, theta: f64) -> Result<(), ThermalError> {
+        let op = self.operator();
+        let kt = op.apply(&self.grid, &self.temperature);
+        let rhs: Vec<f64> = (0..self.temperature.len())
+            .map(|i| op.capacity[i] / dt * self.temperature[i] - (1.0 - theta) * kt[i] + op.rhs[i])
+            .collect();
+        self.temperature = self.solve_spd(&op, 1.0 / dt, theta, &rhs)?;
+        Ok(())
+    }
+
+    fn step_adi(&mut self, dt: f64) {
+        let op = self.operator();
+        let n = self.temperature.len();
+        let kt = op.apply(&self.grid, &self.temperature);
+        let mut rhs: Vec<f64> = (0..n).map(|i| dt * (op.rhs[i] - kt[i])).collect();
+        let mut delta = vec![0.0; n];
+        for axis in 0..3 {
+            if axis > 0 {
+                for i in 0..n {
+                    rhs[i] = op.capacity[i] * delta[i];
+                }
+            }
+            op.solve_lines(&self.grid, axis, 0.5 * dt, &rhs, &mut delta);
+        }
+        for (t, d) in self.temperature.iter_mut().zip(&delta) {
+            *t += d;
+        }
+    }
+
+    /// Solve (shift·C + scale·K)·x = rhs with IC(0)-preconditioned CG
+    fn solve_spd(
+        &self,
+        op: &Operator,
+        shift: f64,
+        scale: f64,
+        rhs: &[f64],
+    ) -> Result<Vec<f64>, ThermalError> {
+        let a = op.assemble(&self.grid, shift, scale).to_csr();
+        let preconditioner = IncompleteCholesky::new(&a)?;
+        let solution = conjugate_gradient(
+            &a,
+            rhs,
+            Some(&self.temperature),
+            &preconditioner,
+            &self.linear_options,
+        )?
+        .into_result()?;
+        Ok(solution.x)
+    }
+
+    fn operator(&self) -> Operator {
+        let grid = &self.grid;
+        let n = grid.len();
+        let volume = grid.cell_volume();
+        let capacity = self
+            .materials
+            .iter()
+            .map(|m| m.volumetric_heat_capacity() * volume)
+            .collect();
+        let mut rhs: Vec<f64> = self.source.iter().map(|q| q * volume).collect();
+        let mut coupling = [vec![0.0; n], vec![0.0; n], vec![0.0; n]];
+        let mut boundary = [vec![0.0; n], vec![0.0; n], vec![0.0; n]];
+
+        for axis in 0..3 {
+            let (stride, len) = (grid.stride(axis), grid.cells(axis));
+            let half = 0.5 * grid.spacing(axis);
+            let area = grid.face_area(axis);
+            for i in 0..n {
+                let position = (i / stride) % len;
+                let k = self.materials[i].conductivity;
+                if position + 1 < len {
+                    let k_next = self.materials[i + stride].conductivity;
+                    coupling[axis][i] = area / (half / k + half / k_next);
+                }
+                for face in [Face::ALL[2 * axis], Face::ALL[2 * axis + 1]] {
+                    let on_face = if face.is_max() {
+                        position + 1 == len
+                    } else {
+                        position == 0
+                    };
+                    if !on_face {
+                        continue;
+                    }
+                    match self.boundaries[face as usize] {
+                        Boundary::Dirichlet(t) => {
+                            let g = area * k / half;
+                            boundary[axis][i] += g;
+                            rhs[i] += g * t;
+                        }
+                        Boundary::Neumann(q) => rhs[i] += q * area,
+                        Boundary::Robin { h, ambient } => {
+                            let g = area / (1.0 / h + half / k);
+                            boundary[axis][i] += g;
+                            rhs[i] += g * ambient;
+                        }
+                    }
+                }
+            }
+        }
+        Operator {
+            capacity,
+            coupling,
+            boundary,
+            rhs,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    /// Decay of the fundamental sine mode with T = 0 on the X (and Y) faces
+    fn sine_mode(nx: usize, ny: usize) -> (HeatSolver, f64) {
+        let (length, material) = (0.01, Material::ALUMINUM);
+        let grid = Grid::plane(nx, ny, length / nx as f64, length / ny as f64, 1e-3).unwrap();
+        let mut solver = HeatSolver::new(grid, material, 0.0).unwrap();
+        let mut faces = vec![Face::XMin, Face::XMax];
+        if ny > 1 {
+            faces.extend([Face::YMin, Face::YMax]);
+        }
+        for face in faces {
+            solver.set_boundary(face, Boundary::Dirichlet(0.0)).unwrap();
+        }
+        for index in 0..grid.len() {
+            let (i, j, k) = grid.coordinates(index);
+            let [x, y, _] = grid.center(i, j, k);
+            let profile = if ny > 1 { (PI * y / length).sin() } else { 1.0 };
+            solver.temperature[index] = (PI * x / length).sin() * profile;
+        }
+        let dims = if ny > 1 { 2.0 } else { 1.0 };
+        let rate = dims * material.diffusivity() * (PI / length).powi(2);
+        (solver, rate)
+    }
+
+    fn amplitude(solver: &HeatSolver) -> f64 {
+        solver.hot_spot().1
+    }
+
+    #[test]
+    fn test_explicit_matches_analytic_decay_and_checks_stability() {
+        let (mut solver, rate) = sine_mode(64, 1);
+        let limit = solver.stable_time_step();
+        let alpha = Material::ALUMINUM.diffusivity();
+        let dx = solver.grid().dx;
+        // Half-cell conductance to the Dirichlet walls: Kᵢᵢ = 3kA/Δx at the ends
+        assert!((limit - dx * dx / (3.0 * alpha)).abs() / limit < 1e-9);
+
+        let initial = amplitude(&solver);
+        let duration = 0.5 / rate;
+        solver
+            .advance(duration, 0.9 * limit, Scheme::Explicit)
+            .unwrap();
+        let expected = initial * (-rate * duration).exp();
+        assert!((amplitude(&solver) - expected).abs() / expected < 2e-3);
+
+        assert!(matches!(
+            solver.step(1.5 * limit, Scheme::Explicit),
+            Err(ThermalError::Unstable { .. })
+        ));
+    }
+
+    #[test]
+    fn test_implicit_schemes_take_large_steps() {
+        for scheme in [Scheme::CrankNicolson, Scheme::Adi, Scheme::BackwardEuler] {
+            let (mut solver, rate) = sine_mode(32, 32);
+            let dt = 20.0 * solver.stable_time_step();
+            let initial = amplitude(&solver);
+            let duration = 0.5 / rate;
+            solver.advance(duration, dt, scheme).unwrap();
+            let expected = initial * (-rate * duration).exp();
+            let tolerance = if scheme == Scheme::BackwardEuler {
+                3e-2
+            } else {
+                5e-3
+            };
+            let error = (amplitude(&solver) - expected).abs() / expected;
+            assert!(error < tolerance, "{:?}: error {}", scheme, error);
+        }
+    }
+
+    #[test]
+    fn test_steady_state_series_materials_and_convection() {
+        // Copper + FR4 rod between 100 °C and 0 °C: series thermal resistance
+        let (n, length, area) = (40, 0.02, 1e-6);
+        let grid = Grid::line(n, length / n as f64, area).unwrap();
+        let mut solver = HeatSolver::new(grid, Material::COPPER, 0.0)
+            .unwrap()
+            .with_boundary(Face::XMin, Boundary::Dirichlet(100.0))
+            .unwrap()
+            .with_boundary(Face::XMax, Boundary::Dirichlet(0.0))
+            .unwrap();
+        solver
+            .fill_material(Material::FR4, |c| c[0] > 0.5 * length)
+            .unwrap();
+        let t = solver.steady_state().unwrap();
+        let half = 0.5 * length;
+        let (r_cu, r_fr4) = (
+            half / (Material::COPPER.conductivity * area),
+            half / (Material::FR4.conductivity * area),
+        );
+        let interface = 100.0 * r_fr4 / (r_cu + r_fr4);
+        // Cells either side of the interface straddle it almost exactly
+        assert!((t[n / 2 - 1] - interface).abs() < 0.05);
+        assert!((t[n / 2] - interface).abs() < 3.0);
+
+        // Board with a 2 W regulator, convection from top and bottom:
+        // every watt leaves through the Robin faces
+        let grid = Grid::plane(20, 20, 2.5e-3, 2.5e-3, 1.6e-3).unwrap();
+        let (h, ambient) = (10.0, 25.0);
+        let mut board = HeatSolver::new(grid, Material::FR4, ambient).unwrap();
+        for face in [Face::ZMin, Face::ZMax] {
+            board
+                .set_boundary(face, Boundary::Robin { h, ambient })
+                .unwrap();
+        }
+        board.set_power(10, 10, 0, 2.0);
+        board.temperature = board.steady_state().unwrap();
+        let ((i, j, _), peak) = board.hot_spot();
+        assert_eq!((i, j), (10, 10));
+        assert!(peak > ambient + 10.0);
+        let op = board.operator();
+        let removed: f64 = (0..grid.len())
+            .map(|c| op.boundary[2][c] * (board.temperature[c] - ambient))
+            .sum();
+        assert!((removed - 2.0).abs() < 1e-6);
+
+        let insulated = HeatSolver::new(grid, Material::FR4, 0.0).unwrap();
+        assert_eq!(insulated.steady_state(), Err(ThermalError::IllPosed));
+    }
+
+    #[test]
+    fn test_insulated_energy_balance() {
+        // Heterogeneous 3D block, internal source and a Neumann inflow
+        let grid = Grid::new(6, 5, 4, 1e-3, 1e-3, 1e-3).unwrap();
+        let flux = 500.0;
+        for scheme in [Scheme::Explicit, Scheme::CrankNicolson, Scheme::Adi] {
+            let mut solver = HeatSolver::new(grid, Material::ALUMINUM, 300.0)
+                .unwrap()
+                .with_boundary(Face::YMin, Boundary::Neumann(flux))
+                .unwrap();
+            solver
+                .fill_material(Material::SILICON, |c| c[2] > 2e-3)
+                .unwrap();
+            solver.set_power(1, 2, 3, 0.5);
+            let e0 = solver.thermal_energy();
+            let dt = 0.5 * solver.stable_time_step();
+            solver.advance(50.0 * dt, dt, scheme).unwrap();
+            let inflow = solver.total_power() + flux * 6e-3 * 4e-3;
+            let gained = solver.thermal_energy() - e0;
+            assert!(
+                (gained - inflow * solver.time).abs() < 1e-8 * gained,
+                "{:?}",
+                scheme
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(Grid::new(0, 1, 1, 1.0, 1.0, 1.0).is_err());
+        assert!(Grid::line(4, -1.0, 1.0).is_err());
+        let grid = Grid::line(4, 1e-3, 1e-6).unwrap();
+        assert!(HeatSolver::new(grid, Material::new(0.0, 1.0, 1.0), 0.0).is_err());
+        let mut solver = HeatSolver::new(grid, Material::COPPER, 0.0).unwrap();
+        assert!(solver
+            .set_boundary(
+                Face::XMin,
+                Boundary::Robin {
+                    h: -1.0,
+                    ambient: 0.0
+                }
+            )
+            .is_err());
+        assert!(solver.step(0.0, Scheme::Adi).is_err());
+    }
+}
@@ -1,12 +1,13 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: mod.rs | DNA/src/physics/thermal/mod.rs
 //! PURPOSE: Module exports: conduction
-//! MODIFIED: 2025-12-09
+//! MODIFIED: 2026-10-18
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
 
-/// Heat equation solver
+/// Heat equation solver (FTCS, Crank-Nicolson, ADI, steady state)
 pub mod conduction;
+pub use conduction::{Boundary, Face, Grid, HeatSolver, Material, Scheme, ThermalError};
 
 // pub mod convection;  // TODO: Advection-diffusion
 // pub mod radiation;   // TODO: Stefan-Boltzmann law
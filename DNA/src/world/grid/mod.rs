@@ -5,6 +5,9 @@
 //! LAYER: DNA (foundation)
 //! ═══════════════════════════════════════════════════════════════════════════════
 
+/// Triangle / tetrahedron meshes (linear and quadratic) for FEM
+pub mod unstructured;
+pub use unstructured::{ElementKind, Facet, UnstructuredMesh, UnstructuredMeshError};
+
 // pub mod uniform;       // TODO: Phase 4
 // pub mod adaptive;      // TODO: Phase 4 (AMR)
-// pub mod unstructured;  // TODO: Phase 4 (FEM meshes)
@@ -0,0 +1,550 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: unstructured.rs | DNA/src/world/grid/unstructured.rs
+//! PURPOSE: Unstructured triangle / tetrahedron meshes for finite elements
+//! MODIFIED: 2026-10-18
+//! LAYER: DNA (foundation)
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+//!
+//! PURPOSE: Nodes plus element connectivity of one element kind, with boundary
+//!          facets and node / facet selection for boundary conditions
+//!
+//! LAYER: DNA → WORLD → GRID
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ ELEMENT KINDS (node order as in VTK)                                        │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ Triangle3   corners 0 1 2 (counter-clockwise)                               │
+//! │ Triangle6   + mid-edge 3 (0-1), 4 (1-2), 5 (2-0)                            │
+//! │ Tetra4      corners 0 1 2 3 with (1-0)×(2-0)·(3-0) > 0                      │
+//! │ Tetra10     + mid-edge 4 (0-1), 5 (1-2), 6 (2-0), 7 (0-3), 8 (1-3), 9 (2-3) │
+//! │                                                                             │
+//! │ Facets: element edges (2D) or faces (3D), corners first, then mid-edge      │
+//! │ nodes; faces wind counter-clockwise seen from outside the element           │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ DATA DEFINED                                                                │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ ElementKind              Triangle3, Triangle6, Tetra4, Tetra10              │
+//! │ Facet                    Boundary edge / face of an element                 │
+//! │ UnstructuredMesh         Nodes (DVec3, z = 0 in 2D) and connectivity        │
+//! │ UnstructuredMeshError    Missing node, inverted element, bad parameter      │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! Generators: `rectangle` (structured triangles), `cuboid` (six Kuhn
+//! tetrahedra per cell, conforming across cells); `to_quadratic` adds the
+//! mid-edge nodes of straight-sided quadratic elements.
+//!
+//! DEPENDS ON:
+//!   • glam::DVec3 → Node positions
+//!
+//! USED BY:
+//!   • PHYSICS/SOLVERS/PDE/FEM → Poisson, heat, linear elasticity
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use glam::DVec3;
+use std::collections::HashMap;
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// ERRORS
+// ─────────────────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum UnstructuredMeshError {
+    InvalidParameter(String),
+    /// Element references a node index past the end of the node list
+    MissingNode {
+        element: usize,
+        node: usize,
+    },
+    /// Element with zero or negative area / volume
+    Inverted {
+        element: usize,
+    },
+}
+
+impl std::fmt::Display for UnstructuredMeshError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UnstructuredMeshError::InvalidParameter(msg) => {
+                write!(f, "Invalid parameter: {}", msg)
+            }
+            UnstructuredMeshError::MissingNode { element, node } => {
+                write!(f, "Element {} references missing node {}", element, node)
+            }
+            UnstructuredMeshError::Inverted { element } => {
+                write!(f, "Element {} is degenerate or inverted", element)
+            }
+        }
+    }
+}
+
+impl std::error::Error for UnstructuredMeshError {}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// ELEMENT KINDS
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// Lagrange simplex element
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ElementKind {
+    Triangle3,
+    Triangle6,
+    Tetra4,
+    Tetra10,
+}
+
+const TRIANGLE3_FACETS: [&[usize]; 3] = [&[0, 1], &[1, 2], &[2, 0]];
+const TRIANGLE6_FACETS: [&[usize]; 3] = [&[0, 1, 3], &[1, 2, 4], &[2, 0, 5]];
+const TETRA4_FACETS: [&[usize]; 4] = [&[0, 2, 1], &[0, 1, 3], &[0, 3, 2], &[1, 2, 3]];
+const TETRA10_FACETS: [&[usize]; 4] = [
+    &[0, 2, 1, 6, 5, 4],
+    &[0, 1, 3, 4, 8, 7],
+    &[0, 3, 2, 7, 9, 6],
+    &[1, 2, 3, 5, 9, 8],
+];
+/// Corner pairs of the mid-edge nodes, in node order
+const TRIANGLE_EDGES: [[usize; 2]; 3] = [[0, 1], [1, 2], [2, 0]];
+const TETRA_EDGES: [[usize; 2]; 6] = [[0, 1], [1, 2], [2, 0], [0, 3], [1, 3], [2, 3]];
+
+impl ElementKind {
+    /// Spatial dimension (2 or 3)
+    pub fn dimension(self) -> usize {
+        match self {
+            ElementKind::Triangle3 | ElementKind::Triangle6 => 2,
+            ElementKind::Tetra4 | ElementKind::Tetra10 => 3,
+        }
+    }
+
+    /// Polynomial order (1 or 2)
+    pub fn order(self) -> usize {
+        match self {
+            ElementKind::Triangle3 | ElementKind::Tetra4 => 1,
+            ElementKind::Triangle6 | ElementKind::Tetra10 => 2,
+        }
+    }
+
+    pub fn node_count(self) -> usize {
+        match self {
+            ElementKind::Triangle3 => 3,
+            ElementKind::Triangle6 => 6,
+            ElementKind::Tetra4 => 4,
+            ElementKind::Tetra10 => 10,
+        }
+    }
+
+    /// Corner nodes (the first `dimension + 1`)
+    pub fn corner_count(self) -> usize {
+        self.dimension() + 1
+    }
+
+    /// Local node lists of the facets (see module docs for the order)
+    pub fn facets(self) -> &'static [&'static [usize]] {
+        match self {
+            ElementKind::Triangle3 => &TRIANGLE3_FACETS,
+            ElementKind::Triangle6 => &TRIANGLE6_FACETS,
+            ElementKind::Tetra4 => &TETRA4_FACETS,
+            ElementKind::Tetra10 => &TETRA10_FACETS,
+        }
+    }
+
+    /// Corner pairs whose midpoints are the quadratic nodes
+    pub fn edges(self) -> &'static [[usize; 2]] {
+        match self.dimension() {
+            2 => &TRIANGLE_EDGES,
+            _ => &TETRA_EDGES,
+        }
+    }
+
+    /// Quadratic element of the same shape
+    pub fn quadratic(self) -> Self {
+        match self {
+            ElementKind::Triangle3 | ElementKind::Triangle6 => ElementKind::Triangle6,
+            ElementKind::Tetra4 | ElementKind::Tetra10 => ElementKind::Tetra10,
+        }
+    }
+}
+
+/// Boundary facet: `nodes` are global indices in the local facet order
+#[derive(Clone, Debug, PartialEq)]
+pub struct Facet {
+    pub element: usize,
+    /// Index into `ElementKind::facets`
+    pub local: usize,
+    pub nodes: Vec<usize>,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// MESH
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// Simplex mesh of a single element kind
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnstructuredMesh {
+    nodes: Vec<DVec3>,
+    kind: ElementKind,
+    /// `kind.node_count()` global node indices per element
+    connectivity: Vec<usize>,
+}
+
+impl UnstructuredMesh {
+    /// Validate indices and orientation (positive area / volume)
+    pub fn new(
+        nodes: Vec<DVec3>,
+        kind: ElementKind,
+        connectivity: Vec<usize>,
+    ) -> Result<Self, UnstructuredMeshError> {
+        let per = kind.node_count();
+        if !connectivity.len().is_multiple_of(per) {
+            return Err(UnstructuredMeshError::InvalidParameter(format!(
+                "connectivity length {} is not a multiple of {}",
+                connectivity.len(),
+                per
+            )));
+        }
+        let mesh = Self {
+            nodes,
+            kind,
+            connectivity,
+        };
+        for element in 0..mesh.element_count() {
+            if let Some(&node) = mesh
+                .element(element)
+                .iter()
+                .find(|&&n| n >= mesh.nodes.len())
+            {
+                return Err(UnstructuredMeshError::MissingNode { element, node });
+            }
+            // NaN measures (degenerate coordinates) are rejected too
+            if mesh.corner_measure(element).partial_cmp(&0.0) != Some(std::cmp::Ordering::Greater) {
+                return Err(UnstructuredMeshError::Inverted { element });
+            }
+        }
+        Ok(mesh)
+    }
+
+    /// [0, width] × [0, height] split into nx × ny cells of two triangles
+    /// (diagonals alternate so the mesh has no preferred direction)
+    pub fn rectangle(
+        width: f64,
+        height: f64,
+        nx: usize,
+        ny: usize,
+    ) -> Result<Self, UnstructuredMeshError> {
+        check_size(&[width, height], &[nx, ny])?;
+        let mut nodes = Vec::with_capacity((nx + 1) * (ny + 1));
+        for j in 0..=ny {
+            for i in 0..=nx {
+                nodes.push(DVec3::new(
+                    width * i as f64 / nx as f64,
+                    height * j as f64 / ny as f64,
+                    0.0,
+                ));
+            }
+        }
+        let index = |i: usize, j: usize| j * (nx + 1) + i;
+        let mut connectivity = Vec::with_capacity(6 * nx * ny);
+        for j in 0..ny {
+            for i in 0..nx {
+                let (a, b, c, d) = (
+                    index(i, j),
+                    index(i + 1, j),
+                    index(i + 1, j + 1),
+                    index(i, j + 1),
+                );
+                if (i + j) % 2 == 0 {
+                    connectivity.extend_from_slice(&[a, b, c, a, c, d]);
+                } else {
+                    connectivity.extend_from_slice(&[a, b, d, b, c, d]);
+                }
+            }
+        }
+        Self::new(nodes, ElementKind::Triangle3, connectivity)
+    }
+
+    /// [0, lx] × [0, ly] × [0, lz] split into nx × ny × nz cells of six
+    /// tetrahedra sharing the cell diagonal
+    pub fn cuboid(
+        lx: f64,
+        ly: f64,
+        lz: f64,
+        nx: usize,
+        ny: usize,
+        nz: usize,
+    ) -> Result<Self, UnstructuredMeshError> {
+        check_size(&[lx, ly, lz], &[nx, ny, nz])?;
+        let mut nodes = Vec::with_capacity((nx + 1) * (ny + 1) * (nz + 1));
+        for k in 0..=nz {
+            for j in 0..=ny {
+                for i in 0..=nx {
+                    nodes.push(DVec3::new(
+                        lx * i as f64 / nx as f64,
+                        ly * j as f64 / ny as f64,
+                        lz * k as f64 / nz as f64,
+                    ));
+                }
+            }
+        }
+        let index = |i: usize, j: usize, k: usize| (k * (ny + 1) + j) * (nx + 1) + i;
+        // Kuhn simplices: paths 000 → 111 stepping one axis at a time
+        const PATHS: [[usize; 3]; 6] = [
+            [0, 1, 2],
+            [0, 2, 1],
+            [1, 0, 2],
+            [1, 2, 0],
+            [2, 0, 1],
+            [2, 1, 0],
+        ];
+        let mut connectivity = Vec::with_capacity(24 * nx * ny * nz);
+        for k in 0..nz {
+            for j in 0..ny {
+                for i in 0..nx {
+                    for path in PATHS {
+                        let mut corner = [i, j, k];
+                        let mut tet = [index(i, j, k), 0, 0, 0];
+                        for (step, &axis) in path.iter().enumerate() {
+                            corner[axis] += 1;
+                            tet[step + 1] = index(corner[0], corner[1], corner[2]);
+                        }
+                        // Odd permutations come out left-handed
+                        if path == [0, 2, 1] || path == [1, 0, 2] || path == [2, 1, 0] {
+                            tet.swap(1, 2);
+                        }
+                        connectivity.extend_from_slice(&tet);
+                    }
+                }
+            }
+        }
+        Self::new(nodes, ElementKind::Tetra4, connectivity)
+    }
+
+    /// Quadratic mesh with a node at the midpoint of every edge
+    pub fn to_quadratic(&self) -> Self {
+        if self.kind.order() == 2 {
+            return self.clone();
+        }
+        let kind = self.kind.quadratic();
+        let mut nodes = self.nodes.clone();
+        let mut midpoints: HashMap<(usize, usize), usize> = HashMap::new();
+        let mut connectivity = Vec::with_capacity(self.element_count() * kind.node_count());
+        for element in 0..self.element_count() {
+            let corners = self.element(element);
+            connectivity.extend_from_slice(corners);
+            for &[a, b] in kind.edges() {
+                let (a, b) = (corners[a], corners[b]);
+                let node = *midpoints.entry((a.min(b), a.max(b))).or_insert_with(|| {
+                    nodes.push(0.5 * (nodes[a] + nodes[b]));
+                    nodes.len() - 1
+                });
+                connectivity.push(node);
+            }
+        }
+        Self {
+            nodes,
+            kind,
+            connectivity,
+        }
+    }
+
+    pub fn kind(&self) -> ElementKind {
+        self.kind
+    }
+
+    pub fn dimension(&self) -> usize {
+        self.kind.dimension()
+    }
+
+    pub fn nodes(&self) -> &[DVec3] {
+        &self.nodes
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn element_count(&self) -> usize {
+        self.connectivity.len() / self.kind.node_count()
+    }
+
+    /// Global node indices of an element
+    pub fn element(&self, element: usize) -> &[usize] {
+        let per = self.kind.node_count();
+        &self.connectivity[element * per..(element + 1) * per]
+    }
+
+    /// Node positions of an element
+    pub fn element_nodes(&self, element: usize) -> Vec<DVec3> {
+        self.element(element)
+            .iter()
+            .map(|&n| self.nodes[n])
+            .collect()
+    }
+
+    pub fn centroid(&self, element: usize) -> DVec3 {
+        let corners = &self.element(element)[..self.kind.corner_count()];
+        corners.iter().map(|&n| self.nodes[n]).sum::<DVec3>() / corners.len() as f64
+    }
+
+    /// Area (2D) or volume (3D) spanned by the corner nodes
+    pub fn corner_measure(&self, element: usize) -> f64 {
+        let c = self.element(element);
+        let p = |k: usize| self.nodes[c[k]];
+        match self.dimension() {
+            2 => 0.5 * (p(1) - p(0)).cross(p(2) - p(0)).z,
+            _ => (p(1) - p(0)).cross(p(2) - p(0)).dot(p(3) - p(0)) / 6.0,
+        }
+    }
+
+    /// Total area (2D) or volume (3D)
+    pub fn measure(&self) -> f64 {
+        (0..self.element_count())
+            .map(|e| self.corner_measure(e))
+            .sum()
+    }
+
+    /// Facets belonging to exactly one element
+    pub fn boundary_facets(&self) -> Vec<Facet> {
+        let corners = self.dimension();
+        let mut count: HashMap<Vec<usize>, usize> = HashMap::new();
+        let key = |nodes: &[usize]| {
+            let mut key = nodes[..corners].to_vec();
+            key.sort_unstable();
+            key
+        };
+        for element in 0..self.element_count() {
+            let nodes = self.element(element);
+            for local in self.kind.facets() {
+                let global: Vec<usize> = local.iter().map(|&l| nodes[l]).collect();
+                *count.entry(key(&global)).or_default() += 1;
+            }
+        }
+        let mut facets = Vec::new();
+        for element in 0..self.element_count() {
+            let nodes = self.element(element);
+            for (local, facet) in self.kind.facets().iter().enumerate() {
+                let global: Vec<usize> = facet.iter().map(|&l| nodes[l]).collect();
+                if count[&key(&global)] == 1 {
+                    facets.push(Facet {
+                        element,
+                        local,
+                        nodes: global,
+                    });
+                }
+            }
+        }
+        facets
+    }
+
+    /// Boundary facets whose nodes all satisfy `predicate`
+    pub fn boundary_facets_where(&self, predicate: impl Fn(DVec3) -> bool) -> Vec<Facet> {
+        self.boundary_facets()
+            .into_iter()
+            .filter(|f| f.nodes.iter().all(|&n| predicate(self.nodes[n])))
+            .collect()
+    }
+
+    /// Nodes satisfying `predicate`, ascending
+    pub fn nodes_where(&self, predicate: impl Fn(DVec3) -> bool) -> Vec<usize> {
+        (0..self.nodes.len())
+            .filter(|&n| predicate(self.nodes[n]))
+            .collect()
+    }
+
+    /// Elements sharing each node (CSR: offsets, element indices)
+    pub fn node_elements(&self) -> (Vec<usize>, Vec<usize>) {
+        let mut offsets = vec![0; self.nodes.len() + 1];
+        for &n in &self.connectivity {
+            offsets[n + 1] += 1;
+        }
+        for i in 0..self.nodes.len() {
+            offsets[i + 1] += offsets[i];
+        }
+        let mut fill = offsets.clone();
+        let mut elements = vec![0; self.connectivity.len()];
+        let per = self.kind.node_count();
+        for (k, &n) in self.connectivity.iter().enumerate() {
+            elements[fill[n]] = k / per;
+            fill[n] += 1;
+        }
+        (offsets, elements)
+    }
+}
+
+fn check_size(lengths: &[f64], cells: &[usize]) -> Result<(), UnstructuredMeshError> {
+    if lengths.iter().any(|&l| !(l > 0.0 && l.is_finite())) || cells.contains(&0) {
+        return Err(UnstructuredMeshError::InvalidParameter(format!(
+            "extent {:?} and cells {:?} must be positive",
+            lengths, cells
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rectangle_and_boundary() {
+        let mesh = UnstructuredMesh::rectangle(2.0, 1.0, 4, 3).unwrap();
+        assert_eq!(mesh.node_count(), 20);
+        assert_eq!(mesh.element_count(), 24);
+        assert!((mesh.measure() - 2.0).abs() < 1e-12);
+        let boundary = mesh.boundary_facets();
+        assert_eq!(boundary.len(), 2 * (4 + 3));
+        let left = mesh.boundary_facets_where(|p| p.x < 1e-9);
+        assert_eq!(left.len(), 3);
+        assert_eq!(mesh.nodes_where(|p| p.x < 1e-9).len(), 4);
+
+        let quadratic = mesh.to_quadratic();
+        assert_eq!(quadratic.kind(), ElementKind::Triangle6);
+        assert_eq!(quadratic.node_count(), 9 * 7);
+        for facet in quadratic.boundary_facets() {
+            let p: Vec<DVec3> = facet.nodes.iter().map(|&n| quadratic.nodes()[n]).collect();
+            assert!((p[2] - 0.5 * (p[0] + p[1])).length() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_cuboid_is_conforming() {
+        let mesh = UnstructuredMesh::cuboid(1.0, 2.0, 3.0, 2, 3, 4).unwrap();
+        assert_eq!(mesh.element_count(), 6 * 24);
+        assert!((mesh.measure() - 6.0).abs() < 1e-12);
+        // Interior faces pair up exactly: only the 2 triangles per outer
+        // cell face remain
+        let boundary = mesh.boundary_facets();
+        assert_eq!(boundary.len(), 2 * 2 * (2 * 3 + 3 * 4 + 2 * 4));
+        // Boundary faces wind outward
+        let centre = DVec3::new(0.5, 1.0, 1.5);
+        for facet in &boundary {
+            let p: Vec<DVec3> = facet.nodes.iter().map(|&n| mesh.nodes()[n]).collect();
+            let normal = (p[1] - p[0]).cross(p[2] - p[0]);
+            assert!(normal.dot(p[0] - centre) > 0.0);
+        }
+        let quadratic = mesh.to_quadratic();
+        assert_eq!(quadratic.node_count(), 5 * 7 * 9);
+        assert_eq!(quadratic.boundary_facets().len(), boundary.len());
+    }
+
+    #[test]
+    fn test_validation() {
+        let nodes = vec![DVec3::ZERO, DVec3::X, DVec3::Y];
+        assert!(
+            UnstructuredMesh::new(nodes.clone(), ElementKind::Triangle3, vec![0, 1, 2]).is_ok()
+        );
+        assert_eq!(
+            UnstructuredMesh::new(nodes.clone(), ElementKind::Triangle3, vec![0, 2, 1]),
+            Err(UnstructuredMeshError::Inverted { element: 0 })
+        );
+        assert_eq!(
+            UnstructuredMesh::new(nodes, ElementKind::Triangle3, vec![0, 1, 3]),
+            Err(UnstructuredMeshError::MissingNode {
+                element: 0,
+                node: 3
+            })
+        );
+        assert!(UnstructuredMesh::rectangle(1.0, 1.0, 0, 1).is_err());
+    }
+}
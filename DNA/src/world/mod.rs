@@ -10,7 +10,7 @@
 //! - coordinates/  - Cartesian, spherical (cylindrical, polar - future)
 //! - transforms/   - Astronomical coordinate transforms
 //! - topology/     - Toroidal wrap-around boundaries
-//! - grid/         - Spatial grids (future)
+//! - grid/         - Unstructured FEM meshes (uniform, adaptive - future)
 //! - units.rs      - Physical units and conversions
 //! - cca/          - Conformal Celestial Algebra (CGA + SE(3) Lie groups)
 //!
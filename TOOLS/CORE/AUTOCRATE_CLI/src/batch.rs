@@ -0,0 +1,481 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: batch.rs | TOOLS/CORE/AUTOCRATE_CLI/src/batch.rs
+//! PURPOSE: Load one or many CrateSpec entries from JSON, TOML or CSV files
+//! MODIFIED: 2026-10-18
+//! LAYER: TOOLS → CORE → AUTOCRATE_CLI
+//! ═══════════════════════════════════════════════════════════════════════════════
+//!
+//! Every entry is merged onto `CrateSpec::default()`, so a spec file only needs
+//! the fields that differ (usually just the product):
+//!
+//! ┌────────┬──────────────────────────────────────────────────────────────────┐
+//! │ Format │ Layout                                                           │
+//! ├────────┼──────────────────────────────────────────────────────────────────┤
+//! │ JSON   │ one spec object, an array of specs, or {"defaults", "crates"}    │
+//! │ TOML   │ one spec table, or [defaults] + [[crates]]                       │
+//! │ CSV    │ one crate per row; headers are field paths ("clearances.top")    │
+//! │        │ or short aliases (length, width, height, weight, standard, ...)  │
+//! └────────┴──────────────────────────────────────────────────────────────────┘
+//!
+//! Entries may carry a `name`, used for the output folder; unnamed entries are
+//! called `<file stem>-<n>`. A malformed entry yields an `Err` spec for that
+//! entry only, so one bad CSV row does not stop a batch.
+
+use anyhow::{bail, Context, Result};
+use autocrate_engine::{BaseStyle, CrateSpec, CrateStandard, CrateStyle, LumberSize};
+use serde_json::{Map, Value};
+use std::path::Path;
+
+/// One crate to build, as read from a spec file
+#[derive(Clone, Debug)]
+pub struct SpecEntry {
+    /// Output name (sanitised, unique within its file)
+    pub name: String,
+    /// "file" for whole-file specs, "file:row" / "file#n" for batch entries
+    pub source: String,
+    /// Parsed spec, or the reason this entry could not be read
+    pub spec: Result<CrateSpec, String>,
+}
+
+/// Spec file formats, chosen by extension
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpecFormat {
+    Json,
+    Toml,
+    Csv,
+}
+
+impl SpecFormat {
+    pub fn from_path(path: &Path) -> Option<SpecFormat> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "json" => Some(SpecFormat::Json),
+            "toml" => Some(SpecFormat::Toml),
+            "csv" => Some(SpecFormat::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// Read every spec entry in `path`
+pub fn load_specs(path: &Path) -> Result<Vec<SpecEntry>> {
+    let format = SpecFormat::from_path(path).with_context(|| {
+        format!(
+            "{}: unknown spec format (use .json, .toml or .csv)",
+            path.display()
+        )
+    })?;
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("crate");
+    let source = path.display().to_string();
+    parse_specs(&text, format, stem, &source)
+}
+
+/// Parse spec entries from `text`; `stem` names unnamed entries
+pub fn parse_specs(
+    text: &str,
+    format: SpecFormat,
+    stem: &str,
+    source: &str,
+) -> Result<Vec<SpecEntry>> {
+    let mut entries = match format {
+        SpecFormat::Json => {
+            let value: Value =
+                serde_json::from_str(text).with_context(|| format!("{}: invalid JSON", source))?;
+            document_entries(value, stem, source)?
+        }
+        SpecFormat::Toml => {
+            let value: toml::Value =
+                toml::from_str(text).with_context(|| format!("{}: invalid TOML", source))?;
+            let value = serde_json::to_value(value)
+                .with_context(|| format!("{}: unsupported TOML value", source))?;
+            document_entries(value, stem, source)?
+        }
+        SpecFormat::Csv => csv_entries(text, stem, source)?,
+    };
+    dedupe_names(&mut entries);
+    Ok(entries)
+}
+
+/// Make output names unique by suffixing repeats with "-2", "-3", ...
+pub fn dedupe_names(entries: &mut [SpecEntry]) {
+    let mut seen = std::collections::HashSet::new();
+    for entry in entries.iter_mut() {
+        let base = entry.name.clone();
+        let mut n = 1;
+        while !seen.insert(entry.name.to_ascii_lowercase()) {
+            n += 1;
+            entry.name = format!("{}-{}", base, n);
+        }
+    }
+}
+
+/// Reduce a crate name to characters safe in a folder name
+pub fn sanitize_name(name: &str) -> String {
+    let cleaned: String = name
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let cleaned = cleaned.trim_matches('_').to_string();
+    if cleaned.is_empty() {
+        "crate".to_string()
+    } else {
+        cleaned
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// JSON / TOML documents
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn document_entries(value: Value, stem: &str, source: &str) -> Result<Vec<SpecEntry>> {
+    let mut base = default_spec_value();
+    let (items, single) = match value {
+        Value::Array(items) => (items, false),
+        Value::Object(mut map) if map.contains_key("crates") => {
+            if let Some(defaults) = map.remove("defaults") {
+                if !defaults.is_object() {
+                    bail!("{}: \"defaults\" must be an object", source);
+                }
+                merge(&mut base, defaults);
+            }
+            match map.remove("crates") {
+                Some(Value::Array(items)) => (items, false),
+                _ => bail!("{}: \"crates\" must be an array", source),
+            }
+        }
+        Value::Object(map) => (vec![Value::Object(map)], true),
+        _ => bail!("{}: expected a spec object or a list of specs", source),
+    };
+
+    Ok(items
+        .into_iter()
+        .enumerate()
+        .map(|(i, item)| {
+            let entry_source = if single {
+                source.to_string()
+            } else {
+                format!("{}#{}", source, i + 1)
+            };
+            let fallback = if single {
+                stem.to_string()
+            } else {
+                format!("{}-{}", stem, i + 1)
+            };
+            match item {
+                Value::Object(mut fields) => {
+                    let name = take_name(&mut fields).unwrap_or(fallback);
+                    let mut spec = base.clone();
+                    merge(&mut spec, Value::Object(fields));
+                    SpecEntry {
+                        name: sanitize_name(&name),
+                        source: entry_source,
+                        spec: serde_json::from_value(spec).map_err(|e| e.to_string()),
+                    }
+                }
+                _ => SpecEntry {
+                    name: sanitize_name(&fallback),
+                    source: entry_source,
+                    spec: Err("entry is not an object".to_string()),
+                },
+            }
+        })
+        .collect())
+}
+
+fn take_name(fields: &mut Map<String, Value>) -> Option<String> {
+    match fields.remove("name")? {
+        Value::String(s) => Some(s),
+        other => Some(other.to_string()),
+    }
+}
+
+fn default_spec_value() -> Value {
+    serde_json::to_value(CrateSpec::default()).expect("CrateSpec serialises")
+}
+
+/// Deep-merge `patch` into `target`; objects merge key by key, anything else replaces
+fn merge(target: &mut Value, patch: Value) {
+    match (target, patch) {
+        (Value::Object(target), Value::Object(patch)) => {
+            for (key, value) in patch {
+                match target.get_mut(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        target.insert(key, value);
+                    }
+                }
+            }
+        }
+        (target, patch) => *target = patch,
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// CSV rows
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Short column names accepted in CSV headers
+const CSV_ALIASES: &[(&str, &str)] = &[
+    ("length", "product.length"),
+    ("width", "product.width"),
+    ("height", "product.height"),
+    ("weight", "product.weight"),
+    ("standard", "requirements.standard"),
+    ("style", "requirements.style"),
+    ("base", "requirements.base"),
+    ("lift_cleats", "requirements.lift_cleats"),
+    ("shipping_mode", "requirements.shipping_mode"),
+    ("side_clearance", "clearances.side"),
+    ("end_clearance", "clearances.end"),
+    ("top_clearance", "clearances.top"),
+];
+
+fn csv_entries(text: &str, stem: &str, source: &str) -> Result<Vec<SpecEntry>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .comment(Some(b'#'))
+        .from_reader(text.as_bytes());
+    let headers: Vec<String> = reader
+        .headers()
+        .with_context(|| format!("{}: missing CSV header", source))?
+        .iter()
+        .map(|h| {
+            let h = h.to_ascii_lowercase();
+            CSV_ALIASES
+                .iter()
+                .find(|(alias, _)| *alias == h)
+                .map(|(_, path)| path.to_string())
+                .unwrap_or(h)
+        })
+        .collect();
+
+    let base = default_spec_value();
+    let mut entries = Vec::new();
+    for (i, record) in reader.records().enumerate() {
+        // Header is line 1
+        let line = record
+            .as_ref()
+            .ok()
+            .and_then(|r| r.position())
+            .map(|p| p.line())
+            .unwrap_or(i as u64 + 2);
+        let entry_source = format!("{}:{}", source, line);
+        let mut name = format!("{}-{}", stem, i + 1);
+        let spec = match record {
+            Ok(record) => {
+                let mut spec = base.clone();
+                let mut result = Ok(());
+                for (header, cell) in headers.iter().zip(record.iter()) {
+                    if cell.is_empty() {
+                        continue;
+                    }
+                    if header == "name" {
+                        name = cell.to_string();
+                        continue;
+                    }
+                    if let Err(e) =
+                        cell_value(header, cell).and_then(|v| set_path(&mut spec, header, v))
+                    {
+                        result = Err(e);
+                        break;
+                    }
+                }
+                result.and_then(|_| serde_json::from_value(spec).map_err(|e| e.to_string()))
+            }
+            Err(e) => Err(e.to_string()),
+        };
+        entries.push(SpecEntry {
+            name: sanitize_name(&name),
+            source: entry_source,
+            spec,
+        });
+    }
+    Ok(entries)
+}
+
+/// Convert a CSV cell to the JSON value its column expects
+fn cell_value(path: &str, cell: &str) -> Result<Value, String> {
+    if path.ends_with("_size") {
+        let size = LumberSize::from_name(cell)
+            .ok_or_else(|| format!("{}: unknown lumber size \"{}\"", path, cell))?;
+        return serde_json::to_value(size).map_err(|e| e.to_string());
+    }
+    let variants: Vec<(Value, &str)> = match path {
+        "requirements.standard" => [
+            CrateStandard::AstmD6039,
+            CrateStandard::MilC104,
+            CrateStandard::AstmD6251,
+        ]
+        .iter()
+        .map(|s| (serde_json::to_value(s).unwrap_or_default(), s.name()))
+        .collect(),
+        "requirements.style" => [
+            CrateStyle::Sheathed,
+            CrateStyle::Open,
+            CrateStyle::NailedBox,
+        ]
+        .iter()
+        .map(|s| (serde_json::to_value(s).unwrap_or_default(), s.name()))
+        .collect(),
+        "requirements.base" => [BaseStyle::Skids, BaseStyle::SubBase]
+            .iter()
+            .map(|s| (serde_json::to_value(s).unwrap_or_default(), ""))
+            .collect(),
+        _ => Vec::new(),
+    };
+    if !variants.is_empty() {
+        let key = normalize(cell);
+        return variants
+            .into_iter()
+            .find(|(value, name)| {
+                value.as_str().map(normalize).as_deref() == Some(key.as_str())
+                    || normalize(name) == key
+            })
+            .map(|(value, _)| value)
+            .ok_or_else(|| format!("{}: unknown value \"{}\"", path, cell));
+    }
+
+    match cell.to_ascii_lowercase().as_str() {
+        "true" | "yes" | "y" => return Ok(Value::Bool(true)),
+        "false" | "no" | "n" => return Ok(Value::Bool(false)),
+        _ => {}
+    }
+    if let Ok(n) = cell.parse::<i64>() {
+        return Ok(Value::from(n));
+    }
+    if let Ok(x) = cell.parse::<f64>() {
+        return serde_json::Number::from_f64(x)
+            .map(Value::Number)
+            .ok_or_else(|| format!("{}: \"{}\" is not a finite number", path, cell));
+    }
+    Ok(Value::String(cell.to_string()))
+}
+
+/// Lowercase alphanumerics only, so "ASTM D6039" matches "AstmD6039"
+fn normalize(s: &str) -> String {
+    s.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// Set a dotted field path inside the spec value; the path must already exist
+fn set_path(spec: &mut Value, path: &str, value: Value) -> Result<(), String> {
+    let mut node = spec;
+    let mut parts = path.split('.').peekable();
+    while let Some(part) = parts.next() {
+        let map = node
+            .as_object_mut()
+            .ok_or_else(|| format!("{}: \"{}\" is not a group", path, part))?;
+        if parts.peek().is_none() {
+            // Optional fields serialise as null, so every real field exists
+            if !map.contains_key(part) {
+                return Err(format!("unknown column \"{}\"", path));
+            }
+            map.insert(part.to_string(), value);
+            return Ok(());
+        }
+        node = map
+            .get_mut(part)
+            .ok_or_else(|| format!("unknown column \"{}\"", path))?;
+    }
+    Err(format!("unknown column \"{}\"", path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_batch_with_defaults() {
+        let text = r#"{
+            "defaults": { "requirements": { "lift_cleats": true }, "skid_count": 4 },
+            "crates": [
+                { "name": "Pump A", "product": { "length": 48, "width": 36, "height": 40, "weight": 900 } },
+                { "product": { "length": 60 }, "skid_count": 2 },
+                { "product": { "length": "long" } }
+            ]
+        }"#;
+        let entries = parse_specs(text, SpecFormat::Json, "order", "order.json").unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].name, "Pump_A");
+        assert_eq!(entries[1].name, "order-2");
+        assert_eq!(entries[1].source, "order.json#2");
+
+        let a = entries[0].spec.as_ref().unwrap();
+        assert_eq!(a.product.weight, 900.0);
+        assert!(a.requirements.lift_cleats);
+        assert_eq!(a.skid_count, 4);
+        // Unspecified fields keep the built-in defaults
+        assert_eq!(a.clearances.top, CrateSpec::default().clearances.top);
+
+        assert_eq!(entries[1].spec.as_ref().unwrap().skid_count, 2);
+        assert!(entries[2].spec.is_err());
+    }
+
+    #[test]
+    fn test_toml_single_and_list() {
+        let single = "[product]\nlength = 72.0\nwidth = 40.0\nheight = 30.0\nweight = 1500.0\n";
+        let entries = parse_specs(single, SpecFormat::Toml, "pump", "pump.toml").unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].name, "pump");
+        assert_eq!(entries[0].spec.as_ref().unwrap().product.length, 72.0);
+
+        let list = "[defaults.clearances]\ntop = 4.0\n\n[[crates]]\nname = \"a\"\n[crates.product]\nlength = 50.0\n\n[[crates]]\nname = \"a\"\n";
+        let entries = parse_specs(list, SpecFormat::Toml, "batch", "batch.toml").unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].name, "a-2");
+        assert_eq!(entries[0].spec.as_ref().unwrap().clearances.top, 4.0);
+    }
+
+    #[test]
+    fn test_csv_rows_aliases_and_errors() {
+        let text =
+            "name,length,width,height,weight,standard,skid_size,lift_cleats,clearances.side\n\
+                    Motor,48,36,40,1200,MIL-C-104,4x6,yes,3\n\
+                    Frame,60,40,30,800,,,,\n\
+                    Bad,48,36,40,900,ASTM X1,,,\n\
+                    Worse,48,36,40,900,,9x9,,\n";
+        let entries = parse_specs(text, SpecFormat::Csv, "quote", "quote.csv").unwrap();
+        assert_eq!(entries.len(), 4);
+
+        let motor = entries[0].spec.as_ref().unwrap();
+        assert_eq!(entries[0].source, "quote.csv:2");
+        assert_eq!(motor.product.length, 48.0);
+        assert_eq!(motor.requirements.standard, CrateStandard::MilC104);
+        assert_eq!(motor.skid_size, LumberSize::L4x6);
+        assert!(motor.requirements.lift_cleats);
+        assert_eq!(motor.clearances.side, 3.0);
+
+        let frame = entries[1].spec.as_ref().unwrap();
+        assert_eq!(
+            frame.requirements.standard,
+            CrateSpec::default().requirements.standard
+        );
+
+        assert!(entries[2].spec.as_ref().unwrap_err().contains("ASTM X1"));
+        assert!(entries[3].spec.as_ref().unwrap_err().contains("9x9"));
+
+        let unknown = parse_specs("colour\nred\n", SpecFormat::Csv, "x", "x.csv").unwrap();
+        assert!(unknown[0]
+            .spec
+            .as_ref()
+            .unwrap_err()
+            .contains("unknown column"));
+    }
+
+    #[test]
+    fn test_sanitize_name() {
+        assert_eq!(sanitize_name("  PO 1234 / rev B "), "PO_1234___rev_B");
+        assert_eq!(sanitize_name("../"), "crate");
+    }
+}
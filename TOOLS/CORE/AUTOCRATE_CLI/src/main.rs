@@ -0,0 +1,604 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: main.rs | TOOLS/CORE/AUTOCRATE_CLI/src/main.rs
+//! PURPOSE: Batch CLI: crate spec files → STEP, BOM, cut list, drawings, quotes
+//! MODIFIED: 2026-10-18
+//! LAYER: TOOLS → CORE → AUTOCRATE_CLI
+//! ═══════════════════════════════════════════════════════════════════════════════
+//!
+//! Usage:
+//!   autocrate-cli validate specs.csv                    # Check specs, write nothing
+//!   autocrate-cli build specs.csv -o out/               # One folder per crate + summary.csv
+//!   autocrate-cli build a.json b.toml -o out/ --prices supplier.csv --strict
+//!   autocrate-cli build specs.csv -o out/ --apply-standard # Size members per standard
+//!
+//! Per-crate output (out/<name>/):
+//!
+//! ┌──────────────────────┬─────────────────────────────────────────────────────┐
+//! │ File                 │ Contents                                            │
+//! ├──────────────────────┼─────────────────────────────────────────────────────┤
+//! │ <name>.step          │ AP242 assembly (inches), NX-importable              │
+//! │ bom.csv              │ Bill of materials                                   │
+//! │ cut_list.csv         │ Cut list                                            │
+//! │ purchase_list.csv    │ Stock boards and plywood sheets to buy              │
+//! │ drawings.pdf / .dxf  │ Shop drawing set (skipped with --no-drawings)       │
+//! │ quote.csv / .pdf     │ Priced quote (only with --prices)                   │
+//! └──────────────────────┴─────────────────────────────────────────────────────┘
+//!
+//! Exits non-zero if any crate is invalid (or has warnings, with --strict).
+
+mod batch;
+
+use anyhow::{Context, Result};
+use autocrate_engine::{
+    analyze_structure, calculate_board_feet, check_requirements, design_from_spec, export_bom_csv,
+    export_cut_list_csv, export_drawings_dxf, export_drawings_pdf, export_purchase_list_csv,
+    export_step, quote_design, quote_to_csv, quote_to_pdf, validate_spec, CrateDesign, CrateSpec,
+    PriceList, QuoteOptions, StructuralOptions,
+};
+use batch::{load_specs, SpecEntry};
+use clap::{Parser, Subcommand};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::process;
+
+#[derive(Parser)]
+#[command(name = "autocrate-cli")]
+#[command(about = "Batch-build shipping crates from spec files", long_about = None)]
+struct Cli {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Validate spec files without writing outputs
+    Validate {
+        /// Spec files (.json, .toml or .csv)
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+
+        /// Treat standard and structural warnings as errors
+        #[arg(long)]
+        strict: bool,
+
+        /// Size skids, floorboards and cleats from each spec's standard
+        #[arg(long)]
+        apply_standard: bool,
+    },
+
+    /// Build every crate and write its outputs into a folder
+    Build {
+        /// Spec files (.json, .toml or .csv)
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+
+        /// Output directory (one sub-folder per crate)
+        #[arg(short, long, default_value = "autocrate-out")]
+        output: PathBuf,
+
+        /// Supplier price list (.json or .csv) for quotes
+        #[arg(short, long)]
+        prices: Option<PathBuf>,
+
+        /// Labor rate for quotes ($/hour)
+        #[arg(long)]
+        labor_rate: Option<f32>,
+
+        /// Gross margin for quotes (percent of selling price)
+        #[arg(long)]
+        margin: Option<f32>,
+
+        /// Treat standard and structural warnings as errors
+        #[arg(long)]
+        strict: bool,
+
+        /// Size skids, floorboards and cleats from each spec's standard
+        #[arg(long)]
+        apply_standard: bool,
+
+        /// Skip the PDF/DXF drawing sets
+        #[arg(long)]
+        no_drawings: bool,
+    },
+}
+
+/// Outcome of one crate in the batch
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Status {
+    Ok,
+    Warnings,
+    Rejected,
+    Invalid,
+}
+
+impl Status {
+    fn name(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Warnings => "warnings",
+            Status::Rejected => "rejected",
+            Status::Invalid => "invalid",
+        }
+    }
+
+    fn failed(&self) -> bool {
+        matches!(self, Status::Rejected | Status::Invalid)
+    }
+}
+
+/// One row of summary.csv
+struct CrateResult {
+    name: String,
+    source: String,
+    status: Status,
+    spec: Option<CrateSpec>,
+    /// Design that was checked (valid specs only); reused for the exports
+    design: Option<CrateDesign>,
+    overall: Option<(f32, f32, f32)>,
+    parts: usize,
+    board_feet: f32,
+    quote_total: Option<f32>,
+    messages: Vec<String>,
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Commands::Validate {
+            files,
+            strict,
+            apply_standard,
+        } => run_validate(&files, strict, apply_standard),
+        Commands::Build {
+            files,
+            output,
+            prices,
+            labor_rate,
+            margin,
+            strict,
+            apply_standard,
+            no_drawings,
+        } => {
+            let mut quote = QuoteOptions::default();
+            if let Some(rate) = labor_rate {
+                quote.hourly_rate = rate;
+            }
+            if let Some(margin) = margin {
+                quote.margin_pct = margin;
+            }
+            run_build(
+                &files,
+                &output,
+                prices.as_deref(),
+                &quote,
+                strict,
+                apply_standard,
+                !no_drawings,
+            )
+        }
+    };
+
+    match result {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            process::exit(2);
+        }
+    }
+}
+
+/// Load all entries from all files, keeping output names unique across files
+fn load_all(files: &[PathBuf]) -> Result<Vec<SpecEntry>> {
+    let mut entries = Vec::new();
+    for file in files {
+        entries.extend(load_specs(file)?);
+    }
+    batch::dedupe_names(&mut entries);
+    Ok(entries)
+}
+
+/// Validate an entry: parse errors and limit violations are errors, standard
+/// and structural findings are warnings. `apply_standard` opts the spec into
+/// its standard's member sizing before anything is checked.
+fn check_entry(entry: &SpecEntry, strict: bool, apply_standard: bool) -> CrateResult {
+    let mut result = CrateResult {
+        name: entry.name.clone(),
+        source: entry.source.clone(),
+        status: Status::Ok,
+        spec: None,
+        design: None,
+        overall: None,
+        parts: 0,
+        board_feet: 0.0,
+        quote_total: None,
+        messages: Vec::new(),
+    };
+
+    let mut spec = match &entry.spec {
+        Ok(spec) => spec.clone(),
+        Err(e) => {
+            result.status = Status::Invalid;
+            result.messages.push(e.clone());
+            return result;
+        }
+    };
+
+    spec.requirements.apply_standard |= apply_standard;
+    let errors = validate_spec(&spec);
+    if !errors.is_empty() {
+        result.status = Status::Invalid;
+        result.messages = errors;
+        return result;
+    }
+
+    let design = design_from_spec(&spec);
+    let report = analyze_structure(&spec, &design.geometry, &StructuralOptions::default());
+    result.messages = check_requirements(&spec);
+    result.messages.extend(report.failures().map(|c| {
+        format!(
+            "{}: {} {:.1} {} vs capacity {:.1} {}",
+            c.member,
+            c.kind.name(),
+            c.demand,
+            c.units,
+            c.capacity,
+            c.units
+        )
+    }));
+    if !result.messages.is_empty() {
+        result.status = if strict {
+            Status::Rejected
+        } else {
+            Status::Warnings
+        };
+    }
+
+    let g = &design.geometry;
+    result.overall = Some((g.overall_length, g.overall_width, g.overall_height));
+    result.parts = design.parts.len();
+    result.board_feet = calculate_board_feet(g);
+    result.spec = Some(design.spec.clone());
+    result.design = Some(design);
+    result
+}
+
+fn run_validate(files: &[PathBuf], strict: bool, apply_standard: bool) -> Result<bool> {
+    let results: Vec<CrateResult> = load_all(files)?
+        .iter()
+        .map(|e| check_entry(e, strict, apply_standard))
+        .collect();
+    print_summary(&results);
+    Ok(!results.iter().any(|r| r.status.failed()))
+}
+
+fn run_build(
+    files: &[PathBuf],
+    output: &Path,
+    prices: Option<&Path>,
+    quote_options: &QuoteOptions,
+    strict: bool,
+    apply_standard: bool,
+    drawings: bool,
+) -> Result<bool> {
+    let prices = prices.map(load_prices).transpose()?;
+    let entries = load_all(files)?;
+    std::fs::create_dir_all(output)
+        .with_context(|| format!("Failed to create {}", output.display()))?;
+
+    let mut results = Vec::with_capacity(entries.len());
+    for entry in &entries {
+        let mut result = check_entry(entry, strict, apply_standard);
+        let design = result.design.take();
+        let Some(design) = design.filter(|_| !result.status.failed()) else {
+            results.push(result);
+            continue;
+        };
+
+        // Quote first: unpriced items can still reject the crate under --strict
+        let quote = prices
+            .as_ref()
+            .map(|prices| quote_design(&design, prices, quote_options));
+        if let Some(quote) = &quote {
+            result.quote_total = Some(quote.total());
+            if !quote.missing_prices.is_empty() {
+                // Unpriced items count as $0, so the total is too low
+                result.messages.push(format!(
+                    "{} quote items without a price (listed in quote.pdf)",
+                    quote.missing_prices.len()
+                ));
+                result.status = if strict {
+                    Status::Rejected
+                } else {
+                    Status::Warnings
+                };
+            }
+        }
+
+        if !result.status.failed() {
+            let dir = output.join(&result.name);
+            std::fs::create_dir_all(&dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+
+            write(
+                &dir.join(format!("{}.step", result.name)),
+                export_step(&design),
+            )?;
+            write(&dir.join("bom.csv"), export_bom_csv(&design))?;
+            write(&dir.join("cut_list.csv"), export_cut_list_csv(&design))?;
+            write(
+                &dir.join("purchase_list.csv"),
+                export_purchase_list_csv(&design),
+            )?;
+            if drawings {
+                write(&dir.join("drawings.pdf"), export_drawings_pdf(&design))?;
+                write(&dir.join("drawings.dxf"), export_drawings_dxf(&design))?;
+            }
+            if let Some(quote) = &quote {
+                write(&dir.join("quote.csv"), quote_to_csv(quote))?;
+                write(
+                    &dir.join("quote.pdf"),
+                    quote_to_pdf(quote, &format!("Quote - {}", result.name)),
+                )?;
+            }
+        }
+        results.push(result);
+    }
+
+    write(&output.join("summary.csv"), summary_csv(&results))?;
+    print_summary(&results);
+    println!("\nWrote {} (summary.csv)", output.display());
+    Ok(!results.iter().any(|r| r.status.failed()))
+}
+
+fn load_prices(path: &Path) -> Result<PriceList> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let is_csv = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("csv"));
+    let list = if is_csv {
+        let supplier = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("supplier");
+        PriceList::from_csv(supplier, &text)
+    } else {
+        PriceList::from_json(&text)
+    };
+    list.with_context(|| format!("{}: invalid price list", path.display()))
+}
+
+fn write(path: &Path, contents: impl AsRef<[u8]>) -> Result<()> {
+    std::fs::write(path, contents).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn summary_csv(results: &[CrateResult]) -> String {
+    let mut out = String::from(
+        "name,source,status,product_length,product_width,product_height,product_weight,\
+         overall_length,overall_width,overall_height,parts,board_feet,quote_total,messages\n",
+    );
+    for r in results {
+        let product = r
+            .spec
+            .as_ref()
+            .map(|s| {
+                format!(
+                    "{},{},{},{}",
+                    s.product.length, s.product.width, s.product.height, s.product.weight
+                )
+            })
+            .unwrap_or_else(|| ",,,".to_string());
+        let overall = r
+            .overall
+            .map(|(l, w, h)| format!("{:.2},{:.2},{:.2}", l, w, h))
+            .unwrap_or_else(|| ",,".to_string());
+        let quote = r
+            .quote_total
+            .map(|t| format!("{:.2}", t))
+            .unwrap_or_default();
+        let _ = writeln!(
+            out,
+            "{},{},{},{},{},{},{:.1},{},{}",
+            csv_field(&r.name),
+            csv_field(&r.source),
+            r.status.name(),
+            product,
+            overall,
+            r.parts,
+            r.board_feet,
+            quote,
+            csv_field(&r.messages.join("; "))
+        );
+    }
+    out
+}
+
+fn print_summary(results: &[CrateResult]) {
+    println!(
+        "{:<24} {:<9} {:>22} {:>6} {:>8} {:>11}",
+        "CRATE", "STATUS", "OVERALL (in)", "PARTS", "BD FT", "QUOTE"
+    );
+    for r in results {
+        let overall = r
+            .overall
+            .map(|(l, w, h)| format!("{:.1} x {:.1} x {:.1}", l, w, h))
+            .unwrap_or_default();
+        let quote = r
+            .quote_total
+            .map(|t| format!("${:.2}", t))
+            .unwrap_or_default();
+        println!(
+            "{:<24} {:<9} {:>22} {:>6} {:>8.1} {:>11}",
+            r.name,
+            r.status.name(),
+            overall,
+            r.parts,
+            r.board_feet,
+            quote
+        );
+        for m in &r.messages {
+            println!("    - {}", m);
+        }
+    }
+
+    let count = |s: Status| results.iter().filter(|r| r.status == s).count();
+    println!(
+        "\n{} crates: {} ok, {} with warnings, {} rejected, {} invalid",
+        results.len(),
+        count(Status::Ok),
+        count(Status::Warnings),
+        count(Status::Rejected),
+        count(Status::Invalid)
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_prices_downgrade_status() {
+        let dir = std::env::temp_dir().join(format!("autocrate-cli-prices-{}", process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let specs = dir.join("specs.json");
+        std::fs::write(
+            &specs,
+            r#"{ "name": "pump", "product": { "length": 24, "width": 18, "height": 12, "weight": 100 } }"#,
+        )
+        .unwrap();
+        let prices = dir.join("prices.csv");
+        std::fs::write(&prices, "kind,key,length_in,price\n").unwrap();
+        let options = QuoteOptions::default();
+        let status = |out: &str| {
+            let summary = std::fs::read_to_string(dir.join(out).join("summary.csv")).unwrap();
+            summary
+                .lines()
+                .nth(1)
+                .unwrap()
+                .split(',')
+                .nth(2)
+                .unwrap()
+                .to_string()
+        };
+
+        let files = [specs.clone()];
+        assert!(run_build(
+            &files,
+            &dir.join("plain"),
+            None,
+            &options,
+            false,
+            false,
+            false
+        )
+        .unwrap());
+        assert_eq!(status("plain"), "ok");
+
+        let priced = Some(prices.as_path());
+        assert!(run_build(
+            &files,
+            &dir.join("loose"),
+            priced,
+            &options,
+            false,
+            false,
+            false
+        )
+        .unwrap());
+        assert_eq!(status("loose"), "warnings");
+
+        assert!(!run_build(
+            &files,
+            &dir.join("strict"),
+            priced,
+            &options,
+            true,
+            false,
+            false
+        )
+        .unwrap());
+        assert_eq!(status("strict"), "rejected");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_strict_rejection_writes_no_outputs() {
+        let dir = std::env::temp_dir().join(format!("autocrate-cli-strict-{}", process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let specs = dir.join("specs.json");
+        std::fs::write(
+            &specs,
+            r#"{ "name": "pump", "product": { "length": 24, "width": 18, "height": 12, "weight": 100 } }"#,
+        )
+        .unwrap();
+        let prices = dir.join("prices.csv");
+        std::fs::write(&prices, "kind,key,length_in,price\n").unwrap();
+        let options = QuoteOptions::default();
+        let files = [specs.clone()];
+        let priced = Some(prices.as_path());
+
+        assert!(run_build(
+            &files,
+            &dir.join("loose"),
+            priced,
+            &options,
+            false,
+            false,
+            false
+        )
+        .unwrap());
+        assert!(dir.join("loose/pump/pump.step").exists());
+
+        assert!(!run_build(
+            &files,
+            &dir.join("strict"),
+            priced,
+            &options,
+            true,
+            false,
+            false
+        )
+        .unwrap());
+        assert!(dir.join("strict/summary.csv").exists());
+        assert!(!dir.join("strict/pump/pump.step").exists());
+        assert!(!dir.join("strict/pump").exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_apply_standard_resizes_members() {
+        let mut spec = CrateSpec::default();
+        spec.product.weight = 12000.0;
+        spec.requirements.standard = autocrate_engine::CrateStandard::MilC104;
+        let entry = SpecEntry {
+            name: "press".to_string(),
+            source: "press.json".to_string(),
+            spec: Ok(spec),
+        };
+
+        let as_written = check_entry(&entry, false, false);
+        assert!(as_written
+            .messages
+            .iter()
+            .any(|m| m.starts_with("Skid 4x4")));
+
+        let sized = check_entry(&entry, false, true);
+        assert!(!sized.messages.iter().any(|m| m.starts_with("Skid 4x4")));
+        let sized_spec = sized.spec.unwrap();
+        assert!(sized_spec.requirements.apply_standard);
+        assert_eq!(sized_spec.skid_count, 4);
+        assert_eq!(sized_spec.skid_size, autocrate_engine::LumberSize::L6x6);
+    }
+}
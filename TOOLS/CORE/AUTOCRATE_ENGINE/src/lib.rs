@@ -1,7 +1,7 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: lib.rs | TOOLS/CORE/AUTOCRATE_ENGINE/src/lib.rs
 //! PURPOSE: ASTM-standard shipping crate design automation engine
-//! MODIFIED: 2025-12-09
+//! MODIFIED: 2026-10-18
 //! LAYER: CORE → AUTOCRATE_ENGINE
 //! ═══════════════════════════════════════════════════════════════════════════════
 //!
@@ -11,6 +11,11 @@
 //! - Panel geometry (front/back/left/right/top)
 //! - Cleat positioning
 //! - Lumber bill of materials
+//! - Cutting plans (lumber cutting stock, plywood nesting, purchase list)
+//! - Quotes (supplier price lists, labor estimates, margin; CSV/PDF)
+//! - Shop drawing sets (orthographic, isometric, exploded views; PDF/DXF)
+//! - Standard profiles (ASTM D6039, MIL-C-104, ASTM D6251), sub-bases, lift cleats
+//! - Structural checks (skids, floorboards, panels, stacking) with margins
 //!
 //! ┌─────────────────────────────────────────────────────────────────────────────┐
 //! │ ARCHITECTURE                                                                │
@@ -21,7 +26,8 @@
 //! │       ├── CrateSpec           (DNA/autocrate/types)                         │
 //! │       ├── CrateGeometry       (DNA/autocrate/types)                         │
 //! │       ├── LumberSize          (DNA/autocrate/constants)                     │
-//! │       └── calculate_crate()   (DNA/autocrate/calculator)                    │
+//! │       ├── calculate_crate()   (DNA/autocrate/calculator)                    │
+//! │       └── analyze_structure() (DNA/autocrate/structural)                    │
 //! │                                                                             │
 //! │   Design flow:                                                              │
 //! │   1. Specify product dimensions (L x W x H, weight)                         │
@@ -48,6 +54,7 @@
 pub use dna::autocrate::{
     // Calculator
     calculate_crate,
+    BaseStyle,
     BoardGeometry,
     BoundingBox,
     Clearances,
@@ -57,8 +64,11 @@ pub use dna::autocrate::{
     CratePart,
     CratePartKind,
     CrateSpec,
+    CrateStandard,
+    CrateStyle,
     KlimpPosition,
     LagScrewPosition,
+    LiftCleatGeometry,
     PartCategory,
     PartMaterial,
     // Constants
@@ -71,6 +81,7 @@ pub use dna::autocrate::{
     Point3,
     // Types
     ProductDimensions,
+    RequirementsSpec,
     SkidGeometry,
 };
 
@@ -79,6 +90,37 @@ pub use dna::autocrate::reports::{
     bom_to_csv, cut_list_to_csv, generate_bom, generate_cut_list, BomRow, CutListRow,
 };
 
+// Re-export cutting optimisation (stock boards, sheet nesting, diagrams)
+pub use dna::autocrate::cutting::{
+    cut_lumber, cutting_plan_to_pdf, cutting_plan_to_svg, nest_plywood, optimize_cutting,
+    purchase_list_to_csv, CuttingOptions, CuttingPlan, LumberCut, PlywoodSheet, PurchaseItem,
+    SheetCut, StockBoard,
+};
+
+// Re-export drawing sets (multi-sheet shop drawings)
+pub use dna::autocrate::drawings::{
+    dimension_text, drawing_set_to_dxf, drawing_set_to_dxf_document, drawing_set_to_pdf,
+    generate_drawing_set, panel_layout_to_dxf, DrawingEntity, DrawingLayer, DrawingOptions, DrawingSet, DrawingSheet, SheetPoint,
+};
+
+// Re-export pricing (supplier price lists, labor, quotes)
+pub use dna::autocrate::pricing::{
+    quote_design, quote_to_csv, quote_to_pdf, HardwarePrice, LumberPrice, PlywoodPrice,
+    PriceList, PricingError, Quote, QuoteCategory, QuoteLine, QuoteOptions,
+};
+
+// Re-export standard profiles and member sizing rules
+pub use dna::autocrate::standards::{
+    apply_standard, check_requirements, effective_spec, lift_cleat_size, notch_size,
+    size_members, MemberSizing, StandardProfile,
+};
+
+// Re-export structural verification (member checks against ASTM D6039 profiles)
+pub use dna::autocrate::structural::{
+    analyze_structure, estimate_tare_weight, structural_checks_to_csv, CheckKind,
+    StructuralCheck, StructuralOptions, StructuralReport, WoodAllowables,
+};
+
 // Re-export STEP export (NX-importable assembly, inches)
 pub use dna::export::step::{export_step_ap242, StepExportOptions};
 
@@ -93,6 +135,7 @@ pub fn quick_design(length: f32, width: f32, height: f32, weight: f32) -> CrateG
             width,
             height,
             weight,
+            center_of_gravity: None,
         },
         ..CrateSpec::default()
     };
@@ -100,6 +143,52 @@ pub fn quick_design(length: f32, width: f32, height: f32, weight: f32) -> CrateG
     calculate_crate(&spec)
 }
 
+/// Check a spec against the input limits (dimensions, weight, clearances,
+/// skid count). Returns one message per problem; empty = valid.
+pub fn validate_spec(spec: &CrateSpec) -> Vec<String> {
+    use dna::autocrate::constants::validation::*;
+
+    let mut errors = Vec::new();
+    let p = &spec.product;
+    for (name, value) in [("length", p.length), ("width", p.width), ("height", p.height)] {
+        if !value.is_finite() || !(MIN_DIMENSION..=MAX_DIMENSION).contains(&value) {
+            errors.push(format!(
+                "Product {} {} in outside {:.0}-{:.0} in",
+                name, value, MIN_DIMENSION, MAX_DIMENSION
+            ));
+        }
+    }
+    if !p.weight.is_finite() || !(MIN_WEIGHT..=MAX_WEIGHT).contains(&p.weight) {
+        errors.push(format!(
+            "Product weight {} lbs outside {:.0}-{:.0} lbs",
+            p.weight, MIN_WEIGHT, MAX_WEIGHT
+        ));
+    }
+
+    let c = &spec.clearances;
+    for (name, value, max) in [
+        ("side", c.side, MAX_SIDE_CLEARANCE),
+        ("end", c.end, MAX_SIDE_CLEARANCE),
+        ("top", c.top, MAX_TOP_CLEARANCE),
+    ] {
+        if !(MIN_CLEARANCE..=max).contains(&value) {
+            errors.push(format!(
+                "{} clearance {} in outside {:.0}-{:.0} in",
+                name, value, MIN_CLEARANCE, max
+            ));
+        }
+    }
+
+    if spec.skid_count < 2 {
+        errors.push(format!("Skid count {} below minimum of 2", spec.skid_count));
+    }
+    if spec.materials.plywood_thickness <= 0.0 || spec.materials.panel_thickness <= 0.0 {
+        errors.push("Plywood and panel thickness must be positive".to_string());
+    }
+
+    errors
+}
+
 /// Build the canonical `CrateDesign` (parts graph) from a spec.
 pub fn design_from_spec(spec: &CrateSpec) -> CrateDesign {
     CrateDesign::from_spec(spec)
@@ -122,6 +211,22 @@ pub fn export_cut_list_csv(design: &CrateDesign) -> String {
     cut_list_to_csv(&cut)
 }
 
+/// Export the purchase list (stock boards + plywood sheets) as CSV.
+pub fn export_purchase_list_csv(design: &CrateDesign) -> String {
+    let plan = optimize_cutting(design, &CuttingOptions::default());
+    purchase_list_to_csv(&plan.purchase_list())
+}
+
+/// Export the shop drawing set (one page per sheet) as PDF bytes.
+pub fn export_drawings_pdf(design: &CrateDesign) -> Vec<u8> {
+    drawing_set_to_pdf(&generate_drawing_set(design, &DrawingOptions::default()))
+}
+
+/// Export the shop drawing set as DXF (sheets laid out side by side).
+pub fn export_drawings_dxf(design: &CrateDesign) -> String {
+    drawing_set_to_dxf(&generate_drawing_set(design, &DrawingOptions::default()))
+}
+
 /// Design a heavy-duty crate for weights over 5000 lbs
 ///
 /// Uses larger lumber (4x6 skids, 2x8 floorboards) and 5 skids for heavy loads.
@@ -132,6 +237,7 @@ pub fn heavy_duty_design(length: f32, width: f32, height: f32, weight: f32) -> C
             width,
             height,
             weight,
+            center_of_gravity: None,
         },
         clearances: Clearances {
             side: 3.0,
@@ -223,4 +329,18 @@ mod tests {
         assert!(step.contains("ISO-10303-21;"));
         assert!(step.contains("CONVERSION_BASED_UNIT('INCH'"));
     }
+
+    #[test]
+    fn test_validate_spec_limits() {
+        assert!(validate_spec(&CrateSpec::default()).is_empty());
+
+        let mut spec = CrateSpec::default();
+        spec.product.length = 200.0;
+        spec.product.weight = 10.0;
+        spec.clearances.top = 0.5;
+        spec.skid_count = 1;
+        let errors = validate_spec(&spec);
+        assert_eq!(errors.len(), 4, "{:?}", errors);
+        assert!(errors[0].starts_with("Product length 200"));
+    }
 }
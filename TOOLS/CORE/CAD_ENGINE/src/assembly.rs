@@ -0,0 +1,492 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: assembly.rs | TOOLS/CORE/CAD_ENGINE/src/assembly.rs
+//! PURPOSE: Placed multi-part assemblies with materials, CG and clash detection
+//! MODIFIED: 2026-10-18
+//! LAYER: CORE → CAD_ENGINE
+//! ═══════════════════════════════════════════════════════════════════════════════
+//!
+//! ┌─────────────────────────────────────────────────────────────────────────────┐
+//! │ INTERFERENCE PIPELINE                                                       │
+//! ├─────────────────────────────────────────────────────────────────────────────┤
+//! │ 1. Broad phase   World BoundingBox3 per part, expanded by the clearance     │
+//! │ 2. Narrow phase  Faceted boundaries: edge/face crossings + containment      │
+//! │ 3. Clearance     Minimum triangle-triangle distance                         │
+//! └─────────────────────────────────────────────────────────────────────────────┘
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use glam::Vec3;
+
+use dna::cad::{BoundingBox3, Point3, Solid, Transform3};
+
+use crate::mass::{boundary_triangles, triangle_mass_properties, MassProperties, Triangle};
+
+/// Material with uniform density (mass per cubic model unit)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Material {
+    pub name: String,
+    pub density: f32,
+}
+
+impl Material {
+    pub fn new(name: impl Into<String>, density: f32) -> Self {
+        Self {
+            name: name.into(),
+            density,
+        }
+    }
+}
+
+/// A solid placed in an assembly
+#[derive(Clone, Debug)]
+pub struct AssemblyPart {
+    pub name: String,
+    pub solid: Solid,
+    /// Local → assembly transform
+    pub placement: Transform3,
+    pub material: Material,
+}
+
+impl AssemblyPart {
+    pub fn new(name: impl Into<String>, solid: Solid, material: Material) -> Self {
+        Self {
+            name: name.into(),
+            solid,
+            placement: Transform3::IDENTITY,
+            material,
+        }
+    }
+
+    pub fn with_placement(mut self, placement: Transform3) -> Self {
+        self.placement = placement;
+        self
+    }
+
+    /// Mass properties in assembly coordinates
+    pub fn mass_properties(&self) -> MassProperties {
+        triangle_mass_properties(&self.world_triangles(), self.material.density)
+    }
+
+    /// Axis-aligned bounds in assembly coordinates
+    pub fn world_bounds(&self) -> BoundingBox3 {
+        triangle_bounds(&self.world_triangles())
+    }
+
+    fn world_triangles(&self) -> Vec<Triangle> {
+        boundary_triangles(&self.solid, &self.placement)
+    }
+}
+
+/// Result of checking one pair of parts
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PairStatus {
+    /// Solids overlap in volume
+    Interfering,
+    /// Boundaries touch but volumes do not overlap
+    Touching,
+    /// Separated by the given minimum distance
+    Clear(f32),
+}
+
+/// Pair of parts that interfere, touch, or violate the requested clearance
+#[derive(Clone, Debug, PartialEq)]
+pub struct Clash {
+    pub first: usize,
+    pub second: usize,
+    pub status: PairStatus,
+}
+
+/// Collection of placed parts
+#[derive(Clone, Debug, Default)]
+pub struct Assembly {
+    pub name: String,
+    pub parts: Vec<AssemblyPart>,
+}
+
+impl Assembly {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            parts: Vec::new(),
+        }
+    }
+
+    /// Add a part, returning its index
+    pub fn add_part(&mut self, part: AssemblyPart) -> usize {
+        self.parts.push(part);
+        self.parts.len() - 1
+    }
+
+    /// Combined mass properties (inertia about the assembly CG)
+    pub fn mass_properties(&self) -> MassProperties {
+        let bodies: Vec<MassProperties> = self.parts.iter().map(|p| p.mass_properties()).collect();
+        MassProperties::combine(&bodies)
+    }
+
+    pub fn total_mass(&self) -> f32 {
+        self.mass_properties().mass
+    }
+
+    /// Centre of gravity, or None for an empty / massless assembly
+    pub fn center_of_gravity(&self) -> Option<Point3> {
+        let props = self.mass_properties();
+        (props.mass > 0.0).then_some(props.center_of_mass)
+    }
+
+    /// Exact status of a single pair of parts
+    pub fn pair_status(&self, first: usize, second: usize) -> Option<PairStatus> {
+        let a = self.parts.get(first)?.world_triangles();
+        let b = self.parts.get(second)?.world_triangles();
+        Some(pair_status(&a, &b))
+    }
+
+    /// Every pair that interferes, touches, or sits closer than `min_clearance`
+    pub fn check_interference(&self, min_clearance: f32) -> Vec<Clash> {
+        let meshes: Vec<Vec<Triangle>> = self.parts.iter().map(|p| p.world_triangles()).collect();
+        let bounds: Vec<BoundingBox3> = meshes
+            .iter()
+            .map(|m| triangle_bounds(m).expand(min_clearance.max(0.0)))
+            .collect();
+
+        let mut clashes = Vec::new();
+        for i in 0..meshes.len() {
+            for j in (i + 1)..meshes.len() {
+                // Broad phase
+                if !bounds[i].intersects(&bounds[j]) {
+                    continue;
+                }
+                let status = pair_status(&meshes[i], &meshes[j]);
+                let report = match status {
+                    PairStatus::Clear(distance) => distance < min_clearance,
+                    _ => true,
+                };
+                if report {
+                    clashes.push(Clash {
+                        first: i,
+                        second: j,
+                        status,
+                    });
+                }
+            }
+        }
+        clashes
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// NARROW PHASE
+// ─────────────────────────────────────────────────────────────────────────────────
+
+fn triangle_bounds(triangles: &[Triangle]) -> BoundingBox3 {
+    triangles
+        .iter()
+        .flatten()
+        .fold(BoundingBox3::EMPTY, |bb, p| {
+            bb.expand_by_point(Point3::from_vec3(*p))
+        })
+}
+
+fn pair_status(a: &[Triangle], b: &[Triangle]) -> PairStatus {
+    if a.is_empty() || b.is_empty() {
+        return PairStatus::Clear(f32::INFINITY);
+    }
+    let (bounds_a, bounds_b) = (triangle_bounds(a), triangle_bounds(b));
+    let scale = bounds_a.union(bounds_b).diagonal().max(1.0);
+    let tol = scale * 1e-5;
+
+    if bounds_a.expand(tol).intersects(&bounds_b)
+        && (meshes_cross(a, b, tol)
+            || contains_any(a, b, &bounds_b, tol)
+            || contains_any(b, a, &bounds_a, tol))
+    {
+        return PairStatus::Interfering;
+    }
+
+    let distance = min_distance(a, b);
+    if distance <= tol {
+        PairStatus::Touching
+    } else {
+        PairStatus::Clear(distance)
+    }
+}
+
+/// Any edge of one mesh passing through a face of the other
+fn meshes_cross(a: &[Triangle], b: &[Triangle], tol: f32) -> bool {
+    let crosses = |edges: &[Triangle], faces: &[Triangle]| {
+        edges.iter().any(|t| {
+            (0..3).any(|k| {
+                let (p, q) = (t[k], t[(k + 1) % 3]);
+                faces.iter().any(|f| segment_crosses_triangle(p, q, f, tol))
+            })
+        })
+    };
+    crosses(a, b) || crosses(b, a)
+}
+
+/// Transversal crossing: endpoints strictly on opposite sides, hit point on the triangle
+fn segment_crosses_triangle(p: Vec3, q: Vec3, tri: &Triangle, tol: f32) -> bool {
+    let Some(n) = (tri[1] - tri[0]).cross(tri[2] - tri[0]).try_normalize() else {
+        return false;
+    };
+    let dp = n.dot(p - tri[0]);
+    let dq = n.dot(q - tri[0]);
+    if dp.abs() <= tol || dq.abs() <= tol || (dp > 0.0) == (dq > 0.0) {
+        return false;
+    }
+    let hit = p + (q - p) * (dp / (dp - dq));
+    closest_point_on_triangle(hit, tri).distance(hit) <= tol
+}
+
+/// Does a point just inside one of `inner`'s faces lie inside `outer`?
+fn contains_any(
+    inner: &[Triangle],
+    outer: &[Triangle],
+    outer_bounds: &BoundingBox3,
+    tol: f32,
+) -> bool {
+    inner.iter().any(|t| {
+        let Some(n) = (t[1] - t[0]).cross(t[2] - t[0]).try_normalize() else {
+            return false;
+        };
+        // Nudge the face centroid into its own solid
+        let probe = (t[0] + t[1] + t[2]) / 3.0 - n * (tol * 10.0);
+        outer_bounds.contains(Point3::from_vec3(probe))
+            && winding_number(probe, outer) > 0.5
+            && surface_distance(probe, outer) > tol
+    })
+}
+
+/// Generalised winding number via summed solid angles (≈1 inside, ≈0 outside)
+fn winding_number(point: Vec3, triangles: &[Triangle]) -> f32 {
+    let mut total = 0.0f64;
+    for tri in triangles {
+        let a = (tri[0] - point).as_dvec3();
+        let b = (tri[1] - point).as_dvec3();
+        let c = (tri[2] - point).as_dvec3();
+        let (la, lb, lc) = (a.length(), b.length(), c.length());
+        let numerator = a.dot(b.cross(c));
+        let denominator = la * lb * lc + a.dot(b) * lc + b.dot(c) * la + c.dot(a) * lb;
+        total += 2.0 * numerator.atan2(denominator);
+    }
+    (total / (4.0 * std::f64::consts::PI)) as f32
+}
+
+fn surface_distance(point: Vec3, triangles: &[Triangle]) -> f32 {
+    triangles
+        .iter()
+        .map(|t| closest_point_on_triangle(point, t).distance(point))
+        .fold(f32::INFINITY, f32::min)
+}
+
+/// Minimum distance between two triangle meshes
+fn min_distance(a: &[Triangle], b: &[Triangle]) -> f32 {
+    let boxes_b: Vec<(Vec3, Vec3)> = b.iter().map(tri_aabb).collect();
+    let mut best = f32::INFINITY;
+    for ta in a {
+        let (min_a, max_a) = tri_aabb(ta);
+        for (tb, (min_b, max_b)) in b.iter().zip(&boxes_b) {
+            // Skip pairs whose boxes are already farther apart than the best so far
+            let gap = (*min_b - max_a).max(min_a - *max_b).max(Vec3::ZERO);
+            if gap.length() >= best {
+                continue;
+            }
+            best = best.min(triangle_distance(ta, tb));
+        }
+    }
+    best
+}
+
+fn tri_aabb(t: &Triangle) -> (Vec3, Vec3) {
+    (t[0].min(t[1]).min(t[2]), t[0].max(t[1]).max(t[2]))
+}
+
+/// Distance between two non-intersecting triangles
+fn triangle_distance(a: &Triangle, b: &Triangle) -> f32 {
+    let mut best = f32::INFINITY;
+    for k in 0..3 {
+        best = best.min(closest_point_on_triangle(a[k], b).distance(a[k]));
+        best = best.min(closest_point_on_triangle(b[k], a).distance(b[k]));
+        for m in 0..3 {
+            best = best.min(segment_distance(a[k], a[(k + 1) % 3], b[m], b[(m + 1) % 3]));
+        }
+    }
+    best
+}
+
+/// Closest point on a triangle (Ericson, Real-Time Collision Detection 5.1.5)
+fn closest_point_on_triangle(p: Vec3, t: &Triangle) -> Vec3 {
+    let (a, b, c) = (t[0], t[1], t[2]);
+    let ab = b - a;
+    let ac = c - a;
+    let ap = p - a;
+    let d1 = ab.dot(ap);
+    let d2 = ac.dot(ap);
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return a;
+    }
+    let bp = p - b;
+    let d3 = ab.dot(bp);
+    let d4 = ac.dot(bp);
+    if d3 >= 0.0 && d4 <= d3 {
+        return b;
+    }
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        return a + ab * (d1 / (d1 - d3));
+    }
+    let cp = p - c;
+    let d5 = ab.dot(cp);
+    let d6 = ac.dot(cp);
+    if d6 >= 0.0 && d5 <= d6 {
+        return c;
+    }
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        return a + ac * (d2 / (d2 - d6));
+    }
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+        return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+    let denom = 1.0 / (va + vb + vc);
+    a + ab * (vb * denom) + ac * (vc * denom)
+}
+
+/// Distance between segments p1q1 and p2q2 (Ericson 5.1.9)
+fn segment_distance(p1: Vec3, q1: Vec3, p2: Vec3, q2: Vec3) -> f32 {
+    let d1 = q1 - p1;
+    let d2 = q2 - p2;
+    let r = p1 - p2;
+    let a = d1.length_squared();
+    let e = d2.length_squared();
+    let f = d2.dot(r);
+    let eps = f32::EPSILON;
+
+    let (s, t) = if a <= eps && e <= eps {
+        (0.0, 0.0)
+    } else if a <= eps {
+        (0.0, (f / e).clamp(0.0, 1.0))
+    } else {
+        let c = d1.dot(r);
+        if e <= eps {
+            ((-c / a).clamp(0.0, 1.0), 0.0)
+        } else {
+            let b = d1.dot(d2);
+            let denom = a * e - b * b;
+            let mut s = if denom > eps {
+                ((b * f - c * e) / denom).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let mut t = (b * s + f) / e;
+            if t < 0.0 {
+                t = 0.0;
+                s = (-c / a).clamp(0.0, 1.0);
+            } else if t > 1.0 {
+                t = 1.0;
+                s = ((b - c) / a).clamp(0.0, 1.0);
+            }
+            (s, t)
+        }
+    };
+    (p1 + d1 * s).distance(p2 + d2 * t)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// TESTS
+// ─────────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dna::cad::{make_box, make_sphere, Vector3};
+
+    fn steel() -> Material {
+        Material::new("Steel", 0.284)
+    }
+
+    fn block_at(name: &str, x: f32, y: f32, z: f32) -> AssemblyPart {
+        AssemblyPart::new(name, make_box(2.0, 2.0, 2.0), steel())
+            .with_placement(Transform3::from_translation(Vector3::new(x, y, z)))
+    }
+
+    #[test]
+    fn test_assembly_mass_and_cg() {
+        let mut assembly = Assembly::new("Skid test");
+        assembly.add_part(block_at("A", 0.0, 0.0, 0.0));
+        assembly.add_part(
+            AssemblyPart::new("B", make_box(2.0, 2.0, 2.0), Material::new("Heavy", 0.852))
+                .with_placement(Transform3::from_translation(Vector3::new(4.0, 0.0, 0.0))),
+        );
+
+        let mass = assembly.total_mass();
+        assert!((mass - 8.0 * (0.284 + 0.852)).abs() < 1e-3);
+        // Three times the density on B pulls the CG 3/4 of the way over
+        let cg = assembly.center_of_gravity().unwrap();
+        assert!(cg.approx_eq(Point3::new(3.0, 0.0, 0.0), 1e-4));
+        assert!(Assembly::new("empty").center_of_gravity().is_none());
+    }
+
+    #[test]
+    fn test_rotated_placement_keeps_mass() {
+        let part = AssemblyPart::new("Plank", make_box(1.0, 2.0, 8.0), steel()).with_placement(
+            Transform3::from_rotation_x(0.7)
+                .then(Transform3::from_translation(Vector3::new(5.0, 1.0, 2.0))),
+        );
+        let props = part.mass_properties();
+        assert!((props.volume - 16.0).abs() < 1e-3);
+        assert!(props
+            .center_of_mass
+            .approx_eq(Point3::new(5.0, 1.0, 2.0), 1e-4));
+    }
+
+    #[test]
+    fn test_interference_classification() {
+        let mut assembly = Assembly::new("Clash");
+        let a = assembly.add_part(block_at("A", 0.0, 0.0, 0.0));
+        let overlapping = assembly.add_part(block_at("B", 1.5, 0.0, 0.0));
+        let flush = assembly.add_part(block_at("C", -2.0, 0.0, 0.0));
+        let near = assembly.add_part(block_at("D", 0.0, 2.25, 0.0));
+        let far = assembly.add_part(block_at("E", 0.0, 0.0, 50.0));
+
+        assert_eq!(
+            assembly.pair_status(a, overlapping),
+            Some(PairStatus::Interfering)
+        );
+        assert_eq!(assembly.pair_status(a, flush), Some(PairStatus::Touching));
+        match assembly.pair_status(a, near) {
+            Some(PairStatus::Clear(d)) => assert!((d - 0.25).abs() < 1e-4),
+            other => panic!("expected clearance, got {other:?}"),
+        }
+
+        let clashes = assembly.check_interference(0.5);
+        let pairs: Vec<(usize, usize)> = clashes.iter().map(|c| (c.first, c.second)).collect();
+        assert!(pairs.contains(&(a, overlapping)));
+        assert!(pairs.contains(&(a, flush)));
+        assert!(pairs.contains(&(a, near)));
+        assert!(!pairs.iter().any(|&(i, j)| i == far || j == far));
+    }
+
+    #[test]
+    fn test_containment_and_crossing_interfere() {
+        let mut assembly = Assembly::new("Nested");
+        let outer = assembly.add_part(AssemblyPart::new(
+            "Outer",
+            make_box(10.0, 10.0, 10.0),
+            steel(),
+        ));
+        let inner = assembly.add_part(AssemblyPart::new("Inner", make_sphere(1.0, 12, 6), steel()));
+        let bar = assembly.add_part(AssemblyPart::new("Bar", make_box(30.0, 1.0, 1.0), steel()));
+
+        // No boundary contact at all, but the sphere sits inside the box
+        assert_eq!(
+            assembly.pair_status(outer, inner),
+            Some(PairStatus::Interfering)
+        );
+        // Bar passes straight through the box: no vertex of either is inside the other
+        assert_eq!(
+            assembly.pair_status(outer, bar),
+            Some(PairStatus::Interfering)
+        );
+    }
+}
@@ -1,7 +1,7 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: lib.rs | TOOLS/CORE/CAD_ENGINE/src/lib.rs
 //! PURPOSE: B-Rep CAD engine for solid modeling
-//! MODIFIED: 2025-12-09
+//! MODIFIED: 2026-10-18
 //! LAYER: CORE → CAD_ENGINE
 //! ═══════════════════════════════════════════════════════════════════════════════
 //!
@@ -9,7 +9,10 @@
 //! - Geometric primitives (points, vectors, planes)
 //! - B-Rep topology (vertices, edges, faces, shells, solids)
 //! - Solid primitives (box, cylinder, sphere, cone)
+//! - NURBS curves/surfaces (evaluation, knot refinement, point inversion)
 //! - Transformations (translate, rotate, scale)
+//! - Mass properties (volume, centre of mass, inertia tensor)
+//! - Assemblies (placed parts, materials, CG, interference/clearance)
 //! - Boolean operations (planned: union, difference, intersection)
 //!
 //! ┌─────────────────────────────────────────────────────────────────────────────┐
@@ -29,10 +32,17 @@
 //! │       │     ├── Loop, Shell, Solid                                          │
 //! │       │     └── CurveType, SurfaceType                                      │
 //! │       │                                                                     │
-//! │       └── Primitives (DNA/cad/primitives)                                   │
-//! │             ├── make_box, make_cylinder                                     │
-//! │             ├── make_sphere, make_cone                                      │
-//! │             └── (future: make_prism, make_torus)                            │
+//! │       ├── Primitives (DNA/cad/primitives)                                   │
+//! │       │     ├── make_box, make_cylinder                                     │
+//! │       │     ├── make_sphere, make_cone                                      │
+//! │       │     └── (future: make_prism, make_torus)                            │
+//! │       │                                                                     │
+//! │       ├── Mass (mass.rs)                                                    │
+//! │       │     └── MassProperties, mass_properties, center_of_mass             │
+//! │       │                                                                     │
+//! │       └── Assembly (assembly.rs)                                            │
+//! │             ├── Assembly, AssemblyPart, Material                            │
+//! │             └── check_interference → Clash, PairStatus                      │
 //! │                                                                             │
 //! └─────────────────────────────────────────────────────────────────────────────┘
 //!
@@ -46,6 +56,12 @@
 //!
 //! ═══════════════════════════════════════════════════════════════════════════════
 
+pub mod assembly;
+pub mod mass;
+
+pub use assembly::{Assembly, AssemblyPart, Clash, Material, PairStatus};
+pub use mass::{center_of_mass, mass_properties, MassProperties};
+
 // ─────────────────────────────────────────────────────────────────────────────────
 // RE-EXPORTS FROM DNA
 // ─────────────────────────────────────────────────────────────────────────────────
@@ -61,6 +77,9 @@ pub use dna::cad::topology::{
     SurfaceType, Vertex, VertexId,
 };
 
+// NURBS kernel
+pub use dna::cad::nurbs::{NurbsCurve, NurbsSurface};
+
 // Primitive generators
 pub use dna::cad::primitives::{
     make_box, make_box_at, make_cone, make_cone_at, make_cylinder, make_cylinder_at, make_sphere,
@@ -0,0 +1,356 @@
+//! ═══════════════════════════════════════════════════════════════════════════════
+//! FILE: mass.rs | TOOLS/CORE/CAD_ENGINE/src/mass.rs
+//! PURPOSE: Mass properties of B-Rep solids (volume, centre of mass, inertia tensor)
+//! MODIFIED: 2026-10-18
+//! LAYER: CORE → CAD_ENGINE
+//! ═══════════════════════════════════════════════════════════════════════════════
+//!
+//! The boundary is faceted into outward-facing triangles and integrated with the
+//! divergence theorem (Eberly, "Polyhedral Mass Properties"). Results are exact
+//! for planar-faced solids, which covers every primitive in `dna::cad`; curved
+//! faces are integrated over their sampled boundary polygon.
+//!
+//! Units follow the model: with lengths in inches and density in lb/in³, mass
+//! comes out in lb and inertia in lb·in².
+//!
+//! ═══════════════════════════════════════════════════════════════════════════════
+
+use glam::{DVec3, Mat3, Vec3};
+
+use dna::cad::{Loop, Point3, Solid, Transform3, Vector3};
+
+/// Samples per curved (arc / NURBS) edge when faceting face boundaries
+const CURVE_SAMPLES: usize = 16;
+
+/// Outward-oriented boundary triangle
+pub(crate) type Triangle = [Vec3; 3];
+
+/// Volume, mass, centre of mass and inertia tensor of a body
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MassProperties {
+    pub volume: f32,
+    pub mass: f32,
+    pub center_of_mass: Point3,
+    /// Inertia tensor about the centre of mass (world-aligned axes)
+    pub inertia: Mat3,
+}
+
+impl MassProperties {
+    pub const ZERO: Self = Self {
+        volume: 0.0,
+        mass: 0.0,
+        center_of_mass: Point3::ORIGIN,
+        inertia: Mat3::ZERO,
+    };
+
+    /// Inertia tensor about an arbitrary point (parallel axis theorem)
+    pub fn inertia_about_point(&self, point: Point3) -> Mat3 {
+        let d = (self.center_of_mass - point).to_vec3();
+        let shift = Mat3::from_diagonal(Vec3::splat(d.length_squared()))
+            - Mat3::from_cols(d * d.x, d * d.y, d * d.z);
+        self.inertia + shift * self.mass
+    }
+
+    /// Moment of inertia about the axis through `origin` along `direction`
+    pub fn inertia_about_axis(&self, origin: Point3, direction: Vector3) -> f32 {
+        let axis = direction.normalize_or_z().to_vec3();
+        axis.dot(self.inertia_about_point(origin) * axis)
+    }
+
+    /// Principal moments of inertia about the centre of mass, ascending
+    pub fn principal_moments(&self) -> [f32; 3] {
+        let mut moments = symmetric_eigenvalues(self.inertia);
+        moments.sort_by(|a, b| a.total_cmp(b));
+        moments
+    }
+
+    /// Combine several bodies into one (masses add, tensors shift to the joint CG)
+    pub fn combine(bodies: &[MassProperties]) -> MassProperties {
+        let mass: f32 = bodies.iter().map(|b| b.mass).sum();
+        let volume: f32 = bodies.iter().map(|b| b.volume).sum();
+        if mass <= 0.0 {
+            return MassProperties {
+                volume,
+                ..MassProperties::ZERO
+            };
+        }
+        let weighted = bodies.iter().fold(Vec3::ZERO, |acc, b| {
+            acc + b.center_of_mass.to_vec3() * b.mass
+        });
+        let center_of_mass = Point3::from_vec3(weighted / mass);
+        let inertia = bodies.iter().fold(Mat3::ZERO, |acc, b| {
+            acc + b.inertia_about_point(center_of_mass)
+        });
+        MassProperties {
+            volume,
+            mass,
+            center_of_mass,
+            inertia,
+        }
+    }
+}
+
+/// Mass properties of a closed solid with uniform `density`
+pub fn mass_properties(solid: &Solid, density: f32) -> MassProperties {
+    triangle_mass_properties(&boundary_triangles(solid, &Transform3::IDENTITY), density)
+}
+
+/// Centre of mass (centroid) of a closed solid with uniform density
+pub fn center_of_mass(solid: &Solid) -> Point3 {
+    mass_properties(solid, 1.0).center_of_mass
+}
+
+/// Integrate a closed triangle mesh; orientation is fixed up if the mesh is inside out
+pub(crate) fn triangle_mass_properties(triangles: &[Triangle], density: f32) -> MassProperties {
+    if triangles.is_empty() {
+        return MassProperties::ZERO;
+    }
+    // Integrate relative to a nearby reference point to limit cancellation
+    let reference = triangles[0][0].as_dvec3();
+
+    // ∫1, ∫x, ∫y, ∫z, ∫x², ∫y², ∫z², ∫xy, ∫yz, ∫zx
+    let mut integral = [0.0f64; 10];
+    for tri in triangles {
+        let p0 = tri[0].as_dvec3() - reference;
+        let p1 = tri[1].as_dvec3() - reference;
+        let p2 = tri[2].as_dvec3() - reference;
+        let d = (p1 - p0).cross(p2 - p0);
+        let (f1x, f2x, f3x, g0x, g1x, g2x) = subexpressions(p0.x, p1.x, p2.x);
+        let (_, f2y, f3y, g0y, g1y, g2y) = subexpressions(p0.y, p1.y, p2.y);
+        let (_, f2z, f3z, g0z, g1z, g2z) = subexpressions(p0.z, p1.z, p2.z);
+        integral[0] += d.x * f1x;
+        integral[1] += d.x * f2x;
+        integral[2] += d.y * f2y;
+        integral[3] += d.z * f2z;
+        integral[4] += d.x * f3x;
+        integral[5] += d.y * f3y;
+        integral[6] += d.z * f3z;
+        integral[7] += d.x * (p0.y * g0x + p1.y * g1x + p2.y * g2x);
+        integral[8] += d.y * (p0.z * g0y + p1.z * g1y + p2.z * g2y);
+        integral[9] += d.z * (p0.x * g0z + p1.x * g1z + p2.x * g2z);
+    }
+    let mult = [
+        1.0 / 6.0,
+        1.0 / 24.0,
+        1.0 / 24.0,
+        1.0 / 24.0,
+        1.0 / 60.0,
+        1.0 / 60.0,
+        1.0 / 60.0,
+        1.0 / 120.0,
+        1.0 / 120.0,
+        1.0 / 120.0,
+    ];
+    for (value, m) in integral.iter_mut().zip(mult) {
+        *value *= m;
+    }
+    // Inside-out meshes integrate to negative volume
+    if integral[0] < 0.0 {
+        for value in integral.iter_mut() {
+            *value = -*value;
+        }
+    }
+
+    let volume = integral[0];
+    if volume <= f64::EPSILON {
+        return MassProperties::ZERO;
+    }
+    let rho = density as f64;
+    let mass = volume * rho;
+    let c = DVec3::new(integral[1], integral[2], integral[3]) / volume;
+    let ixx = rho * (integral[5] + integral[6]) - mass * (c.y * c.y + c.z * c.z);
+    let iyy = rho * (integral[4] + integral[6]) - mass * (c.z * c.z + c.x * c.x);
+    let izz = rho * (integral[4] + integral[5]) - mass * (c.x * c.x + c.y * c.y);
+    let ixy = -(rho * integral[7] - mass * c.x * c.y);
+    let iyz = -(rho * integral[8] - mass * c.y * c.z);
+    let ixz = -(rho * integral[9] - mass * c.z * c.x);
+
+    MassProperties {
+        volume: volume as f32,
+        mass: mass as f32,
+        center_of_mass: Point3::from_vec3((c + reference).as_vec3()),
+        inertia: Mat3::from_cols(
+            Vec3::new(ixx as f32, ixy as f32, ixz as f32),
+            Vec3::new(ixy as f32, iyy as f32, iyz as f32),
+            Vec3::new(ixz as f32, iyz as f32, izz as f32),
+        ),
+    }
+}
+
+fn subexpressions(w0: f64, w1: f64, w2: f64) -> (f64, f64, f64, f64, f64, f64) {
+    let temp0 = w0 + w1;
+    let f1 = temp0 + w2;
+    let temp1 = w0 * w0;
+    let temp2 = temp1 + w1 * temp0;
+    let f2 = temp2 + w2 * f1;
+    let f3 = w0 * temp1 + w1 * temp2 + w2 * f2;
+    let g0 = f2 + w0 * (f1 + w0);
+    let g1 = f2 + w1 * (f1 + w1);
+    let g2 = f2 + w2 * (f1 + w2);
+    (f1, f2, f3, g0, g1, g2)
+}
+
+/// Eigenvalues of a symmetric 3×3 matrix (closed-form trigonometric solution)
+fn symmetric_eigenvalues(m: Mat3) -> [f32; 3] {
+    let a = m.to_cols_array_2d().map(|col| col.map(|v| v as f64));
+    let p1 = a[0][1].powi(2) + a[0][2].powi(2) + a[1][2].powi(2);
+    if p1 < 1e-18 {
+        return [a[0][0] as f32, a[1][1] as f32, a[2][2] as f32];
+    }
+    let q = (a[0][0] + a[1][1] + a[2][2]) / 3.0;
+    let p2 = (a[0][0] - q).powi(2) + (a[1][1] - q).powi(2) + (a[2][2] - q).powi(2) + 2.0 * p1;
+    let p = (p2 / 6.0).sqrt();
+    let b = |i: usize, j: usize| (a[i][j] - if i == j { q } else { 0.0 }) / p;
+    let det_b = b(0, 0) * (b(1, 1) * b(2, 2) - b(1, 2) * b(2, 1))
+        - b(0, 1) * (b(1, 0) * b(2, 2) - b(1, 2) * b(2, 0))
+        + b(0, 2) * (b(1, 0) * b(2, 1) - b(1, 1) * b(2, 0));
+    let phi = (det_b / 2.0).clamp(-1.0, 1.0).acos() / 3.0;
+    let e1 = q + 2.0 * p * phi.cos();
+    let e3 = q + 2.0 * p * (phi + 2.0 * std::f64::consts::PI / 3.0).cos();
+    let e2 = 3.0 * q - e1 - e3;
+    [e1 as f32, e2 as f32, e3 as f32]
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// FACETING
+// ─────────────────────────────────────────────────────────────────────────────────
+
+/// Facet every face of `solid` into outward-facing triangles, placed by `placement`
+pub(crate) fn boundary_triangles(solid: &Solid, placement: &Transform3) -> Vec<Triangle> {
+    let mut triangles = Vec::new();
+    for face in &solid.faces {
+        let outer = loop_polygon(solid, &face.outer_loop);
+        if outer.len() < 3 {
+            continue;
+        }
+        // Orient the outer boundary by the surface normal at its centroid
+        let centroid = outer.iter().fold(Vec3::ZERO, |acc, p| acc + *p) / outer.len() as f32;
+        let mut expected = face
+            .surface
+            .normal_at(Point3::from_vec3(centroid))
+            .to_vec3();
+        if face.orientation == dna::cad::FaceOrientation::Inward {
+            expected = -expected;
+        }
+        let flip = newell_normal(&outer).dot(expected) < 0.0;
+        let outer_normal = if flip {
+            -newell_normal(&outer)
+        } else {
+            newell_normal(&outer)
+        };
+        push_fan(&mut triangles, &outer, flip, placement);
+
+        // Holes wind opposite to the outer boundary
+        for inner in &face.inner_loops {
+            let hole = loop_polygon(solid, inner);
+            if hole.len() < 3 {
+                continue;
+            }
+            let hole_flip = newell_normal(&hole).dot(outer_normal) > 0.0;
+            push_fan(&mut triangles, &hole, hole_flip, placement);
+        }
+    }
+    triangles
+}
+
+fn push_fan(triangles: &mut Vec<Triangle>, polygon: &[Vec3], flip: bool, placement: &Transform3) {
+    let place = |p: Vec3| placement.0.transform_point3(p);
+    for i in 1..polygon.len() - 1 {
+        let (a, b, c) = (place(polygon[0]), place(polygon[i]), place(polygon[i + 1]));
+        triangles.push(if flip { [a, c, b] } else { [a, b, c] });
+    }
+}
+
+fn newell_normal(polygon: &[Vec3]) -> Vec3 {
+    let mut n = Vec3::ZERO;
+    for (i, a) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()];
+        n += Vec3::new(
+            (a.y - b.y) * (a.z + b.z),
+            (a.z - b.z) * (a.x + b.x),
+            (a.x - b.x) * (a.y + b.y),
+        );
+    }
+    n
+}
+
+fn loop_polygon(solid: &Solid, loop_: &Loop) -> Vec<Vec3> {
+    solid
+        .loop_points(loop_, CURVE_SAMPLES)
+        .into_iter()
+        .map(Point3::to_vec3)
+        .collect()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────────
+// TESTS
+// ─────────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dna::cad::{make_box, make_box_at, make_cylinder};
+
+    #[test]
+    fn test_box_mass_properties() {
+        let solid = make_box_at(Point3::new(1.0, 2.0, 3.0), 2.0, 4.0, 6.0);
+        let props = mass_properties(&solid, 0.5);
+
+        assert!((props.volume - 48.0).abs() < 1e-3);
+        assert!((props.mass - 24.0).abs() < 1e-3);
+        assert!(props
+            .center_of_mass
+            .approx_eq(Point3::new(1.0, 2.0, 3.0), 1e-4));
+
+        // Box: Ixx = m (b² + c²) / 12
+        let m = props.mass;
+        assert!((props.inertia.x_axis.x - m * (16.0 + 36.0) / 12.0).abs() < 1e-2);
+        assert!((props.inertia.y_axis.y - m * (4.0 + 36.0) / 12.0).abs() < 1e-2);
+        assert!((props.inertia.z_axis.z - m * (4.0 + 16.0) / 12.0).abs() < 1e-2);
+        assert!(props.inertia.x_axis.y.abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_inertia_about_arbitrary_axis() {
+        let props = mass_properties(&make_box(2.0, 2.0, 2.0), 1.0);
+        let m = props.mass;
+        let icm = m * 8.0 / 12.0;
+
+        // Parallel axis theorem: edge of the cube along Z
+        let edge = props.inertia_about_axis(Point3::new(1.0, 1.0, 0.0), Vector3::Z);
+        assert!((edge - (icm + m * 2.0)).abs() < 1e-2);
+
+        // A cube's tensor is isotropic: any axis through the centre is the same
+        let diagonal = props.inertia_about_axis(Point3::ORIGIN, Vector3::new(1.0, 1.0, 1.0));
+        assert!((diagonal - icm).abs() < 1e-2);
+        for moment in props.principal_moments() {
+            assert!((moment - icm).abs() < 1e-2);
+        }
+    }
+
+    #[test]
+    fn test_cylinder_approaches_analytic() {
+        let props = mass_properties(&make_cylinder(1.0, 2.0, 64), 1.0);
+        let expected = std::f32::consts::PI * 2.0;
+        assert!((props.volume - expected).abs() / expected < 0.01);
+        assert!(props.center_of_mass.to_vec3().length() < 1e-3);
+        // Izz = m r² / 2
+        assert!((props.inertia.z_axis.z - props.mass * 0.5).abs() / props.mass < 0.01);
+    }
+
+    #[test]
+    fn test_combine_matches_single_body() {
+        // Two unit cubes side by side equal one 2×1×1 block
+        let a = mass_properties(
+            &make_box_at(Point3::new(-0.5, 0.0, 0.0), 1.0, 1.0, 1.0),
+            2.0,
+        );
+        let b = mass_properties(&make_box_at(Point3::new(0.5, 0.0, 0.0), 1.0, 1.0, 1.0), 2.0);
+        let joined = MassProperties::combine(&[a, b]);
+        let block = mass_properties(&make_box(2.0, 1.0, 1.0), 2.0);
+
+        assert!((joined.mass - block.mass).abs() < 1e-4);
+        assert!(joined.center_of_mass.approx_eq(block.center_of_mass, 1e-5));
+        assert!(joined.inertia.abs_diff_eq(block.inertia, 1e-3));
+    }
+}
@@ -1,15 +1,17 @@
 //! ═══════════════════════════════════════════════════════════════════════════════
 //! FILE: lib.rs | TOOLS/CORE/EXPORT_ENGINE/src/lib.rs
 //! PURPOSE: Export pipeline for various file formats
-//! MODIFIED: 2025-12-09
+//! MODIFIED: 2026-10-18
 //! LAYER: CORE → EXPORT_ENGINE
 //! ═══════════════════════════════════════════════════════════════════════════════
 //!
 //! EXPORT_ENGINE generates output files in various formats:
-//! - Gerber X2 (PCB fabrication)
+//! - Gerber X2 + Excellon (PCB fabrication packages: layers, drills, job file, zip)
+//!   plus read-back, headless rendering and design-rule checks; boards can be
+//!   laid out and routed straight from a netlist (DNA/pcb)
 //! - PDF (documentation, schematics)
-//! - STEP (3D CAD exchange) [TODO]
-//! - G-code (CNC machining) [TODO]
+//! - STEP (3D CAD exchange: crate assemblies and generic B-Rep solids, plus import)
+//! - G-code (CNC machining: 2.5D contour/pocket/drill, GRBL and LinuxCNC)
 //!
 //! ┌─────────────────────────────────────────────────────────────────────────────┐
 //! │ ARCHITECTURE                                                                │
@@ -18,8 +20,12 @@
 //! │   ExportEngine                                                              │
 //! │       │                                                                     │
 //! │       ├── GerberDocument        (DNA/export/gerber)                         │
+//! │       ├── FabricationPackage    (DNA/export/fabrication, excellon)          │
+//! │       ├── parse_gerber / run_drc (DNA/export/gerber_import, drc)            │
+//! │       ├── Layout / route        (DNA/pcb)                                   │
 //! │       ├── PdfDocument           (DNA/export/pdf)                            │
-//! │       └── StepWriter            [TODO]                                      │
+//! │       ├── StepWriter            (DNA/export/step)                           │
+//! │       └── CamProgram            (DNA/cam)                                   │
 //! │                                                                             │
 //! │   Export flow:                                                              │
 //! │   1. Accept geometry/data from application                                  │
@@ -31,7 +37,12 @@
 //!
 //! DEPENDS ON:
 //!   • DNA/export/gerber → Gerber X2 generation
+//!   • DNA/export/fabrication → multi-layer Gerber/Excellon packages
+//!   • DNA/export/gerber_import, gerber_render, drc → read-back and checks
+//!   • DNA/pcb → footprints, placement and routing feeding FabBoard
 //!   • DNA/export/pdf → PDF generation
+//!   • DNA/export/step → STEP AP242 generation
+//!   • DNA/cam → toolpaths and G-code post-processing
 //!
 //! USED BY:
 //!   • TOOLS/* → File export functionality
@@ -47,11 +58,47 @@ pub use dna::export::gerber::{
     ApertureDef, ApertureType, GerberCommand, GerberDocument, GerberUnit,
 };
 
+// Re-export fabrication package types from DNA
+pub use dna::export::excellon::{DrillHit, DrillTool, ExcellonDocument};
+pub use dna::export::fabrication::{
+    generate_fabrication_package, BoardSide, FabBoard, FabFile, FabGraphic, FabHole, FabOptions,
+    FabPad, FabTrack, FabVia, FabricationPackage, PadShape, TrackSegment,
+};
+
+// Re-export Gerber/Excellon read-back, rendering and DRC from DNA
+pub use dna::export::drc::{run_drc, DrcRule, DrcRules, DrcViolation};
+pub use dna::export::gerber_import::{parse_excellon, parse_gerber, GerberParseError};
+pub use dna::export::gerber_render::{
+    layer_objects, render_layer, render_layers, LayerObject, ObjectKind, Raster, Shape,
+};
+
+// Re-export PCB layout types from DNA
+pub use dna::pcb::{
+    layout_netlist, place, route, Footprint, Layout, NetlistOptions, PcbError, PcbOptions,
+    PlacementOptions, RouteReport, RouterOptions,
+};
+
 // Re-export PDF types from DNA
 pub use dna::export::pdf::{PdfDocument, PdfPage, TextAlign};
 
 // Re-export STEP export types from DNA
-pub use dna::export::step::{export_step_ap242, StepExportOptions};
+pub use dna::export::step::{
+    export_step_ap242, export_step_assembly, export_step_solid, StepExportOptions, StepLengthUnit,
+    StepSolidPart,
+};
+
+// Re-export STEP import types from DNA
+pub use dna::export::step_import::{
+    import_step, ImportedOccurrence, ImportedProduct, PlacedSolid, StepImport, StepUnit,
+    UnsupportedEntity,
+};
+
+// Re-export CAM / G-code types from DNA
+pub use dna::cam::{
+    contour, drill, pocket, CamError, CamProgram, CutParams, CutSide, FeedsAndSpeeds,
+    GCodeUnits, MillingDirection, Move, Operation, PocketStrategy, PostProcessor, Profile,
+    Region, Tool, ToolKind,
+};
 
 /// Export format enumeration
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -89,9 +136,21 @@ impl Default for ExportConfig {
     }
 }
 
+impl From<ExportUnits> for GCodeUnits {
+    fn from(units: ExportUnits) -> Self {
+        match units {
+            ExportUnits::Millimeters => GCodeUnits::Millimetres,
+            ExportUnits::Inches => GCodeUnits::Inches,
+        }
+    }
+}
+
 /// Check if a format is supported for export
 pub fn is_format_supported(format: ExportFormat) -> bool {
-    matches!(format, ExportFormat::GerberX2 | ExportFormat::Pdf | ExportFormat::Step)
+    matches!(
+        format,
+        ExportFormat::GerberX2 | ExportFormat::Pdf | ExportFormat::Step | ExportFormat::GCode
+    )
 }
 
 #[cfg(test)]
@@ -103,7 +162,7 @@ mod tests {
         assert!(is_format_supported(ExportFormat::GerberX2));
         assert!(is_format_supported(ExportFormat::Pdf));
         assert!(is_format_supported(ExportFormat::Step));
-        assert!(!is_format_supported(ExportFormat::GCode));
+        assert!(is_format_supported(ExportFormat::GCode));
     }
 
     #[test]
@@ -113,6 +172,19 @@ mod tests {
         assert_eq!(config.scale, 1.0);
     }
 
+    #[test]
+    fn test_gcode_program() {
+        let feeds = FeedsAndSpeeds::new(12000.0, 800.0, 200.0);
+        let params = CutParams::new(feeds, 2.0);
+        let outline = Profile::rectangle(0.0, 0.0, 50.0, 30.0);
+        let tool = Tool::flat_end_mill(1, 3.175, 2);
+        let mut program = CamProgram::new("Outline", ExportUnits::Millimeters.into());
+        program.add_operation(contour("Cut out", &outline, CutSide::Outside, &tool, &params).unwrap());
+        let gcode = program.to_gcode(PostProcessor::Grbl);
+        assert!(gcode.contains("G21"));
+        assert!(gcode.trim_end().ends_with("M30"));
+    }
+
     #[test]
     fn test_gerber_document_creation() {
         let doc = GerberDocument::new("Copper,L1,Top");